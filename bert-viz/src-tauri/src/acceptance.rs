@@ -0,0 +1,186 @@
+//! Acceptance criteria as a checklist
+//!
+//! bd keeps acceptance criteria as plain lines of text. Whether each one is met
+//! is stored next to them, as `{ text, done }` items under the
+//! `acceptance_checklist` key of the bead's extra metadata (which round-trips
+//! through bd's `--metadata` blob). The criteria stay the source of the text:
+//! items are matched to them by text, so criteria edited elsewhere keep the
+//! done flags of the lines that didn't change and start new lines unchecked.
+//!
+//! The view model shows each bead's progress, and the transition policy can
+//! warn about or reject closing a bead with unmet criteria.
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::bd::BeadOp;
+use crate::error::BertError;
+use crate::project::context_for;
+use crate::Bead;
+
+/// Key of the checklist in a bead's extra metadata
+pub(crate) const CHECKLIST_KEY: &str = "acceptance_checklist";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AcceptanceItem {
+    pub text: String,
+    #[serde(default)]
+    pub done: bool,
+}
+
+/// Met and total acceptance criteria of a bead
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AcceptanceProgress {
+    pub done: usize,
+    pub total: usize,
+    /// `done / total`, 0-1
+    pub ratio: f64,
+}
+
+/// The bead's acceptance criteria with their done flags
+pub fn checklist(bead: &Bead) -> Vec<AcceptanceItem> {
+    let stored: Vec<AcceptanceItem> = bead
+        .extra_metadata
+        .get(CHECKLIST_KEY)
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default();
+    let Some(criteria) = &bead.acceptance_criteria else { return stored };
+
+    let mut unmatched: Vec<&AcceptanceItem> = stored.iter().collect();
+    criteria
+        .iter()
+        .map(|text| {
+            let done = match unmatched.iter().position(|item| item.text == *text) {
+                Some(i) => unmatched.remove(i).done,
+                None => false,
+            };
+            AcceptanceItem { text: text.clone(), done }
+        })
+        .collect()
+}
+
+/// Store `items` as the bead's acceptance criteria and checklist
+fn set_checklist(bead: &mut Bead, items: &[AcceptanceItem]) -> Result<(), BertError> {
+    bead.acceptance_criteria = Some(items.iter().map(|item| item.text.clone()).collect());
+    bead.extra_metadata.insert(CHECKLIST_KEY.to_string(), serde_json::to_value(items)?);
+    Ok(())
+}
+
+/// Progress of a bead with acceptance criteria
+pub fn progress(bead: &Bead) -> Option<AcceptanceProgress> {
+    let items = checklist(bead);
+    if items.is_empty() {
+        return None;
+    }
+    let done = items.iter().filter(|item| item.done).count();
+    Some(AcceptanceProgress { done, total: items.len(), ratio: done as f64 / items.len() as f64 })
+}
+
+/// Criteria of the bead that aren't met yet
+pub fn unmet(bead: &Bead) -> Vec<String> {
+    checklist(bead).into_iter().filter(|item| !item.done).map(|item| item.text).collect()
+}
+
+/// Apply `change` to the checklist of `bead_id` and save the bead
+fn edit_checklist(
+    bead_id: &str,
+    window: &tauri::Window,
+    project_handle: Option<&str>,
+    app_handle: &AppHandle,
+    change: impl FnOnce(&mut Vec<AcceptanceItem>) -> Result<(), BertError>,
+) -> Result<Vec<AcceptanceItem>, BertError> {
+    let ctx = context_for(window, project_handle)?;
+    let mut bead = crate::bd::get_bead_by_id(&ctx, bead_id)?;
+    let mut items = checklist(&bead);
+    change(&mut items)?;
+    set_checklist(&mut bead, &items)?;
+    crate::bd::submit_op(&ctx, app_handle, BeadOp::Update { bead })?;
+    Ok(items)
+}
+
+/// Mark the acceptance criterion at `index` met, or unmet again
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn toggle_acceptance_item(
+    beadId: String,
+    index: usize,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    app_handle: AppHandle,
+) -> Result<Vec<AcceptanceItem>, BertError> {
+    edit_checklist(&beadId, &window, project_handle.as_deref(), &app_handle, |items| {
+        let count = items.len();
+        let item = items.get_mut(index).ok_or_else(|| {
+            BertError::InvalidInput(format!("{} has {} acceptance criteria, no item {}", beadId, count, index))
+        })?;
+        item.done = !item.done;
+        eprintln!("☑️  {} criterion {}: {}", beadId, index, if item.done { "met" } else { "unmet" });
+        Ok(())
+    })
+}
+
+/// Add an unmet acceptance criterion to the end of the list
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn add_acceptance_item(
+    beadId: String,
+    text: String,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    app_handle: AppHandle,
+) -> Result<Vec<AcceptanceItem>, BertError> {
+    // bd stores the criteria one per line
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.is_empty() {
+        return Err(BertError::InvalidInput("An acceptance criterion needs some text".to_string()));
+    }
+    edit_checklist(&beadId, &window, project_handle.as_deref(), &app_handle, |items| {
+        items.push(AcceptanceItem { text, done: false });
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bead(criteria: &str, checklist: serde_json::Value) -> Bead {
+        crate::test_bead(json!({
+            "id": "bp6-1",
+            "title": "Parser",
+            "acceptance_criteria": criteria,
+            "acceptance_checklist": checklist,
+        }))
+    }
+
+    #[test]
+    fn test_checklist_follows_the_criteria_text() {
+        let stored = json!([{ "text": "Parses", "done": true }, { "text": "Old line", "done": true }]);
+        let bead = bead("Parses\nHas tests", stored);
+        assert_eq!(
+            checklist(&bead),
+            vec![
+                AcceptanceItem { text: "Parses".into(), done: true },
+                AcceptanceItem { text: "Has tests".into(), done: false },
+            ]
+        );
+        assert_eq!(progress(&bead), Some(AcceptanceProgress { done: 1, total: 2, ratio: 0.5 }));
+        assert_eq!(unmet(&bead), vec!["Has tests"]);
+
+        assert_eq!(progress(&self::bead("", json!(null))), None);
+    }
+
+    #[test]
+    fn test_set_checklist_round_trips() {
+        let mut bead = bead("A", json!(null));
+        let items = vec![AcceptanceItem { text: "A".into(), done: true }, AcceptanceItem { text: "B".into(), done: false }];
+        set_checklist(&mut bead, &items).unwrap();
+
+        let reread: Bead = serde_json::from_value(serde_json::to_value(&bead).unwrap()).unwrap();
+        assert_eq!(reread.acceptance_criteria, Some(vec!["A".to_string(), "B".to_string()]));
+        assert_eq!(checklist(&reread), items);
+    }
+}
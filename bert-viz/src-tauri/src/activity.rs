@@ -0,0 +1,339 @@
+//! Project activity feed
+//!
+//! Merges the three records of what happened in a project into one timeline,
+//! newest first: bead changes from the history log, agent sessions started
+//! and finished on the project's beads, and comments. Pages are requested
+//! with the `nextCursor` of the previous one, so entries recorded while the
+//! user scrolls don't shift the pages.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::agent::LogEventType;
+use crate::baseline::sanitize_file_name;
+use crate::error::BertError;
+use crate::history::{ChangeKind, FieldChange};
+use crate::Bead;
+
+const DEFAULT_LIMIT: usize = 50;
+const MAX_LIMIT: usize = 500;
+
+/// Length of the comment excerpts, in characters
+const EXCERPT_CHARS: usize = 160;
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum ActivityDetail {
+    BeadChange {
+        kind: ChangeKind,
+        changes: Vec<FieldChange>,
+    },
+    #[serde(rename_all = "camelCase")]
+    SessionStarted {
+        session_id: String,
+        persona: String,
+        backend: String,
+    },
+    /// The last reply of a session
+    #[serde(rename_all = "camelCase")]
+    SessionEnded {
+        session_id: String,
+        persona: String,
+        backend: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Comment {
+        comment_id: String,
+        parent_id: Option<String>,
+        excerpt: String,
+    },
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityEntry {
+    /// Unique within the feed; ties between equal timestamps are broken by it
+    pub id: String,
+    pub timestamp: String,
+    pub bead_id: Option<String>,
+    /// None for beads that no longer exist
+    pub bead_title: Option<String>,
+    pub actor: Option<String>,
+    #[serde(flatten)]
+    pub detail: ActivityDetail,
+    #[serde(skip)]
+    at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityPage {
+    pub entries: Vec<ActivityEntry>,
+    /// Pass back as `cursor` for the next (older) page; None on the last one
+    pub next_cursor: Option<String>,
+}
+
+fn parse_time(timestamp: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(timestamp).ok().map(|t| t.with_timezone(&Utc))
+}
+
+/// Whether a file was modified before `since`, so it holds nothing newer
+fn untouched_since(path: &Path, since: Option<DateTime<Utc>>) -> bool {
+    let Some(since) = since else { return false };
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .map(|modified| DateTime::<Utc>::from(modified) < since)
+        .unwrap_or(false)
+}
+
+fn jsonl_files(dir: &Path, since: Option<DateTime<Utc>>) -> Vec<std::path::PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else { return Vec::new() };
+    entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("jsonl") && !untouched_since(p, since))
+        .collect()
+}
+
+fn excerpt(body: &str) -> String {
+    let flat = body.split_whitespace().collect::<Vec<_>>().join(" ");
+    match flat.char_indices().nth(EXCERPT_CHARS) {
+        Some((end, _)) => format!("{}…", &flat[..end]),
+        None => flat,
+    }
+}
+
+/// Where the records of a project live
+struct Sources<'a> {
+    history_dir: &'a Path,
+    comments_dir: &'a Path,
+    sessions_root: &'a Path,
+}
+
+/// Every entry of the project's feed at or after `since`, unsorted
+fn collect(sources: &Sources, beads: &[Bead], since: Option<DateTime<Utc>>) -> Vec<ActivityEntry> {
+    let titles: HashMap<&str, &str> = beads.iter().map(|b| (b.id.as_str(), b.title.as_str())).collect();
+    // History logs are named after the sanitized bead ID
+    let by_file_name: HashMap<String, &str> = beads.iter().map(|b| (sanitize_file_name(&b.id), b.id.as_str())).collect();
+
+    let mut feed = Vec::new();
+    let mut push = |id: String, timestamp: &str, bead_id: &str, actor: Option<String>, detail: ActivityDetail| {
+        let Some(at) = parse_time(timestamp) else { return };
+        if since.is_some_and(|since| at < since) {
+            return;
+        }
+        feed.push(ActivityEntry {
+            id,
+            timestamp: timestamp.to_string(),
+            bead_id: Some(bead_id.to_string()),
+            bead_title: titles.get(bead_id).map(|t| t.to_string()),
+            actor,
+            detail,
+            at,
+        });
+    };
+
+    for path in jsonl_files(sources.history_dir, since) {
+        let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else { continue };
+        let bead_id = by_file_name.get(stem).copied().unwrap_or(stem);
+        let entries = crate::history::read_history_file(&path).unwrap_or_else(|e| {
+            eprintln!("⚠️  Skipping history of {}: {}", bead_id, e);
+            Vec::new()
+        });
+        for (i, entry) in entries.into_iter().enumerate() {
+            let detail = ActivityDetail::BeadChange { kind: entry.kind, changes: entry.changes };
+            push(format!("history:{}:{}", bead_id, i), &entry.timestamp, bead_id, entry.actor, detail);
+        }
+    }
+
+    for path in jsonl_files(sources.comments_dir, since) {
+        let comments = crate::comments::read_comments(&path).unwrap_or_default();
+        for comment in comments.into_iter().filter(|c| !c.deleted) {
+            let detail = ActivityDetail::Comment {
+                comment_id: comment.id.clone(),
+                parent_id: comment.parent_id,
+                excerpt: excerpt(&comment.body),
+            };
+            push(format!("comment:{}", comment.id), &comment.created_at, &comment.bead_id, comment.author, detail);
+        }
+    }
+
+    // Session logs are kept per bead for all projects; only this project's beads count
+    for bead_id in titles.keys() {
+        for path in jsonl_files(&sources.sessions_root.join(bead_id), since) {
+            let Ok(events) = crate::agent::session::read_log_events(&path) else { continue };
+            let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+            for (i, event) in events.iter().enumerate() {
+                let is_last = i + 1 == events.len();
+                let detail = match event.event_type {
+                    LogEventType::SessionStart => ActivityDetail::SessionStarted {
+                        session_id: event.session_id.clone(),
+                        persona: event.persona.clone(),
+                        backend: event.backend.clone(),
+                    },
+                    LogEventType::SessionEnd if is_last => ActivityDetail::SessionEnded {
+                        session_id: event.session_id.clone(),
+                        persona: event.persona.clone(),
+                        backend: event.backend.clone(),
+                    },
+                    _ => continue,
+                };
+                push(format!("session:{}:{}", stem, i), &event.timestamp, bead_id, Some(event.persona.clone()), detail);
+            }
+        }
+    }
+    feed
+}
+
+fn cursor_of(entry: &ActivityEntry) -> String {
+    format!("{}|{}", entry.at.to_rfc3339_opts(SecondsFormat::Nanos, true), entry.id)
+}
+
+fn parse_cursor(cursor: &str) -> Result<(DateTime<Utc>, &str), BertError> {
+    cursor
+        .split_once('|')
+        .and_then(|(at, id)| Some((parse_time(at)?, id)))
+        .ok_or_else(|| BertError::InvalidInput(format!("Invalid activity cursor: {}", cursor)))
+}
+
+/// Sort `feed` newest first and cut the page after `cursor`
+fn page(mut feed: Vec<ActivityEntry>, limit: usize, cursor: Option<&str>) -> Result<ActivityPage, BertError> {
+    feed.sort_by(|a, b| b.at.cmp(&a.at).then_with(|| b.id.cmp(&a.id)));
+    if let Some(cursor) = cursor {
+        let (at, id) = parse_cursor(cursor)?;
+        feed.retain(|e| (e.at, e.id.as_str()) < (at, id));
+    }
+
+    let more = feed.len() > limit;
+    feed.truncate(limit);
+    let next_cursor = if more { feed.last().map(cursor_of) } else { None };
+    Ok(ActivityPage { entries: feed, next_cursor })
+}
+
+/// What happened in the project, newest first: bead changes, agent sessions
+/// and comments. `since` (RFC 3339) bounds how far back the feed goes;
+/// `cursor` continues from a previous page.
+#[tauri::command]
+pub fn get_activity_feed(
+    limit: Option<usize>,
+    since: Option<String>,
+    cursor: Option<String>,
+    window: tauri::Window,
+    project_handle: Option<String>,
+) -> Result<ActivityPage, BertError> {
+    let since = match since.as_deref() {
+        Some(s) => Some(parse_time(s).ok_or_else(|| BertError::InvalidInput(format!("Invalid timestamp: {}", s)))?),
+        None => None,
+    };
+    let ctx = crate::project::context_for(&window, project_handle.as_deref())?;
+    let beads_path = ctx.beads_path()?;
+    let beads = crate::bd::load_beads(&ctx)?;
+
+    let history_dir = crate::history::get_history_dir(&crate::history::project_path_for(&beads_path))?;
+    let sources = Sources {
+        history_dir: &history_dir,
+        comments_dir: &crate::comments::comments_dir(&beads_path),
+        sessions_root: &crate::agent::session::sessions_root()?,
+    };
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    page(collect(&sources, &beads, since), limit, cursor.as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn write_lines(path: &Path, lines: &[serde_json::Value]) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let contents: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        fs::write(path, contents.join("\n") + "\n").unwrap();
+    }
+
+    fn log_event(timestamp: &str, event_type: &str) -> serde_json::Value {
+        json!({
+            "timestamp": timestamp, "session_id": "s1", "bead_id": "bp-1", "persona": "specialist",
+            "backend": "claude-code", "event_type": event_type, "content": "",
+        })
+    }
+
+    /// bp-1 with two history entries, a comment and one agent session
+    fn project(dir: &Path) -> Vec<ActivityEntry> {
+        let beads: Vec<Bead> = vec![serde_json::from_value(json!({
+            "id": "bp-1", "title": "Ship it", "status": "open", "priority": 2, "issue_type": "task",
+        }))
+        .unwrap()];
+
+        write_lines(&dir.join("history/bp-1.jsonl"), &[
+            json!({ "timestamp": "2026-03-01T09:00:00Z", "actor": "ann", "kind": "created", "changes": [] }),
+            json!({ "timestamp": "2026-03-01T12:00:00+00:00", "actor": "bob", "kind": "updated",
+                    "changes": [{ "field": "status", "old": "open", "new": "in_progress" }] }),
+        ]);
+        write_lines(&dir.join("history/gone.jsonl"), &[
+            json!({ "timestamp": "2026-03-01T08:00:00Z", "actor": "ann", "kind": "deleted", "changes": [] }),
+        ]);
+        write_lines(&dir.join("comments/bp-1.jsonl"), &[
+            json!({ "id": "c1", "beadId": "bp-1", "author": "cy", "body": "Looks\n  good", "createdAt": "2026-03-01T11:00:00Z" }),
+            json!({ "id": "c2", "beadId": "bp-1", "author": "cy", "body": "", "createdAt": "2026-03-01T11:30:00Z", "deleted": true }),
+        ]);
+        write_lines(&dir.join("sessions/bp-1/s1-100.jsonl"), &[
+            log_event("2026-03-01T10:00:00Z", "sessionstart"),
+            log_event("2026-03-01T10:01:00Z", "chunk"),
+            log_event("2026-03-01T10:02:00Z", "sessionend"),
+        ]);
+        // Another project's bead
+        write_lines(&dir.join("sessions/other-1/s2-100.jsonl"), &[log_event("2026-03-01T13:00:00Z", "sessionstart")]);
+
+        let sources = Sources {
+            history_dir: &dir.join("history"),
+            comments_dir: &dir.join("comments"),
+            sessions_root: &dir.join("sessions"),
+        };
+        collect(&sources, &beads, None)
+    }
+
+    #[test]
+    fn test_feed_merges_sources_newest_first() {
+        let dir = TempDir::new().unwrap();
+        let feed = page(project(dir.path()), 10, None).unwrap();
+        let types: Vec<String> = feed
+            .entries
+            .iter()
+            .map(|e| serde_json::to_value(e).unwrap()["type"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(types, vec!["bead-change", "comment", "session-ended", "session-started", "bead-change", "bead-change"]);
+        assert_eq!(feed.next_cursor, None);
+
+        let comment = serde_json::to_value(&feed.entries[1]).unwrap();
+        assert_eq!(comment["excerpt"], "Looks good");
+        assert_eq!(comment["beadTitle"], "Ship it");
+        assert_eq!(feed.entries[5].bead_id.as_deref(), Some("gone"));
+        assert_eq!(feed.entries[5].bead_title, None);
+    }
+
+    #[test]
+    fn test_cursor_pages_and_since() {
+        let dir = TempDir::new().unwrap();
+        let feed = project(dir.path());
+
+        let first = page(feed.clone(), 4, None).unwrap();
+        let cursor = first.next_cursor.clone().unwrap();
+        let second = page(feed.clone(), 4, Some(&cursor)).unwrap();
+        assert_eq!(second.entries.len(), 2);
+        assert_eq!(second.entries[0].timestamp, "2026-03-01T09:00:00Z");
+        assert_eq!(second.next_cursor, None);
+        assert!(page(feed, 4, Some("nonsense")).is_err());
+
+        let sources = Sources {
+            history_dir: &dir.path().join("history"),
+            comments_dir: &dir.path().join("comments"),
+            sessions_root: &dir.path().join("sessions"),
+        };
+        let recent = collect(&sources, &[], parse_time("2026-03-01T10:30:00Z"));
+        assert_eq!(recent.len(), 2);
+    }
+}
@@ -0,0 +1,274 @@
+//! Diffs and test results pulled out of agent replies
+//!
+//! Agents print diffs and test output that scroll out of view quickly. When a
+//! reply completes, the session reader runs `extract` over it and logs each
+//! artifact it finds as an `artifact` event in the session log, so
+//! `get_session_artifacts` can list them later, also for finished sessions.
+//!
+//! - Diff: a fenced `diff`/`patch` block, or any fenced block that reads like
+//!   a unified diff
+//! - Test report: a test run summary (cargo, pytest, jest), fenced or not
+//! - Command output: a fenced `console`/`output` block, or one whose first
+//!   line is a `$ ` prompt
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+use crate::error::BertError;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ArtifactKind {
+    Diff,
+    TestReport,
+    CommandOutput,
+}
+
+/// Counts read from a test summary
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TestCounts {
+    pub passed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionArtifact {
+    pub kind: ArtifactKind,
+    /// Changed files of a diff, the summary line of a test run, or the
+    /// command of an output block
+    pub title: String,
+    pub content: String,
+    /// Test reports only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tests: Option<TestCounts>,
+    /// When the reply was logged (RFC 3339); empty before logging
+    #[serde(default)]
+    pub timestamp: String,
+}
+
+const OUTPUT_LANGUAGES: [&str; 5] = ["console", "terminal", "output", "shell-session", "log"];
+
+/// Summary lines of cargo test, pytest and jest
+fn summary_line() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"^(?:test result: (?:ok|FAILED)\.|=+ .*\b\d+ (?:passed|failed)\b.*=+$|Tests:\s+.*\b\d+ total\b)").unwrap()
+    })
+}
+
+fn count_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"\b(\d+) (passed|failed|ignored|skipped)\b").unwrap())
+}
+
+/// Counts of a test summary line
+fn test_counts(line: &str) -> TestCounts {
+    let mut counts = TestCounts::default();
+    for capture in count_pattern().captures_iter(line) {
+        let n: usize = capture[1].parse().unwrap_or(0);
+        match &capture[2] {
+            "passed" => counts.passed += n,
+            "failed" => counts.failed += n,
+            _ => counts.skipped += n,
+        }
+    }
+    counts
+}
+
+/// The last test summary line in `text`
+fn find_summary(text: &str) -> Option<&str> {
+    text.lines().rev().map(str::trim).find(|line| summary_line().is_match(line))
+}
+
+fn looks_like_diff(content: &str) -> bool {
+    let lines: Vec<&str> = content.lines().collect();
+    lines.iter().any(|l| l.starts_with("diff --git "))
+        || (lines.windows(2).any(|w| w[0].starts_with("--- ") && w[1].starts_with("+++ "))
+            && lines.iter().any(|l| l.starts_with("@@")))
+}
+
+/// Files named in the `+++` lines of a diff
+fn diff_title(content: &str) -> String {
+    let files: Vec<&str> = content
+        .lines()
+        .filter_map(|l| l.strip_prefix("+++ "))
+        .map(|path| path.split('\t').next().unwrap_or(path).trim())
+        .map(|path| path.strip_prefix("b/").unwrap_or(path))
+        .filter(|path| *path != "/dev/null")
+        .collect();
+    if files.is_empty() {
+        "Diff".to_string()
+    } else {
+        files.join(", ")
+    }
+}
+
+fn classify_block(language: &str, content: &str) -> Option<SessionArtifact> {
+    let artifact = |kind, title: String, tests| SessionArtifact {
+        kind,
+        title,
+        content: content.to_string(),
+        tests,
+        timestamp: String::new(),
+    };
+
+    if language == "diff" || language == "patch" || looks_like_diff(content) {
+        return Some(artifact(ArtifactKind::Diff, diff_title(content), None));
+    }
+    if let Some(summary) = find_summary(content) {
+        return Some(artifact(ArtifactKind::TestReport, summary.to_string(), Some(test_counts(summary))));
+    }
+    let first_line = content.lines().map(str::trim).find(|l| !l.is_empty()).unwrap_or_default();
+    if let Some(command) = first_line.strip_prefix("$ ") {
+        return Some(artifact(ArtifactKind::CommandOutput, command.to_string(), None));
+    }
+    OUTPUT_LANGUAGES
+        .contains(&language)
+        .then(|| artifact(ArtifactKind::CommandOutput, "Command output".to_string(), None))
+}
+
+/// Artifacts in a completed reply, in the order they appear
+pub fn extract(reply: &str) -> Vec<SessionArtifact> {
+    let mut artifacts = Vec::new();
+    let mut fence: Option<(&str, String, Vec<&str>)> = None;
+    let mut prose: Vec<&str> = Vec::new();
+
+    let flush_prose = |prose: &mut Vec<&str>, artifacts: &mut Vec<SessionArtifact>| {
+        for line in prose.drain(..).map(str::trim).filter(|l| summary_line().is_match(l)) {
+            artifacts.push(SessionArtifact {
+                kind: ArtifactKind::TestReport,
+                title: line.to_string(),
+                content: line.to_string(),
+                tests: Some(test_counts(line)),
+                timestamp: String::new(),
+            });
+        }
+    };
+
+    for line in reply.lines() {
+        let trimmed = line.trim_start();
+        match &mut fence {
+            Some((marker, language, body)) => {
+                if trimmed.starts_with(*marker) && trimmed.trim_end().chars().all(|c| c == '`' || c == '~') {
+                    if let Some(artifact) = classify_block(language, &body.join("\n")) {
+                        artifacts.push(artifact);
+                    }
+                    fence = None;
+                } else {
+                    body.push(line);
+                }
+            }
+            None => {
+                let marker = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m));
+                match marker {
+                    Some(marker) => {
+                        flush_prose(&mut prose, &mut artifacts);
+                        let language = trimmed[marker.len()..].trim_start_matches(['`', '~']).trim();
+                        let language = language.split_whitespace().next().unwrap_or_default().to_lowercase();
+                        fence = Some((marker, language, Vec::new()));
+                    }
+                    None => prose.push(line),
+                }
+            }
+        }
+    }
+    // An unclosed fence is a cut-off reply; its block is left out
+    flush_prose(&mut prose, &mut artifacts);
+    artifacts
+}
+
+/// Artifact as logged: the content in the event, the rest in its metadata
+pub(crate) fn metadata(artifact: &SessionArtifact) -> serde_json::Value {
+    serde_json::json!({ "kind": artifact.kind, "title": artifact.title, "tests": artifact.tests })
+}
+
+/// Artifact of an `artifact` log event
+pub(crate) fn from_event(event: &crate::agent::LogEvent) -> Option<SessionArtifact> {
+    let metadata = event.metadata.as_ref()?;
+    Some(SessionArtifact {
+        kind: serde_json::from_value(metadata.get("kind")?.clone()).ok()?,
+        title: metadata.get("title").and_then(|t| t.as_str()).unwrap_or_default().to_string(),
+        content: event.content.clone(),
+        tests: metadata.get("tests").and_then(|t| serde_json::from_value(t.clone()).ok()),
+        timestamp: event.timestamp.clone(),
+    })
+}
+
+/// Diffs, test reports and command output of a session, oldest first
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn get_session_artifacts(sessionId: String) -> Result<Vec<SessionArtifact>, BertError> {
+    let prefix = format!("{}-", sessionId);
+    let mut artifacts = Vec::new();
+    for path in crate::agent::session::session_log_paths()? {
+        let is_session_log = path.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with(&prefix));
+        if !is_session_log {
+            continue;
+        }
+        let events = crate::agent::session::read_log_events(&path)?;
+        artifacts.extend(events.iter().filter(|e| e.event_type == crate::agent::LogEventType::Artifact).filter_map(from_event));
+    }
+    artifacts.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    eprintln!("🧾 Session {}: {} artifacts", sessionId, artifacts.len());
+    Ok(artifacts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(artifacts: &[SessionArtifact]) -> Vec<(ArtifactKind, &str)> {
+        artifacts.iter().map(|a| (a.kind, a.title.as_str())).collect()
+    }
+
+    #[test]
+    fn test_extracts_diffs_tests_and_output_in_order() {
+        let reply = "I changed the parser:\n\n```diff\n--- a/src/bd.rs\n+++ b/src/bd.rs\n@@ -1 +1 @@\n-old\n+new\n```\n\n\
+                     Running the tests:\n\n```\nrunning 3 tests\ntest result: FAILED. 2 passed; 1 failed; 0 ignored\n```\n\n\
+                     ```console\n$ git status --short\n M src/bd.rs\n```\n\n\
+                     ```rust\nfn main() {}\n```\n\n\
+                     pytest says:\n===== 4 passed, 1 skipped in 0.31s =====\n";
+        let artifacts = extract(reply);
+        assert_eq!(
+            kinds(&artifacts),
+            vec![
+                (ArtifactKind::Diff, "src/bd.rs"),
+                (ArtifactKind::TestReport, "test result: FAILED. 2 passed; 1 failed; 0 ignored"),
+                (ArtifactKind::CommandOutput, "git status --short"),
+                (ArtifactKind::TestReport, "===== 4 passed, 1 skipped in 0.31s ====="),
+            ]
+        );
+        assert_eq!(artifacts[1].tests, Some(TestCounts { passed: 2, failed: 1, skipped: 0 }));
+        assert_eq!(artifacts[3].tests, Some(TestCounts { passed: 4, failed: 0, skipped: 1 }));
+        assert!(artifacts[0].content.contains("+new"));
+    }
+
+    #[test]
+    fn test_unlabelled_diffs_and_cut_off_blocks() {
+        let reply = "```\ndiff --git a/x.ts b/x.ts\n--- a/x.ts\n+++ b/x.ts\n@@ -2 +2 @@\n```\n```diff\n--- a/y.rs\n+++ b/y.rs";
+        assert_eq!(kinds(&extract(reply)), vec![(ArtifactKind::Diff, "x.ts")]);
+        assert!(extract("Tests: 1 failed, 9 passed but no total").is_empty());
+        assert_eq!(extract("Tests:       1 failed, 9 passed, 10 total")[0].tests, Some(TestCounts { passed: 9, failed: 1, skipped: 0 }));
+    }
+
+    #[test]
+    fn test_logged_artifact_round_trips() {
+        let artifact = extract("```patch\n+++ b/a.rs\n```").remove(0);
+        let event = crate::agent::LogEvent {
+            timestamp: "2026-10-16T10:00:00Z".to_string(),
+            session_id: "s".to_string(),
+            bead_id: None,
+            persona: "specialist".to_string(),
+            backend: "claude-code".to_string(),
+            event_type: crate::agent::LogEventType::Artifact,
+            content: artifact.content.clone(),
+            metadata: Some(metadata(&artifact)),
+        };
+        let read = from_event(&event).unwrap();
+        assert_eq!(read, SessionArtifact { timestamp: event.timestamp.clone(), ..artifact });
+    }
+}
@@ -0,0 +1,512 @@
+//! Autopilot: working the ready queue unattended
+//!
+//! `start_autopilot` starts a background loop for the project open in the
+//! calling window. Every poll it runs `bd ready`, picks the highest-priority
+//! unblocked task carrying the configured labels, claims it and starts a
+//! specialist session on it, up to the configured concurrency. When a
+//! session's run ends the loop checks that the bead was actually closed:
+//! closed beads have their session terminated (merging its worktree, if any),
+//! while beads left open keep their session so the user can follow up.
+//! Every change is broadcast as an `autopilot-status` event.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Emitter, Manager, State};
+use uuid::Uuid;
+
+use crate::agent::plugin::BackendId;
+use crate::agent::session::AgentState;
+use crate::agent::worktree::{SessionWorktree, WorktreeAction};
+use crate::error::BertError;
+use crate::project::ProjectContext;
+use crate::Bead;
+
+/// Persona and task the implementer sessions run with, as for the sidebar's
+/// "Implement" action (the template is picked from the bead's role label)
+pub(crate) const IMPLEMENTER_PERSONA: &str = "specialist";
+pub(crate) const IMPLEMENTER_TASK: &str = "implement";
+
+fn default_max_concurrency() -> usize {
+    1
+}
+
+fn default_poll_interval_secs() -> u64 {
+    30
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AutopilotConfig {
+    /// Only beads carrying all of these labels are picked
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Sessions running at once
+    #[serde(default = "default_max_concurrency")]
+    pub max_concurrency: usize,
+    /// Stop after starting this many tasks
+    #[serde(default)]
+    pub max_tasks: Option<usize>,
+    /// Seconds between `bd ready` polls
+    #[serde(default = "default_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    /// Stop once nothing is ready and no session is running
+    #[serde(default)]
+    pub stop_when_idle: bool,
+    /// Stop after the first task that fails to start or isn't closed
+    #[serde(default)]
+    pub stop_on_failure: bool,
+    /// Backend for the sessions (defaults to the CLI preference)
+    #[serde(default)]
+    pub cli_backend: Option<String>,
+    /// Run each session in its own worktree, merged back once its bead closes
+    #[serde(default)]
+    pub worktree: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AutopilotPhase {
+    #[default]
+    Idle,
+    Running,
+    /// No new tasks are started; waiting for running sessions to finish
+    Stopping,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RunOutcome {
+    Running,
+    /// The session closed the bead
+    Closed,
+    /// The session ended with the bead still open
+    NotClosed,
+    /// The session could not be started
+    Failed,
+}
+
+/// One task the autopilot picked up
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutopilotRun {
+    pub bead_id: String,
+    pub title: String,
+    pub session_id: String,
+    /// Seconds since UNIX epoch
+    pub started_at: u64,
+    pub finished_at: Option<u64>,
+    pub outcome: RunOutcome,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutopilotStatus {
+    pub phase: AutopilotPhase,
+    pub config: Option<AutopilotConfig>,
+    /// Tasks picked up since the autopilot was started, oldest first
+    pub runs: Vec<AutopilotRun>,
+    /// Why the autopilot stopped or is stopping
+    pub stop_reason: Option<String>,
+    /// When `bd ready` last ran (seconds since UNIX epoch)
+    pub last_poll: Option<u64>,
+    /// Error from the last `bd ready`, cleared by the next successful one
+    pub last_error: Option<String>,
+}
+
+impl AutopilotStatus {
+    fn active(&self) -> usize {
+        self.runs.iter().filter(|r| r.outcome == RunOutcome::Running).count()
+    }
+
+    fn limit_reached(&self, config: &AutopilotConfig) -> bool {
+        config.max_tasks.is_some_and(|max| self.runs.len() >= max)
+    }
+}
+
+/// Autopilot state shared by the commands, the loop and the session readers
+#[derive(Default)]
+pub struct Autopilot {
+    status: Mutex<AutopilotStatus>,
+    /// Sessions whose run ended and that the loop hasn't checked yet
+    ended: Mutex<Vec<String>>,
+    /// Wakes the loop when a run ends or a stop is requested
+    wake: Condvar,
+}
+
+impl Autopilot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn status(&self) -> AutopilotStatus {
+        self.status.lock().unwrap().clone()
+    }
+
+    /// Called by the session reader when a session's run ends
+    pub fn run_ended(&self, session_id: &str) {
+        let tracked = self
+            .status
+            .lock()
+            .unwrap()
+            .runs
+            .iter()
+            .any(|r| r.session_id == session_id && r.outcome == RunOutcome::Running);
+        if tracked {
+            self.ended.lock().unwrap().push(session_id.to_string());
+            self.wake.notify_all();
+        }
+    }
+
+    /// Apply `change` to the status and broadcast the result
+    fn update(&self, app_handle: &AppHandle, change: impl FnOnce(&mut AutopilotStatus)) {
+        let status = {
+            let mut status = self.status.lock().unwrap();
+            change(&mut status);
+            status.clone()
+        };
+        let _ = app_handle.emit("autopilot-status", status);
+    }
+
+    /// Stop starting new tasks; the first reason given is kept
+    fn request_stop(&self, app_handle: &AppHandle, reason: &str) {
+        self.update(app_handle, |status| {
+            if status.phase == AutopilotPhase::Running {
+                eprintln!("🛬 Autopilot stopping: {}", reason);
+                status.phase = AutopilotPhase::Stopping;
+                status.stop_reason = Some(reason.to_string());
+            }
+        });
+        // Taking the lock ensures the loop is either waiting or yet to check
+        let _ended = self.ended.lock().unwrap();
+        self.wake.notify_all();
+    }
+
+    /// Sessions whose run ended, waiting up to `timeout` for one
+    fn wait(&self, timeout: Duration) -> Vec<String> {
+        let mut ended = self.ended.lock().unwrap();
+        if ended.is_empty() {
+            ended = self.wake.wait_timeout(ended, timeout).unwrap().0;
+        }
+        std::mem::take(&mut *ended)
+    }
+}
+
+/// Highest-priority ready bead (oldest first on ties) carrying all `labels`,
+/// skipping epics and beads already attempted
+fn pick_next<'a>(ready: &'a [Bead], labels: &[String], attempted: &HashSet<&str>) -> Option<&'a Bead> {
+    ready
+        .iter()
+        .filter(|b| b.issue_type != "epic" && !attempted.contains(b.id.as_str()))
+        .filter(|b| {
+            let bead_labels = b.labels.as_deref().unwrap_or_default();
+            labels.iter().all(|l| bead_labels.contains(l))
+        })
+        .min_by(|a, b| a.priority.cmp(&b.priority).then_with(|| a.created_at.cmp(&b.created_at)))
+}
+
+/// Unblocked open beads according to `bd ready`
+fn ready_beads(project: &ProjectContext) -> Result<Vec<Bead>, BertError> {
+    let output = crate::bd::execute_bd(project, vec!["ready".to_string(), "--json".to_string()])?;
+    // bd prints `null` rather than `[]` when nothing is ready
+    let ready: Option<Vec<Bead>> = serde_json::from_str(&output)?;
+    Ok(ready.unwrap_or_default())
+}
+
+/// Whether the bead is in a done status, in the project or the session's
+/// worktree (where `bd` may have written it before the merge)
+fn is_closed(project: &ProjectContext, worktree: Option<&SessionWorktree>, bead_id: &str) -> Result<bool, BertError> {
+    let beads_path = project.beads_path()?;
+    let workflow = crate::workflow::WorkflowConfig::load_for_beads_file(&beads_path)?;
+    if workflow.is_done(&crate::bd::get_bead_by_id(project, bead_id)?.status) {
+        return Ok(true);
+    }
+
+    let Some(worktree) = worktree else { return Ok(false) };
+    let Ok(relative) = beads_path.strip_prefix(&worktree.repo_root) else { return Ok(false) };
+    let worktree_beads = crate::bd::read_beads(&worktree.path.join(relative)).unwrap_or_default();
+    Ok(worktree_beads.iter().any(|b| b.id == bead_id && workflow.is_done(&b.status)))
+}
+
+/// Record the outcome of a session whose run ended
+fn settle(app_handle: &AppHandle, state: &AgentState, project: &ProjectContext, config: &AutopilotConfig, session_id: &str) {
+    let autopilot = &state.autopilot;
+    let Some(bead_id) = autopilot
+        .status()
+        .runs
+        .iter()
+        .find(|r| r.session_id == session_id && r.outcome == RunOutcome::Running)
+        .map(|r| r.bead_id.clone())
+    else {
+        return;
+    };
+
+    let worktree = state.sessions.lock().unwrap().get(session_id).and_then(|s| s.worktree.clone());
+    let (outcome, mut error) = match is_closed(project, worktree.as_ref(), &bead_id) {
+        Ok(true) => (RunOutcome::Closed, None),
+        Ok(false) => (RunOutcome::NotClosed, None),
+        Err(e) => (RunOutcome::NotClosed, Some(e.to_string())),
+    };
+
+    // Finished work is merged and its session closed; unfinished sessions
+    // stay open for the user to pick up
+    if outcome == RunOutcome::Closed {
+        if let Err(e) = crate::agent::session::terminate_session_internal(app_handle, state, session_id, Some(WorktreeAction::Merge)) {
+            error = Some(e.to_string());
+        }
+    }
+
+    eprintln!("🛬 Autopilot: {} finished with {:?}", bead_id, outcome);
+    autopilot.update(app_handle, |status| {
+        if let Some(run) = status.runs.iter_mut().find(|r| r.session_id == session_id) {
+            run.outcome = outcome;
+            run.finished_at = Some(now_secs());
+            run.error = error;
+        }
+    });
+
+    if outcome == RunOutcome::NotClosed && config.stop_on_failure {
+        autopilot.request_stop(app_handle, &format!("{} was not closed", bead_id));
+    }
+}
+
+/// Start sessions on ready beads until every slot is taken
+fn start_ready_tasks(
+    app_handle: &AppHandle,
+    state: &AgentState,
+    project: &ProjectContext,
+    config: &AutopilotConfig,
+    backend: BackendId,
+    auto_capture: bool,
+) {
+    let autopilot = &state.autopilot;
+    let ready = match ready_beads(project) {
+        Ok(ready) => ready,
+        Err(e) => {
+            eprintln!("⚠️  Autopilot: bd ready failed: {}", e);
+            autopilot.update(app_handle, |status| {
+                status.last_poll = Some(now_secs());
+                status.last_error = Some(e.to_string());
+            });
+            return;
+        }
+    };
+    autopilot.update(app_handle, |status| {
+        status.last_poll = Some(now_secs());
+        status.last_error = None;
+    });
+
+    loop {
+        let status = autopilot.status();
+        if status.phase != AutopilotPhase::Running {
+            return;
+        }
+        if status.limit_reached(config) {
+            let reason = format!("Started {} tasks", status.runs.len());
+            autopilot.request_stop(app_handle, &reason);
+            return;
+        }
+        if status.active() >= config.max_concurrency {
+            return;
+        }
+
+        let attempted: HashSet<&str> = status.runs.iter().map(|r| r.bead_id.as_str()).collect();
+        let Some(bead) = pick_next(&ready, &config.labels, &attempted) else {
+            if config.stop_when_idle && status.active() == 0 {
+                autopilot.request_stop(app_handle, "No ready work left");
+            }
+            return;
+        };
+
+        // The run is recorded first so its end can't be missed
+        let session_id = Uuid::new_v4().to_string();
+        eprintln!("🛫 Autopilot: starting {} in session {}", bead.id, session_id);
+        autopilot.update(app_handle, |status| {
+            status.runs.push(AutopilotRun {
+                bead_id: bead.id.clone(),
+                title: bead.title.clone(),
+                session_id: session_id.clone(),
+                started_at: now_secs(),
+                finished_at: None,
+                outcome: RunOutcome::Running,
+                error: None,
+            });
+        });
+
+        if let Err(e) = crate::bd::claim_bead_internal(project, &bead.id) {
+            eprintln!("⚠️  Autopilot: failed to claim {}: {}", bead.id, e);
+        }
+        crate::project::emit_beads_updated(app_handle, project);
+
+        let started = crate::agent::session::start_session_internal(
+            app_handle,
+            state,
+            project.clone(),
+            session_id.clone(),
+            backend,
+            IMPLEMENTER_PERSONA.to_string(),
+            Some(IMPLEMENTER_TASK.to_string()),
+            Some(bead.id.clone()),
+            None,
+            None,
+            config.worktree,
+            auto_capture,
+        );
+        if let Err(e) = started {
+            eprintln!("❌ Autopilot: failed to start {}: {}", bead.id, e);
+            autopilot.update(app_handle, |status| {
+                if let Some(run) = status.runs.iter_mut().find(|r| r.session_id == session_id) {
+                    run.outcome = RunOutcome::Failed;
+                    run.finished_at = Some(now_secs());
+                    run.error = Some(e.to_string());
+                }
+            });
+            if config.stop_on_failure {
+                autopilot.request_stop(app_handle, &format!("{} could not be started", bead.id));
+            }
+        }
+    }
+}
+
+fn run_loop(app_handle: AppHandle, project: ProjectContext, config: AutopilotConfig, backend: BackendId, auto_capture: bool) {
+    let Some(state) = app_handle.try_state::<AgentState>() else { return };
+    let autopilot = &state.autopilot;
+    let poll_interval = Duration::from_secs(config.poll_interval_secs.max(1));
+    let mut ended: Vec<String> = Vec::new();
+
+    loop {
+        for session_id in ended.drain(..) {
+            settle(&app_handle, &state, &project, &config, &session_id);
+        }
+
+        start_ready_tasks(&app_handle, &state, &project, &config, backend, auto_capture);
+
+        let status = autopilot.status();
+        if status.phase == AutopilotPhase::Stopping && status.active() == 0 {
+            autopilot.update(&app_handle, |status| status.phase = AutopilotPhase::Idle);
+            eprintln!("🛬 Autopilot stopped");
+            return;
+        }
+
+        ended = autopilot.wait(poll_interval);
+    }
+}
+
+/// Start working the ready queue of the window's project
+#[tauri::command]
+pub fn start_autopilot(
+    app_handle: AppHandle,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    config: AutopilotConfig,
+    state: State<'_, AgentState>,
+    settings_state: State<'_, crate::SettingsState>,
+) -> Result<AutopilotStatus, BertError> {
+    if config.max_concurrency == 0 {
+        return Err(BertError::InvalidInput("Max concurrency must be at least 1".to_string()));
+    }
+    crate::bd::check_bd_available()?;
+
+    let project = crate::project::context_for(&window, project_handle.as_deref())?;
+    let backend = crate::agent::session::resolve_backend(config.cli_backend.as_deref(), &settings_state)?;
+    let auto_capture = settings_state.settings.lock().map_err(|e| e.to_string())?.auto_capture_summary;
+
+    {
+        let mut status = state.autopilot.status.lock().unwrap();
+        if status.phase != AutopilotPhase::Idle {
+            return Err(BertError::InvalidInput("Autopilot is already running".to_string()));
+        }
+        *status = AutopilotStatus {
+            phase: AutopilotPhase::Running,
+            config: Some(config.clone()),
+            ..AutopilotStatus::default()
+        };
+    }
+    state.autopilot.ended.lock().unwrap().clear();
+    let status = state.autopilot.status();
+    let _ = app_handle.emit("autopilot-status", status.clone());
+
+    eprintln!("🛫 Autopilot started in {}", project.project_path().display());
+    let handle = app_handle.clone();
+    std::thread::spawn(move || run_loop(handle, project, config, backend, auto_capture));
+
+    Ok(status)
+}
+
+/// Stop starting tasks; sessions already running finish first
+#[tauri::command]
+pub fn stop_autopilot(app_handle: AppHandle, state: State<'_, AgentState>) -> Result<AutopilotStatus, BertError> {
+    state.autopilot.request_stop(&app_handle, "Stopped by user");
+    Ok(state.autopilot.status())
+}
+
+#[tauri::command]
+pub fn get_autopilot_status(state: State<'_, AgentState>) -> Result<AutopilotStatus, BertError> {
+    Ok(state.autopilot.status())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bead(id: &str, priority: u32, issue_type: &str, labels: &[&str], created_at: &str) -> Bead {
+        crate::test_bead(serde_json::json!({
+            "id": id,
+            "priority": priority,
+            "issue_type": issue_type,
+            "labels": labels,
+            "created_at": created_at,
+        }))
+    }
+
+    #[test]
+    fn test_pick_next_by_priority_and_labels() {
+        let ready = vec![
+            bead("bp6-epic", 0, "epic", &["backend"], "2026-01-01"),
+            bead("bp6-new", 1, "task", &["backend"], "2026-03-01"),
+            bead("bp6-old", 1, "bug", &["backend", "rust"], "2026-02-01"),
+            bead("bp6-ui", 0, "task", &["frontend"], "2026-01-01"),
+            bead("bp6-low", 3, "task", &["backend"], "2026-01-01"),
+        ];
+        let none = HashSet::new();
+
+        assert_eq!(pick_next(&ready, &[], &none).unwrap().id, "bp6-ui");
+        assert_eq!(pick_next(&ready, &["backend".to_string()], &none).unwrap().id, "bp6-old");
+
+        let attempted = HashSet::from(["bp6-old", "bp6-new"]);
+        assert_eq!(pick_next(&ready, &["backend".to_string()], &attempted).unwrap().id, "bp6-low");
+        assert!(pick_next(&ready, &["backend".to_string(), "web".to_string()], &none).is_none());
+    }
+
+    #[test]
+    fn test_only_running_sessions_wake_the_loop() {
+        let autopilot = Autopilot::new();
+        autopilot.status.lock().unwrap().runs = ["s1", "s2"]
+            .iter()
+            .map(|id| AutopilotRun {
+                bead_id: format!("bp6-{}", id),
+                title: String::new(),
+                session_id: id.to_string(),
+                started_at: 0,
+                finished_at: None,
+                outcome: if *id == "s1" { RunOutcome::Running } else { RunOutcome::NotClosed },
+                error: None,
+            })
+            .collect();
+
+        autopilot.run_ended("s2");
+        autopilot.run_ended("other");
+        autopilot.run_ended("s1");
+        assert_eq!(autopilot.wait(Duration::from_millis(1)), vec!["s1".to_string()]);
+        assert!(autopilot.wait(Duration::from_millis(1)).is_empty());
+    }
+}
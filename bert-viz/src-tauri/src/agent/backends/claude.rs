@@ -1,5 +1,6 @@
 /// Anthropic Claude Code CLI backend implementation
-use crate::agent::plugin::{AgentChunk, CliBackendPlugin};
+use crate::agent::plugin::{AgentChunk, CliBackendPlugin, TokenUsage, ToolUse};
+use crate::settings::AgentSafetyPolicy;
 use serde_json::Value;
 
 /// Claude Code CLI backend plugin
@@ -15,6 +16,21 @@ impl ClaudeCodeBackend {
     }
 }
 
+/// Token usage and cost from a result event:
+/// `{"type": "result", "total_cost_usd": 0.05, "usage": {"input_tokens": ...}}`
+fn parse_usage(json: &Value) -> Option<TokenUsage> {
+    let usage = json.get("usage")?;
+    let count = |key: &str| usage[key].as_u64().unwrap_or(0);
+    Some(TokenUsage {
+        input_tokens: count("input_tokens"),
+        output_tokens: count("output_tokens"),
+        cache_read_tokens: count("cache_read_input_tokens"),
+        cache_creation_tokens: count("cache_creation_input_tokens"),
+        // Older CLI versions report `cost_usd`
+        cost_usd: json["total_cost_usd"].as_f64().or_else(|| json["cost_usd"].as_f64()),
+    })
+}
+
 impl CliBackendPlugin for ClaudeCodeBackend {
     fn command_name(&self) -> &str {
         "claude"
@@ -24,13 +40,19 @@ impl CliBackendPlugin for ClaudeCodeBackend {
         true
     }
 
-    fn build_args(&self, prompt: &str, resume: bool, session_id: Option<&str>) -> Vec<String> {
+    fn build_args(&self, prompt: &str, resume: bool, session_id: Option<&str>, policy: AgentSafetyPolicy) -> Vec<String> {
         let mut args = vec![
             "--output-format".to_string(),
             "stream-json".to_string(),
             "--verbose".to_string(),
-            "--dangerously-skip-permissions".to_string(),
         ];
+        // Without a terminal to ask in, the default mode denies edits and
+        // commands; plan mode only reads
+        match policy {
+            AgentSafetyPolicy::FullAuto => args.push("--dangerously-skip-permissions".to_string()),
+            AgentSafetyPolicy::ApproveWrites => args.extend(["--permission-mode".to_string(), "default".to_string()]),
+            AgentSafetyPolicy::ReadOnly => args.extend(["--permission-mode".to_string(), "plan".to_string()]),
+        }
 
         if resume {
             args.push("--resume".to_string());
@@ -52,6 +74,21 @@ impl CliBackendPlugin for ClaudeCodeBackend {
         args
     }
 
+    fn allow_tools_args(&self, rules: &[String]) -> Vec<String> {
+        if rules.is_empty() {
+            return Vec::new();
+        }
+        let mut args = vec!["--allowedTools".to_string()];
+        args.extend(rules.iter().cloned());
+        args
+    }
+
+    fn build_interactive_args(&self, session_id: Option<&str>) -> Vec<String> {
+        session_id
+            .map(|sid| vec!["--resume".to_string(), sid.to_string()])
+            .unwrap_or_default()
+    }
+
     fn parse_stdout_line(&self, json: &Value) -> Option<AgentChunk> {
         // Handle Claude Code message format:
         // {"type": "assistant", "message": {"content": [...]}}
@@ -66,6 +103,7 @@ impl CliBackendPlugin for ClaudeCodeBackend {
                                     content: text.to_string(),
                                     is_done: false,
                                     session_id: None,
+                                    usage: None,
                                 });
                             }
                         }
@@ -86,6 +124,7 @@ impl CliBackendPlugin for ClaudeCodeBackend {
                                     content: message,
                                     is_done: false,
                                     session_id: None,
+                                    usage: None,
                                 });
                             }
                         }
@@ -96,6 +135,8 @@ impl CliBackendPlugin for ClaudeCodeBackend {
 
         // Handle completion: {"type": "result"}
         if json["type"] == "result" {
+            let usage = parse_usage(json);
+
             // Check for errors in result
             if json["is_error"].as_bool().unwrap_or(false) {
                 if let Some(errors) = json["errors"].as_array() {
@@ -110,6 +151,7 @@ impl CliBackendPlugin for ClaudeCodeBackend {
                             content: format!("❌ Error: {}", error_messages.join("; ")),
                             is_done: true,
                             session_id: None,
+                            usage,
                         });
                     }
                 }
@@ -120,12 +162,31 @@ impl CliBackendPlugin for ClaudeCodeBackend {
                 content: String::new(),
                 is_done: true,
                 session_id: None,
+                usage,
             });
         }
 
         // Ignore other JSON types (user messages, etc.)
         None
     }
+
+    fn parse_tool_uses(&self, json: &Value) -> Vec<ToolUse> {
+        if json["type"] != "assistant" {
+            return Vec::new();
+        }
+        json["message"]["content"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|block| block["type"] == "tool_use")
+            .filter_map(|block| {
+                Some(ToolUse {
+                    name: block["name"].as_str()?.to_string(),
+                    input: block["input"].clone(),
+                })
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -148,7 +209,7 @@ mod tests {
     #[test]
     fn test_build_args_basic() {
         let backend = ClaudeCodeBackend::new();
-        let args = backend.build_args("test prompt", false, None);
+        let args = backend.build_args("test prompt", false, None, AgentSafetyPolicy::FullAuto);
 
         assert_eq!(args[0], "--output-format");
         assert_eq!(args[1], "stream-json");
@@ -158,11 +219,47 @@ mod tests {
         assert_eq!(args.len(), 5);
     }
 
+    #[test]
+    fn test_build_args_safety_policy() {
+        let backend = ClaudeCodeBackend::new();
+        let args = backend.build_args("test prompt", false, None, AgentSafetyPolicy::ApproveWrites);
+        assert_eq!(args[3..5], ["--permission-mode", "default"]);
+        assert!(!args.contains(&"--dangerously-skip-permissions".to_string()));
+
+        let args = backend.build_args("test prompt", false, None, AgentSafetyPolicy::ReadOnly);
+        assert_eq!(args[3..5], ["--permission-mode", "plan"]);
+
+        let rules = ["Edit".to_string(), "Bash(cargo test)".to_string()];
+        assert_eq!(backend.allow_tools_args(&rules), ["--allowedTools", "Edit", "Bash(cargo test)"]);
+        assert!(backend.allow_tools_args(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_parse_tool_uses() {
+        let backend = ClaudeCodeBackend::new();
+        let json = json!({
+            "type": "assistant",
+            "message": {
+                "content": [
+                    { "type": "text", "text": "Editing" },
+                    { "type": "tool_use", "name": "Edit", "input": { "file_path": "src/main.rs" } },
+                    { "type": "tool_use", "name": "Read", "input": { "file_path": "README.md" } }
+                ]
+            }
+        });
+
+        let tools = backend.parse_tool_uses(&json);
+        assert_eq!(tools.len(), 2);
+        assert_eq!(tools[0].name, "Edit");
+        assert_eq!(tools[0].input["file_path"], "src/main.rs");
+        assert!(backend.parse_tool_uses(&json!({ "type": "result" })).is_empty());
+    }
+
     #[test]
     fn test_build_args_with_session_id() {
         let backend = ClaudeCodeBackend::new();
         let session_id = "550e8400-e29b-41d4-a716-446655440000";
-        let args = backend.build_args("test prompt", false, Some(session_id));
+        let args = backend.build_args("test prompt", false, Some(session_id), AgentSafetyPolicy::FullAuto);
 
         assert!(args.contains(&"--session-id".to_string()));
         assert!(args.contains(&session_id.to_string()));
@@ -173,7 +270,7 @@ mod tests {
     fn test_build_args_with_resume() {
         let backend = ClaudeCodeBackend::new();
         let session_id = "550e8400-e29b-41d4-a716-446655440000";
-        let args = backend.build_args("test prompt", true, Some(session_id));
+        let args = backend.build_args("test prompt", true, Some(session_id), AgentSafetyPolicy::FullAuto);
 
         assert!(args.contains(&"--resume".to_string()));
         assert!(args.contains(&session_id.to_string()));
@@ -182,6 +279,14 @@ mod tests {
         assert_eq!(args.last().unwrap(), "test prompt"); // Prompt still last
     }
 
+    #[test]
+    fn test_build_interactive_args() {
+        let backend = ClaudeCodeBackend::new();
+        let session_id = "550e8400-e29b-41d4-a716-446655440000";
+        assert_eq!(backend.build_interactive_args(Some(session_id)), vec!["--resume", session_id]);
+        assert!(backend.build_interactive_args(None).is_empty());
+    }
+
     #[test]
     fn test_parse_message() {
         let backend = ClaudeCodeBackend::new();
@@ -237,6 +342,30 @@ mod tests {
         let chunk = backend.parse_stdout_line(&json).unwrap();
         assert_eq!(chunk.content, "");
         assert!(chunk.is_done);
+        assert!(chunk.usage.is_none());
+    }
+
+    #[test]
+    fn test_parse_result_usage() {
+        let backend = ClaudeCodeBackend::new();
+        let json = json!({
+            "type": "result",
+            "subtype": "success",
+            "total_cost_usd": 0.0421,
+            "usage": {
+                "input_tokens": 12,
+                "output_tokens": 340,
+                "cache_read_input_tokens": 15000,
+                "cache_creation_input_tokens": 2100
+            }
+        });
+
+        let usage = backend.parse_stdout_line(&json).unwrap().usage.unwrap();
+        assert_eq!(usage.input_tokens, 12);
+        assert_eq!(usage.output_tokens, 340);
+        assert_eq!(usage.cache_read_tokens, 15000);
+        assert_eq!(usage.cache_creation_tokens, 2100);
+        assert_eq!(usage.cost_usd, Some(0.0421));
     }
 
     #[test]
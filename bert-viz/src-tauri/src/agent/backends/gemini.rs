@@ -1,5 +1,6 @@
 /// Google Gemini CLI backend implementation
-use crate::agent::plugin::{AgentChunk, CliBackendPlugin};
+use crate::agent::plugin::{AgentChunk, CliBackendPlugin, TokenUsage, ToolUse};
+use crate::settings::AgentSafetyPolicy;
 use serde_json::Value;
 
 /// Gemini CLI backend plugin
@@ -15,6 +16,21 @@ impl GeminiBackend {
     }
 }
 
+/// Token usage from a result event:
+/// `{"type": "result", "stats": {"input_tokens": ..., "output_tokens": ..., "cached": ...}}`
+/// Gemini doesn't report a cost.
+fn parse_usage(json: &Value) -> Option<TokenUsage> {
+    let stats = json.get("stats")?;
+    let count = |key: &str| stats[key].as_u64().unwrap_or(0);
+    Some(TokenUsage {
+        input_tokens: count("input_tokens"),
+        output_tokens: count("output_tokens"),
+        cache_read_tokens: count("cached"),
+        cache_creation_tokens: 0,
+        cost_usd: None,
+    })
+}
+
 impl CliBackendPlugin for GeminiBackend {
     fn command_name(&self) -> &str {
         "gemini"
@@ -24,12 +40,20 @@ impl CliBackendPlugin for GeminiBackend {
         true
     }
 
-    fn build_args(&self, prompt: &str, resume: bool, session_id: Option<&str>) -> Vec<String> {
+    fn build_args(&self, prompt: &str, resume: bool, session_id: Option<&str>, policy: AgentSafetyPolicy) -> Vec<String> {
         let mut args = vec![
             "--output-format".to_string(),
             "stream-json".to_string(),
-            "--yolo".to_string(),
         ];
+        // Gemini has no read-only mode; tools needing approval are refused
+        // when there is no terminal to ask in, and writes are flagged by the
+        // session reader
+        match policy {
+            AgentSafetyPolicy::FullAuto => args.push("--yolo".to_string()),
+            AgentSafetyPolicy::ApproveWrites | AgentSafetyPolicy::ReadOnly => {
+                args.extend(["--approval-mode".to_string(), "default".to_string()])
+            }
+        }
 
         if resume {
             args.push("--resume".to_string());
@@ -43,6 +67,21 @@ impl CliBackendPlugin for GeminiBackend {
         args
     }
 
+    fn allow_tools_args(&self, rules: &[String]) -> Vec<String> {
+        if rules.is_empty() {
+            return Vec::new();
+        }
+        let mut args = vec!["--allowed-tools".to_string()];
+        args.extend(rules.iter().cloned());
+        args
+    }
+
+    fn build_interactive_args(&self, session_id: Option<&str>) -> Vec<String> {
+        session_id
+            .map(|sid| vec!["--resume".to_string(), sid.to_string()])
+            .unwrap_or_default()
+    }
+
     fn parse_stdout_line(&self, json: &Value) -> Option<AgentChunk> {
         // Handle Gemini init message: {"type": "init", "session_id": "...", "model": "..."}
         // This captures the Gemini CLI's session ID for resume capability
@@ -52,6 +91,7 @@ impl CliBackendPlugin for GeminiBackend {
                     content: String::new(),
                     is_done: false,
                     session_id: Some(session_id.to_string()),
+                    usage: None,
                 });
             }
         }
@@ -63,6 +103,7 @@ impl CliBackendPlugin for GeminiBackend {
                     content: content.to_string(),
                     is_done: false,
                     session_id: None,
+                    usage: None,
                 });
             }
         }
@@ -74,6 +115,7 @@ impl CliBackendPlugin for GeminiBackend {
                     content: format!("🔧 Using tool: {}", tool_name),
                     is_done: false,
                     session_id: None,
+                    usage: None,
                 });
             }
         }
@@ -87,6 +129,7 @@ impl CliBackendPlugin for GeminiBackend {
                         content: format!("⚠️ Tool execution {}", status),
                         is_done: false,
                         session_id: None,
+                        usage: None,
                     });
                 }
             }
@@ -94,6 +137,8 @@ impl CliBackendPlugin for GeminiBackend {
 
         // Handle completion: {"type": "result"}
         if json["type"] == "result" {
+            let usage = parse_usage(json);
+
             // Check for errors in result
             if json["subtype"] == "error_during_execution"
                 || json["is_error"].as_bool().unwrap_or(false)
@@ -110,6 +155,7 @@ impl CliBackendPlugin for GeminiBackend {
                             content: format!("❌ Error: {}", error_messages.join("; ")),
                             is_done: true,
                             session_id: None,
+                            usage,
                         });
                     }
                 }
@@ -120,12 +166,24 @@ impl CliBackendPlugin for GeminiBackend {
                 content: String::new(),
                 is_done: true,
                 session_id: None,
+                usage,
             });
         }
 
         // Ignore other JSON types (user messages, init, etc.)
         None
     }
+
+    fn parse_tool_uses(&self, json: &Value) -> Vec<ToolUse> {
+        // {"type": "tool_use", "tool_name": "write_file", "parameters": {...}}
+        match json["tool_name"].as_str() {
+            Some(name) if json["type"] == "tool_use" => vec![ToolUse {
+                name: name.to_string(),
+                input: json["parameters"].clone(),
+            }],
+            _ => Vec::new(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -148,7 +206,7 @@ mod tests {
     #[test]
     fn test_build_args_basic() {
         let backend = GeminiBackend::new();
-        let args = backend.build_args("test prompt", false, None);
+        let args = backend.build_args("test prompt", false, None, AgentSafetyPolicy::FullAuto);
 
         assert_eq!(args[0], "--output-format");
         assert_eq!(args[1], "stream-json");
@@ -158,10 +216,32 @@ mod tests {
         assert_eq!(args.len(), 5);
     }
 
+    #[test]
+    fn test_build_args_safety_policy() {
+        let backend = GeminiBackend::new();
+        let args = backend.build_args("test prompt", false, None, AgentSafetyPolicy::ApproveWrites);
+        assert_eq!(args[2..4], ["--approval-mode", "default"]);
+        assert!(!args.contains(&"--yolo".to_string()));
+
+        let rules = ["run_shell_command(cargo test)".to_string()];
+        assert_eq!(backend.allow_tools_args(&rules), ["--allowed-tools", "run_shell_command(cargo test)"]);
+        assert!(backend.allow_tools_args(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_parse_tool_uses() {
+        let backend = GeminiBackend::new();
+        let json = json!({ "type": "tool_use", "tool_name": "write_file", "parameters": { "file_path": "a.txt" } });
+        let tools = backend.parse_tool_uses(&json);
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].name, "write_file");
+        assert_eq!(tools[0].input["file_path"], "a.txt");
+    }
+
     #[test]
     fn test_build_args_with_resume() {
         let backend = GeminiBackend::new();
-        let args = backend.build_args("test prompt", true, None);
+        let args = backend.build_args("test prompt", true, None, AgentSafetyPolicy::FullAuto);
 
         assert!(args.contains(&"--resume".to_string()));
         assert!(args.contains(&"latest".to_string()));
@@ -207,6 +287,30 @@ mod tests {
         let chunk = backend.parse_stdout_line(&json).unwrap();
         assert_eq!(chunk.content, "");
         assert!(chunk.is_done);
+        assert!(chunk.usage.is_none());
+    }
+
+    #[test]
+    fn test_parse_result_usage() {
+        let backend = GeminiBackend::new();
+        let json = json!({
+            "type": "result",
+            "status": "success",
+            "stats": {
+                "total_tokens": 1530,
+                "input_tokens": 1200,
+                "output_tokens": 330,
+                "cached": 800,
+                "duration_ms": 4200,
+                "tool_calls": 2
+            }
+        });
+
+        let usage = backend.parse_stdout_line(&json).unwrap().usage.unwrap();
+        assert_eq!(usage.input_tokens, 1200);
+        assert_eq!(usage.output_tokens, 330);
+        assert_eq!(usage.cache_read_tokens, 800);
+        assert_eq!(usage.cost_usd, None);
     }
 
     #[test]
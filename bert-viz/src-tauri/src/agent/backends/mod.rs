@@ -1,9 +1,12 @@
-/// CLI backend implementations
+/// CLI and HTTP backend implementations
 ///
 /// This module contains concrete implementations of the CliBackendPlugin trait
-/// for various AI CLI backends (Gemini, Claude Code, etc.)
+/// for various AI CLI backends (Gemini, Claude Code, etc.) and of the
+/// HttpBackendPlugin trait for HTTP backends (Ollama)
 pub mod claude;
 pub mod gemini;
+pub mod ollama;
 
 pub use claude::ClaudeCodeBackend;
 pub use gemini::GeminiBackend;
+pub use ollama::OllamaBackend;
@@ -0,0 +1,124 @@
+/// Ollama local LLM backend implementation
+use crate::agent::plugin::{AgentChunk, ChatMessage, HttpBackendPlugin, TokenUsage};
+use serde_json::Value;
+
+/// Default address of a local Ollama server
+pub const DEFAULT_HOST: &str = "http://localhost:11434";
+/// Default model requested from Ollama
+pub const DEFAULT_MODEL: &str = "llama3.1";
+
+/// Ollama HTTP backend plugin
+///
+/// Implements the HttpBackendPlugin trait for Ollama's `/api/chat` endpoint.
+/// The host and model come from the app settings.
+pub struct OllamaBackend {
+    host: String,
+    model: String,
+}
+
+impl OllamaBackend {
+    /// Create a new Ollama backend for a server and model
+    pub fn new(host: &str, model: &str) -> Self {
+        OllamaBackend {
+            host: host.trim_end_matches('/').to_string(),
+            model: model.to_string(),
+        }
+    }
+}
+
+impl HttpBackendPlugin for OllamaBackend {
+    fn name(&self) -> &str {
+        "ollama"
+    }
+
+    fn endpoint(&self) -> String {
+        format!("{}/api/chat", self.host)
+    }
+
+    fn build_request(&self, messages: &[ChatMessage]) -> Value {
+        serde_json::json!({
+            "model": self.model,
+            "messages": messages,
+            "stream": true,
+        })
+    }
+
+    fn parse_stream_line(&self, json: &Value) -> Option<AgentChunk> {
+        // Handle errors: {"error": "model 'x' not found"}
+        if let Some(error) = json["error"].as_str() {
+            return Some(AgentChunk {
+                content: format!("❌ Error: {}", error),
+                is_done: true,
+                session_id: None,
+                usage: None,
+            });
+        }
+
+        // Handle completion: {"done": true, "prompt_eval_count": ..., "eval_count": ...}
+        if json["done"].as_bool().unwrap_or(false) {
+            return Some(AgentChunk {
+                content: String::new(),
+                is_done: true,
+                session_id: None,
+                // Local models cost nothing, so no cost is reported
+                usage: Some(TokenUsage {
+                    input_tokens: json["prompt_eval_count"].as_u64().unwrap_or(0),
+                    output_tokens: json["eval_count"].as_u64().unwrap_or(0),
+                    ..TokenUsage::default()
+                }),
+            });
+        }
+
+        // Handle message: {"message": {"role": "assistant", "content": "..."}, "done": false}
+        json["message"]["content"]
+            .as_str()
+            .filter(|content| !content.is_empty())
+            .map(|content| AgentChunk {
+                content: content.to_string(),
+                is_done: false,
+                session_id: None,
+                usage: None,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_request_carries_conversation() {
+        let backend = OllamaBackend::new("http://gpu-box:11434/", "qwen2.5-coder");
+        assert_eq!(backend.endpoint(), "http://gpu-box:11434/api/chat");
+
+        let body = backend.build_request(&[ChatMessage::user("Hi"), ChatMessage::assistant("Hello"), ChatMessage::user("Plan bp6-1")]);
+        assert_eq!(body["model"], "qwen2.5-coder");
+        assert_eq!(body["stream"], true);
+        assert_eq!(body["messages"][2], json!({ "role": "user", "content": "Plan bp6-1" }));
+    }
+
+    #[test]
+    fn test_parse_stream() {
+        let backend = OllamaBackend::new(DEFAULT_HOST, DEFAULT_MODEL);
+
+        let chunk = backend
+            .parse_stream_line(&json!({ "message": { "role": "assistant", "content": "Hel" }, "done": false }))
+            .unwrap();
+        assert_eq!(chunk.content, "Hel");
+        assert!(!chunk.is_done);
+
+        let done = backend
+            .parse_stream_line(&json!({ "message": { "role": "assistant", "content": "" }, "done": true, "prompt_eval_count": 26, "eval_count": 290 }))
+            .unwrap();
+        assert!(done.is_done);
+        let usage = done.usage.unwrap();
+        assert_eq!((usage.input_tokens, usage.output_tokens, usage.cost_usd), (26, 290, None));
+
+        let error = backend.parse_stream_line(&json!({ "error": "model 'x' not found" })).unwrap();
+        assert_eq!(error.content, "❌ Error: model 'x' not found");
+        assert!(error.is_done);
+
+        assert!(backend.parse_stream_line(&json!({ "status": "loading" })).is_none());
+    }
+}
@@ -0,0 +1,498 @@
+//! Validation of agent-suggested `bd` commands
+//!
+//! Suggestions come from model output, so they are never handed to `bd`
+//! as written. `BdCommand::parse` accepts only the subcommands that edit the
+//! plan (`create`, `update`, `close`, `dep add`, `dep remove`) and, for each,
+//! a fixed set of flags; anything else is rejected rather than passed
+//! through. `preview` checks every referenced bead against the project's
+//! index and describes the change, so the user sees what approving does
+//! before anything runs. Approving maps the parsed command to `BeadOp`s, so
+//! it goes through the same transition checks and pending-ops queue as an
+//! edit made in the UI.
+
+use serde::Serialize;
+
+use serde_json::{Map, Value};
+
+use crate::agent::proposals::split_command_args;
+use crate::bd::BeadOp;
+use crate::bead_index::BeadIndex;
+use crate::error::BertError;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum BdSubcommand {
+    Create,
+    Update,
+    Close,
+    DepAdd,
+    DepRemove,
+}
+
+/// A flag a subcommand accepts: the spellings bd understands, and the Bead
+/// field it sets (for the preview)
+struct FlagSpec {
+    names: &'static [&'static str],
+    field: &'static str,
+}
+
+const fn flag(names: &'static [&'static str], field: &'static str) -> FlagSpec {
+    FlagSpec { names, field }
+}
+
+const TITLE: FlagSpec = flag(&["--title"], "title");
+const PRIORITY: FlagSpec = flag(&["--priority", "-p"], "priority");
+const TYPE: FlagSpec = flag(&["--type", "-t"], "issue_type");
+const DESCRIPTION: FlagSpec = flag(&["--description", "-d"], "description");
+const ASSIGNEE: FlagSpec = flag(&["--assignee", "-a"], "owner");
+const ESTIMATE: FlagSpec = flag(&["--estimate", "-e"], "estimate");
+const DESIGN: FlagSpec = flag(&["--design"], "design");
+const ACCEPTANCE: FlagSpec = flag(&["--acceptance"], "acceptance_criteria");
+const EXTERNAL_REF: FlagSpec = flag(&["--external-ref"], "external_reference");
+
+const CREATE_FLAGS: &[FlagSpec] = &[
+    TITLE, PRIORITY, TYPE, DESCRIPTION, ASSIGNEE, ESTIMATE, DESIGN, ACCEPTANCE, EXTERNAL_REF,
+    flag(&["--labels", "-l"], "labels"),
+    flag(&["--parent"], "parent"),
+];
+const UPDATE_FLAGS: &[FlagSpec] = &[
+    TITLE, PRIORITY, TYPE, DESCRIPTION, ASSIGNEE, ESTIMATE, DESIGN, ACCEPTANCE, EXTERNAL_REF,
+    flag(&["--status", "-s"], "status"),
+    flag(&["--notes"], "notes"),
+];
+const CLOSE_FLAGS: &[FlagSpec] = &[flag(&["--reason", "-r"], "close_reason")];
+const DEP_ADD_FLAGS: &[FlagSpec] = &[flag(&["--type", "-t"], "type")];
+
+const DEPENDENCY_TYPES: [&str; 4] = ["blocks", "related", "parent-child", "discovered-from"];
+
+impl BdSubcommand {
+    fn flags(self) -> &'static [FlagSpec] {
+        match self {
+            BdSubcommand::Create => CREATE_FLAGS,
+            BdSubcommand::Update => UPDATE_FLAGS,
+            BdSubcommand::Close => CLOSE_FLAGS,
+            BdSubcommand::DepAdd => DEP_ADD_FLAGS,
+            BdSubcommand::DepRemove => &[],
+        }
+    }
+
+    /// Arguments naming the subcommand
+    fn words(self) -> &'static [&'static str] {
+        match self {
+            BdSubcommand::Create => &["create"],
+            BdSubcommand::Update => &["update"],
+            BdSubcommand::Close => &["close"],
+            BdSubcommand::DepAdd => &["dep", "add"],
+            BdSubcommand::DepRemove => &["dep", "remove"],
+        }
+    }
+}
+
+/// A whitelisted bd command line, split into positionals and known flags
+#[derive(Debug, Clone, PartialEq)]
+pub struct BdCommand {
+    pub subcommand: BdSubcommand,
+    pub positionals: Vec<String>,
+    /// Canonical (long) flag name and value, in command order
+    pub flags: Vec<(&'static str, String)>,
+}
+
+fn invalid(message: impl Into<String>) -> BertError {
+    BertError::InvalidInput(message.into())
+}
+
+impl BdCommand {
+    pub fn parse(command: &str) -> Result<Self, BertError> {
+        let args = split_command_args(command.trim()).map_err(BertError::InvalidInput)?;
+        let mut args = args.into_iter();
+        if args.next().as_deref() != Some("bd") {
+            return Err(invalid("Only 'bd' commands are supported for approval"));
+        }
+
+        let subcommand = match (args.next().as_deref(), args.as_slice().first().map(String::as_str)) {
+            (Some("create" | "new"), _) => BdSubcommand::Create,
+            (Some("update"), _) => BdSubcommand::Update,
+            (Some("close"), _) => BdSubcommand::Close,
+            (Some("dep"), Some("add")) => BdSubcommand::DepAdd,
+            (Some("dep"), Some("remove" | "rm")) => BdSubcommand::DepRemove,
+            (Some(other), _) => {
+                return Err(invalid(format!(
+                    "bd {} is not allowed; suggestions may only create, update, close or add/remove dependencies",
+                    other
+                )))
+            }
+            (None, _) => return Err(invalid("Missing bd subcommand")),
+        };
+        if matches!(subcommand, BdSubcommand::DepAdd | BdSubcommand::DepRemove) {
+            args.next();
+        }
+
+        let specs = subcommand.flags();
+        let mut positionals = Vec::new();
+        let mut flags = Vec::new();
+        while let Some(arg) = args.next() {
+            if !arg.starts_with('-') || arg == "-" {
+                positionals.push(arg);
+                continue;
+            }
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) => (name.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+            let spec = specs
+                .iter()
+                .find(|s| s.names.contains(&name.as_str()))
+                .ok_or_else(|| invalid(format!("Flag {} is not allowed for bd {}", name, subcommand.words().join(" "))))?;
+            let value = match inline_value {
+                Some(value) => value,
+                None => args.next().ok_or_else(|| invalid(format!("Flag {} needs a value", name)))?,
+            };
+            flags.push((spec.names[0], value));
+        }
+
+        let cmd = BdCommand { subcommand, positionals, flags };
+        cmd.check_shape()?;
+        Ok(cmd)
+    }
+
+    fn flag(&self, name: &str) -> Option<&str> {
+        self.flags.iter().rev().find(|(n, _)| *n == name).map(|(_, v)| v.as_str())
+    }
+
+    /// Positional counts and values that don't need the project
+    fn check_shape(&self) -> Result<(), BertError> {
+        let count = self.positionals.len();
+        let shape_ok = match self.subcommand {
+            BdSubcommand::Create => count + usize::from(self.flag("--title").is_some()) == 1,
+            BdSubcommand::Update => count == 1 && !self.flags.is_empty(),
+            BdSubcommand::Close => count >= 1,
+            BdSubcommand::DepAdd | BdSubcommand::DepRemove => count == 2,
+        };
+        if !shape_ok {
+            let usage = match self.subcommand {
+                BdSubcommand::Create => "bd create <title> [flags]",
+                BdSubcommand::Update => "bd update <id> <flags>",
+                BdSubcommand::Close => "bd close <id>... [--reason <reason>]",
+                BdSubcommand::DepAdd => "bd dep add <issue> <depends-on> [--type <type>]",
+                BdSubcommand::DepRemove => "bd dep remove <issue> <depends-on>",
+            };
+            return Err(invalid(format!("Expected {}", usage)));
+        }
+
+        if let Some(priority) = self.flag("--priority") {
+            let priority = priority.trim_start_matches(['P', 'p']);
+            if !matches!(priority.parse::<u32>(), Ok(0..=4)) {
+                return Err(invalid(format!("Invalid priority {}", priority)));
+            }
+        }
+        if let Some(estimate) = self.flag("--estimate") {
+            estimate.parse::<u32>().map_err(|_| invalid(format!("Invalid estimate {}", estimate)))?;
+        }
+        if self.subcommand == BdSubcommand::DepAdd {
+            if let Some(dep_type) = self.flag("--type").filter(|t| !DEPENDENCY_TYPES.contains(t)) {
+                return Err(invalid(format!("Invalid dependency type {}", dep_type)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Beads the command refers to, which must exist
+    fn referenced_ids(&self) -> Vec<&str> {
+        match self.subcommand {
+            BdSubcommand::Create => self.flag("--parent").into_iter().collect(),
+            _ => self.positionals.iter().map(String::as_str).collect(),
+        }
+    }
+
+    /// Arguments for `bd`, rebuilt from the parsed command
+    pub fn args(&self) -> Vec<String> {
+        let mut args: Vec<String> = self.subcommand.words().iter().map(|w| w.to_string()).collect();
+        args.extend(self.positionals.iter().cloned());
+        for (name, value) in &self.flags {
+            args.push(name.to_string());
+            args.push(value.clone());
+        }
+        args
+    }
+
+    fn field_of(&self, flag_name: &str) -> &'static str {
+        self.subcommand
+            .flags()
+            .iter()
+            .find(|s| s.names[0] == flag_name)
+            .map(|s| s.field)
+            .unwrap_or("")
+    }
+
+    /// The bead operations approving the command performs, built on the
+    /// beads in `index` (which `preview` has checked exist)
+    pub fn bead_ops(&self, index: &BeadIndex) -> Result<Vec<BeadOp>, BertError> {
+        let existing = |id: &str| {
+            index.get(id).cloned().ok_or_else(|| BertError::NotFound(format!("Bead with ID {} not found", id)))
+        };
+        let with_flags = |mut bead: Map<String, Value>| -> Result<crate::Bead, BertError> {
+            for (name, value) in &self.flags {
+                set_field(&mut bead, self.field_of(name), value)?;
+            }
+            Ok(serde_json::from_value(Value::Object(bead))?)
+        };
+
+        Ok(match self.subcommand {
+            BdSubcommand::Create => {
+                let mut bead = Map::new();
+                bead.insert("id".to_string(), Value::from(""));
+                bead.insert("status".to_string(), Value::from("open"));
+                bead.insert("priority".to_string(), Value::from(2));
+                bead.insert("issue_type".to_string(), Value::from("task"));
+                if let Some(title) = self.positionals.first() {
+                    bead.insert("title".to_string(), Value::from(title.as_str()));
+                }
+                vec![BeadOp::Create { bead: with_flags(bead)? }]
+            }
+            BdSubcommand::Update => {
+                let Value::Object(bead) = serde_json::to_value(existing(&self.positionals[0])?)? else {
+                    return Err(BertError::Internal("Bead did not serialize to an object".to_string()));
+                };
+                vec![BeadOp::Update { bead: with_flags(bead)? }]
+            }
+            BdSubcommand::Close => self
+                .positionals
+                .iter()
+                .map(|id| BeadOp::Close { bead_id: id.clone(), reason: self.flag("--reason").map(str::to_string) })
+                .collect(),
+            BdSubcommand::DepAdd => vec![BeadOp::AddDependency {
+                issue_id: self.positionals[0].clone(),
+                depends_on_id: self.positionals[1].clone(),
+                dep_type: self.flag("--type").unwrap_or("blocks").to_string(),
+            }],
+            BdSubcommand::DepRemove => vec![BeadOp::RemoveDependency {
+                issue_id: self.positionals[0].clone(),
+                depends_on_id: self.positionals[1].clone(),
+            }],
+        })
+    }
+}
+
+/// Set the Bead `field` in `bead` from a flag value
+fn set_field(bead: &mut Map<String, Value>, field: &str, value: &str) -> Result<(), BertError> {
+    let value = match field {
+        "priority" => Value::from(value.trim_start_matches(['P', 'p']).parse::<u32>().map_err(|_| invalid(format!("Invalid priority {}", value)))?),
+        "estimate" => Value::from(value.parse::<u32>().map_err(|_| invalid(format!("Invalid estimate {}", value)))?),
+        "labels" => Value::from(value.split(',').map(str::trim).filter(|l| !l.is_empty()).collect::<Vec<_>>()),
+        _ => Value::from(value),
+    };
+    bead.insert(field.to_string(), value);
+    Ok(())
+}
+
+/// One field the command sets, with its current value when the bead exists
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewChange {
+    pub bead_id: Option<String>,
+    pub field: String,
+    pub old: Option<String>,
+    pub new: String,
+}
+
+/// What approving a suggestion will do
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandPreview {
+    pub subcommand: BdSubcommand,
+    /// Normalized arguments that will be passed to bd
+    pub args: Vec<String>,
+    pub summary: String,
+    pub changes: Vec<PreviewChange>,
+}
+
+/// Current value of `field` of a bead, as text
+fn current_value(index: &BeadIndex, bead_id: &str, field: &str) -> Option<String> {
+    let bead = serde_json::to_value(index.get(bead_id)?).ok()?;
+    match &bead[field] {
+        serde_json::Value::Null => None,
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Array(items) => {
+            Some(items.iter().filter_map(|v| v.as_str()).collect::<Vec<_>>().join("\n"))
+        }
+        other => Some(other.to_string()),
+    }
+}
+
+/// Validate `cmd` against the project's beads and describe its effect
+pub fn preview(cmd: &BdCommand, index: &BeadIndex) -> Result<CommandPreview, BertError> {
+    for id in cmd.referenced_ids() {
+        if index.get(id).is_none() {
+            return Err(BertError::NotFound(format!("Bead with ID {} not found", id)));
+        }
+    }
+
+    let title_of = |id: &str| index.get(id).map(|b| format!("{} ({})", id, b.title)).unwrap_or_else(|| id.to_string());
+    let (summary, changes) = match cmd.subcommand {
+        BdSubcommand::Create => {
+            let title = cmd.positionals.first().map(String::as_str).or(cmd.flag("--title")).unwrap_or_default();
+            let issue_type = cmd.flag("--type").unwrap_or("task");
+            let mut summary = format!("Create {} \"{}\"", issue_type, title);
+            if let Some(parent) = cmd.flag("--parent") {
+                summary.push_str(&format!(" under {}", title_of(parent)));
+            }
+            let mut changes = vec![PreviewChange { bead_id: None, field: "title".to_string(), old: None, new: title.to_string() }];
+            changes.extend(cmd.flags.iter().filter(|(name, _)| *name != "--title").map(|(name, value)| PreviewChange {
+                bead_id: None,
+                field: cmd.field_of(name).to_string(),
+                old: None,
+                new: value.clone(),
+            }));
+            (summary, changes)
+        }
+        BdSubcommand::Update => {
+            let id = &cmd.positionals[0];
+            let changes: Vec<PreviewChange> = cmd
+                .flags
+                .iter()
+                .map(|(name, value)| {
+                    let field = cmd.field_of(name);
+                    PreviewChange {
+                        bead_id: Some(id.clone()),
+                        field: field.to_string(),
+                        old: current_value(index, id, field),
+                        new: value.clone(),
+                    }
+                })
+                .collect();
+            let fields: Vec<&str> = changes.iter().map(|c| c.field.as_str()).collect();
+            (format!("Update {} of {}", fields.join(", "), title_of(id)), changes)
+        }
+        BdSubcommand::Close => {
+            let changes = cmd
+                .positionals
+                .iter()
+                .map(|id| PreviewChange {
+                    bead_id: Some(id.clone()),
+                    field: "status".to_string(),
+                    old: current_value(index, id, "status"),
+                    new: "closed".to_string(),
+                })
+                .collect();
+            let ids: Vec<String> = cmd.positionals.iter().map(|id| title_of(id)).collect();
+            let mut summary = format!("Close {}", ids.join(", "));
+            if let Some(reason) = cmd.flag("--reason") {
+                summary.push_str(&format!(": {}", reason));
+            }
+            (summary, changes)
+        }
+        BdSubcommand::DepAdd | BdSubcommand::DepRemove => {
+            let (issue, depends_on) = (&cmd.positionals[0], &cmd.positionals[1]);
+            let dep_type = cmd.flag("--type").unwrap_or("blocks");
+            let adding = cmd.subcommand == BdSubcommand::DepAdd;
+            let summary = format!(
+                "{} dependency: {} depends on {}{}",
+                if adding { "Add" } else { "Remove" },
+                title_of(issue),
+                title_of(depends_on),
+                if adding { format!(" ({})", dep_type) } else { String::new() }
+            );
+            let change = PreviewChange {
+                bead_id: Some(issue.clone()),
+                field: "dependencies".to_string(),
+                old: None,
+                new: format!("{} {}", if adding { "+" } else { "-" }, depends_on),
+            };
+            (summary, vec![change])
+        }
+    };
+
+    Ok(CommandPreview { subcommand: cmd.subcommand, args: cmd.args(), summary, changes })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Bead;
+    use serde_json::json;
+
+    fn index() -> BeadIndex {
+        let bead = |id: &str, status: &str| -> Bead {
+            serde_json::from_value(json!({
+                "id": id, "title": format!("Bead {}", id), "status": status, "priority": 2, "issue_type": "task",
+            }))
+            .unwrap()
+        };
+        BeadIndex::new(vec![bead("bp6-1", "open"), bead("bp6-2", "in_progress")])
+    }
+
+    #[test]
+    fn test_parses_quoted_args_into_normalized_flags() {
+        let cmd = BdCommand::parse(r#"bd create "Fix the login page" -p 1 --type=bug --parent bp6-1"#).unwrap();
+        assert_eq!(cmd.subcommand, BdSubcommand::Create);
+        assert_eq!(cmd.positionals, vec!["Fix the login page"]);
+        assert_eq!(
+            cmd.args(),
+            vec!["create", "Fix the login page", "--priority", "1", "--type", "bug", "--parent", "bp6-1"]
+        );
+
+        let preview = preview(&cmd, &index()).unwrap();
+        assert_eq!(preview.summary, "Create bug \"Fix the login page\" under bp6-1 (Bead bp6-1)");
+        assert_eq!(preview.changes.len(), 4);
+    }
+
+    #[test]
+    fn test_rejects_other_subcommands_and_flags() {
+        for command in [
+            "bd delete bp6-1",
+            "bd sync",
+            "bd update bp6-1 --db /tmp/other.db --status closed",
+            "bd close bp6-1 --force",
+            "bd create",
+            "bd create A B",
+            "bd update bp6-1",
+            "bd update bp6-1 -p 9",
+            "bd dep add bp6-1 bp6-2 --type owns",
+            "rm -rf / && bd close bp6-1",
+            "bd close \"bp6-1",
+        ] {
+            assert!(BdCommand::parse(command).is_err(), "{}", command);
+        }
+    }
+
+    #[test]
+    fn test_preview_checks_ids_and_shows_old_values() {
+        let index = index();
+        let update = BdCommand::parse("bd update bp6-2 --status closed -a ann").unwrap();
+        let preview = preview(&update, &index).unwrap();
+        assert_eq!(preview.summary, "Update status, owner of bp6-2 (Bead bp6-2)");
+        assert_eq!(preview.changes[0].old.as_deref(), Some("in_progress"));
+        assert_eq!(preview.changes[0].new, "closed");
+        assert_eq!(preview.changes[1].old, None);
+
+        let dep = BdCommand::parse("bd dep rm bp6-2 bp6-1").unwrap();
+        assert_eq!(dep.args(), vec!["dep", "remove", "bp6-2", "bp6-1"]);
+        assert!(super::preview(&dep, &index).is_ok());
+
+        let missing = BdCommand::parse("bd close bp6-1 bp6-404 -r done").unwrap();
+        assert!(matches!(super::preview(&missing, &index), Err(BertError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_maps_commands_to_bead_ops() {
+        let index = index();
+        let update = BdCommand::parse("bd update bp6-2 --status closed -p P0 --notes wip").unwrap();
+        let [BeadOp::Update { bead }] = update.bead_ops(&index).unwrap().as_slice() else { panic!("expected one update") };
+        assert_eq!((bead.id.as_str(), bead.title.as_str()), ("bp6-2", "Bead bp6-2"));
+        assert_eq!((bead.status.as_str(), bead.priority, bead.notes.as_deref()), ("closed", 0, Some("wip")));
+
+        let create = BdCommand::parse("bd create --title Ship -t epic -l a,b --parent bp6-1").unwrap();
+        let [BeadOp::Create { bead }] = create.bead_ops(&index).unwrap().as_slice() else { panic!("expected one create") };
+        assert_eq!((bead.title.as_str(), bead.issue_type.as_str(), bead.status.as_str()), ("Ship", "epic", "open"));
+        assert_eq!(bead.labels, Some(vec!["a".to_string(), "b".to_string()]));
+        assert_eq!(bead.parent.as_deref(), Some("bp6-1"));
+
+        let close = BdCommand::parse("bd close bp6-1 bp6-2 -r done").unwrap();
+        assert_eq!(close.bead_ops(&index).unwrap().len(), 2);
+        let dep = BdCommand::parse("bd dep add bp6-2 bp6-1").unwrap();
+        assert!(matches!(
+            dep.bead_ops(&index).unwrap().as_slice(),
+            [BeadOp::AddDependency { dep_type, .. }] if dep_type == "blocks"
+        ));
+    }
+}
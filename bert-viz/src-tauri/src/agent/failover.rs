@@ -0,0 +1,300 @@
+//! Retries and backend failover for agent runs that fail to start
+//!
+//! A CLI that is missing, logged out or rate limited used to take its
+//! session down with it. Each run now starts under the `agentFailover`
+//! policy: a new CLI is watched for `startupWindowMs`, and if it exits with
+//! an error in that time its stderr says what went wrong. Rate limits and
+//! network errors are retried on the same backend with doubling backoff;
+//! a missing CLI or an auth error moves on to the fallback backend straight
+//! away, since retrying can't help. Other failures are reported as they are.
+//! Probing and backoff block, so the commands that start runs are `async`
+//! and never wait on the main thread.
+//!
+//! Every attempt is recorded, and the chain ends up in `SessionInfo`
+//! (`spawnAttempts`) so the UI can show why a session runs on another
+//! backend, or why it didn't start at all.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::process::{Child, ChildStderr, ExitStatus};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use crate::agent::plugin::BackendId;
+use crate::error::BertError;
+use crate::settings::FailoverPolicy;
+
+/// How often a starting CLI is checked for an exit
+const PROBE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How long the stderr of a CLI that exited is read for
+const STDERR_READ_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Why a run didn't start
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum StartupFailure {
+    /// The CLI isn't installed
+    NotInstalled,
+    /// Not logged in, or the API key was rejected
+    Auth,
+    /// Rate limit, exhausted quota or an overloaded API
+    RateLimited,
+    /// Network error or an unavailable service
+    Transient,
+    /// Anything else, e.g. bad arguments
+    Failed,
+}
+
+impl StartupFailure {
+    /// Whether trying the same backend again may help
+    pub fn retryable(self) -> bool {
+        matches!(self, StartupFailure::RateLimited | StartupFailure::Transient)
+    }
+
+    /// Whether another backend may do better
+    pub fn fails_over(self) -> bool {
+        self != StartupFailure::Failed
+    }
+}
+
+/// Lowercase stderr fragments, checked in this order
+const AUTH_PATTERNS: &[&str] = &[
+    "unauthorized",
+    "unauthenticated",
+    "not authenticated",
+    "authentication",
+    "invalid api key",
+    "invalid_api_key",
+    "api key not valid",
+    "api_key_invalid",
+    "please log in",
+    "please login",
+    "login required",
+    "/login",
+    "credentials",
+    "http 401",
+    "http 403",
+];
+const RATE_LIMIT_PATTERNS: &[&str] = &[
+    "rate limit",
+    "rate_limit",
+    "ratelimit",
+    "too many requests",
+    "quota",
+    "resource_exhausted",
+    "overloaded",
+    "http 429",
+    "http 529",
+];
+const TRANSIENT_PATTERNS: &[&str] = &[
+    "econnreset",
+    "econnrefused",
+    "etimedout",
+    "eai_again",
+    "enotfound",
+    "connection refused",
+    "connection reset",
+    "socket hang up",
+    "timed out",
+    "network",
+    "service unavailable",
+    "temporarily unavailable",
+    "http 500",
+    "http 502",
+    "http 503",
+    "http 504",
+];
+
+/// What the stderr (or error message) of a failed start points at
+pub fn classify(stderr: &str) -> StartupFailure {
+    let stderr = stderr.to_lowercase();
+    let matches = |patterns: &[&str]| patterns.iter().any(|p| stderr.contains(p));
+    if matches(AUTH_PATTERNS) {
+        StartupFailure::Auth
+    } else if matches(RATE_LIMIT_PATTERNS) {
+        StartupFailure::RateLimited
+    } else if matches(TRANSIENT_PATTERNS) {
+        StartupFailure::Transient
+    } else {
+        StartupFailure::Failed
+    }
+}
+
+/// Failure kind of an error returned while starting a run
+pub fn classify_error(error: &BertError) -> StartupFailure {
+    match error {
+        BertError::CliNotInstalled(_) => StartupFailure::NotInstalled,
+        other => classify(&other.to_string()),
+    }
+}
+
+/// One try at starting a run
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SpawnAttempt {
+    pub backend_id: BackendId,
+    /// 1 for the first try on this backend
+    pub attempt: u32,
+    pub started_at: String,
+    /// None for the attempt that started
+    pub failure: Option<StartupFailure>,
+    pub error: Option<String>,
+}
+
+/// Wait up to `window` for `child` to exit; its exit status if it did
+pub fn probe(child: &mut Child, window: Duration) -> Option<ExitStatus> {
+    let deadline = Instant::now() + window;
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return Some(status),
+            Ok(None) if Instant::now() < deadline => std::thread::sleep(PROBE_INTERVAL),
+            _ => return None,
+        }
+    }
+}
+
+/// Whatever an exited CLI wrote to stderr. Gives up after a moment, as a
+/// process it left behind may still hold the pipe open.
+pub fn read_stderr(stderr: Option<ChildStderr>) -> String {
+    let Some(mut stderr) = stderr else { return String::new() };
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut text = String::new();
+        let _ = stderr.read_to_string(&mut text);
+        let _ = tx.send(text);
+    });
+    rx.recv_timeout(STDERR_READ_TIMEOUT).unwrap_or_default()
+}
+
+/// Wait before retry number `retry` (1 for the first retry)
+fn backoff(policy: &FailoverPolicy, retry: u32) -> Duration {
+    Duration::from_millis(policy.backoff_ms.saturating_mul(1u64 << (retry - 1).min(16)))
+}
+
+/// Start a run under `policy`: `start(backend, fallback)` is called for
+/// `backend`, again after retryable failures, and then for the fallback
+/// backend. Returns the run with the backend it started on, or the last
+/// error, along with every attempt made.
+pub fn start_with_failover<T>(
+    policy: &FailoverPolicy,
+    backend: BackendId,
+    mut start: impl FnMut(BackendId, bool) -> Result<T, (StartupFailure, BertError)>,
+    mut on_failure: impl FnMut(&SpawnAttempt),
+) -> (Result<(T, BackendId), BertError>, Vec<SpawnAttempt>) {
+    let mut chain = vec![backend];
+    if let Some(fallback) = policy.fallback_backend.filter(|&fallback| fallback != backend) {
+        chain.push(fallback);
+    }
+
+    let mut attempts = Vec::new();
+    let mut last_error = None;
+    for (i, &backend_id) in chain.iter().enumerate() {
+        let fallback = i > 0;
+        for attempt in 1..=policy.retries + 1 {
+            if attempt > 1 {
+                std::thread::sleep(backoff(policy, attempt - 1));
+            }
+            let started_at = Utc::now().to_rfc3339();
+            match start(backend_id, fallback) {
+                Ok(run) => {
+                    attempts.push(SpawnAttempt { backend_id, attempt, started_at, failure: None, error: None });
+                    return (Ok((run, backend_id)), attempts);
+                }
+                Err((failure, error)) => {
+                    let record = SpawnAttempt {
+                        backend_id,
+                        attempt,
+                        started_at,
+                        failure: Some(failure),
+                        error: Some(error.to_string()),
+                    };
+                    on_failure(&record);
+                    attempts.push(record);
+                    last_error = Some(error);
+                    if !failure.retryable() {
+                        break;
+                    }
+                }
+            }
+        }
+        let failure = attempts.last().and_then(|a| a.failure);
+        if !failure.is_some_and(StartupFailure::fails_over) {
+            break;
+        }
+    }
+
+    let error = last_error.unwrap_or_else(|| BertError::Agent("No backend to start".to_string()));
+    if attempts.len() < 2 {
+        return (Err(error), attempts);
+    }
+    let summary: Vec<String> = attempts
+        .iter()
+        .map(|a| format!("{} #{}: {}", a.backend_id, a.attempt, a.error.as_deref().unwrap_or_default()))
+        .collect();
+    let error = BertError::Agent(format!("Agent failed to start after {} attempts ({})", attempts.len(), summary.join("; ")));
+    (Err(error), attempts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(retries: u32, fallback_backend: Option<BackendId>) -> FailoverPolicy {
+        FailoverPolicy { retries, backoff_ms: 0, startup_window_ms: 0, fallback_backend }
+    }
+
+    #[test]
+    fn test_classifies_startup_stderr() {
+        assert_eq!(classify("Error: Invalid API key · Please run /login"), StartupFailure::Auth);
+        assert_eq!(classify("429 Too Many Requests: RESOURCE_EXHAUSTED"), StartupFailure::RateLimited);
+        assert_eq!(classify("request to https://api failed, reason: getaddrinfo ENOTFOUND"), StartupFailure::Transient);
+        assert_eq!(classify("error: unknown option '--yolo'"), StartupFailure::Failed);
+        assert_eq!(classify_error(&BertError::CliNotInstalled("gemini CLI not found".to_string())), StartupFailure::NotInstalled);
+    }
+
+    #[test]
+    fn test_retries_transient_failures_then_fails_over() {
+        let mut calls = Vec::new();
+        let (result, attempts) = start_with_failover(
+            &policy(1, Some(BackendId::ClaudeCode)),
+            BackendId::Gemini,
+            |backend, fallback| {
+                calls.push((backend, fallback));
+                match backend {
+                    BackendId::Gemini => Err((StartupFailure::RateLimited, BertError::Agent("quota".to_string()))),
+                    _ => Ok("run"),
+                }
+            },
+            |_| {},
+        );
+        assert_eq!(result.unwrap(), ("run", BackendId::ClaudeCode));
+        assert_eq!(calls, [(BackendId::Gemini, false), (BackendId::Gemini, false), (BackendId::ClaudeCode, true)]);
+        let chain: Vec<(u32, Option<StartupFailure>)> = attempts.iter().map(|a| (a.attempt, a.failure)).collect();
+        assert_eq!(chain, [(1, Some(StartupFailure::RateLimited)), (2, Some(StartupFailure::RateLimited)), (1, None)]);
+
+        // Auth errors aren't retried; other failures aren't failed over
+        let mut calls = 0;
+        let (result, attempts) = start_with_failover(
+            &policy(3, Some(BackendId::ClaudeCode)),
+            BackendId::Gemini,
+            |_, _| -> Result<(), _> {
+                calls += 1;
+                Err((StartupFailure::Auth, BertError::Agent("logged out".to_string())))
+            },
+            |_| {},
+        );
+        assert_eq!((calls, attempts.len()), (2, 2));
+        assert!(result.unwrap_err().to_string().contains("after 2 attempts"));
+
+        let (result, attempts) = start_with_failover(
+            &policy(3, Some(BackendId::ClaudeCode)),
+            BackendId::Gemini,
+            |_, _| -> Result<(), _> { Err((StartupFailure::Failed, BertError::Agent("bad flag".to_string()))) },
+            |_| {},
+        );
+        assert_eq!(attempts.len(), 1);
+        assert_eq!(result.unwrap_err().to_string(), BertError::Agent("bad flag".to_string()).to_string());
+    }
+}
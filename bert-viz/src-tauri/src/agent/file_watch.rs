@@ -0,0 +1,264 @@
+//! Files an agent session touched
+//!
+//! While a session is open, a recursive watcher on its working directory (the
+//! session's worktree, or the repo root) records every file created, modified
+//! or deleted, so the user can review an agent's edits without diffing the
+//! repo. `.git` and `.beads` are skipped: git and bd churn there on every
+//! command. Changes are merged per path (a file created then edited is still
+//! "created") and each burst is pushed as `session-files-changed` to the
+//! session's window. The watcher stops when the session is terminated.
+
+use notify::{Config, EventKind, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+
+use crate::events::{emit_scoped, EventScope};
+
+/// Quiet period that ends a burst of file events
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Directories whose contents are never reported
+const IGNORED_DIRS: &[&str] = &[".git", ".beads"];
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FileChangeKind {
+    Created,
+    Modified,
+    Deleted,
+}
+
+/// Net change of one file since the session started
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangedFile {
+    /// Relative to the session's working directory
+    pub path: PathBuf,
+    pub kind: FileChangeKind,
+    /// Last change (seconds since UNIX epoch)
+    pub changed_at: u64,
+}
+
+/// Payload of `session-files-changed`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionFilesChanged {
+    session_id: String,
+    files: Vec<ChangedFile>,
+}
+
+/// Fold one change of `path` into `files`. Returns whether the list changed.
+fn record(files: &mut Vec<ChangedFile>, path: PathBuf, kind: FileChangeKind, changed_at: u64) -> bool {
+    use FileChangeKind::*;
+
+    let Some(index) = files.iter().position(|f| f.path == path) else {
+        files.push(ChangedFile { path, kind, changed_at });
+        return true;
+    };
+    let previous = files[index].kind;
+    let merged = match (previous, kind) {
+        // A file that didn't exist before the session and is gone again
+        (Created, Deleted) => {
+            files.remove(index);
+            return true;
+        }
+        (Created, _) => Created,
+        (Deleted, Created) | (Deleted, Modified) => Modified,
+        (_, kind) => kind,
+    };
+    let file = &mut files[index];
+    let changed = file.kind != merged || file.changed_at != changed_at;
+    file.kind = merged;
+    file.changed_at = changed_at;
+    changed
+}
+
+/// `path` relative to `root`, unless it is outside it or under an ignored directory
+fn relevant_path(root: &Path, path: &Path) -> Option<PathBuf> {
+    let relative = path.strip_prefix(root).ok()?;
+    let ignored = relative
+        .components()
+        .any(|c| matches!(c, Component::Normal(name) if IGNORED_DIRS.iter().any(|dir| name == *dir)));
+    if ignored || relative.as_os_str().is_empty() {
+        return None;
+    }
+    Some(relative.to_path_buf())
+}
+
+/// File changes carried by one notify event, in order
+fn changes_of(event: &notify::Event) -> Vec<(&Path, FileChangeKind)> {
+    use notify::event::{CreateKind, ModifyKind, RemoveKind, RenameMode};
+
+    let each = |kind| event.paths.iter().map(|p| (p.as_path(), kind)).collect::<Vec<_>>();
+    match event.kind {
+        EventKind::Create(CreateKind::Folder) | EventKind::Remove(RemoveKind::Folder) => Vec::new(),
+        EventKind::Create(_) => each(FileChangeKind::Created),
+        EventKind::Remove(_) => each(FileChangeKind::Deleted),
+        EventKind::Modify(ModifyKind::Name(RenameMode::From)) => each(FileChangeKind::Deleted),
+        EventKind::Modify(ModifyKind::Name(RenameMode::To)) => each(FileChangeKind::Created),
+        EventKind::Modify(ModifyKind::Name(RenameMode::Both)) if event.paths.len() == 2 => vec![
+            (event.paths[0].as_path(), FileChangeKind::Deleted),
+            (event.paths[1].as_path(), FileChangeKind::Created),
+        ],
+        EventKind::Modify(ModifyKind::Metadata(_)) => Vec::new(),
+        EventKind::Modify(_) => each(FileChangeKind::Modified),
+        _ => Vec::new(),
+    }
+}
+
+/// Changed files of one session, and the watcher filling them in
+#[derive(Default)]
+pub struct SessionFiles {
+    changes: Arc<Mutex<Vec<ChangedFile>>>,
+    watcher: Option<notify::RecommendedWatcher>,
+}
+
+impl std::fmt::Debug for SessionFiles {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SessionFiles")
+            .field("changes", &self.changes.lock().map(|c| c.len()).unwrap_or_default())
+            .field("watching", &self.watcher.is_some())
+            .finish()
+    }
+}
+
+impl SessionFiles {
+    /// Start recording changes under `root` for `session_id`. A watcher that
+    /// fails to start is logged and leaves the session unwatched.
+    pub fn watch(app_handle: &AppHandle, session_id: &str, root: &Path) -> Self {
+        let mut files = SessionFiles::default();
+        files.ensure_watching(app_handle, session_id, root);
+        files
+    }
+
+    /// Start the watcher if it isn't running, keeping the changes recorded
+    /// so far (a restored session being resumed)
+    pub fn ensure_watching(&mut self, app_handle: &AppHandle, session_id: &str, root: &Path) {
+        if self.watcher.is_some() {
+            return;
+        }
+        let (tx, rx) = mpsc::channel::<notify::Event>();
+        let watcher = notify::RecommendedWatcher::new(
+            move |res: std::result::Result<notify::Event, notify::Error>| match res {
+                Ok(event) => {
+                    let _ = tx.send(event);
+                }
+                Err(e) => eprintln!("Watch error: {:?}", e),
+            },
+            Config::default(),
+        )
+        .and_then(|mut watcher| watcher.watch(root, RecursiveMode::Recursive).map(|_| watcher));
+
+        match watcher {
+            Ok(watcher) => {
+                let changes = self.changes.clone();
+                let handle = app_handle.clone();
+                let (id, root) = (session_id.to_string(), root.to_path_buf());
+                std::thread::spawn(move || record_changes(rx, changes, handle, id, root));
+                self.watcher = Some(watcher);
+            }
+            Err(e) => eprintln!("⚠️  Not tracking files of session {}: failed to watch {}: {}", session_id, root.display(), e),
+        }
+    }
+
+    /// Files changed so far, in order of their first change
+    pub fn changed(&self) -> Vec<ChangedFile> {
+        self.changes.lock().unwrap().clone()
+    }
+}
+
+/// Worker thread of a session's watcher; stops when the watcher is dropped
+fn record_changes(
+    rx: Receiver<notify::Event>,
+    changes: Arc<Mutex<Vec<ChangedFile>>>,
+    app_handle: AppHandle,
+    session_id: String,
+    root: PathBuf,
+) {
+    while let Ok(mut event) = rx.recv() {
+        let mut changed = false;
+        loop {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+            let mut files = changes.lock().unwrap();
+            for (path, kind) in changes_of(&event) {
+                if let Some(relative) = relevant_path(&root, path) {
+                    changed |= record(&mut files, relative, kind, now);
+                }
+            }
+            drop(files);
+
+            match rx.recv_timeout(DEBOUNCE) {
+                Ok(next) => event = next,
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+
+        if changed {
+            let files = changes.lock().unwrap().clone();
+            emit_scoped(
+                &app_handle,
+                EventScope::Session(&session_id),
+                "session-files-changed",
+                SessionFilesChanged { session_id: session_id.clone(), files },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use FileChangeKind::*;
+
+    fn kinds(files: &[ChangedFile]) -> Vec<(&str, FileChangeKind)> {
+        files.iter().map(|f| (f.path.to_str().unwrap(), f.kind)).collect()
+    }
+
+    #[test]
+    fn test_record_merges_changes_per_path() {
+        let mut files = Vec::new();
+        assert!(record(&mut files, "new.rs".into(), Created, 1));
+        assert!(record(&mut files, "new.rs".into(), Modified, 2));
+        assert!(record(&mut files, "old.rs".into(), Modified, 2));
+        assert!(!record(&mut files, "old.rs".into(), Modified, 2));
+        assert!(record(&mut files, "gone.rs".into(), Deleted, 3));
+        assert!(record(&mut files, "back.rs".into(), Deleted, 3));
+        assert!(record(&mut files, "back.rs".into(), Created, 4));
+        assert_eq!(
+            kinds(&files),
+            vec![("new.rs", Created), ("old.rs", Modified), ("gone.rs", Deleted), ("back.rs", Modified)]
+        );
+
+        // Created and deleted again within the session: nothing to report
+        assert!(record(&mut files, "new.rs".into(), Deleted, 5));
+        assert!(record(&mut files, "old.rs".into(), Deleted, 5));
+        assert_eq!(kinds(&files), vec![("old.rs", Deleted), ("gone.rs", Deleted), ("back.rs", Modified)]);
+    }
+
+    #[test]
+    fn test_relevant_path_skips_git_and_beads() {
+        let root = Path::new("/repo");
+        assert_eq!(relevant_path(root, Path::new("/repo/src/main.rs")), Some(PathBuf::from("src/main.rs")));
+        assert_eq!(relevant_path(root, Path::new("/repo/.git/index")), None);
+        assert_eq!(relevant_path(root, Path::new("/repo/.beads/issues.jsonl")), None);
+        assert_eq!(relevant_path(root, Path::new("/repo/vendor/lib/.git/HEAD")), None);
+        assert_eq!(relevant_path(root, Path::new("/elsewhere/file")), None);
+        assert_eq!(relevant_path(root, Path::new("/repo")), None);
+        // Only whole directory names are ignored
+        assert_eq!(relevant_path(root, Path::new("/repo/.github/ci.yml")), Some(PathBuf::from(".github/ci.yml")));
+    }
+
+    #[test]
+    fn test_rename_is_delete_then_create() {
+        use notify::event::{ModifyKind, RenameMode};
+        let event = notify::Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::Both)))
+            .add_path("/repo/a.rs".into())
+            .add_path("/repo/b.rs".into());
+        assert_eq!(changes_of(&event), vec![(Path::new("/repo/a.rs"), Deleted), (Path::new("/repo/b.rs"), Created)]);
+    }
+}
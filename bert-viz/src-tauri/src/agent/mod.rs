@@ -2,15 +2,27 @@
 ///
 /// This module provides a plugin-based architecture for integrating different
 /// CLI backends (Gemini, Claude Code, etc.) and persona templates.
+pub mod artifacts;
+pub mod autopilot;
 pub mod backends;
+pub mod bd_command;
+pub mod failover;
+pub mod file_watch;
 pub mod persona;
 pub mod personas;
 pub mod plugin;
+pub mod proposals;
 pub mod pty;
 pub mod registry;
+pub mod safety;
 pub mod session;
 pub mod session_index;
+pub mod session_queue;
+pub mod session_store;
+pub mod summary;
+pub mod swarm;
 pub mod templates;
+pub mod worktree;
 
 // Re-export commonly used types from plugin module (for future use)
 #[allow(unused_imports)]
@@ -3,7 +3,9 @@
 /// This module defines the trait-based plugin system for different AI personas
 /// (specialist, product-manager, qa-engineer, etc.)
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
+
+use crate::agent::personas::CustomPersona;
 
 /// Quality standards for Product Manager personas
 /// These standards are prepended to all PM templates to ensure consistent quality
@@ -95,6 +97,8 @@ pub enum PersonaType {
     Specialist,
     Architect,
     Customer,
+    /// User-defined persona loaded from `~/.bp6/personas`
+    Custom,
 }
 
 impl PersonaType {
@@ -105,6 +109,7 @@ impl PersonaType {
             PersonaType::Specialist => "specialist",
             PersonaType::Architect => "architect",
             PersonaType::Customer => "customer",
+            PersonaType::Custom => "custom",
         }
     }
 }
@@ -116,6 +121,8 @@ pub struct PersonaContext {
     pub issue_type: Option<String>,
     pub bead_id: Option<String>,
     pub role: Option<String>,
+    /// Root of the project the session runs in
+    pub project_path: Option<std::path::PathBuf>,
 }
 
 /// Plugin trait for AI personas
@@ -124,7 +131,6 @@ pub struct PersonaContext {
 /// and prompt building logic.
 pub trait PersonaPlugin: Send + Sync {
     /// Returns the persona type
-    #[allow(dead_code)]
     fn persona_type(&self) -> PersonaType;
 
     /// Get the template name based on context
@@ -192,16 +198,23 @@ pub trait PersonaPlugin: Send + Sync {
     }
 }
 
-/// Registry for persona plugins
+/// Thread-safe registry for persona plugins
+///
+/// Like `BackendRegistry`, plugins sit behind an `RwLock` (many readers,
+/// rare writers), so personas can be registered or removed at any time
+/// through a shared reference.
 pub struct PersonaRegistry {
-    personas: HashMap<PersonaType, Arc<dyn PersonaPlugin>>,
+    personas: RwLock<HashMap<PersonaType, Arc<dyn PersonaPlugin>>>,
+    /// User-defined personas by name, replaced whenever they are reloaded
+    custom: RwLock<HashMap<String, Arc<CustomPersona>>>,
 }
 
 impl PersonaRegistry {
     /// Create a new empty registry
     pub fn new() -> Self {
         PersonaRegistry {
-            personas: HashMap::new(),
+            personas: RwLock::new(HashMap::new()),
+            custom: RwLock::new(HashMap::new()),
         }
     }
 
@@ -216,25 +229,56 @@ impl PersonaRegistry {
     pub fn register_defaults(&self) {
         use crate::agent::personas::{ArchitectPersona, CustomerPersona, ProductManagerPersona, QaEngineerPersona, SpecialistPersona};
 
-        // SAFETY: We're using interior mutability pattern similar to BackendRegistry
-        // This is safe because registration only happens during initialization
-        unsafe {
-            let personas_ptr = &self.personas as *const HashMap<PersonaType, Arc<dyn PersonaPlugin>>
-                as *mut HashMap<PersonaType, Arc<dyn PersonaPlugin>>;
-            (*personas_ptr).insert(
-                PersonaType::ProductManager,
-                Arc::new(ProductManagerPersona::new()),
-            );
-            (*personas_ptr).insert(PersonaType::QaEngineer, Arc::new(QaEngineerPersona::new()));
-            (*personas_ptr).insert(PersonaType::Specialist, Arc::new(SpecialistPersona::new()));
-            (*personas_ptr).insert(PersonaType::Architect, Arc::new(ArchitectPersona::new()));
-            (*personas_ptr).insert(PersonaType::Customer, Arc::new(CustomerPersona::new()));
-        }
+        self.register(Arc::new(ProductManagerPersona::new()));
+        self.register(Arc::new(QaEngineerPersona::new()));
+        self.register(Arc::new(SpecialistPersona::new()));
+        self.register(Arc::new(ArchitectPersona::new()));
+        self.register(Arc::new(CustomerPersona::new()));
+    }
+
+    /// Register a persona plugin under its persona type, replacing any
+    /// plugin already registered for that type
+    pub fn register(&self, persona: Arc<dyn PersonaPlugin>) {
+        let mut personas = self.personas.write().unwrap();
+        personas.insert(persona.persona_type(), persona);
+    }
+
+    /// Remove the plugin registered for a persona type, returning it
+    #[allow(dead_code)]
+    pub fn unregister(&self, persona_type: PersonaType) -> Option<Arc<dyn PersonaPlugin>> {
+        let mut personas = self.personas.write().unwrap();
+        personas.remove(&persona_type)
     }
 
     /// Get a persona plugin by type
     pub fn get(&self, persona_type: PersonaType) -> Option<Arc<dyn PersonaPlugin>> {
-        self.personas.get(&persona_type).cloned()
+        let personas = self.personas.read().unwrap();
+        personas.get(&persona_type).cloned()
+    }
+
+    /// Check if a persona type is registered
+    #[allow(dead_code)]
+    pub fn has_persona(&self, persona_type: PersonaType) -> bool {
+        let personas = self.personas.read().unwrap();
+        personas.contains_key(&persona_type)
+    }
+
+    /// Replace the user-defined personas
+    pub fn set_custom(&self, personas: Vec<CustomPersona>) {
+        let mut custom = self.custom.write().unwrap();
+        *custom = personas.into_iter().map(|p| (p.name.clone(), Arc::new(p))).collect();
+    }
+
+    /// Get a user-defined persona by name
+    pub fn get_custom(&self, name: &str) -> Option<Arc<CustomPersona>> {
+        self.custom.read().unwrap().get(name).cloned()
+    }
+
+    /// All user-defined personas, sorted by name
+    pub fn list_custom(&self) -> Vec<Arc<CustomPersona>> {
+        let mut personas: Vec<_> = self.custom.read().unwrap().values().cloned().collect();
+        personas.sort_by(|a, b| a.name.cmp(&b.name));
+        personas
     }
 }
 
@@ -243,3 +287,44 @@ impl Default for PersonaRegistry {
         Self::with_defaults()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::personas::{QaEngineerPersona, SpecialistPersona};
+
+    #[test]
+    fn test_with_defaults_registers_personas() {
+        let registry = PersonaRegistry::with_defaults();
+        for persona_type in [
+            PersonaType::ProductManager,
+            PersonaType::QaEngineer,
+            PersonaType::Specialist,
+            PersonaType::Architect,
+            PersonaType::Customer,
+        ] {
+            assert_eq!(registry.get(persona_type).unwrap().persona_type(), persona_type);
+        }
+        assert!(!registry.has_persona(PersonaType::Custom));
+    }
+
+    #[test]
+    fn test_register_and_unregister_after_init() {
+        let registry = Arc::new(PersonaRegistry::new());
+        assert!(registry.get(PersonaType::Specialist).is_none());
+
+        // Registration works through a shared reference from any thread
+        let shared = Arc::clone(&registry);
+        std::thread::spawn(move || shared.register(Arc::new(SpecialistPersona::new())))
+            .join()
+            .unwrap();
+        registry.register(Arc::new(QaEngineerPersona::new()));
+        assert!(registry.has_persona(PersonaType::Specialist));
+
+        let removed = registry.unregister(PersonaType::Specialist).unwrap();
+        assert_eq!(removed.persona_type(), PersonaType::Specialist);
+        assert!(registry.get(PersonaType::Specialist).is_none());
+        assert!(registry.unregister(PersonaType::Specialist).is_none());
+        assert!(registry.has_persona(PersonaType::QaEngineer));
+    }
+}
@@ -13,19 +13,19 @@ impl ArchitectPersona {
 
     /// Load project tech stack context from common configuration files
     ///
-    /// Searches for and reads:
+    /// Searches the session's project root (falling back to the crate root) for and reads:
     /// - Cargo.toml (Rust dependencies)
     /// - package.json (Node/JS dependencies)
     /// - pubspec.yaml (Flutter/Dart dependencies)
     ///
     /// Returns a formatted string with the tech stack information
-    pub fn load_tech_stack_context(&self) -> String {
+    pub fn load_tech_stack_context(&self, project_path: Option<&Path>) -> String {
         let mut context = String::from("\n## Tech Stack Context\n\n");
         let mut found_any = false;
 
         // Try to find project root by looking for common files
         let project_roots = vec![
-            std::env::current_dir().ok(),
+            project_path.map(Path::to_path_buf),
             std::env::var("CARGO_MANIFEST_DIR").ok().map(|p| Path::new(&p).to_path_buf()),
         ];
 
@@ -152,7 +152,7 @@ impl PersonaPlugin for ArchitectPersona {
         let mut prompt = String::new();
 
         // Add tech stack context for architectural decisions
-        prompt.push_str(&self.load_tech_stack_context());
+        prompt.push_str(&self.load_tech_stack_context(context.project_path.as_deref()));
         prompt.push_str("\n---\n\n");
 
         // Add the template content
@@ -186,6 +186,7 @@ mod tests {
             issue_type: Some("epic".to_string()),
             bead_id: Some("bp6-123".to_string()),
             role: None,
+            project_path: None,
         };
 
         let template_name = persona.get_template_name(&context).unwrap();
@@ -200,6 +201,7 @@ mod tests {
             issue_type: None,
             bead_id: Some("bp6-123".to_string()),
             role: None,
+            project_path: None,
         };
 
         let template_name = persona.get_template_name(&context).unwrap();
@@ -214,6 +216,7 @@ mod tests {
             issue_type: None,
             bead_id: Some("bp6-123".to_string()),
             role: None,
+            project_path: None,
         };
 
         let template_name = persona.get_template_name(&context).unwrap();
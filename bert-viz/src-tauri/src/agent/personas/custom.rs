@@ -0,0 +1,304 @@
+//! User-defined personas loaded from `~/.bp6/personas`
+//!
+//! Every directory `~/.bp6/personas/<name>/` is a persona named after the
+//! directory. Its `*.md` files are the templates, picked by the session's
+//! task, and an optional `persona.json` manifest describes it:
+//!
+//! ```json
+//! { "description": "Reviews changes", "tasks": ["review", "chat"], "defaultTask": "chat", "backend": "claude" }
+//! ```
+//!
+//! `tasks` limits the templates that may be used (all of them when empty) and
+//! `backend` is used when a session doesn't ask for one. Directories named
+//! after a built-in persona are ignored. The directory is watched, and the
+//! personas are reloaded whenever anything in it changes.
+
+use notify::{Config, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::agent::persona::{PersonaContext, PersonaPlugin, PersonaType};
+use crate::agent::plugin::BackendId;
+
+/// Manifest file inside a persona directory
+const MANIFEST_FILE: &str = "persona.json";
+
+/// Names that can't be taken by user personas
+const BUILT_IN_NAMES: &[&str] = &["specialist", "product-manager", "qa-engineer", "architect", "customer"];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PersonaManifest {
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Tasks (template names) sessions may use; empty allows every template
+    #[serde(default)]
+    pub tasks: Vec<String>,
+    /// Task used when a session doesn't name one
+    #[serde(default)]
+    pub default_task: Option<String>,
+    /// Backend used when a session doesn't name one
+    #[serde(default)]
+    pub backend: Option<BackendId>,
+}
+
+/// A persona defined by a directory of templates
+#[derive(Debug, Clone)]
+pub struct CustomPersona {
+    pub name: String,
+    pub manifest: PersonaManifest,
+    /// Template names (file stems), sorted
+    pub templates: Vec<String>,
+}
+
+impl CustomPersona {
+    /// Load the persona in `dir`
+    pub fn load(dir: &Path) -> Result<Self, String> {
+        let name = dir
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| format!("Invalid persona directory {}", dir.display()))?
+            .to_string();
+
+        let manifest_path = dir.join(MANIFEST_FILE);
+        let manifest = if manifest_path.exists() {
+            let content = fs::read_to_string(&manifest_path).map_err(|e| format!("Failed to read {}: {}", manifest_path.display(), e))?;
+            serde_json::from_str(&content).map_err(|e| format!("Invalid {}: {}", manifest_path.display(), e))?
+        } else {
+            PersonaManifest::default()
+        };
+
+        let mut templates: Vec<String> = fs::read_dir(dir)
+            .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().and_then(|s| s.to_str()) == Some("md"))
+            .filter_map(|path| path.file_stem().and_then(|s| s.to_str()).map(String::from))
+            .collect();
+        templates.sort();
+
+        if templates.is_empty() {
+            return Err(format!("Persona '{}' has no templates", name));
+        }
+        if let Some(missing) = manifest.tasks.iter().find(|task| !templates.contains(task)) {
+            return Err(format!("Persona '{}' lists task '{}' but has no {}.md", name, missing, missing));
+        }
+
+        Ok(CustomPersona { name, manifest, templates })
+    }
+
+    /// Tasks sessions may use
+    pub fn tasks(&self) -> &[String] {
+        if self.manifest.tasks.is_empty() {
+            &self.templates
+        } else {
+            &self.manifest.tasks
+        }
+    }
+}
+
+impl PersonaPlugin for CustomPersona {
+    fn persona_type(&self) -> PersonaType {
+        PersonaType::Custom
+    }
+
+    fn get_template_name(&self, context: &PersonaContext) -> Result<String, String> {
+        let tasks = self.tasks();
+        let task = context
+            .task
+            .as_deref()
+            .or(self.manifest.default_task.as_deref())
+            .or_else(|| (tasks.len() == 1).then(|| tasks[0].as_str()))
+            .ok_or_else(|| format!("Persona '{}' needs a task: one of {}", self.name, tasks.join(", ")))?;
+
+        if !tasks.iter().any(|t| t == task) {
+            return Err(format!("Persona '{}' does not support task '{}' (one of {})", self.name, task, tasks.join(", ")));
+        }
+        Ok(task.to_string())
+    }
+}
+
+/// `~/.bp6/personas`
+pub fn personas_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".bp6").join("personas"))
+}
+
+/// Load every persona under `root`, skipping invalid ones and built-in names
+pub fn load_all(root: &Path) -> Vec<CustomPersona> {
+    let Ok(entries) = fs::read_dir(root) else { return Vec::new() };
+
+    let mut personas: Vec<CustomPersona> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .filter_map(|dir| match CustomPersona::load(&dir) {
+            Ok(persona) if BUILT_IN_NAMES.contains(&persona.name.as_str()) => {
+                eprintln!("⚠️  Ignoring user persona '{}': the name is taken by a built-in persona", persona.name);
+                None
+            }
+            Ok(persona) => Some(persona),
+            Err(e) => {
+                eprintln!("⚠️  Skipping user persona: {}", e);
+                None
+            }
+        })
+        .collect();
+    personas.sort_by(|a, b| a.name.cmp(&b.name));
+    personas
+}
+
+/// Reload the user personas into the agent state's registry
+fn reload(app_handle: &AppHandle, root: &Path) {
+    let Some(state) = app_handle.try_state::<crate::agent::AgentState>() else { return };
+    let personas = load_all(root);
+    eprintln!("🎭 Loaded {} user persona(s) from {}", personas.len(), root.display());
+    state.persona_registry.set_custom(personas);
+    let _ = app_handle.emit("personas-changed", ());
+}
+
+/// Load the user personas and reload them whenever `~/.bp6/personas` changes
+pub fn watch(app_handle: AppHandle) {
+    let Some(root) = personas_dir() else { return };
+    if let Err(e) = fs::create_dir_all(&root) {
+        eprintln!("⚠️  Failed to create {}: {}", root.display(), e);
+        return;
+    }
+    reload(&app_handle, &root);
+
+    let last_reload = Arc::new(Mutex::new(Instant::now()));
+    let watch_root = root.clone();
+    let watcher = notify::RecommendedWatcher::new(
+        move |res: std::result::Result<notify::Event, notify::Error>| match res {
+            Ok(_) => {
+                let mut last = last_reload.lock().unwrap();
+                let now = Instant::now();
+                if now.duration_since(*last) >= Duration::from_millis(200) {
+                    *last = now;
+                    // Let editors finish writing before reading the templates
+                    std::thread::sleep(Duration::from_millis(50));
+                    reload(&app_handle, &watch_root);
+                }
+            }
+            Err(e) => eprintln!("⚠️  Persona watch error: {:?}", e),
+        },
+        Config::default(),
+    );
+
+    match watcher {
+        Ok(mut watcher) => {
+            if let Err(e) = watcher.watch(&root, RecursiveMode::Recursive) {
+                eprintln!("⚠️  Failed to watch {}: {}", root.display(), e);
+                return;
+            }
+            // Watch for the lifetime of the app
+            std::mem::forget(watcher);
+        }
+        Err(e) => eprintln!("⚠️  Failed to create persona watcher: {}", e),
+    }
+}
+
+/// A persona sessions can be started with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersonaInfo {
+    pub name: String,
+    pub built_in: bool,
+    pub description: Option<String>,
+    pub tasks: Vec<String>,
+    pub backend: Option<BackendId>,
+}
+
+/// Built-in and user-defined personas selectable in `start_agent_session`
+#[tauri::command]
+pub fn list_personas(state: tauri::State<'_, crate::agent::AgentState>) -> Vec<PersonaInfo> {
+    let built_in = ["specialist", "product-manager", "qa-engineer"].into_iter().map(|name| PersonaInfo {
+        name: name.to_string(),
+        built_in: true,
+        description: None,
+        tasks: state.template_loader.list_templates(name).unwrap_or_default(),
+        backend: None,
+    });
+    let custom = state.persona_registry.list_custom().into_iter().map(|p| PersonaInfo {
+        name: p.name.clone(),
+        built_in: false,
+        description: p.manifest.description.clone(),
+        tasks: p.tasks().to_vec(),
+        backend: p.manifest.backend,
+    });
+    built_in.chain(custom).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn context(task: Option<&str>) -> PersonaContext {
+        PersonaContext {
+            task: task.map(String::from),
+            issue_type: None,
+            bead_id: None,
+            role: None,
+            project_path: None,
+        }
+    }
+
+    #[test]
+    fn test_load_all_reads_manifest_and_skips_invalid() {
+        let root = TempDir::new().unwrap();
+        let reviewer = root.path().join("reviewer");
+        fs::create_dir_all(&reviewer).unwrap();
+        fs::write(reviewer.join("review.md"), "Review {{feature_id}}").unwrap();
+        fs::write(reviewer.join("chat.md"), "Chat").unwrap();
+        fs::write(reviewer.join("notes.txt"), "not a template").unwrap();
+        fs::write(
+            reviewer.join(MANIFEST_FILE),
+            r#"{"description": "Reviews changes", "tasks": ["review"], "backend": "claude"}"#,
+        )
+        .unwrap();
+
+        // No templates, a manifest naming a missing template, a built-in name
+        fs::create_dir_all(root.path().join("empty")).unwrap();
+        let broken = root.path().join("broken");
+        fs::create_dir_all(&broken).unwrap();
+        fs::write(broken.join("chat.md"), "Chat").unwrap();
+        fs::write(broken.join(MANIFEST_FILE), r#"{"tasks": ["review"]}"#).unwrap();
+        let shadow = root.path().join("specialist");
+        fs::create_dir_all(&shadow).unwrap();
+        fs::write(shadow.join("web.md"), "Web").unwrap();
+
+        let personas = load_all(root.path());
+        assert_eq!(personas.len(), 1);
+        let reviewer = &personas[0];
+        assert_eq!(reviewer.name, "reviewer");
+        assert_eq!(reviewer.templates, vec!["chat", "review"]);
+        assert_eq!(reviewer.tasks(), ["review"]);
+        assert_eq!(reviewer.manifest.backend, Some(BackendId::ClaudeCode));
+    }
+
+    #[test]
+    fn test_template_selection() {
+        let persona = CustomPersona {
+            name: "writer".to_string(),
+            manifest: PersonaManifest {
+                default_task: Some("draft".to_string()),
+                ..PersonaManifest::default()
+            },
+            templates: vec!["draft".to_string(), "edit".to_string()],
+        };
+        assert_eq!(persona.get_template_name(&context(None)).unwrap(), "draft");
+        assert_eq!(persona.get_template_name(&context(Some("edit"))).unwrap(), "edit");
+        assert!(persona.get_template_name(&context(Some("publish"))).is_err());
+
+        let single = CustomPersona {
+            name: "single".to_string(),
+            manifest: PersonaManifest::default(),
+            templates: vec!["only".to_string()],
+        };
+        assert_eq!(single.get_template_name(&context(None)).unwrap(), "only");
+    }
+}
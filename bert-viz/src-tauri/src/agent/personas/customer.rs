@@ -60,6 +60,7 @@ mod tests {
             issue_type: None,
             bead_id: None,
             role: None,
+            project_path: None,
         };
 
         let template_name = persona.get_template_name(&context).unwrap();
@@ -74,6 +75,7 @@ mod tests {
             issue_type: None,
             bead_id: None,
             role: None,
+            project_path: None,
         };
 
         let template_name = persona.get_template_name(&context).unwrap();
@@ -88,6 +90,7 @@ mod tests {
             issue_type: None,
             bead_id: None,
             role: None,
+            project_path: None,
         };
 
         let template_name = persona.get_template_name(&context).unwrap();
@@ -102,6 +105,7 @@ mod tests {
             issue_type: None,
             bead_id: None,
             role: None,
+            project_path: None,
         };
 
         let template_name = persona.get_template_name(&context).unwrap();
@@ -1,10 +1,12 @@
 /// Persona implementations
 pub mod architect;
+pub mod custom;
 pub mod customer;
 pub mod product_manager;
 pub mod qa_engineer;
 pub mod specialist;
 pub use architect::ArchitectPersona;
+pub use custom::CustomPersona;
 pub use customer::CustomerPersona;
 pub use product_manager::ProductManagerPersona;
 pub use qa_engineer::QaEngineerPersona;
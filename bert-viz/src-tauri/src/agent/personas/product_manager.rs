@@ -33,6 +33,9 @@ impl PersonaPlugin for ProductManagerPersona {
             ("implement", "feature") => "implement-feature",
             ("implement", _) => "implement-task",
 
+            // Estimate unestimated beads listed in the context JSON
+            ("estimate", _) => "estimate",
+
             // Chat template
             ("chat", _) => "chat",
 
@@ -66,6 +69,7 @@ mod tests {
             issue_type: Some("epic".to_string()),
             bead_id: Some("bp6-123".to_string()),
             role: None,
+            project_path: None,
         };
 
         let template_name = persona.get_template_name(&context).unwrap();
@@ -80,6 +84,7 @@ mod tests {
             issue_type: Some("feature".to_string()),
             bead_id: Some("bp6-123".to_string()),
             role: None,
+            project_path: None,
         };
 
         let template_name = persona.get_template_name(&context).unwrap();
@@ -94,6 +99,7 @@ mod tests {
             issue_type: Some("feature".to_string()),
             bead_id: Some("bp6-123".to_string()),
             role: None,
+            project_path: None,
         };
 
         let template_name = persona.get_template_name(&context).unwrap();
@@ -108,6 +114,7 @@ mod tests {
             issue_type: Some("task".to_string()),
             bead_id: Some("bp6-123".to_string()),
             role: None,
+            project_path: None,
         };
 
         let template_name = persona.get_template_name(&context).unwrap();
@@ -122,9 +129,25 @@ mod tests {
             issue_type: None,
             bead_id: Some("bp6-123".to_string()),
             role: None,
+            project_path: None,
         };
 
         let template_name = persona.get_template_name(&context).unwrap();
         assert_eq!(template_name, "chat");
     }
+
+    #[test]
+    fn test_estimate() {
+        let persona = ProductManagerPersona::new();
+        let context = PersonaContext {
+            task: Some("estimate".to_string()),
+            issue_type: None,
+            bead_id: None,
+            role: None,
+            project_path: None,
+        };
+
+        let template_name = persona.get_template_name(&context).unwrap();
+        assert_eq!(template_name, "estimate");
+    }
 }
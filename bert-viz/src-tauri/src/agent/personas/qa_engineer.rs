@@ -21,6 +21,7 @@ impl PersonaPlugin for QaEngineerPersona {
         // Select template based on task, default to chat for interactive mode
         let template_name = match task {
             Some("fix_dependencies") => "fix-dependencies",
+            Some("triage_stale") => "triage-stale",
             Some(_) | None => "chat", // Default to interactive chat mode
         };
 
@@ -40,12 +41,28 @@ mod tests {
             issue_type: None,
             bead_id: None,
             role: None,
+            project_path: None,
         };
 
         let template_name = persona.get_template_name(&context).unwrap();
         assert_eq!(template_name, "fix-dependencies");
     }
 
+    #[test]
+    fn test_triage_stale() {
+        let persona = QaEngineerPersona::new();
+        let context = PersonaContext {
+            task: Some("triage_stale".to_string()),
+            issue_type: None,
+            bead_id: None,
+            role: None,
+            project_path: None,
+        };
+
+        let template_name = persona.get_template_name(&context).unwrap();
+        assert_eq!(template_name, "triage-stale");
+    }
+
     #[test]
     fn test_default_template_is_chat() {
         let persona = QaEngineerPersona::new();
@@ -54,6 +71,7 @@ mod tests {
             issue_type: None,
             bead_id: None,
             role: None,
+            project_path: None,
         };
 
         let template_name = persona.get_template_name(&context).unwrap();
@@ -44,6 +44,7 @@ mod tests {
             issue_type: None,
             bead_id: Some("bp6-123".to_string()),
             role: Some("web".to_string()),
+            project_path: None,
         };
 
         let template_name = persona.get_template_name(&context).unwrap();
@@ -58,6 +59,7 @@ mod tests {
             issue_type: None,
             bead_id: Some("bp6-123".to_string()),
             role: Some("flutter".to_string()),
+            project_path: None,
         };
 
         let template_name = persona.get_template_name(&context).unwrap();
@@ -72,6 +74,7 @@ mod tests {
             issue_type: None,
             bead_id: Some("bp6-123".to_string()),
             role: Some("rust-tauri".to_string()),
+            project_path: None,
         };
 
         let template_name = persona.get_template_name(&context).unwrap();
@@ -86,6 +89,7 @@ mod tests {
             issue_type: None,
             bead_id: Some("bp6-123".to_string()),
             role: Some("rust".to_string()),
+            project_path: None,
         };
 
         let template_name = persona.get_template_name(&context).unwrap();
@@ -100,6 +104,7 @@ mod tests {
             issue_type: None,
             bead_id: Some("bp6-123".to_string()),
             role: Some("supabase-edge".to_string()),
+            project_path: None,
         };
 
         let template_name = persona.get_template_name(&context).unwrap();
@@ -114,6 +119,7 @@ mod tests {
             issue_type: None,
             bead_id: Some("bp6-123".to_string()),
             role: None,
+            project_path: None,
         };
 
         let template_name = persona.get_template_name(&context).unwrap();
@@ -8,6 +8,8 @@
 /// and registered dynamically.
 use serde::{Deserialize, Serialize};
 
+use crate::settings::AgentSafetyPolicy;
+
 /// Type-safe identifier for CLI backends
 ///
 /// Used for registry lookup and configuration. Each variant corresponds
@@ -20,6 +22,17 @@ pub enum BackendId {
     /// Anthropic Claude Code CLI backend
     #[serde(rename = "claude")]
     ClaudeCode,
+    /// Local Ollama server (HTTP backend)
+    Ollama,
+}
+
+/// How a backend is driven
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackendTransport {
+    /// A CLI process is spawned per message (`CliBackendPlugin`)
+    Cli,
+    /// A streaming HTTP request is made per message (`HttpBackendPlugin`)
+    Http,
 }
 
 impl BackendId {
@@ -28,6 +41,15 @@ impl BackendId {
         match self {
             BackendId::Gemini => "Gemini",
             BackendId::ClaudeCode => "Claude Code",
+            BackendId::Ollama => "Ollama",
+        }
+    }
+
+    /// Returns how this backend is driven
+    pub fn transport(&self) -> BackendTransport {
+        match self {
+            BackendId::Gemini | BackendId::ClaudeCode => BackendTransport::Cli,
+            BackendId::Ollama => BackendTransport::Http,
         }
     }
 }
@@ -52,6 +74,56 @@ pub struct AgentChunk {
     /// Serializes as "sessionId" in JSON
     #[serde(skip_serializing_if = "Option::is_none")]
     pub session_id: Option<String>,
+    /// Token usage reported by the CLI, set on the final chunk of a run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<TokenUsage>,
+}
+
+/// A tool call reported in a CLI backend's output
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ToolUse {
+    /// Tool name as the CLI reports it (e.g. "Edit", "write_file")
+    pub name: String,
+    pub input: serde_json::Value,
+}
+
+/// Token counts and cost reported by a CLI backend
+///
+/// Backends report usage once per CLI run (in their result event). Counts
+/// add up across runs; the cost stays `None` for backends that don't report
+/// one (Gemini only reports tokens).
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Input tokens served from the prompt cache
+    #[serde(default)]
+    pub cache_read_tokens: u64,
+    /// Input tokens written to the prompt cache
+    #[serde(default)]
+    pub cache_creation_tokens: u64,
+    /// Cost in US dollars, if the backend reports it
+    #[serde(default)]
+    pub cost_usd: Option<f64>,
+}
+
+impl TokenUsage {
+    /// Add another run's usage to this total
+    pub fn add(&mut self, other: &TokenUsage) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cache_read_tokens += other.cache_read_tokens;
+        self.cache_creation_tokens += other.cache_creation_tokens;
+        self.cost_usd = match (self.cost_usd, other.cost_usd) {
+            (None, None) => None,
+            (a, b) => Some(a.unwrap_or(0.0) + b.unwrap_or(0.0)),
+        };
+    }
+
+    pub fn total_tokens(&self) -> u64 {
+        self.input_tokens + self.output_tokens + self.cache_read_tokens + self.cache_creation_tokens
+    }
 }
 
 /// Plugin trait for CLI backend implementations
@@ -101,12 +173,14 @@ pub struct AgentChunk {
 ///                 content: content.to_string(),
 ///                 is_done: false,
 ///                 session_id: None,
+///                 usage: None,
 ///             })
 ///         } else if json["type"] == "result" {
 ///             Some(AgentChunk {
 ///                 content: String::new(),
 ///                 is_done: true,
 ///                 session_id: None,
+///                 usage: None,
 ///             })
 ///         } else {
 ///             None
@@ -133,12 +207,13 @@ pub trait CliBackendPlugin: Send + Sync {
     /// * `prompt` - The prompt text to send to the agent
     /// * `resume` - Whether to resume the previous session
     /// * `session_id` - Optional session ID for resume (required for some backends)
+    /// * `policy` - Which tools the CLI may run without asking
     ///
     /// # Returns
     ///
     /// A vector of command-line arguments to pass to the CLI binary.
     /// The command name itself should NOT be included.
-    fn build_args(&self, prompt: &str, resume: bool, session_id: Option<&str>) -> Vec<String>;
+    fn build_args(&self, prompt: &str, resume: bool, session_id: Option<&str>, policy: AgentSafetyPolicy) -> Vec<String>;
 
     /// Parses a line of JSON output from the CLI's stdout
     ///
@@ -155,4 +230,81 @@ pub trait CliBackendPlugin: Send + Sync {
     /// * `Some(AgentChunk)` if this line contains parseable content or completion signal
     /// * `None` if this line should be ignored (e.g., non-message JSON)
     fn parse_stdout_line(&self, json: &serde_json::Value) -> Option<AgentChunk>;
+
+    /// Arguments that let the CLI make the calls `rules` describe (e.g.
+    /// `Edit` or `Bash(cargo test)`, see `ToolGrant::rule`) without asking,
+    /// whatever the policy passed to `build_args`. They come before the
+    /// other arguments. The default grants nothing.
+    fn allow_tools_args(&self, _rules: &[String]) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Tool calls requested in a line of stdout, for the safety policy and
+    /// the audit log. The default reports none.
+    fn parse_tool_uses(&self, _json: &serde_json::Value) -> Vec<ToolUse> {
+        Vec::new()
+    }
+
+    /// Builds the arguments for running the CLI interactively in a terminal
+    ///
+    /// # Arguments
+    ///
+    /// * `session_id` - CLI session to resume, if any
+    ///
+    /// The default starts a fresh interactive session.
+    fn build_interactive_args(&self, _session_id: Option<&str>) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// One turn of a conversation sent to an HTTP backend
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ChatMessage {
+    /// "user" or "assistant"
+    pub role: String,
+    pub content: String,
+}
+
+impl ChatMessage {
+    pub fn user(content: &str) -> Self {
+        ChatMessage { role: "user".to_string(), content: content.to_string() }
+    }
+
+    pub fn assistant(content: &str) -> Self {
+        ChatMessage { role: "assistant".to_string(), content: content.to_string() }
+    }
+}
+
+/// Plugin trait for backends reached over HTTP instead of a spawned CLI
+///
+/// HTTP backends (e.g. a local Ollama server) keep no conversation state
+/// between requests, so every request carries the whole conversation. The
+/// response body is read as newline-delimited JSON, and each line is parsed
+/// into the same `AgentChunk`s the CLI backends produce, so sessions, logging
+/// and `agent-chunk` events work unchanged.
+///
+/// # Thread Safety
+///
+/// Implementations must be Send + Sync to support concurrent agent sessions.
+pub trait HttpBackendPlugin: Send + Sync {
+    /// Short backend name used in session logs (e.g., "ollama")
+    fn name(&self) -> &str;
+
+    /// URL the request is POSTed to
+    fn endpoint(&self) -> String;
+
+    /// Builds the JSON request body for a streamed reply to `messages`
+    ///
+    /// # Arguments
+    ///
+    /// * `messages` - The whole conversation so far, ending with the new user message
+    fn build_request(&self, messages: &[ChatMessage]) -> serde_json::Value;
+
+    /// Parses one line of the streamed response body
+    ///
+    /// # Returns
+    ///
+    /// * `Some(AgentChunk)` if this line contains content, an error or the completion signal
+    /// * `None` if this line should be ignored
+    fn parse_stream_line(&self, json: &serde_json::Value) -> Option<AgentChunk>;
 }
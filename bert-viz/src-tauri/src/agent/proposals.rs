@@ -0,0 +1,304 @@
+//! Queue of agent-proposed `bd` commands awaiting approval
+//!
+//! Agents suggest changes to the plan as `bd ...` commands in their replies.
+//! Instead of leaving the UI to scrape them out of rendered HTML, the session
+//! reader feeds every chunk into `ProposalQueue`, which buffers each reply and,
+//! once it completes, extracts the commands from fenced code blocks (and inline
+//! code spans) as pending proposals tied to the session and bead. The user
+//! then approves or rejects them individually or per session.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::SystemTime;
+use uuid::Uuid;
+
+/// Resolved proposals kept for display before the oldest are dropped
+const MAX_RESOLVED: usize = 200;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ProposalStatus {
+    Pending,
+    Approved,
+    Rejected,
+    /// Approved, but the command failed
+    Failed,
+}
+
+/// A `bd` command proposed by an agent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Proposal {
+    pub id: String,
+    pub session_id: String,
+    pub bead_id: Option<String>,
+    pub command: String,
+    /// When the proposal was made (seconds since UNIX epoch)
+    pub created_at: u64,
+    pub status: ProposalStatus,
+    /// Command output (approved) or error message (failed)
+    pub result: Option<String>,
+}
+
+/// Pending and recently resolved proposals, plus the reply currently being
+/// streamed by each session
+#[derive(Default)]
+pub struct ProposalQueue {
+    proposals: Mutex<Vec<Proposal>>,
+    buffers: Mutex<HashMap<String, String>>,
+}
+
+impl ProposalQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed one streamed chunk. When the reply is complete, its commands are
+    /// queued and the newly added proposals are returned.
+    pub fn ingest_chunk(&self, session_id: &str, bead_id: Option<&str>, content: &str, is_done: bool) -> Vec<Proposal> {
+        let reply = {
+            let mut buffers = self.buffers.lock().unwrap();
+            let buffer = buffers.entry(session_id.to_string()).or_default();
+            buffer.push_str(content);
+            if !is_done {
+                return Vec::new();
+            }
+            buffers.remove(session_id).unwrap_or_default()
+        };
+
+        let created_at = SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let mut proposals = self.proposals.lock().unwrap();
+        let mut added = Vec::new();
+        for command in extract_bd_commands(&reply) {
+            // The same command is often repeated in a summary; queue it once
+            let duplicate = proposals.iter().any(|p| {
+                p.session_id == session_id && p.status == ProposalStatus::Pending && p.command == command
+            });
+            if duplicate {
+                continue;
+            }
+            let proposal = Proposal {
+                id: Uuid::new_v4().to_string(),
+                session_id: session_id.to_string(),
+                bead_id: bead_id.map(String::from),
+                command,
+                created_at,
+                status: ProposalStatus::Pending,
+                result: None,
+            };
+            proposals.push(proposal.clone());
+            added.push(proposal);
+        }
+        added
+    }
+
+    /// Queue a command suggested outside an agent reply (e.g. by a heuristic)
+    /// under the pseudo session `source`. Returns the pending proposal for the
+    /// command and whether it was added; a pending duplicate is returned as is.
+    pub fn propose(&self, source: &str, bead_id: Option<&str>, command: &str) -> (Proposal, bool) {
+        let mut proposals = self.proposals.lock().unwrap();
+        let pending = proposals.iter().find(|p| {
+            p.session_id == source && p.status == ProposalStatus::Pending && p.command == command
+        });
+        if let Some(existing) = pending {
+            return (existing.clone(), false);
+        }
+
+        let proposal = Proposal {
+            id: Uuid::new_v4().to_string(),
+            session_id: source.to_string(),
+            bead_id: bead_id.map(String::from),
+            command: command.to_string(),
+            created_at: SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            status: ProposalStatus::Pending,
+            result: None,
+        };
+        proposals.push(proposal.clone());
+        (proposal, true)
+    }
+
+    /// Pending proposals, oldest first, optionally for one session
+    pub fn pending(&self, session_id: Option<&str>) -> Vec<Proposal> {
+        self.proposals
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|p| p.status == ProposalStatus::Pending)
+            .filter(|p| session_id.map(|s| p.session_id == s).unwrap_or(true))
+            .cloned()
+            .collect()
+    }
+
+    pub fn get(&self, id: &str) -> Option<Proposal> {
+        self.proposals.lock().unwrap().iter().find(|p| p.id == id).cloned()
+    }
+
+    /// Record the outcome of a proposal and return the updated proposal
+    pub fn resolve(&self, id: &str, status: ProposalStatus, result: Option<String>) -> Option<Proposal> {
+        let mut proposals = self.proposals.lock().unwrap();
+        let proposal = proposals.iter_mut().find(|p| p.id == id)?;
+        proposal.status = status;
+        proposal.result = result;
+        let updated = proposal.clone();
+
+        let resolved = proposals.iter().filter(|p| p.status != ProposalStatus::Pending).count();
+        if resolved > MAX_RESOLVED {
+            let mut to_drop = resolved - MAX_RESOLVED;
+            proposals.retain(|p| {
+                if to_drop > 0 && p.status != ProposalStatus::Pending {
+                    to_drop -= 1;
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+        Some(updated)
+    }
+
+    /// Forget a session's partially streamed reply (session terminated)
+    pub fn clear_buffer(&self, session_id: &str) {
+        self.buffers.lock().unwrap().remove(session_id);
+    }
+}
+
+/// `bd` commands in a reply: every `bd ...` line of a fenced code block and
+/// every inline code span starting with `bd `, in order of appearance
+pub fn extract_bd_commands(reply: &str) -> Vec<String> {
+    let mut commands: Vec<String> = Vec::new();
+    let mut push = |command: &str| {
+        let command = command.trim().trim_start_matches("$ ").trim();
+        if command.starts_with("bd ") && !commands.iter().any(|c| c == command) {
+            commands.push(command.to_string());
+        }
+    };
+
+    let mut in_fence = false;
+    for line in reply.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            push(trimmed);
+            continue;
+        }
+
+        // Inline code spans: `bd ...`
+        let mut rest = trimmed;
+        while let Some(start) = rest.find('`') {
+            let after = &rest[start + 1..];
+            let Some(end) = after.find('`') else { break };
+            push(&after[..end]);
+            rest = &after[end + 1..];
+        }
+    }
+    commands
+}
+
+/// Split a command line into arguments, honouring single and double quotes
+/// (agents quote titles and descriptions: `bd create "Fix login" -p 1`)
+pub fn split_command_args(command: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+    let mut chars = command.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            (Some(_), c) => current.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+
+    if quote.is_some() {
+        return Err(format!("Unterminated quote in command: {}", command));
+    }
+    if in_arg {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extracts_fenced_and_inline_commands() {
+        let reply = "Run these:\n```bash\n$ bd create \"Login page\" -p 1\nbd dep add bp6-2 bp6-1\necho hi\n```\nThen `bd close bp6-3` and `ls`.\nAgain: `bd close bp6-3`";
+        assert_eq!(
+            extract_bd_commands(reply),
+            vec!["bd create \"Login page\" -p 1", "bd dep add bp6-2 bp6-1", "bd close bp6-3"]
+        );
+    }
+
+    #[test]
+    fn test_queues_commands_once_reply_completes() {
+        let queue = ProposalQueue::new();
+        assert!(queue.ingest_chunk("s1", Some("bp6-1"), "Try `bd clo", false).is_empty());
+        let added = queue.ingest_chunk("s1", Some("bp6-1"), "se bp6-1`", true);
+        assert_eq!(added.len(), 1);
+        assert_eq!(added[0].command, "bd close bp6-1");
+        assert_eq!(added[0].bead_id.as_deref(), Some("bp6-1"));
+
+        // Repeating the same suggestion doesn't queue it twice
+        assert!(queue.ingest_chunk("s1", None, "`bd close bp6-1`", true).is_empty());
+        assert_eq!(queue.pending(Some("s1")).len(), 1);
+        assert!(queue.pending(Some("s2")).is_empty());
+
+        let resolved = queue.resolve(&added[0].id, ProposalStatus::Rejected, None).unwrap();
+        assert_eq!(resolved.status, ProposalStatus::Rejected);
+        assert!(queue.pending(None).is_empty());
+    }
+
+    #[test]
+    fn test_propose_queues_each_command_once() {
+        let queue = ProposalQueue::new();
+        let (first, added) = queue.propose("heuristic", Some("bp6-2"), "bd dep add bp6-2 bp6-1");
+        assert!(added);
+        let (again, added) = queue.propose("heuristic", Some("bp6-2"), "bd dep add bp6-2 bp6-1");
+        assert!(!added);
+        assert_eq!(again.id, first.id);
+        assert_eq!(queue.pending(Some("heuristic")).len(), 1);
+    }
+
+    #[test]
+    fn test_split_command_args() {
+        assert_eq!(
+            split_command_args(r#"bd create "Fix \"login\" page" -d 'a b' -p 1"#).unwrap(),
+            vec!["bd", "create", "Fix \"login\" page", "-d", "a b", "-p", "1"]
+        );
+        assert_eq!(split_command_args("bd update x --notes ''").unwrap(), vec!["bd", "update", "x", "--notes", ""]);
+        assert!(split_command_args("bd create \"oops").is_err());
+    }
+}
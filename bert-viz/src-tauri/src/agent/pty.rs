@@ -3,14 +3,25 @@
 /// This module provides basic plumbing for spawning PTY processes and
 /// managing their lifecycle. Used for terminal-based agent sessions.
 ///
+/// `pty_spawn_for_session` opens the CLI of an agent session interactively
+/// (resuming its conversation) in a PTY. Its raw output, ANSI escapes
+/// included, is streamed to the frontend as `pty-data` events so a real
+/// terminal can be rendered; `pty-exit` follows when the CLI exits.
+///
 /// Note: tauri-plugin-pty is registered in the Tauri builder and provides
-/// frontend-accessible commands (spawn, write, read, resize, kill). This
-/// module provides backend utilities for direct PTY usage if needed.
+/// frontend-accessible commands (spawn, write, read, resize, kill) for
+/// standalone terminals.
 
+use serde::Serialize;
 use std::collections::HashMap;
 use std::ffi::OsString;
 use std::io::{Read, Write};
 use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::agent::plugin::BackendTransport;
+use crate::agent::session::AgentState;
+use crate::error::BertError;
 
 // Re-export portable-pty types for convenience
 pub use portable_pty::{Child, ChildKiller, CommandBuilder, PtyPair, PtySize};
@@ -21,7 +32,8 @@ pub struct PtySession {
     pub child: Box<dyn Child + Send + Sync>,
     pub child_killer: Box<dyn ChildKiller + Send + Sync>,
     pub writer: Box<dyn Write + Send>,
-    pub reader: Box<dyn Read + Send>,
+    /// `None` once taken for streaming with `take_reader`
+    pub reader: Option<Box<dyn Read + Send>>,
 }
 
 /// PtyManager handles spawning and cleanup of PTY processes
@@ -104,7 +116,7 @@ impl PtyManager {
             child,
             child_killer,
             writer,
-            reader,
+            reader: Some(reader),
         };
 
         // Store in sessions map
@@ -156,9 +168,14 @@ impl PtyManager {
 
         let mut session = session_arc.lock().unwrap();
 
+        let reader = session
+            .reader
+            .as_mut()
+            .ok_or_else(|| format!("PTY output of session {} is being streamed", session_id))?;
+
         // Read up to 4096 bytes
         let mut buf = vec![0u8; 4096];
-        match session.reader.read(&mut buf) {
+        match reader.read(&mut buf) {
             Ok(n) => {
                 buf.truncate(n);
                 Ok(buf)
@@ -167,6 +184,30 @@ impl PtyManager {
         }
     }
 
+    /// Take the reader of a PTY session to stream its output elsewhere
+    pub fn take_reader(&self, session_id: &str) -> Result<Box<dyn Read + Send>, String> {
+        let sessions = self.sessions.lock().unwrap();
+
+        let session_arc = sessions
+            .get(session_id)
+            .ok_or_else(|| format!("PTY session not found: {}", session_id))?;
+
+        let mut session = session_arc.lock().unwrap();
+        session
+            .reader
+            .take()
+            .ok_or_else(|| format!("PTY output of session {} is already being streamed", session_id))
+    }
+
+    /// Remove a PTY session whose process exited, returning its exit code
+    ///
+    /// Returns `None` if the session was already removed (e.g. by `kill`).
+    pub fn reap(&self, session_id: &str) -> Option<u32> {
+        let session_arc = self.sessions.lock().unwrap().remove(session_id)?;
+        let mut session = session_arc.lock().unwrap();
+        session.child.wait().ok().map(|status| status.exit_code())
+    }
+
     /// Resize a PTY session
     ///
     /// # Arguments
@@ -244,6 +285,158 @@ impl Default for PtyManager {
     }
 }
 
+/// Decode the complete UTF-8 prefix of `pending`, keeping a character split
+/// across reads for the next call. Invalid bytes become U+FFFD.
+fn take_utf8(pending: &mut Vec<u8>) -> String {
+    let mut out = String::new();
+    loop {
+        match std::str::from_utf8(pending) {
+            Ok(text) => {
+                out.push_str(text);
+                pending.clear();
+                return out;
+            }
+            Err(e) => {
+                let valid = e.valid_up_to();
+                out.push_str(std::str::from_utf8(&pending[..valid]).unwrap_or_default());
+                match e.error_len() {
+                    // Incomplete character at the end: wait for the rest
+                    None => {
+                        pending.drain(..valid);
+                        return out;
+                    }
+                    Some(len) => {
+                        out.push(char::REPLACEMENT_CHARACTER);
+                        pending.drain(..valid + len);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Payload of `pty-data`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PtyData {
+    pub session_id: String,
+    /// Raw terminal output, escape sequences included
+    pub data: String,
+}
+
+/// Payload of `pty-exit`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PtyExit {
+    pub session_id: String,
+    /// `None` if the terminal was killed
+    pub exit_code: Option<u32>,
+}
+
+/// Emit a PTY's output as `pty-data` events until its process exits
+fn stream_output(app_handle: AppHandle, session_id: String, mut reader: Box<dyn Read + Send>) {
+    let mut buf = [0u8; 4096];
+    let mut pending = Vec::new();
+    loop {
+        match reader.read(&mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => {
+                pending.extend_from_slice(&buf[..n]);
+                let data = take_utf8(&mut pending);
+                if !data.is_empty() {
+                    let _ = app_handle.emit("pty-data", PtyData { session_id: session_id.clone(), data });
+                }
+            }
+        }
+    }
+
+    let exit_code = app_handle.try_state::<AgentState>().and_then(|state| state.pty.reap(&session_id));
+    eprintln!("🖥️  PTY for session {} exited ({:?})", session_id, exit_code);
+    let _ = app_handle.emit("pty-exit", PtyExit { session_id, exit_code });
+}
+
+/// Open an agent session's CLI interactively in a PTY
+///
+/// The CLI resumes the session's conversation in its working directory (the
+/// session's worktree, if it has one). Output arrives as `pty-data` events;
+/// input goes through `pty_write`. Sessions on HTTP backends have no CLI, and
+/// a session can't be attached while a reply is being generated.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn pty_spawn_for_session(
+    app_handle: AppHandle,
+    sessionId: String,
+    cols: Option<u16>,
+    rows: Option<u16>,
+    state: State<'_, AgentState>,
+) -> Result<(), BertError> {
+    let (backend_id, cli_session_id, cwd) = {
+        let sessions = state.sessions.lock().unwrap();
+        let session = sessions
+            .get(&sessionId)
+            .ok_or_else(|| BertError::NotFound(format!("Session {} not found", sessionId)))?;
+        if session.process.is_some() {
+            return Err(BertError::InvalidInput(format!(
+                "Session {} is still replying; wait for it or interrupt it first",
+                sessionId
+            )));
+        }
+        let cwd = match session.worktree {
+            Some(ref worktree) => worktree.path.clone(),
+            None => session.project.repo_root()?,
+        };
+        (session.backend_id, session.cli_session_id.clone(), cwd)
+    };
+
+    if backend_id.transport() != BackendTransport::Cli {
+        return Err(BertError::InvalidInput(format!("{} sessions have no CLI to open in a terminal", backend_id)));
+    }
+    if state.pty.has_session(&sessionId) {
+        return Err(BertError::InvalidInput(format!("Session {} already has a terminal", sessionId)));
+    }
+
+    let backend = state
+        .backend_registry
+        .get(backend_id)
+        .ok_or_else(|| BertError::Agent(format!("Backend {:?} not registered", backend_id)))?;
+
+    state
+        .pty
+        .spawn(
+            sessionId.clone(),
+            backend.command_name().to_string(),
+            backend.build_interactive_args(cli_session_id.as_deref()),
+            Some(cwd.to_string_lossy().to_string()),
+            cols,
+            rows,
+        )
+        .map_err(BertError::Agent)?;
+    let reader = state.pty.take_reader(&sessionId).map_err(BertError::Agent)?;
+
+    std::thread::spawn(move || stream_output(app_handle, sessionId, reader));
+    Ok(())
+}
+
+/// Send input (keystrokes, pasted text) to a session's terminal
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn pty_write(sessionId: String, data: String, state: State<'_, AgentState>) -> Result<(), BertError> {
+    state.pty.write(&sessionId, data.as_bytes()).map_err(BertError::Agent)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn pty_resize(sessionId: String, cols: u16, rows: u16, state: State<'_, AgentState>) -> Result<(), BertError> {
+    state.pty.resize(&sessionId, cols, rows).map_err(BertError::Agent)
+}
+
+/// Close a session's terminal; the agent session itself stays
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn pty_kill(sessionId: String, state: State<'_, AgentState>) -> Result<(), BertError> {
+    state.pty.kill(&sessionId).map_err(BertError::Agent)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -260,6 +453,22 @@ mod tests {
         assert!(!manager.has_session("test-session"));
     }
 
+    #[test]
+    fn test_take_utf8_keeps_split_characters() {
+        // "é" is 0xC3 0xA9; the read boundary falls between the two bytes
+        let mut pending = b"\x1b[32mcaf\xC3".to_vec();
+        assert_eq!(take_utf8(&mut pending), "\x1b[32mcaf");
+        assert_eq!(pending, vec![0xC3]);
+
+        pending.extend_from_slice(b"\xA9\x1b[0m");
+        assert_eq!(take_utf8(&mut pending), "é\x1b[0m");
+        assert!(pending.is_empty());
+
+        let mut invalid = b"a\xFFb".to_vec();
+        assert_eq!(take_utf8(&mut invalid), "a\u{FFFD}b");
+        assert!(invalid.is_empty());
+    }
+
     // Note: Actual PTY spawning tests require a real TTY environment
     // and are better suited for integration tests
 }
@@ -65,6 +65,13 @@ impl BackendRegistry {
         backends.insert(id, backend);
     }
 
+    /// Remove a backend plugin, returning it if it was registered
+    #[allow(dead_code)]
+    pub fn unregister(&self, id: BackendId) -> Option<Arc<dyn CliBackendPlugin>> {
+        let mut backends = self.backends.write().unwrap();
+        backends.remove(&id)
+    }
+
     /// Retrieve a backend plugin by ID
     ///
     /// # Arguments
@@ -155,6 +162,17 @@ mod tests {
         assert!(registry.has_backend(BackendId::Gemini));
     }
 
+    #[test]
+    fn test_unregister() {
+        let registry = BackendRegistry::with_defaults();
+        let removed = registry.unregister(BackendId::Gemini).unwrap();
+        assert_eq!(removed.command_name(), "gemini");
+
+        assert!(!registry.has_backend(BackendId::Gemini));
+        assert!(registry.unregister(BackendId::Gemini).is_none());
+        assert!(registry.has_backend(BackendId::ClaudeCode));
+    }
+
     #[test]
     fn test_list_backends() {
         let registry = BackendRegistry::new();
@@ -0,0 +1,471 @@
+//! Agent safety policy: write approvals and the audit log
+//!
+//! The `agentSafetyPolicy` setting decides the permissions each CLI run
+//! starts with (see `CliBackendPlugin::build_args`). The session reader hands
+//! every tool call the CLI reports to `on_tool_use`, which records it in
+//! ~/.bp6/audit/<date>.jsonl. Under `approve-writes` the CLI refuses tools
+//! that change files or run commands, since it has no terminal to ask in;
+//! those calls are queued here instead, and approving one resumes the
+//! session with that call allowed for its next run: a shell tool only for
+//! the approved command, other tools by name. Everything else still runs
+//! under the configured policy. Under `read-only` they are only logged as
+//! blocked.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+use uuid::Uuid;
+
+use crate::agent::plugin::ToolUse;
+use crate::agent::AgentState;
+use crate::error::BertError;
+use crate::settings::AgentSafetyPolicy;
+
+/// Tools that only read, lowercased (Claude Code and Gemini CLI names).
+/// Anything else is treated as a write.
+const READ_ONLY_TOOLS: [&str; 16] = [
+    "read", "glob", "grep", "ls", "webfetch", "websearch", "todowrite", "task",
+    "read_file", "read_many_files", "list_directory", "glob_tool", "search_file_content",
+    "google_web_search", "web_fetch", "save_memory",
+];
+
+/// Tools that run the shell command in their `command` input, lowercased
+const COMMAND_TOOLS: [&str; 2] = ["bash", "run_shell_command"];
+
+pub fn is_write_tool(name: &str) -> bool {
+    !READ_ONLY_TOOLS.contains(&name.to_ascii_lowercase().as_str())
+}
+
+/// An approved tool call, allowed without asking for one run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolGrant {
+    pub tool: String,
+    /// The only command a shell tool may run; None for other tools
+    pub command: Option<String>,
+}
+
+impl ToolGrant {
+    /// Grant for an approved call; None for a shell call without a command,
+    /// which can't be scoped
+    pub fn for_call(tool: &ToolUse) -> Option<Self> {
+        if !COMMAND_TOOLS.contains(&tool.name.to_ascii_lowercase().as_str()) {
+            return Some(ToolGrant { tool: tool.name.clone(), command: None });
+        }
+        let command = tool.input.get("command").and_then(|c| c.as_str())?;
+        Some(ToolGrant { tool: tool.name.clone(), command: Some(command.to_string()) })
+    }
+
+    /// Whether `tool` is the call (or, without a command, the tool) granted
+    pub fn allows(&self, tool: &ToolUse) -> bool {
+        tool.name == self.tool
+            && self.command.as_deref().is_none_or(|command| tool.input.get("command").and_then(|c| c.as_str()) == Some(command))
+    }
+
+    /// The grant as a CLI permission rule: `Bash(git status)`, or the bare
+    /// tool name
+    pub fn rule(&self) -> String {
+        match &self.command {
+            Some(command) => format!("{}({})", self.tool, command),
+            None => self.tool.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum AuditDecision {
+    /// Ran under the run's policy
+    Allowed,
+    /// Refused by the CLI and queued for the user
+    AwaitingApproval,
+    /// Refused under the read-only policy
+    Blocked,
+    Approved,
+    Rejected,
+}
+
+/// What a run's policy does with a tool call
+pub fn decide(policy: AgentSafetyPolicy, tool: &ToolUse) -> AuditDecision {
+    match policy {
+        AgentSafetyPolicy::FullAuto => AuditDecision::Allowed,
+        _ if !is_write_tool(&tool.name) => AuditDecision::Allowed,
+        AgentSafetyPolicy::ApproveWrites => AuditDecision::AwaitingApproval,
+        AgentSafetyPolicy::ReadOnly => AuditDecision::Blocked,
+    }
+}
+
+/// One line of the audit log
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    pub timestamp: String,
+    pub session_id: String,
+    pub bead_id: Option<String>,
+    pub persona: String,
+    pub backend: String,
+    pub policy: AgentSafetyPolicy,
+    pub tool: String,
+    pub input: serde_json::Value,
+    pub decision: AuditDecision,
+    /// Request the entry belongs to, for approval decisions
+    #[serde(default)]
+    pub approval_id: Option<String>,
+}
+
+/// The agent run a tool call came from
+#[derive(Debug, Clone)]
+pub struct RunContext {
+    pub session_id: String,
+    pub bead_id: Option<String>,
+    pub persona: String,
+    pub backend: String,
+    pub policy: AgentSafetyPolicy,
+    /// Calls approved for this run, allowed whatever the policy
+    pub granted_tools: Vec<ToolGrant>,
+}
+
+impl RunContext {
+    fn audit_entry(&self, tool: &ToolUse, decision: AuditDecision, approval_id: Option<String>) -> AuditEntry {
+        AuditEntry {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            session_id: self.session_id.clone(),
+            bead_id: self.bead_id.clone(),
+            persona: self.persona.clone(),
+            backend: self.backend.clone(),
+            policy: self.policy,
+            tool: tool.name.clone(),
+            input: tool.input.clone(),
+            decision,
+            approval_id,
+        }
+    }
+}
+
+/// A write refused under `approve-writes`, waiting for the user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalRequest {
+    pub id: String,
+    pub session_id: String,
+    pub bead_id: Option<String>,
+    pub persona: String,
+    pub backend: String,
+    pub tool: ToolUse,
+    pub created_at: String,
+}
+
+/// Pending approval requests, and the calls approved for the next run of
+/// each session
+#[derive(Default)]
+pub struct ApprovalQueue {
+    pending: Mutex<Vec<ApprovalRequest>>,
+    grants: Mutex<HashMap<String, Vec<ToolGrant>>>,
+}
+
+impl ApprovalQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn push(&self, request: ApprovalRequest) {
+        self.pending.lock().unwrap().push(request);
+    }
+
+    pub fn pending(&self, session_id: Option<&str>) -> Vec<ApprovalRequest> {
+        self.pending
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|r| session_id.is_none_or(|id| r.session_id == id))
+            .cloned()
+            .collect()
+    }
+
+    fn take(&self, id: &str) -> Option<ApprovalRequest> {
+        let mut pending = self.pending.lock().unwrap();
+        let pos = pending.iter().position(|r| r.id == id)?;
+        Some(pending.remove(pos))
+    }
+
+    /// Drop the requests of a stopped session
+    pub fn clear_session(&self, session_id: &str) {
+        self.pending.lock().unwrap().retain(|r| r.session_id != session_id);
+        self.grants.lock().unwrap().remove(session_id);
+    }
+
+    fn grant_next_run(&self, session_id: &str, grant: ToolGrant) {
+        let mut grants = self.grants.lock().unwrap();
+        let tools = grants.entry(session_id.to_string()).or_default();
+        if !tools.contains(&grant) {
+            tools.push(grant);
+        }
+    }
+
+    /// Calls approved for the next run of `session_id`; each grant is used
+    /// up by that run
+    pub fn tools_for_run(&self, session_id: &str) -> Vec<ToolGrant> {
+        self.grants.lock().unwrap().remove(session_id).unwrap_or_default()
+    }
+}
+
+/// The configured policy, full-auto when settings aren't available
+pub fn configured_policy(app_handle: &AppHandle) -> AgentSafetyPolicy {
+    app_handle
+        .try_state::<crate::SettingsState>()
+        .and_then(|s| s.settings.lock().ok().map(|settings| settings.agent_safety_policy))
+        .unwrap_or_default()
+}
+
+fn audit_dir() -> Result<PathBuf, BertError> {
+    let home = dirs::home_dir().ok_or_else(|| BertError::Io("Could not find home directory".to_string()))?;
+    Ok(home.join(".bp6").join("audit"))
+}
+
+/// Audit log of the day `timestamp` (RFC 3339) falls on, in UTC
+fn audit_file(dir: &Path, timestamp: &str) -> PathBuf {
+    dir.join(format!("{}.jsonl", timestamp.get(..10).unwrap_or("unknown")))
+}
+
+fn append_audit(dir: &Path, entry: &AuditEntry) -> Result<(), BertError> {
+    fs::create_dir_all(dir).map_err(|e| BertError::Io(format!("Failed to create {}: {}", dir.display(), e)))?;
+    let path = audit_file(dir, &entry.timestamp);
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| BertError::Io(format!("Failed to open {}: {}", path.display(), e)))?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)
+        .map_err(|e| BertError::Io(format!("Failed to append to {}: {}", path.display(), e)))
+}
+
+fn read_audit(path: &Path) -> Result<Vec<AuditEntry>, BertError> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents =
+        fs::read_to_string(path).map_err(|e| BertError::Io(format!("Failed to read {}: {}", path.display(), e)))?;
+    Ok(contents
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str::<AuditEntry>(line) {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                eprintln!("⚠️  Skipping malformed audit line in {}: {}", path.display(), e);
+                None
+            }
+        })
+        .collect())
+}
+
+fn audit(entry: &AuditEntry) {
+    if let Err(e) = audit_dir().and_then(|dir| append_audit(&dir, entry)) {
+        eprintln!("⚠️  Failed to write the audit log: {}", e);
+    }
+}
+
+/// Audit a change the app made on its own (e.g. escalation rules) under the
+/// pseudo session `source`
+pub(crate) fn audit_app_action(source: &str, action: &str, bead_id: Option<&str>, input: serde_json::Value) {
+    audit(&AuditEntry {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        session_id: source.to_string(),
+        bead_id: bead_id.map(String::from),
+        persona: source.to_string(),
+        backend: "bert-viz".to_string(),
+        policy: AgentSafetyPolicy::FullAuto,
+        tool: action.to_string(),
+        input,
+        decision: AuditDecision::Allowed,
+        approval_id: None,
+    });
+}
+
+/// Audit a tool call reported by a running agent, queueing it for approval
+/// when the run's policy requires it
+pub fn on_tool_use(app_handle: &AppHandle, run: &RunContext, tool: ToolUse) {
+    let decision = if run.granted_tools.iter().any(|grant| grant.allows(&tool)) {
+        AuditDecision::Allowed
+    } else {
+        decide(run.policy, &tool)
+    };
+    let approval_id = (decision == AuditDecision::AwaitingApproval).then(|| Uuid::new_v4().to_string());
+    audit(&run.audit_entry(&tool, decision, approval_id.clone()));
+
+    match decision {
+        AuditDecision::AwaitingApproval => {
+            let request = ApprovalRequest {
+                id: approval_id.unwrap_or_default(),
+                session_id: run.session_id.clone(),
+                bead_id: run.bead_id.clone(),
+                persona: run.persona.clone(),
+                backend: run.backend.clone(),
+                tool,
+                created_at: chrono::Utc::now().to_rfc3339(),
+            };
+            eprintln!("✋ Session {} asks to run {}", run.session_id, request.tool.name);
+            if let Some(state) = app_handle.try_state::<AgentState>() {
+                state.approvals.push(request.clone());
+            }
+            let _ = app_handle.emit("agent-approval-requested", request);
+        }
+        AuditDecision::Blocked => eprintln!("🚫 Session {} blocked from running {}", run.session_id, tool.name),
+        _ => {}
+    }
+}
+
+fn resolve(app_handle: &AppHandle, state: &AgentState, approval_id: &str, decision: AuditDecision) -> Result<ApprovalRequest, BertError> {
+    let request = state
+        .approvals
+        .take(approval_id)
+        .ok_or_else(|| BertError::NotFound(format!("Approval request {} not found", approval_id)))?;
+    let run = RunContext {
+        session_id: request.session_id.clone(),
+        bead_id: request.bead_id.clone(),
+        persona: request.persona.clone(),
+        backend: request.backend.clone(),
+        policy: AgentSafetyPolicy::ApproveWrites,
+        granted_tools: Vec::new(),
+    };
+    audit(&run.audit_entry(&request.tool, decision, Some(request.id.clone())));
+    let _ = app_handle.emit("agent-approval-resolved", serde_json::json!({ "id": request.id, "decision": decision }));
+    Ok(request)
+}
+
+/// Approval requests waiting for the user, optionally of one session
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn list_agent_approvals(sessionId: Option<String>, state: State<'_, AgentState>) -> Vec<ApprovalRequest> {
+    state.approvals.pending(sessionId.as_deref())
+}
+
+/// Approve a refused write. The session is resumed and asked to retry; that
+/// run may make the approved call without asking (a shell tool only with the
+/// approved command), keeps the configured policy for everything else, and
+/// everything it does is audited.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn approve_agent_action(approvalId: String, app_handle: AppHandle, state: State<'_, AgentState>) -> Result<(), BertError> {
+    let request = resolve(&app_handle, &state, &approvalId, AuditDecision::Approved)?;
+    match ToolGrant::for_call(&request.tool) {
+        Some(grant) => state.approvals.grant_next_run(&request.session_id, grant),
+        None => eprintln!("⚠️  {} call without a command; nothing to grant", request.tool.name),
+    }
+    eprintln!("✅ Approved {} for session {}", request.tool.name, request.session_id);
+
+    let message = format!(
+        "The user approved your request to run {} with input {}. Go ahead and retry it.",
+        request.tool.name, request.tool.input
+    );
+    crate::agent::session::send_agent_message(app_handle, request.session_id, message, state).await
+}
+
+/// Reject a refused write; the agent is not told
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn reject_agent_action(approvalId: String, app_handle: AppHandle, state: State<'_, AgentState>) -> Result<(), BertError> {
+    resolve(&app_handle, &state, &approvalId, AuditDecision::Rejected).map(|_| ())
+}
+
+/// Audit log of a day (YYYY-MM-DD, UTC; today by default), optionally of one
+/// session, oldest first
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn get_audit_log(date: Option<String>, sessionId: Option<String>) -> Result<Vec<AuditEntry>, BertError> {
+    let date = date.unwrap_or_else(|| chrono::Utc::now().format("%Y-%m-%d").to_string());
+    if chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d").is_err() {
+        return Err(BertError::InvalidInput(format!("Invalid date: {}", date)));
+    }
+    let mut entries = read_audit(&audit_file(&audit_dir()?, &date))?;
+    if let Some(session_id) = sessionId {
+        entries.retain(|e| e.session_id == session_id);
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn tool(name: &str) -> ToolUse {
+        ToolUse { name: name.to_string(), input: json!({ "file_path": "src/lib.rs" }) }
+    }
+
+    #[test]
+    fn test_policy_decisions() {
+        assert_eq!(decide(AgentSafetyPolicy::FullAuto, &tool("Bash")), AuditDecision::Allowed);
+        assert_eq!(decide(AgentSafetyPolicy::ApproveWrites, &tool("Read")), AuditDecision::Allowed);
+        assert_eq!(decide(AgentSafetyPolicy::ApproveWrites, &tool("Edit")), AuditDecision::AwaitingApproval);
+        assert_eq!(decide(AgentSafetyPolicy::ReadOnly, &tool("write_file")), AuditDecision::Blocked);
+        assert_eq!(decide(AgentSafetyPolicy::ReadOnly, &tool("read_file")), AuditDecision::Allowed);
+        // Unknown tools count as writes
+        assert!(is_write_tool("SomeNewTool"));
+    }
+
+    #[test]
+    fn test_audit_log_per_day() {
+        let dir = TempDir::new().unwrap();
+        let run = RunContext {
+            session_id: "s1".to_string(),
+            bead_id: Some("bp-1".to_string()),
+            persona: "specialist".to_string(),
+            backend: "claude".to_string(),
+            policy: AgentSafetyPolicy::ApproveWrites,
+            granted_tools: Vec::new(),
+        };
+        let mut entry = run.audit_entry(&tool("Edit"), AuditDecision::AwaitingApproval, Some("a1".to_string()));
+        entry.timestamp = "2026-03-01T23:59:00+00:00".to_string();
+        append_audit(dir.path(), &entry).unwrap();
+        append_audit(dir.path(), &entry).unwrap();
+
+        let path = audit_file(dir.path(), "2026-03-01T00:00:00Z");
+        assert_eq!(path, dir.path().join("2026-03-01.jsonl"));
+        assert_eq!(read_audit(&path).unwrap(), vec![entry.clone(), entry]);
+        assert!(read_audit(&audit_file(dir.path(), "2026-03-02")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_approval_grants_the_tool_for_one_run() {
+        let queue = ApprovalQueue::new();
+        queue.push(ApprovalRequest {
+            id: "a1".to_string(),
+            session_id: "s1".to_string(),
+            bead_id: None,
+            persona: "specialist".to_string(),
+            backend: "claude".to_string(),
+            tool: tool("Edit"),
+            created_at: "2026-03-01T10:00:00Z".to_string(),
+        });
+        assert_eq!(queue.pending(Some("s2")).len(), 0);
+        assert_eq!(queue.pending(None).len(), 1);
+
+        assert!(queue.take("a1").is_some());
+        assert!(queue.take("a1").is_none());
+        let edit = ToolGrant::for_call(&tool("Edit")).unwrap();
+        queue.grant_next_run("s1", edit.clone());
+        queue.grant_next_run("s1", edit.clone());
+        assert!(queue.tools_for_run("s2").is_empty());
+        assert_eq!(queue.tools_for_run("s1"), vec![edit]);
+        assert!(queue.tools_for_run("s1").is_empty());
+    }
+
+    #[test]
+    fn test_shell_grants_allow_only_the_approved_command() {
+        let bash = |command: &str| ToolUse { name: "Bash".to_string(), input: json!({ "command": command }) };
+        let grant = ToolGrant::for_call(&bash("cargo test")).unwrap();
+        assert_eq!(grant.rule(), "Bash(cargo test)");
+        assert!(grant.allows(&bash("cargo test")));
+        assert!(!grant.allows(&bash("rm -rf target")));
+        assert!(ToolGrant::for_call(&ToolUse { name: "Bash".to_string(), input: json!({}) }).is_none());
+
+        // Tools without a command are granted by name
+        let edit = ToolGrant::for_call(&tool("Edit")).unwrap();
+        assert_eq!(edit.rule(), "Edit");
+        assert!(edit.allows(&ToolUse { name: "Edit".to_string(), input: json!({ "file_path": "other.rs" }) }));
+    }
+}
@@ -2,14 +2,27 @@ use pulldown_cmark::{html, Options, Parser};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs::{self, File};
-use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
 use std::path::PathBuf;
-use std::process::{Child, Command, Stdio};
+use std::process::{Child, ChildStderr, Command, Stdio};
 use std::sync::{Arc, Mutex};
 use std::time::SystemTime;
 use tauri::{AppHandle, Emitter, Manager, State};
 use uuid::Uuid;
 
+use crate::agent::bd_command::BdCommand;
+use crate::agent::failover::{self, SpawnAttempt, StartupFailure};
+use crate::agent::file_watch::{ChangedFile, SessionFiles};
+use crate::agent::session_queue::QueuedStart;
+use crate::agent::worktree::{SessionWorktree, WorktreeAction};
+use crate::agent::plugin::{AgentChunk, BackendId, BackendTransport, ChatMessage, HttpBackendPlugin, TokenUsage};
+use crate::agent::safety::ToolGrant;
+use crate::error::BertError;
+use crate::events::{emit_scoped, EventScope};
+use crate::notifications::NotificationKind;
+use crate::project::ProjectContext;
+use crate::settings::{AgentSafetyPolicy, FailoverPolicy};
+
 /// Status of an agent session
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -36,8 +49,9 @@ impl Default for ExecutionMode {
 /// Internal session state tracking a running agent process
 #[derive(Debug)]
 pub struct SessionState {
-    /// The running CLI process handle
-    pub process: Child,
+    /// The running CLI process handle (None for a session restored after an
+    /// app restart, until the next message resumes it)
+    pub process: Option<Child>,
     /// The bead/issue ID this session is working on (if any)
     pub bead_id: Option<String>,
     /// The persona/role for this session (specialist, product-manager, qa-engineer)
@@ -62,6 +76,16 @@ pub struct SessionState {
     pub has_unread: bool,
     /// Number of messages in this session
     pub message_count: usize,
+    /// The project the CLI runs in (follow-up commands reuse it)
+    pub project: ProjectContext,
+    /// Token usage accumulated over every CLI run of this session
+    pub usage: TokenUsage,
+    /// Dedicated git worktree the CLI runs in (isolated sessions only)
+    pub worktree: Option<SessionWorktree>,
+    /// Files created, modified or deleted in the session's working directory
+    pub files: SessionFiles,
+    /// How the latest run got started, retries and failovers included
+    pub spawn_attempts: Vec<SpawnAttempt>,
 }
 
 /// Serializable session information for UI display (excludes process handle)
@@ -94,16 +118,27 @@ pub struct SessionInfo {
     pub has_unread: bool,
     /// Number of messages in this session
     pub message_count: usize,
+    /// Token usage accumulated over every CLI run of this session
+    #[serde(default)]
+    pub usage: TokenUsage,
+    /// Dedicated git worktree the CLI runs in (isolated sessions only)
+    #[serde(default)]
+    pub worktree: Option<SessionWorktree>,
+    /// How the latest run got started, retries and failovers included
+    #[serde(default)]
+    pub spawn_attempts: Vec<SpawnAttempt>,
 }
 
 /// Type of log event
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum LogEventType {
     SessionStart,
     Message,
     Chunk,
     SessionEnd,
+    /// A diff, test report or command output found in a completed reply
+    Artifact,
 }
 
 /// Log event for conversation logging
@@ -259,7 +294,29 @@ impl SessionLogger {
                 LogEventType::Chunk
             },
             content: chunk.content.clone(),
-            metadata: None,
+            metadata: chunk.usage.as_ref().map(|usage| serde_json::json!({ "usage": usage })),
+        };
+        self.log_event(event)
+    }
+
+    /// Log an artifact extracted from a completed reply
+    pub fn log_artifact(
+        &mut self,
+        session_id: &str,
+        bead_id: Option<&str>,
+        persona: &str,
+        backend: &str,
+        artifact: &crate::agent::artifacts::SessionArtifact,
+    ) -> std::io::Result<()> {
+        let event = LogEvent {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            session_id: session_id.to_string(),
+            bead_id: bead_id.map(String::from),
+            persona: persona.to_string(),
+            backend: backend.to_string(),
+            event_type: LogEventType::Artifact,
+            content: artifact.content.clone(),
+            metadata: Some(crate::agent::artifacts::metadata(artifact)),
         };
         self.log_event(event)
     }
@@ -312,6 +369,20 @@ pub struct AgentState {
     pub persona_registry: crate::agent::persona::PersonaRegistry,
     /// Template loader for persona prompts
     pub template_loader: crate::agent::templates::TemplateLoader,
+    /// `bd` commands proposed by agents, awaiting approval
+    pub proposals: crate::agent::proposals::ProposalQueue,
+    /// Writes refused under the approve-writes policy, awaiting approval
+    pub approvals: crate::agent::safety::ApprovalQueue,
+    /// Conversation history of sessions on HTTP backends, which keep none themselves
+    pub conversations: Mutex<HashMap<String, Vec<ChatMessage>>>,
+    /// Unattended runner working the ready queue
+    pub autopilot: crate::agent::autopilot::Autopilot,
+    /// Terminals attached to sessions, keyed by session ID
+    pub pty: crate::agent::pty::PtyManager,
+    /// Feature swarms, keyed by feature ID
+    pub swarms: crate::agent::swarm::SwarmState,
+    /// New sessions waiting for the concurrency limit
+    pub queue: crate::agent::session_queue::SessionQueue,
 }
 
 impl AgentState {
@@ -325,6 +396,13 @@ impl AgentState {
             persona_registry: crate::agent::persona::PersonaRegistry::with_defaults(),
             template_loader: crate::agent::templates::TemplateLoader::new()
                 .expect("Failed to initialize template loader"),
+            proposals: crate::agent::proposals::ProposalQueue::new(),
+            approvals: crate::agent::safety::ApprovalQueue::new(),
+            conversations: Mutex::new(HashMap::new()),
+            autopilot: crate::agent::autopilot::Autopilot::new(),
+            pty: crate::agent::pty::PtyManager::new(),
+            swarms: crate::agent::swarm::SwarmState::new(),
+            queue: crate::agent::session_queue::SessionQueue::new(),
         }
     }
 }
@@ -356,7 +434,9 @@ fn interrupt_process_group(pid: u32) {
 // Multi-session helper functions
 
 /// Convert all sessions to SessionInfo and emit session-list-changed event
+/// and persist the sessions so they can be restored after a restart
 fn emit_session_list_changed(app_handle: &AppHandle, sessions: &HashMap<String, SessionState>) {
+    persist_sessions(sessions);
     let session_list = list_active_sessions_internal(sessions);
     println!(
         "📡 Emitting session-list-changed with {} sessions",
@@ -378,74 +458,106 @@ fn emit_session_list_changed(app_handle: &AppHandle, sessions: &HashMap<String,
     let _ = app_handle.emit_to(tauri::EventTarget::Any, "session-list-changed", payload);
 }
 
+/// Write all sessions to ~/.bp6/sessions/active.json
+fn persist_sessions(sessions: &HashMap<String, SessionState>) {
+    let persisted: Vec<crate::agent::session_store::PersistedSession> = sessions
+        .iter()
+        .map(|(session_id, state)| crate::agent::session_store::PersistedSession {
+            info: session_info(session_id, state),
+            project_path: state.project.project_path().to_path_buf(),
+        })
+        .collect();
+    if let Err(e) = crate::agent::session_store::save(&persisted) {
+        eprintln!("⚠️  Failed to persist sessions: {}", e);
+    }
+}
+
 /// Convert HashMap<String, SessionState> to Vec<SessionInfo> for UI consumption
 fn list_active_sessions_internal(sessions: &HashMap<String, SessionState>) -> Vec<SessionInfo> {
     sessions
         .iter()
-        .map(|(session_id, state)| SessionInfo {
-            session_id: session_id.clone(),
-            bead_id: state.bead_id.clone(),
-            persona: state.persona.clone(),
-            backend_id: state.backend_id,
-            status: state.status.clone(),
-            created_at: state
-                .created_at
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs(),
-            cli_session_id: state.cli_session_id.clone(),
-            execution_mode: state.execution_mode.clone(),
-            commands_remaining: state.command_queue.as_ref().map(|q| q.len()),
-            total_commands: state.total_commands,
-            last_activity: state
-                .last_activity
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs(),
-            has_unread: state.has_unread,
-            message_count: state.message_count,
-        })
+        .map(|(session_id, state)| session_info(session_id, state))
         .collect()
 }
 
+fn session_info(session_id: &str, state: &SessionState) -> SessionInfo {
+    SessionInfo {
+        session_id: session_id.to_string(),
+        bead_id: state.bead_id.clone(),
+        persona: state.persona.clone(),
+        backend_id: state.backend_id,
+        status: state.status.clone(),
+        created_at: state
+            .created_at
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        cli_session_id: state.cli_session_id.clone(),
+        execution_mode: state.execution_mode.clone(),
+        commands_remaining: state.command_queue.as_ref().map(|q| q.len()),
+        total_commands: state.total_commands,
+        last_activity: state
+            .last_activity
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        has_unread: state.has_unread,
+        message_count: state.message_count,
+        usage: state.usage.clone(),
+        worktree: state.worktree.clone(),
+        spawn_attempts: state.spawn_attempts.clone(),
+    }
+}
+
 // Backend-specific functions removed - now handled by CliBackendPlugin implementations
 
-/// Run CLI command for a specific session (multi-session architecture)
-///
-/// Spawns a CLI process, manages stdout/stderr reading in separate threads,
-/// and includes session_id in all emitted chunks. Returns the Child process
-/// handle (with stdout/stderr already taken) for storage in SessionState.
-fn run_cli_command_for_session(
-    backend_id: crate::agent::plugin::BackendId,
-    app_handle: AppHandle,
+/// Parses one line of backend output into a chunk
+type LineParser = Box<dyn Fn(&serde_json::Value) -> Option<AgentChunk> + Send>;
+
+/// Picks the tool calls out of one line of backend output
+type ToolUseParser = Box<dyn Fn(&serde_json::Value) -> Vec<crate::agent::plugin::ToolUse> + Send>;
+
+/// Output of one agent run: the CLI process's stdout or the HTTP response body
+struct AgentRun {
+    output: Box<dyn Read + Send>,
+    /// CLI stderr, streamed to the UI as `agent-stderr`
+    stderr: Option<ChildStderr>,
+    process: Option<Child>,
+    parse_line: LineParser,
+    parse_tool_uses: ToolUseParser,
+    /// Permissions the run started with
+    policy: AgentSafetyPolicy,
+    /// Calls approved for this run on top of `policy`
+    granted_tools: Vec<ToolGrant>,
+    /// Backend name recorded in the session log
+    backend_name: String,
+}
+
+/// Spawn the CLI of a CLI backend in `repo_root`, with the permissions of
+/// the safety policy plus the approved `granted_tools`
+#[allow(clippy::too_many_arguments)]
+fn spawn_cli_run(
+    backend_id: BackendId,
+    app_handle: &AppHandle,
     state: &AgentState,
-    session_id: String,
-    bead_id: Option<String>,
-    persona: String,
-    prompt: String,
+    repo_root: &std::path::Path,
+    session_id: &str,
+    prompt: &str,
     resume: bool,
-    cli_session_id: Option<String>,
-) -> Result<Child, String> {
-    let repo_root = crate::bd::find_repo_root().ok_or_else(|| {
-        "Could not locate project root (.beads directory). Please ensure a project is loaded."
-            .to_string()
-    })?;
-
-    eprintln!(
-        "🎯 Starting session {} in directory: {}",
-        session_id,
-        repo_root.display()
-    );
-
+    cli_session_id: Option<&str>,
+    granted_tools: &[ToolGrant],
+) -> Result<AgentRun, BertError> {
     let backend = state
         .backend_registry
         .get(backend_id)
-        .ok_or_else(|| format!("Backend {:?} not registered", backend_id))?;
+        .ok_or_else(|| BertError::Agent(format!("Backend {:?} not registered", backend_id)))?;
 
+    let policy = crate::agent::safety::configured_policy(app_handle);
     let mut cmd = Command::new(backend.command_name());
-    let args = backend.build_args(&prompt, resume, cli_session_id.as_deref());
-    cmd.args(&args);
-    cmd.current_dir(&repo_root);
+    let rules: Vec<String> = granted_tools.iter().map(ToolGrant::rule).collect();
+    cmd.args(backend.allow_tools_args(&rules));
+    cmd.args(backend.build_args(prompt, resume, cli_session_id, policy));
+    cmd.current_dir(repo_root);
 
     #[cfg(unix)]
     {
@@ -463,7 +575,8 @@ fn run_cli_command_for_session(
         .stderr(Stdio::piped())
         .spawn()
         .map_err(|e| {
-            let error_msg = if e.kind() == std::io::ErrorKind::NotFound {
+            let not_installed = e.kind() == std::io::ErrorKind::NotFound;
+            let error_msg = if not_installed {
                 let install_cmd = match backend_id {
                     crate::agent::plugin::BackendId::Gemini => {
                         "npm install -g @google/generative-ai-cli"
@@ -471,6 +584,9 @@ fn run_cli_command_for_session(
                     crate::agent::plugin::BackendId::ClaudeCode => {
                         "See https://docs.anthropic.com/en/docs/claude-code for installation"
                     }
+                    crate::agent::plugin::BackendId::Ollama => {
+                        "See https://ollama.com/download for installation"
+                    }
                 };
                 format!(
                     "{} CLI not found. Please install it first: {}",
@@ -485,27 +601,310 @@ fn run_cli_command_for_session(
                     e
                 )
             };
-            let _ = app_handle.emit("agent-stderr", format!("[Error] {}", error_msg));
-            error_msg
+            emit_scoped(app_handle, EventScope::Session(session_id), "agent-stderr", format!("[Error] {}", error_msg));
+            if not_installed {
+                BertError::CliNotInstalled(error_msg)
+            } else {
+                BertError::Agent(error_msg)
+            }
+        })?;
+
+    let output = child.stdout.take().unwrap();
+    let stderr = child.stderr.take();
+    let backend_name = backend.command_name().to_string();
+    let tool_backend = backend.clone();
+    Ok(AgentRun {
+        output: Box::new(output),
+        stderr,
+        process: Some(child),
+        parse_line: Box::new(move |json| backend.parse_stdout_line(json)),
+        parse_tool_uses: Box::new(move |json| tool_backend.parse_tool_uses(json)),
+        policy,
+        granted_tools: granted_tools.to_vec(),
+        backend_name,
+    })
+}
+
+/// HTTP backend for `backend_id`, configured from the app settings
+fn http_backend_for(backend_id: BackendId, app_handle: &AppHandle) -> Result<Arc<dyn HttpBackendPlugin>, BertError> {
+    match backend_id {
+        BackendId::Ollama => {
+            let settings_state = app_handle
+                .try_state::<crate::SettingsState>()
+                .ok_or_else(|| BertError::Internal("Settings not initialized".to_string()))?;
+            let settings = settings_state.settings.lock().map_err(|e| e.to_string())?;
+            Ok(Arc::new(crate::agent::backends::OllamaBackend::new(&settings.ollama_host, &settings.ollama_model)))
+        }
+        _ => Err(BertError::Agent(format!("{} is not an HTTP backend", backend_id))),
+    }
+}
+
+/// Send `prompt` to an HTTP backend, with the session's earlier turns unless
+/// this starts a new conversation
+fn start_http_run(
+    backend_id: BackendId,
+    app_handle: &AppHandle,
+    state: &AgentState,
+    session_id: &str,
+    prompt: &str,
+    resume: bool,
+) -> Result<AgentRun, BertError> {
+    let backend = http_backend_for(backend_id, app_handle)?;
+    let messages = {
+        let mut conversations = state.conversations.lock().unwrap();
+        let conversation = conversations.entry(session_id.to_string()).or_default();
+        if !resume {
+            conversation.clear();
+        }
+        conversation.push(ChatMessage::user(prompt));
+        conversation.clone()
+    };
+
+    let endpoint = backend.endpoint();
+    let response = ureq::AgentBuilder::new()
+        .timeout_connect(std::time::Duration::from_secs(5))
+        .build()
+        .post(&endpoint)
+        .send_json(backend.build_request(&messages))
+        .map_err(|e| {
+            // The message was never answered; drop it from the conversation
+            if let Some(conversation) = state.conversations.lock().unwrap().get_mut(session_id) {
+                conversation.pop();
+            }
+            let detail = match e {
+                ureq::Error::Status(code, response) => {
+                    format!("HTTP {}: {}", code, response.into_string().unwrap_or_default().trim())
+                }
+                ureq::Error::Transport(transport) => transport.to_string(),
+            };
+            let error_msg = format!("{} request to {} failed ({})", backend_id, endpoint, detail);
+            emit_scoped(app_handle, EventScope::Session(session_id), "agent-stderr", format!("[Error] {}", error_msg));
+            BertError::Agent(error_msg)
         })?;
 
+    let backend_name = backend.name().to_string();
+    Ok(AgentRun {
+        output: Box::new(response.into_reader()),
+        stderr: None,
+        process: None,
+        parse_line: Box::new(move |json| backend.parse_stream_line(json)),
+        // HTTP backends only chat; they have no tools
+        parse_tool_uses: Box::new(|_| Vec::new()),
+        policy: crate::agent::safety::configured_policy(app_handle),
+        granted_tools: Vec::new(),
+        backend_name,
+    })
+}
+
+/// Start one run on `backend_id`. A CLI that exits with an error within the
+/// startup window of `policy` didn't start; its stderr says why.
+#[allow(clippy::too_many_arguments)]
+fn start_run(
+    backend_id: BackendId,
+    app_handle: &AppHandle,
+    state: &AgentState,
+    repo_root: &std::path::Path,
+    session_id: &str,
+    prompt: &str,
+    resume: bool,
+    cli_session_id: Option<&str>,
+    granted_tools: &[ToolGrant],
+    policy: &FailoverPolicy,
+) -> Result<AgentRun, (StartupFailure, BertError)> {
+    let mut run = match backend_id.transport() {
+        BackendTransport::Cli => {
+            spawn_cli_run(backend_id, app_handle, state, repo_root, session_id, prompt, resume, cli_session_id, granted_tools)
+        }
+        BackendTransport::Http => start_http_run(backend_id, app_handle, state, session_id, prompt, resume),
+    }
+    .map_err(|e| (failover::classify_error(&e), e))?;
+
+    let Some(process) = run.process.as_mut().filter(|_| policy.startup_window_ms > 0) else {
+        return Ok(run);
+    };
+    match failover::probe(process, std::time::Duration::from_millis(policy.startup_window_ms)) {
+        Some(status) if !status.success() => {
+            let stderr = failover::read_stderr(run.stderr.take());
+            for line in stderr.lines() {
+                emit_scoped(app_handle, EventScope::Session(session_id), "agent-stderr", format!("[{}] {}", session_id, line));
+            }
+            let detail = stderr.lines().rev().map(str::trim).find(|l| !l.is_empty()).unwrap_or("no output");
+            let error = BertError::Agent(format!("{} exited during startup ({}): {}", run.backend_name, status, detail));
+            Err((failover::classify(&stderr), error))
+        }
+        _ => Ok(run),
+    }
+}
+
+/// A run started by `run_cli_command_for_session`
+struct RunStarted {
+    /// The CLI process (with stdout/stderr already taken); None for HTTP backends
+    pub process: Option<Child>,
+    /// Backend the run started on, the fallback one after a failover
+    pub backend_id: BackendId,
+    /// CLI session ID the run was started with
+    pub cli_session_id: Option<String>,
+    pub attempts: Vec<SpawnAttempt>,
+}
+
+impl RunStarted {
+    /// Record the run in the state of the session it belongs to
+    fn apply(self, session: &mut SessionState) {
+        if self.backend_id != session.backend_id {
+            // The other backend's conversation ID means nothing to this one
+            session.cli_session_id = self.cli_session_id;
+            session.backend_id = self.backend_id;
+        }
+        session.process = self.process;
+        session.spawn_attempts = self.attempts;
+    }
+}
+
+/// Post-reply hook: append the reply's summary block (if any) to the bead's
+/// notes, unless auto-capture is turned off in the settings
+fn capture_summary(app_handle: &AppHandle, project: &ProjectContext, session_id: &str, bead_id: &str, reply: &str) {
+    let enabled = app_handle
+        .try_state::<crate::SettingsState>()
+        .and_then(|s| s.settings.lock().ok().map(|settings| settings.auto_capture_summary))
+        .unwrap_or(false);
+    if !enabled {
+        return;
+    }
+    let Some(summary) = crate::agent::summary::extract_summary(reply) else {
+        return;
+    };
+
+    match crate::agent::summary::capture(project, bead_id, &summary) {
+        Ok(()) => {
+            eprintln!("📝 Session {} summary appended to notes of {}", session_id, bead_id);
+            emit_scoped(
+                app_handle,
+                EventScope::Session(session_id),
+                "summary-captured",
+                serde_json::json!({ "sessionId": session_id, "beadId": bead_id, "summary": summary }),
+            );
+            crate::project::emit_beads_updated(app_handle, project);
+        }
+        Err(e) => eprintln!("⚠️  Failed to capture summary of session {} into {}: {}", session_id, bead_id, e),
+    }
+}
+
+/// Run CLI command for a specific session (multi-session architecture)
+///
+/// Spawns a CLI process (or makes a streaming request to an HTTP backend),
+/// manages output reading in separate threads, and includes session_id in all
+/// emitted chunks. Sessions with a worktree run there instead of the repo root.
+/// Runs that fail to start are retried or moved to the fallback backend as the
+/// `agentFailover` setting says; a fallback backend starts a new conversation.
+/// Returns the Child process handle (with stdout/stderr already taken) for
+/// storage in SessionState; HTTP backends have no process.
+#[allow(clippy::too_many_arguments)]
+fn run_cli_command_for_session(
+    backend_id: crate::agent::plugin::BackendId,
+    app_handle: AppHandle,
+    state: &AgentState,
+    project: &ProjectContext,
+    worktree: Option<&SessionWorktree>,
+    session_id: String,
+    bead_id: Option<String>,
+    persona: String,
+    prompt: String,
+    resume: bool,
+    cli_session_id: Option<String>,
+) -> Result<RunStarted, BertError> {
+    // Isolated sessions run in their own worktree instead of the repo root
+    let repo_root = match worktree {
+        Some(worktree) => worktree.path.clone(),
+        None => project.repo_root().map_err(|e| {
+            BertError::ProjectNotFound(format!("Could not locate project root ({}). Please ensure a project is loaded.", e))
+        })?,
+    };
+
+    eprintln!(
+        "🎯 Starting session {} in directory: {}",
+        session_id,
+        repo_root.display()
+    );
+
+    let policy = app_handle
+        .try_state::<crate::SettingsState>()
+        .and_then(|s| s.settings.lock().ok().map(|settings| settings.agent_failover.clone()))
+        .unwrap_or_default();
+    let conversation = |fallback: bool| if fallback && resume { (false, None) } else { (resume, cli_session_id.clone()) };
+    // Taken once: every attempt, retries and fallback included, gets the approved tools
+    let granted_tools = state.approvals.tools_for_run(&session_id);
+    let (started, attempts) = failover::start_with_failover(
+        &policy,
+        backend_id,
+        |backend, fallback| {
+            let (resume, cli_session_id) = conversation(fallback);
+            start_run(
+                backend,
+                &app_handle,
+                state,
+                &repo_root,
+                &session_id,
+                &prompt,
+                resume,
+                cli_session_id.as_deref(),
+                &granted_tools,
+                &policy,
+            )
+        },
+        |attempt| {
+            eprintln!("⚠️  Session {} failed to start on {} (attempt {}): {:?}", session_id, attempt.backend_id, attempt.attempt, attempt.error);
+            emit_scoped(
+                &app_handle,
+                EventScope::Session(&session_id),
+                "session-spawn-failed",
+                serde_json::json!({ "sessionId": session_id, "attempt": attempt }),
+            );
+        },
+    );
+    let (run, started_on) = match started {
+        Ok(started) => started,
+        Err(e) => {
+            // A session that already exists shows why its run didn't start
+            let mut sessions = state.sessions.lock().unwrap();
+            if let Some(session) = sessions.get_mut(&session_id) {
+                session.spawn_attempts = attempts;
+            }
+            return Err(e);
+        }
+    };
+    if started_on != backend_id {
+        eprintln!("🔀 Session {} failed over from {} to {}", session_id, backend_id, started_on);
+    }
+    let cli_session_id = conversation(started_on != backend_id).1;
+    let backend_id = started_on;
+
     eprintln!("🚀 Session {} - Sending prompt:\n{}", session_id, prompt);
-    let _ = app_handle.emit(
+    emit_scoped(
+        &app_handle,
+        EventScope::Session(&session_id),
         "agent-stderr",
         format!("[Session {}] Sending prompt:\n{}", session_id, prompt),
     );
 
-    // Extract stdout/stderr before spawning threads
-    let stdout = child.stdout.take().unwrap();
-    let stderr = child.stderr.take().unwrap();
-
-    // Spawn stdout reader thread with logging
+    // Spawn output reader thread with logging
     let handle_clone = app_handle.clone();
-    let backend_clone = backend.clone();
+    let parse_line = run.parse_line;
     let session_id_clone = session_id.clone();
     let bead_id_clone = bead_id.clone();
     let persona_clone = persona.clone();
-    let backend_name = backend.command_name().to_string();
+    let backend_name = run.backend_name;
+    let parse_tool_uses = run.parse_tool_uses;
+    let run_context = crate::agent::safety::RunContext {
+        session_id: session_id.clone(),
+        bead_id: bead_id.clone(),
+        persona: persona.clone(),
+        backend: backend_name.clone(),
+        policy: run.policy,
+        granted_tools: run.granted_tools,
+    };
+    let project_clone = project.clone();
+    // HTTP backends keep no history, so replies are recorded for the next request
+    let record_reply = backend_id.transport() == BackendTransport::Http;
 
     std::thread::spawn(move || {
         // Initialize session logger
@@ -541,13 +940,31 @@ fn run_cli_command_for_session(
             let _ = logger.log_event(start_event);
         }
 
-        let reader = BufReader::new(stdout);
+        let reader = BufReader::new(run.output);
+        let mut reply = String::new();
         for line in reader.lines() {
+            // A stopped HTTP session has no process to kill; dropping the
+            // response closes the connection and ends generation
+            if record_reply {
+                let stopped = handle_clone
+                    .try_state::<AgentState>()
+                    .map(|s| !s.conversations.lock().unwrap().contains_key(&session_id_clone))
+                    .unwrap_or(false);
+                if stopped {
+                    eprintln!("🛑 Session {} stopped, closing HTTP stream", session_id_clone);
+                    break;
+                }
+            }
+
             if let Ok(line_str) = line {
                 if line_str.trim().starts_with('{') {
                     if let Ok(json) = serde_json::from_str::<serde_json::Value>(&line_str) {
+                        for tool in parse_tool_uses(&json) {
+                            crate::agent::safety::on_tool_use(&handle_clone, &run_context, tool);
+                        }
+
                         // Parse using backend plugin
-                        if let Some(mut chunk) = backend_clone.parse_stdout_line(&json) {
+                        if let Some(mut chunk) = parse_line(&json) {
                             // If chunk contains a CLI session ID (e.g., from Gemini init message),
                             // store it in the SessionState for future resume operations
                             if let Some(ref cli_session_id) = chunk.session_id {
@@ -557,13 +974,32 @@ fn run_cli_command_for_session(
                                         eprintln!("📝 Captured CLI session ID for {}: {}", session_id_clone, cli_session_id);
                                         session.cli_session_id = Some(cli_session_id.clone());
                                     }
+                                    persist_sessions(&sessions);
                                 }
                             }
 
                             // Set session ID to internal session ID for event routing
                             chunk.session_id = Some(session_id_clone.clone());
 
-                            // Log the chunk
+                            let mut artifacts = Vec::new();
+                            if !chunk.is_done {
+                                reply.push_str(&chunk.content);
+                            } else if !reply.is_empty() {
+                                let reply = std::mem::take(&mut reply);
+                                if record_reply {
+                                    if let Some(agent_state) = handle_clone.try_state::<AgentState>() {
+                                        if let Some(conversation) = agent_state.conversations.lock().unwrap().get_mut(&session_id_clone) {
+                                            conversation.push(ChatMessage::assistant(&reply));
+                                        }
+                                    }
+                                }
+                                if let Some(ref bead_id) = bead_id_clone {
+                                    capture_summary(&handle_clone, &project_clone, &session_id_clone, bead_id, &reply);
+                                }
+                                artifacts = crate::agent::artifacts::extract(&reply);
+                            }
+
+                            // Log the chunk, then the diffs and test output of a completed reply
                             if let Some(ref mut logger) = logger {
                                 let _ = logger.log_chunk(
                                     &session_id_clone,
@@ -572,6 +1008,33 @@ fn run_cli_command_for_session(
                                     &backend_name,
                                     &chunk,
                                 );
+                                for artifact in &artifacts {
+                                    let _ = logger.log_artifact(
+                                        &session_id_clone,
+                                        bead_id_clone.as_deref(),
+                                        &persona_clone,
+                                        &backend_name,
+                                        artifact,
+                                    );
+                                }
+                            }
+                            if !artifacts.is_empty() {
+                                eprintln!("🧾 Session {} produced {} artifacts", session_id_clone, artifacts.len());
+                                emit_scoped(&handle_clone, EventScope::Session(&session_id_clone), "session-artifacts-added", &artifacts);
+                            }
+
+                            // Queue any bd commands proposed in the completed reply
+                            if let Some(agent_state) = handle_clone.try_state::<AgentState>() {
+                                let added = agent_state.proposals.ingest_chunk(
+                                    &session_id_clone,
+                                    bead_id_clone.as_deref(),
+                                    &chunk.content,
+                                    chunk.is_done,
+                                );
+                                for proposal in added {
+                                    eprintln!("📋 Session {} proposed: {}", session_id_clone, proposal.command);
+                                    emit_scoped(&handle_clone, EventScope::Session(&session_id_clone), "proposal-added", proposal);
+                                }
                             }
 
                             // Update session activity tracking
@@ -583,6 +1046,12 @@ fn run_cli_command_for_session(
                                     if !chunk.is_done {
                                         session.message_count += 1;
                                     }
+                                    if let Some(ref usage) = chunk.usage {
+                                        session.usage.add(usage);
+                                    }
+                                }
+                                if chunk.usage.is_some() {
+                                    persist_sessions(&sessions);
                                 }
 
                                 // NOTE: Don't emit session-list-changed on every chunk - causes constant flashing
@@ -606,6 +1075,8 @@ fn run_cli_command_for_session(
                                                             session.backend_id,
                                                             session.bead_id.clone(),
                                                             session.persona.clone(),
+                                                            session.project.clone(),
+                                                            session.worktree.clone(),
                                                         ))
                                                     } else {
                                                         None
@@ -621,7 +1092,7 @@ fn run_cli_command_for_session(
                                         }
                                     };
 
-                                    if let Some((next_command, cli_session_id, backend_id, bead_id, persona)) = queue_info {
+                                    if let Some((next_command, cli_session_id, backend_id, bead_id, persona, project, worktree)) = queue_info {
                                         eprintln!("📋 Command queue: executing next command for session {}", session_id_clone);
 
                                         // Execute next command in queue
@@ -634,6 +1105,8 @@ fn run_cli_command_for_session(
                                                     backend_id,
                                                     handle_for_executor.clone(),
                                                     &state,
+                                                    &project,
+                                                    worktree.as_ref(),
                                                     session_id_for_executor.clone(),
                                                     bead_id,
                                                     persona,
@@ -641,11 +1114,12 @@ fn run_cli_command_for_session(
                                                     true, // resume = true
                                                     cli_session_id,
                                                 ) {
-                                                    Ok(child) => {
+                                                    Ok(started) => {
                                                         // Update session state with new process
                                                         let mut sessions = state.sessions.lock().unwrap();
                                                         if let Some(session) = sessions.get_mut(&session_id_for_executor) {
-                                                            session.process = child;
+                                                            started.apply(session);
+                                                            session.status = SessionStatus::Running;
 
                                                             // Remove executed command from queue
                                                             if let Some(ref mut queue) = session.command_queue {
@@ -656,7 +1130,9 @@ fn run_cli_command_for_session(
                                                                     eprintln!("✅ Command queue completed for session {}", session_id_for_executor);
 
                                                                     // Emit queue-completed event
-                                                                    let _ = handle_for_executor.emit(
+                                                                    emit_scoped(
+                                                                        &handle_for_executor,
+                                                                        EventScope::Session(&session_id_for_executor),
                                                                         "headless-queue-completed",
                                                                         serde_json::json!({
                                                                             "sessionId": session_id_for_executor,
@@ -670,7 +1146,9 @@ fn run_cli_command_for_session(
                                                         eprintln!("❌ Failed to execute next command in queue: {}", e);
 
                                                         // Emit error event
-                                                        let _ = handle_for_executor.emit(
+                                                        emit_scoped(
+                                                            &handle_for_executor,
+                                                            EventScope::Session(&session_id_for_executor),
                                                             "command-error",
                                                             serde_json::json!({
                                                                 "sessionId": session_id_for_executor,
@@ -695,11 +1173,12 @@ fn run_cli_command_for_session(
                                 },
                                 is_done: chunk.is_done,
                                 session_id: chunk.session_id.clone(),
+                                usage: chunk.usage.clone(),
                             };
 
                             // Emit to session-specific channel
                             let event_name = format!("agent-chunk-{}", session_id_clone);
-                            let _ = handle_clone.emit(&event_name, html_chunk);
+                            emit_scoped(&handle_clone, EventScope::Session(&session_id_clone), &event_name, html_chunk);
                         }
                     }
                 }
@@ -711,6 +1190,7 @@ fn run_cli_command_for_session(
             content: "".to_string(),
             is_done: true,
             session_id: Some(session_id_clone.clone()),
+            usage: None,
         };
 
         // Log session end
@@ -730,26 +1210,36 @@ fn run_cli_command_for_session(
 
         // Emit to session-specific channel
         let event_name = format!("agent-chunk-{}", session_id_clone);
-        let _ = handle_clone.emit(&event_name, final_chunk);
-    });
+        emit_scoped(&handle_clone, EventScope::Session(&session_id_clone), &event_name, final_chunk);
 
-    // Spawn stderr reader thread
-    let handle_clone_stderr = app_handle.clone();
-    let session_id_clone = session_id.clone();
-    std::thread::spawn(move || {
-        let reader = BufReader::new(stderr);
-        for line in reader.lines() {
-            if let Ok(line_str) = line {
-                eprintln!("🤖 Session {} Stderr: {}", session_id_clone, line_str);
-                let _ = handle_clone_stderr.emit(
-                    "agent-stderr",
-                    format!("[{}] {}", session_id_clone, line_str),
-                );
-            }
+        // The autopilot and feature swarms wait for their sessions' runs to end
+        if let Some(agent_state) = handle_clone.try_state::<AgentState>() {
+            agent_state.autopilot.run_ended(&session_id_clone);
+            agent_state.swarms.run_ended(&handle_clone, &agent_state, &session_id_clone);
         }
     });
 
-    Ok(child)
+    // Spawn stderr reader thread (CLI backends only)
+    if let Some(stderr) = run.stderr {
+        let handle_clone_stderr = app_handle.clone();
+        let session_id_clone = session_id.clone();
+        std::thread::spawn(move || {
+            let reader = BufReader::new(stderr);
+            for line in reader.lines() {
+                if let Ok(line_str) = line {
+                    eprintln!("🤖 Session {} Stderr: {}", session_id_clone, line_str);
+                    emit_scoped(
+                        &handle_clone_stderr,
+                        EventScope::Session(&session_id_clone),
+                        "agent-stderr",
+                        format!("[{}] {}", session_id_clone, line_str),
+                    );
+                }
+            }
+        });
+    }
+
+    Ok(RunStarted { process: run.process, backend_id, cli_session_id, attempts })
 }
 
 /// DEPRECATED: Single-session CLI command runner (use run_cli_command_for_session for multi-session)
@@ -759,9 +1249,10 @@ fn run_cli_command(
     backend_id: crate::agent::plugin::BackendId,
     app_handle: AppHandle,
     state: &AgentState,
+    project: &ProjectContext,
     prompt: String,
     resume: bool,
-) -> Result<(), String> {
+) -> Result<(), BertError> {
     // Get CLI session ID from state for backward compatibility
     let cli_session_id = state.current_session_id.lock().unwrap().clone();
 
@@ -769,10 +1260,12 @@ fn run_cli_command(
     let temp_session_id = Uuid::new_v4().to_string();
 
     // Call new function and discard Child handle
-    let _child = run_cli_command_for_session(
+    let _run = run_cli_command_for_session(
         backend_id,
         app_handle,
         state,
+        project,
+        None,
         temp_session_id,
         None,                  // No bead_id for deprecated single-session mode
         "unknown".to_string(), // Default persona for deprecated mode
@@ -787,30 +1280,38 @@ fn run_cli_command(
 /// Build prompt using persona plugin system
 fn build_prompt_with_persona(
     state: &AgentState,
+    project: &ProjectContext,
     persona: &str,
     task: Option<&str>,
     bead_id: Option<&str>,
     explicit_role: Option<&str>,
-) -> Result<String, String> {
-    use crate::agent::persona::{PersonaContext, PersonaType};
+) -> Result<String, BertError> {
+    use crate::agent::persona::{PersonaContext, PersonaPlugin, PersonaType};
 
-    // Map persona string to PersonaType
+    // Map persona string to PersonaType; other names are user-defined personas
     let persona_type = match persona {
         "specialist" => PersonaType::Specialist,
         "product-manager" => PersonaType::ProductManager,
         "qa-engineer" => PersonaType::QaEngineer,
-        _ => return Err(format!("Unknown persona: {}", persona)),
+        _ => PersonaType::Custom,
     };
 
     // Get persona plugin from registry
-    let persona_plugin = state
-        .persona_registry
-        .get(persona_type)
-        .ok_or_else(|| format!("Persona {:?} not registered", persona_type))?;
+    let persona_plugin: Arc<dyn PersonaPlugin> = if persona_type == PersonaType::Custom {
+        state
+            .persona_registry
+            .get_custom(persona)
+            .ok_or_else(|| BertError::InvalidInput(format!("Unknown persona: {}", persona)))?
+    } else {
+        state
+            .persona_registry
+            .get(persona_type)
+            .ok_or_else(|| BertError::Agent(format!("Persona {:?} not registered", persona_type)))?
+    };
 
     // Get bead and extract information
     let (bead_json, issue_type, bead_role) = if let Some(bid) = bead_id {
-        let bead = crate::bd::get_bead_by_id(bid).map_err(|e| e.to_string())?;
+        let bead = crate::bd::get_bead_by_id(project, bid)?;
         let json = serde_json::to_string_pretty(&bead).ok();
         let issue_type = Some(bead.issue_type.clone());
         let role = get_role_from_bead(&bead);
@@ -828,16 +1329,27 @@ fn build_prompt_with_persona(
         issue_type,
         bead_id: bead_id.map(String::from),
         role,
+        project_path: Some(project.project_path().to_path_buf()),
     };
 
     // Get template name from persona plugin
     let template_name = persona_plugin.get_template_name(&context)?;
 
-    // Load template using TemplateLoader
-    let template_content = state
-        .template_loader
-        .load_template(persona_type.as_str(), &template_name)
-        .map_err(|e| format!("Failed to load template: {}", e))?;
+    // Load template using TemplateLoader; the project's own copy wins
+    let loader = match project.repo_root() {
+        Ok(root) => state.template_loader.clone().with_project_root(crate::agent::templates::project_templates_dir(&root)),
+        Err(_) => state.template_loader.clone(),
+    };
+    let template_content = if persona_type == PersonaType::Custom {
+        loader.load_user_template(persona, &template_name)
+    } else {
+        loader.load_template(persona_type.as_str(), &template_name)
+    }
+    .map_err(|e| BertError::Agent(format!("Failed to load template: {}", e)))?;
+
+    let variables = crate::agent::templates::variables_for(project, bead_id)?;
+    let template_content = crate::agent::templates::render(&template_content, &variables)
+        .map_err(|e| BertError::InvalidInput(format!("Template '{}' of persona '{}': {}", template_name, persona, e)))?;
 
     // Build final prompt using persona plugin
     let prompt = persona_plugin.build_prompt(template_content, &context, bead_json);
@@ -846,8 +1358,11 @@ fn build_prompt_with_persona(
 }
 
 #[tauri::command]
-pub fn start_agent_session(
+#[allow(clippy::too_many_arguments)]
+pub async fn start_agent_session(
     app_handle: AppHandle,
+    window: tauri::Window,
+    project_handle: Option<String>,
     state: State<'_, AgentState>,
     settings_state: State<'_, crate::SettingsState>,
     persona: String,
@@ -855,63 +1370,44 @@ pub fn start_agent_session(
     bead_id: Option<String>,
     cli_backend: Option<String>,
     role: Option<String>,
-) -> Result<String, String> {
+    worktree: Option<bool>,
+) -> Result<String, BertError> {
     // Generate unique session ID
     let session_id = Uuid::new_v4().to_string();
 
-    // Parse CLI backend from argument, falling back to persisted setting
-    let backend = if let Some(backend_str) = cli_backend {
-        match backend_str.to_lowercase().as_str() {
-            "gemini" => crate::agent::plugin::BackendId::Gemini,
-            "claude" | "claude-code" => crate::agent::plugin::BackendId::ClaudeCode,
-            _ => {
-                let settings = settings_state.settings.lock().map_err(|e| e.to_string())?;
-                settings.cli_backend
-            }
-        }
-    } else {
-        let settings = settings_state.settings.lock().map_err(|e| e.to_string())?;
-        settings.cli_backend
+    // Parse CLI backend from argument, falling back to the persona's backend
+    // hint and then the persisted setting
+    let backend_hint = state.persona_registry.get_custom(&persona).and_then(|p| p.manifest.backend);
+    let backend = match (cli_backend.as_deref(), backend_hint) {
+        (None, Some(hint)) => hint,
+        (name, _) => resolve_backend(name, &settings_state)?,
     };
 
-    // Build initial prompt using persona plugin system
-    let prompt = build_prompt_with_persona(&state, &persona, task.as_deref(), bead_id.as_deref(), role.as_deref())?;
+    // Ask for a summary block that is captured into the bead's notes
+    let auto_capture = settings_state.settings.lock().map_err(|e| e.to_string())?.auto_capture_summary;
 
-    // Start the CLI process for this session
-    // Pass our session_id to the CLI backend so it uses the same UUID
-    let child = run_cli_command_for_session(
-        backend,
-        app_handle.clone(),
-        &state,
-        session_id.clone(),
-        bead_id.clone(),
-        persona.clone(),
-        prompt,
-        false,                    // resume = false for new session
-        Some(session_id.clone()), // Pass our session_id to the CLI backend
-    )?;
+    // Sessions run in the project open in the window that started them
+    let project = crate::project::context_for(&window, project_handle.as_deref())?;
 
-    // Create SessionState and store in HashMap
-    let now = SystemTime::now();
-    let session_state = SessionState {
-        process: child,
-        bead_id: bead_id.clone(),
-        persona: persona.clone(),
-        backend_id: backend,
-        status: SessionStatus::Running,
-        created_at: now,
-        cli_session_id: Some(session_id.clone()), // Store the session ID for resuming
-        execution_mode: ExecutionMode::Interactive,
-        command_queue: None,
-        total_commands: None,
-        last_activity: now,
-        has_unread: false,
-        message_count: 0,
+    let start = QueuedStart {
+        session_id: session_id.clone(),
+        project,
+        backend,
+        persona,
+        task,
+        bead_id,
+        role,
+        worktree: worktree.unwrap_or(false),
+        auto_capture,
+        queued_at: chrono::Utc::now().to_rfc3339(),
     };
-
-    {
-        let mut sessions = state.sessions.lock().unwrap();
-        sessions.insert(session_id.clone(), session_state);
+    // At the concurrency limit the session waits under this ID instead
+    match state.queue.enqueue(&app_handle, &state, start) {
+        Ok(position) => {
+            eprintln!("⏳ Session {} queued at position {}", session_id, position);
+            return Ok(session_id);
+        }
+        Err(start) => state.queue.start_reserved(&app_handle, &state, *start)?,
     }
 
     // Update active session ID
@@ -920,51 +1416,254 @@ pub fn start_agent_session(
         *active = Some(session_id.clone());
     }
 
-    // Emit session-created event
-    let _ = app_handle.emit("session-created", session_id.clone());
-
-    // Emit session-list-changed event
-    {
-        let sessions = state.sessions.lock().unwrap();
-        emit_session_list_changed(&app_handle, &sessions);
-    }
-
     // Emit active-session-changed event
     let _ = app_handle.emit("active-session-changed", session_id.clone());
 
     Ok(session_id)
 }
 
+/// Who work started from the app is assigned to: the configured owner name,
+/// else the project's git user
+fn work_owner(project: &ProjectContext, settings_state: &crate::SettingsState) -> Result<Option<String>, BertError> {
+    let configured = settings_state.settings.lock().map_err(|e| e.to_string())?.owner_name.clone();
+    Ok(configured.or_else(|| crate::comments::author(project).0))
+}
+
+/// "Work on this": claim `bead_id` for the local user (status in_progress,
+/// owner from settings or git) and start an implementation session on it,
+/// which picks the feature or task template from the bead's type. If the
+/// session fails to start the claim is undone. Returns the session ID.
 #[tauri::command]
-#[allow(non_snake_case)]
-pub fn send_agent_message(
+#[allow(clippy::too_many_arguments)]
+pub async fn work_on_bead(
     app_handle: AppHandle,
-    sessionId: String,
-    message: String,
+    window: tauri::Window,
+    project_handle: Option<String>,
     state: State<'_, AgentState>,
-) -> Result<(), String> {
-    // Get session info from HashMap
-    let (backend_id, cli_session_id, bead_id, persona) = {
-        let sessions = state.sessions.lock().unwrap();
-        let session = sessions
-            .get(&sessionId)
-            .ok_or_else(|| format!("Session {} not found", sessionId))?;
-
-        (
-            session.backend_id,
-            session.cli_session_id.clone(),
-            session.bead_id.clone(),
-            session.persona.clone(),
-        )
+    settings_state: State<'_, crate::SettingsState>,
+    bead_id: String,
+    persona: Option<String>,
+    cli_backend: Option<String>,
+) -> Result<String, BertError> {
+    let persona = persona.unwrap_or_else(|| "product-manager".to_string());
+    let backend_hint = state.persona_registry.get_custom(&persona).and_then(|p| p.manifest.backend);
+    let backend = match (cli_backend.as_deref(), backend_hint) {
+        (None, Some(hint)) => hint,
+        (name, _) => resolve_backend(name, &settings_state)?,
     };
+    let auto_capture = settings_state.settings.lock().map_err(|e| e.to_string())?.auto_capture_summary;
+    let project = crate::project::context_for(&window, project_handle.as_deref())?;
+
+    // Claim first, so the session's prompt shows the bead as taken
+    let original = crate::bd::get_bead_by_id(&project, &bead_id)?;
+    let mut claimed = original.clone();
+    claimed.status = "in_progress".to_string();
+    claimed.owner = work_owner(&project, &settings_state)?.or(claimed.owner);
+    let claim = crate::bd::BeadOp::Update { bead: claimed };
+    crate::transitions::check(&project, &claim, false)?;
+    crate::bd::apply_bead_op(&project, &claim)?;
+    crate::autocommit::note(&project, crate::autocommit::op_message(&claim, &bead_id));
+    crate::project::emit_beads_updated(&app_handle, &project);
 
-    // Resume the session with the message
-    let child = run_cli_command_for_session(
-        backend_id,
-        app_handle.clone(),
+    let session_id = Uuid::new_v4().to_string();
+    let started = start_session_internal(
+        &app_handle,
         &state,
-        sessionId.clone(),
-        bead_id,
+        project.clone(),
+        session_id.clone(),
+        backend,
+        persona,
+        Some("implement".to_string()),
+        Some(bead_id.clone()),
+        None,
+        None,
+        false,
+        auto_capture,
+    );
+    if let Err(e) = started {
+        eprintln!("⚠️  Session for {} failed to start ({}); releasing the claim", bead_id, e);
+        let release = crate::bd::BeadOp::Update { bead: original };
+        match crate::bd::apply_bead_op(&project, &release) {
+            Ok(_) => crate::autocommit::note(&project, crate::autocommit::op_message(&release, &bead_id)),
+            Err(undo) => eprintln!("  ⚠️  Failed to release {}: {}", bead_id, undo),
+        }
+        crate::project::emit_beads_updated(&app_handle, &project);
+        return Err(e);
+    }
+
+    *state.active_session_id.lock().unwrap() = Some(session_id.clone());
+    let _ = app_handle.emit("active-session-changed", session_id.clone());
+    eprintln!("🛠️  Working on {} in session {}", bead_id, session_id);
+    Ok(session_id)
+}
+
+/// Backend named by the caller, falling back to the persisted setting for
+/// a missing or unknown name
+pub(crate) fn resolve_backend(
+    name: Option<&str>,
+    settings_state: &crate::SettingsState,
+) -> Result<crate::agent::plugin::BackendId, BertError> {
+    match name.map(str::to_lowercase).as_deref() {
+        Some("gemini") => Ok(crate::agent::plugin::BackendId::Gemini),
+        Some("claude") | Some("claude-code") => Ok(crate::agent::plugin::BackendId::ClaudeCode),
+        Some("ollama") => Ok(crate::agent::plugin::BackendId::Ollama),
+        _ => {
+            let settings = settings_state.settings.lock().map_err(|e| e.to_string())?;
+            Ok(settings.cli_backend)
+        }
+    }
+}
+
+/// Start an interactive session with a caller-chosen ID in `project`
+///
+/// Shared by `start_agent_session` and the autopilot. Unlike the command it
+/// does not focus the new session. `context` is JSON appended to the prompt
+/// for sessions that aren't about a single bead.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn start_session_internal(
+    app_handle: &AppHandle,
+    state: &AgentState,
+    project: ProjectContext,
+    session_id: String,
+    backend: crate::agent::plugin::BackendId,
+    persona: String,
+    task: Option<String>,
+    bead_id: Option<String>,
+    role: Option<String>,
+    context: Option<String>,
+    worktree: bool,
+    auto_capture: bool,
+) -> Result<(), BertError> {
+    // Build initial prompt using persona plugin system
+    let mut prompt = build_prompt_with_persona(state, &project, &persona, task.as_deref(), bead_id.as_deref(), role.as_deref())?;
+    if let Some(context) = context {
+        prompt.push_str("\nContext JSON:\n```json\n");
+        prompt.push_str(&context);
+        prompt.push_str("\n```\n");
+    }
+
+    // Ask for a summary block that is captured into the bead's notes
+    if bead_id.is_some() && auto_capture {
+        prompt.push_str(&crate::agent::summary::prompt_instructions());
+    }
+
+    // Isolated sessions get their own worktree so parallel sessions don't clobber each other's files
+    let worktree = if worktree {
+        Some(crate::agent::worktree::create(&project.repo_root()?, &session_id)?)
+    } else {
+        None
+    };
+
+    // Record the files the agent touches from before it starts
+    let root = match worktree {
+        Some(ref worktree) => worktree.path.clone(),
+        None => project.repo_root()?,
+    };
+    let files = SessionFiles::watch(app_handle, &session_id, &root);
+
+    // Start the CLI process for this session
+    // Pass our session_id to the CLI backend so it uses the same UUID
+    let started = run_cli_command_for_session(
+        backend,
+        app_handle.clone(),
+        state,
+        &project,
+        worktree.as_ref(),
+        session_id.clone(),
+        bead_id.clone(),
+        persona.clone(),
+        prompt,
+        false,                    // resume = false for new session
+        Some(session_id.clone()), // Pass our session_id to the CLI backend
+    )
+    .inspect_err(|_| {
+        if let Some(ref worktree) = worktree {
+            let _ = worktree.remove();
+        }
+    })?;
+
+    // Create SessionState and store in HashMap
+    let now = SystemTime::now();
+    let session_state = SessionState {
+        process: started.process,
+        bead_id,
+        persona,
+        backend_id: started.backend_id,
+        status: SessionStatus::Running,
+        created_at: now,
+        cli_session_id: started.cli_session_id, // Store the session ID for resuming
+        execution_mode: ExecutionMode::Interactive,
+        command_queue: None,
+        total_commands: None,
+        last_activity: now,
+        has_unread: false,
+        message_count: 0,
+        project,
+        usage: TokenUsage::default(),
+        worktree,
+        files,
+        spawn_attempts: started.attempts,
+    };
+
+    {
+        let mut sessions = state.sessions.lock().unwrap();
+        sessions.insert(session_id.clone(), session_state);
+    }
+
+    // Emit session-created event
+    let _ = app_handle.emit("session-created", session_id.clone());
+
+    // Emit session-list-changed event
+    {
+        let sessions = state.sessions.lock().unwrap();
+        emit_session_list_changed(app_handle, &sessions);
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn send_agent_message(
+    app_handle: AppHandle,
+    sessionId: String,
+    message: String,
+    state: State<'_, AgentState>,
+) -> Result<(), BertError> {
+    // The CLI is already running interactively in a terminal
+    if state.pty.has_session(&sessionId) {
+        return Err(BertError::InvalidInput(format!(
+            "Session {} is open in a terminal; close it before sending messages",
+            sessionId
+        )));
+    }
+
+    // Get session info from HashMap
+    let (backend_id, cli_session_id, bead_id, persona, project, worktree) = {
+        let sessions = state.sessions.lock().unwrap();
+        let session = sessions
+            .get(&sessionId)
+            .ok_or_else(|| BertError::NotFound(format!("Session {} not found", sessionId)))?;
+
+        (
+            session.backend_id,
+            session.cli_session_id.clone(),
+            session.bead_id.clone(),
+            session.persona.clone(),
+            session.project.clone(),
+            session.worktree.clone(),
+        )
+    };
+
+    // Resume the session with the message
+    let started = run_cli_command_for_session(
+        backend_id,
+        app_handle.clone(),
+        &state,
+        &project,
+        worktree.as_ref(),
+        sessionId.clone(),
+        bead_id,
         persona,
         message,
         true, // resume = true
@@ -975,9 +1674,18 @@ pub fn send_agent_message(
     {
         let mut sessions = state.sessions.lock().unwrap();
         if let Some(session_state) = sessions.get_mut(&sessionId) {
-            session_state.process = child;
+            started.apply(session_state);
             session_state.status = SessionStatus::Running;
+            // Restored sessions aren't watched until they resume
+            let root = match session_state.worktree {
+                Some(ref worktree) => Some(worktree.path.clone()),
+                None => session_state.project.repo_root().ok(),
+            };
+            if let Some(root) = root {
+                session_state.files.ensure_watching(&app_handle, &sessionId, &root);
+            }
         }
+        persist_sessions(&sessions);
     }
 
     Ok(())
@@ -989,18 +1697,24 @@ pub fn stop_agent_session(
     app_handle: AppHandle,
     sessionId: String,
     state: State<'_, AgentState>,
-) -> Result<(), String> {
+) -> Result<(), BertError> {
     // Remove session from HashMap and get the Child handle
     let child = {
         let mut sessions = state.sessions.lock().unwrap();
         let session_state = sessions
             .remove(&sessionId)
-            .ok_or_else(|| format!("Session {} not found", sessionId))?;
+            .ok_or_else(|| BertError::NotFound(format!("Session {} not found", sessionId)))?;
         session_state.process
     };
 
     // Kill the process
-    kill_process_group(child.id());
+    if let Some(child) = child {
+        kill_process_group(child.id());
+    }
+    state.proposals.clear_buffer(&sessionId);
+    state.approvals.clear_session(&sessionId);
+    state.conversations.lock().unwrap().remove(&sessionId);
+    let _ = state.pty.kill(&sessionId);
 
     // Update active session if this was the active one
     {
@@ -1038,7 +1752,7 @@ pub fn stop_agent_session(
 pub fn interrupt_agent_session(
     sessionId: String,
     state: State<'_, AgentState>,
-) -> Result<(), String> {
+) -> Result<(), BertError> {
     eprintln!("⏸️  Interrupting session: {}", sessionId);
 
     // Get the process ID without removing the session from HashMap
@@ -1046,8 +1760,12 @@ pub fn interrupt_agent_session(
         let sessions = state.sessions.lock().unwrap();
         let session_state = sessions
             .get(&sessionId)
-            .ok_or_else(|| format!("Session {} not found", sessionId))?;
-        session_state.process.id()
+            .ok_or_else(|| BertError::NotFound(format!("Session {} not found", sessionId)))?;
+        session_state
+            .process
+            .as_ref()
+            .map(|p| p.id())
+            .ok_or_else(|| BertError::InvalidInput(format!("Session {} has no running process", sessionId)))?
     };
 
     // Send SIGINT to interrupt (but not kill) the process
@@ -1061,19 +1779,177 @@ pub fn interrupt_agent_session(
     Ok(())
 }
 
+/// Validate an agent-suggested command line against the project and preview it
+fn preview_bd_command_line(
+    command: &str,
+    project: &ProjectContext,
+) -> Result<(BdCommand, crate::agent::bd_command::CommandPreview), BertError> {
+    let cmd = BdCommand::parse(command)?;
+    let index = crate::bead_index::index_for(&project.beads_path()?)?;
+    let preview = crate::agent::bd_command::preview(&cmd, &index)?;
+    Ok((cmd, preview))
+}
+
+/// Run an agent-suggested `bd` command line in `project`, as the bead
+/// operations it maps to: transition rules and dependency cycles are checked
+/// first, and an unavailable environment queues them like any other edit.
+/// Returns the IDs of the affected beads, one per line.
+fn execute_bd_command_line(command: &str, project: &ProjectContext, app_handle: &AppHandle) -> Result<String, BertError> {
+    let (cmd, preview) = preview_bd_command_line(command, project)?;
+    let ops = cmd.bead_ops(&crate::bead_index::index_for(&project.beads_path()?)?)?;
+    for op in &ops {
+        crate::transitions::check(project, op, false)?;
+        if let crate::bd::BeadOp::AddDependency { issue_id, depends_on_id, dep_type } = op {
+            crate::bd::validate_new_dependency(&crate::bd::load_beads(project)?, issue_id, depends_on_id, dep_type)?;
+        }
+    }
+
+    eprintln!("✅ Executing suggestion: {}", preview.summary);
+    let mut affected = Vec::new();
+    for op in ops {
+        affected.push(crate::bd::submit_op(project, app_handle, op)?);
+    }
+    Ok(affected.join("\n"))
+}
+
+/// Describe what approving a suggested `bd` command would change, without
+/// running it. Fails for commands `approve_suggestion` would refuse.
 #[tauri::command]
-pub fn approve_suggestion(command: String) -> Result<String, String> {
-    if !command.starts_with("bd ") {
-        return Err("Only 'bd' commands are supported for approval".to_string());
+pub fn preview_suggestion(
+    command: String,
+    window: tauri::Window,
+    project_handle: Option<String>,
+) -> Result<crate::agent::bd_command::CommandPreview, BertError> {
+    Ok(preview_bd_command_line(&command, &crate::project::context_for(&window, project_handle.as_deref())?)?.1)
+}
+
+#[tauri::command]
+pub fn approve_suggestion(
+    command: String,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    app_handle: AppHandle,
+) -> Result<String, BertError> {
+    execute_bd_command_line(&command, &crate::project::context_for(&window, project_handle.as_deref())?, &app_handle)
+}
+
+/// List proposed `bd` commands awaiting approval, oldest first
+///
+/// # Arguments
+/// * `session_id` - Only list proposals from this session (all sessions if None)
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn list_pending_proposals(
+    sessionId: Option<String>,
+    state: State<'_, AgentState>,
+) -> Result<Vec<crate::agent::proposals::Proposal>, BertError> {
+    Ok(state.proposals.pending(sessionId.as_deref()))
+}
+
+/// Execute a pending proposal and record the outcome
+///
+/// Runs in the project of the session that proposed it, or the calling
+/// window's project (or tab `project_handle`) if that session is gone. A failed command is recorded as
+/// `failed` rather than returned as an error, so `approve_all` can continue.
+fn approve_proposal_internal(
+    app_handle: &AppHandle,
+    window: &tauri::Window,
+    project_handle: Option<&str>,
+    state: &AgentState,
+    proposal_id: &str,
+) -> Result<crate::agent::proposals::Proposal, BertError> {
+    use crate::agent::proposals::ProposalStatus;
+
+    let proposal = state
+        .proposals
+        .get(proposal_id)
+        .ok_or_else(|| BertError::NotFound(format!("Proposal {} not found", proposal_id)))?;
+    if proposal.status != ProposalStatus::Pending {
+        return Err(BertError::InvalidInput(format!("Proposal {} was already resolved", proposal_id)));
     }
 
-    let args: Vec<String> = command
-        .split_whitespace()
-        .skip(1)
-        .map(|s| s.to_string())
-        .collect();
+    let project = {
+        let sessions = state.sessions.lock().unwrap();
+        sessions.get(&proposal.session_id).map(|s| s.project.clone())
+    };
+    let project = match project {
+        Some(project) => project,
+        None => crate::project::context_for(window, project_handle)?,
+    };
 
-    crate::bd::execute_bd(args)
+    let (status, result) = match execute_bd_command_line(&proposal.command, &project, app_handle) {
+        Ok(output) => (ProposalStatus::Approved, output),
+        Err(e) => (ProposalStatus::Failed, e.to_string()),
+    };
+    eprintln!("📋 Proposal {} {:?}: {}", proposal_id, status, proposal.command);
+
+    let updated = state
+        .proposals
+        .resolve(proposal_id, status, Some(result))
+        .ok_or_else(|| BertError::NotFound(format!("Proposal {} not found", proposal_id)))?;
+    emit_scoped(app_handle, EventScope::Session(&updated.session_id), "proposal-resolved", updated.clone());
+    Ok(updated)
+}
+
+/// Approve (execute) a single proposal
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn approve_proposal(
+    app_handle: AppHandle,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    proposalId: String,
+    state: State<'_, AgentState>,
+) -> Result<crate::agent::proposals::Proposal, BertError> {
+    approve_proposal_internal(&app_handle, &window, project_handle.as_deref(), &state, &proposalId)
+}
+
+/// Reject a proposal without executing it
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn reject_proposal(
+    app_handle: AppHandle,
+    proposalId: String,
+    state: State<'_, AgentState>,
+) -> Result<crate::agent::proposals::Proposal, BertError> {
+    use crate::agent::proposals::ProposalStatus;
+
+    let pending = state
+        .proposals
+        .get(&proposalId)
+        .map(|p| p.status == ProposalStatus::Pending)
+        .ok_or_else(|| BertError::NotFound(format!("Proposal {} not found", proposalId)))?;
+    if !pending {
+        return Err(BertError::InvalidInput(format!("Proposal {} was already resolved", proposalId)));
+    }
+
+    let updated = state
+        .proposals
+        .resolve(&proposalId, ProposalStatus::Rejected, None)
+        .ok_or_else(|| BertError::NotFound(format!("Proposal {} not found", proposalId)))?;
+    emit_scoped(&app_handle, EventScope::Session(&updated.session_id), "proposal-resolved", updated.clone());
+    Ok(updated)
+}
+
+/// Approve every pending proposal of a session, in the order they were made
+///
+/// # Returns
+/// The resolved proposals (approved or failed)
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn approve_all(
+    app_handle: AppHandle,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    sessionId: String,
+    state: State<'_, AgentState>,
+) -> Result<Vec<crate::agent::proposals::Proposal>, BertError> {
+    state
+        .proposals
+        .pending(Some(&sessionId))
+        .iter()
+        .map(|p| approve_proposal_internal(&app_handle, &window, project_handle.as_deref(), &state, &p.id))
+        .collect()
 }
 
 /// List all active agent sessions
@@ -1081,7 +1957,7 @@ pub fn approve_suggestion(command: String) -> Result<String, String> {
 /// Returns a vector of SessionInfo containing metadata for each active session.
 /// Sessions are sorted by creation time (oldest first).
 #[tauri::command]
-pub fn list_active_sessions(state: State<'_, AgentState>) -> Result<Vec<SessionInfo>, String> {
+pub fn list_active_sessions(state: State<'_, AgentState>) -> Result<Vec<SessionInfo>, BertError> {
     let sessions = state.sessions.lock().unwrap();
     println!(
         "🔍 list_active_sessions: HashMap has {} entries",
@@ -1109,7 +1985,7 @@ pub fn list_active_sessions(state: State<'_, AgentState>) -> Result<Vec<SessionI
 ///
 /// Returns the session ID of the currently focused/active session, or None if no session is active.
 #[tauri::command]
-pub fn get_active_session_id(state: State<'_, AgentState>) -> Result<Option<String>, String> {
+pub fn get_active_session_id(state: State<'_, AgentState>) -> Result<Option<String>, BertError> {
     let active_id = state.active_session_id.lock().unwrap();
     Ok(active_id.clone())
 }
@@ -1130,12 +2006,12 @@ pub fn switch_active_session(
     app_handle: AppHandle,
     sessionId: String,
     state: State<'_, AgentState>,
-) -> Result<(), String> {
+) -> Result<(), BertError> {
     // Validate that the session exists
     {
         let sessions = state.sessions.lock().unwrap();
         if !sessions.contains_key(&sessionId) {
-            return Err(format!("Session {} not found", sessionId));
+            return Err(BertError::NotFound(format!("Session {} not found", sessionId)));
         }
     }
 
@@ -1162,22 +2038,49 @@ pub fn switch_active_session(
 ///
 /// # Arguments
 /// * `session_id` - The session ID to terminate
+/// * `worktree_action` - What to do with the session's worktree (isolated sessions only)
 ///
 /// # Errors
-/// Returns an error if the session doesn't exist
+/// Returns an error if the session doesn't exist, or if its worktree has
+/// changes and no worktree action was given (the session is left running)
 #[tauri::command]
 #[allow(non_snake_case)]
 pub fn terminate_session(
     app_handle: AppHandle,
     sessionId: String,
+    worktreeAction: Option<WorktreeAction>,
     state: State<'_, AgentState>,
-) -> Result<(), String> {
-    eprintln!("🗑️  Terminating session: {}", sessionId);
+) -> Result<(), BertError> {
+    terminate_session_internal(&app_handle, &state, &sessionId, worktreeAction)
+}
+
+/// Tear down a session and apply `worktree_action` to its worktree
+///
+/// Shared by `terminate_session` and the autopilot.
+pub(crate) fn terminate_session_internal(
+    app_handle: &AppHandle,
+    state: &AgentState,
+    session_id: &str,
+    worktree_action: Option<WorktreeAction>,
+) -> Result<(), BertError> {
+    eprintln!("🗑️  Terminating session: {}", session_id);
+
+    // Check the worktree before tearing anything down
+    let worktree = {
+        let sessions = state.sessions.lock().unwrap();
+        let session_state = sessions
+            .get(session_id)
+            .ok_or_else(|| BertError::NotFound(format!("Session {} not found", session_id)))?;
+        session_state.worktree.clone()
+    };
+    if let Some(ref worktree) = worktree {
+        worktree.guard(worktree_action, session_id)?;
+    }
 
     // Close any windows associated with this session (before terminating)
     // Get WindowRegistry from app state
     if let Some(window_registry) = app_handle.try_state::<crate::window::WindowRegistry>() {
-        if let Some(window_label) = window_registry.get_window_label(&sessionId) {
+        if let Some(window_label) = window_registry.get_window_label(session_id) {
             eprintln!("  🪟 Closing window for session: {}", window_label);
 
             // Close the window
@@ -1186,7 +2089,7 @@ pub fn terminate_session(
             }
 
             // Unregister from WindowRegistry
-            window_registry.unregister_by_session(&sessionId);
+            window_registry.unregister_by_session(session_id);
         }
     }
 
@@ -1194,18 +2097,23 @@ pub fn terminate_session(
     let child = {
         let mut sessions = state.sessions.lock().unwrap();
         let session_state = sessions
-            .remove(&sessionId)
-            .ok_or_else(|| format!("Session {} not found", sessionId))?;
+            .remove(session_id)
+            .ok_or_else(|| BertError::NotFound(format!("Session {} not found", session_id)))?;
         session_state.process
     };
 
     // Kill the process
-    kill_process_group(child.id());
+    if let Some(child) = child {
+        kill_process_group(child.id());
+    }
+    state.proposals.clear_buffer(session_id);
+    state.conversations.lock().unwrap().remove(session_id);
+    let _ = state.pty.kill(session_id);
 
     // Update active session if needed
     {
         let mut active_id = state.active_session_id.lock().unwrap();
-        if active_id.as_ref() == Some(&sessionId) {
+        if active_id.as_deref() == Some(session_id) {
             // Find another session to make active
             let sessions = state.sessions.lock().unwrap();
             *active_id = sessions.keys().next().cloned();
@@ -1215,18 +2123,68 @@ pub fn terminate_session(
     // Emit events
     let _ = app_handle.emit(
         "session-terminated",
-        serde_json::json!({ "sessionId": sessionId }),
+        serde_json::json!({ "sessionId": session_id }),
     );
 
     // Emit session list changed event
     {
         let sessions = state.sessions.lock().unwrap();
-        emit_session_list_changed(&app_handle, &sessions);
+        emit_session_list_changed(app_handle, &sessions);
+    }
+
+    // Merge or discard the worktree now that the CLI can no longer write to it
+    if let Some(worktree) = worktree {
+        worktree.finish(worktree_action, session_id).map_err(|e| {
+            BertError::Agent(format!("Session terminated, but its worktree was not cleaned up: {}", e))
+        })?;
     }
 
     Ok(())
 }
 
+/// Merge or discard the worktree of an isolated session
+///
+/// Works for live sessions (which keep running in the repo root afterwards)
+/// and for sessions terminated with the worktree kept.
+///
+/// # Arguments
+/// * `session_id` - The session whose worktree to clean up
+/// * `action` - Merge, discard or keep; without one, only an unchanged worktree is removed
+///
+/// # Errors
+/// Returns an error if there is no worktree for the session, it has changes
+/// and no action was given, or the merge conflicts (the worktree is kept)
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn cleanup_session_worktree(
+    app_handle: AppHandle,
+    sessionId: String,
+    action: Option<WorktreeAction>,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    state: State<'_, AgentState>,
+) -> Result<(), BertError> {
+    let live = {
+        let sessions = state.sessions.lock().unwrap();
+        sessions.get(&sessionId).map(|s| s.worktree.clone())
+    };
+    let worktree = match live {
+        Some(worktree) => worktree,
+        None => crate::agent::worktree::find(&crate::project::context_for(&window, project_handle.as_deref())?.repo_root()?, &sessionId)?,
+    }
+    .ok_or_else(|| BertError::NotFound(format!("Session {} has no worktree", sessionId)))?;
+
+    worktree.finish(action, &sessionId)?;
+    if action != Some(WorktreeAction::Keep) {
+        let mut sessions = state.sessions.lock().unwrap();
+        if let Some(session) = sessions.get_mut(&sessionId) {
+            session.worktree = None;
+        }
+        emit_session_list_changed(&app_handle, &sessions);
+    }
+    Ok(())
+}
+
 /// Get session conversation history from JSONL log files
 ///
 /// Reads conversation logs from ~/.bp6/sessions/<bead-id>/<session-id>-*.jsonl
@@ -1246,7 +2204,7 @@ pub fn terminate_session(
 pub fn get_session_history(
     sessionId: String,
     beadId: Option<String>,
-) -> Result<Vec<ConversationMessage>, String> {
+) -> Result<Vec<ConversationMessage>, BertError> {
     // Get home directory
     let home_dir = dirs::home_dir().ok_or_else(|| "Could not find home directory".to_string())?;
 
@@ -1261,7 +2219,7 @@ pub fn get_session_history(
     // Find the log file for this session
     let log_file = if session_dir.exists() {
         fs::read_dir(&session_dir)
-            .map_err(|e| format!("Failed to read session directory: {}", e))?
+            .map_err(|e| BertError::Io(format!("Failed to read session directory: {}", e)))?
             .filter_map(|entry| entry.ok())
             .map(|entry| entry.path())
             .find(|path| {
@@ -1277,15 +2235,15 @@ pub fn get_session_history(
     };
 
     let log_file_path = log_file.ok_or_else(|| {
-        format!(
+        BertError::NotFound(format!(
             "No log file found for session {} in {}",
             sessionId,
             session_dir.display()
-        )
+        ))
     })?;
 
     // Read and parse JSONL file
-    let file = File::open(&log_file_path).map_err(|e| format!("Failed to open log file: {}", e))?;
+    let file = File::open(&log_file_path).map_err(|e| BertError::Io(format!("Failed to open log file: {}", e)))?;
     let reader = BufReader::new(file);
 
     let mut messages = Vec::new();
@@ -1293,11 +2251,11 @@ pub fn get_session_history(
     let mut current_timestamp: Option<String> = None;
 
     for line in reader.lines() {
-        let line = line.map_err(|e| format!("Failed to read line: {}", e))?;
+        let line = line.map_err(|e| BertError::Io(format!("Failed to read line: {}", e)))?;
 
         // Parse LogEvent
         let event: LogEvent =
-            serde_json::from_str(&line).map_err(|e| format!("Failed to parse log event: {}", e))?;
+            serde_json::from_str(&line).map_err(|e| BertError::Parse(format!("Failed to parse log event: {}", e)))?;
 
         match event.event_type {
             LogEventType::Message => {
@@ -1331,7 +2289,7 @@ pub fn get_session_history(
                     });
                 }
             }
-            LogEventType::SessionStart => {
+            LogEventType::SessionStart | LogEventType::Artifact => {
                 // Metadata event - skip
                 continue;
             }
@@ -1346,6 +2304,431 @@ pub fn get_session_history(
     Ok(messages)
 }
 
+// ============================================================================
+// Session log browser
+// ============================================================================
+
+/// Maximum number of matching snippets returned per log by `search_session_logs`
+const MAX_SNIPPETS_PER_LOG: usize = 5;
+/// Characters of context kept on each side of a search match
+const SNIPPET_CONTEXT: usize = 60;
+
+/// Summary of one persisted session log
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionLogSummary {
+    /// Absolute path of the JSONL file (pass to `get_session_transcript`)
+    pub log_path: String,
+    pub session_id: String,
+    pub bead_id: Option<String>,
+    pub persona: String,
+    pub backend: String,
+    /// Timestamp of the first event (ISO 8601)
+    pub started_at: Option<String>,
+    /// Timestamp of the last event (ISO 8601)
+    pub last_event_at: Option<String>,
+    /// Number of user and assistant messages
+    pub message_count: usize,
+}
+
+/// One entry in a reconstructed transcript
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptEntry {
+    /// user, assistant or system (session start/end markers)
+    pub role: String,
+    /// Raw markdown content
+    pub content: String,
+    /// Timestamp (ISO 8601 format)
+    pub timestamp: String,
+    /// False for an assistant reply that was cut off before its end marker
+    pub complete: bool,
+}
+
+/// A full session log, ready for replay in the UI
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionTranscript {
+    pub summary: SessionLogSummary,
+    pub entries: Vec<TranscriptEntry>,
+}
+
+/// Transcript entry matching a search query
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptSnippet {
+    pub role: String,
+    pub timestamp: String,
+    /// Text around the match
+    pub snippet: String,
+}
+
+/// A session log with at least one entry matching a search query
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionLogMatch {
+    pub summary: SessionLogSummary,
+    pub snippets: Vec<TranscriptSnippet>,
+}
+
+/// Root of all session logs (~/.bp6/sessions)
+pub(crate) fn sessions_root() -> Result<PathBuf, BertError> {
+    let home_dir = dirs::home_dir().ok_or_else(|| BertError::Io("Could not find home directory".to_string()))?;
+    Ok(home_dir.join(".bp6").join("sessions"))
+}
+
+/// JSONL files directly inside `dir`
+pub(crate) fn log_files_in(dir: &std::path::Path) -> Result<Vec<PathBuf>, BertError> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    Ok(fs::read_dir(dir)
+        .map_err(|e| BertError::Io(format!("Failed to read {}: {}", dir.display(), e)))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("jsonl"))
+        .collect())
+}
+
+/// Read the events of a session log, skipping malformed lines
+pub(crate) fn read_log_events(path: &std::path::Path) -> Result<Vec<LogEvent>, BertError> {
+    let file = File::open(path).map_err(|e| BertError::Io(format!("Failed to open {}: {}", path.display(), e)))?;
+    let mut events = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(|e| BertError::Io(format!("Failed to read {}: {}", path.display(), e)))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<LogEvent>(&line) {
+            Ok(event) => events.push(event),
+            Err(e) => eprintln!("⚠️  Skipping malformed log line in {}: {}", path.display(), e),
+        }
+    }
+    Ok(events)
+}
+
+/// Rebuild a transcript from log events. Chunks are joined into one assistant
+/// message per turn; each turn ends at a SessionEnd event.
+fn build_transcript(log_path: &std::path::Path, events: &[LogEvent]) -> SessionTranscript {
+    let mut entries = Vec::new();
+    let mut reply: Option<(String, String)> = None;
+
+    for event in events {
+        match event.event_type {
+            LogEventType::SessionStart => entries.push(TranscriptEntry {
+                role: "system".to_string(),
+                content: format!("Session started ({} via {})", event.persona, event.backend),
+                timestamp: event.timestamp.clone(),
+                complete: true,
+            }),
+            LogEventType::Message => entries.push(TranscriptEntry {
+                role: "user".to_string(),
+                content: event.content.clone(),
+                timestamp: event.timestamp.clone(),
+                complete: true,
+            }),
+            LogEventType::Chunk => {
+                let (content, _) = reply.get_or_insert_with(|| (String::new(), event.timestamp.clone()));
+                content.push_str(&event.content);
+            }
+            LogEventType::SessionEnd => {
+                if let Some((content, timestamp)) = reply.take() {
+                    entries.push(TranscriptEntry {
+                        role: "assistant".to_string(),
+                        content,
+                        timestamp,
+                        complete: true,
+                    });
+                }
+            }
+            // Copies of parts of the reply before them
+            LogEventType::Artifact => {}
+        }
+    }
+
+    // The app closed (or the CLI died) mid-reply
+    if let Some((content, timestamp)) = reply {
+        entries.push(TranscriptEntry {
+            role: "assistant".to_string(),
+            content,
+            timestamp,
+            complete: false,
+        });
+    }
+
+    let first = events.first();
+    let file_stem = log_path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+    let summary = SessionLogSummary {
+        log_path: log_path.to_string_lossy().to_string(),
+        // File names are <session-id>-<unix-timestamp>.jsonl
+        session_id: first
+            .map(|e| e.session_id.clone())
+            .unwrap_or_else(|| file_stem.rsplit_once('-').map(|(id, _)| id).unwrap_or(file_stem).to_string()),
+        bead_id: first.and_then(|e| e.bead_id.clone()),
+        persona: first.map(|e| e.persona.clone()).unwrap_or_default(),
+        backend: first.map(|e| e.backend.clone()).unwrap_or_default(),
+        started_at: first.map(|e| e.timestamp.clone()),
+        last_event_at: events.last().map(|e| e.timestamp.clone()),
+        message_count: entries.iter().filter(|e| e.role != "system").count(),
+    };
+
+    SessionTranscript { summary, entries }
+}
+
+/// Snippets of `transcript` entries containing `query` (ASCII case-insensitive)
+fn search_transcript(transcript: &SessionTranscript, query: &str) -> Vec<TranscriptSnippet> {
+    let needle = query.to_ascii_lowercase();
+    let mut snippets = Vec::new();
+
+    for entry in transcript.entries.iter().filter(|e| e.role != "system") {
+        // ASCII lowercasing keeps byte offsets aligned with the original
+        let Some(pos) = entry.content.to_ascii_lowercase().find(&needle) else {
+            continue;
+        };
+
+        let mut start = pos.saturating_sub(SNIPPET_CONTEXT);
+        while !entry.content.is_char_boundary(start) {
+            start -= 1;
+        }
+        let mut end = (pos + needle.len() + SNIPPET_CONTEXT).min(entry.content.len());
+        while !entry.content.is_char_boundary(end) {
+            end += 1;
+        }
+
+        let mut snippet = entry.content[start..end].trim().to_string();
+        if start > 0 {
+            snippet.insert(0, '…');
+        }
+        if end < entry.content.len() {
+            snippet.push('…');
+        }
+        snippets.push(TranscriptSnippet {
+            role: entry.role.clone(),
+            timestamp: entry.timestamp.clone(),
+            snippet,
+        });
+        if snippets.len() == MAX_SNIPPETS_PER_LOG {
+            break;
+        }
+    }
+    snippets
+}
+
+/// Newest first, by start time
+fn sort_newest_first<T>(items: &mut [T], summary: impl Fn(&T) -> &SessionLogSummary) {
+    items.sort_by(|a, b| summary(b).started_at.cmp(&summary(a).started_at));
+}
+
+/// List the persisted session logs of a bead, newest first
+///
+/// # Arguments
+/// * `bead_id` - The bead ID (None lists untracked sessions)
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn list_session_logs(beadId: Option<String>) -> Result<Vec<SessionLogSummary>, BertError> {
+    let dir = sessions_root()?.join(beadId.as_deref().unwrap_or("untracked"));
+
+    let mut summaries = Vec::new();
+    for path in log_files_in(&dir)? {
+        let events = read_log_events(&path)?;
+        summaries.push(build_transcript(&path, &events).summary);
+    }
+    sort_newest_first(&mut summaries, |s| s);
+    Ok(summaries)
+}
+
+/// Load a persisted session log as a structured transcript
+///
+/// # Arguments
+/// * `log_path` - Path returned by `list_session_logs` or `search_session_logs`
+///
+/// # Errors
+/// Returns an error if the path is outside ~/.bp6/sessions or cannot be read
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn get_session_transcript(logPath: String) -> Result<SessionTranscript, BertError> {
+    let root = sessions_root()?;
+    let path = PathBuf::from(&logPath);
+    let canonical = path
+        .canonicalize()
+        .map_err(|_| BertError::NotFound(format!("Session log not found: {}", logPath)))?;
+    let inside_root = root.canonicalize().map(|r| canonical.starts_with(r)).unwrap_or(false);
+    if !inside_root || canonical.extension().and_then(|e| e.to_str()) != Some("jsonl") {
+        return Err(BertError::InvalidInput(format!("Not a session log: {}", logPath)));
+    }
+
+    let events = read_log_events(&canonical)?;
+    Ok(build_transcript(&canonical, &events))
+}
+
+/// Search every persisted session log for `query`, newest sessions first
+#[tauri::command]
+pub fn search_session_logs(query: String) -> Result<Vec<SessionLogMatch>, BertError> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let root = sessions_root()?;
+    let mut matches = Vec::new();
+    for dir in session_dirs(&root)? {
+        for path in log_files_in(&dir)? {
+            let transcript = build_transcript(&path, &read_log_events(&path)?);
+            let snippets = search_transcript(&transcript, query);
+            if !snippets.is_empty() {
+                matches.push(SessionLogMatch { summary: transcript.summary, snippets });
+            }
+        }
+    }
+    sort_newest_first(&mut matches, |m| &m.summary);
+
+    eprintln!("🔎 search_session_logs({:?}): {} sessions matched", query, matches.len());
+    Ok(matches)
+}
+
+/// Every persisted session log, across all bead directories
+pub(crate) fn session_log_paths() -> Result<Vec<PathBuf>, BertError> {
+    let mut paths = Vec::new();
+    for dir in session_dirs(&sessions_root()?)? {
+        paths.extend(log_files_in(&dir)?);
+    }
+    Ok(paths)
+}
+
+/// Per-bead subdirectories of the sessions root
+fn session_dirs(root: &std::path::Path) -> Result<Vec<PathBuf>, BertError> {
+    if !root.exists() {
+        return Ok(Vec::new());
+    }
+    Ok(fs::read_dir(root)
+        .map_err(|e| BertError::Io(format!("Failed to read {}: {}", root.display(), e)))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect())
+}
+
+// ============================================================================
+// Token usage and cost
+// ============================================================================
+
+/// Usage of all agent sessions that worked on one bead
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BeadCost {
+    /// None for sessions not tied to a bead
+    pub bead_id: Option<String>,
+    pub usage: TokenUsage,
+    /// Distinct agent sessions
+    pub session_count: usize,
+    /// CLI runs that reported usage
+    pub run_count: usize,
+}
+
+/// Agent usage across every logged session, per bead
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentCostReport {
+    /// Most expensive first
+    pub beads: Vec<BeadCost>,
+    pub total: TokenUsage,
+}
+
+/// Usage recorded on a log event (the final event of each CLI run)
+fn event_usage(event: &LogEvent) -> Option<TokenUsage> {
+    let usage = event.metadata.as_ref()?.get("usage")?;
+    serde_json::from_value(usage.clone()).ok()
+}
+
+/// Sum the usage recorded in session logs per bead
+fn build_cost_report(logs: &[Vec<LogEvent>]) -> AgentCostReport {
+    let mut by_bead: HashMap<Option<String>, (BeadCost, std::collections::HashSet<String>)> = HashMap::new();
+    let mut total = TokenUsage::default();
+
+    for event in logs.iter().flatten() {
+        let Some(usage) = event_usage(event) else {
+            continue;
+        };
+        let (cost, sessions) = by_bead.entry(event.bead_id.clone()).or_insert_with(|| {
+            let cost = BeadCost {
+                bead_id: event.bead_id.clone(),
+                usage: TokenUsage::default(),
+                session_count: 0,
+                run_count: 0,
+            };
+            (cost, std::collections::HashSet::new())
+        });
+        cost.usage.add(&usage);
+        cost.run_count += 1;
+        sessions.insert(event.session_id.clone());
+        cost.session_count = sessions.len();
+        total.add(&usage);
+    }
+
+    let mut beads: Vec<BeadCost> = by_bead.into_values().map(|(cost, _)| cost).collect();
+    beads.sort_by(|a, b| {
+        let cost = |c: &BeadCost| c.usage.cost_usd.unwrap_or(0.0);
+        cost(b)
+            .total_cmp(&cost(a))
+            .then_with(|| b.usage.total_tokens().cmp(&a.usage.total_tokens()))
+            .then_with(|| a.bead_id.cmp(&b.bead_id))
+    });
+    AgentCostReport { beads, total }
+}
+
+/// Token usage accumulated by an open session
+///
+/// # Errors
+/// Returns an error if the session is not registered
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn get_session_usage(sessionId: String, state: State<'_, AgentState>) -> Result<TokenUsage, BertError> {
+    let sessions = state.sessions.lock().unwrap();
+    sessions
+        .get(&sessionId)
+        .map(|session| session.usage.clone())
+        .ok_or_else(|| BertError::NotFound(format!("Session not found: {}", sessionId)))
+}
+
+/// Files the session created, modified or deleted in its working directory,
+/// relative to it
+///
+/// # Errors
+/// Returns an error if the session is not registered
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn get_session_changed_files(sessionId: String, state: State<'_, AgentState>) -> Result<Vec<ChangedFile>, BertError> {
+    let sessions = state.sessions.lock().unwrap();
+    sessions
+        .get(&sessionId)
+        .map(|session| session.files.changed())
+        .ok_or_else(|| BertError::NotFound(format!("Session not found: {}", sessionId)))
+}
+
+/// Token usage and cost of every logged agent run, aggregated per bead
+///
+/// Reads the usage recorded in the session logs, so terminated sessions and
+/// earlier app runs are included.
+#[tauri::command]
+pub fn get_agent_cost_report() -> Result<AgentCostReport, BertError> {
+    let root = sessions_root()?;
+    let mut logs = Vec::new();
+    for dir in session_dirs(&root)? {
+        for path in log_files_in(&dir)? {
+            logs.push(read_log_events(&path)?);
+        }
+    }
+
+    let report = build_cost_report(&logs);
+    eprintln!(
+        "💰 get_agent_cost_report: {} beads, {} tokens, ${:.4}",
+        report.beads.len(),
+        report.total.total_tokens(),
+        report.total.cost_usd.unwrap_or(0.0)
+    );
+    Ok(report)
+}
+
 /// Mark a session as read (clear unread indicator)
 ///
 /// Clears the has_unread flag for a session when the user views it.
@@ -1364,13 +2747,13 @@ pub fn mark_session_read(
     app_handle: AppHandle,
     sessionId: String,
     state: State<'_, AgentState>,
-) -> Result<(), String> {
+) -> Result<(), BertError> {
     // Update has_unread flag
     {
         let mut sessions = state.sessions.lock().unwrap();
         let session = sessions
             .get_mut(&sessionId)
-            .ok_or_else(|| format!("Session {} not found", sessionId))?;
+            .ok_or_else(|| BertError::NotFound(format!("Session {} not found", sessionId)))?;
 
         session.has_unread = false;
 
@@ -1401,33 +2784,30 @@ pub fn mark_session_read(
 /// # Errors
 /// Returns error if backend not found, command queue empty, or process spawn fails
 #[tauri::command]
-pub fn start_agent_session_headless(
+#[allow(clippy::too_many_arguments)]
+pub async fn start_agent_session_headless(
     app_handle: AppHandle,
+    window: tauri::Window,
+    project_handle: Option<String>,
     state: State<'_, AgentState>,
     settings_state: State<'_, crate::SettingsState>,
     bead_id: Option<String>,
     persona: String,
     backend_id: String,
     commands: Vec<String>,
-) -> Result<SessionInfo, String> {
+) -> Result<SessionInfo, BertError> {
     // Validate command queue
     if commands.is_empty() {
-        return Err("Command queue cannot be empty".to_string());
+        return Err(BertError::InvalidInput("Command queue cannot be empty".to_string()));
     }
 
     // Generate unique session ID
     let session_id = Uuid::new_v4().to_string();
 
-    // Parse backend ID
-    let backend = match backend_id.to_lowercase().as_str() {
-        "gemini" => crate::agent::plugin::BackendId::Gemini,
-        "claude" | "claude-code" => crate::agent::plugin::BackendId::ClaudeCode,
-        _ => {
-            // Fallback to settings preference
-            let settings = settings_state.settings.lock().map_err(|e| e.to_string())?;
-            settings.cli_backend
-        }
-    };
+    // Parse backend ID, falling back to settings preference
+    let backend = resolve_backend(Some(&backend_id), &settings_state)?;
+
+    let project = crate::project::context_for(&window, project_handle.as_deref())?;
 
     // Get first command from queue
     let first_command = commands[0].clone();
@@ -1437,12 +2817,16 @@ pub fn start_agent_session_headless(
         None
     };
 
+    let files = SessionFiles::watch(&app_handle, &session_id, &project.repo_root()?);
+
     // Start the CLI process with first command
     // Pass session_id so the CLI backend uses the same UUID
-    let child = run_cli_command_for_session(
+    let started = run_cli_command_for_session(
         backend,
         app_handle.clone(),
         &state,
+        &project,
+        None,
         session_id.clone(),
         bead_id.clone(),
         persona.clone(),
@@ -1455,50 +2839,35 @@ pub fn start_agent_session_headless(
     let now = SystemTime::now();
     let total_commands_count = commands.len();
     let session_state = SessionState {
-        process: child,
+        process: started.process,
         bead_id: bead_id.clone(),
         persona: persona.clone(),
-        backend_id: backend,
+        backend_id: started.backend_id,
         status: SessionStatus::Running,
         created_at: now,
-        cli_session_id: Some(session_id.clone()),
+        cli_session_id: started.cli_session_id,
         execution_mode: ExecutionMode::Headless,
         command_queue: remaining_commands,
         total_commands: Some(total_commands_count),
         last_activity: now,
         has_unread: false,
         message_count: 0,
+        project,
+        usage: TokenUsage::default(),
+        worktree: None,
+        files,
+        spawn_attempts: started.attempts,
     };
 
+    // Build SessionInfo for return
+    let info = session_info(&session_id, &session_state);
+
     // Store in sessions map
     {
         let mut sessions = state.sessions.lock().unwrap();
         sessions.insert(session_id.clone(), session_state);
     }
 
-    // Build SessionInfo for return
-    let session_info = SessionInfo {
-        session_id: session_id.clone(),
-        bead_id,
-        persona,
-        backend_id: backend,
-        status: SessionStatus::Running,
-        created_at: now
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs(),
-        cli_session_id: Some(session_id.clone()),
-        execution_mode: ExecutionMode::Headless,
-        commands_remaining: Some(total_commands_count - 1), // First command is already executing
-        total_commands: Some(total_commands_count),
-        last_activity: now
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs(),
-        has_unread: false,
-        message_count: 0,
-    };
-
     // Emit session-created event
     let _ = app_handle.emit("session-created", session_id.clone());
 
@@ -1508,7 +2877,7 @@ pub fn start_agent_session_headless(
         emit_session_list_changed(&app_handle, &sessions);
     }
 
-    Ok(session_info)
+    Ok(info)
 }
 
 // ============================================================================
@@ -1531,7 +2900,7 @@ pub fn start_agent_session_headless(
 pub fn find_recent_session(
     beadId: Option<String>,
     persona: String,
-) -> Result<Option<super::session_index::SessionMetadata>, String> {
+) -> Result<Option<super::session_index::SessionMetadata>, BertError> {
     let index = super::session_index::SessionIndex::load()?;
     Ok(index.get_session(beadId.as_deref(), &persona).cloned())
 }
@@ -1555,7 +2924,7 @@ pub fn record_session_for_resume(
     sessionId: String,
     cliSessionId: Option<String>,
     backendId: String,
-) -> Result<(), String> {
+) -> Result<(), BertError> {
     let mut index = super::session_index::SessionIndex::load()?;
     index.record_session(
         beadId.as_deref(),
@@ -1576,7 +2945,7 @@ pub fn record_session_for_resume(
 pub fn touch_session(
     beadId: Option<String>,
     persona: String,
-) -> Result<(), String> {
+) -> Result<(), BertError> {
     let mut index = super::session_index::SessionIndex::load()?;
     index.touch_session(beadId.as_deref(), &persona);
     index.save()?;
@@ -1610,7 +2979,7 @@ pub fn handover_to_interactive(
     app_handle: AppHandle,
     sessionId: String,
     state: State<'_, AgentState>,
-) -> Result<(), String> {
+) -> Result<(), BertError> {
     eprintln!("🔄 Handover to interactive: {}", sessionId);
 
     // Update session state
@@ -1618,11 +2987,11 @@ pub fn handover_to_interactive(
         let mut sessions = state.sessions.lock().unwrap();
         let session = sessions
             .get_mut(&sessionId)
-            .ok_or_else(|| format!("Session {} not found", sessionId))?;
+            .ok_or_else(|| BertError::NotFound(format!("Session {} not found", sessionId)))?;
 
         // Validate current mode
         if session.execution_mode != ExecutionMode::Headless {
-            return Err("Session is not in headless mode".to_string());
+            return Err(BertError::InvalidInput("Session is not in headless mode".to_string()));
         }
 
         // Update execution mode to Interactive
@@ -1651,3 +3020,338 @@ pub fn handover_to_interactive(
 
     Ok(())
 }
+
+/// Re-register the sessions that were open when the app last closed
+///
+/// Reads ~/.bp6/sessions/active.json and adds every session that has a CLI
+/// session ID (and whose project still exists) as a stopped session with no
+/// process. `send_agent_message` then continues it through the backend's
+/// `--resume` support. Headless command queues are not restored; the
+/// sessions come back as interactive. Sessions already registered are skipped,
+/// so calling this more than once is harmless.
+///
+/// # Returns
+/// The sessions that were restored
+#[tauri::command]
+pub fn restore_sessions(
+    app_handle: AppHandle,
+    state: State<'_, AgentState>,
+) -> Result<Vec<SessionInfo>, BertError> {
+    let persisted = crate::agent::session_store::load().map_err(BertError::Io)?;
+    let from_secs = |secs: u64| std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs);
+
+    let mut restored = Vec::new();
+    {
+        let mut sessions = state.sessions.lock().unwrap();
+        for entry in persisted {
+            if !entry.is_resumable() || sessions.contains_key(&entry.info.session_id) {
+                continue;
+            }
+            let info = entry.info;
+            let session_state = SessionState {
+                process: None,
+                bead_id: info.bead_id.clone(),
+                persona: info.persona.clone(),
+                backend_id: info.backend_id,
+                status: SessionStatus::Stopped,
+                created_at: from_secs(info.created_at),
+                cli_session_id: info.cli_session_id.clone(),
+                execution_mode: ExecutionMode::Interactive,
+                command_queue: None,
+                total_commands: None,
+                last_activity: from_secs(info.last_activity),
+                has_unread: false,
+                message_count: info.message_count,
+                project: ProjectContext::new(entry.project_path),
+                usage: info.usage.clone(),
+                worktree: info.worktree.clone(),
+                // Watched again once a message resumes it
+                files: SessionFiles::default(),
+                spawn_attempts: info.spawn_attempts.clone(),
+            };
+            restored.push(session_info(&info.session_id, &session_state));
+            sessions.insert(info.session_id, session_state);
+        }
+
+        if !restored.is_empty() {
+            eprintln!("♻️  Restored {} agent sessions", restored.len());
+            emit_session_list_changed(&app_handle, &sessions);
+        }
+    }
+
+    Ok(restored)
+}
+
+// ============================================================================
+// Session health
+// ============================================================================
+
+/// How often the reaper checks session processes
+const REAP_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Exit code of a CLI that handled SIGINT (128 + 2)
+const SIGINT_EXIT_CODE: i32 = 130;
+
+/// Payload of `session-status-changed`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionStatusChange {
+    pub session_id: String,
+    pub status: SessionStatus,
+    /// `None` if the process was ended by a signal
+    pub exit_code: Option<i32>,
+}
+
+/// Status of a session whose CLI process exited
+///
+/// Every message spawns a process that exits once it has replied, so a clean
+/// exit (or an interrupt) leaves the session stopped and resumable. Anything
+/// else means the CLI crashed.
+fn status_after_exit(exit: &std::process::ExitStatus) -> SessionStatus {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if exit.signal() == Some(2) {
+            return SessionStatus::Stopped;
+        }
+    }
+    if exit.success() || exit.code() == Some(SIGINT_EXIT_CODE) {
+        SessionStatus::Stopped
+    } else {
+        SessionStatus::Error
+    }
+}
+
+/// Wait on the processes of sessions whose run ended and update their status
+fn reap_exited(sessions: &mut HashMap<String, SessionState>) -> Vec<SessionStatusChange> {
+    let mut changes = Vec::new();
+    for (session_id, session) in sessions.iter_mut() {
+        let Some(child) = session.process.as_mut() else { continue };
+        let (status, exit_code) = match child.try_wait() {
+            Ok(None) => continue,
+            Ok(Some(exit)) => (status_after_exit(&exit), exit.code()),
+            Err(e) => {
+                eprintln!("⚠️  Failed to check process of session {}: {}", session_id, e);
+                (SessionStatus::Error, None)
+            }
+        };
+        session.process = None;
+        session.status = status.clone();
+        changes.push(SessionStatusChange {
+            session_id: session_id.clone(),
+            status,
+            exit_code,
+        });
+    }
+    changes
+}
+
+/// "specialist on bp6-12", naming a session in notifications
+fn session_label(session: &SessionState) -> String {
+    match &session.bead_id {
+        Some(bead_id) => format!("{} on {}", session.persona, bead_id),
+        None => session.persona.clone(),
+    }
+}
+
+/// Start the background thread that reaps exited session processes
+///
+/// Sessions whose CLI crashed are removed from the session list (their
+/// worktree is kept); sessions whose run simply finished stay for follow-up
+/// messages. Both raise a desktop notification. Queued sessions are started
+/// as slots free up.
+pub fn spawn_session_reaper(app_handle: AppHandle) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(REAP_INTERVAL);
+        let Some(state) = app_handle.try_state::<AgentState>() else { continue };
+
+        let changes: Vec<(SessionStatusChange, String)> = {
+            let mut sessions = state.sessions.lock().unwrap();
+            let changes = reap_exited(&mut sessions);
+            if !changes.is_empty() {
+                emit_session_list_changed(&app_handle, &sessions);
+            }
+            changes
+                .into_iter()
+                .map(|change| {
+                    let label = sessions.get(&change.session_id).map(session_label).unwrap_or_default();
+                    (change, label)
+                })
+                .collect()
+        };
+
+        for (change, label) in changes {
+            let _ = app_handle.emit("session-status-changed", change.clone());
+            if change.status != SessionStatus::Error {
+                // Interrupted runs were stopped by the user, who needs no reminder
+                if change.exit_code == Some(0) {
+                    crate::notifications::notify(NotificationKind::SessionFinished, "Agent finished", &label);
+                }
+                continue;
+            }
+
+            eprintln!("💀 Session {} CLI exited unexpectedly (code {:?})", change.session_id, change.exit_code);
+            let exit = change.exit_code.map(|code| format!("exit code {}", code)).unwrap_or_else(|| "killed".to_string());
+            crate::notifications::notify(NotificationKind::SessionErrored, "Agent crashed", &format!("{} ({})", label, exit));
+            if let Err(e) = terminate_session_internal(&app_handle, &state, &change.session_id, Some(WorktreeAction::Keep)) {
+                eprintln!("⚠️  Failed to remove dead session {}: {}", change.session_id, e);
+            }
+        }
+
+        // Ended runs (or a raised limit) free slots for queued sessions
+        state.queue.drain(&app_handle, &state);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(event_type: LogEventType, content: &str, timestamp: &str) -> LogEvent {
+        LogEvent {
+            timestamp: timestamp.to_string(),
+            session_id: "s1".to_string(),
+            bead_id: Some("bp6-1".to_string()),
+            persona: "specialist".to_string(),
+            backend: "claude".to_string(),
+            event_type,
+            content: content.to_string(),
+            metadata: None,
+        }
+    }
+
+    fn session_with(process: Child) -> SessionState {
+        SessionState {
+            process: Some(process),
+            bead_id: None,
+            persona: "specialist".to_string(),
+            backend_id: BackendId::ClaudeCode,
+            status: SessionStatus::Running,
+            created_at: SystemTime::now(),
+            cli_session_id: None,
+            execution_mode: ExecutionMode::Interactive,
+            command_queue: None,
+            total_commands: None,
+            last_activity: SystemTime::now(),
+            has_unread: false,
+            message_count: 0,
+            project: ProjectContext::new(PathBuf::from("/tmp")),
+            usage: TokenUsage::default(),
+            worktree: None,
+            files: SessionFiles::default(),
+            spawn_attempts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_reaper_updates_exited_sessions() {
+        let spawn = |script: &str| Command::new("sh").args(["-c", script]).spawn().unwrap();
+        let mut sessions = HashMap::new();
+        sessions.insert("done".to_string(), session_with(spawn("exit 0")));
+        sessions.insert("crashed".to_string(), session_with(spawn("exit 3")));
+        sessions.insert("interrupted".to_string(), session_with(spawn("exit 130")));
+        sessions.insert("busy".to_string(), session_with(spawn("sleep 5")));
+
+        let mut changes = Vec::new();
+        for _ in 0..100 {
+            changes.extend(reap_exited(&mut sessions));
+            if changes.len() == 3 {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        changes.sort_by(|a, b| a.session_id.cmp(&b.session_id));
+
+        let summary: Vec<_> = changes.iter().map(|c| (c.session_id.as_str(), c.status.clone(), c.exit_code)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("crashed", SessionStatus::Error, Some(3)),
+                ("done", SessionStatus::Stopped, Some(0)),
+                ("interrupted", SessionStatus::Stopped, Some(130)),
+            ]
+        );
+        assert!(sessions["done"].process.is_none());
+        assert_eq!(sessions["busy"].status, SessionStatus::Running);
+
+        let mut busy = sessions.remove("busy").unwrap().process.unwrap();
+        let _ = busy.kill();
+        let _ = busy.wait();
+    }
+
+    #[test]
+    fn test_transcript_joins_chunks_and_flags_cut_off_reply() {
+        let events = vec![
+            event(LogEventType::SessionStart, "", "t0"),
+            event(LogEventType::Message, "Fix the parser", "t1"),
+            event(LogEventType::Chunk, "Looking ", "t2"),
+            event(LogEventType::Chunk, "now.", "t3"),
+            event(LogEventType::SessionEnd, "", "t4"),
+            event(LogEventType::Chunk, "Still wor", "t5"),
+        ];
+        let transcript = build_transcript(std::path::Path::new("/logs/s1-100.jsonl"), &events);
+
+        let roles: Vec<&str> = transcript.entries.iter().map(|e| e.role.as_str()).collect();
+        assert_eq!(roles, vec!["system", "user", "assistant", "assistant"]);
+        assert_eq!(transcript.entries[2].content, "Looking now.");
+        assert_eq!(transcript.entries[2].timestamp, "t2");
+        assert!(!transcript.entries[3].complete);
+        assert_eq!(transcript.summary.message_count, 3);
+        assert_eq!(transcript.summary.last_event_at.as_deref(), Some("t5"));
+    }
+
+    #[test]
+    fn test_search_is_case_insensitive_with_context() {
+        let long_reply = format!("{}The PARSER is fixed{}", "x".repeat(100), "y".repeat(100));
+        let events = vec![
+            event(LogEventType::Message, "Fix the parser", "t1"),
+            event(LogEventType::Chunk, &long_reply, "t2"),
+            event(LogEventType::SessionEnd, "", "t3"),
+        ];
+        let transcript = build_transcript(std::path::Path::new("/logs/s1-100.jsonl"), &events);
+
+        let snippets = search_transcript(&transcript, "parser");
+        assert_eq!(snippets.len(), 2);
+        assert_eq!(snippets[0].snippet, "Fix the parser");
+        assert!(snippets[1].snippet.starts_with('…') && snippets[1].snippet.ends_with('…'));
+        assert!(snippets[1].snippet.contains("PARSER is fixed"));
+        assert!(search_transcript(&transcript, "missing").is_empty());
+    }
+
+    #[test]
+    fn test_cost_report_sums_usage_per_bead() {
+        let run_end = |session_id: &str, bead_id: Option<&str>, output_tokens: u64, cost_usd: Option<f64>| {
+            let usage = TokenUsage {
+                input_tokens: 10,
+                output_tokens,
+                cost_usd,
+                ..TokenUsage::default()
+            };
+            LogEvent {
+                session_id: session_id.to_string(),
+                bead_id: bead_id.map(String::from),
+                metadata: Some(serde_json::json!({ "usage": usage })),
+                ..event(LogEventType::SessionEnd, "", "t9")
+            }
+        };
+        let logs = vec![
+            vec![event(LogEventType::Chunk, "hi", "t1"), run_end("s1", Some("bp6-1"), 100, Some(0.25))],
+            vec![run_end("s1", Some("bp6-1"), 50, Some(0.5)), run_end("s2", Some("bp6-1"), 5, None)],
+            vec![run_end("s3", Some("bp6-2"), 900, None)],
+            vec![run_end("s4", None, 1, Some(1.0))],
+        ];
+
+        let report = build_cost_report(&logs);
+        let order: Vec<Option<&str>> = report.beads.iter().map(|b| b.bead_id.as_deref()).collect();
+        assert_eq!(order, vec![None, Some("bp6-1"), Some("bp6-2")]);
+
+        let bp6_1 = &report.beads[1];
+        assert_eq!(bp6_1.run_count, 3);
+        assert_eq!(bp6_1.session_count, 2);
+        assert_eq!(bp6_1.usage.output_tokens, 155);
+        assert_eq!(bp6_1.usage.cost_usd, Some(0.75));
+        assert_eq!(report.beads[2].usage.cost_usd, None, "Gemini-only beads have no cost");
+        assert_eq!(report.total.input_tokens, 50);
+        assert_eq!(report.total.cost_usd, Some(1.75));
+    }
+}
@@ -0,0 +1,289 @@
+//! Queue of agent sessions waiting for a free slot
+//!
+//! Many agents at once can exhaust API quotas and CPU. With
+//! `maxConcurrentSessions` set, `start_agent_session` doesn't start a session
+//! while that many sessions have a CLI run in progress: the request waits
+//! here under the session ID it will get, and its place in line is broadcast
+//! as `session-queue-changed`. The session reaper starts queued sessions in
+//! order as runs end or the limit is raised, each on its own thread so a
+//! slow startup doesn't hold up reaping.
+//!
+//! A session being started has no running CLI yet, so the queue reserves its
+//! slot under the queue lock until the start returns; otherwise concurrent
+//! starts could all see a free slot.
+//!
+//! Only new sessions wait. Follow-up messages to existing sessions are never
+//! held back, and the autopilot and feature swarms keep their own limits.
+
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::agent::plugin::BackendId;
+use crate::agent::session::{AgentState, SessionStatus};
+use crate::error::BertError;
+use crate::project::ProjectContext;
+
+/// Everything needed to start a queued session later
+pub(crate) struct QueuedStart {
+    pub session_id: String,
+    pub project: ProjectContext,
+    pub backend: BackendId,
+    pub persona: String,
+    pub task: Option<String>,
+    pub bead_id: Option<String>,
+    pub role: Option<String>,
+    pub worktree: bool,
+    pub auto_capture: bool,
+    pub queued_at: String,
+}
+
+/// A waiting session, as shown to the user
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedSession {
+    pub session_id: String,
+    /// 1 for the next one to start
+    pub position: usize,
+    pub persona: String,
+    pub task: Option<String>,
+    pub bead_id: Option<String>,
+    pub backend_id: BackendId,
+    pub project_path: String,
+    pub queued_at: String,
+}
+
+#[derive(Default)]
+struct Waiting {
+    entries: VecDeque<QueuedStart>,
+    /// Slots reserved for sessions that are being started
+    starting: usize,
+}
+
+#[derive(Default)]
+pub struct SessionQueue {
+    waiting: Mutex<Waiting>,
+}
+
+/// Sessions with a CLI run in progress
+fn running_sessions(state: &AgentState) -> usize {
+    state.sessions.lock().unwrap().values().filter(|s| s.status == SessionStatus::Running).count()
+}
+
+/// The configured session limit, if any
+fn session_limit(settings_state: &crate::SettingsState) -> Option<usize> {
+    settings_state.settings.lock().ok().and_then(|s| s.max_concurrent_sessions)
+}
+
+fn summarize(entries: &VecDeque<QueuedStart>) -> Vec<QueuedSession> {
+    entries
+        .iter()
+        .enumerate()
+        .map(|(i, start)| QueuedSession {
+            session_id: start.session_id.clone(),
+            position: i + 1,
+            persona: start.persona.clone(),
+            task: start.task.clone(),
+            bead_id: start.bead_id.clone(),
+            backend_id: start.backend,
+            project_path: start.project.project_path().display().to_string(),
+            queued_at: start.queued_at.clone(),
+        })
+        .collect()
+}
+
+impl SessionQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn list(&self) -> Vec<QueuedSession> {
+        summarize(&self.waiting.lock().unwrap().entries)
+    }
+
+    /// Queue `start` if it has to wait: sessions are already waiting, or
+    /// `running` plus the sessions being started has reached `limit`.
+    /// Returns its position when queued, or reserves a slot and hands
+    /// `start` back to be started now (see `start_reserved`).
+    fn admit(&self, start: QueuedStart, running: usize, limit: Option<usize>) -> Result<usize, Box<QueuedStart>> {
+        let mut waiting = self.waiting.lock().unwrap();
+        let full = limit.is_some_and(|limit| running + waiting.starting >= limit);
+        if limit.is_none() || (waiting.entries.is_empty() && !full) {
+            waiting.starting += 1;
+            return Err(Box::new(start));
+        }
+        waiting.entries.push_back(start);
+        Ok(waiting.entries.len())
+    }
+
+    /// Remove a waiting session; false if it isn't queued (any more)
+    fn cancel(&self, session_id: &str) -> bool {
+        let entries = &mut self.waiting.lock().unwrap().entries;
+        let before = entries.len();
+        entries.retain(|start| start.session_id != session_id);
+        entries.len() != before
+    }
+
+    /// The next session to start, with its slot reserved, if one waits and
+    /// the limit allows it
+    fn next(&self, running: usize, limit: Option<usize>) -> Option<QueuedStart> {
+        let mut waiting = self.waiting.lock().unwrap();
+        if limit.is_some_and(|limit| running + waiting.starting >= limit) {
+            return None;
+        }
+        let start = waiting.entries.pop_front()?;
+        waiting.starting += 1;
+        Some(start)
+    }
+
+    /// Free the slot reserved by `admit` or `next`
+    fn release(&self) {
+        let mut waiting = self.waiting.lock().unwrap();
+        waiting.starting = waiting.starting.saturating_sub(1);
+    }
+
+    /// Start a session `enqueue` handed back, then free its reserved slot;
+    /// by then a started session counts as running
+    pub(crate) fn start_reserved(&self, app_handle: &AppHandle, state: &AgentState, start: QueuedStart) -> Result<(), BertError> {
+        let result = start_now(app_handle, state, start);
+        self.release();
+        result
+    }
+
+    fn emit_changed(&self, app_handle: &AppHandle) {
+        let _ = app_handle.emit("session-queue-changed", self.list());
+    }
+
+    /// Queue a session that can't start yet and tell the UI where it stands.
+    /// Returns its position, or `start` when it can run right away, to be
+    /// passed to `start_reserved`.
+    pub(crate) fn enqueue(&self, app_handle: &AppHandle, state: &AgentState, start: QueuedStart) -> Result<usize, Box<QueuedStart>> {
+        let limit = app_handle.try_state::<crate::SettingsState>().and_then(|s| session_limit(&s));
+        let position = self.admit(start, running_sessions(state), limit)?;
+        self.emit_changed(app_handle);
+        Ok(position)
+    }
+
+    /// Start waiting sessions while the limit allows, each on its own thread;
+    /// called by the session reaper
+    pub fn drain(&self, app_handle: &AppHandle, state: &AgentState) {
+        let limit = app_handle.try_state::<crate::SettingsState>().and_then(|s| session_limit(&s));
+        let mut started = false;
+        while let Some(start) = self.next(running_sessions(state), limit) {
+            started = true;
+            eprintln!("⏳ Starting queued session {} ({})", start.session_id, start.persona);
+            let app_handle = app_handle.clone();
+            std::thread::spawn(move || {
+                let state = app_handle.state::<AgentState>();
+                let session_id = start.session_id.clone();
+                if let Err(e) = state.queue.start_reserved(&app_handle, &state, start) {
+                    eprintln!("❌ Failed to start queued session {}: {}", session_id, e);
+                    let _ = app_handle.emit("session-queue-failed", serde_json::json!({ "sessionId": session_id, "error": e }));
+                }
+            });
+        }
+        if started {
+            self.emit_changed(app_handle);
+        }
+    }
+}
+
+/// Start `start` right away
+fn start_now(app_handle: &AppHandle, state: &AgentState, start: QueuedStart) -> Result<(), BertError> {
+    crate::agent::session::start_session_internal(
+        app_handle,
+        state,
+        start.project,
+        start.session_id,
+        start.backend,
+        start.persona,
+        start.task,
+        start.bead_id,
+        start.role,
+        None,
+        start.worktree,
+        start.auto_capture,
+    )
+}
+
+/// Sessions waiting for a free slot, next one first
+#[tauri::command]
+pub fn get_session_queue(state: State<'_, AgentState>) -> Vec<QueuedSession> {
+    state.queue.list()
+}
+
+/// Drop a waiting session before it starts
+#[tauri::command]
+pub fn cancel_queued_session(id: String, app_handle: AppHandle, state: State<'_, AgentState>) -> Result<(), BertError> {
+    if !state.queue.cancel(&id) {
+        return Err(BertError::NotFound(format!("No queued session {}", id)));
+    }
+    eprintln!("⏳ Cancelled queued session {}", id);
+    state.queue.emit_changed(&app_handle);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn start(id: &str) -> QueuedStart {
+        QueuedStart {
+            session_id: id.to_string(),
+            project: ProjectContext::new("/tmp/project"),
+            backend: BackendId::Gemini,
+            persona: "specialist".to_string(),
+            task: None,
+            bead_id: Some("bp6-1".to_string()),
+            role: None,
+            worktree: false,
+            auto_capture: true,
+            queued_at: "2026-10-16T12:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_waits_only_at_the_limit_and_keeps_order() {
+        let queue = SessionQueue::new();
+        assert!(queue.admit(start("free"), 5, None).is_err());
+        queue.release();
+        assert!(queue.admit(start("below"), 1, Some(2)).is_err());
+        queue.release();
+        assert_eq!(queue.admit(start("a"), 2, Some(2)).ok(), Some(1));
+        // Later requests line up behind waiting ones even below the limit
+        assert_eq!(queue.admit(start("b"), 0, Some(2)).ok(), Some(2));
+
+        let positions: Vec<(String, usize)> = queue.list().into_iter().map(|q| (q.session_id, q.position)).collect();
+        assert_eq!(positions, [("a".to_string(), 1), ("b".to_string(), 2)]);
+
+        assert!(queue.next(2, Some(2)).is_none());
+        assert_eq!(queue.next(1, Some(2)).map(|s| s.session_id).as_deref(), Some("a"));
+        assert_eq!(queue.list()[0].position, 1);
+    }
+
+    #[test]
+    fn test_sessions_being_started_hold_their_slot() {
+        let queue = SessionQueue::new();
+        // Nothing runs yet, but the first start has reserved the only slot
+        assert!(queue.admit(start("first"), 0, Some(1)).is_err());
+        assert_eq!(queue.admit(start("second"), 0, Some(1)).ok(), Some(1));
+        assert!(queue.next(0, Some(1)).is_none());
+
+        queue.release();
+        assert_eq!(queue.next(0, Some(1)).map(|s| s.session_id).as_deref(), Some("second"));
+        assert!(queue.admit(start("third"), 0, Some(1)).is_ok());
+    }
+
+    #[test]
+    fn test_cancel_removes_a_waiting_session() {
+        let queue = SessionQueue::new();
+        for id in ["a", "b", "c"] {
+            assert!(queue.admit(start(id), 1, Some(1)).is_ok());
+        }
+        assert!(queue.cancel("b"));
+        assert!(!queue.cancel("b"));
+        let ids: Vec<String> = queue.list().into_iter().map(|q| q.session_id).collect();
+        assert_eq!(ids, ["a", "c"]);
+    }
+}
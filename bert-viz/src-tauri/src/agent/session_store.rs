@@ -0,0 +1,121 @@
+//! Persisted agent sessions - survives app restarts
+//!
+//! Every session state change writes the open sessions (including the CLI
+//! session ID needed for `--resume`) to ~/.bp6/sessions/active.json, so
+//! `restore_sessions` can re-register them the next time the app starts.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::session::SessionInfo;
+
+/// A session as stored in active.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersistedSession {
+    #[serde(flatten)]
+    pub info: SessionInfo,
+    /// Project the CLI ran in
+    pub project_path: PathBuf,
+}
+
+impl PersistedSession {
+    /// Sessions without a CLI session ID can't be continued with `--resume`
+    pub fn is_resumable(&self) -> bool {
+        self.info.cli_session_id.is_some() && self.project_path.is_dir()
+    }
+}
+
+/// Path of the active sessions file
+fn store_path() -> Result<PathBuf, String> {
+    let home = dirs::home_dir().ok_or_else(|| "Could not find home directory".to_string())?;
+    Ok(home.join(".bp6").join("sessions").join("active.json"))
+}
+
+fn load_from(path: &Path) -> Result<Vec<PersistedSession>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read active sessions: {}", e))?;
+    serde_json::from_str(&contents).map_err(|e| format!("Failed to parse active sessions: {}", e))
+}
+
+fn save_to(path: &Path, sessions: &[PersistedSession]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create sessions directory: {}", e))?;
+    }
+
+    let json = serde_json::to_string_pretty(sessions)
+        .map_err(|e| format!("Failed to serialize active sessions: {}", e))?;
+    // Write to a temp file and rename so a crash never leaves a truncated file
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, json).map_err(|e| format!("Failed to write active sessions: {}", e))?;
+    fs::rename(&tmp, path).map_err(|e| format!("Failed to replace active sessions: {}", e))
+}
+
+/// Load the sessions that were open when the app last ran
+pub fn load() -> Result<Vec<PersistedSession>, String> {
+    load_from(&store_path()?)
+}
+
+/// Replace the stored sessions
+pub fn save(sessions: &[PersistedSession]) -> Result<(), String> {
+    save_to(&store_path()?, sessions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::plugin::{BackendId, TokenUsage};
+    use crate::agent::session::{ExecutionMode, SessionStatus};
+    use tempfile::TempDir;
+
+    fn persisted(cli_session_id: Option<&str>, project_path: &Path) -> PersistedSession {
+        PersistedSession {
+            info: SessionInfo {
+                session_id: "s1".to_string(),
+                bead_id: Some("bp6-1".to_string()),
+                persona: "specialist".to_string(),
+                backend_id: BackendId::ClaudeCode,
+                status: SessionStatus::Running,
+                created_at: 100,
+                cli_session_id: cli_session_id.map(String::from),
+                execution_mode: ExecutionMode::Interactive,
+                commands_remaining: None,
+                total_commands: None,
+                last_activity: 200,
+                has_unread: false,
+                message_count: 4,
+                usage: TokenUsage::default(),
+                worktree: None,
+                spawn_attempts: Vec::new(),
+            },
+            project_path: project_path.to_path_buf(),
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("sessions").join("active.json");
+        assert!(load_from(&path).unwrap().is_empty());
+
+        save_to(&path, &[persisted(Some("cli-1"), dir.path())]).unwrap();
+        let loaded = load_from(&path).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].info.cli_session_id.as_deref(), Some("cli-1"));
+        assert_eq!(loaded[0].project_path, dir.path());
+    }
+
+    #[test]
+    fn test_resumable_needs_cli_id_and_project() {
+        let dir = TempDir::new().unwrap();
+        assert!(persisted(Some("cli-1"), dir.path()).is_resumable());
+        assert!(!persisted(None, dir.path()).is_resumable());
+        assert!(!persisted(Some("cli-1"), &dir.path().join("gone")).is_resumable());
+    }
+}
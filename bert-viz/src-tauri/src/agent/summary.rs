@@ -0,0 +1,70 @@
+//! Capture of agent summaries into bead notes
+//!
+//! When auto-capture is enabled, sessions working on a bead are asked to end
+//! their reply with a `<bead-summary>` block. When a reply completes, the
+//! session reader extracts the block and appends it to the bead's notes, so
+//! the summary survives even if the agent never runs `bd update --notes`.
+
+use crate::error::BertError;
+use crate::project::ProjectContext;
+
+const SUMMARY_START: &str = "<bead-summary>";
+const SUMMARY_END: &str = "</bead-summary>";
+
+/// Appended to the prompt of sessions working on a bead
+pub fn prompt_instructions() -> String {
+    format!(
+        "\n\nWhen you finish, end your final reply with a short summary of what you did, \
+         what is left and anything the next person should know, wrapped in \
+         {} and {} tags. It is saved to the bead's notes automatically.",
+        SUMMARY_START, SUMMARY_END
+    )
+}
+
+/// The last complete, non-empty summary block in a reply
+pub fn extract_summary(reply: &str) -> Option<String> {
+    let start = reply.rfind(SUMMARY_START)? + SUMMARY_START.len();
+    let end = reply[start..].find(SUMMARY_END)? + start;
+    let summary = reply[start..end].trim();
+    (!summary.is_empty()).then(|| summary.to_string())
+}
+
+/// Notes with the summary appended under a timestamped heading
+fn append_summary(notes: Option<&str>, summary: &str, timestamp: &str) -> String {
+    let entry = format!("Agent summary ({}):\n{}", timestamp, summary);
+    match notes.map(str::trim_end).filter(|n| !n.is_empty()) {
+        Some(notes) => format!("{}\n\n{}", notes, entry),
+        None => entry,
+    }
+}
+
+/// Append `summary` to the notes of `bead_id`
+pub fn capture(project: &ProjectContext, bead_id: &str, summary: &str) -> Result<(), BertError> {
+    let mut bead = crate::bd::get_bead_by_id(project, bead_id)?;
+    let timestamp = chrono::Utc::now().format("%Y-%m-%d %H:%M UTC").to_string();
+    bead.notes = Some(append_summary(bead.notes.as_deref(), summary, &timestamp));
+    crate::bd::update_bead_internal(project, &bead)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extracts_last_complete_block() {
+        let reply = "Done.\n<bead-summary>old</bead-summary>\nMore work.\n<bead-summary>\n- Fixed the parser\n- Tests pass\n</bead-summary>";
+        assert_eq!(extract_summary(reply).as_deref(), Some("- Fixed the parser\n- Tests pass"));
+        assert_eq!(extract_summary("<bead-summary>  </bead-summary>"), None);
+        assert_eq!(extract_summary("<bead-summary>cut off"), None);
+        assert_eq!(extract_summary("no summary"), None);
+    }
+
+    #[test]
+    fn test_append_keeps_existing_notes() {
+        assert_eq!(append_summary(None, "Fixed it", "2026-10-16 09:00 UTC"), "Agent summary (2026-10-16 09:00 UTC):\nFixed it");
+        assert_eq!(
+            append_summary(Some("Repro steps\n"), "Fixed it", "2026-10-16 09:00 UTC"),
+            "Repro steps\n\nAgent summary (2026-10-16 09:00 UTC):\nFixed it"
+        );
+    }
+}
@@ -0,0 +1,387 @@
+//! Feature swarms: implementing a feature's tasks in parallel
+//!
+//! `start_feature_swarm` takes the direct children of a feature and starts an
+//! implementer session on every child whose blockers are done, at most
+//! `max_parallel` at a time. Whenever one of its sessions finishes a run, the
+//! swarm marks the task done if its bead was closed (terminating the session)
+//! or failed if not (keeping the session for follow-up), re-reads the beads
+//! and starts the tasks that became unblocked. Tasks waiting on a failed task
+//! are never started. Every change is broadcast as a `swarm-progress` event.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, State};
+use uuid::Uuid;
+
+use crate::agent::autopilot::{IMPLEMENTER_PERSONA, IMPLEMENTER_TASK};
+use crate::agent::plugin::BackendId;
+use crate::agent::session::AgentState;
+use crate::error::BertError;
+use crate::project::ProjectContext;
+use crate::workflow::WorkflowConfig;
+use crate::Bead;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SwarmTaskStatus {
+    /// Waiting for blocking beads to be done
+    Blocked,
+    Ready,
+    Running,
+    Done,
+    /// The session couldn't be started or ended without closing the bead
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwarmTask {
+    pub bead_id: String,
+    pub title: String,
+    pub status: SwarmTaskStatus,
+    pub session_id: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Payload of `swarm-progress`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwarmProgress {
+    pub feature_id: String,
+    pub max_parallel: usize,
+    pub tasks: Vec<SwarmTask>,
+    pub total: usize,
+    pub done: usize,
+    pub running: usize,
+    pub failed: usize,
+    /// No task is running and none can be started
+    pub finished: bool,
+    /// Stopped by the user: running tasks finish, no new ones start
+    pub stopped: bool,
+    /// Error from the last attempt to read the beads
+    pub error: Option<String>,
+}
+
+impl SwarmProgress {
+    fn count(&self, status: SwarmTaskStatus) -> usize {
+        self.tasks.iter().filter(|t| t.status == status).count()
+    }
+
+    fn update_counts(&mut self) {
+        self.total = self.tasks.len();
+        self.done = self.count(SwarmTaskStatus::Done);
+        self.running = self.count(SwarmTaskStatus::Running);
+        self.failed = self.count(SwarmTaskStatus::Failed);
+        self.finished = self.running == 0 && (self.stopped || self.count(SwarmTaskStatus::Ready) == 0);
+    }
+}
+
+struct Swarm {
+    progress: SwarmProgress,
+    project: ProjectContext,
+    backend: BackendId,
+    auto_capture: bool,
+}
+
+/// Swarms by feature ID; finished swarms are kept until the feature is swarmed again
+#[derive(Default)]
+pub struct SwarmState {
+    swarms: Mutex<HashMap<String, Swarm>>,
+}
+
+/// Direct children of a feature
+fn children_of<'a>(feature_id: &str, beads: &'a [Bead]) -> Vec<&'a Bead> {
+    beads
+        .iter()
+        .filter(|b| {
+            b.parent.as_deref() == Some(feature_id)
+                || b.dependencies.iter().any(|d| d.r#type == "parent-child" && d.depends_on_id == feature_id)
+        })
+        .collect()
+}
+
+/// Update the tasks that aren't running from the current beads
+fn refresh(tasks: &mut [SwarmTask], beads: &[Bead], workflow: &WorkflowConfig) {
+    let by_id: HashMap<&str, &Bead> = beads.iter().map(|b| (b.id.as_str(), b)).collect();
+    let is_done = |id: &str| by_id.get(id).is_some_and(|b| workflow.is_done(&b.status));
+
+    for task in tasks.iter_mut().filter(|t| t.status != SwarmTaskStatus::Running) {
+        let Some(bead) = by_id.get(task.bead_id.as_str()) else { continue };
+        if workflow.is_done(&bead.status) {
+            task.status = SwarmTaskStatus::Done;
+        } else if task.status == SwarmTaskStatus::Failed {
+            // Failed tasks only change once someone closes the bead
+        } else if bead.dependencies.iter().any(|d| d.r#type == "blocks" && !is_done(&d.depends_on_id)) {
+            task.status = SwarmTaskStatus::Blocked;
+        } else {
+            task.status = SwarmTaskStatus::Ready;
+        }
+    }
+}
+
+fn load(project: &ProjectContext) -> Result<(Vec<Bead>, WorkflowConfig), BertError> {
+    let beads = crate::bd::load_beads(project)?;
+    let workflow = WorkflowConfig::load_for_beads_file(&project.beads_path()?)?;
+    Ok((beads, workflow))
+}
+
+impl SwarmState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Refresh a swarm's tasks, start ready ones in free slots and broadcast
+    /// its progress
+    fn advance(app_handle: &AppHandle, state: &AgentState, swarm: &mut Swarm) {
+        match load(&swarm.project) {
+            Ok((beads, workflow)) => {
+                refresh(&mut swarm.progress.tasks, &beads, &workflow);
+                swarm.progress.error = None;
+            }
+            Err(e) => {
+                eprintln!("⚠️  Swarm {}: failed to read beads: {}", swarm.progress.feature_id, e);
+                swarm.progress.error = Some(e.to_string());
+            }
+        }
+
+        let mut running = swarm.progress.count(SwarmTaskStatus::Running);
+        for task in swarm.progress.tasks.iter_mut() {
+            if swarm.progress.stopped || running >= swarm.progress.max_parallel {
+                break;
+            }
+            if task.status != SwarmTaskStatus::Ready {
+                continue;
+            }
+
+            let session_id = Uuid::new_v4().to_string();
+            eprintln!("🐝 Swarm {}: starting {} in session {}", swarm.progress.feature_id, task.bead_id, session_id);
+            if let Err(e) = crate::bd::claim_bead_internal(&swarm.project, &task.bead_id) {
+                eprintln!("⚠️  Swarm: failed to claim {}: {}", task.bead_id, e);
+            }
+
+            let started = crate::agent::session::start_session_internal(
+                app_handle,
+                state,
+                swarm.project.clone(),
+                session_id.clone(),
+                swarm.backend,
+                IMPLEMENTER_PERSONA.to_string(),
+                Some(IMPLEMENTER_TASK.to_string()),
+                Some(task.bead_id.clone()),
+                None,
+                None,
+                false,
+                swarm.auto_capture,
+            );
+            match started {
+                Ok(()) => {
+                    task.status = SwarmTaskStatus::Running;
+                    task.session_id = Some(session_id);
+                    running += 1;
+                }
+                Err(e) => {
+                    eprintln!("❌ Swarm: failed to start {}: {}", task.bead_id, e);
+                    task.status = SwarmTaskStatus::Failed;
+                    task.error = Some(e.to_string());
+                }
+            }
+        }
+
+        swarm.progress.update_counts();
+        let _ = app_handle.emit("swarm-progress", swarm.progress.clone());
+        crate::project::emit_beads_updated(app_handle, &swarm.project);
+    }
+
+    /// Called by the session reader when a session's run ends
+    pub fn run_ended(&self, app_handle: &AppHandle, state: &AgentState, session_id: &str) {
+        // Held while sessions start, so a run can't end before it is recorded
+        let mut swarms = self.swarms.lock().unwrap();
+        let is_running = |t: &SwarmTask| t.session_id.as_deref() == Some(session_id) && t.status == SwarmTaskStatus::Running;
+        let Some(swarm) = swarms.values_mut().find(|s| s.progress.tasks.iter().any(is_running)) else {
+            return;
+        };
+
+        let Some(task) = swarm.progress.tasks.iter_mut().find(|t| is_running(t)) else { return };
+        let closed = load(&swarm.project)
+            .map(|(beads, workflow)| beads.iter().any(|b| b.id == task.bead_id && workflow.is_done(&b.status)));
+        match closed {
+            Ok(true) => {
+                task.status = SwarmTaskStatus::Done;
+                if let Err(e) = crate::agent::session::terminate_session_internal(app_handle, state, session_id, None) {
+                    task.error = Some(e.to_string());
+                }
+            }
+            Ok(false) => task.status = SwarmTaskStatus::Failed,
+            Err(e) => {
+                task.status = SwarmTaskStatus::Failed;
+                task.error = Some(e.to_string());
+            }
+        }
+        eprintln!("🐝 Swarm {}: {} is {:?}", swarm.progress.feature_id, task.bead_id, task.status);
+
+        Self::advance(app_handle, state, swarm);
+    }
+}
+
+/// Implement a feature's tasks with parallel agent sessions
+///
+/// Fails if the feature has no children or a swarm on it is still running.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn start_feature_swarm(
+    app_handle: AppHandle,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    featureId: String,
+    maxParallel: usize,
+    state: State<'_, AgentState>,
+    settings_state: State<'_, crate::SettingsState>,
+) -> Result<SwarmProgress, BertError> {
+    if maxParallel == 0 {
+        return Err(BertError::InvalidInput("Max parallel sessions must be at least 1".to_string()));
+    }
+
+    let project = crate::project::context_for(&window, project_handle.as_deref())?;
+    let backend = crate::agent::session::resolve_backend(None, &settings_state)?;
+    let auto_capture = settings_state.settings.lock().map_err(|e| e.to_string())?.auto_capture_summary;
+
+    let beads = crate::bd::load_beads(&project)?;
+    let tasks: Vec<SwarmTask> = children_of(&featureId, &beads)
+        .into_iter()
+        .map(|b| SwarmTask {
+            bead_id: b.id.clone(),
+            title: b.title.clone(),
+            status: SwarmTaskStatus::Blocked,
+            session_id: None,
+            error: None,
+        })
+        .collect();
+    if tasks.is_empty() {
+        return Err(BertError::InvalidInput(format!("{} has no tasks to implement", featureId)));
+    }
+
+    let mut swarms = state.swarms.swarms.lock().unwrap();
+    if swarms.get(&featureId).is_some_and(|s| !s.progress.finished) {
+        return Err(BertError::InvalidInput(format!("A swarm is already working on {}", featureId)));
+    }
+
+    eprintln!("🐝 Starting swarm on {} ({} tasks, {} at a time)", featureId, tasks.len(), maxParallel);
+    let swarm = Swarm {
+        progress: SwarmProgress {
+            feature_id: featureId.clone(),
+            max_parallel: maxParallel,
+            tasks,
+            total: 0,
+            done: 0,
+            running: 0,
+            failed: 0,
+            finished: false,
+            stopped: false,
+            error: None,
+        },
+        project,
+        backend,
+        auto_capture,
+    };
+    // Tracked before any session starts, so run_ended finds it
+    let swarm = swarms.entry(featureId).insert_entry(swarm).into_mut();
+    SwarmState::advance(&app_handle, &state, swarm);
+
+    Ok(swarm.progress.clone())
+}
+
+/// Stop starting tasks; running sessions finish first
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn stop_feature_swarm(app_handle: AppHandle, featureId: String, state: State<'_, AgentState>) -> Result<SwarmProgress, BertError> {
+    let mut swarms = state.swarms.swarms.lock().unwrap();
+    let swarm = swarms
+        .get_mut(&featureId)
+        .ok_or_else(|| BertError::NotFound(format!("No swarm on {}", featureId)))?;
+
+    swarm.progress.stopped = true;
+    swarm.progress.update_counts();
+    let _ = app_handle.emit("swarm-progress", swarm.progress.clone());
+    Ok(swarm.progress.clone())
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn get_feature_swarm(featureId: String, state: State<'_, AgentState>) -> Result<Option<SwarmProgress>, BertError> {
+    let swarms = state.swarms.swarms.lock().unwrap();
+    Ok(swarms.get(&featureId).map(|s| s.progress.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bead(id: &str, status: &str, deps: serde_json::Value) -> Bead {
+        crate::test_bead(json!({
+            "id": id,
+            "status": status,
+            "dependencies": deps,
+        }))
+    }
+
+    fn dep(issue: &str, on: &str, kind: &str) -> serde_json::Value {
+        json!({ "issue_id": issue, "depends_on_id": on, "type": kind })
+    }
+
+    #[test]
+    fn test_children_become_ready_as_blockers_close() {
+        let mut beads = vec![
+            bead("f", "open", json!([])),
+            bead("t1", "open", json!([dep("t1", "f", "parent-child")])),
+            bead("t2", "open", json!([dep("t2", "f", "parent-child"), dep("t2", "t1", "blocks")])),
+            bead("t3", "closed", json!([dep("t3", "f", "parent-child")])),
+            bead("other", "open", json!([])),
+        ];
+        let workflow = WorkflowConfig::default();
+
+        let mut tasks: Vec<SwarmTask> = children_of("f", &beads)
+            .into_iter()
+            .map(|b| SwarmTask {
+                bead_id: b.id.clone(),
+                title: b.title.clone(),
+                status: SwarmTaskStatus::Blocked,
+                session_id: None,
+                error: None,
+            })
+            .collect();
+        assert_eq!(tasks.len(), 3);
+
+        refresh(&mut tasks, &beads, &workflow);
+        let statuses: Vec<_> = tasks.iter().map(|t| t.status).collect();
+        assert_eq!(statuses, vec![SwarmTaskStatus::Ready, SwarmTaskStatus::Blocked, SwarmTaskStatus::Done]);
+
+        // t1 is being worked on, then closed
+        tasks[0].status = SwarmTaskStatus::Running;
+        refresh(&mut tasks, &beads, &workflow);
+        assert_eq!(tasks[0].status, SwarmTaskStatus::Running);
+        tasks[0].status = SwarmTaskStatus::Done;
+        beads[1].status = "closed".to_string();
+        refresh(&mut tasks, &beads, &workflow);
+        assert_eq!(tasks[1].status, SwarmTaskStatus::Ready);
+
+        let mut progress = SwarmProgress {
+            feature_id: "f".to_string(),
+            max_parallel: 2,
+            tasks,
+            total: 0,
+            done: 0,
+            running: 0,
+            failed: 0,
+            finished: false,
+            stopped: false,
+            error: None,
+        };
+        progress.update_counts();
+        assert_eq!((progress.total, progress.done, progress.running, progress.finished), (3, 2, 0, false));
+        progress.tasks[1].status = SwarmTaskStatus::Failed;
+        progress.update_counts();
+        assert!(progress.finished);
+    }
+}
@@ -1,14 +1,53 @@
 /// Template loading and variable substitution for persona prompts
-use std::collections::HashMap;
+///
+/// Templates use `{{name}}` placeholders. The built-in variables are
+/// `feature_id`, `project_name`, `tech_stack`, `branch` and `today`; projects
+/// add their own under `templateVariables` in `.beads/bert-viz.json`, and
+/// those win over the built-in values. A placeholder naming no variable is
+/// an error, so typos never reach the agent; write `\{{name}}` to show the
+/// placeholder itself.
+///
+/// A project can replace any template with its own copy in
+/// `.beads/templates/<persona>/<template>.md`.
+use serde::Serialize;
+use serde_json::Map;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::error::BertError;
+use crate::project::{context_for, ProjectContext};
+
+/// Variables every template can use
+pub const BUILTIN_VARIABLES: &[&str] = &["feature_id", "project_name", "tech_stack", "branch", "today"];
+
+/// Key of the project's own variables in the project config file
+const CONFIG_KEY: &str = "templateVariables";
+
+/// Files that give away a technology, for `{{tech_stack}}`
+const STACK_MARKERS: &[(&str, &str)] = &[
+    ("Cargo.toml", "Rust"),
+    ("tauri.conf.json", "Tauri"),
+    ("package.json", "Node.js"),
+    ("tsconfig.json", "TypeScript"),
+    ("pubspec.yaml", "Flutter/Dart"),
+    ("go.mod", "Go"),
+    ("pyproject.toml", "Python"),
+    ("requirements.txt", "Python"),
+    ("supabase", "Supabase"),
+];
+
 /// Template loader for persona prompt templates
 ///
 /// Loads markdown templates from the filesystem and performs variable substitution.
 /// Templates are located in the `templates/personas/` directory organized by persona type.
+#[derive(Clone)]
 pub struct TemplateLoader {
     template_root: PathBuf,
+    /// Directory of user-defined persona templates (`~/.bp6/personas`)
+    user_root: Option<PathBuf>,
+    /// Project overrides (`.beads/templates`), taking precedence over both
+    project_root: Option<PathBuf>,
 }
 
 impl TemplateLoader {
@@ -29,7 +68,11 @@ impl TemplateLoader {
             .map(|p| p.join("bert-viz/templates/personas"))
             .ok_or_else(|| "Failed to determine template root path".to_string())?;
 
-        Ok(TemplateLoader { template_root })
+        Ok(TemplateLoader {
+            template_root,
+            user_root: crate::agent::personas::custom::personas_dir(),
+            project_root: None,
+        })
     }
 
     /// Create a template loader with a custom template directory
@@ -37,7 +80,33 @@ impl TemplateLoader {
     pub fn with_root<P: AsRef<Path>>(root: P) -> Self {
         TemplateLoader {
             template_root: root.as_ref().to_path_buf(),
+            user_root: None,
+            project_root: None,
+        }
+    }
+
+    /// Use a custom directory for user-defined persona templates
+    #[allow(dead_code)]
+    pub fn with_user_root<P: AsRef<Path>>(mut self, root: P) -> Self {
+        self.user_root = Some(root.as_ref().to_path_buf());
+        self
+    }
+
+    /// Prefer the project's templates in `root` (its `.beads/templates`)
+    pub fn with_project_root<P: AsRef<Path>>(mut self, root: P) -> Self {
+        self.project_root = Some(root.as_ref().to_path_buf());
+        self
+    }
+
+    /// The project's copy of a template, if it has one
+    fn load_project_override(&self, persona: &str, template_name: &str) -> Option<Result<String, String>> {
+        let path = self.project_root.as_ref()?.join(persona).join(format!("{}.md", template_name));
+        if !path.is_file() {
+            return None;
         }
+        Some(fs::read_to_string(&path).map_err(|e| {
+            format!("Failed to load project template '{}' for persona '{}': {}. Path: {}", template_name, persona, e, path.display())
+        }))
     }
 
     /// Load a template by persona and template name
@@ -58,6 +127,9 @@ impl TemplateLoader {
     /// let template = loader.load_template("product-manager", "decompose-feature")?;
     /// ```
     pub fn load_template(&self, persona: &str, template_name: &str) -> Result<String, String> {
+        if let Some(template) = self.load_project_override(persona, template_name) {
+            return template;
+        }
         let template_path = self
             .template_root
             .join(persona)
@@ -74,7 +146,31 @@ impl TemplateLoader {
         })
     }
 
-    /// Load a template and substitute variables
+    /// Load a template of a user-defined persona
+    ///
+    /// User personas live in `~/.bp6/personas/<persona>/<template_name>.md`.
+    pub fn load_user_template(&self, persona: &str, template_name: &str) -> Result<String, String> {
+        if let Some(template) = self.load_project_override(persona, template_name) {
+            return template;
+        }
+        let user_root = self
+            .user_root
+            .as_ref()
+            .ok_or_else(|| "No directory for user persona templates".to_string())?;
+        let template_path = user_root.join(persona).join(format!("{}.md", template_name));
+
+        fs::read_to_string(&template_path).map_err(|e| {
+            format!(
+                "Failed to load template '{}' for user persona '{}': {}. Path: {}",
+                template_name,
+                persona,
+                e,
+                template_path.display()
+            )
+        })
+    }
+
+    /// Load a template and substitute variables (see `render`)
     #[allow(dead_code)]
     ///
     /// # Arguments
@@ -100,21 +196,15 @@ impl TemplateLoader {
         template_name: &str,
         variables: &HashMap<String, String>,
     ) -> Result<String, String> {
-        let mut template = self.load_template(persona, template_name)?;
-
-        // Substitute all {{variable}} placeholders
-        for (key, value) in variables {
-            let placeholder = format!("{{{{{}}}}}", key);
-            template = template.replace(&placeholder, value);
-        }
-
-        Ok(template)
+        let template = self.load_template(persona, template_name)?;
+        render(&template, variables)
     }
 
-    /// List all available templates for a persona
+    /// List all available templates for a persona, project overrides included
     #[allow(dead_code)]
     pub fn list_templates(&self, persona: &str) -> Result<Vec<String>, String> {
         let persona_dir = self.template_root.join(persona);
+        let overrides = self.project_root.as_ref().map(|root| root.join(persona));
 
         let entries = fs::read_dir(&persona_dir).map_err(|e| {
             format!(
@@ -124,8 +214,10 @@ impl TemplateLoader {
             )
         })?;
 
+        let override_entries = overrides.and_then(|dir| fs::read_dir(dir).ok()).into_iter().flatten();
+
         let mut templates = Vec::new();
-        for entry in entries {
+        for entry in entries.chain(override_entries) {
             if let Ok(entry) = entry {
                 let path = entry.path();
                 if path.extension().and_then(|s| s.to_str()) == Some("md") {
@@ -137,10 +229,157 @@ impl TemplateLoader {
         }
 
         templates.sort();
+        templates.dedup();
         Ok(templates)
     }
 }
 
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_') && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Replace the `{{name}}` placeholders of `template` with `variables`
+///
+/// Names that are neither in `variables` nor built in are an error. A
+/// built-in variable without a value here (`feature_id` outside a bead) is
+/// left as is, and `\{{name}}` becomes `{{name}}`.
+pub fn render(template: &str, variables: &HashMap<String, String>) -> Result<String, String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut unknown: Vec<&str> = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else { break };
+        let end = start + len + 4;
+        let name = rest[start + 2..start + 2 + len].trim();
+        if !is_variable_name(name) {
+            // Not a placeholder, e.g. `{{0-4}}`
+            rendered.push_str(&rest[..end]);
+        } else if rest[..start].ends_with('\\') {
+            rendered.push_str(&rest[..start - 1]);
+            rendered.push_str(&rest[start..end]);
+        } else {
+            rendered.push_str(&rest[..start]);
+            match variables.get(name) {
+                Some(value) => rendered.push_str(value),
+                None => {
+                    if !BUILTIN_VARIABLES.contains(&name) && !unknown.contains(&name) {
+                        unknown.push(name);
+                    }
+                    rendered.push_str(&rest[start..end]);
+                }
+            }
+        }
+        rest = &rest[end..];
+    }
+    rendered.push_str(rest);
+
+    if !unknown.is_empty() {
+        let names: Vec<String> = unknown.iter().map(|name| format!("{{{{{}}}}}", name)).collect();
+        return Err(format!("Unknown template variables: {} (write \\{{{{name}}}} to keep a placeholder)", names.join(", ")));
+    }
+    Ok(rendered)
+}
+
+/// Directory of a project's template overrides
+pub fn project_templates_dir(repo_root: &Path) -> PathBuf {
+    repo_root.join(".beads").join("templates")
+}
+
+/// Technologies the project at `root` uses, from marker files in it and in
+/// its direct subdirectories (e.g. `src-tauri`)
+fn detect_tech_stack(root: &Path) -> String {
+    let mut dirs = vec![root.to_path_buf()];
+    if let Ok(entries) = fs::read_dir(root) {
+        dirs.extend(entries.flatten().map(|e| e.path()).filter(|path| {
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+            path.is_dir() && !name.starts_with('.') && !matches!(name, "node_modules" | "target" | "build" | "dist")
+        }));
+    }
+
+    let mut stack: Vec<&str> = Vec::new();
+    for (marker, technology) in STACK_MARKERS {
+        if !stack.contains(technology) && dirs.iter().any(|dir| dir.join(marker).exists()) {
+            stack.push(technology);
+        }
+    }
+    stack.join(", ")
+}
+
+/// The project's own variables, from `templateVariables` in its config file
+pub fn load_for_beads_file(beads_path: &Path) -> Result<BTreeMap<String, String>, BertError> {
+    Ok(crate::schedule::load_config_section(beads_path, CONFIG_KEY)?.unwrap_or_default())
+}
+
+/// Built-in variables of `project`, for a prompt about `bead_id`
+fn builtin_variables(project: &ProjectContext, bead_id: Option<&str>) -> BTreeMap<String, String> {
+    let mut variables = BTreeMap::new();
+    if let Some(bead_id) = bead_id {
+        variables.insert("feature_id".to_string(), bead_id.to_string());
+    }
+    let root = project.repo_root().unwrap_or_else(|_| project.project_path().to_path_buf());
+    if let Some(name) = root.file_name().and_then(|n| n.to_str()) {
+        variables.insert("project_name".to_string(), name.to_string());
+    }
+    variables.insert("tech_stack".to_string(), detect_tech_stack(&root));
+    if let Ok(branch) = crate::git::git(&root, &["rev-parse", "--abbrev-ref", "HEAD"]) {
+        variables.insert("branch".to_string(), branch.trim().to_string());
+    }
+    variables.insert("today".to_string(), chrono::Local::now().format("%Y-%m-%d").to_string());
+    variables
+}
+
+/// Everything a template of `project` can use: the built-in variables, then
+/// the project's own on top
+pub fn variables_for(project: &ProjectContext, bead_id: Option<&str>) -> Result<HashMap<String, String>, BertError> {
+    let mut variables: HashMap<String, String> = builtin_variables(project, bead_id).into_iter().collect();
+    if let Ok(beads_path) = project.beads_path() {
+        variables.extend(load_for_beads_file(&beads_path)?);
+    }
+    Ok(variables)
+}
+
+/// Template variables of a project, as `get_template_variables` shows them
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateVariables {
+    /// Current values of the built-in variables (no `feature_id`; it depends on the bead)
+    pub builtin: BTreeMap<String, String>,
+    /// The project's own variables from `.beads/bert-viz.json`
+    pub project: BTreeMap<String, String>,
+}
+
+/// The variables prompt templates of the current project can use
+#[tauri::command]
+pub fn get_template_variables(window: tauri::Window, project_handle: Option<String>) -> Result<TemplateVariables, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    Ok(TemplateVariables {
+        builtin: builtin_variables(&ctx, None),
+        project: load_for_beads_file(&ctx.beads_path()?)?,
+    })
+}
+
+/// Replace the current project's own template variables
+#[tauri::command]
+pub fn set_template_variables(
+    variables: BTreeMap<String, String>,
+    window: tauri::Window,
+    project_handle: Option<String>,
+) -> Result<(), BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    if let Some(name) = variables.keys().find(|name| !is_variable_name(name)) {
+        return Err(BertError::InvalidInput(format!(
+            "Invalid template variable name '{}': use letters, digits and underscores",
+            name
+        )));
+    }
+    let mut fields = Map::new();
+    fields.insert(CONFIG_KEY.to_string(), serde_json::to_value(&variables)?);
+    crate::schedule::merge_config_fields(&ctx.beads_path()?, fields)?;
+    eprintln!("📝 Saved {} template variables", variables.len());
+    Ok(())
+}
+
 impl Default for TemplateLoader {
     fn default() -> Self {
         Self::new().expect("Failed to create default TemplateLoader")
@@ -211,6 +450,49 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_load_user_template() {
+        let temp_dir = create_test_templates();
+        let user_dir = temp_dir.path().join("user");
+        fs::create_dir_all(user_dir.join("reviewer")).unwrap();
+        fs::write(user_dir.join("reviewer").join("review.md"), "# Review").unwrap();
+
+        let loader = TemplateLoader::with_root(temp_dir.path().join("personas"));
+        assert!(loader.load_user_template("reviewer", "review").is_err());
+
+        let loader = loader.with_user_root(&user_dir);
+        assert_eq!(loader.load_user_template("reviewer", "review").unwrap(), "# Review");
+        assert!(loader.load_template("reviewer", "review").is_err());
+    }
+
+    #[test]
+    fn test_render_checks_variables() {
+        let vars = HashMap::from([
+            ("project_name".to_string(), "bp6".to_string()),
+            ("team".to_string(), "core".to_string()),
+        ]);
+
+        let rendered = render("{{ project_name }} by {{team}}, see {{feature_id}}; \\{{epic_id}} P{{0-4}}", &vars).unwrap();
+        assert_eq!(rendered, "bp6 by core, see {{feature_id}}; {{epic_id}} P{{0-4}}");
+
+        let err = render("{{featur_id}} and {{epic_id}} and {{featur_id}}", &vars).unwrap_err();
+        assert!(err.starts_with("Unknown template variables: {{featur_id}}, {{epic_id}} "), "{}", err);
+    }
+
+    #[test]
+    fn test_project_templates_take_precedence() {
+        let temp_dir = create_test_templates();
+        let project_dir = temp_dir.path().join("project");
+        fs::create_dir_all(project_dir.join("specialist")).unwrap();
+        fs::write(project_dir.join("specialist").join("web.md"), "# Our web stack").unwrap();
+        fs::write(project_dir.join("specialist").join("mobile.md"), "# Mobile").unwrap();
+
+        let loader = TemplateLoader::with_root(temp_dir.path().join("personas")).with_project_root(&project_dir);
+        assert_eq!(loader.load_template("specialist", "web").unwrap(), "# Our web stack");
+        assert!(loader.load_template("product-manager", "test-template").unwrap().contains("Test Template"));
+        assert_eq!(loader.list_templates("specialist").unwrap(), ["mobile", "web"]);
+    }
+
     #[test]
     fn test_list_templates() {
         let temp_dir = create_test_templates();
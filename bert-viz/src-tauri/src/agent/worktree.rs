@@ -0,0 +1,207 @@
+//! Git worktree isolation for agent sessions
+//!
+//! Sessions normally all run in the repo root, so two implementer sessions
+//! edit the same files. An isolated session gets its own worktree under
+//! `<git dir>/bert-worktrees/<session-id>` on a `bert/session-<session-id>`
+//! branch. When the session ends its work is merged back into the repo
+//! root's current branch, discarded, or kept for later cleanup.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::error::BertError;
+
+/// Directory (inside the git dir) holding the session worktrees
+const WORKTREES_DIR: &str = "bert-worktrees";
+
+/// A session's dedicated worktree
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionWorktree {
+    pub path: PathBuf,
+    pub branch: String,
+    /// Checkout the worktree was created from, where its work is merged
+    pub repo_root: PathBuf,
+}
+
+/// What to do with a session's worktree when the session ends
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum WorktreeAction {
+    /// Commit outstanding changes and merge the branch into the repo root
+    Merge,
+    /// Delete the worktree and its branch
+    Discard,
+    /// Leave the worktree for `cleanup_session_worktree`
+    Keep,
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<String, BertError> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| BertError::Agent(format!("Failed to run git: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(BertError::Agent(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Branch a session's worktree is checked out on
+fn branch_name(session_id: &str) -> String {
+    format!("bert/session-{}", session_id)
+}
+
+/// `<git dir>/bert-worktrees/<session-id>`, using the main repository's git
+/// dir even when `repo_root` is itself a worktree
+fn worktree_path(repo_root: &Path, session_id: &str) -> Result<PathBuf, BertError> {
+    let common_dir = PathBuf::from(git(repo_root, &["rev-parse", "--git-common-dir"])?);
+    let common_dir = if common_dir.is_absolute() { common_dir } else { repo_root.join(common_dir) };
+    Ok(common_dir.join(WORKTREES_DIR).join(session_id))
+}
+
+/// Create a worktree for a session on a new branch from the repo root's HEAD
+pub fn create(repo_root: &Path, session_id: &str) -> Result<SessionWorktree, BertError> {
+    let path = worktree_path(repo_root, session_id)?;
+    let branch = branch_name(session_id);
+    git(repo_root, &["worktree", "add", "-b", &branch, &path.to_string_lossy(), "HEAD"])?;
+
+    eprintln!("🌳 Created worktree {} on {}", path.display(), branch);
+    Ok(SessionWorktree {
+        path,
+        branch,
+        repo_root: repo_root.to_path_buf(),
+    })
+}
+
+/// The worktree of a session that is no longer registered, if it still exists
+pub fn find(repo_root: &Path, session_id: &str) -> Result<Option<SessionWorktree>, BertError> {
+    let path = worktree_path(repo_root, session_id)?;
+    Ok(path.is_dir().then(|| SessionWorktree {
+        path,
+        branch: branch_name(session_id),
+        repo_root: repo_root.to_path_buf(),
+    }))
+}
+
+impl SessionWorktree {
+    /// Whether the worktree has uncommitted changes or commits the repo
+    /// root's branch doesn't have
+    pub fn has_changes(&self) -> Result<bool, BertError> {
+        if !git(&self.path, &["status", "--porcelain"])?.is_empty() {
+            return Ok(true);
+        }
+        let ahead = git(&self.repo_root, &["rev-list", "--count", &format!("HEAD..{}", self.branch)])?;
+        Ok(ahead != "0")
+    }
+
+    /// Commit outstanding changes, merge the branch into the repo root's
+    /// current branch and remove the worktree. A conflicting merge is aborted
+    /// and the worktree kept.
+    pub fn merge(&self, message: &str) -> Result<(), BertError> {
+        if !git(&self.path, &["status", "--porcelain"])?.is_empty() {
+            git(&self.path, &["add", "-A"])?;
+            git(&self.path, &["commit", "-m", message])?;
+        }
+
+        if let Err(e) = git(&self.repo_root, &["merge", "--no-ff", "--no-edit", &self.branch]) {
+            let _ = git(&self.repo_root, &["merge", "--abort"]);
+            return Err(BertError::Agent(format!("{} (worktree kept at {})", e, self.path.display())));
+        }
+
+        eprintln!("🔀 Merged {} into {}", self.branch, self.repo_root.display());
+        self.remove()
+    }
+
+    /// Delete the worktree and its branch, discarding any work in it
+    pub fn remove(&self) -> Result<(), BertError> {
+        git(&self.repo_root, &["worktree", "remove", "--force", &self.path.to_string_lossy()])?;
+        git(&self.repo_root, &["branch", "-D", &self.branch])?;
+        eprintln!("🧹 Removed worktree {}", self.path.display());
+        Ok(())
+    }
+
+    /// Refuse to end a session without an explicit action while its worktree
+    /// has changes, so work is never dropped silently
+    pub fn guard(&self, action: Option<WorktreeAction>, session_id: &str) -> Result<(), BertError> {
+        if action.is_none() && self.has_changes()? {
+            return Err(BertError::InvalidInput(format!(
+                "Session {} has unmerged changes in {}; choose to merge, discard or keep them",
+                session_id,
+                self.path.display()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Apply `action`. Without one, an unchanged worktree is removed.
+    pub fn finish(&self, action: Option<WorktreeAction>, session_id: &str) -> Result<(), BertError> {
+        self.guard(action, session_id)?;
+        match action {
+            Some(WorktreeAction::Merge) => self.merge(&format!("Agent session {}", session_id)),
+            Some(WorktreeAction::Discard) | None => self.remove(),
+            Some(WorktreeAction::Keep) => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn repo() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        for args in [
+            &["init", "-q"][..],
+            &["config", "user.email", "test@example.com"],
+            &["config", "user.name", "Test"],
+        ] {
+            git(dir.path(), args).unwrap();
+        }
+        fs::write(dir.path().join("README.md"), "hello\n").unwrap();
+        git(dir.path(), &["add", "-A"]).unwrap();
+        git(dir.path(), &["commit", "-qm", "init"]).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_merge_brings_session_work_back() {
+        let dir = repo();
+        let worktree = create(dir.path(), "s1").unwrap();
+        assert!(worktree.path.starts_with(dir.path().join(".git").join(WORKTREES_DIR)));
+        assert!(!worktree.has_changes().unwrap());
+
+        fs::write(worktree.path.join("feature.rs"), "fn main() {}\n").unwrap();
+        assert!(worktree.has_changes().unwrap());
+        assert!(matches!(worktree.finish(None, "s1"), Err(BertError::InvalidInput(_))));
+
+        worktree.finish(Some(WorktreeAction::Merge), "s1").unwrap();
+        assert!(dir.path().join("feature.rs").exists());
+        assert!(!worktree.path.exists());
+        assert_eq!(find(dir.path(), "s1").unwrap(), None);
+    }
+
+    #[test]
+    fn test_discard_and_clean_removal() {
+        let dir = repo();
+        let changed = create(dir.path(), "s1").unwrap();
+        fs::write(changed.path.join("scratch.txt"), "x").unwrap();
+        let unchanged = create(dir.path(), "s2").unwrap();
+
+        assert_eq!(find(dir.path(), "s1").unwrap().as_ref(), Some(&changed));
+        changed.finish(Some(WorktreeAction::Discard), "s1").unwrap();
+        unchanged.finish(None, "s2").unwrap();
+
+        assert!(!dir.path().join("scratch.txt").exists());
+        assert!(git(dir.path(), &["branch", "--list", "bert/*"]).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,293 @@
+//! Opt-in auto-commit of the beads file
+//!
+//! Teams forget to commit `.beads/issues.jsonl`, so edits made in the app sit
+//! in the working tree until someone notices. With `autoCommit.enabled` in
+//! `.beads/bert-viz.json`, every app mutation (each `emit_beads_updated`)
+//! schedules a commit of the beads file once `batchWindowSecs` passed without
+//! further edits. Operations applied through `pending_ops` leave a message
+//! like `bd: close bp6-123 (reason)`; a batch of several becomes one commit
+//! listing them. In sync-branch mode the beads file lives in the sync branch
+//! worktree, so the commit lands on the sync branch.
+//!
+//! Only the beads file is committed (`git commit --only`), never whatever
+//! else the user staged. Outcomes are kept in memory for `get_autocommit_log`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Map;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::bd::BeadOp;
+use crate::error::BertError;
+use crate::events::{emit_scoped, EventScope};
+use crate::git::git;
+use crate::project::{context_for, ProjectContext};
+use crate::schedule::load_config_section;
+
+/// Key of the auto-commit settings in the project config file
+const CONFIG_KEY: &str = "autoCommit";
+
+/// Outcomes kept per beads file
+const LOG_LIMIT: usize = 100;
+
+/// Longest batching window accepted
+const MAX_BATCH_WINDOW_SECS: u64 = 3600;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AutoCommitConfig {
+    pub enabled: bool,
+    /// Seconds without further edits before the pending changes are committed
+    pub batch_window_secs: u64,
+}
+
+impl Default for AutoCommitConfig {
+    fn default() -> Self {
+        AutoCommitConfig { enabled: false, batch_window_secs: 10 }
+    }
+}
+
+/// One auto-commit attempt
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoCommitEntry {
+    /// Hash of the new commit; `None` when it failed
+    pub commit: Option<String>,
+    pub message: String,
+    /// Branch committed to (the sync branch in sync-branch mode)
+    pub branch: Option<String>,
+    /// RFC 3339
+    pub at: String,
+    pub error: Option<String>,
+}
+
+/// Edits waiting for the batching window to close, per beads file
+#[derive(Default)]
+struct Batch {
+    messages: Vec<String>,
+    /// A timer thread is waiting to commit this batch
+    scheduled: bool,
+    /// Bumped by every edit; the timer commits once it stops changing
+    generation: u64,
+}
+
+static BATCHES: OnceLock<Mutex<HashMap<PathBuf, Batch>>> = OnceLock::new();
+static LOG: OnceLock<Mutex<HashMap<PathBuf, VecDeque<AutoCommitEntry>>>> = OnceLock::new();
+
+fn batches() -> &'static Mutex<HashMap<PathBuf, Batch>> {
+    BATCHES.get_or_init(Default::default)
+}
+
+fn log() -> &'static Mutex<HashMap<PathBuf, VecDeque<AutoCommitEntry>>> {
+    LOG.get_or_init(Default::default)
+}
+
+pub(crate) fn load_config(beads_path: &Path) -> Result<AutoCommitConfig, BertError> {
+    Ok(load_config_section(beads_path, CONFIG_KEY)?.unwrap_or_default())
+}
+
+/// Commit message line for an applied operation; `bead_id` is the affected
+/// bead (the new ID for creates)
+pub fn op_message(op: &BeadOp, bead_id: &str) -> String {
+    match op {
+        BeadOp::Create { bead } => format!("bd: create {} {}", bead_id, bead.title),
+        BeadOp::Update { .. } => format!("bd: update {}", bead_id),
+        BeadOp::Close { reason: Some(reason), .. } if !reason.trim().is_empty() => {
+            format!("bd: close {} ({})", bead_id, reason.trim())
+        }
+        BeadOp::Close { .. } => format!("bd: close {}", bead_id),
+        BeadOp::Reopen { .. } => format!("bd: reopen {}", bead_id),
+        BeadOp::Claim { .. } => format!("bd: claim {}", bead_id),
+        BeadOp::AddDependency { depends_on_id, dep_type, .. } => {
+            format!("bd: dep add {} {} ({})", bead_id, depends_on_id, dep_type)
+        }
+        BeadOp::RemoveDependency { depends_on_id, .. } => format!("bd: dep rm {} {}", bead_id, depends_on_id),
+    }
+}
+
+/// Full commit message for a batch of operation messages
+fn batch_message(messages: &[String]) -> String {
+    match messages {
+        [] => "bd: update beads".to_string(),
+        [single] => single.clone(),
+        many => {
+            let body: Vec<String> = many.iter().map(|m| format!("- {}", m)).collect();
+            format!("bd: {} changes\n\n{}", many.len(), body.join("\n"))
+        }
+    }
+}
+
+/// Remember what an applied operation did, for the next auto-commit of the
+/// project's beads file
+pub fn note(ctx: &ProjectContext, message: String) {
+    let Ok(beads_path) = ctx.beads_path() else { return };
+    batches().lock().unwrap().entry(beads_path).or_default().messages.push(message);
+}
+
+/// Commit the project's beads file once the batching window passes without
+/// further edits, if auto-commit is enabled for it
+pub fn schedule(app_handle: &AppHandle, ctx: &ProjectContext) {
+    let Ok(beads_path) = ctx.beads_path() else { return };
+    let config = match load_config(&beads_path) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("⚠️  Not auto-committing {}: {}", beads_path.display(), e);
+            AutoCommitConfig::default()
+        }
+    };
+
+    let mut pending = batches().lock().unwrap();
+    if !config.enabled {
+        pending.remove(&beads_path);
+        return;
+    }
+    let batch = pending.entry(beads_path.clone()).or_default();
+    batch.generation += 1;
+    if batch.scheduled {
+        return;
+    }
+    batch.scheduled = true;
+
+    let window = Duration::from_secs(config.batch_window_secs.min(MAX_BATCH_WINDOW_SECS));
+    let mut seen = batch.generation;
+    let (handle, ctx) = (app_handle.clone(), ctx.clone());
+    std::thread::spawn(move || {
+        let messages = loop {
+            std::thread::sleep(window);
+            let mut batches = batches().lock().unwrap();
+            let Some(batch) = batches.get_mut(&beads_path) else { return };
+            if batch.generation == seen {
+                batch.scheduled = false;
+                break std::mem::take(&mut batch.messages);
+            }
+            seen = batch.generation;
+        };
+
+        let entry = commit_beads_file(&beads_path, &batch_message(&messages));
+        match (&entry.commit, &entry.error) {
+            (Some(commit), _) => eprintln!("📝 Auto-committed {} as {}", beads_path.display(), commit),
+            (None, Some(error)) => eprintln!("⚠️  Auto-commit of {} failed: {}", beads_path.display(), error),
+            (None, None) => return,
+        }
+        emit_scoped(&handle, EventScope::Project(&ctx), "autocommit-created", entry.clone());
+
+        let mut log = log().lock().unwrap();
+        let entries = log.entry(beads_path).or_default();
+        entries.push_front(entry);
+        entries.truncate(LOG_LIMIT);
+    });
+}
+
+/// Stage and commit only the beads file in the checkout holding it. The entry
+/// has neither a commit nor an error when there was nothing to commit.
+pub(crate) fn commit_beads_file(beads_path: &Path, message: &str) -> AutoCommitEntry {
+    let mut entry = AutoCommitEntry {
+        commit: None,
+        message: message.to_string(),
+        branch: None,
+        at: chrono::Utc::now().to_rfc3339(),
+        error: None,
+    };
+    // .beads/issues.jsonl -> checkout root (repo or sync worktree)
+    let Some(checkout) = beads_path.parent().and_then(|p| p.parent()) else {
+        entry.error = Some(format!("Unexpected beads file location: {}", beads_path.display()));
+        return entry;
+    };
+    let relative = Path::new(".beads").join(beads_path.file_name().unwrap_or_default());
+    let relative = relative.to_string_lossy();
+
+    let result = (|| -> Result<Option<String>, BertError> {
+        git(checkout, &["add", "--", &relative])?;
+        if git(checkout, &["diff", "--cached", "--quiet", "--", &relative]).is_ok() {
+            return Ok(None);
+        }
+        git(checkout, &["commit", "--no-verify", "--only", "-m", message, "--", &relative])?;
+        Ok(Some(git(checkout, &["rev-parse", "--short", "HEAD"])?))
+    })();
+
+    entry.branch = git(checkout, &["symbolic-ref", "--short", "-q", "HEAD"]).ok();
+    match result {
+        Ok(commit) => entry.commit = commit,
+        Err(e) => entry.error = Some(e.to_string()),
+    }
+    entry
+}
+
+/// Auto-commit settings of the project
+#[tauri::command]
+pub fn get_autocommit_config(window: tauri::Window, project_handle: Option<String>) -> Result<AutoCommitConfig, BertError> {
+    load_config(&context_for(&window, project_handle.as_deref())?.beads_path()?)
+}
+
+/// Turn auto-commit on or off for the project and set its batching window
+#[tauri::command]
+pub fn save_autocommit_config(
+    config: AutoCommitConfig,
+    window: tauri::Window,
+    project_handle: Option<String>,
+) -> Result<(), BertError> {
+    if config.batch_window_secs > MAX_BATCH_WINDOW_SECS {
+        return Err(BertError::InvalidInput(format!(
+            "Batching window must be at most {} seconds",
+            MAX_BATCH_WINDOW_SECS
+        )));
+    }
+    let beads_path = context_for(&window, project_handle.as_deref())?.beads_path()?;
+    let mut fields = Map::new();
+    fields.insert(CONFIG_KEY.to_string(), serde_json::to_value(&config)?);
+    crate::schedule::merge_config_fields(&beads_path, fields)?;
+    eprintln!("📝 Auto-commit for {}: {:?}", beads_path.display(), config);
+    Ok(())
+}
+
+/// Auto-commits of the project's beads file since the app started, newest first
+#[tauri::command]
+pub fn get_autocommit_log(window: tauri::Window, project_handle: Option<String>) -> Result<Vec<AutoCommitEntry>, BertError> {
+    let beads_path = context_for(&window, project_handle.as_deref())?.beads_path()?;
+    Ok(log().lock().unwrap().get(&beads_path).map(|entries| entries.iter().cloned().collect()).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedule::config_path_for;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_op_messages() {
+        let close = BeadOp::Close { bead_id: "bp6-123".into(), reason: Some("shipped".into()) };
+        assert_eq!(op_message(&close, "bp6-123"), "bd: close bp6-123 (shipped)");
+        let close = BeadOp::Close { bead_id: "bp6-123".into(), reason: Some(" ".into()) };
+        assert_eq!(op_message(&close, "bp6-123"), "bd: close bp6-123");
+        let dep = BeadOp::AddDependency {
+            issue_id: "bp6-2".into(),
+            depends_on_id: "bp6-1".into(),
+            dep_type: "blocks".into(),
+        };
+        assert_eq!(op_message(&dep, "bp6-2"), "bd: dep add bp6-2 bp6-1 (blocks)");
+    }
+
+    #[test]
+    fn test_batch_message() {
+        assert_eq!(batch_message(&[]), "bd: update beads");
+        assert_eq!(batch_message(&["bd: claim bp6-1".to_string()]), "bd: claim bp6-1");
+        assert_eq!(
+            batch_message(&["bd: claim bp6-1".to_string(), "bd: close bp6-2".to_string()]),
+            "bd: 2 changes\n\n- bd: claim bp6-1\n- bd: close bp6-2"
+        );
+    }
+
+    #[test]
+    fn test_config_defaults_to_disabled() {
+        let dir = TempDir::new().unwrap();
+        let beads_path = dir.path().join("issues.jsonl");
+        assert_eq!(load_config(&beads_path).unwrap(), AutoCommitConfig::default());
+
+        fs::write(config_path_for(&beads_path), r#"{"autoCommit":{"enabled":true}}"#).unwrap();
+        assert_eq!(load_config(&beads_path).unwrap(), AutoCommitConfig { enabled: true, batch_window_secs: 10 });
+    }
+}
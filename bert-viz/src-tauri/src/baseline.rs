@@ -0,0 +1,351 @@
+//! Schedule baselines
+//!
+//! A baseline freezes the computed schedule (cell offsets, durations, critical
+//! path, blocked state) so later plans can be compared against it. Baselines
+//! are stored per project under ~/.bp6/baselines/<project>/<timestamp>.json.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::schedule::SchedulingConfig;
+use crate::workflow::WorkflowConfig;
+use crate::{Bead, BeadNode, FilterParams};
+
+/// Schedule of a single bead at baseline time
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BaselineEntry {
+    pub title: String,
+    pub status: String,
+    pub cell_offset: usize,
+    pub cell_count: usize,
+    pub is_critical: bool,
+    pub is_blocked: bool,
+    #[serde(default)]
+    pub start_date: Option<String>,
+    #[serde(default)]
+    pub end_date: Option<String>,
+}
+
+/// Summary shown in baseline lists
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BaselineInfo {
+    /// Timestamp-based ID, also the file stem
+    pub id: String,
+    pub name: Option<String>,
+    pub created_at: String,
+    pub bead_count: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Baseline {
+    #[serde(flatten)]
+    pub info: BaselineInfo,
+    pub project_path: String,
+    /// Bead ID -> scheduled position (sorted for stable diffs on disk)
+    pub entries: BTreeMap<String, BaselineEntry>,
+}
+
+/// How one bead moved relative to the baseline
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BeadDrift {
+    pub bead_id: String,
+    pub title: String,
+    /// Positive = starts later than planned
+    pub start_slip: i64,
+    /// Positive = takes longer than planned
+    pub duration_change: i64,
+    /// Blocked now but not at baseline time
+    pub newly_blocked: bool,
+    pub was_critical: bool,
+    pub is_critical: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BaselineComparison {
+    pub baseline: BaselineInfo,
+    /// Beads whose schedule changed, largest slip first
+    pub drift: Vec<BeadDrift>,
+    /// Beads that did not exist at baseline time
+    pub added: Vec<String>,
+    /// Beads from the baseline that no longer exist
+    pub removed: Vec<String>,
+}
+
+// ============================================================================
+// File Path Helpers
+// ============================================================================
+
+/// Replace anything but ASCII alphanumerics, '-', '_' and '.' so `name` is safe as a file name
+pub(crate) fn sanitize_file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' { c } else { '_' })
+        .collect()
+}
+
+/// 64-bit FNV-1a hash of `bytes`. Unlike `DefaultHasher` its value never
+/// changes between Rust releases, so it can name files that outlive a build.
+pub(crate) fn stable_hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3))
+}
+
+/// Key of a project in the ~/.bp6 stores: the folder name, for whoever
+/// browses them, and a hash of the canonical path, so same-named repos
+/// don't share data
+pub(crate) fn project_store_key(project_path: &Path) -> String {
+    let name = project_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("project");
+    let canonical = fs::canonicalize(project_path).unwrap_or_else(|_| project_path.to_path_buf());
+    format!("{}-{:016x}", sanitize_file_name(name), stable_hash(canonical.as_os_str().as_encoded_bytes()))
+}
+
+/// Get the baselines directory for a project (~/.bp6/baselines/<project key>)
+fn get_baselines_dir(project_path: &Path) -> Result<PathBuf, String> {
+    let home = dirs::home_dir().ok_or("Failed to get home directory")?;
+    let dir = home.join(".bp6").join("baselines").join(project_store_key(project_path));
+
+    if !dir.exists() {
+        fs::create_dir_all(&dir).map_err(|e| format!("Failed to create baselines directory: {}", e))?;
+    }
+    Ok(dir)
+}
+
+// ============================================================================
+// Snapshot and Comparison
+// ============================================================================
+
+/// Compute the current schedule for all beads (no filters applied, so the
+/// workflow's status categories don't affect which beads are included)
+fn snapshot_schedule(beads: &[Bead], scheduling: &SchedulingConfig) -> BTreeMap<String, BaselineEntry> {
+    let view_model = crate::bert_core::build_project_view_model(beads.to_vec(), &FilterParams::default(), scheduling, &WorkflowConfig::default());
+
+    fn walk(nodes: &[BeadNode], out: &mut BTreeMap<String, BaselineEntry>) {
+        for node in nodes {
+            out.insert(
+                node.id.clone(),
+                BaselineEntry {
+                    title: node.title.clone(),
+                    status: node.status.clone(),
+                    cell_offset: node.cell_offset,
+                    cell_count: node.cell_count,
+                    is_critical: node.is_critical,
+                    is_blocked: node.is_blocked,
+                    start_date: node.start_date.clone(),
+                    end_date: node.end_date.clone(),
+                },
+            );
+            walk(&node.children, out);
+        }
+    }
+
+    let mut entries = BTreeMap::new();
+    walk(&view_model.tree, &mut entries);
+    entries
+}
+
+fn compare_entries(
+    baseline: &BTreeMap<String, BaselineEntry>,
+    current: &BTreeMap<String, BaselineEntry>,
+) -> (Vec<BeadDrift>, Vec<String>, Vec<String>) {
+    let mut drift = Vec::new();
+    let mut added = Vec::new();
+
+    for (id, now) in current {
+        let Some(then) = baseline.get(id) else {
+            added.push(id.clone());
+            continue;
+        };
+
+        let entry = BeadDrift {
+            bead_id: id.clone(),
+            title: now.title.clone(),
+            start_slip: now.cell_offset as i64 - then.cell_offset as i64,
+            duration_change: now.cell_count as i64 - then.cell_count as i64,
+            newly_blocked: now.is_blocked && !then.is_blocked,
+            was_critical: then.is_critical,
+            is_critical: now.is_critical,
+        };
+        let changed = entry.start_slip != 0
+            || entry.duration_change != 0
+            || entry.newly_blocked
+            || entry.was_critical != entry.is_critical;
+        if changed {
+            drift.push(entry);
+        }
+    }
+
+    let removed: Vec<String> = baseline.keys().filter(|id| !current.contains_key(*id)).cloned().collect();
+
+    drift.sort_by(|a, b| b.start_slip.cmp(&a.start_slip).then(a.bead_id.cmp(&b.bead_id)));
+    (drift, added, removed)
+}
+
+fn current_project(window: &tauri::Window, project_handle: Option<&str>) -> Result<(PathBuf, Vec<Bead>, SchedulingConfig), String> {
+    let ctx = crate::project::context_for(window, project_handle)?;
+    let beads_path = ctx.beads_path()?;
+    let scheduling = SchedulingConfig::load_for_beads_file(&beads_path)?;
+    Ok((ctx.repo_root()?, crate::snapshot::read_beads(&beads_path)?, scheduling))
+}
+
+fn load_baseline(project_path: &Path, id: &str) -> Result<Baseline, String> {
+    if id.contains(['/', '\\']) || id.contains("..") {
+        return Err(format!("Invalid baseline ID: {}", id));
+    }
+    let path = get_baselines_dir(project_path)?.join(format!("{}.json", id));
+    let contents = fs::read_to_string(&path).map_err(|e| format!("Failed to read baseline {}: {}", id, e))?;
+    serde_json::from_str(&contents).map_err(|e| format!("Failed to parse baseline {}: {}", id, e))
+}
+
+/// Every readable baseline of a project
+pub(crate) fn all_baselines(project_path: &Path) -> Result<Vec<Baseline>, String> {
+    let dir = get_baselines_dir(project_path)?;
+    let mut baselines = Vec::new();
+    for entry in fs::read_dir(&dir).map_err(|e| format!("Failed to read baselines directory: {}", e))? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        match fs::read_to_string(&path).ok().and_then(|c| serde_json::from_str::<Baseline>(&c).ok()) {
+            Some(baseline) => baselines.push(baseline),
+            None => eprintln!("⚠️  Skipping unreadable baseline {}", path.display()),
+        }
+    }
+    Ok(baselines)
+}
+
+/// Write a baseline of a project under its ID; returns the file written
+pub(crate) fn store_baseline(project_path: &Path, baseline: &Baseline) -> Result<PathBuf, String> {
+    let path = get_baselines_dir(project_path)?.join(format!("{}.json", sanitize_file_name(&baseline.info.id)));
+    let contents = serde_json::to_string_pretty(baseline)
+        .map_err(|e| format!("Failed to serialize baseline: {}", e))?;
+    fs::write(&path, contents).map_err(|e| format!("Failed to write baseline file: {}", e))?;
+    Ok(path)
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// Snapshot the current schedule as a new baseline
+#[tauri::command]
+pub fn save_baseline(name: Option<String>, window: tauri::Window, project_handle: Option<String>) -> Result<BaselineInfo, String> {
+    let (project_path, beads, scheduling) = current_project(&window, project_handle.as_deref())?;
+    let entries = snapshot_schedule(&beads, &scheduling);
+
+    let now = chrono::Utc::now();
+    let info = BaselineInfo {
+        id: now.format("%Y%m%dT%H%M%S%3fZ").to_string(),
+        name: name.filter(|n| !n.trim().is_empty()),
+        created_at: now.to_rfc3339(),
+        bead_count: entries.len(),
+    };
+    let baseline = Baseline {
+        info: info.clone(),
+        project_path: project_path.to_string_lossy().to_string(),
+        entries,
+    };
+
+    crate::viewer::ensure_writable(&project_path)?;
+    let path = store_baseline(&project_path, &baseline)?;
+
+    eprintln!("📸 Saved baseline {} ({} beads) to {}", info.id, info.bead_count, path.display());
+    Ok(info)
+}
+
+/// List baselines for the current project, newest first
+#[tauri::command]
+pub fn list_baselines(window: tauri::Window, project_handle: Option<String>) -> Result<Vec<BaselineInfo>, String> {
+    let project_path = crate::project::context_for(&window, project_handle.as_deref())?.repo_root()?;
+    let mut infos: Vec<BaselineInfo> = all_baselines(&project_path)?.into_iter().map(|b| b.info).collect();
+    infos.sort_by(|a, b| b.id.cmp(&a.id));
+    Ok(infos)
+}
+
+/// Compare the current schedule against a saved baseline
+#[tauri::command]
+pub fn compare_baseline(id: String, window: tauri::Window, project_handle: Option<String>) -> Result<BaselineComparison, String> {
+    let (project_path, beads, scheduling) = current_project(&window, project_handle.as_deref())?;
+    let baseline = load_baseline(&project_path, &id)?;
+    let current = snapshot_schedule(&beads, &scheduling);
+
+    let (drift, added, removed) = compare_entries(&baseline.entries, &current);
+    eprintln!(
+        "📊 compare_baseline {}: {} drifted, {} added, {} removed",
+        id,
+        drift.len(),
+        added.len(),
+        removed.len()
+    );
+
+    Ok(BaselineComparison {
+        baseline: baseline.info,
+        drift,
+        added,
+        removed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bead(id: &str, deps: serde_json::Value) -> Bead {
+        crate::test_bead(serde_json::json!({
+            "id": id,
+            "dependencies": deps,
+        }))
+    }
+
+    fn config() -> SchedulingConfig {
+        SchedulingConfig {
+            start_date: Some("2026-01-05".to_string()),
+            ..SchedulingConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_detects_slip_added_and_removed() {
+        let before = vec![bead("a", serde_json::json!([])), bead("b", serde_json::json!([])), bead("c", serde_json::json!([]))];
+        let after = vec![
+            bead("a", serde_json::json!([])),
+            bead("b", serde_json::json!([{"issue_id": "b", "depends_on_id": "a", "type": "blocks"}])),
+            bead("d", serde_json::json!([])),
+        ];
+
+        let baseline = snapshot_schedule(&before, &config());
+        let current = snapshot_schedule(&after, &config());
+        let (drift, added, removed) = compare_entries(&baseline, &current);
+
+        let b = drift.iter().find(|d| d.bead_id == "b").expect("b should drift");
+        assert_eq!(b.start_slip, 1);
+        assert!(b.newly_blocked);
+        assert_eq!(added, vec!["d".to_string()]);
+        assert_eq!(removed, vec!["c".to_string()]);
+    }
+
+    #[test]
+    fn test_unchanged_schedule_has_no_drift() {
+        let beads = vec![bead("a", serde_json::json!([]))];
+        let snapshot = snapshot_schedule(&beads, &config());
+        let (drift, added, removed) = compare_entries(&snapshot, &snapshot);
+        assert!(drift.is_empty() && added.is_empty() && removed.is_empty());
+    }
+
+    #[test]
+    fn test_project_store_key_tells_same_named_projects_apart() {
+        let key = project_store_key(Path::new("/nonexistent/a/my project"));
+        assert!(key.starts_with("my_project-"), "{}", key);
+        assert_eq!(key, project_store_key(Path::new("/nonexistent/a/my project")));
+        assert_ne!(key, project_store_key(Path::new("/nonexistent/b/my project")));
+        // Fixed across toolchains
+        assert_eq!(stable_hash(b"bp6"), 0x0072_7219_1369_74bb);
+    }
+}
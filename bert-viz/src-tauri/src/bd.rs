@@ -1,10 +1,18 @@
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::time::Duration;
-use tauri::{AppHandle, Emitter};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::time::{Duration, Instant};
+use memmap2::Mmap;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tauri::AppHandle;
+use crate::error::BertError;
 use crate::Bead;
+use crate::pending_ops::Submitted;
+use crate::project::{context_for, ProjectContext};
+use crate::transitions::TransitionViolation;
 
 pub fn get_sync_branch_name(repo_path: &std::path::Path) -> Option<String> {
     // Try to read sync.branch from bd config
@@ -25,8 +33,9 @@ pub fn get_sync_branch_name(repo_path: &std::path::Path) -> Option<String> {
     None
 }
 
-pub fn find_beads_file() -> Option<PathBuf> {
-    let mut curr = std::env::current_dir().ok()?;
+/// Locate `.beads/issues.jsonl` starting at `start` and walking up
+pub fn find_beads_file_from(start: &Path) -> Option<PathBuf> {
+    let mut curr = start.to_path_buf();
     loop {
         // First check if there's a sync-branch worktree (remote/sync mode)
         if let Some(sync_branch) = get_sync_branch_name(&curr) {
@@ -55,9 +64,9 @@ pub fn find_beads_file() -> Option<PathBuf> {
     None
 }
 
-pub fn find_repo_root() -> Option<PathBuf> {
-    let curr_dir = std::env::current_dir().ok()?;
-    let mut curr = curr_dir.clone();
+/// Find the directory containing `.beads`, starting at `start` and walking up
+pub fn find_repo_root_from(start: &Path) -> Option<PathBuf> {
+    let mut curr = start.to_path_buf();
     loop {
         let beads_path = curr.join(".beads");
         if beads_path.exists() {
@@ -70,23 +79,75 @@ pub fn find_repo_root() -> Option<PathBuf> {
     None
 }
 
-pub fn check_bd_available() -> Result<(), String> {
-    if Command::new("bd").arg("--version").output().is_err() {
-        return Err("The 'bd' CLI is not found in the PATH. Please ensure it is installed and available.".to_string());
+pub fn check_bd_available() -> Result<(), BertError> {
+    if !is_bd_available() {
+        return Err(BertError::BdNotInstalled);
     }
     Ok(())
 }
 
+/// Whether the `bd` binary can be spawned. When it can't, mutations fall back
+/// to patching issues.jsonl directly (see the native fallback section below).
+pub fn is_bd_available() -> bool {
+    Command::new("bd").arg("--version").output().is_ok()
+}
+
 #[tauri::command]
-pub fn get_beads() -> Result<Vec<Bead>, String> {
-    let path = find_beads_file().ok_or_else(|| "Could not locate .beads/issues.jsonl in any parent directory".to_string())?;
+pub fn get_beads(window: tauri::Window, project_handle: Option<String>) -> Result<Vec<Bead>, BertError> {
+    load_beads(&context_for(&window, project_handle.as_deref())?)
+}
+
+/// Read all beads of a project
+pub fn load_beads(ctx: &ProjectContext) -> Result<Vec<Bead>, BertError> {
+    crate::snapshot::read_beads(&ctx.beads_path()?)
+}
+
+/// A line of issues.jsonl that isn't a valid bead and was skipped
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ParseWarning {
+    /// 1-based
+    pub line: usize,
+    pub message: String,
+    /// Start of the offending line, for the user to recognize it
+    pub excerpt: String,
+}
 
+/// Longest excerpt of a broken line kept in a `ParseWarning`
+const EXCERPT_CHARS: usize = 120;
+
+/// Lines skipped by the last read of each beads file
+static PARSE_WARNINGS: std::sync::Mutex<std::collections::BTreeMap<PathBuf, Vec<ParseWarning>>> =
+    std::sync::Mutex::new(std::collections::BTreeMap::new());
+
+/// Lines skipped by the last read of `beads_path`
+pub fn parse_warnings(beads_path: &Path) -> Vec<ParseWarning> {
+    PARSE_WARNINGS.lock().unwrap().get(beads_path).cloned().unwrap_or_default()
+}
+
+/// Remember the lines skipped by a read of `beads_path`, logging new ones
+pub(crate) fn record_parse_warnings(beads_path: &Path, warnings: Vec<ParseWarning>) {
+    let mut all = PARSE_WARNINGS.lock().unwrap();
+    if all.get(beads_path) != Some(&warnings) {
+        for warning in &warnings {
+            eprintln!("⚠️  Skipping line {} of {}: {}", warning.line, beads_path.display(), warning.message);
+        }
+    }
+    all.insert(beads_path.to_path_buf(), warnings);
+}
+
+/// Read beads from an issues.jsonl file
+///
+/// The file is memory-mapped and each line is parsed straight from the
+/// mapping, so large files are read without a `String` per line. Lines that
+/// aren't valid beads are skipped and listed by `parse_warnings`.
+pub fn read_beads(path: &Path) -> Result<Vec<Bead>, BertError> {
     // Retry opening and reading the file to handle transient locks and partial writes
     let mut last_error = String::new();
     for i in 0..5 {
-        match File::open(&path) {
+        match File::open(path) {
             Ok(file) => {
-                let metadata = file.metadata().map_err(|e| e.to_string())?;
+                let metadata = file.metadata()?;
 
                 if metadata.len() == 0 && i < 4 {
                     std::thread::sleep(Duration::from_millis(100 * (i + 1)));
@@ -94,286 +155,1561 @@ pub fn get_beads() -> Result<Vec<Bead>, String> {
                 }
 
                 if metadata.len() == 0 {
+                    record_parse_warnings(path, Vec::new());
                     return Ok(Vec::new());
                 }
 
-                let reader = BufReader::new(file);
-                let mut beads = Vec::new();
-                let mut had_parse_error = false;
-
-                for (index, line) in reader.lines().enumerate() {
-                    let line = match line {
-                        Ok(l) => l,
-                        Err(e) => {
-                            if i < 4 {
-                                had_parse_error = true;
-                                last_error = format!("IO error reading line {}: {}", index + 1, e);
-                                break;
-                            } else {
-                                return Err(format!("Error reading line {}: {}", index + 1, e));
-                            }
-                        }
-                    };
-
-                    if line.trim().is_empty() {
-                        continue;
-                    }
-
-                    match serde_json::from_str::<Bead>(&line) {
-                        Ok(bead) => beads.push(bead),
-                        Err(e) => {
-                            if i < 4 {
-                                had_parse_error = true;
-                                last_error = format!("Failed to parse bead at line {}: {}", index + 1, e);
-                                break;
-                            } else {
-                                return Err(format!("Failed to parse bead at line {}: {}", index + 1, e));
-                            }
-                        }
-                    }
-                }
-
-                if !had_parse_error {
-                    return Ok(beads);
+                // SAFETY: bd and the native fallback replace issues.jsonl by
+                // rename instead of rewriting it in place, so the mapped file
+                // is not modified while it is parsed. Anything else writing it
+                // concurrently shows up as a broken last line, which is retried.
+                let map = unsafe { Mmap::map(&file) }
+                    .map_err(|e| BertError::Io(format!("Failed to map {}: {}", path.display(), e)))?;
+                let (beads, warnings) = parse_beads_lenient(&map);
+                let torn = warnings.last().is_some_and(|w| w.line == last_line(&map));
+                if torn && i < 4 {
+                    last_error = warnings.last().map(|w| w.message.clone()).unwrap_or_default();
+                    std::thread::sleep(Duration::from_millis(100 * (i + 1)));
+                    continue;
                 }
-
-                std::thread::sleep(Duration::from_millis(100 * (i + 1)));
+                record_parse_warnings(path, warnings);
+                return Ok(beads);
             }
             Err(e) => {
                 if i == 4 {
-                    return Err(format!("Failed to open issues.jsonl after retries: {}", e));
+                    let message = format!("Failed to open issues.jsonl after retries: {}", e);
+                    return Err(match e.kind() {
+                        std::io::ErrorKind::NotFound => BertError::BeadsFileNotFound(message),
+                        std::io::ErrorKind::WouldBlock => BertError::FileLocked(message),
+                        _ => BertError::Io(message),
+                    });
                 }
                 std::thread::sleep(Duration::from_millis(100 * (i + 1)));
             }
         }
     }
 
-    Err(format!("Failed to read beads after retries. Last error: {}", last_error))
+    Err(BertError::Parse(format!("Failed to read beads after retries. Last error: {}", last_error)))
+}
+
+/// 1-based number of the last non-blank line of `bytes`, 0 if there is none
+fn last_line(bytes: &[u8]) -> usize {
+    bytes
+        .split(|&b| b == b'\n')
+        .enumerate()
+        .filter(|(_, line)| !line.iter().all(u8::is_ascii_whitespace))
+        .last()
+        .map_or(0, |(index, _)| index + 1)
 }
 
-pub fn get_bead_by_id(id: &str) -> Result<Bead, String> {
-    let beads = get_beads()?;
-    beads.into_iter()
-        .find(|b| b.id == id)
-        .ok_or_else(|| format!("Bead with ID {} not found", id))
+/// Parse the contents of an issues.jsonl file, skipping blank lines. Fails on
+/// the first line that isn't a valid bead.
+pub(crate) fn parse_beads(bytes: &[u8]) -> Result<Vec<Bead>, BertError> {
+    let (beads, warnings) = parse_beads_lenient(bytes);
+    match warnings.into_iter().next() {
+        Some(w) => Err(BertError::Parse(format!("Failed to parse bead at line {}: {}", w.line, w.message))),
+        None => Ok(beads),
+    }
+}
+
+/// Parse the contents of an issues.jsonl file, skipping blank lines and
+/// collecting a warning for each line that isn't a valid bead
+pub(crate) fn parse_beads_lenient(bytes: &[u8]) -> (Vec<Bead>, Vec<ParseWarning>) {
+    let line_count = bytes.iter().filter(|&&b| b == b'\n').count() + 1;
+    let mut beads = Vec::with_capacity(line_count);
+    let mut warnings = Vec::new();
+
+    for (index, line) in bytes.split(|&b| b == b'\n').enumerate() {
+        if line.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+        match serde_json::from_slice::<Bead>(line) {
+            Ok(mut bead) => {
+                hydrate_from_metadata(&mut bead);
+                beads.push(bead);
+            }
+            Err(e) => warnings.push(ParseWarning {
+                line: index + 1,
+                message: e.to_string(),
+                excerpt: String::from_utf8_lossy(line).trim().chars().take(EXCERPT_CHARS).collect(),
+            }),
+        }
+    }
+
+    (beads, warnings)
+}
+
+/// Lines of the window's beads file skipped by the last read, so the UI can
+/// point at them instead of silently showing fewer beads
+#[tauri::command]
+pub fn get_parse_warnings(window: tauri::Window, project_handle: Option<String>) -> Result<Vec<ParseWarning>, BertError> {
+    let beads_path = context_for(&window, project_handle.as_deref())?.beads_path()?;
+    if !PARSE_WARNINGS.lock().unwrap().contains_key(&beads_path) {
+        // Not read yet in this session; reading records its warnings
+        crate::snapshot::read_beads(&beads_path)?;
+    }
+    Ok(parse_warnings(&beads_path))
+}
+
+/// The bead's `--metadata` blob, which bd stores as an object or a JSON string
+fn bd_metadata(bead: &Bead) -> Option<Map<String, Value>> {
+    match bead.extra_metadata.get("metadata") {
+        Some(Value::Object(obj)) => Some(obj.clone()),
+        Some(Value::String(raw)) => serde_json::from_str::<Map<String, Value>>(raw).ok(),
+        _ => None,
+    }
+}
+
+/// bd has no flags for due dates, milestones, sibling ranks, acceptance
+/// checklists or external dependencies, so they round-trip through the
+/// `--metadata` blob. Lift them back onto the Bead when the top-level fields
+/// are absent.
+fn hydrate_from_metadata(bead: &mut Bead) {
+    let Some(metadata) = bd_metadata(bead) else { return };
+
+    if bead.due_date.is_none() {
+        bead.due_date = metadata.get("due_date").and_then(|v| v.as_str()).map(|s| s.to_string());
+    }
+    if bead.is_milestone.is_none() {
+        bead.is_milestone = metadata.get("is_milestone").and_then(|v| v.as_bool());
+    }
+    if bead.is_favorite.is_none() {
+        bead.is_favorite = metadata.get("is_favorite").and_then(|v| v.as_bool());
+    }
+    if let Some(rank) = metadata.get(SORT_RANK_KEY).filter(|v| v.is_number()) {
+        bead.extra_metadata.entry(SORT_RANK_KEY).or_insert_with(|| rank.clone());
+    }
+    if let Some(items) = metadata.get(crate::acceptance::CHECKLIST_KEY).filter(|v| v.is_array()) {
+        bead.extra_metadata.entry(crate::acceptance::CHECKLIST_KEY).or_insert_with(|| items.clone());
+    }
+    if let Some(refs) = metadata.get(crate::external::EXTERNAL_DEPS_KEY).filter(|v| v.is_array()) {
+        bead.extra_metadata.entry(crate::external::EXTERNAL_DEPS_KEY).or_insert_with(|| refs.clone());
+    }
+}
+
+/// Key of a bead's position among its siblings, in `extra_metadata`
+pub(crate) const SORT_RANK_KEY: &str = "sort_rank";
+
+/// Position of a bead among its siblings, if one was stored
+pub(crate) fn sort_rank(bead: &Bead) -> Option<i64> {
+    bead.extra_metadata.get(SORT_RANK_KEY).and_then(|v| v.as_i64())
+}
+
+pub fn get_bead_by_id(ctx: &ProjectContext, id: &str) -> Result<Bead, BertError> {
+    crate::bead_index::get_bead_internal(ctx, id)
 }
 
 #[tauri::command]
 #[allow(non_snake_case)]
-pub fn update_bead(updatedBead: Bead, app_handle: AppHandle) -> Result<(), String> {
-    check_bd_available()?;
-    let repo_path = find_repo_root().ok_or_else(|| "Could not locate .beads directory in any parent".to_string())?;
+pub fn update_bead(
+    updatedBead: Bead,
+    preview: Option<bool>,
+    force: Option<bool>,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    app_handle: AppHandle,
+) -> Result<Previewed<Vec<TransitionViolation>>, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    let op = BeadOp::Update { bead: updatedBead };
+    let warnings = crate::transitions::check(&ctx, &op, force.unwrap_or(false))?;
+    if preview.unwrap_or(false) {
+        return Ok(Previewed::Preview(preview_bead_op(&ctx, &op)?));
+    }
+    submit_op(&ctx, &app_handle, op)?;
+    Ok(Previewed::Applied(warnings))
+}
+
+pub(crate) fn update_bead_internal(ctx: &ProjectContext, bead: &Bead) -> Result<(), BertError> {
+    ctx.ensure_writable()?;
+    if !is_bd_available() {
+        eprintln!("⚠️  bd CLI missing, patching issues.jsonl directly for {}", bead.id);
+        native_update_bead(&ctx.beads_path()?, bead)?;
+        return Ok(());
+    }
+
+    let repo_path = ctx.repo_root()?;
+    let output = Command::new("bd").args(update_args(bead)?).current_dir(repo_path).output()?;
+
+    if !output.status.success() {
+        return Err(BertError::BdCommandFailed(String::from_utf8_lossy(&output.stderr).to_string()));
+    }
+
+    Ok(())
+}
 
-    let mut cmd = Command::new("bd");
-    cmd.arg("update")
-        .arg(&updatedBead.id)
-        .arg("--title").arg(&updatedBead.title)
-        .arg("--status").arg(&updatedBead.status)
-        .arg("--priority").arg(updatedBead.priority.to_string())
-        .arg("--type").arg(&updatedBead.issue_type);
+/// Arguments of the `bd update` that writes every field of `bead`
+fn update_args(bead: &Bead) -> Result<Vec<String>, BertError> {
+    let mut args = vec![
+        "update".to_string(),
+        bead.id.clone(),
+        "--title".to_string(), bead.title.clone(),
+        "--status".to_string(), bead.status.clone(),
+        "--priority".to_string(), bead.priority.to_string(),
+        "--type".to_string(), bead.issue_type.clone(),
+    ];
+    let mut flag = |name: &str, value: String| {
+        args.push(name.to_string());
+        args.push(value);
+    };
 
-    if let Some(desc) = &updatedBead.description {
-        cmd.arg("--description").arg(desc);
+    if let Some(desc) = &bead.description {
+        flag("--description", desc.clone());
     }
-    if let Some(est) = updatedBead.estimate {
-        cmd.arg("--estimate").arg(est.to_string());
+    if let Some(est) = bead.estimate {
+        flag("--estimate", est.to_string());
     }
-    if let Some(owner) = &updatedBead.owner {
-        cmd.arg("--assignee").arg(owner);
+    if let Some(owner) = &bead.owner {
+        flag("--assignee", owner.clone());
     }
-    if let Some(labels) = &updatedBead.labels {
+    if let Some(labels) = &bead.labels {
         if !labels.is_empty() {
-            cmd.arg("--set-labels").arg(labels.join(","));
+            flag("--set-labels", labels.join(","));
         }
     }
-    if let Some(ac) = &updatedBead.acceptance_criteria {
+    if let Some(ac) = &bead.acceptance_criteria {
         if !ac.is_empty() {
-            cmd.arg("--acceptance").arg(ac.join("\n"));
+            flag("--acceptance", ac.join("\n"));
         }
     }
-    if let Some(parent) = &updatedBead.parent {
-        cmd.arg("--parent").arg(parent);
+    if let Some(parent) = &bead.parent {
+        flag("--parent", parent.clone());
+    }
+    if let Some(ext_ref) = &bead.external_reference {
+        flag("--external-ref", ext_ref.clone());
     }
-    if let Some(ext_ref) = &updatedBead.external_reference {
-        cmd.arg("--external-ref").arg(ext_ref);
+    if let Some(design) = &bead.design {
+        flag("--design", design.clone());
     }
-    if let Some(design) = &updatedBead.design {
-        cmd.arg("--design").arg(design);
+    if let Some(notes) = &bead.notes {
+        flag("--notes", notes.clone());
     }
-    if let Some(notes) = &updatedBead.notes {
-        cmd.arg("--notes").arg(notes);
+
+    let metadata_json = serde_json::to_string(bead).map_err(|e| e.to_string())?;
+    flag("--metadata", metadata_json);
+    Ok(args)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn close_bead(
+    beadId: String,
+    reason: Option<String>,
+    preview: Option<bool>,
+    force: Option<bool>,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    app_handle: AppHandle,
+) -> Result<Previewed<Vec<TransitionViolation>>, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    let op = BeadOp::Close { bead_id: beadId, reason };
+    let warnings = crate::transitions::check(&ctx, &op, force.unwrap_or(false))?;
+    if preview.unwrap_or(false) {
+        return Ok(Previewed::Preview(preview_bead_op(&ctx, &op)?));
     }
+    submit_op(&ctx, &app_handle, op)?;
+    Ok(Previewed::Applied(warnings))
+}
 
-    let metadata_json = serde_json::to_string(&updatedBead).map_err(|e| e.to_string())?;
-    cmd.arg("--metadata").arg(metadata_json);
+fn close_bead_internal(ctx: &ProjectContext, bead_id: &str, reason: Option<&str>) -> Result<(), BertError> {
+    ctx.ensure_writable()?;
+    if !is_bd_available() {
+        native_close_bead(&ctx.beads_path()?, bead_id, reason)?;
+        return Ok(());
+    }
 
-    let output = cmd.current_dir(repo_path).output().map_err(|e| e.to_string())?;
+    let repo_path = ctx.repo_root()?;
+    let output = Command::new("bd").args(close_args(bead_id, reason)).current_dir(repo_path).output()?;
 
     if !output.status.success() {
-        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+        return Err(BertError::BdCommandFailed(String::from_utf8_lossy(&output.stderr).to_string()));
     }
 
-    let _ = app_handle.emit("beads-updated", ());
     Ok(())
 }
 
+fn close_args(bead_id: &str, reason: Option<&str>) -> Vec<String> {
+    let mut args = vec!["close".to_string(), bead_id.to_string()];
+    if let Some(r) = reason {
+        args.push("--reason".to_string());
+        args.push(r.to_string());
+    }
+    args
+}
+
 #[tauri::command]
 #[allow(non_snake_case)]
-pub fn close_bead(beadId: String, reason: Option<String>, app_handle: AppHandle) -> Result<(), String> {
-    check_bd_available()?;
-    let repo_path = find_repo_root().ok_or_else(|| "Could not locate .beads directory in any parent".to_string())?;
-
-    let mut cmd = Command::new("bd");
-    cmd.arg("close").arg(&beadId);
+pub fn reopen_bead(beadId: String, force: Option<bool>, window: tauri::Window, project_handle: Option<String>, app_handle: AppHandle) -> Result<Vec<TransitionViolation>, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    let op = BeadOp::Reopen { bead_id: beadId };
+    let warnings = crate::transitions::check(&ctx, &op, force.unwrap_or(false))?;
+    submit_op(&ctx, &app_handle, op)?;
+    Ok(warnings)
+}
 
-    if let Some(r) = reason {
-        cmd.arg("--reason").arg(r);
+fn reopen_bead_internal(ctx: &ProjectContext, bead_id: &str) -> Result<(), BertError> {
+    ctx.ensure_writable()?;
+    if !is_bd_available() {
+        native_reopen_bead(&ctx.beads_path()?, bead_id)?;
+        return Ok(());
     }
 
-    let output = cmd.current_dir(repo_path).output().map_err(|e| e.to_string())?;
+    let repo_path = ctx.repo_root()?;
+    let output = Command::new("bd").args(reopen_args(bead_id)).current_dir(repo_path).output()?;
 
     if !output.status.success() {
-        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+        return Err(BertError::BdCommandFailed(String::from_utf8_lossy(&output.stderr).to_string()));
     }
 
-    let _ = app_handle.emit("beads-updated", ());
     Ok(())
 }
 
+fn reopen_args(bead_id: &str) -> Vec<String> {
+    vec!["reopen".to_string(), bead_id.to_string()]
+}
+
 #[tauri::command]
 #[allow(non_snake_case)]
-pub fn reopen_bead(beadId: String, app_handle: AppHandle) -> Result<(), String> {
-    check_bd_available()?;
-    let repo_path = find_repo_root().ok_or_else(|| "Could not locate .beads directory in any parent".to_string())?;
+pub fn claim_bead(beadId: String, force: Option<bool>, window: tauri::Window, project_handle: Option<String>, app_handle: AppHandle) -> Result<Vec<TransitionViolation>, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    let op = BeadOp::Claim { bead_id: beadId };
+    let warnings = crate::transitions::check(&ctx, &op, force.unwrap_or(false))?;
+    submit_op(&ctx, &app_handle, op)?;
+    Ok(warnings)
+}
 
-    let mut cmd = Command::new("bd");
-    cmd.arg("reopen").arg(&beadId);
+pub(crate) fn claim_bead_internal(ctx: &ProjectContext, bead_id: &str) -> Result<(), BertError> {
+    ctx.ensure_writable()?;
+    if !is_bd_available() {
+        native_set_status(&ctx.beads_path()?, bead_id, "in_progress")?;
+        return Ok(());
+    }
 
-    let output = cmd.current_dir(repo_path).output().map_err(|e| e.to_string())?;
+    let repo_path = ctx.repo_root()?;
+    let output = Command::new("bd").args(claim_args(bead_id)).current_dir(repo_path).output()?;
 
     if !output.status.success() {
-        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+        return Err(BertError::BdCommandFailed(String::from_utf8_lossy(&output.stderr).to_string()));
     }
 
-    let _ = app_handle.emit("beads-updated", ());
     Ok(())
 }
 
+fn claim_args(bead_id: &str) -> Vec<String> {
+    vec!["update".to_string(), bead_id.to_string(), "--status".to_string(), "in_progress".to_string()]
+}
+
 #[tauri::command]
 #[allow(non_snake_case)]
-pub fn claim_bead(beadId: String, app_handle: AppHandle) -> Result<(), String> {
-    check_bd_available()?;
-    let repo_path = find_repo_root().ok_or_else(|| "Could not locate .beads directory in any parent".to_string())?;
+pub fn create_bead(newBead: Bead, preview: Option<bool>, window: tauri::Window, project_handle: Option<String>, app_handle: AppHandle) -> Result<Previewed<String>, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    let op = BeadOp::Create { bead: newBead };
+    if preview.unwrap_or(false) {
+        return Ok(Previewed::Preview(preview_bead_op(&ctx, &op)?));
+    }
+    submit_op(&ctx, &app_handle, op).map(Previewed::Applied)
+}
 
-    let mut cmd = Command::new("bd");
-    cmd.arg("update")
-        .arg(&beadId)
-        .arg("--status")
-        .arg("in_progress");
+pub(crate) fn create_bead_internal(ctx: &ProjectContext, bead: &Bead) -> Result<String, BertError> {
+    ctx.ensure_writable()?;
+    if !is_bd_available() {
+        let new_id = native_create_bead(&ctx.beads_path()?, bead)?;
+        eprintln!("⚠️  bd CLI missing, appended {} to issues.jsonl directly", new_id);
+        return Ok(new_id);
+    }
 
-    let output = cmd.current_dir(repo_path).output().map_err(|e| e.to_string())?;
+    let repo_path = ctx.repo_root()?;
+    let output = Command::new("bd").args(create_args(bead)).current_dir(&repo_path).output()?;
 
     if !output.status.success() {
-        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(BertError::BdCommandFailed(format!("CLI Create Error: {}", stderr)));
     }
 
-    let _ = app_handle.emit("beads-updated", ());
-    Ok(())
-}
+    let new_id = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if new_id.is_empty() {
+        return Err(BertError::BdCommandFailed("Create command succeeded but returned no ID".to_string()));
+    }
 
-#[tauri::command]
-#[allow(non_snake_case)]
-pub fn create_bead(newBead: Bead, app_handle: AppHandle) -> Result<String, String> {
-    check_bd_available()?;
-    let repo_path = find_repo_root().ok_or_else(|| "Could not locate .beads directory in any parent".to_string())?;
+    let update_output = Command::new("bd").args(created_update_args(&new_id, bead)?).current_dir(&repo_path).output()?;
 
-    let mut cmd = Command::new("bd");
-    cmd.arg("create")
-        .arg(&newBead.title)
-        .arg("--priority").arg(newBead.priority.to_string())
-        .arg("--type").arg(&newBead.issue_type)
-        .arg("--silent");
+    if !update_output.status.success() {
+        let stderr = String::from_utf8_lossy(&update_output.stderr);
+        return Err(BertError::BdCommandFailed(format!(
+            "Bead created as {} but initial update failed: {}", 
+            new_id, 
+            stderr
+        )));
+    }
+
+    Ok(new_id)
+}
 
-    if let Some(desc) = &newBead.description {
-        cmd.arg("--description").arg(desc);
+/// Arguments of the `bd create` for `bead`, which prints only the new ID
+fn create_args(bead: &Bead) -> Vec<String> {
+    let mut args = vec![
+        "create".to_string(),
+        bead.title.clone(),
+        "--priority".to_string(), bead.priority.to_string(),
+        "--type".to_string(), bead.issue_type.clone(),
+        "--silent".to_string(),
+    ];
+    let mut flag = |name: &str, value: String| {
+        args.push(name.to_string());
+        args.push(value);
+    };
+
+    if let Some(desc) = &bead.description {
+        flag("--description", desc.clone());
     }
-    if let Some(est) = newBead.estimate {
-        cmd.arg("--estimate").arg(est.to_string());
+    if let Some(est) = bead.estimate {
+        flag("--estimate", est.to_string());
     }
-    if let Some(owner) = &newBead.owner {
-        cmd.arg("--assignee").arg(owner);
+    if let Some(owner) = &bead.owner {
+        flag("--assignee", owner.clone());
     }
-    if let Some(labels) = &newBead.labels {
+    if let Some(labels) = &bead.labels {
         if !labels.is_empty() {
-            cmd.arg("--labels").arg(labels.join(","));
+            flag("--labels", labels.join(","));
         }
     }
-    if let Some(ac) = &newBead.acceptance_criteria {
+    if let Some(ac) = &bead.acceptance_criteria {
         if !ac.is_empty() {
-            cmd.arg("--acceptance").arg(ac.join("\n"));
+            flag("--acceptance", ac.join("\n"));
         }
     }
-    if let Some(parent) = &newBead.parent {
-        cmd.arg("--parent").arg(parent);
+    if let Some(parent) = &bead.parent {
+        flag("--parent", parent.clone());
     }
-    if let Some(ext_ref) = &newBead.external_reference {
-        cmd.arg("--external-ref").arg(ext_ref);
+    if let Some(ext_ref) = &bead.external_reference {
+        flag("--external-ref", ext_ref.clone());
     }
-    if let Some(design) = &newBead.design {
-        cmd.arg("--design").arg(design);
+    if let Some(design) = &bead.design {
+        flag("--design", design.clone());
     }
-    if let Some(notes) = &newBead.notes {
-        cmd.arg("--notes").arg(notes);
+    if let Some(notes) = &bead.notes {
+        flag("--notes", notes.clone());
     }
+    args
+}
 
-    let output = cmd.current_dir(&repo_path).output().map_err(|e| e.to_string())?;
+/// Arguments of the `bd update` that sets the status and metadata `bd create`
+/// has no flags for, once the new bead has its ID
+fn created_update_args(new_id: &str, bead: &Bead) -> Result<Vec<String>, BertError> {
+    let metadata_json = serde_json::to_string(bead).map_err(|e| e.to_string())?;
+    Ok(vec![
+        "update".to_string(),
+        new_id.to_string(),
+        "--status".to_string(), bead.status.clone(),
+        "--metadata".to_string(), metadata_json,
+    ])
+}
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("CLI Create Error: {}", stderr));
+/// A single mutation within a `batch_update_beads` call.
+/// Serialized with an `op` tag, e.g. `{ "op": "close", "beadId": "bp6-1", "reason": "done" }`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum BeadOp {
+    Create { bead: Bead },
+    Update { bead: Bead },
+    Close {
+        #[serde(rename = "beadId")]
+        bead_id: String,
+        #[serde(default)]
+        reason: Option<String>,
+    },
+    Reopen {
+        #[serde(rename = "beadId")]
+        bead_id: String,
+    },
+    Claim {
+        #[serde(rename = "beadId")]
+        bead_id: String,
+    },
+    #[serde(rename = "add-dependency")]
+    AddDependency {
+        #[serde(rename = "issueId")]
+        issue_id: String,
+        #[serde(rename = "dependsOnId")]
+        depends_on_id: String,
+        #[serde(rename = "depType")]
+        dep_type: String,
+    },
+    #[serde(rename = "remove-dependency")]
+    RemoveDependency {
+        #[serde(rename = "issueId")]
+        issue_id: String,
+        #[serde(rename = "dependsOnId")]
+        depends_on_id: String,
+    },
+}
+
+/// Outcome of one operation in a batch, in the same order as the input.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BeadOpResult {
+    pub index: usize,
+    pub success: bool,
+    /// The affected bead ID (the newly assigned ID for creates)
+    pub bead_id: Option<String>,
+    pub error: Option<String>,
+    /// The environment was unavailable and the operation waits in the pending
+    /// queue (`error` says why)
+    #[serde(default)]
+    pub queued: bool,
+    /// What the operation would do, for previewed batches
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preview: Option<OpPreview>,
+}
+
+pub(crate) fn apply_bead_op(ctx: &ProjectContext, op: &BeadOp) -> Result<String, BertError> {
+    match op {
+        BeadOp::Create { bead } => create_bead_internal(ctx, bead),
+        BeadOp::Update { bead } => update_bead_internal(ctx, bead).map(|_| bead.id.clone()),
+        BeadOp::Close { bead_id, reason } => close_bead_internal(ctx, bead_id, reason.as_deref()).map(|_| bead_id.clone()),
+        BeadOp::Reopen { bead_id } => reopen_bead_internal(ctx, bead_id).map(|_| bead_id.clone()),
+        BeadOp::Claim { bead_id } => claim_bead_internal(ctx, bead_id).map(|_| bead_id.clone()),
+        BeadOp::AddDependency { issue_id, depends_on_id, dep_type } => {
+            add_dependency_internal(ctx, issue_id, depends_on_id, dep_type).map(|_| issue_id.clone())
+        }
+        BeadOp::RemoveDependency { issue_id, depends_on_id } => {
+            remove_dependency_internal(ctx, issue_id, depends_on_id).map(|_| issue_id.clone())
+        }
     }
+}
 
-    let new_id = String::from_utf8_lossy(&output.stdout).trim().to_string();
-    if new_id.is_empty() {
-        return Err("Create command succeeded but returned no ID".to_string());
+/// Stands for the ID `bd create` prints in previewed follow-up commands
+pub const NEW_ID_PLACEHOLDER: &str = "<new-id>";
+
+/// What a mutation would do, returned instead of running it when previewing
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OpPreview {
+    /// `bd` command lines that would run, in order; empty when `native`
+    pub commands: Vec<String>,
+    /// bd is missing, so issues.jsonl would be patched directly instead
+    pub native: bool,
+    /// The affected bead as it would look afterwards
+    pub bead: Option<Bead>,
+}
+
+/// Result of a mutating command that can be called with `preview`:
+/// its usual value when applied, the preview otherwise
+#[derive(Serialize, Debug)]
+#[serde(untagged)]
+pub enum Previewed<T> {
+    Applied(T),
+    Preview(OpPreview),
+}
+
+/// `bd` and `args` as a shell command line, quoting arguments that need it
+pub(crate) fn command_line(args: &[String]) -> String {
+    let quote = |arg: &String| {
+        let plain = !arg.is_empty() && arg.chars().all(|c| c.is_alphanumeric() || "-_./:,=@".contains(c));
+        if plain {
+            arg.clone()
+        } else {
+            format!("'{}'", arg.replace('\'', "'\\''"))
+        }
+    };
+    std::iter::once("bd".to_string()).chain(args.iter().map(quote)).collect::<Vec<_>>().join(" ")
+}
+
+/// The bead as predicted by earlier previews in the same batch, or as stored
+fn predicted_bead(ctx: &ProjectContext, predicted: &HashMap<String, Bead>, id: &str) -> Result<Bead, BertError> {
+    match predicted.get(id) {
+        Some(bead) => Ok(bead.clone()),
+        None => get_bead_by_id(ctx, id),
     }
+}
 
-    let mut update_cmd = Command::new("bd");
-    update_cmd.arg("update")
-        .arg(&new_id)
-        .arg("--status").arg(&newBead.status);
+/// Describe `op` without running it. `predicted` carries the beads changed
+/// by earlier operations of a batch so later ones build on them.
+fn preview_op(ctx: &ProjectContext, op: &BeadOp, predicted: &mut HashMap<String, Bead>, bd_available: bool) -> Result<OpPreview, BertError> {
+    let (args, bead) = match op {
+        BeadOp::Create { bead } => (vec![create_args(bead), created_update_args(NEW_ID_PLACEHOLDER, bead)?], bead.clone()),
+        BeadOp::Update { bead } => {
+            predicted_bead(ctx, predicted, &bead.id)?;
+            (vec![update_args(bead)?], bead.clone())
+        }
+        BeadOp::Close { bead_id, reason } => {
+            let mut bead = predicted_bead(ctx, predicted, bead_id)?;
+            bead.status = "closed".to_string();
+            bead.closed_at = Some(chrono::Utc::now().to_rfc3339());
+            bead.close_reason = reason.clone();
+            (vec![close_args(bead_id, reason.as_deref())], bead)
+        }
+        BeadOp::Reopen { bead_id } => {
+            let mut bead = predicted_bead(ctx, predicted, bead_id)?;
+            bead.status = "open".to_string();
+            bead.closed_at = None;
+            bead.close_reason = None;
+            (vec![reopen_args(bead_id)], bead)
+        }
+        BeadOp::Claim { bead_id } => {
+            let mut bead = predicted_bead(ctx, predicted, bead_id)?;
+            bead.status = "in_progress".to_string();
+            (vec![claim_args(bead_id)], bead)
+        }
+        BeadOp::AddDependency { issue_id, depends_on_id, dep_type } => {
+            let mut bead = predicted_bead(ctx, predicted, issue_id)?;
+            if !bead.dependencies.iter().any(|d| &d.depends_on_id == depends_on_id) {
+                bead.dependencies.push(crate::Dependency {
+                    issue_id: issue_id.clone(),
+                    depends_on_id: depends_on_id.clone(),
+                    r#type: dep_type.clone(),
+                    metadata: None,
+                });
+            }
+            (vec![dep_add_args(issue_id, depends_on_id, dep_type)], bead)
+        }
+        BeadOp::RemoveDependency { issue_id, depends_on_id } => {
+            let mut bead = predicted_bead(ctx, predicted, issue_id)?;
+            bead.dependencies.retain(|d| &d.depends_on_id != depends_on_id);
+            (vec![dep_rm_args(issue_id, depends_on_id)], bead)
+        }
+    };
 
-    let metadata_json = serde_json::to_string(&newBead).map_err(|e| e.to_string())?;
-    update_cmd.arg("--metadata").arg(metadata_json);
+    if !bead.id.is_empty() {
+        predicted.insert(bead.id.clone(), bead.clone());
+    }
+    Ok(OpPreview {
+        commands: if bd_available { args.iter().map(|a| command_line(a)).collect() } else { Vec::new() },
+        native: !bd_available,
+        bead: Some(bead),
+    })
+}
 
-    let update_output = update_cmd.current_dir(&repo_path).output().map_err(|e| e.to_string())?;
+/// Describe a single mutation without running it
+pub(crate) fn preview_bead_op(ctx: &ProjectContext, op: &BeadOp) -> Result<OpPreview, BertError> {
+    preview_op(ctx, op, &mut HashMap::new(), is_bd_available())
+}
 
-    if !update_output.status.success() {
-        let stderr = String::from_utf8_lossy(&update_output.stderr);
-        return Err(format!(
-            "Bead created as {} but initial update failed: {}", 
-            new_id, 
-            stderr
-        ));
+/// Apply one mutation, or queue it if the environment is unavailable (see
+/// `pending_ops`). Returns the affected bead ID, or the pending operation's
+/// handle for a queued create.
+pub(crate) fn submit_op(ctx: &ProjectContext, app_handle: &AppHandle, op: BeadOp) -> Result<String, BertError> {
+    match crate::pending_ops::submit(ctx, op)? {
+        Submitted::Applied(bead_id) => {
+            crate::project::emit_beads_updated(app_handle, ctx);
+            Ok(bead_id)
+        }
+        Submitted::Queued(pending) => {
+            crate::pending_ops::emit_changed(app_handle, ctx);
+            Ok(pending.affected_id())
+        }
     }
+}
 
-    let _ = app_handle.emit("beads-updated", ());
-    Ok(new_id)
+/// Execute a sequence of mutations, continuing past failures.
+/// Emits a single `beads-updated` event at the end instead of one per operation.
+/// With `preview`, nothing runs and each result carries its `OpPreview`.
+#[tauri::command]
+pub fn batch_update_beads(ops: Vec<BeadOp>, preview: Option<bool>, window: tauri::Window, project_handle: Option<String>, app_handle: AppHandle) -> Result<Vec<BeadOpResult>, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    if preview.unwrap_or(false) {
+        return Ok(preview_batch(&ctx, &ops));
+    }
+    Ok(apply_batch(&ctx, &app_handle, &ops))
+}
+
+/// Preview `ops` in order, each on top of the predicted result of the ones before
+pub(crate) fn preview_batch(ctx: &ProjectContext, ops: &[BeadOp]) -> Vec<BeadOpResult> {
+    let bd_available = is_bd_available();
+    let mut predicted = HashMap::new();
+    ops.iter()
+        .enumerate()
+        .map(|(index, op)| match preview_op(ctx, op, &mut predicted, bd_available) {
+            Ok(preview) => BeadOpResult {
+                index,
+                success: true,
+                bead_id: preview.bead.as_ref().map(|b| b.id.clone()).filter(|id| !id.is_empty()),
+                error: None,
+                queued: false,
+                preview: Some(preview),
+            },
+            Err(e) => BeadOpResult { index, success: false, bead_id: None, error: Some(e.to_string()), queued: false, preview: None },
+        })
+        .collect()
+}
+
+/// Apply `ops` in order, continuing past failures, and emit one
+/// `beads-updated` if any succeeded
+pub(crate) fn apply_batch(ctx: &ProjectContext, app_handle: &AppHandle, ops: &[BeadOp]) -> Vec<BeadOpResult> {
+    let start = std::time::Instant::now();
+    let mut results = Vec::with_capacity(ops.len());
+
+    for (index, op) in ops.iter().enumerate() {
+        let result = match crate::pending_ops::submit(ctx, op.clone()) {
+            Ok(Submitted::Applied(bead_id)) => {
+                BeadOpResult { index, success: true, bead_id: Some(bead_id), error: None, queued: false, preview: None }
+            }
+            Ok(Submitted::Queued(pending)) => BeadOpResult {
+                index,
+                success: true,
+                bead_id: Some(pending.affected_id()),
+                error: Some(pending.last_error.clone()),
+                queued: true,
+                preview: None,
+            },
+            Err(e) => {
+                eprintln!("❌ batch op {} failed: {}", index, e);
+                BeadOpResult { index, success: false, bead_id: None, error: Some(e.to_string()), queued: false, preview: None }
+            }
+        };
+        results.push(result);
+    }
+
+    if results.iter().any(|r| r.queued) {
+        crate::pending_ops::emit_changed(app_handle, ctx);
+    }
+    let succeeded = results.iter().filter(|r| r.success && !r.queued).count();
+    eprintln!(
+        "📦 batch_update_beads: {}/{} ops succeeded in {:.2}ms",
+        succeeded,
+        results.len(),
+        start.elapsed().as_secs_f64() * 1000.0
+    );
+
+    if succeeded > 0 {
+        crate::project::emit_beads_updated(app_handle, ctx);
+    }
+    results
+}
+
+/// Reject a new dependency edge if it would introduce a cycle.
+///
+/// For "blocks", `depends_on_id` must not already be (transitively) blocked by
+/// `issue_id`. For "parent-child", the new parent must not be a descendant of
+/// the child.
+pub(crate) fn validate_new_dependency(beads: &[Bead], issue_id: &str, depends_on_id: &str, dep_type: &str) -> Result<(), BertError> {
+    if issue_id == depends_on_id {
+        return Err(BertError::InvalidInput(format!("A bead cannot depend on itself ({})", issue_id)));
+    }
+    for id in [issue_id, depends_on_id] {
+        if !beads.iter().any(|b| b.id == id) {
+            return Err(BertError::NotFound(format!("Bead with ID {} not found", id)));
+        }
+    }
+
+    match dep_type {
+        "blocks" => {
+            let graph = crate::bert_core::build_dependency_graph(beads);
+            if graph.reaches(issue_id, depends_on_id) {
+                return Err(BertError::InvalidInput(format!(
+                    "Adding {} -> {} would create a dependency cycle",
+                    depends_on_id, issue_id
+                )));
+            }
+        }
+        "parent-child" => {
+            let parent_of: std::collections::HashMap<&str, &str> = beads
+                .iter()
+                .filter_map(|b| {
+                    b.dependencies
+                        .iter()
+                        .find(|d| d.r#type == "parent-child")
+                        .map(|d| (b.id.as_str(), d.depends_on_id.as_str()))
+                })
+                .collect();
+
+            let mut curr = Some(depends_on_id);
+            while let Some(id) = curr {
+                if id == issue_id {
+                    return Err(BertError::InvalidInput(format!(
+                        "{} is a descendant of {} and cannot become its parent",
+                        depends_on_id, issue_id
+                    )));
+                }
+                curr = parent_of.get(id).copied();
+            }
+        }
+        other => return Err(BertError::InvalidInput(format!("Unsupported dependency type: {}", other))),
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn add_dependency(issueId: String, dependsOnId: String, depType: String, window: tauri::Window, project_handle: Option<String>, app_handle: AppHandle) -> Result<(), BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    let beads = load_beads(&ctx)?;
+    validate_new_dependency(&beads, &issueId, &dependsOnId, &depType)?;
+    submit_op(&ctx, &app_handle, BeadOp::AddDependency { issue_id: issueId, depends_on_id: dependsOnId, dep_type: depType })?;
+    Ok(())
+}
+
+/// Add a dependency edge without validation or events (callers validate first)
+pub(crate) fn add_dependency_internal(ctx: &ProjectContext, issue_id: &str, depends_on_id: &str, dep_type: &str) -> Result<(), BertError> {
+    ctx.ensure_writable()?;
+    if !is_bd_available() {
+        return native_add_dependency(&ctx.beads_path()?, issue_id, depends_on_id, dep_type);
+    }
+
+    execute_bd(ctx, dep_add_args(issue_id, depends_on_id, dep_type))?;
+    Ok(())
+}
+
+fn dep_add_args(issue_id: &str, depends_on_id: &str, dep_type: &str) -> Vec<String> {
+    vec![
+        "dep".to_string(),
+        "add".to_string(),
+        issue_id.to_string(),
+        depends_on_id.to_string(),
+        "--type".to_string(),
+        dep_type.to_string(),
+    ]
 }
 
-pub fn execute_bd(args: Vec<String>) -> Result<String, String> {
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn remove_dependency(issueId: String, dependsOnId: String, window: tauri::Window, project_handle: Option<String>, app_handle: AppHandle) -> Result<(), BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    submit_op(&ctx, &app_handle, BeadOp::RemoveDependency { issue_id: issueId, depends_on_id: dependsOnId })?;
+    Ok(())
+}
+
+fn remove_dependency_internal(ctx: &ProjectContext, issue_id: &str, depends_on_id: &str) -> Result<(), BertError> {
+    ctx.ensure_writable()?;
+    if !is_bd_available() {
+        return native_remove_dependency(&ctx.beads_path()?, issue_id, depends_on_id);
+    }
+
+    execute_bd(ctx, dep_rm_args(issue_id, depends_on_id))?;
+    Ok(())
+}
+
+fn dep_rm_args(issue_id: &str, depends_on_id: &str) -> Vec<String> {
+    vec!["dep".to_string(), "rm".to_string(), issue_id.to_string(), depends_on_id.to_string()]
+}
+
+/// Make `parent_id` the bead's only parent, or make it a root bead with `None`
+pub(crate) fn set_parent_internal(ctx: &ProjectContext, bead: &Bead, parent_id: Option<&str>) -> Result<(), BertError> {
+    ctx.ensure_writable()?;
+    let current = bead.dependencies.iter().find(|d| d.r#type == "parent-child").map(|d| d.depends_on_id.as_str());
+    if current == parent_id {
+        return Ok(());
+    }
+
+    match (parent_id, is_bd_available()) {
+        (Some(parent_id), true) => {
+            execute_bd(ctx, vec!["update".to_string(), bead.id.clone(), "--parent".to_string(), parent_id.to_string()])?;
+        }
+        (Some(parent_id), false) => {
+            patch_jsonl_bead(&ctx.beads_path()?, &bead.id, |obj| set_parent_dependency(obj, &bead.id, parent_id))?;
+        }
+        (None, true) => {
+            if let Some(current) = current {
+                execute_bd(ctx, vec!["dep".to_string(), "rm".to_string(), bead.id.clone(), current.to_string()])?;
+            }
+        }
+        (None, false) => {
+            patch_jsonl_bead(&ctx.beads_path()?, &bead.id, |obj| {
+                if let Some(Value::Array(deps)) = obj.get_mut("dependencies") {
+                    deps.retain(|d| d.get("type").and_then(|t| t.as_str()) != Some("parent-child"));
+                }
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// Store a bead's position among its siblings
+pub(crate) fn set_sort_rank_internal(ctx: &ProjectContext, bead: &Bead, rank: i64) -> Result<(), BertError> {
+    if sort_rank(bead) == Some(rank) {
+        return Ok(());
+    }
+    set_metadata_value(ctx, bead, SORT_RANK_KEY, Value::from(rank))
+}
+
+/// Star or unstar a bead
+pub(crate) fn set_favorite_internal(ctx: &ProjectContext, bead: &Bead, favorite: bool) -> Result<(), BertError> {
+    set_metadata_value(ctx, bead, "is_favorite", Value::Bool(favorite))
+}
+
+/// Store `key` in the bd metadata of a bead, or as a top-level field of its
+/// JSONL record without bd
+fn set_metadata_value(ctx: &ProjectContext, bead: &Bead, key: &str, value: Value) -> Result<(), BertError> {
+    ctx.ensure_writable()?;
+    if !is_bd_available() {
+        return patch_jsonl_bead(&ctx.beads_path()?, &bead.id, |obj| {
+            obj.insert(key.to_string(), value);
+        });
+    }
+
+    // Keep whatever else the metadata blob holds
+    let mut metadata = bd_metadata(bead).unwrap_or_default();
+    metadata.insert(key.to_string(), value);
+    execute_bd(ctx, vec![
+        "update".to_string(),
+        bead.id.clone(),
+        "--metadata".to_string(),
+        Value::Object(metadata).to_string(),
+    ])?;
+    Ok(())
+}
+
+/// Replace a bead's labels
+pub(crate) fn set_labels_internal(ctx: &ProjectContext, bead_id: &str, labels: &[String]) -> Result<(), BertError> {
+    ctx.ensure_writable()?;
+    if !is_bd_available() {
+        return native_set_labels(&ctx.beads_path()?, bead_id, labels);
+    }
+    execute_bd(ctx, vec!["update".to_string(), bead_id.to_string(), "--set-labels".to_string(), labels.join(",")])?;
+    Ok(())
+}
+
+/// `bd` subcommands that never change the beads
+const READ_SUBCOMMANDS: &[&str] = &["list", "show", "ready", "blocked", "search", "stats", "info", "version"];
+
+/// Run `bd` in the project. Anything but a read fails on a read-only snapshot.
+pub fn execute_bd(ctx: &ProjectContext, args: Vec<String>) -> Result<String, BertError> {
+    if !args.first().is_some_and(|sub| READ_SUBCOMMANDS.contains(&sub.as_str())) {
+        ctx.ensure_writable()?;
+    }
     check_bd_available()?;
-    let repo_path = find_repo_root().ok_or_else(|| "Could not locate .beads directory in any parent".to_string())?;
+    let repo_path = ctx.repo_root()?;
+    if args.first().map(String::as_str) == Some("delete") {
+        if let Ok(beads_path) = ctx.beads_path() {
+            crate::snapshot::expect_removals(&beads_path);
+        }
+    }
 
+    let started = Instant::now();
     let output = Command::new("bd")
-        .args(args)
+        .args(&args)
         .current_dir(repo_path)
-        .output()
-        .map_err(|e| e.to_string())?;
+        .output()?;
 
     if !output.status.success() {
-        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        if started.elapsed() >= crate::notifications::LONG_BD_COMMAND {
+            crate::notifications::notify(
+                crate::notifications::NotificationKind::BdCommandFailed,
+                &format!("bd {} failed", args.first().map(String::as_str).unwrap_or_default()),
+                stderr.lines().next().unwrap_or_default(),
+            );
+        }
+        return Err(BertError::BdCommandFailed(stderr));
     }
 
     Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
 }
+
+
+// ============================================================================
+// Native JSONL fallback (used when the bd CLI is not installed)
+// ============================================================================
+//
+// Lines are handled as raw JSON objects rather than `Bead` so fields this app
+// doesn't model survive a rewrite untouched. Every write takes an exclusive
+// lock on a sibling `.lock` file and replaces issues.jsonl via atomic rename,
+// so the file watcher never observes a half-written file.
+
+/// Exclusive advisory lock on `<issues.jsonl>.lock`, released on drop.
+pub(crate) struct BeadsFileLock {
+    _file: File,
+}
+
+pub(crate) fn lock_beads_file(path: &Path) -> Result<BeadsFileLock, BertError> {
+    lock_file(&path.with_extension("jsonl.lock"))
+}
+
+/// Take an exclusive advisory lock on `lock_path`, creating it if needed
+pub(crate) fn lock_file(lock_path: &Path) -> Result<BeadsFileLock, BertError> {
+    let file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(false)
+        .open(lock_path)
+        .map_err(|e| BertError::Io(format!("Failed to open lock file {}: {}", lock_path.display(), e)))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::io::AsRawFd;
+        // SAFETY: the fd is owned by `file`, which outlives the lock
+        let rc = unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) };
+        if rc != 0 {
+            return Err(BertError::FileLocked(format!("Failed to lock {}: {}", lock_path.display(), std::io::Error::last_os_error())));
+        }
+    }
+
+    Ok(BeadsFileLock { _file: file })
+}
+
+/// Read every non-empty line of the JSONL file as a JSON object.
+pub(crate) fn read_jsonl_objects(path: &Path) -> Result<Vec<Map<String, Value>>, BertError> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let file = File::open(path).map_err(|e| BertError::Io(format!("Failed to open {}: {}", path.display(), e)))?;
+    let mut objects = Vec::new();
+
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|e| BertError::Io(format!("Error reading line {}: {}", index + 1, e)))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<Value>(&line) {
+            Ok(Value::Object(obj)) => objects.push(obj),
+            Ok(_) => return Err(BertError::Parse(format!("Line {} is not a JSON object", index + 1))),
+            Err(e) => return Err(BertError::Parse(format!("Failed to parse bead at line {}: {}", index + 1, e))),
+        }
+    }
+
+    Ok(objects)
+}
+
+/// Write all objects to a temp file next to `path`, then rename over it.
+pub(crate) fn write_jsonl_atomic(path: &Path, objects: &[Map<String, Value>]) -> Result<(), BertError> {
+    let tmp_path = path.with_extension("jsonl.tmp");
+    {
+        let mut tmp = File::create(&tmp_path)
+            .map_err(|e| BertError::Io(format!("Failed to create {}: {}", tmp_path.display(), e)))?;
+        for obj in objects {
+            let line = serde_json::to_string(obj).map_err(|e| e.to_string())?;
+            writeln!(tmp, "{}", line).map_err(|e| BertError::Io(format!("Failed to write {}: {}", tmp_path.display(), e)))?;
+        }
+        tmp.sync_all()?;
+    }
+
+    fs::rename(&tmp_path, path)
+        .map_err(|e| BertError::Io(format!("Failed to replace {}: {}", path.display(), e)))
+}
+
+/// Lock the file, apply `patch` to the bead with `id`, and write it back.
+fn patch_jsonl_bead<F>(path: &Path, id: &str, patch: F) -> Result<(), BertError>
+where
+    F: FnOnce(&mut Map<String, Value>),
+{
+    let _lock = lock_beads_file(path)?;
+    let mut objects = read_jsonl_objects(path)?;
+
+    let obj = objects
+        .iter_mut()
+        .find(|o| o.get("id").and_then(|v| v.as_str()) == Some(id))
+        .ok_or_else(|| BertError::NotFound(format!("Bead with ID {} not found", id)))?;
+
+    patch(obj);
+    obj.insert("updated_at".to_string(), Value::String(chrono::Utc::now().to_rfc3339()));
+
+    write_jsonl_atomic(path, &objects)
+}
+
+/// Point the bead's single parent-child dependency at `parent_id`.
+fn set_parent_dependency(obj: &mut Map<String, Value>, id: &str, parent_id: &str) {
+    let mut deps: Vec<Value> = obj
+        .get("dependencies")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    deps.retain(|d| d.get("type").and_then(|t| t.as_str()) != Some("parent-child"));
+    deps.push(serde_json::json!({
+        "issue_id": id,
+        "depends_on_id": parent_id,
+        "type": "parent-child",
+        "created_at": chrono::Utc::now().to_rfc3339(),
+    }));
+    obj.insert("dependencies".to_string(), Value::Array(deps));
+}
+
+/// Convert a Bead into the JSON object shape bd writes: None fields omitted,
+/// acceptance criteria joined into a single string.
+pub(crate) fn bead_to_jsonl_object(bead: &Bead) -> Result<Map<String, Value>, BertError> {
+    let mut obj = match serde_json::to_value(bead).map_err(|e| e.to_string())? {
+        Value::Object(obj) => obj,
+        _ => return Err(BertError::Internal("Bead did not serialize to a JSON object".to_string())),
+    };
+    obj.retain(|_, v| !v.is_null());
+    if let Some(ac) = &bead.acceptance_criteria {
+        obj.insert("acceptance_criteria".to_string(), Value::String(ac.join("\n")));
+    }
+    Ok(obj)
+}
+
+/// Stored fields an edit doesn't overwrite directly. Dependencies are managed
+/// separately (only the parent link follows the edit) and the close fields
+/// follow the status.
+const UPDATE_KEPT_KEYS: &[&str] = &["id", "dependencies", "created_at", "created_by", "closed_at", "close_reason"];
+
+pub(crate) fn native_update_bead(path: &Path, bead: &Bead) -> Result<(), BertError> {
+    let fields = bead_to_jsonl_object(bead)?;
+    let cleared: Vec<String> = match serde_json::to_value(bead)? {
+        Value::Object(obj) => obj.into_iter().filter(|(_, v)| v.is_null()).map(|(k, _)| k).collect(),
+        _ => Vec::new(),
+    };
+    let is_closed = bead.status == "closed" || bead.status == "done";
+    let now = chrono::Utc::now().to_rfc3339();
+
+    patch_jsonl_bead(path, &bead.id, |obj| {
+        for (key, value) in fields {
+            if UPDATE_KEPT_KEYS.contains(&key.as_str()) {
+                continue;
+            }
+            obj.insert(key, value);
+        }
+        for key in cleared {
+            if !UPDATE_KEPT_KEYS.contains(&key.as_str()) {
+                obj.remove(&key);
+            }
+        }
+
+        // Mirror native_close_bead / native_reopen_bead for status edits
+        if is_closed {
+            if !obj.contains_key("closed_at") {
+                let closed_at = bead.closed_at.clone().unwrap_or(now);
+                obj.insert("closed_at".to_string(), Value::String(closed_at));
+            }
+            if let Some(reason) = &bead.close_reason {
+                obj.insert("close_reason".to_string(), Value::String(reason.clone()));
+            }
+        } else {
+            obj.remove("closed_at");
+            obj.remove("close_reason");
+        }
+
+        match &bead.parent {
+            Some(parent) => set_parent_dependency(obj, &bead.id, parent),
+            // The edit dropped its parent-child link, so the bead becomes a root
+            None if !bead.dependencies.iter().any(|d| d.r#type == "parent-child") => {
+                if let Some(Value::Array(deps)) = obj.get_mut("dependencies") {
+                    deps.retain(|d| d.get("type").and_then(|t| t.as_str()) != Some("parent-child"));
+                }
+            }
+            None => {}
+        }
+    })
+}
+
+pub(crate) fn native_set_status(path: &Path, id: &str, status: &str) -> Result<(), BertError> {
+    patch_jsonl_bead(path, id, |obj| {
+        obj.insert("status".to_string(), Value::String(status.to_string()));
+    })
+}
+
+pub(crate) fn native_set_labels(path: &Path, id: &str, labels: &[String]) -> Result<(), BertError> {
+    patch_jsonl_bead(path, id, |obj| {
+        obj.insert("labels".to_string(), Value::from(labels.to_vec()));
+    })
+}
+
+pub(crate) fn native_close_bead(path: &Path, id: &str, reason: Option<&str>) -> Result<(), BertError> {
+    patch_jsonl_bead(path, id, |obj| {
+        obj.insert("status".to_string(), Value::String("closed".to_string()));
+        obj.insert("closed_at".to_string(), Value::String(chrono::Utc::now().to_rfc3339()));
+        if let Some(r) = reason {
+            obj.insert("close_reason".to_string(), Value::String(r.to_string()));
+        }
+    })
+}
+
+pub(crate) fn native_reopen_bead(path: &Path, id: &str) -> Result<(), BertError> {
+    patch_jsonl_bead(path, id, |obj| {
+        obj.insert("status".to_string(), Value::String("open".to_string()));
+        obj.remove("closed_at");
+        obj.remove("close_reason");
+    })
+}
+
+/// Generate an ID in bd's style: `<parent>.<n>` for children, otherwise
+/// `<prefix>-<3 base36 chars>` using the prefix of existing root beads.
+fn generate_bead_id(existing: &[Map<String, Value>], parent: Option<&str>, fallback_prefix: &str) -> String {
+    let ids: Vec<&str> = existing
+        .iter()
+        .filter_map(|o| o.get("id").and_then(|v| v.as_str()))
+        .collect();
+
+    if let Some(parent_id) = parent {
+        let child_prefix = format!("{}.", parent_id);
+        let next = ids
+            .iter()
+            .filter_map(|id| id.strip_prefix(&child_prefix))
+            .filter_map(|rest| rest.split('.').next()?.parse::<u32>().ok())
+            .max()
+            .unwrap_or(0)
+            + 1;
+        return format!("{}.{}", parent_id, next);
+    }
+
+    let prefix = ids
+        .iter()
+        .filter_map(|id| id.split('.').next()?.rsplit_once('-').map(|(p, _)| p.to_string()))
+        .next()
+        .unwrap_or_else(|| fallback_prefix.to_string());
+
+    const ALPHABET: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+    loop {
+        let bytes = uuid::Uuid::new_v4().into_bytes();
+        let suffix: String = bytes[..3]
+            .iter()
+            .map(|b| ALPHABET[(*b as usize) % ALPHABET.len()] as char)
+            .collect();
+        let candidate = format!("{}-{}", prefix, suffix);
+        if !ids.contains(&candidate.as_str()) {
+            return candidate;
+        }
+    }
+}
+
+/// `issue-prefix` from `.beads/config.yaml`, as written by `init_project`
+/// without bd
+pub(crate) fn configured_prefix(beads_dir: &Path) -> Option<String> {
+    let config = fs::read_to_string(beads_dir.join("config.yaml")).ok()?;
+    config.lines().find_map(|line| {
+        let value = line.trim().strip_prefix("issue-prefix:")?.trim().trim_matches(|c| c == '"' || c == '\'');
+        (!value.is_empty()).then(|| value.to_string())
+    })
+}
+
+pub(crate) fn native_create_bead(path: &Path, bead: &Bead) -> Result<String, BertError> {
+    let _lock = lock_beads_file(path)?;
+    let mut objects = read_jsonl_objects(path)?;
+
+    let fallback_prefix = path
+        .parent()
+        .and_then(configured_prefix)
+        .or_else(|| {
+            path.parent()
+                .and_then(|beads_dir| beads_dir.parent())
+                .and_then(|repo| repo.file_name())
+                .and_then(|n| n.to_str())
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| "bd".to_string());
+    let new_id = generate_bead_id(&objects, bead.parent.as_deref(), &fallback_prefix);
+
+    let mut obj = bead_to_jsonl_object(bead)?;
+    let now = chrono::Utc::now().to_rfc3339();
+    obj.insert("id".to_string(), Value::String(new_id.clone()));
+    obj.insert("created_at".to_string(), Value::String(now.clone()));
+    obj.insert("updated_at".to_string(), Value::String(now));
+    obj.insert("dependencies".to_string(), Value::Array(Vec::new()));
+    if let Some(parent) = &bead.parent {
+        set_parent_dependency(&mut obj, &new_id, parent);
+    }
+
+    objects.push(obj);
+    write_jsonl_atomic(path, &objects)?;
+    Ok(new_id)
+}
+
+pub(crate) fn native_add_dependency(path: &Path, issue_id: &str, depends_on_id: &str, dep_type: &str) -> Result<(), BertError> {
+    if dep_type == "parent-child" {
+        return patch_jsonl_bead(path, issue_id, |obj| set_parent_dependency(obj, issue_id, depends_on_id));
+    }
+
+    patch_jsonl_bead(path, issue_id, |obj| {
+        let mut deps: Vec<Value> = obj
+            .get("dependencies")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+        let exists = deps.iter().any(|d| {
+            d.get("depends_on_id").and_then(|v| v.as_str()) == Some(depends_on_id)
+                && d.get("type").and_then(|v| v.as_str()) == Some(dep_type)
+        });
+        if !exists {
+            deps.push(serde_json::json!({
+                "issue_id": issue_id,
+                "depends_on_id": depends_on_id,
+                "type": dep_type,
+                "created_at": chrono::Utc::now().to_rfc3339(),
+            }));
+        }
+        obj.insert("dependencies".to_string(), Value::Array(deps));
+    })
+}
+
+pub(crate) fn native_remove_dependency(path: &Path, issue_id: &str, depends_on_id: &str) -> Result<(), BertError> {
+    patch_jsonl_bead(path, issue_id, |obj| {
+        if let Some(Value::Array(deps)) = obj.get_mut("dependencies") {
+            deps.retain(|d| d.get("depends_on_id").and_then(|v| v.as_str()) != Some(depends_on_id));
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use tempfile::TempDir;
+
+    fn write_fixture(dir: &TempDir) -> PathBuf {
+        let beads_dir = dir.path().join(".beads");
+        fs::create_dir_all(&beads_dir).unwrap();
+        let path = beads_dir.join("issues.jsonl");
+        fs::write(
+            &path,
+            concat!(
+                r#"{"id":"bp6-abc","title":"Epic","status":"open","priority":1,"issue_type":"epic","custom_field":42}"#, "\n",
+                r#"{"id":"bp6-abc.1","title":"Task","status":"open","priority":2,"issue_type":"task","dependencies":[{"issue_id":"bp6-abc.1","depends_on_id":"bp6-abc","type":"parent-child"}]}"#, "\n",
+            ),
+        )
+        .unwrap();
+        path
+    }
+
+    fn find<'a>(objects: &'a [Map<String, Value>], id: &str) -> &'a Map<String, Value> {
+        objects.iter().find(|o| o["id"] == id).unwrap()
+    }
+
+    #[test]
+    fn test_parse_beads_skips_blank_lines_and_reports_line() {
+        let content = concat!(
+            r#"{"id":"bp6-1","title":"One","status":"open","priority":1,"issue_type":"task"}"#, "\n",
+            "\n",
+            r#"{"id":"bp6-2","title":"Two","status":"open","priority":2,"issue_type":"task"}"#, "\r\n",
+        );
+        let beads = super::parse_beads(content.as_bytes()).unwrap();
+        assert_eq!(beads.iter().map(|b| b.id.as_str()).collect::<Vec<_>>(), vec!["bp6-1", "bp6-2"]);
+
+        let err = super::parse_beads(format!("{}{{\"id\": ", content).as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("line 4"), "{}", err);
+    }
+
+    #[test]
+    fn test_read_beads_skips_broken_lines_and_records_them() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("issues.jsonl");
+        fs::write(
+            &path,
+            concat!(
+                r#"{"id":"bp6-1","title":"One","status":"open","priority":1,"issue_type":"task"}"#, "\n",
+                r#"{"id":"bp6-2","title":"Two","status":"open","priority":"#, "\n",
+                "not json at all\n",
+                r#"{"id":"bp6-3","title":"Three","status":"open","priority":2,"issue_type":"task"}"#, "\n",
+            ),
+        )
+        .unwrap();
+
+        let beads = read_beads(&path).unwrap();
+        assert_eq!(beads.iter().map(|b| b.id.as_str()).collect::<Vec<_>>(), vec!["bp6-1", "bp6-3"]);
+        let warnings = parse_warnings(&path);
+        assert_eq!(warnings.iter().map(|w| w.line).collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(warnings[1].excerpt, "not json at all");
+
+        // Fixing the file clears them
+        fs::write(&path, r#"{"id":"bp6-1","title":"One","status":"open","priority":1,"issue_type":"task"}"#).unwrap();
+        assert_eq!(read_beads(&path).unwrap().len(), 1);
+        assert!(parse_warnings(&path).is_empty());
+    }
+
+    #[test]
+    fn test_last_line_ignores_trailing_blank_lines() {
+        assert_eq!(last_line(b"a\nb\n\n  \n"), 2);
+        assert_eq!(last_line(b"a\nb"), 2);
+        assert_eq!(last_line(b"\n\n"), 0);
+    }
+
+    proptest! {
+        #[test]
+        fn parse_beads_lenient_keeps_every_valid_line(lines in prop::collection::vec(
+            prop_oneof![
+                (0..100u32).prop_map(|i| format!(r#"{{"id":"bp6-{}","title":"t","status":"open","priority":2,"issue_type":"task"}}"#, i)),
+                "[^\n]*",
+                prop::collection::vec(any::<u8>(), 0..40).prop_map(|b| String::from_utf8_lossy(&b).replace('\n', "")),
+            ],
+            0..20,
+        )) {
+            let (beads, warnings) = parse_beads_lenient(lines.join("\n").as_bytes());
+            let content = lines.iter().filter(|l| !l.bytes().all(|b| b.is_ascii_whitespace())).count();
+            let valid = lines.iter().filter(|l| serde_json::from_str::<Bead>(l).is_ok()).count();
+            prop_assert_eq!(beads.len(), valid);
+            prop_assert_eq!(beads.len() + warnings.len(), content);
+        }
+    }
+
+    #[test]
+    fn test_hydrate_due_date_from_bd_metadata() {
+        let mut bead: Bead = serde_json::from_str(
+            r#"{"id":"bp6-1","title":"Ship","status":"open","priority":1,"issue_type":"task","metadata":"{\"due_date\":\"2026-03-01\",\"is_milestone\":true,\"sort_rank\":3,\"is_favorite\":true}"}"#,
+        )
+        .unwrap();
+        hydrate_from_metadata(&mut bead);
+        assert_eq!(bead.due_date.as_deref(), Some("2026-03-01"));
+        assert_eq!(bead.is_milestone, Some(true));
+        assert_eq!(bead.is_favorite, Some(true));
+        assert_eq!(sort_rank(&bead), Some(3));
+    }
+
+    #[test]
+    fn test_native_close_and_reopen() {
+        let dir = TempDir::new().unwrap();
+        let path = write_fixture(&dir);
+
+        native_close_bead(&path, "bp6-abc.1", Some("done")).unwrap();
+        let objects = read_jsonl_objects(&path).unwrap();
+        let task = find(&objects, "bp6-abc.1");
+        assert_eq!(task["status"], "closed");
+        assert_eq!(task["close_reason"], "done");
+        assert!(task.contains_key("closed_at"));
+
+        native_reopen_bead(&path, "bp6-abc.1").unwrap();
+        let objects = read_jsonl_objects(&path).unwrap();
+        let task = find(&objects, "bp6-abc.1");
+        assert_eq!(task["status"], "open");
+        assert!(!task.contains_key("closed_at"));
+    }
+
+    #[test]
+    fn test_native_update_preserves_unknown_fields() {
+        let dir = TempDir::new().unwrap();
+        let path = write_fixture(&dir);
+
+        let objects = read_jsonl_objects(&path).unwrap();
+        let mut bead: Bead = serde_json::from_value(Value::Object(find(&objects, "bp6-abc").clone())).unwrap();
+        bead.title = "Renamed".to_string();
+        native_update_bead(&path, &bead).unwrap();
+
+        let objects = read_jsonl_objects(&path).unwrap();
+        let epic = find(&objects, "bp6-abc");
+        assert_eq!(epic["title"], "Renamed");
+        assert_eq!(epic["custom_field"], 42);
+        assert!(!epic.contains_key("owner"));
+    }
+
+    #[test]
+    fn test_native_update_clears_fields_and_stamps_closed_at() {
+        let dir = TempDir::new().unwrap();
+        let path = write_fixture(&dir);
+
+        let objects = read_jsonl_objects(&path).unwrap();
+        let mut bead: Bead = serde_json::from_value(Value::Object(find(&objects, "bp6-abc.1").clone())).unwrap();
+        bead.description = Some("Details".to_string());
+        bead.owner = Some("alice".to_string());
+        native_update_bead(&path, &bead).unwrap();
+
+        bead.description = None;
+        bead.owner = None;
+        bead.status = "closed".to_string();
+        native_update_bead(&path, &bead).unwrap();
+
+        let objects = read_jsonl_objects(&path).unwrap();
+        let task = find(&objects, "bp6-abc.1");
+        assert!(!task.contains_key("description"));
+        assert!(!task.contains_key("owner"));
+        assert!(task.contains_key("closed_at"));
+        // The parent link survives an edit that still carries it
+        assert_eq!(task["dependencies"][0]["depends_on_id"], "bp6-abc");
+
+        bead.status = "open".to_string();
+        bead.dependencies.clear();
+        native_update_bead(&path, &bead).unwrap();
+
+        let objects = read_jsonl_objects(&path).unwrap();
+        let task = find(&objects, "bp6-abc.1");
+        assert!(!task.contains_key("closed_at"));
+        assert_eq!(task["dependencies"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn test_native_create_child_ids() {
+        let dir = TempDir::new().unwrap();
+        let path = write_fixture(&dir);
+
+        let mut bead: Bead = serde_json::from_str(
+            r#"{"id":"","title":"New","status":"open","priority":2,"issue_type":"task"}"#,
+        )
+        .unwrap();
+        bead.parent = Some("bp6-abc".to_string());
+
+        let id = native_create_bead(&path, &bead).unwrap();
+        assert_eq!(id, "bp6-abc.2");
+
+        bead.parent = None;
+        let root_id = native_create_bead(&path, &bead).unwrap();
+        assert!(root_id.starts_with("bp6-"));
+        assert_eq!(read_jsonl_objects(&path).unwrap().len(), 4);
+    }
+
+    fn parse_beads(path: &Path) -> Vec<Bead> {
+        read_jsonl_objects(path)
+            .unwrap()
+            .into_iter()
+            .map(|o| serde_json::from_value(Value::Object(o)).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_dependency_rejects_cycles() {
+        let dir = TempDir::new().unwrap();
+        let path = write_fixture(&dir);
+        let mut beads = parse_beads(&path);
+
+        // Parent cannot become a child of its own descendant
+        assert!(validate_new_dependency(&beads, "bp6-abc", "bp6-abc.1", "parent-child").is_err());
+        assert!(validate_new_dependency(&beads, "bp6-abc", "bp6-abc", "blocks").is_err());
+        assert!(validate_new_dependency(&beads, "bp6-abc.1", "bp6-abc", "blocks").is_ok());
+
+        native_add_dependency(&path, "bp6-abc.1", "bp6-abc", "blocks").unwrap();
+        beads = parse_beads(&path);
+        assert!(validate_new_dependency(&beads, "bp6-abc", "bp6-abc.1", "blocks").is_err());
+    }
+
+    #[test]
+    fn test_native_remove_dependency() {
+        let dir = TempDir::new().unwrap();
+        let path = write_fixture(&dir);
+
+        native_add_dependency(&path, "bp6-abc.1", "bp6-abc", "blocks").unwrap();
+        native_remove_dependency(&path, "bp6-abc.1", "bp6-abc").unwrap();
+        let beads = parse_beads(&path);
+        let task = beads.iter().find(|b| b.id == "bp6-abc.1").unwrap();
+        assert!(task.dependencies.is_empty());
+    }
+
+    #[test]
+    fn test_bead_op_deserialization() {
+        let ops: Vec<BeadOp> = serde_json::from_str(
+            r#"[{"op":"close","beadId":"bp6-1","reason":"done"},{"op":"reopen","beadId":"bp6-2"},{"op":"claim","beadId":"bp6-3"}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(ops.len(), 3);
+        assert!(matches!(&ops[0], BeadOp::Close { bead_id, reason } if bead_id == "bp6-1" && reason.as_deref() == Some("done")));
+        assert!(matches!(&ops[1], BeadOp::Reopen { bead_id } if bead_id == "bp6-2"));
+        assert!(matches!(&ops[2], BeadOp::Claim { bead_id } if bead_id == "bp6-3"));
+    }
+
+    #[test]
+    fn test_native_patch_missing_bead() {
+        let dir = TempDir::new().unwrap();
+        let path = write_fixture(&dir);
+        assert!(native_set_status(&path, "bp6-nope", "closed").is_err());
+    }
+
+    #[test]
+    fn test_preview_batch_chains_predictions_without_writing() {
+        let dir = TempDir::new().unwrap();
+        let path = write_fixture(&dir);
+        let before = fs::read_to_string(&path).unwrap();
+        let ctx = ProjectContext::new(dir.path());
+
+        let ops = vec![
+            BeadOp::Claim { bead_id: "bp6-abc.1".to_string() },
+            BeadOp::Close { bead_id: "bp6-abc.1".to_string(), reason: Some("won't fix".to_string()) },
+            BeadOp::Reopen { bead_id: "missing".to_string() },
+        ];
+        let mut predicted = HashMap::new();
+        let claimed = preview_op(&ctx, &ops[0], &mut predicted, true).unwrap();
+        assert_eq!(claimed.commands, vec!["bd update bp6-abc.1 --status in_progress"]);
+        assert_eq!(claimed.bead.unwrap().status, "in_progress");
+
+        let closed = preview_op(&ctx, &ops[1], &mut predicted, false).unwrap();
+        assert!(closed.native && closed.commands.is_empty());
+        let bead = closed.bead.unwrap();
+        assert_eq!((bead.status.as_str(), bead.close_reason.as_deref()), ("closed", Some("won't fix")));
+        assert!(matches!(preview_op(&ctx, &ops[2], &mut predicted, true), Err(BertError::NotFound(_))));
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), before);
+    }
+
+    #[test]
+    fn test_command_line_quotes_arguments() {
+        let args = close_args("bp6-1", Some("won't fix"));
+        assert_eq!(command_line(&args), "bd close bp6-1 --reason 'won'\\''t fix'");
+        assert_eq!(command_line(&claim_args("bp6-1")), "bd update bp6-1 --status in_progress");
+    }
+
+}
@@ -0,0 +1,203 @@
+//! In-memory index of the beads in each issues.jsonl
+//!
+//! Single-bead lookups used to reparse the whole file. `BeadIndex` keeps the
+//! beads of a file by ID together with their parent-child links, and is shared
+//! between callers until the file changes. The beads watcher refreshes it
+//! from the bytes it has already read; lookups also compare the file's size
+//! and modification time, so a write the watcher hasn't seen yet still causes
+//! a reload instead of a stale answer.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use crate::error::BertError;
+use crate::project::{context_for, ProjectContext};
+use crate::Bead;
+
+static INDEXES: Mutex<BTreeMap<PathBuf, Arc<BeadIndex>>> = Mutex::new(BTreeMap::new());
+
+/// Size and modification time of an indexed file
+pub(crate) type FileStamp = (u64, Option<SystemTime>);
+
+pub(crate) fn stamp(path: &Path) -> Option<FileStamp> {
+    std::fs::metadata(path).ok().map(|m| (m.len(), m.modified().ok()))
+}
+
+#[derive(Debug, Default)]
+pub struct BeadIndex {
+    beads: HashMap<String, Bead>,
+    /// Parent ID -> child IDs, in file order
+    children: HashMap<String, Vec<String>>,
+    /// Child ID -> parent ID
+    parents: HashMap<String, String>,
+    stamp: Option<FileStamp>,
+}
+
+impl BeadIndex {
+    pub fn new(beads: Vec<Bead>) -> Self {
+        let mut index = BeadIndex::default();
+        for bead in beads {
+            // The first parent-child dependency is the parent, as in the WBS tree
+            if let Some(dep) = bead.dependencies.iter().find(|d| d.r#type == "parent-child") {
+                index.children.entry(dep.depends_on_id.clone()).or_default().push(bead.id.clone());
+                index.parents.insert(bead.id.clone(), dep.depends_on_id.clone());
+            }
+            index.beads.insert(bead.id.clone(), bead);
+        }
+        index
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Bead> {
+        self.beads.get(id)
+    }
+
+    fn require(&self, id: &str) -> Result<&Bead, BertError> {
+        self.get(id)
+            .ok_or_else(|| BertError::NotFound(format!("Bead with ID {} not found", id)))
+    }
+
+    /// Direct children of `id`, in file order
+    pub fn children(&self, id: &str) -> Vec<&Bead> {
+        self.children
+            .get(id)
+            .map(|ids| ids.iter().filter_map(|child| self.get(child)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Ancestors of `id`, parent first and root last
+    pub fn ancestors(&self, id: &str) -> Vec<&Bead> {
+        let mut seen = HashSet::from([id]);
+        let mut ancestors = Vec::new();
+        let mut current = id;
+        while let Some(parent) = self.parents.get(current) {
+            // Guard against parent-child cycles
+            if !seen.insert(parent.as_str()) {
+                break;
+            }
+            let Some(bead) = self.get(parent) else { break };
+            ancestors.push(bead);
+            current = parent;
+        }
+        ancestors
+    }
+}
+
+/// Index of the beads in `beads_path`, reloaded when the file changed since
+/// it was built
+pub fn index_for(beads_path: &Path) -> Result<Arc<BeadIndex>, BertError> {
+    let current = stamp(beads_path);
+    if let Some(index) = INDEXES.lock().unwrap().get(beads_path) {
+        if index.stamp.is_some() && index.stamp == current {
+            return Ok(index.clone());
+        }
+    }
+
+    let snapshot = crate::snapshot::read_snapshot(beads_path)?;
+    let mut index = BeadIndex::new(snapshot.beads.clone());
+    // A snapshot served in place of a torn read doesn't match the file
+    index.stamp = current.filter(|_| snapshot.stamp() == current);
+    Ok(store(beads_path, index))
+}
+
+/// Replace the index of `beads_path` with `bytes`, the contents the watcher
+/// just read
+pub fn refresh(beads_path: &Path, bytes: &[u8]) {
+    // Broken lines are skipped, as in any other read of the file
+    let (beads, warnings) = crate::bd::parse_beads_lenient(bytes);
+    crate::bd::record_parse_warnings(beads_path, warnings);
+    // Only trust the stamp if the file still holds what was read
+    let current = stamp(beads_path).filter(|(len, _)| *len == bytes.len() as u64);
+    crate::snapshot::record(beads_path, beads.clone(), current);
+    let mut index = BeadIndex::new(beads);
+    index.stamp = current;
+    store(beads_path, index);
+}
+
+/// Drop the index of a removed beads file
+pub fn forget(beads_path: &Path) {
+    INDEXES.lock().unwrap().remove(beads_path);
+}
+
+fn store(beads_path: &Path, index: BeadIndex) -> Arc<BeadIndex> {
+    let index = Arc::new(index);
+    INDEXES.lock().unwrap().insert(beads_path.to_path_buf(), index.clone());
+    index
+}
+
+/// Look up a bead of the project without re-reading its file when unchanged
+pub fn get_bead_internal(ctx: &ProjectContext, id: &str) -> Result<Bead, BertError> {
+    index_for(&ctx.beads_path()?)?.require(id).cloned()
+}
+
+#[tauri::command]
+pub fn get_bead(id: String, window: tauri::Window, project_handle: Option<String>) -> Result<Bead, BertError> {
+    get_bead_internal(&context_for(&window, project_handle.as_deref())?, &id)
+}
+
+/// Direct children of a bead, in file order
+#[tauri::command]
+pub fn get_children(id: String, window: tauri::Window, project_handle: Option<String>) -> Result<Vec<Bead>, BertError> {
+    let index = index_for(&context_for(&window, project_handle.as_deref())?.beads_path()?)?;
+    index.require(&id)?;
+    Ok(index.children(&id).into_iter().cloned().collect())
+}
+
+/// Ancestors of a bead, parent first and root last
+#[tauri::command]
+pub fn get_ancestors(id: String, window: tauri::Window, project_handle: Option<String>) -> Result<Vec<Bead>, BertError> {
+    let index = index_for(&context_for(&window, project_handle.as_deref())?.beads_path()?)?;
+    index.require(&id)?;
+    Ok(index.ancestors(&id).into_iter().cloned().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const BEADS: &str = concat!(
+        r#"{"id":"e","title":"Epic","status":"open","priority":1,"issue_type":"epic"}"#, "\n",
+        r#"{"id":"f","title":"Feature","status":"open","priority":2,"issue_type":"feature","dependencies":[{"issue_id":"f","depends_on_id":"e","type":"parent-child"}]}"#, "\n",
+        r#"{"id":"t2","title":"Second","status":"open","priority":2,"issue_type":"task","dependencies":[{"issue_id":"t2","depends_on_id":"f","type":"parent-child"}]}"#, "\n",
+        r#"{"id":"t1","title":"First","status":"open","priority":2,"issue_type":"task","dependencies":[{"issue_id":"t1","depends_on_id":"f","type":"parent-child"},{"issue_id":"t1","depends_on_id":"t2","type":"blocks"}]}"#, "\n",
+    );
+
+    fn ids(beads: Vec<&Bead>) -> Vec<&str> {
+        beads.into_iter().map(|b| b.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_children_and_ancestors() {
+        let index = BeadIndex::new(crate::bd::parse_beads(BEADS.as_bytes()).unwrap());
+        assert_eq!(index.get("t1").unwrap().title, "First");
+        assert_eq!(ids(index.children("f")), vec!["t2", "t1"]);
+        assert!(index.children("t1").is_empty());
+        assert_eq!(ids(index.ancestors("t1")), vec!["f", "e"]);
+        assert!(index.ancestors("e").is_empty());
+        assert!(index.require("missing").is_err());
+    }
+
+    #[test]
+    fn test_index_reloads_when_file_changes() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("issues.jsonl");
+        std::fs::write(&path, BEADS).unwrap();
+
+        let first = index_for(&path).unwrap();
+        assert!(Arc::ptr_eq(&first, &index_for(&path).unwrap()));
+
+        let updated = BEADS.replace("\"First\"", "\"First, renamed\"");
+        std::fs::write(&path, &updated).unwrap();
+        assert_eq!(index_for(&path).unwrap().get("t1").unwrap().title, "First, renamed");
+
+        // The watcher hands over what it read, which is kept until the next write
+        let renamed = updated.replace("Epic", "Renamed epic");
+        std::fs::write(&path, &renamed).unwrap();
+        refresh(&path, renamed.as_bytes());
+        let refreshed = index_for(&path).unwrap();
+        assert_eq!(refreshed.get("e").unwrap().title, "Renamed epic");
+        assert!(Arc::ptr_eq(&refreshed, &index_for(&path).unwrap()));
+    }
+}
@@ -0,0 +1,102 @@
+//! Load-time benchmarks for large projects
+//!
+//! Generates an issues.jsonl shaped like a real project (epics of tasks with
+//! blocking chains, a third of them closed) and times loading it and building
+//! the view model. The benchmarks are ignored by default; run them with
+//!
+//! ```sh
+//! cargo test --release bench_ -- --ignored --nocapture
+//! ```
+//!
+//! Each run prints the time spent loading and building the view model, and
+//! flags the 50k-bead runs that miss `BUDGET` end to end.
+
+use std::io::Write;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::schedule::SchedulingConfig;
+use crate::workflow::WorkflowConfig;
+use crate::FilterParams;
+
+const BUDGET: Duration = Duration::from_millis(100);
+
+/// Tasks per epic
+const EPIC_SIZE: usize = 50;
+
+/// Write `count` beads to `path`
+fn write_project(path: &Path, count: usize) {
+    let mut file = std::io::BufWriter::new(std::fs::File::create(path).unwrap());
+    for i in 0..count {
+        let id = format!("bench-{}", i);
+        let epic = i - i % EPIC_SIZE;
+        let mut dependencies = Vec::new();
+        if i != epic {
+            dependencies.push(serde_json::json!({ "issue_id": id, "depends_on_id": format!("bench-{}", epic), "type": "parent-child" }));
+            if i - 1 != epic {
+                dependencies.push(serde_json::json!({ "issue_id": id, "depends_on_id": format!("bench-{}", i - 1), "type": "blocks" }));
+            }
+        }
+
+        let bead = serde_json::json!({
+            "id": id,
+            "title": format!("Generated bead number {}", i),
+            "description": "A description long enough to resemble what people actually write in beads.",
+            "status": if i % 3 == 0 { "closed" } else { "open" },
+            "priority": i % 4,
+            "issue_type": if i == epic { "epic" } else { "task" },
+            "owner": format!("dev{}", i % 7),
+            "labels": ["backend", "bench"],
+            "created_at": "2026-01-01T00:00:00Z",
+            "dependencies": dependencies,
+        });
+        writeln!(file, "{}", bead).unwrap();
+    }
+}
+
+/// Time loading `count` beads and building their view model, returning the total
+fn run(count: usize, params: &FilterParams) -> Duration {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("issues.jsonl");
+    write_project(&path, count);
+
+    let start = Instant::now();
+    let beads = crate::bd::read_beads(&path).unwrap();
+    let loaded = start.elapsed();
+    let view_model = crate::bert_core::build_project_view_model(beads, params, &SchedulingConfig::default(), &WorkflowConfig::default());
+    let total = start.elapsed();
+
+    println!(
+        "{} beads: load {:.1}ms, view model {:.1}ms, total {:.1}ms ({} root nodes)",
+        count,
+        loaded.as_secs_f64() * 1000.0,
+        (total - loaded).as_secs_f64() * 1000.0,
+        total.as_secs_f64() * 1000.0,
+        view_model.tree.len()
+    );
+    if count >= 50_000 && total > BUDGET {
+        println!("⚠️  over the {:?} budget", BUDGET);
+    }
+    total
+}
+
+#[test]
+#[ignore]
+fn bench_load_50k_beads() {
+    for count in [1_000, 10_000, 50_000] {
+        run(count, &FilterParams::default());
+    }
+
+    run(50_000, &FilterParams { hide_closed: true, ..FilterParams::default() });
+}
+
+#[test]
+#[ignore]
+fn bench_search_50k_beads() {
+    let params = FilterParams {
+        filter_text: "number 4".to_string(),
+        include_hierarchy: true,
+        ..FilterParams::default()
+    };
+    run(50_000, &params);
+}
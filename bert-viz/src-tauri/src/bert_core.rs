@@ -0,0 +1,2754 @@
+//! Project processing core
+//!
+//! Everything between loading beads and handing the UI its data: filtering,
+//! the WBS tree and its dependency ordering, Gantt scheduling and the
+//! critical path, state distributions and the unified view model. Nothing
+//! here touches Tauri (no windows, `AppHandle` or events), so the commands in
+//! lib.rs, the `bert-viz-cli` binary and tests all run the same functions on
+//! beads they loaded themselves.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::bd::ParseWarning;
+use crate::perf::Timings;
+use crate::workflow::{self, StatusCategory, WorkflowConfig};
+use crate::{bd, layout, references, schedule, search, Bead, Dependency};
+
+// ============================================================================
+// Processed Data (bp6-07y.5.2)
+// ============================================================================
+
+/// Filter, sort and lay out beads: the data behind `get_processed_data`
+pub fn process_beads(
+    beads: Vec<Bead>,
+    params: &FilterParams,
+    layout_kind: layout::LayoutKind,
+    workflow: &WorkflowConfig,
+) -> ProcessedData {
+    let mut timings = Timings { bead_count: beads.len(), ..Timings::default() };
+
+    // 2. Apply filters (status, time, text search and hierarchy)
+    let filter_start = std::time::Instant::now();
+    let filtered = select_beads(beads, params, workflow);
+    timings.visible_count = filtered.len();
+    timings.record("filter", filter_start);
+
+    let tree_start = std::time::Instant::now();
+
+    // 3. Build dependency graph
+    let graph = build_dependency_graph(&filtered);
+
+    // 4. Build WBS tree
+    let mut tree = build_wbs_tree(&filtered);
+
+    // 5. Sort siblings (by dependencies or explicit sort)
+    tree = sort_wbs_tree_siblings(tree, &graph, &params.sort_by, &params.sort_order);
+
+    timings.record("tree", tree_start);
+    let layout_start = std::time::Instant::now();
+
+    // Apply collapsed state to tree
+    fn apply_collapsed_state(nodes: &mut [WBSNode], collapsed_ids: &[String]) {
+        for node in nodes {
+            if collapsed_ids.contains(&node.bead.id) {
+                node.is_expanded = false;
+            }
+            if !node.children.is_empty() {
+                apply_collapsed_state(&mut node.children, collapsed_ids);
+            }
+        }
+    }
+    apply_collapsed_state(&mut tree, &params.collapsed_ids);
+
+    // 6. Build blocks and successors maps for Gantt layout
+    let mut blocks_map: HashMap<String, Vec<String>> = HashMap::new();
+    let mut successors_map: HashMap<String, Vec<String>> = HashMap::new();
+
+    for bead in &filtered {
+        for dep in &bead.dependencies {
+            if dep.r#type == "blocks" {
+                // dep.depends_on_id blocks bead.id
+                blocks_map
+                    .entry(bead.id.clone())
+                    .or_insert_with(Vec::new)
+                    .push(dep.depends_on_id.clone());
+
+                successors_map
+                    .entry(dep.depends_on_id.clone())
+                    .or_insert_with(Vec::new)
+                    .push(bead.id.clone());
+            }
+        }
+    }
+
+    // 7. Calculate earliest start times (X positions)
+    let x_map = calculate_earliest_start_times(&filtered, &blocks_map, params.level_by_owner);
+
+    // 8. Calculate node ranges (position and width)
+    let mut range_cache: HashMap<String, NodeRange> = HashMap::new();
+    calculate_node_ranges(&tree, &x_map, &mut range_cache);
+
+    // 9. Find critical path
+    let critical_path = find_critical_path(&filtered, &successors_map);
+
+    // 10. Mark critical nodes in tree
+    fn mark_critical_nodes(nodes: &mut [WBSNode], critical_path: &HashSet<String>) {
+        for node in nodes {
+            if critical_path.contains(&node.bead.id) {
+                node.is_critical = true;
+            }
+            mark_critical_nodes(&mut node.children, critical_path);
+        }
+    }
+    mark_critical_nodes(&mut tree, &critical_path);
+
+    // 11. Lay out items and connectors with the requested engine
+    let mut layout = layout::engine_for(layout_kind).layout(&layout::LayoutInput {
+        beads: &filtered,
+        tree: &tree,
+        x_map: &x_map,
+        range_cache: &range_cache,
+        critical_path: &critical_path,
+        zoom: params.zoom,
+        aggregate: params.aggregate_below_zoom.is_some_and(|threshold| params.zoom < threshold),
+    });
+    if params.level_by_owner && layout_kind == layout::LayoutKind::Gantt {
+        layout.owner_lanes = build_owner_lanes(&filtered, &x_map);
+    }
+
+    timings.record("layout", layout_start);
+    let distribution_start = std::time::Instant::now();
+
+    // 12. Calculate state distributions from tree
+    // Convert tree to temporary BeadNode tree for distribution calculation
+    fn wbs_to_temp_bead_nodes(
+        nodes: &[WBSNode],
+        x_map: &HashMap<String, usize>,
+        range_cache: &HashMap<String, NodeRange>,
+    ) -> Vec<BeadNode> {
+        nodes
+            .iter()
+            .map(|node| {
+                let node_range = range_cache.get(&node.bead.id);
+                let (cell_offset, cell_count) = if let Some(range) = node_range {
+                    let offset = (range.x / 10.0).round() as usize;
+                    let count = (range.width / 10.0).ceil().max(1.0) as usize;
+                    (offset, count)
+                } else {
+                    let offset = x_map.get(&node.bead.id).copied().unwrap_or(0);
+                    (offset, 1)
+                };
+
+                BeadNode {
+                    id: node.bead.id.clone(),
+                    title: node.bead.title.clone(),
+                    description: node.bead.description.clone(),
+                    status: node.bead.status.clone(),
+                    priority: node.bead.priority,
+                    issue_type: node.bead.issue_type.clone(),
+                    estimate: node.bead.estimate,
+                    dependencies: node.bead.dependencies.clone(),
+                    owner: node.bead.owner.clone(),
+                    created_at: node.bead.created_at.clone(),
+                    created_by: node.bead.created_by.clone(),
+                    updated_at: node.bead.updated_at.clone(),
+                    labels: node.bead.labels.clone(),
+                    acceptance_criteria: node.bead.acceptance_criteria.clone(),
+                    acceptance_progress: crate::acceptance::progress(&node.bead),
+                    external_dependencies: crate::external::pending(&node.bead),
+                    is_externally_blocked: false,
+                    is_pinned: false,
+                    closed_at: node.bead.closed_at.clone(),
+                    close_reason: node.bead.close_reason.clone(),
+                    is_favorite: node.bead.is_favorite,
+                    parent: node.bead.parent.clone(),
+                    external_reference: node.bead.external_reference.clone(),
+                    due_date: node.bead.due_date.clone(),
+                    is_milestone: node.bead.is_milestone.unwrap_or(false),
+                    design: node.bead.design.clone(),
+                    notes: node.bead.notes.clone(),
+                    children: wbs_to_temp_bead_nodes(&node.children, x_map, range_cache),
+                    is_blocked: node.is_blocked,
+                    is_critical: node.is_critical,
+                    blocking_ids: vec![],
+                    references: vec![],
+                    is_overdue: false,
+                    total_estimate: 0,
+                    completed_estimate: 0,
+                    progress_percent: 0.0,
+                    actual_minutes: 0,
+                    depth: 0,
+                    cell_offset,
+                    cell_count,
+                    start_date: None,
+                    end_date: None,
+                    is_expanded: node.is_expanded,
+                    is_visible: true,
+                    extra_metadata: node.bead.extra_metadata.clone(),
+                }
+            })
+            .collect()
+    }
+
+    let temp_tree = wbs_to_temp_bead_nodes(&tree, &x_map, &range_cache);
+    let distributions = calculate_state_distribution_from_tree(&temp_tree, workflow);
+    timings.record("distributions", distribution_start);
+
+    ProcessedData {
+        tree,
+        layout,
+        distributions,
+        timings,
+        parse_warnings: Vec::new(),
+    }
+}
+
+// ============================================================================
+// Unified View Layer (bp6-75y.2)
+// ============================================================================
+
+/// Convert a Bead to a BeadNode with computed properties.
+/// Field names are now unified (design, notes) in both Bead and BeadNode.
+pub(crate) fn bead_to_bead_node(
+    bead: &Bead,
+    children: Vec<BeadNode>,
+    depth: usize,
+    cell_offset: usize,
+    cell_count: usize,
+    is_blocked: bool,
+    is_critical: bool,
+    blocking_ids: Vec<String>,
+    is_expanded: bool,
+    is_visible: bool,
+) -> BeadNode {
+    BeadNode {
+        // Core Bead Data
+        id: bead.id.clone(),
+        title: bead.title.clone(),
+        description: bead.description.clone(),
+        status: bead.status.clone(),
+        priority: bead.priority,
+        issue_type: bead.issue_type.clone(),
+        estimate: bead.estimate,
+        dependencies: bead.dependencies.clone(),
+
+        // Metadata
+        owner: bead.owner.clone(),
+        created_at: bead.created_at.clone(),
+        created_by: bead.created_by.clone(),
+        updated_at: bead.updated_at.clone(),
+        labels: bead.labels.clone(),
+        acceptance_criteria: bead.acceptance_criteria.clone(),
+        acceptance_progress: crate::acceptance::progress(bead),
+        external_dependencies: crate::external::pending(bead),
+        // Set by external::resolve_tree once the other projects are read
+        is_externally_blocked: false,
+        // Set by watchlist::apply_pins
+        is_pinned: false,
+        closed_at: bead.closed_at.clone(),
+        close_reason: bead.close_reason.clone(),
+        is_favorite: bead.is_favorite,
+        parent: bead.parent.clone(),
+        external_reference: bead.external_reference.clone(),
+        due_date: bead.due_date.clone(),
+        is_milestone: bead.is_milestone.unwrap_or(false),
+
+        // Unified Field Naming
+        design: bead.design.clone(),
+        notes: bead.notes.clone(),
+
+        // Hierarchical Structure
+        children,
+
+        // Computed Properties
+        is_blocked,
+        is_critical,
+        blocking_ids,
+        // Set by convert_wbs_to_bead_nodes, which has the reference scanner
+        references: Vec::new(),
+        // Set by convert_wbs_to_bead_nodes, which knows today's date
+        is_overdue: false,
+        // Set by roll_up_progress once the whole tree is built
+        total_estimate: 0,
+        completed_estimate: 0,
+        progress_percent: 0.0,
+        // Set by timetracking::apply_actuals from the recorded time
+        actual_minutes: 0,
+
+        // Logical Positioning
+        depth,
+        cell_offset,
+        cell_count,
+
+        // Calendar dates are filled in by schedule::annotate_dates
+        start_date: None,
+        end_date: None,
+
+        // UI State
+        is_expanded,
+        is_visible,
+
+        // Extra metadata
+        extra_metadata: bead.extra_metadata.clone(),
+    }
+}
+
+/// Convert WBSNode tree to BeadNode tree with computed properties.
+pub(crate) fn convert_wbs_to_bead_nodes(
+    nodes: &[WBSNode],
+    depth: usize,
+    x_map: &HashMap<String, usize>,
+    range_cache: &HashMap<String, NodeRange>,
+    critical_path: &HashSet<String>,
+    collapsed_ids: &[String],
+    scanner: &references::ReferenceScanner,
+) -> Vec<BeadNode> {
+    let today = chrono::Local::now().date_naive();
+
+    nodes.iter().map(|node| {
+        // Get cell positioning
+        // x_map contains cell offsets (0, 1, 2, 3...) - these ARE the cell positions
+        // range cache contains duration in time units (10, 20, 30...)
+        let cell_offset = x_map.get(&node.bead.id).copied().unwrap_or(0);
+
+        let node_range = range_cache.get(&node.bead.id);
+        let cell_count = if node.bead.is_milestone == Some(true) {
+            // Milestones are zero-width markers
+            0
+        } else if let Some(range) = node_range {
+            // Convert time units to cell count (10 time units = 1 cell)
+            (range.width / 10.0).ceil().max(1.0) as usize
+        } else {
+            // Fallback: 1 cell
+            1
+        };
+
+        // Compute properties
+        let is_blocked = node.is_blocked;
+        let is_critical = critical_path.contains(&node.bead.id);
+        let blocking_ids: Vec<String> = node.bead.dependencies
+            .iter()
+            .filter(|dep| dep.r#type == "blocks")
+            .map(|dep| dep.depends_on_id.clone())
+            .collect();
+
+        // UI state
+        let is_expanded = !collapsed_ids.contains(&node.bead.id);
+        let is_visible = true; // Will be computed during tree traversal
+
+        // Recursively convert children
+        let children = if !node.children.is_empty() {
+            convert_wbs_to_bead_nodes(
+                &node.children,
+                depth + 1,
+                x_map,
+                range_cache,
+                critical_path,
+                collapsed_ids,
+                scanner,
+            )
+        } else {
+            Vec::new()
+        };
+
+        let mut bead_node = bead_to_bead_node(
+            &node.bead,
+            children,
+            depth,
+            cell_offset,
+            cell_count,
+            is_blocked,
+            is_critical,
+            blocking_ids,
+            is_expanded,
+            is_visible,
+        );
+        bead_node.is_overdue = is_bead_overdue(&node.bead, today);
+        bead_node.references = scanner.references(&node.bead);
+        bead_node
+    }).collect()
+}
+
+/// Roll estimates and progress up from the leaf tasks to their parents.
+/// Returns the total and completed estimate of `nodes` with the number of
+/// leaf tasks below them and how many of those are done.
+pub(crate) fn roll_up_progress(nodes: &mut [BeadNode], workflow: &workflow::WorkflowConfig) -> (u64, u64, usize, usize) {
+    let mut sums = (0, 0, 0, 0);
+    for node in nodes {
+        let (total, completed, leaves, done_leaves) = if node.children.is_empty() {
+            let total = node.estimate.unwrap_or(0) as u64;
+            let done = workflow.is_done(&node.status);
+            (total, if done { total } else { 0 }, 1, done as usize)
+        } else {
+            roll_up_progress(&mut node.children, workflow)
+        };
+
+        node.total_estimate = total;
+        node.completed_estimate = completed;
+        node.progress_percent = if total > 0 {
+            completed as f64 * 100.0 / total as f64
+        } else {
+            done_leaves as f64 * 100.0 / leaves as f64
+        };
+
+        sums.0 += total;
+        sums.1 += completed;
+        sums.2 += leaves;
+        sums.3 += done_leaves;
+    }
+    sums
+}
+
+/// Parse a due date given as YYYY-MM-DD or a full RFC 3339 timestamp
+pub(crate) fn parse_due_date(due: &str) -> Option<chrono::NaiveDate> {
+    chrono::NaiveDate::parse_from_str(due.trim(), "%Y-%m-%d")
+        .ok()
+        .or_else(|| chrono::DateTime::parse_from_rfc3339(due.trim()).ok().map(|dt| dt.date_naive()))
+}
+
+/// A bead is overdue when its due date has passed and it is not closed
+pub(crate) fn is_bead_overdue(bead: &Bead, today: chrono::NaiveDate) -> bool {
+    if bead.status == "closed" || bead.status == "done" {
+        return false;
+    }
+    bead.due_date
+        .as_deref()
+        .and_then(parse_due_date)
+        .map(|due| due < today)
+        .unwrap_or(false)
+}
+
+/// Build ViewIndexes for fast lookups.
+pub(crate) fn build_view_indexes(tree: &[BeadNode], critical_path: &HashSet<String>) -> ViewIndexes {
+    let mut id_to_index = HashMap::new();
+    let mut id_to_parent = HashMap::new();
+    let mut index = 0;
+
+    fn traverse(
+        nodes: &[BeadNode],
+        parent_id: Option<&str>,
+        id_to_index: &mut HashMap<String, usize>,
+        id_to_parent: &mut HashMap<String, String>,
+        index: &mut usize,
+    ) {
+        for node in nodes {
+            id_to_index.insert(node.id.clone(), *index);
+            *index += 1;
+
+            if let Some(parent) = parent_id {
+                id_to_parent.insert(node.id.clone(), parent.to_string());
+            }
+
+            if !node.children.is_empty() {
+                traverse(&node.children, Some(&node.id), id_to_index, id_to_parent, index);
+            }
+        }
+    }
+
+    traverse(tree, None, &mut id_to_index, &mut id_to_parent, &mut index);
+
+    // Convert critical path HashSet to Vec
+    let critical_path_vec: Vec<String> = critical_path.iter().cloned().collect();
+
+    ViewIndexes {
+        id_to_index,
+        id_to_parent,
+        critical_path: critical_path_vec,
+    }
+}
+
+/// Calculate project metadata (aggregate statistics).
+/// Statuses are counted by their workflow category.
+pub(crate) fn calculate_project_metadata(
+    _tree: &[BeadNode],
+    filtered_beads: &[Bead],
+    distributions: Vec<BucketDistribution>,
+    _critical_path: &HashSet<String>,
+    x_map: &HashMap<String, usize>,
+    workflow: &workflow::WorkflowConfig,
+) -> ProjectMetadata {
+    let mut open_count = 0;
+    let mut in_progress_count = 0;
+    let mut blocked_count = 0;
+    let mut closed_count = 0;
+
+    let open_ids: HashSet<&str> = filtered_beads
+        .iter()
+        .filter(|b| !workflow.is_done(&b.status))
+        .map(|b| b.id.as_str())
+        .collect();
+
+    for bead in filtered_beads {
+        match workflow.category_of(&bead.status) {
+            StatusCategory::Todo => open_count += 1,
+            StatusCategory::Doing => in_progress_count += 1,
+            StatusCategory::Done => closed_count += 1,
+            StatusCategory::Blocked => {
+                // Explicitly blocked; no need to look at dependencies
+                blocked_count += 1;
+                continue;
+            }
+        }
+
+        if !workflow.is_done(&bead.status) {
+            // Check if blocked
+            // Blocked by a visible bead that isn't done
+            let is_blocked = bead
+                .dependencies
+                .iter()
+                .any(|dep| dep.r#type == "blocks" && open_ids.contains(dep.depends_on_id.as_str()));
+
+            if is_blocked {
+                blocked_count += 1;
+            }
+        }
+    }
+
+    // Calculate total duration (critical path length)
+    let total_duration = x_map.values().copied().map(|v| v as f64).fold(0.0f64, f64::max);
+
+    ProjectMetadata {
+        total_beads: filtered_beads.len(),
+        open_count,
+        in_progress_count,
+        blocked_count,
+        closed_count,
+        total_duration,
+        distributions,
+        dated_distributions: Vec::new(),
+        axis: None,
+    }
+}
+
+/// Build the full ProjectViewModel from loaded beads.
+/// This does all CPU-intensive computation: filtering, sorting, dependency
+/// graph building, critical path calculation, and tree construction.
+pub fn build_project_view_model(
+    beads: Vec<Bead>,
+    params: &FilterParams,
+    scheduling: &schedule::SchedulingConfig,
+    workflow: &workflow::WorkflowConfig,
+) -> ProjectViewModel {
+    // References to beads outside the filter are still links
+    let scanner = references::ReferenceScanner::new(&beads);
+
+    let mut timings = Timings { bead_count: beads.len(), ..Timings::default() };
+
+    // 2. Apply filters
+    let filter_start = std::time::Instant::now();
+    let filtered = select_beads(beads, params, workflow);
+    timings.visible_count = filtered.len();
+    timings.record("filter", filter_start);
+
+    let tree_start = std::time::Instant::now();
+
+    // 3. Build dependency graph
+    let graph = build_dependency_graph(&filtered);
+
+    let mut tree = build_wbs_tree(&filtered);
+
+    // 5. Sort siblings (by dependencies or explicit sort)
+    tree = sort_wbs_tree_siblings(tree, &graph, &params.sort_by, &params.sort_order);
+
+    // Apply collapsed state
+    fn apply_collapsed_state(nodes: &mut [WBSNode], collapsed_ids: &[String]) {
+        for node in nodes {
+            if collapsed_ids.contains(&node.bead.id) {
+                node.is_expanded = false;
+            }
+            if !node.children.is_empty() {
+                apply_collapsed_state(&mut node.children, collapsed_ids);
+            }
+        }
+    }
+    apply_collapsed_state(&mut tree, &params.collapsed_ids);
+
+    timings.record("tree", tree_start);
+    let compute_start = std::time::Instant::now();
+
+    // 6. Build blocks and successors maps
+    let mut blocks_map: HashMap<String, Vec<String>> = HashMap::new();
+    let mut successors_map: HashMap<String, Vec<String>> = HashMap::new();
+
+    for bead in &filtered {
+        for dep in &bead.dependencies {
+            if dep.r#type == "blocks" {
+                blocks_map
+                    .entry(bead.id.clone())
+                    .or_insert_with(Vec::new)
+                    .push(dep.depends_on_id.clone());
+
+                successors_map
+                    .entry(dep.depends_on_id.clone())
+                    .or_insert_with(Vec::new)
+                    .push(bead.id.clone());
+            }
+        }
+    }
+
+    // 7. Calculate earliest start times (logical units, not pixels)
+    let x_map = calculate_earliest_start_times(&filtered, &blocks_map, params.level_by_owner);
+    eprintln!("⏱️  x_map has {} entries", x_map.len());
+    if !x_map.is_empty() {
+        let first_entry = x_map.iter().next().unwrap();
+        eprintln!("⏱️  First x_map entry: {} -> {}", first_entry.0, first_entry.1);
+    }
+
+    // 8. Calculate node ranges
+    let mut range_cache: HashMap<String, NodeRange> = HashMap::new();
+    calculate_node_ranges(&tree, &x_map, &mut range_cache);
+    eprintln!("⏱️  range_cache has {} entries", range_cache.len());
+
+    // 9. Find critical path
+    let critical_path = find_critical_path(&filtered, &successors_map);
+
+    // 10. Mark critical nodes in tree
+    fn mark_critical_nodes(nodes: &mut [WBSNode], critical_path: &HashSet<String>) {
+        for node in nodes {
+            if critical_path.contains(&node.bead.id) {
+                node.is_critical = true;
+            }
+            mark_critical_nodes(&mut node.children, critical_path);
+        }
+    }
+    mark_critical_nodes(&mut tree, &critical_path);
+
+    // 11. Convert WBS tree to BeadNode tree, rolling estimates up to parents
+    let mut bead_node_tree = convert_wbs_to_bead_nodes(
+        &tree,
+        0, // root depth
+        &x_map,
+        &range_cache,
+        &critical_path,
+        &params.collapsed_ids,
+        &scanner,
+    );
+    roll_up_progress(&mut bead_node_tree, workflow);
+
+    // 11b. Map cells to calendar dates
+    let calendar = schedule::CalendarMapper::new(scheduling, &filtered);
+    let total_cells = schedule::annotate_dates(&mut bead_node_tree, &calendar);
+
+    // 12. Generate Gantt layout for distributions (reuse existing logic)
+    // 12. Calculate state distributions from tree (before building layout)
+    let distributions = calculate_state_distribution_from_tree(&bead_node_tree, workflow);
+
+    // 13. Build indexes
+    let indexes = build_view_indexes(&bead_node_tree, &critical_path);
+
+    // 14. Calculate metadata
+    let mut metadata = calculate_project_metadata(
+        &bead_node_tree,
+        &filtered,
+        distributions,
+        &critical_path,
+        &x_map,
+        workflow,
+    );
+
+    if params.distribution_mode != DistributionMode::Cell {
+        metadata.dated_distributions =
+            calculate_dated_distribution(&bead_node_tree, workflow, &calendar, params.distribution_mode);
+    }
+    metadata.axis = Some(calendar.axis(total_cells));
+
+    timings.record("compute", compute_start);
+
+    ProjectViewModel {
+        tree: bead_node_tree,
+        metadata,
+        indexes,
+        timings,
+        parse_warnings: Vec::new(),
+    }
+}
+
+// ============================================================================
+// Data Structures for Processed Output (bp6-07y.1)
+// ============================================================================
+
+/// WBSNode represents a node in the Work Breakdown Structure tree.
+/// Extends Bead with tree-specific metadata for hierarchical display.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WBSNode {
+    // Flatten all Bead fields
+    #[serde(flatten)]
+    pub bead: Bead,
+
+    // Tree structure
+    pub children: Vec<WBSNode>,
+
+    // UI state flags (use camelCase for TypeScript compatibility)
+    #[serde(rename = "isExpanded")]
+    pub is_expanded: bool,
+    #[serde(rename = "isBlocked")]
+    pub is_blocked: bool,
+    #[serde(rename = "isCritical")]
+    pub is_critical: bool,
+}
+
+/// Point represents an (x, y) coordinate for Gantt chart rendering.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// GanttItem represents a single bead's position in the Gantt chart.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GanttItem {
+    pub bead: Bead,
+    pub x: f64,
+    pub width: f64,
+    pub row: usize,
+    pub depth: usize,
+    #[serde(rename = "isCritical")]
+    pub is_critical: bool,
+    #[serde(rename = "isBlocked")]
+    pub is_blocked: bool,
+    /// Milestones are drawn as zero-width markers at `x`
+    #[serde(default, rename = "isMilestone")]
+    pub is_milestone: bool,
+    /// Set on a summary bar standing in for the leaf tasks folded into it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<GanttSummary>,
+}
+
+/// The leaf tasks an aggregated Gantt bar stands for
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GanttSummary {
+    pub count: usize,
+    pub status_counts: BTreeMap<String, usize>,
+    pub critical_count: usize,
+    pub blocked_count: usize,
+}
+
+/// GanttConnector represents a dependency line between two beads in the Gantt chart.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GanttConnector {
+    pub from: Point,
+    pub to: Point,
+    #[serde(rename = "isCritical")]
+    pub is_critical: bool,
+}
+
+/// GanttLayout contains all computed layout data for Gantt chart rendering.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GanttLayout {
+    pub items: Vec<GanttItem>,
+    pub connectors: Vec<GanttConnector>,
+    #[serde(rename = "rowCount")]
+    pub row_count: usize,
+    #[serde(rename = "rowDepths")]
+    pub row_depths: Vec<usize>,
+    /// Per-owner task sequence, populated when resource leveling is enabled
+    #[serde(rename = "ownerLanes", default)]
+    pub owner_lanes: Vec<OwnerLane>,
+}
+
+/// OwnerLane lists the beads assigned to one owner in scheduled order.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OwnerLane {
+    pub owner: String,
+    #[serde(rename = "beadIds")]
+    pub bead_ids: Vec<String>,
+    /// First free cell after the owner's last task
+    #[serde(rename = "endCell")]
+    pub end_cell: usize,
+}
+
+/// BucketDistribution represents status counts for a time bucket in the Gantt header.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BucketDistribution {
+    pub open: usize,
+    #[serde(rename = "inProgress")]
+    pub in_progress: usize,
+    pub blocked: usize,
+    pub closed: usize,
+}
+
+/// ProcessedData is the top-level response structure containing all processed data.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProcessedData {
+    pub tree: Vec<WBSNode>,
+    pub layout: GanttLayout,
+    pub distributions: Vec<BucketDistribution>,
+    /// Per-stage durations and bead counts of this computation
+    #[serde(default)]
+    pub timings: Timings,
+    /// Lines of the beads file skipped because they aren't valid beads
+    #[serde(default, rename = "parseWarnings")]
+    pub parse_warnings: Vec<ParseWarning>,
+}
+
+// ============================================================================
+// Unified View Model (bp6-75y.1)
+// ============================================================================
+
+/// BeadNode is the unified node structure in the view model tree.
+/// It contains all bead data, computed properties, hierarchical structure,
+/// and logical positioning (NOT pixel coordinates - those are computed by frontend).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BeadNode {
+    // ===== Core Bead Data =====
+    pub id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: String,
+    pub priority: u32,
+    #[serde(rename = "issueType")]
+    pub issue_type: String,
+    pub estimate: Option<u32>,
+    #[serde(default)]
+    pub dependencies: Vec<Dependency>,
+
+    // ===== Metadata Fields =====
+    pub owner: Option<String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<String>,
+    #[serde(rename = "createdBy")]
+    pub created_by: Option<String>,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: Option<String>,
+    pub labels: Option<Vec<String>>,
+    #[serde(default, rename = "acceptanceCriteria")]
+    pub acceptance_criteria: Option<Vec<String>>,
+    #[serde(rename = "closedAt")]
+    pub closed_at: Option<String>,
+    #[serde(rename = "closeReason")]
+    pub close_reason: Option<String>,
+    #[serde(rename = "isFavorite")]
+    pub is_favorite: Option<bool>,
+    pub parent: Option<String>,
+    #[serde(rename = "externalReference")]
+    pub external_reference: Option<String>,
+    #[serde(default, rename = "dueDate")]
+    pub due_date: Option<String>,
+    #[serde(default, rename = "isMilestone")]
+    pub is_milestone: bool,
+
+    // ===== Unified Field Naming =====
+    // Note: JSONL uses 'design' and 'notes', NOT 'design_notes'/'working_notes'
+    pub design: Option<String>,
+    pub notes: Option<String>,
+
+    // ===== Hierarchical Structure =====
+    pub children: Vec<BeadNode>,
+
+    // ===== Computed Properties (Backend calculates these) =====
+    #[serde(rename = "isBlocked")]
+    pub is_blocked: bool,
+    #[serde(rename = "isCritical")]
+    pub is_critical: bool,
+    #[serde(rename = "blockingIds")]
+    pub blocking_ids: Vec<String>,
+    /// Beads mentioned in the description, design, notes or acceptance criteria
+    #[serde(default)]
+    pub references: Vec<String>,
+    /// Past its due date and not closed
+    #[serde(default, rename = "isOverdue")]
+    pub is_overdue: bool,
+    /// Sum of the estimates of the leaf tasks below (its own for a leaf)
+    #[serde(default, rename = "totalEstimate")]
+    pub total_estimate: u64,
+    /// Part of `total_estimate` that belongs to done tasks
+    #[serde(default, rename = "completedEstimate")]
+    pub completed_estimate: u64,
+    /// Work done, 0-100: by estimate, or by the share of done leaf tasks
+    /// when none of them is estimated
+    #[serde(default, rename = "progressPercent")]
+    pub progress_percent: f64,
+    /// Minutes of tracked time on this bead and the beads below it
+    #[serde(default, rename = "actualMinutes")]
+    pub actual_minutes: u64,
+    /// Met acceptance criteria, for beads that have any
+    #[serde(default, rename = "acceptanceProgress", skip_serializing_if = "Option::is_none")]
+    pub acceptance_progress: Option<crate::acceptance::AcceptanceProgress>,
+    /// Beads in other projects this one waits on, looked up on every load
+    #[serde(default, rename = "externalDependencies")]
+    pub external_dependencies: Vec<crate::external::ExternalDependency>,
+    /// Waiting on an external bead that isn't done or can't be found
+    #[serde(default, rename = "isExternallyBlocked")]
+    pub is_externally_blocked: bool,
+    /// On the user's own watchlist (see `watchlist`), unlike the shared `is_favorite`
+    #[serde(default, rename = "isPinned")]
+    pub is_pinned: bool,
+
+    // ===== Logical Positioning (NOT pixels - frontend converts to pixels) =====
+    /// Tree depth (0 = root, 1 = child, 2 = grandchild, etc.)
+    pub depth: usize,
+    /// Cell offset: which grid cell column this task starts in (0, 1, 2, ...)
+    #[serde(rename = "cellOffset")]
+    pub cell_offset: usize,
+    /// Cell count: how many grid cells wide this task is (1, 2, 3, ...)
+    #[serde(rename = "cellCount")]
+    pub cell_count: usize,
+
+    // ===== Calendar Dates (derived from the project scheduling config) =====
+    /// ISO date (YYYY-MM-DD) the task starts on
+    #[serde(default)]
+    pub start_date: Option<String>,
+    /// ISO date (YYYY-MM-DD) of the last working day the task covers
+    #[serde(default)]
+    pub end_date: Option<String>,
+
+    // ===== UI State =====
+    #[serde(rename = "isExpanded")]
+    pub is_expanded: bool,
+    #[serde(rename = "isVisible")]
+    pub is_visible: bool,
+
+    // Extra metadata (preserves any unknown fields from JSONL)
+    #[serde(flatten)]
+    pub extra_metadata: serde_json::Map<String, serde_json::Value>,
+}
+
+/// ViewIndexes provides fast lookups into the view model tree.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ViewIndexes {
+    /// Map from bead ID to its index in a flattened tree traversal
+    #[serde(rename = "idToIndex")]
+    pub id_to_index: HashMap<String, usize>,
+
+    /// Map from bead ID to its parent's ID
+    #[serde(rename = "idToParent")]
+    pub id_to_parent: HashMap<String, String>,
+
+    /// List of all critical path bead IDs (in order)
+    #[serde(rename = "criticalPath")]
+    pub critical_path: Vec<String>,
+}
+
+/// DatedBucket holds status counts for one calendar week or sprint in the Gantt header.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DatedBucket {
+    /// Display label, e.g. "Week of 12 Oct" or "Sprint 3"
+    pub label: String,
+    /// ISO date (YYYY-MM-DD) the period starts on
+    pub start_date: String,
+    /// ISO date of the last day in the period
+    pub end_date: String,
+    #[serde(flatten)]
+    pub counts: BucketDistribution,
+}
+
+/// ProjectMetadata contains aggregate statistics about the project.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectMetadata {
+    /// Total number of beads in the project
+    #[serde(rename = "totalBeads")]
+    pub total_beads: usize,
+
+    /// Number of open beads
+    #[serde(rename = "openCount")]
+    pub open_count: usize,
+
+    /// Number of blocked beads
+    #[serde(rename = "blockedCount")]
+    pub blocked_count: usize,
+
+    /// Number of beads in progress
+    #[serde(rename = "inProgressCount")]
+    pub in_progress_count: usize,
+
+    /// Number of closed beads
+    #[serde(rename = "closedCount")]
+    pub closed_count: usize,
+
+    /// Total project duration (critical path length)
+    #[serde(rename = "totalDuration")]
+    pub total_duration: f64,
+
+    /// State distributions by time bucket
+    pub distributions: Vec<BucketDistribution>,
+
+    /// State distributions by calendar week or sprint, filled when
+    /// `FilterParams::distribution_mode` asks for them
+    #[serde(default)]
+    pub dated_distributions: Vec<DatedBucket>,
+
+    /// Date-labeled timeline axis
+    #[serde(default)]
+    pub axis: Option<schedule::TimelineAxis>,
+}
+
+/// ProjectViewModel is the single source of truth for all UI components.
+/// Backend computes this once per filter/view change, frontend reactively renders it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectViewModel {
+    /// Hierarchical tree of beads with all computed properties
+    pub tree: Vec<BeadNode>,
+
+    /// Project-level metadata and statistics
+    pub metadata: ProjectMetadata,
+
+    /// Fast lookup indexes
+    pub indexes: ViewIndexes,
+
+    /// Per-stage durations and bead counts of this computation
+    #[serde(default)]
+    pub timings: Timings,
+
+    /// Lines of the beads file skipped because they aren't valid beads
+    #[serde(default)]
+    pub parse_warnings: Vec<ParseWarning>,
+}
+
+// ============================================================================
+// WBS Tree Building Algorithms (bp6-07y.2)
+// ============================================================================
+
+/// DependencyGraph contains the graph representation of blocking dependencies.
+/// Used for topological sorting and building the WBS tree.
+#[derive(Debug, Clone)]
+pub struct DependencyGraph {
+    /// Map from bead ID to list of beads it blocks (successors)
+    pub blocks: HashMap<String, Vec<String>>,
+    /// Map from bead ID to list of beads that block it (predecessors)
+    pub blocked_by: HashMap<String, Vec<String>>,
+}
+
+impl DependencyGraph {
+    fn new() -> Self {
+        DependencyGraph {
+            blocks: HashMap::new(),
+            blocked_by: HashMap::new(),
+        }
+    }
+
+    /// Whether `target` can be reached from `start` by following "blocks" edges.
+    /// Adding an edge `target -> start` would close a cycle exactly when this is true.
+    pub(crate) fn reaches(&self, start: &str, target: &str) -> bool {
+        let mut stack = vec![start.to_string()];
+        let mut seen: HashSet<String> = HashSet::new();
+
+        while let Some(id) = stack.pop() {
+            if id == target {
+                return true;
+            }
+            if !seen.insert(id.clone()) {
+                continue;
+            }
+            if let Some(next) = self.blocks.get(&id) {
+                stack.extend(next.iter().cloned());
+            }
+        }
+
+        false
+    }
+}
+
+/// Construct a dependency graph from bead dependencies.
+/// Separates parent-child relationships from blocking dependencies.
+///
+/// Returns a DependencyGraph with blocks and blocked_by maps populated.
+pub fn build_dependency_graph(beads: &[Bead]) -> DependencyGraph {
+    let mut graph = DependencyGraph::new();
+
+    // Initialize empty vectors for all beads
+    for bead in beads {
+        graph.blocks.insert(bead.id.clone(), Vec::new());
+        graph.blocked_by.insert(bead.id.clone(), Vec::new());
+    }
+
+    // Build the graph from dependencies
+    for bead in beads {
+        for dep in &bead.dependencies {
+            if dep.r#type == "blocks" {
+                // dep.depends_on_id blocks bead.id
+                // So: depends_on_id -> bead.id (edge in graph)
+                let blocker_id = dep.depends_on_id.clone();
+                let blocked_id = bead.id.clone();
+
+                // Add to blocks map (blocker blocks blocked_id)
+                graph.blocks
+                    .entry(blocker_id.clone())
+                    .or_insert_with(Vec::new)
+                    .push(blocked_id.clone());
+
+                // Add to blocked_by map (blocked_id is blocked by blocker)
+                graph.blocked_by
+                    .entry(blocked_id)
+                    .or_insert_with(Vec::new)
+                    .push(blocker_id);
+            }
+        }
+    }
+
+    graph
+}
+
+/// Topologically sort nodes using Kahn's Algorithm.
+///
+/// This handles circular dependencies by appending remaining nodes at the end.
+/// Secondary sort by priority for deterministic ordering.
+///
+/// # Arguments
+/// * `nodes` - The WBSNodes to sort (typically siblings at the same tree level)
+/// * `bead_ids` - Set of bead IDs that are part of this sibling group
+/// * `graph` - The dependency graph for all beads
+///
+/// # Returns
+/// A topologically sorted vector of WBSNodes.
+pub fn topological_sort(
+    nodes: Vec<WBSNode>,
+    bead_ids: &HashSet<String>,
+    graph: &DependencyGraph,
+) -> Vec<WBSNode> {
+    if nodes.len() <= 1 {
+        return nodes;
+    }
+
+    // Build a map for quick lookup; nodes are moved out as they're sorted
+    let mut node_map: HashMap<String, WBSNode> =
+        nodes.into_iter().map(|n| (n.bead.id.clone(), n)).collect();
+
+    // Calculate in-degree for nodes in this sibling group
+    let mut in_degree: HashMap<String, usize> = HashMap::new();
+
+    for id in bead_ids {
+        // Count how many blockers from THIS sibling group block this node
+        let empty_vec = Vec::new();
+        let blockers = graph.blocked_by.get(id).unwrap_or(&empty_vec);
+        let count = blockers.iter().filter(|b| bead_ids.contains(*b)).count();
+        in_degree.insert(id.clone(), count);
+    }
+
+    // Initialize queue with nodes that have in-degree 0
+    let mut initial_nodes: Vec<&WBSNode> = node_map
+        .values()
+        .filter(|n| *in_degree.get(&n.bead.id).unwrap_or(&0) == 0)
+        .collect();
+
+    // Sort by priority for deterministic ordering (ascending: P0 < P1 < P2)
+    // Secondary sort by ID for stability
+    initial_nodes.sort_by(|a, b| {
+        let ord = a.bead.priority.cmp(&b.bead.priority);
+        if ord == std::cmp::Ordering::Equal {
+            a.bead.id.cmp(&b.bead.id)
+        } else {
+            ord
+        }
+    });
+
+    let mut queue: Vec<String> = initial_nodes.iter().map(|n| n.bead.id.clone()).collect();
+    let mut result: Vec<WBSNode> = Vec::with_capacity(node_map.len());
+
+    // Kahn's algorithm
+    while let Some(u) = queue.pop() {
+        if let Some(node) = node_map.remove(&u) {
+            result.push(node);
+        }
+
+        // Process neighbors (nodes that u blocks)
+        if let Some(neighbors) = graph.blocks.get(&u) {
+            for v in neighbors {
+                // Only process if v is in this sibling group
+                if bead_ids.contains(v) {
+                    if let Some(degree) = in_degree.get_mut(v) {
+                        *degree = degree.saturating_sub(1);
+                        if *degree == 0 {
+                            queue.push(v.clone());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Handle circular dependencies: append remaining nodes sorted by priority
+    if !node_map.is_empty() {
+        let mut remaining: Vec<WBSNode> = node_map.into_values().collect();
+
+        remaining.sort_by_key(|n| n.bead.priority);
+        result.extend(remaining);
+    }
+
+    result
+}
+
+// ============================================================================
+// Filtering and State Distribution (bp6-07y.4)
+// ============================================================================
+
+/// ClosedTimeFilter enum for filtering closed beads by time.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ClosedTimeFilter {
+    All,
+    #[serde(rename = "1h")]
+    OneHour,
+    #[serde(rename = "6h")]
+    SixHours,
+    #[serde(rename = "24h")]
+    TwentyFourHours,
+    #[serde(rename = "7d")]
+    SevenDays,
+    #[serde(rename = "30d")]
+    ThirtyDays,
+    #[serde(rename = "older_than_6h")]
+    OlderThan6h,
+}
+
+/// How the Gantt header histogram buckets beads
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DistributionMode {
+    /// One bucket per grid cell (`ProjectMetadata::distributions` only)
+    #[default]
+    Cell,
+    /// Calendar weeks, Monday to Sunday
+    Week,
+    /// Sprints of `SchedulingConfig::sprint_length_days` from the start date
+    Sprint,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+    None,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SortBy {
+    Priority,
+    Title,
+    Type,
+    Id,
+    /// Order stored by `reorder_siblings`, dependencies for unranked beads
+    Manual,
+    None,
+}
+
+/// Check if a bead passes the closed time filter.
+pub(crate) fn passes_closed_time_filter(bead: &Bead, filter: &ClosedTimeFilter, workflow: &workflow::WorkflowConfig) -> bool {
+    // If not closed, always passes
+    if !workflow.is_done(&bead.status) {
+        return true;
+    }
+
+    // 'all' filter shows all closed tasks
+    if *filter == ClosedTimeFilter::All {
+        return true;
+    }
+
+    // If no closed_at timestamp, include it (benefit of the doubt)
+    let closed_at = match &bead.closed_at {
+        Some(s) if !s.is_empty() => s,
+        _ => return true,
+    };
+
+    // Parse the timestamp (RFC3339 format expected)
+    let closed_date = match chrono::DateTime::parse_from_rfc3339(closed_at) {
+        Ok(dt) => dt,
+        Err(_) => return true, // Invalid timestamp, include it
+    };
+
+    let now = chrono::Utc::now();
+    let duration = now.signed_duration_since(closed_date);
+    let hours_ago = duration.num_hours() as f64 + (duration.num_minutes() % 60) as f64 / 60.0;
+
+    match filter {
+        ClosedTimeFilter::All => true,
+        ClosedTimeFilter::OneHour => hours_ago <= 1.0,
+        ClosedTimeFilter::SixHours => hours_ago <= 6.0,
+        ClosedTimeFilter::TwentyFourHours => hours_ago <= 24.0,
+        ClosedTimeFilter::SevenDays => hours_ago <= 24.0 * 7.0,
+        ClosedTimeFilter::ThirtyDays => hours_ago <= 24.0 * 30.0,
+        ClosedTimeFilter::OlderThan6h => hours_ago > 6.0,
+    }
+}
+
+/// Indices of the beads a view shows, in file order.
+///
+/// Tombstones are always dropped; done beads are dropped by `hide_closed` and
+/// the closed time filter ("closed" means any status in the workflow's done
+/// category). The text filter is a search query (see `search` for the
+/// syntax): plain words match title, id, owner, labels, description, design
+/// and notes. With `include_hierarchy`, the ancestors of search hits are
+/// included too, whatever their status, so tree context is preserved.
+///
+/// With `scope_root_id`, beads outside its scope (see `scope_ids`) are
+/// dropped before anything else, ancestors of search hits included.
+pub(crate) fn visible_indices(beads: &[Bead], params: &FilterParams, workflow: &workflow::WorkflowConfig) -> Vec<usize> {
+    let query = search::SearchQuery::parse(&params.filter_text);
+    let scope = params.scope_root_id.as_deref().map(|root| scope_ids(beads, root));
+    let in_scope = |b: &Bead| scope.as_ref().is_none_or(|ids| ids.contains(b.id.as_str()));
+    let mut visible: Vec<bool> = beads
+        .iter()
+        .map(|b| {
+            in_scope(b)
+                && b.status != "tombstone"
+                && !(params.hide_closed && workflow.is_done(&b.status))
+                && passes_closed_time_filter(b, &params.closed_time_filter, workflow)
+                && (query.is_empty() || query.matches(b))
+        })
+        .collect();
+
+    if !params.filter_text.is_empty() && params.include_hierarchy {
+        let index_of: HashMap<&str, usize> = beads.iter().enumerate().map(|(i, b)| (b.id.as_str(), i)).collect();
+        let parent_of = |i: usize| {
+            beads[i]
+                .dependencies
+                .iter()
+                .rev()
+                .find(|d| d.r#type == "parent-child")
+                .and_then(|d| index_of.get(d.depends_on_id.as_str()).copied())
+        };
+
+        let hits: Vec<usize> = (0..beads.len()).filter(|&i| visible[i]).collect();
+        for hit in hits {
+            let mut current = parent_of(hit);
+            // Stop at ancestors already included (also guards against cycles)
+            while let Some(ancestor) = current.filter(|&a| !visible[a] && in_scope(&beads[a])) {
+                visible[ancestor] = true;
+                current = parent_of(ancestor);
+            }
+        }
+    }
+
+    (0..beads.len()).filter(|&i| visible[i]).collect()
+}
+
+/// The epic `root`, the beads below it and the beads outside it that block
+/// one of them: what a view with `scope_root_id` shows
+fn scope_ids<'a>(beads: &'a [Bead], root: &'a str) -> HashSet<&'a str> {
+    let mut ids = crate::graph_export::subtree_ids(beads, root);
+    let blockers: Vec<&str> = beads
+        .iter()
+        .filter(|b| ids.contains(b.id.as_str()))
+        .flat_map(|b| b.dependencies.iter().filter(|d| d.r#type == "blocks").map(|d| d.depends_on_id.as_str()))
+        .collect();
+    ids.extend(blockers);
+    ids
+}
+
+/// The beads a view shows (see `visible_indices`), moved out of `beads`
+/// rather than copied.
+///
+/// In a scoped view the root and the outside blockers lose their link to a
+/// parent outside the scope, so they head the tree instead of dropping out
+/// of it.
+pub fn select_beads(beads: Vec<Bead>, params: &FilterParams, workflow: &workflow::WorkflowConfig) -> Vec<Bead> {
+    let visible: HashSet<usize> = visible_indices(&beads, params, workflow).into_iter().collect();
+    let Some(root) = params.scope_root_id.as_deref() else {
+        return beads
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, bead)| visible.contains(&i).then_some(bead))
+            .collect();
+    };
+
+    // Heads of the scoped tree: the root and the blockers outside it
+    let subtree: HashSet<String> = crate::graph_export::subtree_ids(&beads, root).into_iter().map(str::to_string).collect();
+    let selected_ids: HashSet<String> = visible.iter().map(|&i| beads[i].id.clone()).collect();
+    beads
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, bead)| visible.contains(&i).then_some(bead))
+        .map(|mut bead| {
+            if bead.id == root || !subtree.contains(&bead.id) {
+                bead.dependencies.retain(|d| d.r#type != "parent-child" || selected_ids.contains(&d.depends_on_id));
+            }
+            bead
+        })
+        .collect()
+}
+
+/// Calculate state distribution (open/inProgress/blocked/closed counts) across grid cell buckets.
+/// Used for Gantt header visualization. Each bucket = 1 grid cell.
+/// Statuses are bucketed by their workflow category.
+pub fn calculate_state_distribution_from_tree(
+    tree: &[BeadNode],
+    workflow: &workflow::WorkflowConfig,
+) -> Vec<BucketDistribution> {
+    // Flatten tree to get all nodes
+    fn flatten<'a>(nodes: &'a [BeadNode], acc: &mut Vec<&'a BeadNode>) {
+        for node in nodes {
+            acc.push(node);
+            if !node.children.is_empty() {
+                flatten(&node.children, acc);
+            }
+        }
+    }
+
+    let mut all_nodes = Vec::new();
+    flatten(tree, &mut all_nodes);
+
+    if all_nodes.is_empty() {
+        return Vec::new();
+    }
+
+    // Find the maximum cell offset + count to determine number of buckets
+    let max_cell = all_nodes
+        .iter()
+        .map(|node| node.cell_offset + node.cell_count)
+        .max()
+        .unwrap_or(1);
+
+    let num_buckets = max_cell.max(1);
+
+    let mut buckets: Vec<BucketDistribution> = (0..num_buckets)
+        .map(|_| BucketDistribution {
+            open: 0,
+            in_progress: 0,
+            blocked: 0,
+            closed: 0,
+        })
+        .collect();
+
+    // Count beads in each bucket by status
+    // Exclude epics and features (tasks only)
+    for node in all_nodes {
+        if node.issue_type == "epic" || node.issue_type == "feature" {
+            continue;
+        }
+
+        let start_bucket = node.cell_offset;
+        let end_bucket = node.cell_offset + node.cell_count - 1;
+
+        // Handle bead overlap across buckets
+        let category = workflow.category_of(&node.status);
+        for bucket_idx in start_bucket..=end_bucket.min(num_buckets - 1) {
+            match category {
+                StatusCategory::Todo => buckets[bucket_idx].open += 1,
+                StatusCategory::Doing => buckets[bucket_idx].in_progress += 1,
+                StatusCategory::Done => buckets[bucket_idx].closed += 1,
+                StatusCategory::Blocked => {}
+            }
+
+            // Count blocked beads (by status or by an open blocker)
+            if category == StatusCategory::Blocked || node.is_blocked {
+                buckets[bucket_idx].blocked += 1;
+            }
+        }
+    }
+
+    buckets
+}
+
+/// Calculate state distribution across calendar weeks or sprints.
+/// Closed beads span their actual created..closed dates; others span their
+/// scheduled dates. Every period between the first and last span gets a
+/// bucket, so gaps show as empty bars.
+pub fn calculate_dated_distribution(
+    tree: &[BeadNode],
+    workflow: &workflow::WorkflowConfig,
+    calendar: &schedule::CalendarMapper,
+    mode: DistributionMode,
+) -> Vec<DatedBucket> {
+    use chrono::{Datelike, NaiveDate};
+
+    fn flatten<'a>(nodes: &'a [BeadNode], acc: &mut Vec<&'a BeadNode>) {
+        for node in nodes {
+            acc.push(node);
+            flatten(&node.children, acc);
+        }
+    }
+
+    let rfc3339_date = |s: &Option<String>| {
+        s.as_deref().and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok()).map(|dt| dt.date_naive())
+    };
+    let iso_date = |s: &Option<String>| s.as_deref().and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok());
+
+    let mut all_nodes = Vec::new();
+    flatten(tree, &mut all_nodes);
+
+    // Exclude epics and features (tasks only), as the cell distribution does
+    let spans: Vec<(NaiveDate, NaiveDate, &BeadNode)> = all_nodes
+        .into_iter()
+        .filter(|node| node.issue_type != "epic" && node.issue_type != "feature")
+        .filter_map(|node| {
+            let actual = match (rfc3339_date(&node.created_at), rfc3339_date(&node.closed_at)) {
+                (Some(created), Some(closed)) if workflow.is_done(&node.status) => Some((created.min(closed), closed)),
+                _ => None,
+            };
+            let (start, end) = actual.or_else(|| Some((iso_date(&node.start_date)?, iso_date(&node.end_date)?)))?;
+            Some((start, end.max(start), node))
+        })
+        .collect();
+
+    let (Some(first), Some(last)) = (spans.iter().map(|s| s.0).min(), spans.iter().map(|s| s.1).max()) else {
+        return Vec::new();
+    };
+
+    let period_days = match mode {
+        DistributionMode::Sprint => calendar.sprint_length_days(),
+        _ => 7,
+    };
+    let period_start = |date: NaiveDate| match mode {
+        DistributionMode::Sprint => calendar.sprint_start(date),
+        _ => date - chrono::Duration::days(date.weekday().num_days_from_monday() as i64),
+    };
+    let origin = period_start(first);
+    let index_of = |date: NaiveDate| ((period_start(date) - origin).num_days() / period_days) as usize;
+
+    let mut buckets: Vec<DatedBucket> = (0..=index_of(last))
+        .map(|i| {
+            let start = origin + chrono::Duration::days(i as i64 * period_days);
+            let label = match mode {
+                DistributionMode::Sprint => format!("Sprint {}", calendar.sprint_number(start)),
+                _ => format!("Week of {}", start.format("%-d %b")),
+            };
+            DatedBucket {
+                label,
+                start_date: start.format("%Y-%m-%d").to_string(),
+                end_date: (start + chrono::Duration::days(period_days - 1)).format("%Y-%m-%d").to_string(),
+                counts: BucketDistribution { open: 0, in_progress: 0, blocked: 0, closed: 0 },
+            }
+        })
+        .collect();
+
+    for (start, end, node) in spans {
+        let category = workflow.category_of(&node.status);
+        for bucket in &mut buckets[index_of(start)..=index_of(end)] {
+            match category {
+                StatusCategory::Todo => bucket.counts.open += 1,
+                StatusCategory::Doing => bucket.counts.in_progress += 1,
+                StatusCategory::Done => bucket.counts.closed += 1,
+                StatusCategory::Blocked => {}
+            }
+            if category == StatusCategory::Blocked || node.is_blocked {
+                bucket.counts.blocked += 1;
+            }
+        }
+    }
+
+    buckets
+}
+
+// ============================================================================
+// WBS Tree Building - Build Tree Structure (bp6-07y.2.3)
+// ============================================================================
+
+/// Build hierarchical WBS tree from flat bead list using parent-child dependencies.
+/// Groups beads into root nodes and nested children recursively to support deep hierarchies.
+pub fn build_wbs_tree(beads: &[Bead]) -> Vec<WBSNode> {
+    let bead_map: HashMap<String, &Bead> = beads.iter().map(|b| (b.id.clone(), b)).collect();
+    
+    // Map of parent_id -> list of child_ids (ordered by appearance in beads list)
+    let mut parent_to_children: HashMap<String, Vec<String>> = HashMap::new();
+    let mut root_ids: Vec<String> = Vec::new();
+
+    // 1. Identify roots and parent-child relationships
+    for bead in beads {
+        let parent_dep = bead.dependencies.iter().find(|d| d.r#type == "parent-child");
+        if let Some(dep) = parent_dep {
+            parent_to_children
+                .entry(dep.depends_on_id.clone())
+                .or_default()
+                .push(bead.id.clone());
+        } else {
+            root_ids.push(bead.id.clone());
+        }
+    }
+
+    // 2. Pre-calculate blocked status for efficiency
+    let status_map: HashMap<String, String> = beads.iter().map(|b| (b.id.clone(), b.status.clone())).collect();
+    let mut blocked_map: HashMap<String, bool> = HashMap::new();
+    
+    for bead in beads {
+        let is_blocked = bead.dependencies.iter()
+            .filter(|d| d.r#type == "blocks")
+            .any(|d| {
+                status_map.get(&d.depends_on_id)
+                    .map(|status| status != "closed" && status != "done")
+                    .unwrap_or(false)
+            });
+        blocked_map.insert(bead.id.clone(), is_blocked);
+    }
+
+    // 3. Recursive builder function
+    fn build_node_recursive(
+        id: &str,
+        bead_map: &HashMap<String, &Bead>,
+        parent_to_children: &HashMap<String, Vec<String>>,
+        blocked_map: &HashMap<String, bool>,
+    ) -> WBSNode {
+        let bead = bead_map.get(id).expect("Bead ID missing from map");
+        let mut children = Vec::new();
+        
+        if let Some(child_ids) = parent_to_children.get(id) {
+            for child_id in child_ids {
+                children.push(build_node_recursive(child_id, bead_map, parent_to_children, blocked_map));
+            }
+        }
+
+        WBSNode {
+            bead: (*bead).clone(),
+            children,
+            is_expanded: true,
+            is_blocked: *blocked_map.get(id).unwrap_or(&false),
+            is_critical: false,
+        }
+    }
+
+    // 4. Build the tree starting from roots
+    root_ids.into_iter()
+        .map(|id| build_node_recursive(&id, &bead_map, &parent_to_children, &blocked_map))
+        .collect()
+}
+
+// ============================================================================
+// Gantt Layout Calculation - Earliest Start Times (bp6-07y.3.1)
+// ============================================================================
+
+/// Calculate earliest start time (X position) for each bead based on blocking dependencies.
+/// Uses memoization to avoid recomputation.
+/// When `level_by_owner` is set, a resource-leveling pass then serializes leaf
+/// tasks that share the same owner so nobody is scheduled on two tasks at once.
+pub(crate) fn calculate_earliest_start_times(
+    beads: &[Bead],
+    blocks_map: &HashMap<String, Vec<String>>,
+    level_by_owner: bool,
+) -> HashMap<String, usize> {
+    let mut x_map: HashMap<String, usize> = HashMap::new();
+
+    fn get_x(
+        id: &str,
+        blocks_map: &HashMap<String, Vec<String>>,
+        x_map: &mut HashMap<String, usize>,
+        visited: &mut HashSet<String>,
+    ) -> usize {
+        // Return memoized result if available
+        if let Some(&x) = x_map.get(id) {
+            return x;
+        }
+
+        // Detect circular dependencies
+        if visited.contains(id) {
+            return 0;
+        }
+
+        // Get predecessors (beads that block this one)
+        let preds = match blocks_map.get(id) {
+            Some(preds) if !preds.is_empty() => preds,
+            _ => {
+                // No blockers, start at x=0
+                x_map.insert(id.to_string(), 0);
+                return 0;
+            }
+        };
+
+        // Calculate x as max(predecessor x values) + 1; `visited` holds the
+        // current path only, so it's unwound once the predecessors are done
+        visited.insert(id.to_string());
+        let max_pred_x = preds
+            .iter()
+            .map(|p| get_x(p, blocks_map, x_map, visited))
+            .max()
+            .unwrap_or(0);
+        visited.remove(id);
+
+        let x = max_pred_x + 1;
+        x_map.insert(id.to_string(), x);
+        x
+    }
+
+    // Calculate x position for all beads
+    for bead in beads {
+        let mut visited = HashSet::new();
+        get_x(&bead.id, blocks_map, &mut x_map, &mut visited);
+    }
+
+    if level_by_owner {
+        x_map = level_start_times_by_owner(beads, blocks_map, &x_map);
+    }
+
+    x_map
+}
+
+/// Resource-leveling pass: list-schedule leaf tasks in dependency order,
+/// delaying each one until both its blockers are done and its owner is free.
+/// Parent nodes and unowned tasks keep their dependency-only start time
+/// (shifted only if a leveled blocker moved).
+pub(crate) fn level_start_times_by_owner(
+    beads: &[Bead],
+    blocks_map: &HashMap<String, Vec<String>>,
+    unleveled: &HashMap<String, usize>,
+) -> HashMap<String, usize> {
+    let parent_ids: HashSet<&str> = beads
+        .iter()
+        .flat_map(|b| b.dependencies.iter())
+        .filter(|d| d.r#type == "parent-child")
+        .map(|d| d.depends_on_id.as_str())
+        .collect();
+
+    // Process in unleveled order so blockers are always placed first
+    let mut order: Vec<&Bead> = beads.iter().collect();
+    order.sort_by(|a, b| {
+        let xa = unleveled.get(&a.id).copied().unwrap_or(0);
+        let xb = unleveled.get(&b.id).copied().unwrap_or(0);
+        xa.cmp(&xb)
+            .then(a.priority.cmp(&b.priority))
+            .then(a.id.cmp(&b.id))
+    });
+
+    let mut leveled: HashMap<String, usize> = HashMap::new();
+    let mut owner_next_free: HashMap<&str, usize> = HashMap::new();
+
+    for bead in order {
+        let earliest = blocks_map
+            .get(&bead.id)
+            .map(|preds| {
+                preds
+                    .iter()
+                    .map(|p| {
+                        leveled
+                            .get(p)
+                            .or_else(|| unleveled.get(p))
+                            .copied()
+                            .unwrap_or(0)
+                            + 1
+                    })
+                    .max()
+                    .unwrap_or(0)
+            })
+            .unwrap_or(0);
+
+        let owner = bead
+            .owner
+            .as_deref()
+            .filter(|o| !o.is_empty() && !parent_ids.contains(bead.id.as_str()));
+
+        let start = match owner {
+            Some(o) => {
+                let free = owner_next_free.get(o).copied().unwrap_or(0);
+                let start = earliest.max(free);
+                owner_next_free.insert(o, start + 1);
+                start
+            }
+            None => earliest,
+        };
+
+        leveled.insert(bead.id.clone(), start);
+    }
+
+    leveled
+}
+
+/// Group owned leaf beads into per-owner lanes ordered by scheduled start.
+pub(crate) fn build_owner_lanes(beads: &[Bead], x_map: &HashMap<String, usize>) -> Vec<OwnerLane> {
+    let parent_ids: HashSet<&str> = beads
+        .iter()
+        .flat_map(|b| b.dependencies.iter())
+        .filter(|d| d.r#type == "parent-child")
+        .map(|d| d.depends_on_id.as_str())
+        .collect();
+
+    let mut by_owner: HashMap<String, Vec<(usize, String)>> = HashMap::new();
+    for bead in beads {
+        if parent_ids.contains(bead.id.as_str()) {
+            continue;
+        }
+        if let Some(owner) = bead.owner.as_ref().filter(|o| !o.is_empty()) {
+            let x = x_map.get(&bead.id).copied().unwrap_or(0);
+            by_owner.entry(owner.clone()).or_default().push((x, bead.id.clone()));
+        }
+    }
+
+    let mut lanes: Vec<OwnerLane> = by_owner
+        .into_iter()
+        .map(|(owner, mut entries)| {
+            entries.sort();
+            let end_cell = entries.last().map(|(x, _)| x + 1).unwrap_or(0);
+            OwnerLane {
+                owner,
+                bead_ids: entries.into_iter().map(|(_, id)| id).collect(),
+                end_cell,
+            }
+        })
+        .collect();
+
+    lanes.sort_by(|a, b| a.owner.cmp(&b.owner));
+    lanes
+}
+
+// ============================================================================
+// Filter Parameters (bp6-07y.5.1)
+// ============================================================================
+
+/// FilterParams contains all filter and display parameters passed from frontend.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FilterParams {
+    /// Search query, e.g. `status:open owner:alice "exact phrase"`
+    #[serde(default)]
+    pub filter_text: String,
+
+    #[serde(default)]
+    pub hide_closed: bool,
+
+    #[serde(default)]
+    pub closed_time_filter: ClosedTimeFilter,
+
+    #[serde(default = "default_true")]
+    pub include_hierarchy: bool,
+
+    #[serde(default = "default_zoom")]
+    pub zoom: f64,
+
+    #[serde(default)]
+    pub collapsed_ids: Vec<String>,
+
+    #[serde(default)]
+    pub sort_by: SortBy,
+
+    #[serde(default)]
+    pub sort_order: SortOrder,
+
+    /// Serialize tasks that share an owner (resource leveling)
+    #[serde(default)]
+    pub level_by_owner: bool,
+
+    /// Also bucket the header histogram by calendar week or sprint
+    #[serde(default)]
+    pub distribution_mode: DistributionMode,
+
+    /// Below this zoom the Gantt layout folds leaf tasks into summary bars
+    /// on their feature or epic; None never aggregates
+    #[serde(default)]
+    pub aggregate_below_zoom: Option<f64>,
+
+    /// Focus on one epic: only it, the beads below it and the beads outside
+    /// that block them go through the pipeline
+    #[serde(default)]
+    pub scope_root_id: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_zoom() -> f64 {
+    1.0
+}
+
+impl Default for FilterParams {
+    fn default() -> Self {
+        FilterParams {
+            filter_text: String::new(),
+            hide_closed: false,
+            closed_time_filter: ClosedTimeFilter::All,
+            include_hierarchy: true,
+            zoom: 1.0,
+            collapsed_ids: Vec::new(),
+            sort_by: SortBy::None,
+            sort_order: SortOrder::None,
+            level_by_owner: false,
+            distribution_mode: DistributionMode::Cell,
+            aggregate_below_zoom: None,
+            scope_root_id: None,
+        }
+    }
+}
+
+impl Default for ClosedTimeFilter {
+    fn default() -> Self {
+        ClosedTimeFilter::All
+    }
+}
+
+impl Default for SortOrder {
+    fn default() -> Self {
+        SortOrder::None
+    }
+}
+
+impl Default for SortBy {
+    fn default() -> Self {
+        SortBy::None
+    }
+}
+
+// ============================================================================
+// WBS Tree Building - Sort Siblings by Dependencies (bp6-07y.2.4)
+// ============================================================================
+
+/// Recursively sort sibling nodes using topological sort or explicit property sort.
+pub fn sort_wbs_tree_siblings(
+    mut tree: Vec<WBSNode>,
+    graph: &DependencyGraph,
+    sort_by: &SortBy,
+    sort_order: &SortOrder,
+) -> Vec<WBSNode> {
+    if *sort_by == SortBy::Manual {
+        // Ranked beads first by rank; the stable sort keeps the dependency
+        // order of the unranked ones after them
+        if tree.len() > 1 {
+            let sibling_ids: HashSet<String> = tree.iter().map(|n| n.bead.id.clone()).collect();
+            tree = topological_sort(tree, &sibling_ids, graph);
+        }
+        tree.sort_by_key(|n| bd::sort_rank(&n.bead).unwrap_or(i64::MAX));
+    } else if *sort_by != SortBy::None && *sort_order != SortOrder::None {
+        // If explicit sort is requested, use it
+        tree.sort_by(|a, b| {
+            let ord = match sort_by {
+                SortBy::Priority => a.bead.priority.cmp(&b.bead.priority),
+                SortBy::Title => a.bead.title.to_lowercase().cmp(&b.bead.title.to_lowercase()),
+                SortBy::Type => a.bead.issue_type.cmp(&b.bead.issue_type),
+                SortBy::Id => a.bead.id.cmp(&b.bead.id),
+                SortBy::Manual | SortBy::None => std::cmp::Ordering::Equal,
+            };
+
+            // Use ID as tie-breaker for stable sorting across runs
+            let ord = if ord == std::cmp::Ordering::Equal {
+                a.bead.id.cmp(&b.bead.id)
+            } else {
+                ord
+            };
+
+            if *sort_order == SortOrder::Desc {
+                ord.reverse()
+            } else {
+                ord
+            }
+        });
+    } else {
+        // Fallback to topological sort based on dependencies
+        let sibling_ids: HashSet<String> = tree.iter().map(|n| n.bead.id.clone()).collect();
+
+        // If only one node, no sorting needed
+        if tree.len() > 1 {
+            tree = topological_sort(tree, &sibling_ids, graph);
+        }
+    }
+
+    // Recursively sort children
+    for node in &mut tree {
+        if !node.children.is_empty() {
+            node.children = sort_wbs_tree_siblings(
+                std::mem::take(&mut node.children),
+                graph,
+                sort_by,
+                sort_order,
+            );
+        }
+    }
+
+    tree
+}
+
+// ============================================================================
+// Gantt Layout - Calculate Node Ranges (bp6-07y.3.2)
+// ============================================================================
+
+/// NodeRange represents the calculated position and width of a node in the Gantt chart.
+#[derive(Debug, Clone)]
+pub(crate) struct NodeRange {
+    pub(crate) x: f64,
+    pub(crate) width: f64,
+}
+
+/// Calculate position and width for each node in the tree.
+/// All values are in logical time units (NOT pixels).
+/// Leaf nodes: start at earliestStart, duration = 1 grid cell (10 time units) or estimate-based.
+/// Parent nodes: span from earliest child start to latest child end (rollup).
+pub(crate) fn calculate_node_ranges(
+    tree: &[WBSNode],
+    x_map: &HashMap<String, usize>,
+    range_cache: &mut HashMap<String, NodeRange>,
+) {
+    fn calc_range(
+        node: &WBSNode,
+        x_map: &HashMap<String, usize>,
+        range_cache: &mut HashMap<String, NodeRange>,
+    ) -> NodeRange {
+        // Return cached result if available
+        if let Some(range) = range_cache.get(&node.bead.id) {
+            return range.clone();
+        }
+
+        let range = if node.children.is_empty() {
+            // Leaf node: position in logical time units
+            let earliest_start = x_map.get(&node.bead.id).copied().unwrap_or(0) as f64;
+
+            // Duration: default to 10 time units (1 grid cell), or use estimate
+            // If estimate exists and is > 0, map it to time units (assume minutes, 1 time unit = 60 min)
+            let duration = if node.bead.is_milestone == Some(true) {
+                0.0  // Milestone: zero-width marker
+            } else if let Some(est) = node.bead.estimate {
+                if est > 0 {
+                    (est as f64 / 60.0).max(10.0)  // Convert minutes to time units, min 10 units (1 grid cell)
+                } else {
+                    10.0  // Zero estimate = milestone, but still give it width for now
+                }
+            } else {
+                10.0  // No estimate = 1 grid cell (10 time units)
+            };
+
+            NodeRange { x: earliest_start, width: duration }
+        } else {
+            // Parent node: spans children's ranges (rollup behavior)
+            let child_ranges: Vec<NodeRange> = node
+                .children
+                .iter()
+                .map(|child| calc_range(child, x_map, range_cache))
+                .collect();
+
+            if child_ranges.is_empty() {
+                // Fallback if somehow no children (shouldn't happen)
+                let earliest_start = x_map.get(&node.bead.id).copied().unwrap_or(0) as f64;
+                NodeRange { x: earliest_start, width: 10.0 }
+            } else {
+                // Start at earliest child's start, end at latest child's end
+                let min_x = child_ranges.iter().map(|r| r.x).fold(f64::INFINITY, f64::min);
+                let max_x = child_ranges
+                    .iter()
+                    .map(|r| r.x + r.width)
+                    .fold(f64::NEG_INFINITY, f64::max);
+
+                NodeRange {
+                    x: min_x,
+                    width: max_x - min_x,
+                }
+            }
+        };
+
+        range_cache.insert(node.bead.id.clone(), range.clone());
+        range
+    }
+
+    // Calculate ranges for all root nodes
+    for node in tree {
+        calc_range(node, x_map, range_cache);
+    }
+}
+
+// ============================================================================
+// Gantt Layout - Find Critical Path (bp6-07y.3.3)
+// ============================================================================
+
+/// Find critical path using longest path algorithm.
+/// Returns a set of node IDs that are on the critical path.
+pub fn find_critical_path(
+    beads: &[Bead],
+    successors_map: &HashMap<String, Vec<String>>,
+) -> HashSet<String> {
+    critical_path_chain(beads, successors_map).into_iter().collect()
+}
+
+/// The critical path in blocking order, starting from the bead with the
+/// longest chain of successors
+pub fn critical_path_chain(
+    beads: &[Bead],
+    successors_map: &HashMap<String, Vec<String>>,
+) -> Vec<String> {
+    if beads.is_empty() {
+        return Vec::new();
+    }
+
+    let mut max_dist_map: HashMap<String, usize> = HashMap::new();
+    let mut next_in_path: HashMap<String, String> = HashMap::new();
+
+    /// Recursively find maximum distance to furthest successor.
+    fn find_max_dist(
+        id: &str,
+        successors_map: &HashMap<String, Vec<String>>,
+        max_dist_map: &mut HashMap<String, usize>,
+        next_in_path: &mut HashMap<String, String>,
+    ) -> usize {
+        // Return memoized result if available
+        if let Some(&dist) = max_dist_map.get(id) {
+            return dist;
+        }
+
+        let succs = successors_map.get(id).cloned().unwrap_or_default();
+
+        if succs.is_empty() {
+            // No successors, distance is 0
+            max_dist_map.insert(id.to_string(), 0);
+            return 0;
+        }
+
+        // A bead already on the chain being searched counts as a leaf, which
+        // cuts blocking cycles instead of recursing forever
+        max_dist_map.insert(id.to_string(), 0);
+
+        let mut max_val = 0;
+        let mut best_succ = String::new();
+
+        for s in &succs {
+            let d = find_max_dist(s, successors_map, max_dist_map, next_in_path);
+            // Leaf successors are at distance 0 too, so take the first one
+            if d > max_val || best_succ.is_empty() {
+                max_val = d;
+                best_succ = s.clone();
+            }
+        }
+
+        let dist = max_val + 1;
+        max_dist_map.insert(id.to_string(), dist);
+        if !best_succ.is_empty() {
+            next_in_path.insert(id.to_string(), best_succ);
+        }
+
+        dist
+    }
+
+    // Find the global maximum distance (start of critical path)
+    let mut global_max = 0;
+    let mut start_node = String::new();
+
+    for bead in beads {
+        let d = find_max_dist(
+            &bead.id,
+            successors_map,
+            &mut max_dist_map,
+            &mut next_in_path,
+        );
+        if d > global_max {
+            global_max = d;
+            start_node = bead.id.clone();
+        }
+    }
+
+    // Without any blocking edge nothing is critical
+    if start_node.is_empty() {
+        return Vec::new();
+    }
+
+    // Reconstruct critical path
+    let mut critical_path_nodes: Vec<String> = Vec::new();
+    let mut curr = Some(start_node);
+
+    while let Some(node_id) = curr {
+        if critical_path_nodes.contains(&node_id) {
+            break;
+        }
+        curr = next_in_path.get(&node_id).cloned();
+        critical_path_nodes.push(node_id);
+    }
+
+    critical_path_nodes
+}
+
+// ============================================================================
+// Gantt Layout - Generate Gantt Items and Connectors (bp6-07y.3.4)
+// ============================================================================
+
+/// Generate GanttItems and GanttConnectors from the WBS tree and computed data.
+///
+/// With `aggregate` (zoomed far out) leaf tasks get no rows of their own: they
+/// are folded into a summary bar on their parent feature or epic, and their
+/// dependencies are drawn between the summary bars.
+pub(crate) fn generate_gantt_layout(
+    beads: &[Bead],
+    tree: &[WBSNode],
+    x_map: &HashMap<String, usize>,
+    range_cache: &HashMap<String, NodeRange>,
+    critical_path: &HashSet<String>,
+    zoom: f64,
+    aggregate: bool,
+) -> GanttLayout {
+    let mut items: Vec<GanttItem> = Vec::new();
+    let mut connectors: Vec<GanttConnector> = Vec::new();
+
+    // Flatten tree to get visible rows and depths
+    let mut visible_rows: Vec<String> = Vec::new();
+    let mut row_depths: Vec<usize> = Vec::new();
+    // Folded leaf ID -> ID of the summary bar it is drawn in
+    let mut folded_into: HashMap<String, String> = HashMap::new();
+
+    fn flatten_tree(
+        nodes: &[WBSNode],
+        depth: usize,
+        parent: Option<&str>,
+        aggregate: bool,
+        visible_rows: &mut Vec<String>,
+        row_depths: &mut Vec<usize>,
+        folded_into: &mut HashMap<String, String>,
+    ) {
+        for node in nodes {
+            if let (true, true, Some(parent)) = (aggregate, node.children.is_empty(), parent) {
+                folded_into.insert(node.bead.id.clone(), parent.to_string());
+                continue;
+            }
+            visible_rows.push(node.bead.id.clone());
+            row_depths.push(depth);
+            if node.is_expanded {
+                flatten_tree(&node.children, depth + 1, Some(&node.bead.id), aggregate, visible_rows, row_depths, folded_into);
+            }
+        }
+    }
+
+    flatten_tree(tree, 0, None, aggregate, &mut visible_rows, &mut row_depths, &mut folded_into);
+
+    let row_count = visible_rows.len();
+
+    // Create row map for quick lookup
+    let row_map: HashMap<String, usize> = visible_rows
+        .iter()
+        .enumerate()
+        .map(|(idx, id)| (id.clone(), idx))
+        .collect();
+
+    // Create depth map
+    let depth_map: HashMap<String, usize> = visible_rows
+        .iter()
+        .zip(row_depths.iter())
+        .map(|(id, &depth)| (id.clone(), depth))
+        .collect();
+
+    // Helper to check if a bead is blocked
+    let is_blocked = |bead: &Bead| -> bool {
+        bead.dependencies
+            .iter()
+            .filter(|d| d.r#type == "blocks")
+            .any(|d| {
+                beads
+                    .iter()
+                    .find(|b| b.id == d.depends_on_id)
+                    .map(|pred| pred.status != "closed")
+                    .unwrap_or(false)
+            })
+    };
+
+    // Tally the folded leaves of each summary bar
+    let mut summaries: HashMap<&str, GanttSummary> = HashMap::new();
+    for bead in beads {
+        let Some(summary_id) = folded_into.get(&bead.id) else { continue };
+        let summary = summaries.entry(summary_id.as_str()).or_default();
+        summary.count += 1;
+        *summary.status_counts.entry(bead.status.clone()).or_insert(0) += 1;
+        summary.critical_count += usize::from(critical_path.contains(&bead.id));
+        summary.blocked_count += usize::from(is_blocked(bead));
+    }
+
+    // Generate GanttItems
+    for bead in beads {
+        let row = match row_map.get(&bead.id) {
+            Some(&r) => r,
+            None => continue, // Bead not visible in tree
+        };
+
+        // Get range from cache or calculate fallback
+        let range = range_cache.get(&bead.id).cloned().unwrap_or_else(|| {
+            let earliest_start = x_map.get(&bead.id).copied().unwrap_or(0);
+            let x = (earliest_start * 100 + 40) as f64;
+            let estimate = bead.estimate.unwrap_or(600);
+            let width = (estimate as f64 / 10.0).max(40.0);
+            NodeRange { x, width }
+        });
+
+        // Apply zoom factor
+        let x = range.x * zoom;
+        let is_milestone = bead.is_milestone == Some(true);
+        let width = if is_milestone { 0.0 } else { range.width * zoom };
+
+        items.push(GanttItem {
+            bead: bead.clone(),
+            x,
+            width,
+            row,
+            depth: *depth_map.get(&bead.id).unwrap_or(&0),
+            is_critical: critical_path.contains(&bead.id),
+            is_blocked: is_blocked(bead),
+            is_milestone,
+            summary: summaries.remove(bead.id.as_str()),
+        });
+    }
+
+    // The row a bead is drawn in: its own, or its summary bar's
+    fn anchor<'a>(
+        id: &'a str,
+        folded_into: &'a HashMap<String, String>,
+        row_map: &HashMap<String, usize>,
+    ) -> Option<(&'a str, usize)> {
+        let anchor_id = folded_into.get(id).map(String::as_str).unwrap_or(id);
+        row_map.get(anchor_id).map(|&row| (anchor_id, row))
+    }
+    let mut drawn: HashSet<(&str, &str)> = HashSet::new();
+
+    // Generate GanttConnectors
+    for bead in beads {
+        let (anchor_id, row) = match anchor(&bead.id, &folded_into, &row_map) {
+            Some(found) => found,
+            None => continue,
+        };
+
+        let range = range_cache.get(anchor_id).cloned().unwrap_or_else(|| {
+            let earliest_start = x_map.get(anchor_id).copied().unwrap_or(0);
+            let x = (earliest_start * 100 + 40) as f64;
+            let estimate = bead.estimate.unwrap_or(600);
+            let _width = (estimate as f64 / 10.0).max(40.0);
+            NodeRange { x, width: _width }
+        });
+
+        let x = range.x * zoom;
+        let _width = range.width * zoom;
+
+        // Create connectors for blocking dependencies
+        for dep in &bead.dependencies {
+            if dep.r#type != "blocks" {
+                continue;
+            }
+
+            let pred_id = &dep.depends_on_id;
+            let (pred_anchor, pred_row) = match anchor(pred_id, &folded_into, &row_map) {
+                Some(found) => found,
+                None => continue,
+            };
+            // Dependencies inside a summary bar, or repeated between two, draw nothing new
+            if aggregate && (pred_anchor == anchor_id || !drawn.insert((pred_anchor, anchor_id))) {
+                continue;
+            }
+
+            let pred_range = range_cache.get(pred_anchor).cloned().unwrap_or_else(|| {
+                let earliest_start = x_map.get(pred_anchor).copied().unwrap_or(0);
+                let x = (earliest_start * 100 + 40) as f64;
+                let pred_bead = beads.iter().find(|b| b.id == pred_anchor);
+                let estimate = pred_bead.and_then(|b| b.estimate).unwrap_or(600);
+                let width = (estimate as f64 / 10.0).max(40.0);
+                NodeRange { x, width }
+            });
+
+            let pred_x = pred_range.x * zoom;
+            let pred_width = pred_range.width * zoom;
+
+            connectors.push(GanttConnector {
+                from: Point {
+                    x: pred_x + pred_width,
+                    y: (pred_row * 48 + 24) as f64,
+                },
+                to: Point {
+                    x,
+                    y: (row * 48 + 24) as f64,
+                },
+                is_critical: critical_path.contains(&bead.id) && critical_path.contains(pred_id),
+            });
+        }
+    }
+
+    GanttLayout {
+        items,
+        connectors,
+        row_count,
+        row_depths,
+        owner_lanes: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use serde_json::json;
+
+    const STATUSES: [&str; 4] = ["open", "in_progress", "blocked", "closed"];
+
+    /// Bead `i` with an optional parent and blockers, all given as indices
+    fn bead(i: usize, status: &str, issue_type: &str, parent: Option<usize>, blockers: &[usize]) -> Bead {
+        let mut deps: Vec<_> = blockers
+            .iter()
+            .map(|b| json!({"issue_id": format!("b-{}", i), "depends_on_id": format!("b-{}", b), "type": "blocks"}))
+            .collect();
+        if let Some(p) = parent {
+            deps.push(json!({"issue_id": format!("b-{}", i), "depends_on_id": format!("b-{}", p), "type": "parent-child"}));
+        }
+        crate::test_bead(json!({
+            "id": format!("b-{}", i),
+            "title": format!("Bead {}", i),
+            "status": status,
+            "priority": i % 5,
+            "issue_type": issue_type,
+            "estimate": 60 * (i % 4),
+            "dependencies": deps,
+        }))
+    }
+
+    /// Projects of up to 30 beads where each parent and blocker comes
+    /// earlier in the list, so neither hierarchy nor blocking has cycles
+    fn project() -> impl Strategy<Value = Vec<Bead>> {
+        use prop::sample::Index;
+        let spec = (any::<Index>(), prop::option::of(any::<Index>()), prop::collection::vec(any::<Index>(), 0..3), 0..3usize);
+        prop::collection::vec(spec, 1..30)
+            .prop_map(|specs| {
+                specs
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, (status, parent, blockers, kind))| {
+                        let parent = parent.filter(|_| i > 0).map(|p| p.index(i));
+                        let mut blockers: Vec<usize> = if i > 0 { blockers.iter().map(|b| b.index(i)).collect() } else { Vec::new() };
+                        blockers.sort_unstable();
+                        blockers.dedup();
+                        let issue_type = ["task", "bug", "feature"][kind];
+                        bead(i, STATUSES[status.index(STATUSES.len())], issue_type, parent, &blockers)
+                    })
+                    .collect()
+            })
+    }
+
+    fn flatten_ids(nodes: &[WBSNode], out: &mut Vec<String>) {
+        for node in nodes {
+            out.push(node.bead.id.clone());
+            flatten_ids(&node.children, out);
+        }
+    }
+
+    fn parent_id(bead: &Bead) -> Option<&str> {
+        bead.dependencies.iter().find(|d| d.r#type == "parent-child").map(|d| d.depends_on_id.as_str())
+    }
+
+    /// Blockers and successors of each bead, as `process_beads` builds them
+    fn blocking_maps(beads: &[Bead]) -> (HashMap<String, Vec<String>>, HashMap<String, Vec<String>>) {
+        let mut blocks_map: HashMap<String, Vec<String>> = HashMap::new();
+        let mut successors_map: HashMap<String, Vec<String>> = HashMap::new();
+        for bead in beads {
+            for dep in bead.dependencies.iter().filter(|d| d.r#type == "blocks") {
+                blocks_map.entry(bead.id.clone()).or_default().push(dep.depends_on_id.clone());
+                successors_map.entry(dep.depends_on_id.clone()).or_default().push(bead.id.clone());
+            }
+        }
+        (blocks_map, successors_map)
+    }
+
+    proptest! {
+        #[test]
+        fn wbs_tree_contains_every_bead_once_under_its_parent(beads in project()) {
+            let tree = build_wbs_tree(&beads);
+
+            let mut ids = Vec::new();
+            flatten_ids(&tree, &mut ids);
+            let mut expected: Vec<String> = beads.iter().map(|b| b.id.clone()).collect();
+            ids.sort();
+            expected.sort();
+            prop_assert_eq!(ids, expected);
+
+            fn check(nodes: &[WBSNode], parent: Option<&str>) -> Result<(), TestCaseError> {
+                for node in nodes {
+                    prop_assert_eq!(parent_id(&node.bead), parent);
+                    check(&node.children, Some(&node.bead.id))?;
+                }
+                Ok(())
+            }
+            check(&tree, None)?;
+        }
+
+        #[test]
+        fn topological_sort_puts_blockers_first(beads in project()) {
+            // Flatten the hierarchy so every bead is a sibling
+            let beads: Vec<Bead> = beads
+                .into_iter()
+                .map(|mut b| {
+                    b.dependencies.retain(|d| d.r#type == "blocks");
+                    b
+                })
+                .collect();
+            let graph = build_dependency_graph(&beads);
+            let ids: HashSet<String> = beads.iter().map(|b| b.id.clone()).collect();
+            let sorted = topological_sort(build_wbs_tree(&beads), &ids, &graph);
+
+            let position: HashMap<&str, usize> = sorted.iter().enumerate().map(|(i, n)| (n.bead.id.as_str(), i)).collect();
+            prop_assert_eq!(position.len(), beads.len());
+            for bead in &beads {
+                for dep in &bead.dependencies {
+                    prop_assert!(position[dep.depends_on_id.as_str()] < position[bead.id.as_str()]);
+                }
+            }
+        }
+
+        #[test]
+        fn topological_sort_keeps_every_node_with_cycles(edges in prop::collection::vec((0..8usize, 0..8usize), 0..20)) {
+            let beads: Vec<Bead> = (0..8)
+                .map(|i| {
+                    let blockers: Vec<usize> = edges.iter().filter(|(to, from)| *to == i && from != to).map(|(_, from)| *from).collect();
+                    bead(i, "open", "task", None, &blockers)
+                })
+                .collect();
+            let graph = build_dependency_graph(&beads);
+            let ids: HashSet<String> = beads.iter().map(|b| b.id.clone()).collect();
+            let mut sorted: Vec<String> = topological_sort(build_wbs_tree(&beads), &ids, &graph)
+                .into_iter()
+                .map(|n| n.bead.id)
+                .collect();
+            sorted.sort();
+            let mut expected: Vec<String> = ids.into_iter().collect();
+            expected.sort();
+            prop_assert_eq!(sorted, expected);
+        }
+
+        #[test]
+        fn earliest_start_follows_every_blocker(beads in project(), level_by_owner in any::<bool>()) {
+            let (blocks_map, _) = blocking_maps(&beads);
+            let x_map = calculate_earliest_start_times(&beads, &blocks_map, level_by_owner);
+
+            prop_assert_eq!(x_map.len(), beads.len());
+            for bead in &beads {
+                let blockers = blocks_map.get(&bead.id).map(Vec::as_slice).unwrap_or_default();
+                for blocker in blockers {
+                    prop_assert!(x_map[&bead.id] > x_map[blocker], "{} starts before its blocker {}", bead.id, blocker);
+                }
+                // Without leveling, a bead starts right after its latest blocker
+                if !level_by_owner {
+                    let expected = blockers.iter().map(|b| x_map[b] + 1).max().unwrap_or(0);
+                    prop_assert_eq!(x_map[&bead.id], expected);
+                }
+            }
+        }
+
+        #[test]
+        fn parent_ranges_span_their_children(beads in project()) {
+            let (blocks_map, _) = blocking_maps(&beads);
+            let x_map = calculate_earliest_start_times(&beads, &blocks_map, false);
+            let tree = build_wbs_tree(&beads);
+            let mut range_cache = HashMap::new();
+            calculate_node_ranges(&tree, &x_map, &mut range_cache);
+
+            fn check(nodes: &[WBSNode], ranges: &HashMap<String, NodeRange>) -> Result<(), TestCaseError> {
+                for node in nodes {
+                    let parent = &ranges[&node.bead.id];
+                    prop_assert!(parent.width >= 0.0);
+                    for child in &node.children {
+                        let range = &ranges[&child.bead.id];
+                        prop_assert!(parent.x <= range.x, "{} starts after its child {}", node.bead.id, child.bead.id);
+                        prop_assert!(parent.x + parent.width >= range.x + range.width, "{} ends before its child {}", node.bead.id, child.bead.id);
+                    }
+                    check(&node.children, ranges)?;
+                }
+                Ok(())
+            }
+            prop_assert_eq!(range_cache.len(), beads.len());
+            check(&tree, &range_cache)?;
+        }
+
+        #[test]
+        fn critical_path_is_a_longest_blocking_chain(beads in project()) {
+            let (blocks_map, successors_map) = blocking_maps(&beads);
+            let chain = critical_path_chain(&beads, &successors_map);
+
+            let ids: HashSet<&str> = beads.iter().map(|b| b.id.as_str()).collect();
+            prop_assert!(chain.iter().all(|id| ids.contains(id.as_str())));
+            // Each bead blocks the next one
+            for pair in chain.windows(2) {
+                prop_assert!(successors_map.get(&pair[0]).is_some_and(|s| s.contains(&pair[1])), "{} does not block {}", pair[0], pair[1]);
+            }
+            // The projects are acyclic, so the unleveled start of a bead is the
+            // length of the longest chain leading to it; a lone bead is no chain
+            let x_map = calculate_earliest_start_times(&beads, &blocks_map, false);
+            let longest = x_map.values().max().copied().unwrap_or(0) + 1;
+            prop_assert_eq!(chain.len(), if longest > 1 { longest } else { 0 });
+            prop_assert_eq!(find_critical_path(&beads, &successors_map).len(), chain.len());
+        }
+
+        #[test]
+        fn distribution_counts_each_task_cell_once(beads in project()) {
+            let workflow = WorkflowConfig::default();
+            let view = build_project_view_model(beads, &FilterParams::default(), &schedule::SchedulingConfig::default(), &workflow);
+
+            fn tasks<'a>(nodes: &'a [BeadNode], out: &mut Vec<&'a BeadNode>) {
+                for node in nodes {
+                    if node.issue_type != "epic" && node.issue_type != "feature" {
+                        out.push(node);
+                    }
+                    tasks(&node.children, out);
+                }
+            }
+            let mut task_nodes = Vec::new();
+            tasks(&view.tree, &mut task_nodes);
+            let cells = |category: StatusCategory| -> usize {
+                task_nodes.iter().filter(|n| workflow.category_of(&n.status) == category).map(|n| n.cell_count).sum()
+            };
+
+            let distributions = &view.metadata.distributions;
+            prop_assert_eq!(distributions.iter().map(|d| d.open).sum::<usize>(), cells(StatusCategory::Todo));
+            prop_assert_eq!(distributions.iter().map(|d| d.in_progress).sum::<usize>(), cells(StatusCategory::Doing));
+            prop_assert_eq!(distributions.iter().map(|d| d.closed).sum::<usize>(), cells(StatusCategory::Done));
+            // Blocked is counted alongside the status, so it can't exceed the task cells
+            let total_cells: usize = task_nodes.iter().map(|n| n.cell_count).sum();
+            prop_assert!(distributions.iter().map(|d| d.blocked).sum::<usize>() <= total_cells);
+        }
+    }
+
+    /// `get_processed_data` output for the projects in fixtures/layout must
+    /// match their golden files (see the README there)
+    #[test]
+    fn test_processed_data_matches_golden_files() {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures").join("layout");
+        let update = std::env::var_os("UPDATE_GOLDEN").is_some();
+        let mut fixtures: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+            .collect();
+        fixtures.sort();
+        assert!(!fixtures.is_empty(), "No fixtures in {}", dir.display());
+
+        for fixture in fixtures {
+            let beads = bd::parse_beads(&std::fs::read(&fixture).unwrap()).unwrap();
+            let mut processed =
+                process_beads(beads, &FilterParams::default(), layout::LayoutKind::Gantt, &WorkflowConfig::default());
+            // Durations differ on every run
+            processed.timings.stages.clear();
+            processed.timings.total_ms = 0.0;
+            let actual = serde_json::to_value(&processed).unwrap();
+
+            let golden = fixture.with_extension("golden.json");
+            if update || !golden.exists() {
+                std::fs::write(&golden, serde_json::to_string_pretty(&actual).unwrap() + "\n").unwrap();
+                eprintln!("Wrote {}", golden.display());
+                continue;
+            }
+            let expected: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&golden).unwrap()).unwrap();
+            assert!(
+                actual == expected,
+                "{} no longer matches {}; if the change is intended, rerun with UPDATE_GOLDEN=1 and review the diff",
+                fixture.display(),
+                golden.display()
+            );
+        }
+    }
+
+    #[test]
+    fn test_low_zoom_folds_leaf_tasks_into_summary_bars() {
+        // Epic 0 holds features 1 and 7 and task 5; 6 is a loose task.
+        // Task 8 under feature 7 waits on task 3 under feature 1.
+        let beads = vec![
+            bead(0, "open", "epic", None, &[]),
+            bead(1, "open", "feature", Some(0), &[]),
+            bead(2, "open", "task", Some(1), &[]),
+            bead(3, "closed", "task", Some(1), &[]),
+            bead(4, "open", "task", Some(1), &[2]),
+            bead(5, "open", "task", Some(0), &[]),
+            bead(6, "open", "task", None, &[]),
+            bead(7, "open", "feature", Some(0), &[]),
+            bead(8, "open", "task", Some(7), &[3]),
+        ];
+        let layout_at = |zoom| {
+            let params = FilterParams { zoom, aggregate_below_zoom: Some(0.5), ..FilterParams::default() };
+            process_beads(beads.clone(), &params, layout::LayoutKind::Gantt, &WorkflowConfig::default()).layout
+        };
+
+        let full = layout_at(1.0);
+        assert_eq!(full.items.len(), 9);
+        assert!(full.items.iter().all(|i| i.summary.is_none()));
+
+        let folded = layout_at(0.25);
+        let mut rows: Vec<&str> = folded.items.iter().map(|i| i.bead.id.as_str()).collect();
+        rows.sort();
+        assert_eq!(rows, vec!["b-0", "b-1", "b-6", "b-7"]);
+        assert_eq!(folded.row_count, 4);
+
+        let summary = |id: &str| folded.items.iter().find(|i| i.bead.id == id).unwrap().summary.clone();
+        let feature = summary("b-1").unwrap();
+        assert_eq!(feature.count, 3);
+        assert_eq!(feature.status_counts, BTreeMap::from([("closed".to_string(), 1), ("open".to_string(), 2)]));
+        assert_eq!(feature.blocked_count, 1);
+        assert_eq!(summary("b-0").unwrap().count, 1);
+        assert!(summary("b-6").is_none());
+
+        // Only the dependency between the two features is left to draw
+        assert_eq!(folded.connectors.len(), 1);
+    }
+
+    #[test]
+    fn test_scope_root_limits_the_view_to_one_epic_and_its_blockers() {
+        let beads = vec![
+            bead(0, "open", "epic", None, &[]),
+            bead(1, "open", "feature", Some(0), &[]),
+            bead(2, "open", "task", Some(1), &[3]),
+            bead(3, "open", "task", Some(4), &[]),
+            bead(4, "open", "epic", None, &[]),
+            bead(5, "open", "task", Some(4), &[]),
+        ];
+        let view = |root: &str, filter_text: &str| {
+            let params = FilterParams { scope_root_id: Some(root.to_string()), filter_text: filter_text.to_string(), ..FilterParams::default() };
+            build_project_view_model(beads.clone(), &params, &schedule::SchedulingConfig::default(), &WorkflowConfig::default())
+        };
+        fn ids(nodes: &[BeadNode], out: &mut Vec<String>) {
+            for node in nodes {
+                out.push(node.id.clone());
+                ids(&node.children, out);
+            }
+        }
+
+        let scoped = view("b-0", "");
+        let mut roots: Vec<&str> = scoped.tree.iter().map(|n| n.id.as_str()).collect();
+        roots.sort();
+        assert_eq!(roots, ["b-0", "b-3"]);
+        let mut all = Vec::new();
+        ids(&scoped.tree, &mut all);
+        all.sort();
+        assert_eq!(all, ["b-0", "b-1", "b-2", "b-3"]);
+
+        // Ancestors of search hits stop at the scope root
+        let mut all = Vec::new();
+        ids(&view("b-1", "\"Bead 2\"").tree, &mut all);
+        assert_eq!(all, ["b-1", "b-2"]);
+    }
+
+    #[test]
+    fn test_owner_leveling_serializes_leaf_tasks() {
+        // b-0 and b-1 are ann's tasks under ann's epic b-2; b-3 is unowned;
+        // zoe's b-4 waits for b-1
+        let mut beads = vec![
+            bead(0, "open", "task", Some(2), &[]),
+            bead(1, "open", "task", Some(2), &[]),
+            bead(2, "open", "epic", None, &[]),
+            bead(3, "open", "task", None, &[]),
+            bead(4, "open", "task", None, &[1]),
+        ];
+        for bead in &mut beads[..3] {
+            bead.owner = Some("ann".to_string());
+        }
+        beads[4].owner = Some("zoe".to_string());
+        let (blocks_map, _) = blocking_maps(&beads);
+
+        let unleveled = calculate_earliest_start_times(&beads, &blocks_map, false);
+        assert_eq!((unleveled["b-0"], unleveled["b-1"]), (0, 0));
+
+        let x_map = calculate_earliest_start_times(&beads, &blocks_map, true);
+        // Same owner, both unblocked: one waits for the other
+        assert_eq!((x_map["b-0"], x_map["b-1"]), (0, 1));
+        // The parent and the unowned task keep their dependency-only start
+        assert_eq!((x_map["b-2"], x_map["b-3"]), (0, 0));
+        // A blocker that moved pushes its successor along
+        assert_eq!(x_map["b-4"], 2);
+
+        let lanes = build_owner_lanes(&beads, &x_map);
+        let summary: Vec<(&str, Vec<&str>, usize)> =
+            lanes.iter().map(|l| (l.owner.as_str(), l.bead_ids.iter().map(String::as_str).collect(), l.end_cell)).collect();
+        assert_eq!(summary, vec![("ann", vec!["b-0", "b-1"], 2), ("zoe", vec!["b-4"], 3)]);
+    }
+
+    #[test]
+    fn test_dated_distribution_buckets_by_week_and_sprint() {
+        let mut done = bead(0, "closed", "task", None, &[]);
+        done.created_at = Some("2026-10-01T10:00:00Z".to_string());
+        done.closed_at = Some("2026-10-14T16:00:00Z".to_string());
+        let beads = vec![done, bead(1, "open", "task", None, &[]), bead(2, "open", "feature", None, &[])];
+        let scheduling = schedule::SchedulingConfig {
+            start_date: Some("2026-10-19".to_string()),
+            ..schedule::SchedulingConfig::default()
+        };
+        let view = |mode| {
+            let params = FilterParams { distribution_mode: mode, ..FilterParams::default() };
+            build_project_view_model(beads.clone(), &params, &scheduling, &WorkflowConfig::default()).metadata
+        };
+
+        assert!(view(DistributionMode::Cell).dated_distributions.is_empty());
+
+        let weeks = view(DistributionMode::Week).dated_distributions;
+        let summary: Vec<(&str, usize, usize)> = weeks.iter().map(|b| (b.start_date.as_str(), b.counts.open, b.counts.closed)).collect();
+        assert_eq!(summary, vec![("2026-09-28", 0, 1), ("2026-10-05", 0, 1), ("2026-10-12", 0, 1), ("2026-10-19", 1, 0)]);
+        assert_eq!(weeks[0].label, "Week of 28 Sep");
+        assert_eq!(weeks[0].end_date, "2026-10-04");
+
+        let sprints = view(DistributionMode::Sprint).dated_distributions;
+        let summary: Vec<(&str, &str, usize, usize)> =
+            sprints.iter().map(|b| (b.label.as_str(), b.start_date.as_str(), b.counts.open, b.counts.closed)).collect();
+        assert_eq!(
+            summary,
+            vec![("Sprint -1", "2026-09-21", 0, 1), ("Sprint 0", "2026-10-05", 0, 1), ("Sprint 1", "2026-10-19", 1, 0)]
+        );
+    }
+}
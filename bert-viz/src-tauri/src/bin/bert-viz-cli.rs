@@ -0,0 +1,3 @@
+fn main() -> std::process::ExitCode {
+    bert_viz_lib::cli::main()
+}
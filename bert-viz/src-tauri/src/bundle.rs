@@ -0,0 +1,307 @@
+//! Snapshot bundles: one-file backup, restore and sharing
+//!
+//! `export_snapshot` packs a project into a single gzip-compressed JSON file:
+//! its beads, the comment threads under `.beads/comments`, the project config,
+//! its schedule baselines and its tracked time. `import_snapshot` restores a
+//! bundle into a directory as a regular project (a backup, or moving to
+//! another machine); bd imports the restored issues.jsonl the first time it
+//! runs there. Opening a bundle instead shows it read-only (see `viewer`).
+//!
+//! bp6 keeps no attachments of its own; files linked from beads stay where
+//! the links point. Version 1 bundles (uncompressed, beads and config only)
+//! still open and import.
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::baseline::Baseline;
+use crate::error::BertError;
+use crate::project::{context_for, ProjectContext};
+use crate::schedule::config_path_for;
+use crate::timetracking::TimeEntry;
+use crate::Bead;
+
+const SNAPSHOT_FORMAT: &str = "bp6-snapshot";
+const SNAPSHOT_VERSION: u32 = 2;
+
+/// First bytes of a gzip stream
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// What a snapshot is of
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotInfo {
+    pub project_name: String,
+    /// RFC 3339
+    pub exported_at: String,
+    pub bead_count: usize,
+    /// The bundle the snapshot was opened from; empty inside the bundle
+    #[serde(default)]
+    pub source: String,
+}
+
+/// The exported file
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SnapshotBundle {
+    format: String,
+    version: u32,
+    #[serde(flatten)]
+    pub info: SnapshotInfo,
+    pub beads: Vec<Bead>,
+    /// Contents of `.beads/bert-viz.json` (workflow, scheduling, label colors, ...)
+    #[serde(default)]
+    pub config: Map<String, Value>,
+    /// Comment files of `.beads/comments` by file name, as stored
+    #[serde(default)]
+    pub comments: BTreeMap<String, String>,
+    #[serde(default)]
+    pub baselines: Vec<Baseline>,
+    #[serde(default)]
+    pub time_entries: Vec<TimeEntry>,
+}
+
+impl SnapshotBundle {
+    pub fn new(project_name: &str, beads: Vec<Bead>, config: Map<String, Value>) -> Self {
+        SnapshotBundle {
+            format: SNAPSHOT_FORMAT.to_string(),
+            version: SNAPSHOT_VERSION,
+            info: SnapshotInfo {
+                project_name: project_name.to_string(),
+                exported_at: chrono::Utc::now().to_rfc3339(),
+                bead_count: beads.len(),
+                source: String::new(),
+            },
+            beads,
+            config,
+            comments: BTreeMap::new(),
+            baselines: Vec::new(),
+            time_entries: Vec::new(),
+        }
+    }
+}
+
+/// What `import_snapshot` restored
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSummary {
+    pub project_path: String,
+    pub project_name: String,
+    pub bead_count: usize,
+    pub comment_threads: usize,
+    pub baselines: usize,
+    pub time_entries: usize,
+}
+
+/// Collect everything of the project that goes into a bundle
+fn pack(ctx: &ProjectContext) -> Result<SnapshotBundle, BertError> {
+    let beads_path = ctx.beads_path()?;
+    let beads: Vec<Bead> = crate::bd::load_beads(ctx)?.into_iter().filter(|b| b.status != "tombstone").collect();
+    let config_path = config_path_for(&beads_path);
+    let config = match fs::read_to_string(&config_path) {
+        Ok(contents) => serde_json::from_str(&contents)
+            .map_err(|e| BertError::Parse(format!("Failed to parse {}: {}", config_path.display(), e)))?,
+        Err(_) => Map::new(),
+    };
+    // A snapshot of a snapshot keeps the original project name
+    let project_name = match crate::viewer::snapshot_of(ctx.project_path()) {
+        Some(info) => info.project_name,
+        None => crate::project_name(&ctx.project_path().to_string_lossy()),
+    };
+
+    let mut bundle = SnapshotBundle::new(&project_name, beads, config);
+    if let Ok(entries) = fs::read_dir(crate::comments::comments_dir(&beads_path)) {
+        for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else { continue };
+            if name.ends_with(".jsonl") {
+                let contents = fs::read_to_string(&path).map_err(|e| BertError::Io(format!("Failed to read {}: {}", path.display(), e)))?;
+                bundle.comments.insert(name.to_string(), contents);
+            }
+        }
+    }
+    bundle.baselines = crate::baseline::all_baselines(&ctx.repo_root()?).map_err(BertError::Io)?;
+    bundle.time_entries = crate::timetracking::entries_for(&beads_path)?;
+    Ok(bundle)
+}
+
+fn encode(bundle: &SnapshotBundle) -> Result<Vec<u8>, BertError> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&serde_json::to_vec(bundle)?)?;
+    Ok(encoder.finish()?)
+}
+
+pub(crate) fn read_bundle(path: &Path) -> Result<SnapshotBundle, BertError> {
+    let bytes = fs::read(path).map_err(|e| BertError::Io(format!("Failed to read {}: {}", path.display(), e)))?;
+    let mut contents = String::new();
+    if bytes.starts_with(&GZIP_MAGIC) {
+        GzDecoder::new(&bytes[..])
+            .read_to_string(&mut contents)
+            .map_err(|e| BertError::Parse(format!("Failed to decompress {}: {}", path.display(), e)))?;
+    } else {
+        contents = String::from_utf8_lossy(&bytes).into_owned();
+    }
+
+    let bundle: SnapshotBundle = serde_json::from_str(&contents)
+        .map_err(|e| BertError::InvalidInput(format!("{} is not a project or a bp6 snapshot: {}", path.display(), e)))?;
+    if bundle.format != SNAPSHOT_FORMAT {
+        return Err(BertError::InvalidInput(format!("{} is not a bp6 snapshot", path.display())));
+    }
+    if bundle.version > SNAPSHOT_VERSION {
+        return Err(BertError::InvalidInput(format!(
+            "{} is a version {} snapshot; this version of bp6 reads up to version {}",
+            path.display(),
+            bundle.version,
+            SNAPSHOT_VERSION
+        )));
+    }
+    Ok(bundle)
+}
+
+/// Write the bundle's `.beads` contents into project directory `dir`: beads,
+/// the config minus the sections named in `skip_config`, and comments
+pub(crate) fn write_project(bundle: &SnapshotBundle, dir: &Path, skip_config: &[&str]) -> Result<(), BertError> {
+    let beads_dir = dir.join(".beads");
+    let comments_dir = crate::comments::comments_dir(&beads_dir.join("issues.jsonl"));
+    fs::create_dir_all(&comments_dir).map_err(|e| BertError::Io(format!("Failed to create {}: {}", comments_dir.display(), e)))?;
+
+    let objects = bundle.beads.iter().map(crate::bd::bead_to_jsonl_object).collect::<Result<Vec<_>, _>>()?;
+    crate::bd::write_jsonl_atomic(&beads_dir.join("issues.jsonl"), &objects)?;
+
+    let mut config = bundle.config.clone();
+    config.retain(|key, _| !skip_config.contains(&key.as_str()));
+    if !config.is_empty() {
+        let path = config_path_for(&beads_dir.join("issues.jsonl"));
+        fs::write(&path, serde_json::to_string_pretty(&config)?)
+            .map_err(|e| BertError::Io(format!("Failed to write {}: {}", path.display(), e)))?;
+    }
+
+    for (name, contents) in &bundle.comments {
+        // Names come from the file; don't let one escape the comments directory
+        let safe = crate::baseline::sanitize_file_name(name.trim_end_matches(".jsonl"));
+        let path = comments_dir.join(format!("{}.jsonl", safe));
+        fs::write(&path, contents).map_err(|e| BertError::Io(format!("Failed to write {}: {}", path.display(), e)))?;
+    }
+    Ok(())
+}
+
+/// Write the current project to a compressed snapshot bundle at `path`.
+/// Returns the number of beads exported.
+#[tauri::command]
+pub fn export_snapshot(path: String, window: tauri::Window, project_handle: Option<String>) -> Result<usize, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    let bundle = pack(&ctx)?;
+    fs::write(&path, encode(&bundle)?).map_err(|e| BertError::Io(format!("Failed to write {}: {}", path, e)))?;
+    eprintln!(
+        "📦 Exported a snapshot of {} ({} beads, {} comment threads, {} baselines, {} time entries) to {}",
+        bundle.info.project_name,
+        bundle.info.bead_count,
+        bundle.comments.len(),
+        bundle.baselines.len(),
+        bundle.time_entries.len(),
+        path
+    );
+    Ok(bundle.info.bead_count)
+}
+
+/// Restore the bundle at `path` as a project in `target_dir`, which must not
+/// have a beads project yet
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn import_snapshot(path: String, targetDir: String) -> Result<ImportSummary, BertError> {
+    let bundle = read_bundle(Path::new(&path))?;
+    let target = PathBuf::from(&targetDir);
+    if target.join(".beads").join("issues.jsonl").exists() {
+        return Err(BertError::InvalidInput(format!("{} already has a beads project; pick an empty directory", targetDir)));
+    }
+
+    write_project(&bundle, &target, &[])?;
+    for baseline in &bundle.baselines {
+        let restored = Baseline { project_path: target.to_string_lossy().to_string(), ..baseline.clone() };
+        crate::baseline::store_baseline(&target, &restored).map_err(BertError::Io)?;
+    }
+    crate::timetracking::restore_entries(&target.join(".beads").join("issues.jsonl"), &bundle.time_entries)?;
+
+    let summary = ImportSummary {
+        project_path: target.to_string_lossy().to_string(),
+        project_name: bundle.info.project_name.clone(),
+        bead_count: bundle.beads.len(),
+        comment_threads: bundle.comments.len(),
+        baselines: bundle.baselines.len(),
+        time_entries: bundle.time_entries.len(),
+    };
+    eprintln!("📦 Restored the snapshot of {} to {}: {:?}", summary.project_name, targetDir, summary);
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn bead(id: &str, status: &str) -> Bead {
+        crate::test_bead(json!({
+            "id": id,
+            "status": status,
+            "acceptance_criteria": "Renders\nIs fast",
+        }))
+    }
+
+    #[test]
+    fn test_compressed_bundle_round_trips_and_restores() {
+        let dir = TempDir::new().unwrap();
+        let config = json!({ "workflow": { "statuses": [] }, "autoCommit": { "enabled": true } });
+        let mut bundle = SnapshotBundle::new("bp6", vec![bead("bp6-1", "open"), bead("bp6-2", "closed")], config.as_object().unwrap().clone());
+        bundle.comments.insert("bp6-1.jsonl".into(), "{\"id\":\"c1\"}\n".into());
+        bundle.comments.insert("../escape.jsonl".into(), "{}\n".into());
+        let file = dir.path().join("plan.bp6");
+        fs::write(&file, encode(&bundle).unwrap()).unwrap();
+
+        let read = read_bundle(&file).unwrap();
+        assert_eq!(read.info, bundle.info);
+        assert_eq!(read.comments.len(), 2);
+
+        let project = dir.path().join("restored");
+        write_project(&read, &project, &[]).unwrap();
+        let beads = crate::snapshot::read_beads(&project.join(".beads/issues.jsonl")).unwrap();
+        assert_eq!(beads.iter().map(|b| b.id.as_str()).collect::<Vec<_>>(), vec!["bp6-1", "bp6-2"]);
+        assert_eq!(beads[0].acceptance_criteria, Some(vec!["Renders".to_string(), "Is fast".to_string()]));
+        let config: Value = serde_json::from_str(&fs::read_to_string(project.join(".beads/bert-viz.json")).unwrap()).unwrap();
+        assert!(config.get("autoCommit").is_some());
+        assert_eq!(fs::read_to_string(project.join(".beads/comments/bp6-1.jsonl")).unwrap(), "{\"id\":\"c1\"}\n");
+        assert!(!dir.path().join("escape.jsonl").exists());
+    }
+
+    #[test]
+    fn test_reads_version_1_and_rejects_others() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("plan.json");
+        let v1 = json!({
+            "format": "bp6-snapshot",
+            "version": 1,
+            "projectName": "bp6",
+            "exportedAt": "2026-10-01T09:00:00Z",
+            "beadCount": 1,
+            "beads": [bead("bp6-1", "open")],
+            "config": {},
+        });
+        fs::write(&file, v1.to_string()).unwrap();
+        let read = read_bundle(&file).unwrap();
+        assert_eq!((read.beads.len(), read.comments.len(), read.time_entries.len()), (1, 0, 0));
+
+        fs::write(&file, r#"{ "hello": "world" }"#).unwrap();
+        assert_eq!(read_bundle(&file).unwrap_err().code(), "invalid_input");
+
+        let mut newer = serde_json::to_value(SnapshotBundle::new("bp6", Vec::new(), Map::new())).unwrap();
+        newer["version"] = json!(SNAPSHOT_VERSION + 1);
+        fs::write(&file, newer.to_string()).unwrap();
+        assert!(read_bundle(&file).unwrap_err().to_string().contains("version 3 snapshot"));
+    }
+}
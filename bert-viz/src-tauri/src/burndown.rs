@@ -0,0 +1,245 @@
+//! Burndown / burnup chart data
+//!
+//! Buckets bead `created_at`/`closed_at` timestamps over a date range so the
+//! frontend can draw burndown and cumulative-flow charts from a few dozen
+//! points instead of the raw bead list.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::Bead;
+
+/// Bucket size for the time axis
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BurndownBucket {
+    #[default]
+    Day,
+    Week,
+    Month,
+}
+
+/// Inclusive date range (YYYY-MM-DD). Missing bounds default to the earliest
+/// bead creation date and today.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BurndownRange {
+    #[serde(default)]
+    pub start: Option<String>,
+    #[serde(default)]
+    pub end: Option<String>,
+}
+
+/// Counts for a single bucket
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BurndownPoint {
+    /// First day of the bucket
+    pub start: String,
+    /// Last day of the bucket (clamped to the range end)
+    pub end: String,
+    /// Beads created within the bucket
+    pub created: usize,
+    /// Beads closed within the bucket
+    pub closed: usize,
+    /// Beads open at the end of the bucket
+    pub open_total: usize,
+    /// Beads closed at or before the end of the bucket
+    pub closed_total: usize,
+    /// Sum of estimates (minutes) of beads open at the end of the bucket
+    pub remaining_estimate: u64,
+    /// Sum of estimates (minutes) of beads closed at or before the end of the bucket
+    pub completed_estimate: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BurndownData {
+    pub bucket: BurndownBucket,
+    pub start: String,
+    pub end: String,
+    pub points: Vec<BurndownPoint>,
+}
+
+fn parse_timestamp(ts: &str) -> Option<NaiveDate> {
+    DateTime::parse_from_rfc3339(ts)
+        .map(|dt| dt.with_timezone(&Utc).date_naive())
+        .ok()
+        .or_else(|| NaiveDate::parse_from_str(ts, "%Y-%m-%d").ok())
+}
+
+fn parse_bound(raw: &Option<String>, label: &str) -> Result<Option<NaiveDate>, String> {
+    raw.as_deref()
+        .filter(|s| !s.trim().is_empty())
+        .map(|s| parse_timestamp(s.trim()).ok_or_else(|| format!("Invalid {} date '{}'", label, s)))
+        .transpose()
+}
+
+/// First day of the bucket after the one starting at `start`
+fn next_bucket_start(start: NaiveDate, bucket: BurndownBucket) -> NaiveDate {
+    match bucket {
+        BurndownBucket::Day => start + Duration::days(1),
+        BurndownBucket::Week => start + Duration::days(7),
+        BurndownBucket::Month => {
+            let (year, month) = if start.month() == 12 { (start.year() + 1, 1) } else { (start.year(), start.month() + 1) };
+            NaiveDate::from_ymd_opt(year, month, 1).unwrap_or(start + Duration::days(31))
+        }
+    }
+}
+
+/// Align a date to the start of its bucket (weeks start on Monday)
+fn align_to_bucket(date: NaiveDate, bucket: BurndownBucket) -> NaiveDate {
+    match bucket {
+        BurndownBucket::Day => date,
+        BurndownBucket::Week => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+        BurndownBucket::Month => date.with_day(1).unwrap_or(date),
+    }
+}
+
+/// Build bucketed burndown data for `beads` between `start` and `end` (inclusive)
+pub fn compute_burndown(beads: &[Bead], start: NaiveDate, end: NaiveDate, bucket: BurndownBucket) -> Vec<BurndownPoint> {
+    // (created, closed, estimate) per bead; beads without a creation date count from the start
+    let timeline: Vec<(NaiveDate, Option<NaiveDate>, u64)> = beads
+        .iter()
+        .map(|b| {
+            let created = b.created_at.as_deref().and_then(parse_timestamp).unwrap_or(start);
+            let closed = b.closed_at.as_deref().and_then(parse_timestamp).or_else(|| {
+                // Closed beads with no timestamp are treated as closed at creation
+                (b.status == "closed" || b.status == "done").then_some(created)
+            });
+            (created, closed, b.estimate.unwrap_or(0) as u64)
+        })
+        .collect();
+
+    let mut points = Vec::new();
+    let mut bucket_start = align_to_bucket(start, bucket);
+    while bucket_start <= end {
+        let next = next_bucket_start(bucket_start, bucket);
+        let bucket_end = (next - Duration::days(1)).min(end);
+        let display_start = bucket_start.max(start);
+
+        let mut point = BurndownPoint {
+            start: display_start.format("%Y-%m-%d").to_string(),
+            end: bucket_end.format("%Y-%m-%d").to_string(),
+            created: 0,
+            closed: 0,
+            open_total: 0,
+            closed_total: 0,
+            remaining_estimate: 0,
+            completed_estimate: 0,
+        };
+
+        for (created, closed, estimate) in &timeline {
+            if *created >= display_start && *created <= bucket_end {
+                point.created += 1;
+            }
+            if let Some(closed) = closed {
+                if *closed >= display_start && *closed <= bucket_end {
+                    point.closed += 1;
+                }
+            }
+            if *created > bucket_end {
+                continue;
+            }
+            match closed {
+                Some(c) if *c <= bucket_end => {
+                    point.closed_total += 1;
+                    point.completed_estimate += estimate;
+                }
+                _ => {
+                    point.open_total += 1;
+                    point.remaining_estimate += estimate;
+                }
+            }
+        }
+
+        points.push(point);
+        bucket_start = next;
+    }
+
+    points
+}
+
+/// Get time-bucketed open/closed counts and estimate sums for all beads
+#[tauri::command]
+pub fn get_burndown_data(
+    range: Option<BurndownRange>,
+    bucket: Option<BurndownBucket>,
+    window: tauri::Window,
+    project_handle: Option<String>,
+) -> Result<BurndownData, String> {
+    let beads = crate::bd::load_beads(&crate::project::context_for(&window, project_handle.as_deref())?)?;
+    let range = range.unwrap_or_default();
+    let bucket = bucket.unwrap_or_default();
+
+    let start = parse_bound(&range.start, "start")?
+        .or_else(|| beads.iter().filter_map(|b| b.created_at.as_deref().and_then(parse_timestamp)).min())
+        .unwrap_or_else(|| Utc::now().date_naive());
+    let end = parse_bound(&range.end, "end")?.unwrap_or_else(|| Utc::now().date_naive());
+    if end < start {
+        return Err(format!("Burndown range end {} is before start {}", end, start));
+    }
+
+    let points = compute_burndown(&beads, start, end, bucket);
+    eprintln!("📉 get_burndown_data: {} {:?} buckets from {} beads", points.len(), bucket, beads.len());
+
+    Ok(BurndownData {
+        bucket,
+        start: start.format("%Y-%m-%d").to_string(),
+        end: end.format("%Y-%m-%d").to_string(),
+        points,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bead(id: &str, created: &str, closed: Option<&str>, estimate: u32) -> Bead {
+        crate::test_bead(serde_json::json!({
+            "id": id,
+            "status": if closed.is_some() { "closed" } else { "open" },
+            "estimate": estimate,
+            "created_at": created,
+            "closed_at": closed,
+        }))
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_daily_buckets_track_open_and_closed() {
+        let beads = vec![
+            bead("a", "2026-01-05T09:00:00Z", Some("2026-01-06T17:00:00Z"), 60),
+            bead("b", "2026-01-05T10:00:00Z", None, 30),
+            bead("c", "2026-01-07T10:00:00Z", None, 15),
+        ];
+        let points = compute_burndown(&beads, date("2026-01-05"), date("2026-01-07"), BurndownBucket::Day);
+        assert_eq!(points.len(), 3);
+
+        assert_eq!((points[0].created, points[0].open_total, points[0].remaining_estimate), (2, 2, 90));
+        assert_eq!((points[1].closed, points[1].closed_total, points[1].open_total), (1, 1, 1));
+        assert_eq!(points[1].completed_estimate, 60);
+        assert_eq!((points[2].created, points[2].open_total, points[2].remaining_estimate), (1, 2, 45));
+    }
+
+    #[test]
+    fn test_weekly_buckets_align_to_monday_and_clamp() {
+        // 2026-01-07 is a Wednesday
+        let points = compute_burndown(&[], date("2026-01-07"), date("2026-01-20"), BurndownBucket::Week);
+        let ranges: Vec<(&str, &str)> = points.iter().map(|p| (p.start.as_str(), p.end.as_str())).collect();
+        assert_eq!(
+            ranges,
+            vec![("2026-01-07", "2026-01-11"), ("2026-01-12", "2026-01-18"), ("2026-01-19", "2026-01-20")]
+        );
+    }
+
+    #[test]
+    fn test_monthly_buckets_roll_over_year() {
+        let points = compute_burndown(&[], date("2025-12-15"), date("2026-01-10"), BurndownBucket::Month);
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[1].start, "2026-01-01");
+    }
+}
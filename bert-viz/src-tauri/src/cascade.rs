@@ -0,0 +1,309 @@
+//! Closing a parent together with its children
+//!
+//! Closing an epic or feature usually means deciding what happens to the
+//! work still open under it. `close_bead_cascade` plans that with one of
+//! three strategies: close every open descendant (deepest first, each with
+//! its own reason), move the open children under another bead, or refuse
+//! while anything below is open. The plan runs step by step through bd; if a
+//! step fails, the steps already applied are undone in reverse order, so the
+//! project ends up either fully cascaded or as it was.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tauri::AppHandle;
+
+use crate::bd::{apply_bead_op, load_beads, BeadOp};
+use crate::error::BertError;
+use crate::project::{context_for, ProjectContext};
+use crate::transitions::TransitionKind;
+use crate::workflow::WorkflowConfig;
+use crate::Bead;
+
+/// What to do with the open work under the bead being closed
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "strategy", rename_all = "kebab-case")]
+pub enum CascadeStrategy {
+    /// Close every open descendant; `reasons` maps bead IDs to their close
+    /// reason, the others get one naming the closed parent
+    CloseAllDescendants {
+        #[serde(default)]
+        reasons: HashMap<String, String>,
+    },
+    /// Move the open children (with their subtrees) under another bead
+    ReparentOpenChildren {
+        #[serde(rename = "newParentId")]
+        new_parent_id: String,
+    },
+    /// Fail if anything below is still open
+    AbortIfOpenChildren,
+}
+
+/// One step of a cascade, in execution order
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "action", rename_all = "kebab-case")]
+pub enum CascadeStep {
+    Close {
+        #[serde(rename = "beadId")]
+        bead_id: String,
+        reason: Option<String>,
+    },
+    Reparent {
+        #[serde(rename = "beadId")]
+        bead_id: String,
+        #[serde(rename = "fromParentId")]
+        from_parent_id: String,
+        #[serde(rename = "toParentId")]
+        to_parent_id: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CascadeOutcome {
+    pub steps: Vec<CascadeStep>,
+    /// False for previews
+    pub applied: bool,
+}
+
+fn find<'a>(beads: &'a [Bead], id: &str) -> Result<&'a Bead, BertError> {
+    beads
+        .iter()
+        .find(|b| b.id == id)
+        .ok_or_else(|| BertError::NotFound(format!("Bead with ID {} not found", id)))
+}
+
+/// Direct children of each bead, in file order
+fn children_map(beads: &[Bead]) -> HashMap<&str, Vec<&Bead>> {
+    let mut children: HashMap<&str, Vec<&Bead>> = HashMap::new();
+    for bead in beads {
+        if let Some(dep) = bead.dependencies.iter().find(|d| d.r#type == "parent-child") {
+            children.entry(dep.depends_on_id.as_str()).or_default().push(bead);
+        }
+    }
+    children
+}
+
+/// Descendants of `id`, children before their parents
+fn descendants_post_order<'a>(children: &HashMap<&str, Vec<&'a Bead>>, id: &str) -> Vec<&'a Bead> {
+    fn visit<'a>(children: &HashMap<&str, Vec<&'a Bead>>, id: &str, seen: &mut HashSet<String>, out: &mut Vec<&'a Bead>) {
+        for child in children.get(id).into_iter().flatten() {
+            // Guard against parent-child cycles
+            if seen.insert(child.id.clone()) {
+                visit(children, &child.id, seen, out);
+                out.push(child);
+            }
+        }
+    }
+    let mut out = Vec::new();
+    visit(children, id, &mut HashSet::from([id.to_string()]), &mut out);
+    out
+}
+
+/// Steps closing `bead_id` with `strategy`, ending with the bead itself
+pub fn plan(
+    beads: &[Bead],
+    bead_id: &str,
+    strategy: &CascadeStrategy,
+    reason: Option<&str>,
+    workflow: &WorkflowConfig,
+) -> Result<Vec<CascadeStep>, BertError> {
+    let bead = find(beads, bead_id)?;
+    if workflow.is_done(&bead.status) {
+        return Err(BertError::InvalidInput(format!("{} is already {}", bead_id, bead.status)));
+    }
+    let children = children_map(beads);
+    let descendants = descendants_post_order(&children, bead_id);
+    let open: Vec<&Bead> = descendants.iter().copied().filter(|b| !workflow.is_done(&b.status)).collect();
+
+    let mut steps = Vec::new();
+    match strategy {
+        CascadeStrategy::CloseAllDescendants { reasons } => {
+            for descendant in open {
+                let reason = reasons.get(&descendant.id).cloned().unwrap_or_else(|| format!("Closed with {}", bead_id));
+                steps.push(CascadeStep::Close { bead_id: descendant.id.clone(), reason: Some(reason) });
+            }
+        }
+        CascadeStrategy::ReparentOpenChildren { new_parent_id } => {
+            find(beads, new_parent_id)?;
+            if new_parent_id == bead_id || descendants.iter().any(|d| &d.id == new_parent_id) {
+                return Err(BertError::InvalidInput(format!(
+                    "{} is {} or below it; pick a parent outside the subtree being closed",
+                    new_parent_id, bead_id
+                )));
+            }
+            for child in children.get(bead_id).into_iter().flatten().filter(|c| !workflow.is_done(&c.status)) {
+                crate::hierarchy::validate_move(beads, &child.id, Some(new_parent_id))?;
+                steps.push(CascadeStep::Reparent {
+                    bead_id: child.id.clone(),
+                    from_parent_id: bead_id.to_string(),
+                    to_parent_id: new_parent_id.clone(),
+                });
+            }
+        }
+        CascadeStrategy::AbortIfOpenChildren => {
+            if !open.is_empty() {
+                let ids: Vec<&str> = open.iter().map(|b| b.id.as_str()).collect();
+                return Err(BertError::Transition(
+                    TransitionKind::CloseWithOpenChildren,
+                    format!("{} has {} open descendants: {}", bead_id, ids.len(), ids.join(", ")),
+                ));
+            }
+        }
+    }
+    steps.push(CascadeStep::Close { bead_id: bead_id.to_string(), reason: reason.map(str::to_string) });
+    Ok(steps)
+}
+
+fn apply_step(ctx: &ProjectContext, beads: &[Bead], step: &CascadeStep) -> Result<(), BertError> {
+    match step {
+        CascadeStep::Close { bead_id, reason } => {
+            apply_bead_op(ctx, &BeadOp::Close { bead_id: bead_id.clone(), reason: reason.clone() }).map(|_| ())
+        }
+        CascadeStep::Reparent { bead_id, to_parent_id, .. } => {
+            crate::bd::set_parent_internal(ctx, find(beads, bead_id)?, Some(to_parent_id))
+        }
+    }
+}
+
+/// Reverse an applied step; `beads` are the beads from before the cascade
+fn undo_step(ctx: &ProjectContext, beads: &[Bead], step: &CascadeStep) -> Result<(), BertError> {
+    match step {
+        CascadeStep::Close { bead_id, .. } => {
+            let original = find(beads, bead_id)?;
+            apply_bead_op(ctx, &BeadOp::Reopen { bead_id: bead_id.clone() })?;
+            if original.status != "open" {
+                apply_bead_op(ctx, &BeadOp::Update { bead: original.clone() })?;
+            }
+            Ok(())
+        }
+        CascadeStep::Reparent { bead_id, from_parent_id, .. } => {
+            let moved = crate::bd::get_bead_by_id(ctx, bead_id)?;
+            crate::bd::set_parent_internal(ctx, &moved, Some(from_parent_id))
+        }
+    }
+}
+
+/// Apply `steps` in order, undoing the applied ones if any fails
+fn execute(ctx: &ProjectContext, beads: &[Bead], steps: &[CascadeStep]) -> Result<(), BertError> {
+    for (index, step) in steps.iter().enumerate() {
+        let Err(e) = apply_step(ctx, beads, step) else { continue };
+        eprintln!("⚠️  Cascade step {} failed ({}); rolling back {} applied steps", index + 1, e, index);
+        for applied in steps[..index].iter().rev() {
+            if let Err(undo_error) = undo_step(ctx, beads, applied) {
+                return Err(BertError::Internal(format!(
+                    "{}; rolling back failed at {:?}: {}. The project is partially cascaded",
+                    e, applied, undo_error
+                )));
+            }
+        }
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Close `beadId` and handle its open descendants with `strategy`. With
+/// `preview`, returns the planned steps without running them.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn close_bead_cascade(
+    beadId: String,
+    strategy: CascadeStrategy,
+    reason: Option<String>,
+    preview: Option<bool>,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    app_handle: AppHandle,
+) -> Result<CascadeOutcome, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    let beads = load_beads(&ctx)?;
+    let workflow = WorkflowConfig::load_for_beads_file(&ctx.beads_path()?)?;
+    let steps = plan(&beads, &beadId, &strategy, reason.as_deref(), &workflow)?;
+    if preview.unwrap_or(false) {
+        return Ok(CascadeOutcome { steps, applied: false });
+    }
+
+    let result = execute(&ctx, &beads, &steps);
+    // Even a rolled back cascade rewrote the file
+    crate::project::emit_beads_updated(&app_handle, &ctx);
+    result?;
+    eprintln!("🧹 Closed {} in {} steps", beadId, steps.len());
+    crate::autocommit::note(&ctx, format!("bd: close {} with {} cascaded changes", beadId, steps.len() - 1));
+    Ok(CascadeOutcome { steps, applied: true })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bead(id: &str, status: &str, issue_type: &str, parent: Option<&str>) -> Bead {
+        let deps: Vec<_> = parent
+            .iter()
+            .map(|p| json!({ "issue_id": id, "depends_on_id": p, "type": "parent-child" }))
+            .collect();
+        crate::test_bead(json!({
+            "id": id,
+            "status": status,
+            "issue_type": issue_type,
+            "dependencies": deps,
+        }))
+    }
+
+    fn project() -> Vec<Bead> {
+        vec![
+            bead("e1", "open", "epic", None),
+            bead("e2", "open", "epic", None),
+            bead("f1", "open", "feature", Some("e1")),
+            bead("f2", "closed", "feature", Some("e1")),
+            bead("t1", "in_progress", "task", Some("f1")),
+            bead("t2", "closed", "task", Some("f1")),
+            bead("f3", "open", "feature", None),
+        ]
+    }
+
+    fn close(id: &str, reason: &str) -> CascadeStep {
+        CascadeStep::Close { bead_id: id.to_string(), reason: Some(reason.to_string()) }
+    }
+
+    #[test]
+    fn test_close_all_closes_open_descendants_deepest_first() {
+        let strategy = CascadeStrategy::CloseAllDescendants { reasons: HashMap::from([("t1".to_string(), "Obsolete".to_string())]) };
+        let steps = plan(&project(), "e1", &strategy, Some("Shipped"), &WorkflowConfig::default()).unwrap();
+        assert_eq!(steps, vec![close("t1", "Obsolete"), close("f1", "Closed with e1"), close("e1", "Shipped")]);
+    }
+
+    #[test]
+    fn test_reparent_moves_open_children_outside_the_subtree() {
+        let workflow = WorkflowConfig::default();
+        let strategy = CascadeStrategy::ReparentOpenChildren { new_parent_id: "e2".to_string() };
+        let steps = plan(&project(), "e1", &strategy, None, &workflow).unwrap();
+        assert_eq!(
+            steps,
+            vec![
+                CascadeStep::Reparent { bead_id: "f1".into(), from_parent_id: "e1".into(), to_parent_id: "e2".into() },
+                CascadeStep::Close { bead_id: "e1".into(), reason: None },
+            ]
+        );
+
+        let inside = CascadeStrategy::ReparentOpenChildren { new_parent_id: "f1".to_string() };
+        assert!(matches!(plan(&project(), "e1", &inside, None, &workflow), Err(BertError::InvalidInput(_))));
+        // A feature can't hold features
+        let too_low = CascadeStrategy::ReparentOpenChildren { new_parent_id: "f3".to_string() };
+        assert!(plan(&project(), "e1", &too_low, None, &workflow).is_err());
+    }
+
+    #[test]
+    fn test_abort_if_open_children() {
+        let workflow = WorkflowConfig::default();
+        let err = plan(&project(), "e1", &CascadeStrategy::AbortIfOpenChildren, None, &workflow).unwrap_err();
+        assert_eq!(err.code(), "close_with_open_children");
+        assert!(err.to_string().contains("t1, f1"), "{}", err);
+
+        let mut beads = project();
+        beads[4].status = "closed".into();
+        beads[2].status = "done".into();
+        let steps = plan(&beads, "e1", &CascadeStrategy::AbortIfOpenChildren, None, &workflow).unwrap();
+        assert_eq!(steps, vec![CascadeStep::Close { bead_id: "e1".into(), reason: None }]);
+        assert!(plan(&beads, "f2", &CascadeStrategy::AbortIfOpenChildren, None, &workflow).is_err());
+    }
+}
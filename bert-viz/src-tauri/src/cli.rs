@@ -0,0 +1,393 @@
+//! Headless command line interface (`bert-viz-cli`)
+//!
+//! Runs the same pipeline as the app against an issues.jsonl file, without a
+//! window, so CI jobs can publish project reports:
+//!
+//! - `report`: health score, status counts, critical path and risk flags
+//! - `export-csv`: the filtered bead list, as the spreadsheet export writes it
+//! - `critical-path`: the open beads on the critical path, in blocking order
+//! - `validate`: parse the file and check dependencies; exits 1 on errors
+//! - `mcp`: serve the project's beads to agents over the Model Context
+//!   Protocol on stdin/stdout (see `mcp`)
+//!
+//! The beads file defaults to the one `bd` would use for the current
+//! directory.
+
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use crate::dependencies::Severity;
+use crate::error::BertError;
+use crate::health::{ProjectHealth, DEFAULT_MAX_OPEN_CHILDREN, DEFAULT_STALE_DAYS};
+use crate::workflow::WorkflowConfig;
+use crate::{Bead, FilterParams};
+
+const USAGE: &str = "\
+Usage: bert-viz-cli <command> [options]
+
+Commands:
+  report         Health score, status counts, critical path and risk flags
+  export-csv     Write the (filtered) bead list to a CSV file
+  critical-path  List the open beads on the critical path
+  validate       Check the beads file and its dependencies (exit code 1 on errors)
+  mcp            Serve bead tools to agents over MCP (stdio)
+
+Options:
+  --beads <path>     issues.jsonl to read (default: found from the current directory)
+  --json             Print JSON instead of text (report, critical-path, validate)
+  --output <path>    CSV file to write (export-csv, required)
+  --filter <query>   Search query, e.g. 'status:open owner:alice' (export-csv)
+  --hide-closed      Leave out done beads (export-csv)
+  --project <dir>    Project to serve (mcp, default: the current directory)
+  -h, --help         Show this help";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Command {
+    Report,
+    ExportCsv,
+    CriticalPath,
+    Validate,
+    Mcp,
+}
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    command: Command,
+    beads: Option<PathBuf>,
+    json: bool,
+    output: Option<PathBuf>,
+    filter: String,
+    hide_closed: bool,
+    project: Option<PathBuf>,
+}
+
+/// Parse arguments (without the program name). `Ok(None)` asks for help.
+fn parse_args(args: &[String]) -> Result<Option<Args>, String> {
+    let mut args = args.iter();
+    let command = match args.next().map(String::as_str) {
+        None | Some("-h" | "--help" | "help") => return Ok(None),
+        Some("report") => Command::Report,
+        Some("export-csv") => Command::ExportCsv,
+        Some("critical-path") => Command::CriticalPath,
+        Some("validate") => Command::Validate,
+        Some("mcp") => Command::Mcp,
+        Some(other) => return Err(format!("Unknown command '{}'", other)),
+    };
+
+    let mut parsed = Args { command, beads: None, json: false, output: None, filter: String::new(), hide_closed: false, project: None };
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().cloned().ok_or_else(|| format!("{} needs a value", arg));
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--beads" if command != Command::Mcp => parsed.beads = Some(PathBuf::from(value()?)),
+            "--json" => parsed.json = true,
+            "--output" | "-o" if command == Command::ExportCsv => parsed.output = Some(PathBuf::from(value()?)),
+            "--filter" if command == Command::ExportCsv => parsed.filter = value()?,
+            "--hide-closed" if command == Command::ExportCsv => parsed.hide_closed = true,
+            "--project" if command == Command::Mcp => parsed.project = Some(PathBuf::from(value()?)),
+            other => return Err(format!("Unexpected argument '{}'", other)),
+        }
+    }
+    if command == Command::ExportCsv && parsed.output.is_none() {
+        return Err("export-csv needs --output <path>".to_string());
+    }
+    Ok(Some(parsed))
+}
+
+/// One bead on the critical path
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CriticalPathStep {
+    pub id: String,
+    pub title: String,
+    pub status: String,
+    pub owner: Option<String>,
+    pub estimate: Option<u32>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectReport {
+    pub beads_path: PathBuf,
+    pub generated_at: String,
+    /// Beads per status, tombstones excluded
+    pub status_counts: BTreeMap<String, usize>,
+    pub health: ProjectHealth,
+    pub critical_path: Vec<CriticalPathStep>,
+}
+
+pub(crate) fn critical_path(beads: &[Bead], workflow: &WorkflowConfig) -> Vec<CriticalPathStep> {
+    let by_id: BTreeMap<&str, &Bead> = beads.iter().map(|b| (b.id.as_str(), b)).collect();
+    crate::notifications::open_critical_chain(beads, workflow)
+        .iter()
+        .filter_map(|id| by_id.get(id.as_str()))
+        .map(|b| CriticalPathStep {
+            id: b.id.clone(),
+            title: b.title.clone(),
+            status: b.status.clone(),
+            owner: b.owner.clone(),
+            estimate: b.estimate,
+        })
+        .collect()
+}
+
+fn build_report(beads_path: &Path, beads: &[Bead], workflow: &WorkflowConfig) -> ProjectReport {
+    let mut status_counts = BTreeMap::new();
+    for bead in beads.iter().filter(|b| b.status != "tombstone") {
+        *status_counts.entry(bead.status.clone()).or_insert(0) += 1;
+    }
+    ProjectReport {
+        beads_path: beads_path.to_path_buf(),
+        generated_at: Utc::now().to_rfc3339(),
+        status_counts,
+        health: crate::health::assess(beads, workflow, DEFAULT_STALE_DAYS, DEFAULT_MAX_OPEN_CHILDREN, Utc::now()),
+        critical_path: critical_path(beads, workflow),
+    }
+}
+
+fn format_step(i: usize, step: &CriticalPathStep) -> String {
+    let mut line = format!("{}. {} {} [{}]", i + 1, step.id, step.title, step.status);
+    if let Some(owner) = &step.owner {
+        line.push_str(&format!(" @{}", owner));
+    }
+    if let Some(estimate) = step.estimate {
+        line.push_str(&format!(" ({}m)", estimate));
+    }
+    line
+}
+
+/// Markdown rendering of a report, for CI job summaries
+fn render_report(report: &ProjectReport) -> String {
+    let health = &report.health;
+    let mut out = format!(
+        "# Project report\n\n{}\nGenerated {}\n\n**Health score: {}/100** ({} open tasks, {} blocked)\n\n## Status\n\n| Status | Beads |\n| --- | ---: |\n",
+        report.beads_path.display(),
+        report.generated_at,
+        health.score,
+        health.open_count,
+        health.blocked_count
+    );
+    for (status, count) in &report.status_counts {
+        out.push_str(&format!("| {} | {} |\n", status, count));
+    }
+
+    out.push_str("\n## Critical path\n\n");
+    if report.critical_path.is_empty() {
+        out.push_str("No blocking chain among open beads.\n");
+    }
+    for (i, step) in report.critical_path.iter().enumerate() {
+        out.push_str(&format_step(i, step));
+        out.push('\n');
+    }
+
+    out.push_str("\n## Flags\n\n");
+    if health.flags.is_empty() {
+        out.push_str("None.\n");
+    }
+    for flag in &health.flags {
+        let severity = if flag.severity == Severity::Error { "error" } else { "warning" };
+        out.push_str(&format!("- **{}** {} (-{})\n", severity, flag.message, flag.penalty));
+    }
+    out
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, BertError> {
+    Ok(serde_json::to_string_pretty(value)?)
+}
+
+/// Serve MCP on stdin/stdout until the client closes stdin
+fn serve_mcp(args: &Args) -> Result<(), BertError> {
+    let project = match &args.project {
+        Some(path) => path.clone(),
+        None => std::env::current_dir()?,
+    };
+    let ctx = crate::project::ProjectContext::new(project);
+    let beads_path = ctx.beads_path()?;
+    // stdout carries the protocol; progress goes to stderr
+    eprintln!("🔌 MCP server for {}", beads_path.display());
+    crate::mcp::McpServer::new(ctx).run(std::io::stdin().lock(), std::io::stdout().lock())?;
+    Ok(())
+}
+
+/// Run a parsed command, returning what to print and whether it succeeded
+fn execute(args: &Args) -> Result<(String, bool), BertError> {
+    if args.command == Command::Mcp {
+        return serve_mcp(args).map(|()| (String::new(), true));
+    }
+
+    let beads_path = match &args.beads {
+        Some(path) => path.clone(),
+        None => {
+            let cwd = std::env::current_dir()?;
+            crate::bd::find_beads_file_from(&cwd).ok_or_else(|| {
+                BertError::ProjectNotFound(format!("No .beads/issues.jsonl found from {}", cwd.display()))
+            })?
+        }
+    };
+    if !beads_path.is_file() {
+        return Err(BertError::NotFound(format!("{} does not exist", beads_path.display())));
+    }
+    let beads = crate::bd::read_beads(&beads_path)?;
+    let workflow = WorkflowConfig::load_for_beads_file(&beads_path)?;
+
+    match args.command {
+        Command::Report => {
+            let report = build_report(&beads_path, &beads, &workflow);
+            Ok((if args.json { to_json(&report)? } else { render_report(&report) }, true))
+        }
+        Command::ExportCsv => {
+            let params = FilterParams { filter_text: args.filter.clone(), hide_closed: args.hide_closed, ..Default::default() };
+            let rows = crate::export::filter_and_sort_beads(&beads, &params, &workflow);
+            let output = args.output.as_deref().expect("checked by parse_args");
+            crate::export::write_csv(&rows, output).map_err(BertError::Io)?;
+            Ok((format!("Exported {} beads to {}", rows.len(), output.display()), true))
+        }
+        Command::CriticalPath => {
+            let steps = critical_path(&beads, &workflow);
+            if args.json {
+                return Ok((to_json(&steps)?, true));
+            }
+            let lines: Vec<String> = steps.iter().enumerate().map(|(i, s)| format_step(i, s)).collect();
+            Ok((lines.join("\n"), true))
+        }
+        Command::Mcp => unreachable!("served above"),
+        Command::Validate => {
+            let report = crate::dependencies::validate(&beads, true);
+            if args.json {
+                return Ok((to_json(&report)?, report.valid));
+            }
+            let mut lines = vec![format!(
+                "{} beads, {} dependencies, {} issues",
+                report.bead_count,
+                report.dependency_count,
+                report.issues.len()
+            )];
+            for issue in &report.issues {
+                let severity = if issue.severity == Severity::Error { "error" } else { "warning" };
+                lines.push(format!("{}: {}", severity, issue.message));
+                if let Some(fix) = &issue.fix {
+                    lines.push(format!("  fix: {}", fix.command));
+                }
+            }
+            Ok((lines.join("\n"), report.valid))
+        }
+    }
+}
+
+/// Entry point of the `bert-viz-cli` binary
+pub fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args = match parse_args(&args) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match execute(&args) {
+        Ok((output, ok)) => {
+            if !output.is_empty() {
+                println!("{}", output);
+            }
+            if ok { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+        }
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn bead(id: &str, status: &str, blocked_by: &[&str]) -> Bead {
+        let deps: Vec<_> = blocked_by
+            .iter()
+            .map(|b| json!({"issue_id": id, "depends_on_id": b, "type": "blocks"}))
+            .collect();
+        crate::test_bead(json!({
+            "id": id, "title": format!("Bead {}", id), "status": status, "estimate": 60, "dependencies": deps,
+        }))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse_args(&[]), Ok(None));
+        let args = parse_args(&strings(&["export-csv", "--beads", "x.jsonl", "-o", "out.csv", "--hide-closed"]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(args.command, Command::ExportCsv);
+        assert_eq!(args.beads, Some(PathBuf::from("x.jsonl")));
+        assert_eq!(args.output, Some(PathBuf::from("out.csv")));
+        assert!(args.hide_closed);
+
+        assert!(parse_args(&strings(&["export-csv"])).is_err());
+        assert!(parse_args(&strings(&["report", "--hide-closed"])).is_err());
+        assert!(parse_args(&strings(&["report", "--beads"])).is_err());
+
+        let mcp = parse_args(&strings(&["mcp", "--project", "repo"])).unwrap().unwrap();
+        assert_eq!((mcp.command, mcp.project), (Command::Mcp, Some(PathBuf::from("repo"))));
+        assert!(parse_args(&strings(&["report", "--project", "repo"])).is_err());
+        assert!(parse_args(&strings(&["deploy"])).is_err());
+    }
+
+    #[test]
+    fn test_report_lists_critical_path_in_order() {
+        let beads = vec![
+            bead("p-3", "open", &["p-2"]),
+            bead("p-2", "in_progress", &["p-1"]),
+            bead("p-1", "closed", &[]),
+            bead("p-4", "open", &[]),
+        ];
+        let report = build_report(Path::new("issues.jsonl"), &beads, &WorkflowConfig::default());
+        let ids: Vec<&str> = report.critical_path.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["p-2", "p-3"]);
+        assert_eq!(report.status_counts["open"], 2);
+
+        let markdown = render_report(&report);
+        assert!(markdown.contains("1. p-2 Bead p-2 [in_progress] (60m)\n2. p-3 Bead p-3 [open] (60m)"));
+        assert!(markdown.contains("| open | 2 |"));
+    }
+
+    #[test]
+    fn test_validate_and_export_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("issues.jsonl");
+        let lines: Vec<String> = [bead("p-1", "open", &["p-2"]), bead("p-2", "open", &["p-1"])]
+            .iter()
+            .map(|b| serde_json::to_string(b).unwrap())
+            .collect();
+        std::fs::write(&path, lines.join("\n")).unwrap();
+
+        let validate = parse_args(&strings(&["validate", "--beads", path.to_str().unwrap()])).unwrap().unwrap();
+        let (output, ok) = execute(&validate).unwrap();
+        assert!(!ok);
+        assert!(output.contains("error: Blocking cycle"));
+
+        let csv = dir.path().join("out.csv");
+        let export = parse_args(&strings(&[
+            "export-csv", "--beads", path.to_str().unwrap(), "--output", csv.to_str().unwrap(), "--filter", "p-2",
+        ]))
+        .unwrap()
+        .unwrap();
+        let (output, ok) = execute(&export).unwrap();
+        assert!(ok);
+        assert!(output.starts_with("Exported 1 beads"));
+        assert_eq!(std::fs::read_to_string(&csv).unwrap().lines().count(), 2);
+    }
+}
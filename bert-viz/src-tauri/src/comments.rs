@@ -0,0 +1,325 @@
+//! Threaded comments on beads
+//!
+//! Review notes and discussion don't belong in a bead's `notes`, and bd has
+//! no place for them. Comments are stored next to the beads, one file per
+//! bead in `.beads/comments/<bead-id>.jsonl`, so they are committed with the
+//! project but never touched by bd. Replies point at their parent comment;
+//! the frontend builds the threads. Authors are taken from the git config of
+//! the project.
+//!
+//! Every change emits `comments-updated` with the bead ID. The beads watcher
+//! emits it as well when a comment file changes on disk (e.g. after a pull).
+
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tauri::{AppHandle, Emitter};
+use uuid::Uuid;
+
+use crate::baseline::sanitize_file_name;
+use crate::error::BertError;
+use crate::project::{context_for, ProjectContext};
+
+/// Directory of the comment files, inside `.beads`
+const COMMENTS_DIR: &str = "comments";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Comment {
+    pub id: String,
+    pub bead_id: String,
+    /// Comment this one replies to
+    #[serde(default)]
+    pub parent_id: Option<String>,
+    pub author: Option<String>,
+    #[serde(default)]
+    pub author_email: Option<String>,
+    pub body: String,
+    pub created_at: String,
+    #[serde(default)]
+    pub edited_at: Option<String>,
+    /// Deleted comments that have replies stay, without a body, to keep the thread
+    #[serde(default)]
+    pub deleted: bool,
+}
+
+/// Payload of `comments-updated`
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentsUpdated {
+    pub bead_id: String,
+}
+
+pub(crate) fn comments_dir(beads_path: &Path) -> PathBuf {
+    beads_path.parent().unwrap_or(Path::new(".")).join(COMMENTS_DIR)
+}
+
+fn comments_file(beads_path: &Path, bead_id: &str) -> PathBuf {
+    comments_dir(beads_path).join(format!("{}.jsonl", sanitize_file_name(bead_id)))
+}
+
+/// Bead ID of a comment file, for the beads watcher
+pub fn bead_id_of(path: &Path) -> Option<String> {
+    let dir = path.parent()?;
+    if dir.file_name()? != COMMENTS_DIR {
+        return None;
+    }
+    path.file_stem().and_then(|s| s.to_str()).map(str::to_string)
+}
+
+pub(crate) fn read_comments(path: &Path) -> Result<Vec<Comment>, BertError> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents = fs::read_to_string(path)
+        .map_err(|e| BertError::Io(format!("Failed to read {}: {}", path.display(), e)))?;
+
+    let mut comments = Vec::new();
+    for line in contents.lines().filter(|l| !l.trim().is_empty()) {
+        match serde_json::from_str::<Comment>(line) {
+            Ok(comment) => comments.push(comment),
+            Err(e) => eprintln!("⚠️  Skipping malformed comment in {}: {}", path.display(), e),
+        }
+    }
+    Ok(comments)
+}
+
+fn append_comment(path: &Path, comment: &Comment) -> Result<(), BertError> {
+    crate::viewer::ensure_writable(path)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| BertError::Io(format!("Failed to create {}: {}", dir.display(), e)))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| BertError::Io(format!("Failed to open {}: {}", path.display(), e)))?;
+    writeln!(file, "{}", serde_json::to_string(comment)?)
+        .map_err(|e| BertError::Io(format!("Failed to append to {}: {}", path.display(), e)))
+}
+
+fn write_comments(path: &Path, comments: &[Comment]) -> Result<(), BertError> {
+    crate::viewer::ensure_writable(path)?;
+    if comments.is_empty() {
+        return fs::remove_file(path).map_err(|e| BertError::Io(format!("Failed to remove {}: {}", path.display(), e)));
+    }
+    let mut contents = String::new();
+    for comment in comments {
+        contents.push_str(&serde_json::to_string(comment)?);
+        contents.push('\n');
+    }
+    // Not .jsonl, so the watcher ignores the temporary file
+    let tmp = path.with_extension("jsonl.tmp");
+    fs::write(&tmp, contents).map_err(|e| BertError::Io(format!("Failed to write {}: {}", tmp.display(), e)))?;
+    fs::rename(&tmp, path).map_err(|e| BertError::Io(format!("Failed to replace {}: {}", path.display(), e)))
+}
+
+fn git_config(repo_dir: &Path, key: &str) -> Option<String> {
+    let output = Command::new("git").args(["config", key]).current_dir(repo_dir).output().ok()?;
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !value.is_empty()).then_some(value)
+}
+
+/// Name and email from the project's git config, falling back to the OS user
+pub(crate) fn author(ctx: &ProjectContext) -> (Option<String>, Option<String>) {
+    let repo_dir = ctx.project_path();
+    let name = git_config(repo_dir, "user.name").or_else(|| std::env::var("USER").or_else(|_| std::env::var("USERNAME")).ok());
+    (name, git_config(repo_dir, "user.email"))
+}
+
+fn find_mut<'a>(comments: &'a mut [Comment], id: &str) -> Result<&'a mut Comment, BertError> {
+    comments
+        .iter_mut()
+        .find(|c| c.id == id && !c.deleted)
+        .ok_or_else(|| BertError::NotFound(format!("Comment {} not found", id)))
+}
+
+fn check_body(body: &str) -> Result<String, BertError> {
+    let body = body.trim();
+    if body.is_empty() {
+        return Err(BertError::InvalidInput("Comments cannot be empty".to_string()));
+    }
+    Ok(body.to_string())
+}
+
+/// Remove comment `id`, or blank it when it has replies. Deleted comments
+/// whose last reply goes are removed as well.
+fn delete_from(comments: &mut Vec<Comment>, id: &str) -> Result<(), BertError> {
+    let has_replies = |comments: &[Comment], id: &str| comments.iter().any(|c| c.parent_id.as_deref() == Some(id));
+
+    let comment = find_mut(comments, id)?;
+    let mut parent = comment.parent_id.clone();
+    if has_replies(comments, id) {
+        let comment = find_mut(comments, id)?;
+        comment.deleted = true;
+        comment.body.clear();
+        return Ok(());
+    }
+
+    comments.retain(|c| c.id != id);
+    while let Some(parent_id) = parent.take() {
+        let Some(p) = comments.iter().find(|c| c.id == parent_id) else { break };
+        if !p.deleted || has_replies(comments, &parent_id) {
+            break;
+        }
+        parent = p.parent_id.clone();
+        comments.retain(|c| c.id != parent_id);
+    }
+    Ok(())
+}
+
+fn emit_comments_updated(app_handle: &AppHandle, bead_id: &str) {
+    let _ = app_handle.emit("comments-updated", CommentsUpdated { bead_id: bead_id.to_string() });
+}
+
+/// Comments of a bead, oldest first
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn list_comments(beadId: String, window: tauri::Window, project_handle: Option<String>) -> Result<Vec<Comment>, BertError> {
+    let beads_path = context_for(&window, project_handle.as_deref())?.beads_path()?;
+    read_comments(&comments_file(&beads_path, &beadId))
+}
+
+/// Comment on a bead, or reply to one of its comments with `parentId`
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn add_comment(
+    beadId: String,
+    body: String,
+    parentId: Option<String>,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    app_handle: AppHandle,
+) -> Result<Comment, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    crate::bead_index::get_bead_internal(&ctx, &beadId)?;
+    let path = comments_file(&ctx.beads_path()?, &beadId);
+    if let Some(parent_id) = &parentId {
+        find_mut(&mut read_comments(&path)?, parent_id)?;
+    }
+
+    let (author, author_email) = author(&ctx);
+    let comment = Comment {
+        id: Uuid::new_v4().to_string(),
+        bead_id: beadId.clone(),
+        parent_id: parentId,
+        author,
+        author_email,
+        body: check_body(&body)?,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        edited_at: None,
+        deleted: false,
+    };
+    append_comment(&path, &comment)?;
+    eprintln!("💬 Comment added to {}", beadId);
+    emit_comments_updated(&app_handle, &beadId);
+    Ok(comment)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn edit_comment(
+    beadId: String,
+    commentId: String,
+    body: String,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    app_handle: AppHandle,
+) -> Result<Comment, BertError> {
+    let path = comments_file(&context_for(&window, project_handle.as_deref())?.beads_path()?, &beadId);
+    let mut comments = read_comments(&path)?;
+
+    let comment = find_mut(&mut comments, &commentId)?;
+    comment.body = check_body(&body)?;
+    comment.edited_at = Some(chrono::Utc::now().to_rfc3339());
+    let edited = comment.clone();
+
+    write_comments(&path, &comments)?;
+    emit_comments_updated(&app_handle, &beadId);
+    Ok(edited)
+}
+
+/// Delete a comment. One with replies keeps its place in the thread, without
+/// its body.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn delete_comment(beadId: String, commentId: String, window: tauri::Window, project_handle: Option<String>, app_handle: AppHandle) -> Result<(), BertError> {
+    let path = comments_file(&context_for(&window, project_handle.as_deref())?.beads_path()?, &beadId);
+    let mut comments = read_comments(&path)?;
+    delete_from(&mut comments, &commentId)?;
+
+    write_comments(&path, &comments)?;
+    eprintln!("💬 Comment deleted from {}", beadId);
+    emit_comments_updated(&app_handle, &beadId);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn comment(id: &str, parent_id: Option<&str>) -> Comment {
+        Comment {
+            id: id.to_string(),
+            bead_id: "bp-1".to_string(),
+            parent_id: parent_id.map(str::to_string),
+            author: Some("ann".to_string()),
+            author_email: None,
+            body: format!("comment {}", id),
+            created_at: "2026-03-01T10:00:00Z".to_string(),
+            edited_at: None,
+            deleted: false,
+        }
+    }
+
+    fn ids(comments: &[Comment]) -> Vec<&str> {
+        comments.iter().map(|c| c.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_comments_round_trip_per_bead() {
+        let dir = TempDir::new().unwrap();
+        let beads_path = dir.path().join(".beads").join("issues.jsonl");
+        let path = comments_file(&beads_path, "bp-1");
+        assert_eq!(path, dir.path().join(".beads/comments/bp-1.jsonl"));
+        assert_eq!(bead_id_of(&path).as_deref(), Some("bp-1"));
+        assert_eq!(bead_id_of(&beads_path), None);
+
+        append_comment(&path, &comment("a", None)).unwrap();
+        append_comment(&path, &comment("b", Some("a"))).unwrap();
+        let mut comments = read_comments(&path).unwrap();
+        assert_eq!(comments, vec![comment("a", None), comment("b", Some("a"))]);
+
+        find_mut(&mut comments, "b").unwrap().body = "edited".to_string();
+        write_comments(&path, &comments).unwrap();
+        assert_eq!(read_comments(&path).unwrap()[1].body, "edited");
+        assert!(read_comments(&comments_file(&beads_path, "bp-2")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_delete_keeps_threads_intact() {
+        let mut comments = vec![comment("a", None), comment("b", Some("a")), comment("c", Some("b")), comment("d", None)];
+
+        delete_from(&mut comments, "a").unwrap();
+        assert_eq!(ids(&comments), vec!["a", "b", "c", "d"]);
+        assert!(comments[0].deleted && comments[0].body.is_empty());
+        assert!(delete_from(&mut comments, "a").is_err());
+
+        delete_from(&mut comments, "d").unwrap();
+        assert_eq!(ids(&comments), vec!["a", "b", "c"]);
+
+        // Removing the last reply also removes deleted ancestors without replies
+        delete_from(&mut comments, "b").unwrap();
+        delete_from(&mut comments, "c").unwrap();
+        assert!(comments.is_empty());
+    }
+
+    #[test]
+    fn test_empty_comments_are_rejected() {
+        assert!(check_body("  \n").is_err());
+        assert_eq!(check_body(" looks good \n").unwrap(), "looks good");
+    }
+}
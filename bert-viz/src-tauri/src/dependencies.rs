@@ -0,0 +1,348 @@
+//! Dependency graph validation
+//!
+//! The scheduler tolerates broken dependency data (the topological sort just
+//! appends whatever it could not order), which hides mistakes. This checks a
+//! project's dependencies for blocking cycles, beads that block themselves,
+//! containers blocking leaf work (a feature blocking a task) and references to
+//! beads that no longer exist. Each finding can carry a suggested
+//! `bd dep rm` command that the user, or the QA persona via
+//! `approve_suggestion`, can apply.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+
+use crate::error::BertError;
+use crate::Bead;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum DependencyIssueKind {
+    /// Beads that (transitively) block each other
+    Cycle,
+    /// A bead listed as its own blocker
+    SelfBlock,
+    /// An epic or feature blocking lower-level work
+    CrossHierarchy,
+    /// A dependency pointing at a bead that does not exist
+    OrphanedReference,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The schedule cannot be computed correctly until this is fixed
+    Error,
+    /// Probably a modelling mistake, but the schedule still works
+    Warning,
+}
+
+/// Removal of one dependency edge
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyFix {
+    pub issue_id: String,
+    pub depends_on_id: String,
+    /// Ready to pass to `approve_suggestion`
+    pub command: String,
+}
+
+impl DependencyFix {
+    fn remove(issue_id: &str, depends_on_id: &str) -> Self {
+        DependencyFix {
+            issue_id: issue_id.to_string(),
+            depends_on_id: depends_on_id.to_string(),
+            command: format!("bd dep rm {} {}", issue_id, depends_on_id),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyIssue {
+    pub kind: DependencyIssueKind,
+    pub severity: Severity,
+    /// Beads involved; for cycles, in blocking order
+    pub bead_ids: Vec<String>,
+    pub message: String,
+    /// Only set when fixes were requested
+    pub fix: Option<DependencyFix>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyReport {
+    /// No errors (warnings are allowed)
+    pub valid: bool,
+    pub bead_count: usize,
+    pub dependency_count: usize,
+    pub issues: Vec<DependencyIssue>,
+}
+
+/// Epics contain features, features contain tasks
+fn hierarchy_level(issue_type: &str) -> u8 {
+    match issue_type {
+        "epic" => 0,
+        "feature" => 1,
+        _ => 2,
+    }
+}
+
+/// Strongly connected components of the blocking graph (Tarjan), keeping only
+/// those with more than one bead
+fn blocking_cycles(edges: &BTreeMap<&str, BTreeSet<&str>>) -> Vec<Vec<String>> {
+    struct Tarjan<'a> {
+        edges: &'a BTreeMap<&'a str, BTreeSet<&'a str>>,
+        index: HashMap<&'a str, usize>,
+        low: HashMap<&'a str, usize>,
+        stack: Vec<&'a str>,
+        on_stack: BTreeSet<&'a str>,
+        components: Vec<Vec<String>>,
+    }
+
+    impl<'a> Tarjan<'a> {
+        fn visit(&mut self, v: &'a str) {
+            let next_index = self.index.len();
+            self.index.insert(v, next_index);
+            self.low.insert(v, next_index);
+            self.stack.push(v);
+            self.on_stack.insert(v);
+
+            let successors: Vec<&'a str> = self.edges.get(v).map(|s| s.iter().copied().collect()).unwrap_or_default();
+            for w in successors {
+                if !self.index.contains_key(w) {
+                    self.visit(w);
+                    let low = self.low[v].min(self.low[w]);
+                    self.low.insert(v, low);
+                } else if self.on_stack.contains(w) {
+                    let low = self.low[v].min(self.index[w]);
+                    self.low.insert(v, low);
+                }
+            }
+
+            if self.low[v] == self.index[v] {
+                let mut component = Vec::new();
+                while let Some(w) = self.stack.pop() {
+                    self.on_stack.remove(w);
+                    component.push(w.to_string());
+                    if w == v {
+                        break;
+                    }
+                }
+                if component.len() > 1 {
+                    component.sort();
+                    self.components.push(component);
+                }
+            }
+        }
+    }
+
+    let mut tarjan = Tarjan {
+        edges,
+        index: HashMap::new(),
+        low: HashMap::new(),
+        stack: Vec::new(),
+        on_stack: BTreeSet::new(),
+        components: Vec::new(),
+    };
+    for &v in edges.keys() {
+        if !tarjan.index.contains_key(v) {
+            tarjan.visit(v);
+        }
+    }
+    tarjan.components
+}
+
+/// Shortest cycle through `start` staying inside `component`, in blocking order
+fn cycle_path(start: &str, component: &BTreeSet<&str>, edges: &BTreeMap<&str, BTreeSet<&str>>) -> Vec<String> {
+    let mut came_from: HashMap<&str, &str> = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(v) = queue.pop_front() {
+        for &w in edges.get(v).into_iter().flatten() {
+            if !component.contains(w) {
+                continue;
+            }
+            if w == start {
+                let mut path = vec![v.to_string()];
+                let mut curr = v;
+                while let Some(&prev) = came_from.get(curr) {
+                    path.push(prev.to_string());
+                    curr = prev;
+                }
+                path.reverse();
+                return path;
+            }
+            if !came_from.contains_key(w) {
+                came_from.insert(w, v);
+                queue.push_back(w);
+            }
+        }
+    }
+
+    vec![start.to_string()]
+}
+
+/// Check every dependency of `beads`. Fixes are only generated when `suggest_fixes` is set.
+pub fn validate(beads: &[Bead], suggest_fixes: bool) -> DependencyReport {
+    let by_id: HashMap<&str, &Bead> = beads.iter().map(|b| (b.id.as_str(), b)).collect();
+    let mut issues = Vec::new();
+    let mut dependency_count = 0;
+    // Blocker -> blocked, for beads that exist
+    let mut edges: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+
+    let fix = |issue_id: &str, depends_on_id: &str| suggest_fixes.then(|| DependencyFix::remove(issue_id, depends_on_id));
+
+    for bead in beads {
+        for dep in &bead.dependencies {
+            dependency_count += 1;
+            let target = dep.depends_on_id.as_str();
+
+            let Some(blocker) = by_id.get(target) else {
+                issues.push(DependencyIssue {
+                    kind: DependencyIssueKind::OrphanedReference,
+                    severity: Severity::Error,
+                    bead_ids: vec![bead.id.clone(), target.to_string()],
+                    message: format!("{} has a {} dependency on missing bead {}", bead.id, dep.r#type, target),
+                    fix: fix(&bead.id, target),
+                });
+                continue;
+            };
+
+            if dep.r#type != "blocks" {
+                continue;
+            }
+
+            if target == bead.id {
+                issues.push(DependencyIssue {
+                    kind: DependencyIssueKind::SelfBlock,
+                    severity: Severity::Error,
+                    bead_ids: vec![bead.id.clone()],
+                    message: format!("{} blocks itself", bead.id),
+                    fix: fix(&bead.id, target),
+                });
+                continue;
+            }
+
+            if hierarchy_level(&blocker.issue_type) < hierarchy_level(&bead.issue_type) {
+                issues.push(DependencyIssue {
+                    kind: DependencyIssueKind::CrossHierarchy,
+                    severity: Severity::Warning,
+                    bead_ids: vec![bead.id.clone(), target.to_string()],
+                    message: format!(
+                        "{} {} blocks {} {}; block on the {}'s tasks instead",
+                        blocker.issue_type, target, bead.issue_type, bead.id, blocker.issue_type
+                    ),
+                    fix: fix(&bead.id, target),
+                });
+            }
+
+            edges.entry(target).or_default().insert(bead.id.as_str());
+        }
+    }
+
+    for component in blocking_cycles(&edges) {
+        let members: BTreeSet<&str> = component.iter().map(|s| s.as_str()).collect();
+        let path = cycle_path(&component[0], &members, &edges);
+        // Break the cycle at its closing edge: last blocks first
+        let closing = path.last().cloned().unwrap_or_default();
+        issues.push(DependencyIssue {
+            kind: DependencyIssueKind::Cycle,
+            severity: Severity::Error,
+            message: format!("Blocking cycle: {} -> {}", path.join(" -> "), path[0]),
+            fix: fix(&path[0], &closing),
+            bead_ids: path,
+        });
+    }
+
+    DependencyReport {
+        valid: !issues.iter().any(|i| i.severity == Severity::Error),
+        bead_count: beads.len(),
+        dependency_count,
+        issues,
+    }
+}
+
+/// Validate the dependency graph of the current project
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn validate_dependencies(suggestFixes: Option<bool>, window: tauri::Window, project_handle: Option<String>) -> Result<DependencyReport, BertError> {
+    let beads = crate::bd::load_beads(&crate::project::context_for(&window, project_handle.as_deref())?)?;
+    let report = validate(&beads, suggestFixes.unwrap_or(false));
+    eprintln!(
+        "🔗 validate_dependencies: {} issues in {} dependencies",
+        report.issues.len(),
+        report.dependency_count
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bead(id: &str, issue_type: &str, deps: &[(&str, &str)]) -> Bead {
+        let dependencies: Vec<serde_json::Value> = deps
+            .iter()
+            .map(|(on, ty)| serde_json::json!({ "issue_id": id, "depends_on_id": on, "type": ty }))
+            .collect();
+        crate::test_bead(serde_json::json!({
+            "id": id,
+            "issue_type": issue_type,
+            "dependencies": dependencies,
+        }))
+    }
+
+    fn kinds(report: &DependencyReport) -> Vec<DependencyIssueKind> {
+        report.issues.iter().map(|i| i.kind).collect()
+    }
+
+    #[test]
+    fn test_clean_graph_is_valid() {
+        let beads = vec![
+            bead("e", "epic", &[]),
+            bead("a", "task", &[("e", "parent-child")]),
+            bead("b", "task", &[("e", "parent-child"), ("a", "blocks")]),
+        ];
+        let report = validate(&beads, true);
+        assert!(report.issues.is_empty());
+        assert!(report.valid);
+        assert_eq!(report.dependency_count, 3);
+    }
+
+    #[test]
+    fn test_detects_cycle_with_closing_edge_fix() {
+        let beads = vec![
+            bead("a", "task", &[("c", "blocks")]),
+            bead("b", "task", &[("a", "blocks")]),
+            bead("c", "task", &[("b", "blocks")]),
+            bead("d", "task", &[("c", "blocks")]),
+        ];
+        let report = validate(&beads, true);
+        assert_eq!(kinds(&report), vec![DependencyIssueKind::Cycle]);
+
+        let cycle = &report.issues[0];
+        assert_eq!(cycle.bead_ids, vec!["a", "b", "c"]);
+        let fix = cycle.fix.as_ref().unwrap();
+        assert_eq!(fix.command, "bd dep rm a c");
+        assert!(!report.valid);
+    }
+
+    #[test]
+    fn test_self_block_orphan_and_cross_hierarchy() {
+        let beads = vec![
+            bead("f", "feature", &[]),
+            bead("t", "task", &[("f", "blocks"), ("t", "blocks"), ("gone", "parent-child")]),
+        ];
+        let report = validate(&beads, false);
+        assert_eq!(
+            kinds(&report),
+            vec![
+                DependencyIssueKind::CrossHierarchy,
+                DependencyIssueKind::SelfBlock,
+                DependencyIssueKind::OrphanedReference,
+            ]
+        );
+        assert!(report.issues.iter().all(|i| i.fix.is_none()));
+        assert_eq!(report.issues[0].severity, Severity::Warning);
+    }
+}
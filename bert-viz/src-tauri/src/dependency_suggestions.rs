@@ -0,0 +1,310 @@
+//! Heuristic suggestions of missing blocking dependencies
+//!
+//! Plans imported or written in a hurry often spell out their ordering in
+//! prose instead of dependencies. This looks for three hints:
+//!
+//! - a bead saying it comes "after", "depends on", "is blocked by", "requires"
+//!   or runs "once" another bead it names by ID (strongest)
+//! - a sibling whose title names an artifact (a `code span` or file path)
+//!   that another sibling's text uses: the first produces what the second needs
+//! - siblings numbered in sequence (`x.1`, `x.2`, ...), each after the previous
+//!
+//! A hint is dropped when either bead already (transitively) blocks the other,
+//! or the blocked bead is done. Suggestions carry a `bd dep add` command that
+//! `suggest_dependencies` queues as a proposal, so each can be approved with
+//! `approve_proposal` like an agent's.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use tauri::{AppHandle, State};
+
+use crate::agent::AgentState;
+use crate::error::BertError;
+use crate::events::{emit_scoped, EventScope};
+use crate::workflow::WorkflowConfig;
+use crate::Bead;
+
+/// Pseudo session the suggestions are queued under
+pub const PROPOSAL_SOURCE: &str = "dependency-suggestions";
+
+const PHRASE_CONFIDENCE: f64 = 0.9;
+const ARTIFACT_CONFIDENCE: f64 = 0.6;
+const SEQUENCE_CONFIDENCE: f64 = 0.4;
+
+/// Words that, shortly before a bead ID, say the bead waits on it
+const AFTER_PHRASES: &[&[&str]] = &[&["after"], &["depends", "on"], &["blocked", "by"], &["requires"], &["once"], &["following"]];
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencySuggestion {
+    pub blocker_id: String,
+    pub blocked_id: String,
+    /// 0..1; several hints for the same pair keep the strongest
+    pub confidence: f64,
+    /// Why the edge is suggested, one entry per hint
+    pub reasons: Vec<String>,
+    pub command: String,
+    /// Pending proposal to approve; set once queued
+    pub proposal_id: Option<String>,
+}
+
+/// Parent through the first parent-child dependency, as in the WBS tree
+fn parent_of(bead: &Bead) -> Option<&str> {
+    bead.dependencies.iter().find(|d| d.r#type == "parent-child").map(|d| d.depends_on_id.as_str())
+}
+
+/// Title, description and notes of a bead
+fn text_of(bead: &Bead) -> String {
+    [Some(&bead.title), bead.description.as_ref(), bead.design.as_ref(), bead.notes.as_ref()]
+        .into_iter()
+        .flatten()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Words of `text`, keeping the characters bead IDs and paths are made of
+fn words(text: &str) -> Vec<&str> {
+    text.split(|c: char| !(c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | '/')))
+        .map(|w| w.trim_end_matches('.'))
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+/// IDs among `ids` that `text` names right after an "after"-like phrase
+fn after_references<'a>(text: &str, ids: &HashSet<&'a str>) -> Vec<&'a str> {
+    let words = words(text);
+    let lower: Vec<String> = words.iter().map(|w| w.to_lowercase()).collect();
+    let mut found = Vec::new();
+    for (i, word) in words.iter().enumerate() {
+        let Some(&id) = ids.get(*word) else { continue };
+        let preceded = AFTER_PHRASES.iter().any(|phrase| {
+            // Allow one filler word ("after task x", "depends on bead x")
+            (0..=1).any(|gap| {
+                let end = i.saturating_sub(gap);
+                end >= phrase.len() && lower[end - phrase.len()..end].iter().zip(phrase.iter()).all(|(w, p)| w == p)
+            })
+        });
+        if preceded && !found.contains(&id) {
+            found.push(id);
+        }
+    }
+    found
+}
+
+/// Code spans and file paths mentioned in `text`
+fn artifacts(text: &str) -> HashSet<String> {
+    let mut found = HashSet::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('`') {
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('`') else { break };
+        let span = after[..end].trim();
+        if span.len() > 2 {
+            found.insert(span.to_string());
+        }
+        rest = &after[end + 1..];
+    }
+    for word in words(text) {
+        let is_path = word.contains('/') && word.len() > 3;
+        let is_file = word.rsplit_once('.').is_some_and(|(stem, ext)| {
+            stem.len() > 1 && (1..=4).contains(&ext.len()) && ext.chars().all(|c| c.is_ascii_alphabetic())
+        });
+        if is_path || is_file {
+            found.insert(word.to_string());
+        }
+    }
+    found
+}
+
+/// The number after the last `.` of a hierarchical ID (`bp6-07y.3` -> 3)
+fn sequence_number(id: &str) -> Option<(&str, u32)> {
+    let (stem, n) = id.rsplit_once('.')?;
+    Some((stem, n.parse().ok()?))
+}
+
+/// Suggested blocking edges among `beads`, limited to the subtree of `scope`
+/// when given, strongest first
+pub fn suggest(beads: &[Bead], scope: Option<&str>, workflow: &WorkflowConfig) -> Result<Vec<DependencySuggestion>, BertError> {
+    let live: Vec<Bead> = beads.iter().filter(|b| b.status != "tombstone").cloned().collect();
+    let in_scope: Option<HashSet<&str>> = match scope {
+        Some(root) if !live.iter().any(|b| b.id == root) => {
+            return Err(BertError::NotFound(format!("Bead with ID {} not found", root)));
+        }
+        Some(root) => Some(crate::graph_export::subtree_ids(&live, root)),
+        None => None,
+    };
+    let candidates: Vec<&Bead> = live
+        .iter()
+        .filter(|b| in_scope.as_ref().is_none_or(|s| s.contains(b.id.as_str())))
+        .collect();
+    let ids: HashSet<&str> = candidates.iter().map(|b| b.id.as_str()).collect();
+    let graph = crate::bert_core::build_dependency_graph(&live);
+    let by_id: HashMap<&str, &Bead> = candidates.iter().map(|b| (b.id.as_str(), *b)).collect();
+
+    // (blocker, blocked) -> (confidence, reasons)
+    let mut hints: BTreeMap<(String, String), (f64, Vec<String>)> = BTreeMap::new();
+    let mut hint = |blocker: &str, blocked: &str, confidence: f64, reason: String| {
+        if blocker == blocked || graph.reaches(blocker, blocked) || graph.reaches(blocked, blocker) {
+            return;
+        }
+        if by_id.get(blocked).is_none_or(|b| workflow.is_done(&b.status)) {
+            return;
+        }
+        let entry = hints.entry((blocker.to_string(), blocked.to_string())).or_insert((0.0, Vec::new()));
+        entry.0 = entry.0.max(confidence);
+        entry.1.push(reason);
+    };
+
+    for bead in &candidates {
+        for blocker in after_references(&text_of(bead), &ids) {
+            hint(blocker, &bead.id, PHRASE_CONFIDENCE, format!("{} says it comes after {}", bead.id, blocker));
+        }
+    }
+
+    let mut siblings: BTreeMap<&str, Vec<&Bead>> = BTreeMap::new();
+    for bead in &candidates {
+        if let Some(parent) = parent_of(bead) {
+            siblings.entry(parent).or_default().push(bead);
+        }
+    }
+    for group in siblings.values() {
+        for producer in group {
+            let produced = artifacts(&producer.title);
+            for consumer in group.iter().filter(|c| c.id != producer.id) {
+                // A sibling naming the artifact in its own title works on it too
+                let used = artifacts(&text_of(consumer));
+                let consumer_title = artifacts(&consumer.title);
+                for artifact in produced.iter().filter(|a| used.contains(*a) && !consumer_title.contains(*a)) {
+                    hint(
+                        &producer.id,
+                        &consumer.id,
+                        ARTIFACT_CONFIDENCE,
+                        format!("{} uses {}, which {} produces", consumer.id, artifact, producer.id),
+                    );
+                }
+            }
+        }
+
+        let mut numbered: Vec<(&str, u32, &str)> = group
+            .iter()
+            .filter_map(|b| sequence_number(&b.id).map(|(stem, n)| (stem, n, b.id.as_str())))
+            .collect();
+        numbered.sort();
+        for pair in numbered.windows(2) {
+            let ((stem_a, n_a, a), (stem_b, n_b, b)) = (pair[0], pair[1]);
+            if stem_a == stem_b && n_b == n_a + 1 {
+                hint(a, b, SEQUENCE_CONFIDENCE, format!("{} follows {} in numbering", b, a));
+            }
+        }
+    }
+
+    let mut suggestions: Vec<DependencySuggestion> = hints
+        .into_iter()
+        .map(|((blocker_id, blocked_id), (confidence, reasons))| DependencySuggestion {
+            command: format!("bd dep add {} {} --type blocks", blocked_id, blocker_id),
+            blocker_id,
+            blocked_id,
+            confidence,
+            reasons,
+            proposal_id: None,
+        })
+        .collect();
+    suggestions.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    Ok(suggestions)
+}
+
+/// Suggest missing blocking dependencies in the project, or in the subtree of
+/// `scope`, and queue each as a pending proposal to approve or reject
+#[tauri::command]
+pub fn suggest_dependencies(
+    scope: Option<String>,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    app_handle: AppHandle,
+    state: State<'_, AgentState>,
+) -> Result<Vec<DependencySuggestion>, BertError> {
+    let ctx = crate::project::context_for(&window, project_handle.as_deref())?;
+    let workflow = WorkflowConfig::load_for_beads_file(&ctx.beads_path()?)?;
+    let beads = crate::bd::load_beads(&ctx)?;
+
+    let mut suggestions = suggest(&beads, scope.as_deref(), &workflow)?;
+    for suggestion in &mut suggestions {
+        let (proposal, added) = state.proposals.propose(PROPOSAL_SOURCE, Some(&suggestion.blocked_id), &suggestion.command);
+        suggestion.proposal_id = Some(proposal.id.clone());
+        if added {
+            emit_scoped(&app_handle, EventScope::Window(window.label()), "proposal-added", proposal);
+        }
+    }
+    eprintln!("🔗 suggest_dependencies: {} suggestions in {}", suggestions.len(), scope.as_deref().unwrap_or("the project"));
+    Ok(suggestions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bead(id: &str, title: &str, description: &str, parent: Option<&str>, blockers: &[&str]) -> Bead {
+        let mut dependencies: Vec<_> = blockers
+            .iter()
+            .map(|on| json!({ "issue_id": id, "depends_on_id": on, "type": "blocks" }))
+            .collect();
+        if let Some(parent) = parent {
+            dependencies.push(json!({ "issue_id": id, "depends_on_id": parent, "type": "parent-child" }));
+        }
+        crate::test_bead(json!({
+            "id": id,
+            "title": title,
+            "description": description,
+            "dependencies": dependencies,
+        }))
+    }
+
+    fn pairs(suggestions: &[DependencySuggestion]) -> Vec<(&str, &str, f64)> {
+        suggestions.iter().map(|s| (s.blocker_id.as_str(), s.blocked_id.as_str(), s.confidence)).collect()
+    }
+
+    #[test]
+    fn test_after_phrases_name_blockers() {
+        let ids = HashSet::from(["bp-1", "bp-2"]);
+        assert_eq!(after_references("Do this after bp-1.", &ids), vec!["bp-1"]);
+        assert_eq!(after_references("Depends on task bp-2; see bp-1", &ids), vec!["bp-2"]);
+        assert!(after_references("Unlike bp-1, this is quick", &ids).is_empty());
+    }
+
+    #[test]
+    fn test_suggestions_from_phrases_artifacts_and_numbering() {
+        let beads = vec![
+            bead("f", "Feature", "", None, &[]),
+            bead("f.1", "Add `parse_config`", "", Some("f"), &[]),
+            bead("f.2", "Load settings", "Calls `parse_config` on startup", Some("f"), &[]),
+            bead("f.3", "Docs", "Write these after f.2", Some("f"), &[]),
+            bead("other", "Unrelated", "", None, &[]),
+        ];
+        let suggestions = suggest(&beads, None, &WorkflowConfig::default()).unwrap();
+        assert_eq!(pairs(&suggestions), vec![("f.2", "f.3", 0.9), ("f.1", "f.2", 0.6)]);
+        assert_eq!(suggestions[1].reasons.len(), 2);
+        assert_eq!(suggestions[0].command, "bd dep add f.3 f.2 --type blocks");
+    }
+
+    #[test]
+    fn test_existing_paths_and_scope_are_respected() {
+        let beads = vec![
+            bead("f", "Feature", "", None, &[]),
+            bead("f.1", "One", "", Some("f"), &[]),
+            bead("f.2", "Two", "", Some("f"), &[]),
+            bead("f.3", "Three", "", Some("f"), &["f.1"]),
+            bead("g", "Other", "", None, &[]),
+            bead("g.1", "One", "", Some("g"), &[]),
+            bead("g.2", "Two", "", Some("g"), &[]),
+        ];
+        // f.1 already blocks f.3; the numbering still links f.1 -> f.2 -> f.3
+        let suggestions = suggest(&beads, Some("f"), &WorkflowConfig::default()).unwrap();
+        assert_eq!(pairs(&suggestions), vec![("f.1", "f.2", 0.4), ("f.2", "f.3", 0.4)]);
+
+        let all = suggest(&beads, None, &WorkflowConfig::default()).unwrap();
+        assert!(pairs(&all).contains(&("g.1", "g.2", 0.4)));
+        assert!(matches!(suggest(&beads, Some("zz"), &WorkflowConfig::default()), Err(BertError::NotFound(_))));
+    }
+}
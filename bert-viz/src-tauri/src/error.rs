@@ -0,0 +1,187 @@
+//! Structured errors returned by Tauri commands
+//!
+//! Commands used to return `Result<_, String>`, leaving the frontend to guess
+//! from the message whether `bd` is missing, the beads file is locked or a line
+//! failed to parse. `BertError` serializes as
+//! `{ "code": "bd_not_installed", "message": "...", "recoverable": true }` so the
+//! UI can offer targeted remediation (install prompts, retry buttons).
+//!
+//! Modules that still use `String` errors interoperate through the `From`
+//! impls in both directions.
+
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BertError {
+    /// The `bd` CLI is not on the PATH
+    BdNotInstalled,
+    /// `bd` ran but exited with an error (stderr)
+    BdCommandFailed(String),
+    /// An agent CLI (claude, gemini) is not on the PATH (message includes install hint)
+    CliNotInstalled(String),
+    /// No `.beads/issues.jsonl` for the project
+    BeadsFileNotFound(String),
+    /// No `.beads` directory above the project path
+    ProjectNotFound(String),
+    /// The beads file is locked or mid-write by another process, or a file
+    /// operation would block or timed out
+    FileLocked(String),
+    /// A file or command output could not be parsed
+    Parse(String),
+    /// A bead, session or window does not exist
+    NotFound(String),
+    /// The request itself is invalid (bad IDs, dependency cycles, ...)
+    InvalidInput(String),
+    Io(String),
+    /// Agent session failures other than a missing CLI
+    Agent(String),
+    /// Window creation or manipulation failed
+    Window(String),
+    /// A git command failed (stderr)
+    Git(String),
+    /// A status change the project's transition policy rejects; the code
+    /// names the transition
+    Transition(crate::transitions::TransitionKind, String),
+    /// A change to a project opened from a shared snapshot export
+    ReadOnlySnapshot(String),
+    Internal(String),
+}
+
+impl BertError {
+    /// Stable identifier the frontend matches on
+    pub fn code(&self) -> &'static str {
+        match self {
+            BertError::BdNotInstalled => "bd_not_installed",
+            BertError::BdCommandFailed(_) => "bd_command_failed",
+            BertError::CliNotInstalled(_) => "cli_not_installed",
+            BertError::BeadsFileNotFound(_) => "beads_file_not_found",
+            BertError::ProjectNotFound(_) => "project_not_found",
+            BertError::FileLocked(_) => "file_locked",
+            BertError::Parse(_) => "parse_error",
+            BertError::NotFound(_) => "not_found",
+            BertError::InvalidInput(_) => "invalid_input",
+            BertError::Io(_) => "io_error",
+            BertError::Agent(_) => "agent_error",
+            BertError::Window(_) => "window_error",
+            BertError::Git(_) => "git_error",
+            BertError::Transition(kind, _) => kind.code(),
+            BertError::ReadOnlySnapshot(_) => "read_only_snapshot",
+            BertError::Internal(_) => "internal_error",
+        }
+    }
+
+    /// Whether the user can resolve the error (install a tool, open another
+    /// project, fix the input, retry) without a code change
+    pub fn recoverable(&self) -> bool {
+        matches!(
+            self,
+            BertError::BdNotInstalled
+                | BertError::CliNotInstalled(_)
+                | BertError::BeadsFileNotFound(_)
+                | BertError::ProjectNotFound(_)
+                | BertError::FileLocked(_)
+                | BertError::InvalidInput(_)
+                | BertError::Transition(..)
+        )
+    }
+}
+
+impl fmt::Display for BertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BertError::BdNotInstalled => write!(
+                f,
+                "The 'bd' CLI is not found in the PATH. Please ensure it is installed and available."
+            ),
+            BertError::BdCommandFailed(msg)
+            | BertError::CliNotInstalled(msg)
+            | BertError::BeadsFileNotFound(msg)
+            | BertError::ProjectNotFound(msg)
+            | BertError::FileLocked(msg)
+            | BertError::Parse(msg)
+            | BertError::NotFound(msg)
+            | BertError::InvalidInput(msg)
+            | BertError::Io(msg)
+            | BertError::Agent(msg)
+            | BertError::Window(msg)
+            | BertError::Git(msg)
+            | BertError::Transition(_, msg)
+            | BertError::ReadOnlySnapshot(msg)
+            | BertError::Internal(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl std::error::Error for BertError {}
+
+impl Serialize for BertError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("BertError", 3)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("recoverable", &self.recoverable())?;
+        state.end()
+    }
+}
+
+impl From<String> for BertError {
+    fn from(message: String) -> Self {
+        BertError::Internal(message)
+    }
+}
+
+impl From<&str> for BertError {
+    fn from(message: &str) -> Self {
+        BertError::Internal(message.to_string())
+    }
+}
+
+/// I/O that would block, timed out or was interrupted is worth retrying, so
+/// it becomes `FileLocked`; everything else (a denied permission, a full
+/// disk) is `Io`
+impl From<std::io::Error> for BertError {
+    fn from(e: std::io::Error) -> Self {
+        use std::io::ErrorKind;
+        match e.kind() {
+            ErrorKind::WouldBlock | ErrorKind::TimedOut | ErrorKind::Interrupted => BertError::FileLocked(e.to_string()),
+            _ => BertError::Io(e.to_string()),
+        }
+    }
+}
+
+impl From<serde_json::Error> for BertError {
+    fn from(e: serde_json::Error) -> Self {
+        BertError::Parse(e.to_string())
+    }
+}
+
+impl From<BertError> for String {
+    fn from(e: BertError) -> Self {
+        e.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serializes_code_message_and_recoverable() {
+        let json = serde_json::to_value(BertError::BdNotInstalled).unwrap();
+        assert_eq!(json["code"], "bd_not_installed");
+        assert_eq!(json["recoverable"], true);
+        assert!(json["message"].as_str().unwrap().contains("'bd'"));
+
+        let json = serde_json::to_value(BertError::Parse("bad line 3".into())).unwrap();
+        assert_eq!(json, serde_json::json!({ "code": "parse_error", "message": "bad line 3", "recoverable": false }));
+    }
+
+    #[test]
+    fn test_string_round_trip() {
+        let err: BertError = String::from("boom").into();
+        assert_eq!(err, BertError::Internal("boom".into()));
+        let msg: String = BertError::NotFound("Bead x not found".into()).into();
+        assert_eq!(msg, "Bead x not found");
+    }
+}
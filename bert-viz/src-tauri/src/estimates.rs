@@ -0,0 +1,341 @@
+//! Estimate suggestions for unestimated beads
+//!
+//! Beads without an estimate get a one-cell bar in the Gantt chart and count
+//! as zero in roll-ups. `suggest` fills the gaps from two heuristics:
+//!
+//! - the median cycle time of finished beads with a similar title (shared
+//!   title words), converted to working minutes (strongest)
+//! - the median estimate of the bead's estimated siblings
+//!
+//! Like dependency suggestions, each one carries a `bd update --estimate`
+//! command that `suggest_estimates` queues as a proposal for batch approval.
+//! With `useAgent`, a product manager session is also handed the unestimated
+//! beads and the heuristic guesses; the commands it replies with show up as
+//! proposals the same way.
+
+use chrono::DateTime;
+use serde::Serialize;
+use std::collections::HashSet;
+use tauri::{AppHandle, Emitter, State};
+use uuid::Uuid;
+
+use crate::agent::AgentState;
+use crate::error::BertError;
+use crate::events::{emit_scoped, EventScope};
+use crate::schedule::SchedulingConfig;
+use crate::workflow::WorkflowConfig;
+use crate::Bead;
+
+/// Pseudo session the suggestions are queued under
+pub const PROPOSAL_SOURCE: &str = "estimate-suggestions";
+
+const HISTORY_CONFIDENCE: f64 = 0.7;
+const SIBLING_CONFIDENCE: f64 = 0.5;
+
+/// Share of title words two beads need in common to count as similar
+const SIMILARITY_THRESHOLD: f64 = 0.5;
+
+/// Words too common to make two titles similar
+const STOP_WORDS: &[&str] = &["the", "and", "for", "with", "from", "into", "add", "fix", "update"];
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EstimateSuggestion {
+    pub bead_id: String,
+    pub title: String,
+    /// Suggested estimate in minutes
+    pub estimate: u32,
+    /// 0..1, from the strongest heuristic that applied
+    pub confidence: f64,
+    /// One entry per heuristic that applied
+    pub reasons: Vec<String>,
+    pub command: String,
+    /// Pending proposal to approve; set once queued
+    pub proposal_id: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EstimateSuggestions {
+    pub suggestions: Vec<EstimateSuggestion>,
+    /// Agent session asked for further estimates, when requested
+    pub session_id: Option<String>,
+}
+
+/// Parent through the first parent-child dependency, as in the WBS tree
+fn parent_of(bead: &Bead) -> Option<&str> {
+    bead.dependencies.iter().find(|d| d.r#type == "parent-child").map(|d| d.depends_on_id.as_str())
+}
+
+/// Lowercased significant words of a title
+fn title_words(title: &str) -> HashSet<String> {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|w| w.len() > 2 && !STOP_WORDS.contains(&w.as_str()))
+        .collect()
+}
+
+/// Shared words over all words of two titles (Jaccard index)
+fn similarity(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+fn median(mut values: Vec<u32>) -> Option<u32> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    let mid = values.len() / 2;
+    Some(if values.len() % 2 == 0 { (values[mid - 1] + values[mid]) / 2 } else { values[mid] })
+}
+
+/// Working minutes between creating and closing a done bead: each full day
+/// counts `hours_per_day`, the rest at most that much
+pub(crate) fn cycle_minutes(bead: &Bead, hours_per_day: f64) -> Option<u32> {
+    let created = DateTime::parse_from_rfc3339(bead.created_at.as_deref()?).ok()?;
+    let closed = DateTime::parse_from_rfc3339(bead.closed_at.as_deref()?).ok()?;
+    let elapsed = (closed - created).num_minutes();
+    if elapsed <= 0 {
+        return None;
+    }
+    let day_minutes = (hours_per_day * 60.0).round() as i64;
+    let minutes = (elapsed / (24 * 60)) * day_minutes + (elapsed % (24 * 60)).min(day_minutes);
+    u32::try_from(minutes.max(1)).ok()
+}
+
+/// Estimate suggestions for the unfinished, unestimated tasks among `beads`,
+/// limited to the subtree of `scope` when given, strongest first
+pub fn suggest(
+    beads: &[Bead],
+    scope: Option<&str>,
+    workflow: &WorkflowConfig,
+    scheduling: &SchedulingConfig,
+) -> Result<Vec<EstimateSuggestion>, BertError> {
+    let live: Vec<&Bead> = beads.iter().filter(|b| b.status != "tombstone").collect();
+    let in_scope: Option<HashSet<&str>> = match scope {
+        Some(root) if !live.iter().any(|b| b.id == root) => {
+            return Err(BertError::NotFound(format!("Bead with ID {} not found", root)));
+        }
+        Some(root) => Some(crate::graph_export::subtree_ids(beads, root)),
+        None => None,
+    };
+
+    // Finished beads with a measurable cycle time, by title words
+    let history: Vec<(HashSet<String>, u32)> = live
+        .iter()
+        .filter(|b| workflow.is_done(&b.status))
+        .filter_map(|b| Some((title_words(&b.title), cycle_minutes(b, scheduling.hours_per_day)?)))
+        .collect();
+
+    let targets = live.iter().filter(|b| {
+        b.estimate.is_none()
+            && b.issue_type != "epic"
+            && b.issue_type != "feature"
+            && !workflow.is_done(&b.status)
+            && in_scope.as_ref().is_none_or(|s| s.contains(b.id.as_str()))
+    });
+
+    let mut suggestions = Vec::new();
+    for bead in targets {
+        let mut candidates: Vec<(f64, u32)> = Vec::new();
+        let mut reasons = Vec::new();
+
+        let words = title_words(&bead.title);
+        let similar: Vec<u32> = history
+            .iter()
+            .filter(|(other, _)| similarity(&words, other) >= SIMILARITY_THRESHOLD)
+            .map(|(_, minutes)| *minutes)
+            .collect();
+        let similar_count = similar.len();
+        if let Some(minutes) = median(similar) {
+            candidates.push((HISTORY_CONFIDENCE, minutes));
+            reasons.push(format!("Median cycle time of {} finished bead(s) with a similar title: {} min", similar_count, minutes));
+        }
+
+        if let Some(parent) = parent_of(bead) {
+            let sibling_estimates: Vec<u32> = live
+                .iter()
+                .filter(|b| b.id != bead.id && parent_of(b) == Some(parent))
+                .filter_map(|b| b.estimate)
+                .collect();
+            let sibling_count = sibling_estimates.len();
+            if let Some(minutes) = median(sibling_estimates) {
+                candidates.push((SIBLING_CONFIDENCE, minutes));
+                reasons.push(format!("Median estimate of {} sibling(s) under {}: {} min", sibling_count, parent, minutes));
+            }
+        }
+
+        let Some(&(confidence, estimate)) = candidates.iter().max_by(|a, b| a.0.total_cmp(&b.0)) else {
+            continue;
+        };
+        suggestions.push(EstimateSuggestion {
+            bead_id: bead.id.clone(),
+            title: bead.title.clone(),
+            estimate,
+            confidence,
+            reasons,
+            command: format!("bd update {} --estimate {}", bead.id, estimate),
+            proposal_id: None,
+        });
+    }
+    suggestions.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    Ok(suggestions)
+}
+
+/// Suggest estimates for the unestimated beads of the project, or of the
+/// subtree of `scope`, and queue each as a pending proposal. With `useAgent`,
+/// also start a product manager session that proposes estimates for them.
+#[tauri::command]
+#[allow(non_snake_case, clippy::too_many_arguments)]
+pub fn suggest_estimates(
+    scope: Option<String>,
+    useAgent: Option<bool>,
+    cliBackend: Option<String>,
+    app_handle: AppHandle,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    state: State<'_, AgentState>,
+    settings_state: State<'_, crate::SettingsState>,
+) -> Result<EstimateSuggestions, BertError> {
+    let ctx = crate::project::context_for(&window, project_handle.as_deref())?;
+    let beads_path = ctx.beads_path()?;
+    let workflow = WorkflowConfig::load_for_beads_file(&beads_path)?;
+    let scheduling = SchedulingConfig::load_for_beads_file(&beads_path)?;
+    let beads = crate::bd::load_beads(&ctx)?;
+
+    let mut suggestions = suggest(&beads, scope.as_deref(), &workflow, &scheduling)?;
+    for suggestion in &mut suggestions {
+        let (proposal, added) = state.proposals.propose(PROPOSAL_SOURCE, Some(&suggestion.bead_id), &suggestion.command);
+        suggestion.proposal_id = Some(proposal.id.clone());
+        if added {
+            emit_scoped(&app_handle, EventScope::Window(window.label()), "proposal-added", proposal);
+        }
+    }
+    eprintln!("⏳ suggest_estimates: {} suggestions in {}", suggestions.len(), scope.as_deref().unwrap_or("the project"));
+
+    let mut session_id = None;
+    if useAgent.unwrap_or(false) {
+        let in_scope = scope.as_deref().map(|root| crate::graph_export::subtree_ids(&beads, root));
+        let unestimated: Vec<serde_json::Value> = beads
+            .iter()
+            .filter(|b| b.status != "tombstone" && b.estimate.is_none() && !workflow.is_done(&b.status))
+            .filter(|b| in_scope.as_ref().is_none_or(|s| s.contains(b.id.as_str())))
+            .map(|b| {
+                serde_json::json!({
+                    "id": b.id,
+                    "title": b.title,
+                    "issue_type": b.issue_type,
+                    "description": b.description,
+                    "parent": parent_of(b),
+                    "heuristic": suggestions.iter().find(|s| s.bead_id == b.id).map(|s| s.estimate),
+                })
+            })
+            .collect();
+
+        if !unestimated.is_empty() {
+            let backend = crate::agent::session::resolve_backend(cliBackend.as_deref(), &settings_state)?;
+            let id = Uuid::new_v4().to_string();
+            crate::agent::session::start_session_internal(
+                &app_handle,
+                &state,
+                ctx,
+                id.clone(),
+                backend,
+                "product-manager".to_string(),
+                Some("estimate".to_string()),
+                None,
+                None,
+                Some(serde_json::to_string_pretty(&unestimated)?),
+                false,
+                false,
+            )?;
+            eprintln!("⏳ Asking session {} to estimate {} beads", id, unestimated.len());
+
+            *state.active_session_id.lock().unwrap() = Some(id.clone());
+            let _ = app_handle.emit("active-session-changed", id.clone());
+            session_id = Some(id);
+        }
+    }
+
+    Ok(EstimateSuggestions { suggestions, session_id })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bead(id: &str, title: &str, status: &str, estimate: Option<u32>, parent: Option<&str>) -> Bead {
+        let dependencies: Vec<_> = parent
+            .iter()
+            .map(|p| json!({ "issue_id": id, "depends_on_id": p, "type": "parent-child" }))
+            .collect();
+        crate::test_bead(json!({
+            "id": id,
+            "title": title,
+            "status": status,
+            "estimate": estimate,
+            "dependencies": dependencies,
+        }))
+    }
+
+    fn closed(id: &str, title: &str, created: &str, closed: &str) -> Bead {
+        let mut bead = bead(id, title, "closed", None, None);
+        bead.created_at = Some(created.to_string());
+        bead.closed_at = Some(closed.to_string());
+        bead
+    }
+
+    #[test]
+    fn test_cycle_time_counts_working_hours() {
+        let same_day = closed("a", "x", "2026-10-12T09:00:00Z", "2026-10-12T11:30:00Z");
+        assert_eq!(cycle_minutes(&same_day, 8.0), Some(150));
+        // One full day plus 20 hours, capped at a working day
+        let long = closed("b", "x", "2026-10-12T09:00:00Z", "2026-10-14T05:00:00Z");
+        assert_eq!(cycle_minutes(&long, 8.0), Some(960));
+        assert_eq!(cycle_minutes(&bead("c", "x", "closed", None, None), 8.0), None);
+    }
+
+    #[test]
+    fn test_history_beats_siblings() {
+        let beads = vec![
+            closed("h1", "Parse config file", "2026-10-12T09:00:00Z", "2026-10-12T10:00:00Z"),
+            closed("h2", "Parse the config loader", "2026-10-12T09:00:00Z", "2026-10-12T12:00:00Z"),
+            bead("f", "Feature", "open", None, None),
+            bead("s1", "Sibling one", "open", Some(30), Some("f")),
+            bead("s2", "Sibling two", "open", Some(90), Some("f")),
+            bead("t1", "Parse config", "open", None, Some("f")),
+            bead("t2", "Write docs", "open", None, Some("f")),
+            bead("t3", "Orphan", "open", None, None),
+        ];
+        let suggestions = suggest(&beads, None, &WorkflowConfig::default(), &SchedulingConfig::default()).unwrap();
+        let summary: Vec<(&str, u32, f64)> = suggestions.iter().map(|s| (s.bead_id.as_str(), s.estimate, s.confidence)).collect();
+        // t3 has neither similar history nor estimated siblings
+        assert_eq!(summary, vec![("t1", 120, 0.7), ("t2", 60, 0.5)]);
+        assert_eq!(suggestions[0].reasons.len(), 2);
+        assert_eq!(suggestions[1].command, "bd update t2 --estimate 60");
+    }
+
+    #[test]
+    fn test_scope_limits_suggestions() {
+        let beads = vec![
+            bead("f", "Feature", "open", None, None),
+            bead("f.1", "One", "open", Some(60), Some("f")),
+            bead("f.2", "Two", "open", None, Some("f")),
+            bead("g", "Other", "open", None, None),
+            bead("g.1", "One", "open", Some(60), Some("g")),
+            bead("g.2", "Two", "open", None, Some("g")),
+        ];
+        let scoped = suggest(&beads, Some("f"), &WorkflowConfig::default(), &SchedulingConfig::default()).unwrap();
+        assert_eq!(scoped.iter().map(|s| s.bead_id.as_str()).collect::<Vec<_>>(), vec!["f.2"]);
+        assert!(matches!(
+            suggest(&beads, Some("zz"), &WorkflowConfig::default(), &SchedulingConfig::default()),
+            Err(BertError::NotFound(_))
+        ));
+    }
+}
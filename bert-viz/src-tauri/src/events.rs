@@ -0,0 +1,104 @@
+//! Routing of backend events to the windows that display them
+//!
+//! Session output (`agent-chunk-*`, `agent-stderr`, proposals) only matters
+//! to the window showing that session, and bead changes only to windows with
+//! the project open. Broadcasting them re-renders every other window for
+//! nothing. `emit_scoped` resolves a scope to window labels through
+//! `WindowRegistry` and `ProjectContexts`, and falls back to a broadcast when
+//! no window claims the session or project (e.g. a session shown in the main
+//! window's chat panel).
+//!
+//! Frontends must listen with `getCurrentWebviewWindow().listen`; a global
+//! `listen` receives events sent to any window.
+
+use serde::Serialize;
+use std::collections::BTreeSet;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::project::{owner_window, ProjectContext, ProjectContexts};
+use crate::window::WindowRegistry;
+
+/// Who an event is meant for
+#[derive(Debug, Clone, Copy)]
+pub enum EventScope<'a> {
+    /// One window, by label
+    Window(&'a str),
+    /// The window registered for a session
+    Session(&'a str),
+    /// Windows that have the project (or another project in its repository) open
+    Project(&'a ProjectContext),
+}
+
+/// Where a scoped event goes
+#[derive(Debug, PartialEq, Eq)]
+enum Targets {
+    All,
+    Windows(Vec<String>),
+}
+
+impl Targets {
+    /// Broadcast when nothing claimed the event, so it isn't lost
+    fn or_all(labels: Vec<String>) -> Self {
+        if labels.is_empty() {
+            Targets::All
+        } else {
+            Targets::Windows(labels)
+        }
+    }
+}
+
+fn resolve(app_handle: &AppHandle, scope: EventScope) -> Targets {
+    match scope {
+        EventScope::Window(label) => Targets::Windows(vec![label.to_string()]),
+        EventScope::Session(session_id) => Targets::or_all(
+            app_handle
+                .try_state::<WindowRegistry>()
+                .and_then(|registry| registry.get_window_label(session_id))
+                .into_iter()
+                .collect(),
+        ),
+        EventScope::Project(ctx) => Targets::or_all(
+            app_handle
+                .try_state::<ProjectContexts>()
+                .map(|contexts| {
+                    // Tabs are shown by the window that owns them
+                    let owners: BTreeSet<String> =
+                        contexts.windows_with(ctx).iter().map(|(key, _)| owner_window(key).to_string()).collect();
+                    owners.into_iter().collect()
+                })
+                .unwrap_or_default(),
+        ),
+    }
+}
+
+/// Emit `event` to the windows in `scope`
+pub fn emit_scoped<S: Serialize + Clone>(app_handle: &AppHandle, scope: EventScope, event: &str, payload: S) {
+    match resolve(app_handle, scope) {
+        Targets::All => {
+            if let Err(e) = app_handle.emit(event, payload) {
+                eprintln!("⚠️  Failed to emit {}: {}", event, e);
+            }
+        }
+        Targets::Windows(labels) => {
+            for label in labels {
+                if let Err(e) = app_handle.emit_to(label.as_str(), event, payload.clone()) {
+                    eprintln!("⚠️  Failed to emit {} to {}: {}", event, label, e);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unclaimed_events_are_broadcast() {
+        assert_eq!(Targets::or_all(Vec::new()), Targets::All);
+        assert_eq!(
+            Targets::or_all(vec!["agent-session-1".to_string()]),
+            Targets::Windows(vec!["agent-session-1".to_string()])
+        );
+    }
+}
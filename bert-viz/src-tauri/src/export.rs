@@ -0,0 +1,249 @@
+//! Spreadsheet export of the filtered bead list
+//!
+//! Writes the beads matching the current FilterParams, in the same tree order
+//! the WBS view shows, to CSV or XLSX so project data can be pulled into
+//! spreadsheets without scripting against issues.jsonl.
+
+use rust_xlsxwriter::{Format, Workbook};
+use std::path::Path;
+
+use crate::workflow::WorkflowConfig;
+use crate::{Bead, FilterParams, WBSNode};
+
+/// Column headers, in output order
+pub const EXPORT_COLUMNS: [&str; 14] = [
+    "id",
+    "title",
+    "status",
+    "priority",
+    "issue_type",
+    "owner",
+    "estimate",
+    "labels",
+    "parent",
+    "dependencies",
+    "created_at",
+    "updated_at",
+    "closed_at",
+    "external_reference",
+];
+
+/// Apply the view filters and sort, returning the WBS tree the view shows
+pub fn filter_and_sort_tree(beads: &[Bead], params: &FilterParams, workflow: &WorkflowConfig) -> Vec<WBSNode> {
+    let filtered: Vec<Bead> = crate::bert_core::visible_indices(beads, params, workflow)
+        .into_iter()
+        .map(|i| beads[i].clone())
+        .collect();
+
+    let graph = crate::bert_core::build_dependency_graph(&filtered);
+    let tree = crate::bert_core::build_wbs_tree(&filtered);
+    crate::bert_core::sort_wbs_tree_siblings(tree, &graph, &params.sort_by, &params.sort_order)
+}
+
+/// Apply the view filters and sort, returning beads in depth-first tree order
+pub fn filter_and_sort_beads(beads: &[Bead], params: &FilterParams, workflow: &WorkflowConfig) -> Vec<Bead> {
+    let tree = filter_and_sort_tree(beads, params, workflow);
+
+    fn flatten(nodes: &[WBSNode], out: &mut Vec<Bead>) {
+        for node in nodes {
+            out.push(node.bead.clone());
+            flatten(&node.children, out);
+        }
+    }
+
+    let mut ordered = Vec::with_capacity(beads.len());
+    flatten(&tree, &mut ordered);
+    ordered
+}
+
+/// Flatten a bead into one row of cell values matching EXPORT_COLUMNS
+pub fn bead_to_row(bead: &Bead) -> Vec<String> {
+    let parent = bead
+        .dependencies
+        .iter()
+        .find(|d| d.r#type == "parent-child")
+        .map(|d| d.depends_on_id.clone())
+        .or_else(|| bead.parent.clone())
+        .unwrap_or_default();
+    let blockers: Vec<&str> = bead
+        .dependencies
+        .iter()
+        .filter(|d| d.r#type == "blocks")
+        .map(|d| d.depends_on_id.as_str())
+        .collect();
+
+    vec![
+        bead.id.clone(),
+        bead.title.clone(),
+        bead.status.clone(),
+        bead.priority.to_string(),
+        bead.issue_type.clone(),
+        bead.owner.clone().unwrap_or_default(),
+        bead.estimate.map(|e| e.to_string()).unwrap_or_default(),
+        bead.labels.as_ref().map(|l| l.join(";")).unwrap_or_default(),
+        parent,
+        blockers.join(";"),
+        bead.created_at.clone().unwrap_or_default(),
+        bead.updated_at.clone().unwrap_or_default(),
+        bead.closed_at.clone().unwrap_or_default(),
+        bead.external_reference.clone().unwrap_or_default(),
+    ]
+}
+
+/// Write beads to a CSV file with a header row
+pub fn write_csv(beads: &[Bead], path: &Path) -> Result<(), String> {
+    let mut writer = csv::Writer::from_path(path)
+        .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    writer
+        .write_record(EXPORT_COLUMNS)
+        .map_err(|e| format!("Failed to write CSV header: {}", e))?;
+    for bead in beads {
+        writer
+            .write_record(bead_to_row(bead))
+            .map_err(|e| format!("Failed to write CSV row for {}: {}", bead.id, e))?;
+    }
+    writer.flush().map_err(|e| format!("Failed to flush {}: {}", path.display(), e))
+}
+
+/// Write beads to an XLSX workbook with a single "Beads" sheet
+pub fn write_xlsx(beads: &[Bead], path: &Path) -> Result<(), String> {
+    let mut workbook = Workbook::new();
+    let sheet = workbook.add_worksheet();
+    sheet
+        .set_name("Beads")
+        .map_err(|e| format!("Failed to name worksheet: {}", e))?;
+
+    let header = Format::new().set_bold();
+    for (col, name) in EXPORT_COLUMNS.iter().enumerate() {
+        sheet
+            .write_string_with_format(0, col as u16, *name, &header)
+            .map_err(|e| format!("Failed to write XLSX header: {}", e))?;
+    }
+
+    for (i, bead) in beads.iter().enumerate() {
+        let row = (i + 1) as u32;
+        for (col, value) in bead_to_row(bead).into_iter().enumerate() {
+            let col = col as u16;
+            // Keep numeric columns numeric so spreadsheets can sum/sort them
+            let is_numeric = matches!(EXPORT_COLUMNS[col as usize], "priority" | "estimate");
+            let result = match value.parse::<f64>() {
+                Ok(n) if is_numeric => sheet.write_number(row, col, n).map(|_| ()),
+                _ => sheet.write_string(row, col, &value).map(|_| ()),
+            };
+            result.map_err(|e| format!("Failed to write XLSX row for {}: {}", bead.id, e))?;
+        }
+    }
+
+    sheet.autofit();
+    workbook
+        .save(path)
+        .map_err(|e| format!("Failed to save {}: {}", path.display(), e))
+}
+
+/// Export the currently filtered/sorted beads to CSV.
+/// Returns the number of beads written.
+#[tauri::command]
+pub fn export_beads_csv(params: FilterParams, path: String, window: tauri::Window, project_handle: Option<String>) -> Result<usize, String> {
+    let ctx = crate::project::context_for(&window, project_handle.as_deref())?;
+    let workflow = WorkflowConfig::load_for_beads_file(&ctx.beads_path()?)?;
+    let beads = filter_and_sort_beads(&crate::bd::load_beads(&ctx)?, &params, &workflow);
+    write_csv(&beads, Path::new(&path))?;
+    eprintln!("📤 Exported {} beads to {}", beads.len(), path);
+    Ok(beads.len())
+}
+
+/// Export the currently filtered/sorted beads to an Excel workbook.
+/// Returns the number of beads written.
+#[tauri::command]
+pub fn export_beads_xlsx(params: FilterParams, path: String, window: tauri::Window, project_handle: Option<String>) -> Result<usize, String> {
+    let ctx = crate::project::context_for(&window, project_handle.as_deref())?;
+    let workflow = WorkflowConfig::load_for_beads_file(&ctx.beads_path()?)?;
+    let beads = filter_and_sort_beads(&crate::bd::load_beads(&ctx)?, &params, &workflow);
+    write_xlsx(&beads, Path::new(&path))?;
+    eprintln!("📤 Exported {} beads to {}", beads.len(), path);
+    Ok(beads.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn bead(id: &str, title: &str, status: &str, deps: serde_json::Value) -> Bead {
+        crate::test_bead(serde_json::json!({
+            "id": id,
+            "title": title,
+            "status": status,
+            "labels": ["ui", "urgent"],
+            "dependencies": deps,
+        }))
+    }
+
+    fn sample() -> Vec<Bead> {
+        vec![
+            bead("bp-1", "Epic", "open", serde_json::json!([])),
+            bead(
+                "bp-1.1",
+                "Child, with comma",
+                "open",
+                serde_json::json!([
+                    {"issue_id": "bp-1.1", "depends_on_id": "bp-1", "type": "parent-child"},
+                    {"issue_id": "bp-1.1", "depends_on_id": "bp-2", "type": "blocks"}
+                ]),
+            ),
+            bead("bp-2", "Done", "closed", serde_json::json!([])),
+        ]
+    }
+
+    #[test]
+    fn test_filter_and_sort_respects_hide_closed_and_tree_order() {
+        let params = FilterParams {
+            hide_closed: true,
+            ..FilterParams::default()
+        };
+        let ids: Vec<String> = filter_and_sort_beads(&sample(), &params, &WorkflowConfig::default()).into_iter().map(|b| b.id).collect();
+        assert_eq!(ids, vec!["bp-1", "bp-1.1"]);
+    }
+
+    #[test]
+    fn test_search_includes_ancestors_of_hits() {
+        let params = FilterParams {
+            hide_closed: true,
+            filter_text: "comma".to_string(),
+            include_hierarchy: true,
+            ..FilterParams::default()
+        };
+        let ids: Vec<String> = filter_and_sort_beads(&sample(), &params, &WorkflowConfig::default()).into_iter().map(|b| b.id).collect();
+        assert_eq!(ids, vec!["bp-1", "bp-1.1"]);
+    }
+
+    #[test]
+    fn test_row_columns() {
+        let row = bead_to_row(&sample()[1]);
+        assert_eq!(row.len(), EXPORT_COLUMNS.len());
+        assert_eq!(row[7], "ui;urgent");
+        assert_eq!(row[8], "bp-1");
+        assert_eq!(row[9], "bp-2");
+    }
+
+    #[test]
+    fn test_write_csv_quotes_fields() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("out.csv");
+        write_csv(&sample(), &path).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let mut lines = contents.lines();
+        assert!(lines.next().unwrap().starts_with("id,title,status"));
+        assert!(contents.contains("\"Child, with comma\""));
+        assert_eq!(contents.lines().count(), 4);
+    }
+
+    #[test]
+    fn test_write_xlsx_creates_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("out.xlsx");
+        write_xlsx(&sample(), &path).unwrap();
+        assert!(std::fs::metadata(&path).unwrap().len() > 0);
+    }
+}
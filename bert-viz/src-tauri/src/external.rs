@@ -0,0 +1,365 @@
+//! Dependencies on beads in other projects
+//!
+//! Work split across repos still depends on work elsewhere. A bead lists the
+//! beads it waits on in other projects as `project#bead-id` references under
+//! the `external_dependencies` key of its extra metadata (which round-trips
+//! through bd's `--metadata` blob). The project part is the path of the other
+//! project, absolute or relative to this project's root, or the name it is
+//! registered under in the project list.
+//!
+//! `PortfolioLoader` reads the beads of each referenced project once per pass.
+//! The view model lists a bead's external dependencies with their state after
+//! every load (they aren't part of the cached model, since the other project's
+//! file can change without this one changing), and flags beads waiting on one
+//! as externally blocked. `check_external_dependencies` reports every reference
+//! in the project, including the ones that no longer resolve.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+use crate::bd::BeadOp;
+use crate::error::BertError;
+use crate::project::{context_for, ProjectContext};
+use crate::workflow::WorkflowConfig;
+use crate::{Bead, BeadNode, Project};
+
+/// Key of the references in a bead's extra metadata
+pub(crate) const EXTERNAL_DEPS_KEY: &str = "external_dependencies";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExternalState {
+    /// Not looked up yet
+    Unresolved,
+    /// The bead is finished in its project
+    Done,
+    /// The bead is still open or in progress
+    Open,
+    /// No beads file at the project path
+    MissingProject,
+    /// The project has no such bead
+    MissingBead,
+    /// Not a `project#bead-id` reference
+    Invalid,
+}
+
+/// A dependency on a bead in another project, with what was found there
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalDependency {
+    /// As stored, `project#bead-id`
+    pub reference: String,
+    /// The project directory the reference resolved to
+    pub project_path: Option<String>,
+    pub bead_id: String,
+    pub state: ExternalState,
+    pub title: Option<String>,
+    pub status: Option<String>,
+}
+
+impl ExternalDependency {
+    fn new(reference: &str, state: ExternalState) -> Self {
+        let bead_id = parse_ref(reference).map(|(_, id)| id.to_string()).unwrap_or_default();
+        ExternalDependency { reference: reference.to_string(), project_path: None, bead_id, state, title: None, status: None }
+    }
+
+    /// Whether the dependent bead should show as waiting on it
+    pub fn is_blocking(&self) -> bool {
+        self.state != ExternalState::Done
+    }
+}
+
+/// One external dependency of a bead in the project
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalCheck {
+    /// The bead in this project that waits on it
+    pub from_bead_id: String,
+    #[serde(flatten)]
+    pub dependency: ExternalDependency,
+}
+
+/// Project and bead ID of a `project#bead-id` reference
+pub fn parse_ref(reference: &str) -> Option<(&str, &str)> {
+    let (project, bead_id) = reference.trim().rsplit_once('#')?;
+    let (project, bead_id) = (project.trim(), bead_id.trim());
+    (!project.is_empty() && !bead_id.is_empty() && !bead_id.contains(char::is_whitespace)).then_some((project, bead_id))
+}
+
+/// The bead's external references, as stored
+pub fn external_refs(bead: &Bead) -> Vec<String> {
+    bead.extra_metadata
+        .get(EXTERNAL_DEPS_KEY)
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// The bead's external dependencies before they are looked up
+pub fn pending(bead: &Bead) -> Vec<ExternalDependency> {
+    external_refs(bead)
+        .iter()
+        .map(|r| {
+            let state = if parse_ref(r).is_some() { ExternalState::Unresolved } else { ExternalState::Invalid };
+            ExternalDependency::new(r, state)
+        })
+        .collect()
+}
+
+/// Root of the project a beads file belongs to, also for a file in the sync
+/// branch worktree (`<root>/.git/beads-worktrees/<branch>/.beads`)
+fn project_root_of(beads_path: &Path) -> PathBuf {
+    let in_git_dir = beads_path.ancestors().find(|p| p.file_name().is_some_and(|name| name == ".git"));
+    match in_git_dir.and_then(Path::parent) {
+        Some(root) => root.to_path_buf(),
+        None => beads_path.parent().and_then(Path::parent).unwrap_or(beads_path).to_path_buf(),
+    }
+}
+
+/// Beads of the projects referenced from one project, each loaded once
+pub(crate) struct PortfolioLoader {
+    root: PathBuf,
+    registered: Vec<Project>,
+    projects: HashMap<PathBuf, Option<(Vec<Bead>, WorkflowConfig)>>,
+}
+
+impl PortfolioLoader {
+    pub fn new(root: impl Into<PathBuf>, registered: Vec<Project>) -> Self {
+        PortfolioLoader { root: root.into(), registered, projects: HashMap::new() }
+    }
+
+    /// Loader for references from the project of `beads_path`, which also
+    /// knows the registered project names
+    pub fn for_beads_file(beads_path: &Path) -> Self {
+        Self::new(project_root_of(beads_path), crate::get_projects().unwrap_or_default())
+    }
+
+    fn project_path(&self, project: &str) -> PathBuf {
+        let path = Path::new(project);
+        if path.is_absolute() {
+            return path.to_path_buf();
+        }
+        match self.registered.iter().find(|p| p.name == project) {
+            Some(registered) => PathBuf::from(&registered.path),
+            None => self.root.join(path),
+        }
+    }
+
+    fn load(&mut self, path: &Path) -> Option<&(Vec<Bead>, WorkflowConfig)> {
+        self.projects
+            .entry(path.to_path_buf())
+            .or_insert_with(|| {
+                // The beads file lookup also searches parent directories; the
+                // project has to be at this path
+                if !path.join(".beads").is_dir() {
+                    return None;
+                }
+                let beads_path = ProjectContext::new(path).beads_path().ok()?;
+                let beads = crate::snapshot::read_beads(&beads_path).ok()?;
+                Some((beads, WorkflowConfig::load_for_beads_file(&beads_path).unwrap_or_default()))
+            })
+            .as_ref()
+    }
+
+    /// Look up the bead a reference points to
+    pub fn resolve(&mut self, reference: &str) -> ExternalDependency {
+        let Some((project, bead_id)) = parse_ref(reference) else {
+            return ExternalDependency::new(reference, ExternalState::Invalid);
+        };
+        let path = self.project_path(project);
+        let mut dependency = ExternalDependency::new(reference, ExternalState::MissingProject);
+        dependency.project_path = Some(path.display().to_string());
+
+        let Some((beads, workflow)) = self.load(&path) else { return dependency };
+        match beads.iter().find(|b| b.id == bead_id && b.status != "tombstone") {
+            Some(bead) => {
+                dependency.state = if workflow.is_done(&bead.status) { ExternalState::Done } else { ExternalState::Open };
+                dependency.title = Some(bead.title.clone());
+                dependency.status = Some(bead.status.clone());
+            }
+            None => dependency.state = ExternalState::MissingBead,
+        }
+        dependency
+    }
+}
+
+/// Look up the external dependencies in a view model tree and flag the beads
+/// waiting on them
+pub(crate) fn resolve_tree(nodes: &mut [BeadNode], beads_path: &Path) {
+    fn any_refs(nodes: &[BeadNode]) -> bool {
+        nodes.iter().any(|n| !n.external_dependencies.is_empty() || any_refs(&n.children))
+    }
+    fn walk(nodes: &mut [BeadNode], loader: &mut PortfolioLoader) {
+        for node in nodes {
+            for dependency in &mut node.external_dependencies {
+                *dependency = loader.resolve(&dependency.reference);
+            }
+            node.is_externally_blocked = node.external_dependencies.iter().any(ExternalDependency::is_blocking);
+            walk(&mut node.children, loader);
+        }
+    }
+
+    if any_refs(nodes) {
+        walk(nodes, &mut PortfolioLoader::for_beads_file(beads_path));
+    }
+}
+
+/// Every external dependency in a project, looked up
+pub fn check(beads: &[Bead], loader: &mut PortfolioLoader) -> Vec<ExternalCheck> {
+    beads
+        .iter()
+        .filter(|b| b.status != "tombstone")
+        .flat_map(|bead| external_refs(bead).into_iter().map(move |r| (bead.id.clone(), r)))
+        .map(|(from_bead_id, reference)| ExternalCheck { from_bead_id, dependency: loader.resolve(&reference) })
+        .collect()
+}
+
+/// Look up every external dependency in the current project
+#[tauri::command]
+pub fn check_external_dependencies(window: tauri::Window, project_handle: Option<String>) -> Result<Vec<ExternalCheck>, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    let beads_path = ctx.beads_path()?;
+    let checks = check(&crate::bd::load_beads(&ctx)?, &mut PortfolioLoader::for_beads_file(&beads_path));
+
+    let broken = checks
+        .iter()
+        .filter(|c| matches!(c.dependency.state, ExternalState::MissingProject | ExternalState::MissingBead | ExternalState::Invalid))
+        .count();
+    eprintln!("🔗 External dependencies: {} ({} broken)", checks.len(), broken);
+    Ok(checks)
+}
+
+fn save_refs(ctx: &ProjectContext, app_handle: &AppHandle, mut bead: Bead, refs: Vec<String>) -> Result<(), BertError> {
+    bead.extra_metadata.insert(EXTERNAL_DEPS_KEY.to_string(), serde_json::to_value(refs)?);
+    crate::bd::submit_op(ctx, app_handle, BeadOp::Update { bead })?;
+    Ok(())
+}
+
+/// Make a bead wait on a bead in another project. The reference must resolve.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn add_external_dependency(
+    beadId: String,
+    reference: String,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    app_handle: AppHandle,
+) -> Result<ExternalDependency, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    let reference = reference.trim().to_string();
+    let dependency = PortfolioLoader::for_beads_file(&ctx.beads_path()?).resolve(&reference);
+    let problem = match dependency.state {
+        ExternalState::Invalid => Some(format!("{} is not a project#bead-id reference", reference)),
+        ExternalState::MissingProject => Some(format!("No beads project at {}", dependency.project_path.as_deref().unwrap_or(&reference))),
+        ExternalState::MissingBead => Some(format!("{} has no bead {}", dependency.project_path.as_deref().unwrap_or(&reference), dependency.bead_id)),
+        _ => None,
+    };
+    if let Some(message) = problem {
+        return Err(BertError::InvalidInput(message));
+    }
+
+    let bead = crate::bd::get_bead_by_id(&ctx, &beadId)?;
+    let mut refs = external_refs(&bead);
+    if !refs.contains(&reference) {
+        refs.push(reference.clone());
+        save_refs(&ctx, &app_handle, bead, refs)?;
+        eprintln!("🔗 {} now waits on {}", beadId, reference);
+    }
+    Ok(dependency)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn remove_external_dependency(
+    beadId: String,
+    reference: String,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    app_handle: AppHandle,
+) -> Result<(), BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    let bead = crate::bd::get_bead_by_id(&ctx, &beadId)?;
+    let mut refs = external_refs(&bead);
+    let before = refs.len();
+    refs.retain(|r| r != reference.trim());
+    if refs.len() == before {
+        return Err(BertError::InvalidInput(format!("{} has no external dependency {}", beadId, reference)));
+    }
+    save_refs(&ctx, &app_handle, bead, refs)?;
+    eprintln!("🔗 {} no longer waits on {}", beadId, reference);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn bead(id: &str, status: &str, refs: &[&str]) -> Bead {
+        crate::test_bead(json!({
+            "id": id,
+            "status": status,
+            "external_dependencies": refs,
+        }))
+    }
+
+    /// `<tmp>/api` with api-1 closed and api-2 open
+    fn other_project() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("api/.beads")).unwrap();
+        let lines = [
+            r#"{"id":"api-1","title":"Auth endpoint","status":"closed","priority":1,"issue_type":"task"}"#,
+            r#"{"id":"api-2","title":"Billing endpoint","status":"open","priority":1,"issue_type":"task"}"#,
+        ];
+        std::fs::write(dir.path().join("api/.beads/issues.jsonl"), lines.join("\n")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_parse_ref() {
+        assert_eq!(parse_ref("../api#api-1"), Some(("../api", "api-1")));
+        assert_eq!(parse_ref(" /srv/repos/a#b#api-2 "), Some(("/srv/repos/a#b", "api-2")));
+        assert_eq!(parse_ref("api-1"), None);
+        assert_eq!(parse_ref("../api#"), None);
+        assert_eq!(parse_ref("#api-1"), None);
+    }
+
+    #[test]
+    fn test_check_resolves_paths_and_registered_names() {
+        let dir = other_project();
+        let registered = vec![Project { name: "API".into(), path: dir.path().join("api").display().to_string(), is_favorite: false, last_opened: None }];
+        let mut loader = PortfolioLoader::new(dir.path().join("web"), registered);
+        let beads = vec![
+            bead("web-1", "open", &["../api#api-1", "API#api-2"]),
+            bead("web-2", "open", &["../api#api-9", "../mobile#m-1", "nonsense"]),
+            bead("web-3", "open", &[]),
+        ];
+
+        let checks = check(&beads, &mut loader);
+        let states: Vec<(&str, &str, ExternalState)> =
+            checks.iter().map(|c| (c.from_bead_id.as_str(), c.dependency.reference.as_str(), c.dependency.state)).collect();
+        assert_eq!(
+            states,
+            vec![
+                ("web-1", "../api#api-1", ExternalState::Done),
+                ("web-1", "API#api-2", ExternalState::Open),
+                ("web-2", "../api#api-9", ExternalState::MissingBead),
+                ("web-2", "../mobile#m-1", ExternalState::MissingProject),
+                ("web-2", "nonsense", ExternalState::Invalid),
+            ]
+        );
+        assert_eq!(checks[1].dependency.title.as_deref(), Some("Billing endpoint"));
+        assert!(!checks[0].dependency.is_blocking() && checks[1].dependency.is_blocking());
+    }
+
+    #[test]
+    fn test_project_root_of_sync_worktree() {
+        assert_eq!(project_root_of(Path::new("/r/web/.beads/issues.jsonl")), PathBuf::from("/r/web"));
+        assert_eq!(
+            project_root_of(Path::new("/r/web/.git/beads-worktrees/beads-sync/.beads/issues.jsonl")),
+            PathBuf::from("/r/web")
+        );
+    }
+}
@@ -0,0 +1,138 @@
+//! Favorite beads
+//!
+//! Starring a bead sets `is_favorite` in its bd metadata (or on its JSONL
+//! record without bd). The beads watcher already diffs every new version of
+//! the file for the history log; when one of those diffs changes the status
+//! or owner of a favorite, it also emits `favorite-bead-changed` so the
+//! frontend can notify about the beads the user is following.
+
+use serde::Serialize;
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::bead_index::BeadIndex;
+use crate::error::BertError;
+use crate::history::{ChangeKind, FieldChange, HistoryEntry};
+use crate::project::context_for;
+use crate::Bead;
+
+/// Fields whose changes are reported for favorites
+const WATCHED_FIELDS: [&str; 3] = ["status", "owner", "assignee"];
+
+/// Payload of `favorite-bead-changed`
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FavoriteBeadChanged {
+    pub bead_id: String,
+    pub title: String,
+    /// Only the status/owner changes
+    pub changes: Vec<FieldChange>,
+}
+
+fn is_favorite(bead: &Bead) -> bool {
+    bead.is_favorite == Some(true)
+}
+
+fn favorite_changes(index: &BeadIndex, entries: &[(String, HistoryEntry)]) -> Vec<FavoriteBeadChanged> {
+    entries
+        .iter()
+        .filter(|(_, entry)| entry.kind == ChangeKind::Updated)
+        .filter_map(|(bead_id, entry)| {
+            let bead = index.get(bead_id).filter(|b| is_favorite(b))?;
+            let changes: Vec<FieldChange> = entry
+                .changes
+                .iter()
+                .filter(|c| WATCHED_FIELDS.contains(&c.field.as_str()))
+                .cloned()
+                .collect();
+            (!changes.is_empty()).then(|| FavoriteBeadChanged {
+                bead_id: bead_id.clone(),
+                title: bead.title.clone(),
+                changes,
+            })
+        })
+        .collect()
+}
+
+/// Status/owner changes of favorites among the history entries the watcher
+/// just recorded for `beads_path`
+pub fn changes(beads_path: &Path, entries: &[(String, HistoryEntry)]) -> Vec<FavoriteBeadChanged> {
+    if entries.is_empty() {
+        return Vec::new();
+    }
+    match crate::bead_index::index_for(beads_path) {
+        Ok(index) => favorite_changes(&index, entries),
+        Err(e) => {
+            eprintln!("⚠️  Failed to check favorites: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Star or unstar a bead, returning it with its new state
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn toggle_bead_favorite(beadId: String, window: tauri::Window, project_handle: Option<String>, app_handle: AppHandle) -> Result<Bead, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    let mut bead = crate::bead_index::get_bead_internal(&ctx, &beadId)?;
+    let favorite = !is_favorite(&bead);
+    crate::bd::set_favorite_internal(&ctx, &bead, favorite)?;
+    bead.is_favorite = Some(favorite);
+
+    eprintln!("⭐ {} {}", if favorite { "Starred" } else { "Unstarred" }, beadId);
+    crate::project::emit_beads_updated(&app_handle, &ctx);
+    Ok(bead)
+}
+
+/// Starred beads of the project, in file order
+#[tauri::command]
+pub fn get_favorites(window: tauri::Window, project_handle: Option<String>) -> Result<Vec<Bead>, BertError> {
+    let beads = crate::bd::load_beads(&context_for(&window, project_handle.as_deref())?)?;
+    Ok(beads.into_iter().filter(|b| is_favorite(b) && b.status != "tombstone").collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn bead(id: &str, favorite: bool) -> Bead {
+        crate::test_bead(json!({
+            "id": id,
+            "title": format!("Bead {}", id),
+            "status": "in_progress",
+            "is_favorite": favorite,
+        }))
+    }
+
+    fn entry(kind: ChangeKind, fields: &[&str]) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: "2026-03-01T10:00:00Z".to_string(),
+            actor: None,
+            kind,
+            changes: fields
+                .iter()
+                .map(|f| FieldChange { field: f.to_string(), old: Value::Null, new: json!("x") })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_only_watched_changes_of_favorites_are_reported() {
+        let index = BeadIndex::new(vec![bead("a", true), bead("b", false), bead("c", true)]);
+        let entries = vec![
+            ("a".to_string(), entry(ChangeKind::Updated, &["title", "status"])),
+            ("b".to_string(), entry(ChangeKind::Updated, &["status"])),
+            ("c".to_string(), entry(ChangeKind::Updated, &["description"])),
+            ("c".to_string(), entry(ChangeKind::Created, &["owner"])),
+            ("gone".to_string(), entry(ChangeKind::Updated, &["owner"])),
+        ];
+
+        let changed = favorite_changes(&index, &entries);
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].bead_id, "a");
+        assert_eq!(changed[0].title, "Bead a");
+        let fields: Vec<&str> = changed[0].changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, vec!["status"]);
+    }
+}
@@ -0,0 +1,440 @@
+//! Bead history reconstructed from git
+//!
+//! `.beads/issues.jsonl` is committed (on the working branch or the bd sync
+//! branch worktree), so every commit that touched a bead's line is part of its
+//! history. This walks `git log -p` for the beads file and reports which commits
+//! changed a given bead, with author, date, message and the fields that changed.
+//!
+//! It also reports the state of the project's checkout for the header
+//! (`get_git_status`), so the UI can warn about uncommitted bead or code
+//! changes. A watcher on the git dir pushes `git-status-changed` whenever
+//! `HEAD`, the index or the reflog of `HEAD` changes (checkouts, commits,
+//! staging, fetches); edits to the working tree alone only show up on the
+//! next `get_git_status`.
+
+use notify::{Config, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::error::BertError;
+use crate::events::{emit_scoped, EventScope};
+use crate::history::{field_changes, ChangeKind, FieldChange};
+use crate::project::ProjectContext;
+
+/// Separates commits in the `git log` output
+const COMMIT_MARKER: &str = "\u{1e}";
+/// Separates header fields within a commit line
+const FIELD_SEPARATOR: char = '\u{1f}';
+
+const DEFAULT_LIMIT: usize = 100;
+
+/// Files in the git dir whose changes refresh the status
+const WATCHED_GIT_FILES: &[&str] = &["HEAD", "index", "FETCH_HEAD"];
+
+/// Quiet period after a git command before the status is re-read
+const STATUS_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Git dir watchers, one per repository, kept for the lifetime of the app
+static STATUS_WATCHERS: OnceLock<Mutex<HashMap<PathBuf, notify::RecommendedWatcher>>> = OnceLock::new();
+
+/// One commit that touched a bead
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GitHistoryEntry {
+    pub commit: String,
+    pub author: String,
+    pub email: String,
+    /// Author date (ISO 8601)
+    pub date: String,
+    pub message: String,
+    pub kind: ChangeKind,
+    pub changes: Vec<FieldChange>,
+}
+
+fn bead_object(line: &str, bead_id: &str) -> Option<Map<String, Value>> {
+    let obj: Map<String, Value> = serde_json::from_str(line.trim()).ok()?;
+    (obj.get("id").and_then(|v| v.as_str()) == Some(bead_id)).then_some(obj)
+}
+
+/// Parse `git log -p` output produced with the format used by `get_bead_git_history`
+fn parse_git_log(output: &str, bead_id: &str) -> Vec<GitHistoryEntry> {
+    let mut entries = Vec::new();
+
+    for chunk in output.split(COMMIT_MARKER).filter(|c| !c.trim().is_empty()) {
+        let mut lines = chunk.lines();
+        let Some(header) = lines.next() else { continue };
+        let fields: Vec<&str> = header.split(FIELD_SEPARATOR).collect();
+        if fields.len() < 5 {
+            continue;
+        }
+
+        let mut removed = None;
+        let mut added = None;
+        for line in lines {
+            if line.starts_with("---") || line.starts_with("+++") {
+                continue;
+            }
+            if let Some(rest) = line.strip_prefix('-') {
+                removed = removed.or_else(|| bead_object(rest, bead_id));
+            } else if let Some(rest) = line.strip_prefix('+') {
+                added = added.or_else(|| bead_object(rest, bead_id));
+            }
+        }
+
+        let kind = match (&removed, &added) {
+            (None, None) => continue,
+            (None, Some(_)) => ChangeKind::Created,
+            (Some(_), None) => ChangeKind::Deleted,
+            (Some(_), Some(_)) => ChangeKind::Updated,
+        };
+        let changes = field_changes(removed.as_ref(), added.as_ref());
+        if kind == ChangeKind::Updated && changes.is_empty() {
+            continue;
+        }
+
+        entries.push(GitHistoryEntry {
+            commit: fields[0].to_string(),
+            author: fields[1].to_string(),
+            email: fields[2].to_string(),
+            date: fields[3].to_string(),
+            message: fields[4..].join(" "),
+            kind,
+            changes,
+        });
+    }
+
+    entries
+}
+
+/// Escape metacharacters for `git log -G`, which uses POSIX basic regex
+/// (where escaping `+`, `?`, `(` etc. would make them special)
+fn escape_regex(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if "\\.*[]^$".contains(c) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn run_git_log(repo_dir: &Path, bead_id: &str, limit: usize) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("log")
+        .arg("-p")
+        .arg("--unified=0")
+        .arg("--no-color")
+        .arg(format!("-n{}", limit))
+        .arg(format!("-G\"id\":\"{}\"", escape_regex(bead_id)))
+        .arg(format!("--format={}%H%x1f%an%x1f%ae%x1f%aI%x1f%s", COMMIT_MARKER))
+        .arg("--")
+        .arg(".beads/issues.jsonl")
+        .current_dir(repo_dir)
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("git log failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// List the commits that changed a bead, newest first.
+/// Uses the sync-branch worktree when the project is in sync mode.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn get_bead_git_history(
+    beadId: String,
+    limit: Option<usize>,
+    window: tauri::Window,
+    project_handle: Option<String>,
+) -> Result<Vec<GitHistoryEntry>, String> {
+    let beads_path = crate::project::context_for(&window, project_handle.as_deref())?.beads_path()?;
+    // .beads/issues.jsonl -> checkout root (repo or sync worktree)
+    let repo_dir = beads_path
+        .parent()
+        .and_then(|p| p.parent())
+        .ok_or_else(|| format!("Unexpected beads file location: {}", beads_path.display()))?;
+
+    let log = run_git_log(repo_dir, &beadId, limit.unwrap_or(DEFAULT_LIMIT))?;
+    let entries = parse_git_log(&log, &beadId);
+    eprintln!("🕰️  get_bead_git_history({}): {} commits", beadId, entries.len());
+    Ok(entries)
+}
+
+/// Run git in `dir` and return its trimmed stdout
+pub(crate) fn git(dir: &Path, args: &[&str]) -> Result<String, BertError> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| BertError::Git(format!("Failed to run git: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(BertError::Git(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim_end().to_string())
+}
+
+/// Latest commit on the checked out branch
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitSummary {
+    pub commit: String,
+    pub short_commit: String,
+    pub author: String,
+    /// Author date (ISO 8601)
+    pub date: String,
+    pub subject: String,
+}
+
+/// State of the project's checkout
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GitStatus {
+    /// Checked out branch; `None` on a detached HEAD
+    pub branch: Option<String>,
+    /// Tracking branch, e.g. `origin/main`
+    pub upstream: Option<String>,
+    /// Commits not on the upstream yet
+    pub ahead: u32,
+    /// Upstream commits not merged yet
+    pub behind: u32,
+    /// Modified, staged, conflicted and untracked files
+    pub dirty_files: usize,
+    /// Whether any of them are under `.beads`
+    pub beads_dirty: bool,
+    /// `None` before the first commit
+    pub last_commit: Option<CommitSummary>,
+}
+
+/// Payload of `git-status-changed`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GitStatusChanged {
+    repo_root: PathBuf,
+    status: GitStatus,
+}
+
+/// Path of a `git status --porcelain=v2` entry
+fn entry_path(line: &str) -> Option<&str> {
+    // Fields before the path: ordinary 8, renamed 9 (path<TAB>original), unmerged 10
+    let path = match line.split_once(' ')? {
+        ("1", _) => line.splitn(9, ' ').nth(8)?,
+        ("2", _) => line.splitn(10, ' ').nth(9)?.split('\t').next()?,
+        ("u", _) => line.splitn(11, ' ').nth(10)?,
+        ("?", path) => path,
+        _ => return None,
+    };
+    Some(path)
+}
+
+/// Parse `git status --porcelain=v2 --branch` output
+fn parse_status(output: &str) -> GitStatus {
+    let mut status = GitStatus::default();
+    for line in output.lines() {
+        if let Some(header) = line.strip_prefix("# ") {
+            match header.split_once(' ') {
+                Some(("branch.head", head)) if head != "(detached)" => status.branch = Some(head.to_string()),
+                Some(("branch.upstream", upstream)) => status.upstream = Some(upstream.to_string()),
+                Some(("branch.ab", counts)) => {
+                    for count in counts.split_whitespace() {
+                        if let Some(n) = count.strip_prefix('+') {
+                            status.ahead = n.parse().unwrap_or(0);
+                        } else if let Some(n) = count.strip_prefix('-') {
+                            status.behind = n.parse().unwrap_or(0);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        } else if let Some(path) = entry_path(line) {
+            status.dirty_files += 1;
+            status.beads_dirty |= path.starts_with(".beads/");
+        }
+    }
+    status
+}
+
+fn parse_last_commit(output: &str) -> Option<CommitSummary> {
+    let fields: Vec<&str> = output.splitn(5, FIELD_SEPARATOR).collect();
+    let [commit, short_commit, author, date, subject] = fields[..] else { return None };
+    Some(CommitSummary {
+        commit: commit.to_string(),
+        short_commit: short_commit.to_string(),
+        author: author.to_string(),
+        date: date.to_string(),
+        subject: subject.to_string(),
+    })
+}
+
+/// Status of the checkout at `repo_root`
+pub fn read_status(repo_root: &Path) -> Result<GitStatus, BertError> {
+    let mut status = parse_status(&git(repo_root, &["status", "--porcelain=v2", "--branch"])?);
+    // Fails on a branch without commits
+    status.last_commit = git(repo_root, &["log", "-1", "--format=%H%x1f%h%x1f%an%x1f%aI%x1f%s"])
+        .ok()
+        .and_then(|log| parse_last_commit(&log));
+    Ok(status)
+}
+
+/// Push `git-status-changed` for the project whenever its git dir changes,
+/// once per repository
+fn watch_status(app_handle: &AppHandle, ctx: &ProjectContext, repo_root: &Path) -> Result<(), BertError> {
+    let mut watchers = STATUS_WATCHERS.get_or_init(Default::default).lock().unwrap();
+    if watchers.contains_key(repo_root) {
+        return Ok(());
+    }
+
+    // The git dir of a linked worktree holds its own HEAD and index
+    let git_dir = PathBuf::from(git(repo_root, &["rev-parse", "--absolute-git-dir"])?);
+    let (tx, rx) = mpsc::channel::<()>();
+    let mut watcher = notify::RecommendedWatcher::new(
+        move |res: std::result::Result<notify::Event, notify::Error>| match res {
+            Ok(event) => {
+                let relevant = event.paths.iter().any(|p| {
+                    p.file_name().and_then(|n| n.to_str()).is_some_and(|n| WATCHED_GIT_FILES.contains(&n))
+                });
+                if relevant {
+                    let _ = tx.send(());
+                }
+            }
+            Err(e) => eprintln!("Watch error: {:?}", e),
+        },
+        Config::default(),
+    )
+    .map_err(|e| BertError::Io(e.to_string()))?;
+    watcher
+        .watch(&git_dir, RecursiveMode::NonRecursive)
+        .map_err(|e| BertError::Io(format!("Failed to watch {}: {}", git_dir.display(), e)))?;
+    // Commits move the branch ref, not HEAD; the reflog of HEAD records them
+    let reflog = git_dir.join("logs").join("HEAD");
+    if reflog.exists() {
+        if let Err(e) = watcher.watch(&reflog, RecursiveMode::NonRecursive) {
+            eprintln!("⚠️  Failed to watch {}: {}", reflog.display(), e);
+        }
+    }
+    eprintln!("🔍 Watching git status of {}", repo_root.display());
+
+    let (handle, ctx, root) = (app_handle.clone(), ctx.clone(), repo_root.to_path_buf());
+    std::thread::spawn(move || {
+        while rx.recv().is_ok() {
+            // Wait for the git command to finish writing
+            loop {
+                match rx.recv_timeout(STATUS_DEBOUNCE) {
+                    Ok(()) => continue,
+                    Err(RecvTimeoutError::Timeout) => break,
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+            }
+            match read_status(&root) {
+                Ok(status) => emit_scoped(
+                    &handle,
+                    EventScope::Project(&ctx),
+                    "git-status-changed",
+                    GitStatusChanged { repo_root: root.clone(), status },
+                ),
+                Err(e) => eprintln!("⚠️  Failed to refresh git status of {}: {}", root.display(), e),
+            }
+        }
+    });
+
+    watchers.insert(repo_root.to_path_buf(), watcher);
+    Ok(())
+}
+
+/// Branch, upstream divergence, uncommitted changes and last commit of the
+/// open project's checkout. Starts pushing `git-status-changed` for it.
+#[tauri::command]
+pub fn get_git_status(
+    app_handle: AppHandle,
+    window: tauri::Window,
+    project_handle: Option<String>,
+) -> Result<GitStatus, BertError> {
+    let ctx = crate::project::context_for(&window, project_handle.as_deref())?;
+    let repo_root = ctx.repo_root()?;
+    let status = read_status(&repo_root)?;
+    if let Err(e) = watch_status(&app_handle, &ctx, &repo_root) {
+        eprintln!("⚠️  Not watching git status of {}: {}", repo_root.display(), e);
+    }
+    Ok(status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(hash: &str, subject: &str, diff: &str) -> String {
+        format!("{m}{h}\u{1f}Alice\u{1f}alice@example.com\u{1f}2026-01-05T10:00:00+00:00\u{1f}{s}\n\n{d}\n", m = COMMIT_MARKER, h = hash, s = subject, d = diff)
+    }
+
+    #[test]
+    fn test_parse_created_updated_and_unrelated() {
+        let log = [
+            commit(
+                "c3",
+                "close bp6-1",
+                "diff --git a/.beads/issues.jsonl b/.beads/issues.jsonl\n--- a/.beads/issues.jsonl\n+++ b/.beads/issues.jsonl\n@@ -1 +1 @@\n-{\"id\":\"bp6-1\",\"status\":\"open\"}\n+{\"id\":\"bp6-1\",\"status\":\"closed\"}",
+            ),
+            commit("c2", "touch other", "@@ -2 +2 @@\n-{\"id\":\"bp6-10\",\"status\":\"open\"}\n+{\"id\":\"bp6-10\",\"status\":\"closed\"}"),
+            commit("c1", "create", "@@ -0,0 +1 @@\n+{\"id\":\"bp6-1\",\"status\":\"open\"}"),
+        ]
+        .concat();
+
+        let entries = parse_git_log(&log, "bp6-1");
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].commit, "c3");
+        assert_eq!(entries[0].kind, ChangeKind::Updated);
+        assert_eq!(entries[0].changes[0].field, "status");
+        assert_eq!(entries[0].author, "Alice");
+        assert_eq!(entries[1].kind, ChangeKind::Created);
+    }
+
+    #[test]
+    fn test_parse_status() {
+        let output = "# branch.oid 1234abcd\n\
+                      # branch.head main\n\
+                      # branch.upstream origin/main\n\
+                      # branch.ab +2 -1\n\
+                      1 .M N... 100644 100644 100644 aaa bbb src/my file.rs\n\
+                      2 R. N... 100644 100644 100644 aaa bbb R100 src/new.rs\tsrc/old.rs\n\
+                      1 M. N... 100644 100644 100644 aaa bbb .beads/issues.jsonl\n\
+                      ? notes.txt\n\
+                      ! target";
+        let status = parse_status(output);
+        assert_eq!(status.branch.as_deref(), Some("main"));
+        assert_eq!(status.upstream.as_deref(), Some("origin/main"));
+        assert_eq!((status.ahead, status.behind), (2, 1));
+        // Ignored files don't count
+        assert_eq!(status.dirty_files, 4);
+        assert!(status.beads_dirty);
+
+        let detached = parse_status("# branch.oid 1234abcd\n# branch.head (detached)\n");
+        assert_eq!(detached, GitStatus::default());
+    }
+
+    #[test]
+    fn test_parse_last_commit() {
+        let commit = parse_last_commit("abc123\u{1f}abc\u{1f}Alice\u{1f}2026-01-05T10:00:00+00:00\u{1f}close bp6-1: done").unwrap();
+        assert_eq!(commit.short_commit, "abc");
+        assert_eq!(commit.subject, "close bp6-1: done");
+        assert_eq!(parse_last_commit(""), None);
+    }
+
+    #[test]
+    fn test_escape_regex() {
+        assert_eq!(escape_regex("bp6-abc.1"), "bp6-abc\\.1");
+    }
+}
@@ -0,0 +1,246 @@
+//! Text export of the blocking dependency graph
+//!
+//! Renders the blocking dependencies of the project, or of one epic's
+//! subtree, as a Mermaid flowchart or a Graphviz DOT digraph that can be
+//! pasted into docs and PRs. Edges point from the blocker to the blocked
+//! bead. Beads without any blocking dependency in scope are left out, and
+//! critical path edges are drawn in red.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+
+use crate::error::BertError;
+use crate::Bead;
+
+const CRITICAL_COLOR: &str = "#d33";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphFormat {
+    Mermaid,
+    Dot,
+}
+
+/// IDs of `root` and its descendants through parent-child dependencies
+pub(crate) fn subtree_ids<'a>(beads: &'a [Bead], root: &'a str) -> HashSet<&'a str> {
+    let mut children: HashMap<&str, Vec<&str>> = HashMap::new();
+    for bead in beads {
+        // The first parent-child dependency is the parent, as in the WBS tree
+        if let Some(dep) = bead.dependencies.iter().find(|d| d.r#type == "parent-child") {
+            children.entry(dep.depends_on_id.as_str()).or_default().push(bead.id.as_str());
+        }
+    }
+
+    let mut ids = HashSet::new();
+    let mut stack = vec![root];
+    while let Some(id) = stack.pop() {
+        if ids.insert(id) {
+            stack.extend(children.get(id).into_iter().flatten());
+        }
+    }
+    ids
+}
+
+/// Beads and blocking edges (blocker, blocked) to draw, with the IDs on the
+/// critical path of the unfinished ones
+struct Graph<'a> {
+    nodes: Vec<&'a Bead>,
+    edges: Vec<(&'a str, &'a str)>,
+    critical: HashSet<String>,
+}
+
+impl<'a> Graph<'a> {
+    fn new(beads: &'a [Bead], scope: Option<&HashSet<&str>>) -> Self {
+        let in_scope: Vec<&Bead> = beads
+            .iter()
+            .filter(|b| b.status != "tombstone" && scope.is_none_or(|s| s.contains(b.id.as_str())))
+            .collect();
+        let ids: HashSet<&str> = in_scope.iter().map(|b| b.id.as_str()).collect();
+
+        // BTreeSet drops duplicate dependencies and keeps the output stable
+        let mut edges: BTreeSet<(&str, &str)> = BTreeSet::new();
+        for bead in &in_scope {
+            for dep in bead.dependencies.iter().filter(|d| d.r#type == "blocks") {
+                if dep.depends_on_id != bead.id && ids.contains(dep.depends_on_id.as_str()) {
+                    edges.insert((dep.depends_on_id.as_str(), bead.id.as_str()));
+                }
+            }
+        }
+        let linked: HashSet<&str> = edges.iter().flat_map(|&(from, to)| [from, to]).collect();
+        let nodes: Vec<&Bead> = in_scope.into_iter().filter(|b| linked.contains(b.id.as_str())).collect();
+
+        let open: Vec<Bead> = nodes.iter().filter(|b| b.status != "closed").map(|b| (*b).clone()).collect();
+        let open_ids: HashSet<&str> = open.iter().map(|b| b.id.as_str()).collect();
+        let mut successors_map: HashMap<String, Vec<String>> = HashMap::new();
+        for &(from, to) in edges.iter().filter(|(f, t)| open_ids.contains(f) && open_ids.contains(t)) {
+            successors_map.entry(from.to_string()).or_default().push(to.to_string());
+        }
+        let critical = crate::bert_core::find_critical_path(&open, &successors_map);
+
+        Graph { nodes, edges: edges.into_iter().collect(), critical }
+    }
+
+    fn is_critical(&self, from: &str, to: &str) -> bool {
+        self.critical.contains(from) && self.critical.contains(to)
+    }
+}
+
+fn mermaid_label(bead: &Bead) -> String {
+    format!("{}: {}", bead.id, bead.title).replace('"', "#quot;")
+}
+
+fn render_mermaid(graph: &Graph) -> String {
+    // Bead IDs may contain characters Mermaid doesn't allow in node IDs
+    let node_ids: HashMap<&str, String> = graph
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(i, b)| (b.id.as_str(), format!("n{}", i)))
+        .collect();
+
+    let mut out = String::from("flowchart LR\n");
+    for bead in &graph.nodes {
+        let _ = writeln!(out, "    {}[\"{}\"]", node_ids[bead.id.as_str()], mermaid_label(bead));
+    }
+    let mut critical_links = Vec::new();
+    for (i, &(from, to)) in graph.edges.iter().enumerate() {
+        let _ = writeln!(out, "    {} --> {}", node_ids[from], node_ids[to]);
+        if graph.is_critical(from, to) {
+            critical_links.push(i.to_string());
+        }
+    }
+
+    let _ = writeln!(out, "    classDef closed fill:#eee,color:#888");
+    let _ = writeln!(out, "    classDef critical stroke:{},stroke-width:2px", CRITICAL_COLOR);
+    for (class, ids) in [
+        ("closed", graph.nodes.iter().filter(|b| b.status == "closed").collect::<Vec<_>>()),
+        ("critical", graph.nodes.iter().filter(|b| graph.critical.contains(&b.id)).collect()),
+    ] {
+        if !ids.is_empty() {
+            let ids: Vec<&str> = ids.iter().map(|b| node_ids[b.id.as_str()].as_str()).collect();
+            let _ = writeln!(out, "    class {} {}", ids.join(","), class);
+        }
+    }
+    if !critical_links.is_empty() {
+        let _ = writeln!(out, "    linkStyle {} stroke:{},stroke-width:2px", critical_links.join(","), CRITICAL_COLOR);
+    }
+    out
+}
+
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn render_dot(graph: &Graph) -> String {
+    let mut out = String::from("digraph dependencies {\n    rankdir=LR;\n    node [shape=box];\n");
+    for bead in &graph.nodes {
+        let mut attrs = format!("label=\"{}\\n{}\"", dot_escape(&bead.id), dot_escape(&bead.title));
+        if bead.status == "closed" {
+            attrs.push_str(", style=filled, fillcolor=\"#eeeeee\", fontcolor=\"#888888\"");
+        }
+        if graph.critical.contains(&bead.id) {
+            let _ = write!(attrs, ", color=\"{}\", penwidth=2", CRITICAL_COLOR);
+        }
+        let _ = writeln!(out, "    \"{}\" [{}];", dot_escape(&bead.id), attrs);
+    }
+    for &(from, to) in &graph.edges {
+        let attrs = if graph.is_critical(from, to) {
+            format!(" [color=\"{}\", penwidth=2]", CRITICAL_COLOR)
+        } else {
+            String::new()
+        };
+        let _ = writeln!(out, "    \"{}\" -> \"{}\"{};", dot_escape(from), dot_escape(to), attrs);
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Render the blocking dependencies of `beads` in `format`, limited to the
+/// subtree of `scope` when given
+pub fn render(beads: &[Bead], format: GraphFormat, scope: Option<&str>) -> Result<String, BertError> {
+    let scope_ids = match scope {
+        Some(root) => {
+            if !beads.iter().any(|b| b.id == root) {
+                return Err(BertError::NotFound(format!("Bead with ID {} not found", root)));
+            }
+            Some(subtree_ids(beads, root))
+        }
+        None => None,
+    };
+
+    let graph = Graph::new(beads, scope_ids.as_ref());
+    Ok(match format {
+        GraphFormat::Mermaid => render_mermaid(&graph),
+        GraphFormat::Dot => render_dot(&graph),
+    })
+}
+
+/// Export the blocking dependency graph of the project, or of the subtree of
+/// `scope` (typically an epic), as Mermaid or DOT text
+#[tauri::command]
+pub fn export_dependency_graph(format: GraphFormat, scope: Option<String>, window: tauri::Window, project_handle: Option<String>) -> Result<String, BertError> {
+    let beads = crate::bd::load_beads(&crate::project::context_for(&window, project_handle.as_deref())?)?;
+    let graph = render(&beads, format, scope.as_deref())?;
+    eprintln!("📤 Exported the dependency graph of {} as {:?}", scope.as_deref().unwrap_or("the project"), format);
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bead(id: &str, status: &str, deps: &[(&str, &str)]) -> Bead {
+        let dependencies: Vec<_> = deps
+            .iter()
+            .map(|(on, ty)| json!({ "issue_id": id, "depends_on_id": on, "type": ty }))
+            .collect();
+        crate::test_bead(json!({
+            "id": id,
+            "title": format!("Do \"{}\"", id),
+            "status": status,
+            "issue_type": if id.starts_with('e') { "epic" } else { "task" },
+            "dependencies": dependencies,
+        }))
+    }
+
+    /// e1 holds a -> b -> c and a -> d; x under e2 blocks a
+    fn project() -> Vec<Bead> {
+        vec![
+            bead("e1", "open", &[]),
+            bead("e2", "open", &[]),
+            bead("a", "closed", &[("e1", "parent-child"), ("x", "blocks")]),
+            bead("b", "open", &[("e1", "parent-child"), ("a", "blocks")]),
+            bead("c", "open", &[("e1", "parent-child"), ("b", "blocks")]),
+            bead("d", "open", &[("e1", "parent-child"), ("a", "blocks")]),
+            bead("x", "open", &[("e2", "parent-child")]),
+        ]
+    }
+
+    #[test]
+    fn test_dot_highlights_critical_edges() {
+        let dot = render(&project(), GraphFormat::Dot, None).unwrap();
+        assert!(dot.starts_with("digraph dependencies {"));
+        assert!(dot.contains("\"b\" -> \"c\" [color=\"#d33\", penwidth=2];"));
+        assert!(dot.contains("\"a\" -> \"d\";"));
+        // a is closed, so the chain through it isn't critical
+        assert!(dot.contains("\"x\" -> \"a\";"));
+        assert!(dot.contains("label=\"b\\nDo \\\"b\\\"\""));
+        assert!(!dot.contains("\"e1\""));
+    }
+
+    #[test]
+    fn test_mermaid_scoped_to_epic() {
+        let mermaid = render(&project(), GraphFormat::Mermaid, Some("e1")).unwrap();
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(!mermaid.contains("x: "));
+        assert!(mermaid.contains("n0[\"a: Do #quot;a#quot;\"]"));
+        // Edges in order: a -> b, a -> d, b -> c
+        assert!(mermaid.contains("    n0 --> n1\n    n0 --> n3\n    n1 --> n2\n"));
+        assert!(mermaid.contains("class n0 closed"));
+        assert!(mermaid.contains("linkStyle 2 stroke:#d33"));
+
+        assert!(render(&project(), GraphFormat::Mermaid, Some("missing")).is_err());
+    }
+}
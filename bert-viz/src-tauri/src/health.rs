@@ -0,0 +1,444 @@
+//! Project health score
+//!
+//! Runs a few heuristics over the project and turns them into a 0-100 score:
+//! how much of the open work is blocked, in-progress beads nobody has touched
+//! in a while, tasks without an estimate or acceptance criteria, blocking
+//! cycles, and features with too many open children. Every flag lists the
+//! beads behind it and how many points it cost, so the UI can drill down.
+//!
+//! Stale in-progress beads are also listed on their own by `get_stale_beads`,
+//! and `nudge_stale_beads` hands them to a QA engineer session that drafts
+//! messages to their owners and proposes re-triage commands for approval.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tauri::{AppHandle, Emitter, State};
+use uuid::Uuid;
+
+use crate::agent::session::AgentState;
+use crate::dependencies::{DependencyIssueKind, Severity};
+use crate::error::BertError;
+use crate::workflow::{StatusCategory, WorkflowConfig};
+use crate::Bead;
+
+/// Days without an update after which an in-progress bead is stale
+pub const DEFAULT_STALE_DAYS: u32 = 14;
+/// Open children above which a feature should be split
+pub const DEFAULT_MAX_OPEN_CHILDREN: usize = 15;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum HealthFlagKind {
+    /// Open work waiting on other beads
+    BlockedWork,
+    /// In progress without an update for the stale threshold
+    StaleInProgress,
+    MissingEstimate,
+    MissingAcceptanceCriteria,
+    DependencyCycle,
+    /// A feature with more open children than the threshold
+    OversizedFeature,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthFlag {
+    pub kind: HealthFlagKind,
+    pub severity: Severity,
+    pub message: String,
+    /// Beads to drill down into
+    pub bead_ids: Vec<String>,
+    /// Points taken off the score
+    pub penalty: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectHealth {
+    /// 100 for a project with no flags
+    pub score: u32,
+    /// Unfinished tasks (epics and features excluded)
+    pub open_count: usize,
+    pub blocked_count: usize,
+    /// blocked_count / open_count, 0 without open work
+    pub blocked_ratio: f64,
+    /// Worst first
+    pub flags: Vec<HealthFlag>,
+}
+
+/// An in-progress bead nobody has updated for a while
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StaleBead {
+    #[serde(flatten)]
+    pub bead: Bead,
+    /// Whole days since the last update
+    pub days_stale: i64,
+}
+
+fn is_container(bead: &Bead) -> bool {
+    matches!(bead.issue_type.as_str(), "epic" | "feature")
+}
+
+fn parent_of(bead: &Bead) -> Option<&str> {
+    bead.dependencies
+        .iter()
+        .find(|d| d.r#type == "parent-child")
+        .map(|d| d.depends_on_id.as_str())
+}
+
+/// Last update of a bead, falling back to its creation
+pub(crate) fn last_touched(bead: &Bead) -> Option<DateTime<Utc>> {
+    bead.updated_at
+        .as_deref()
+        .or(bead.created_at.as_deref())
+        .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// In-progress beads not updated for more than `threshold_days` as of `now`,
+/// least recently touched first
+pub fn stale_beads<'a>(beads: &'a [Bead], workflow: &WorkflowConfig, threshold_days: u32, now: DateTime<Utc>) -> Vec<&'a Bead> {
+    let cutoff = now - Duration::days(threshold_days as i64);
+    let mut stale: Vec<(DateTime<Utc>, &Bead)> = beads
+        .iter()
+        .filter(|b| workflow.category_of(&b.status) == StatusCategory::Doing)
+        .filter_map(|b| last_touched(b).filter(|t| *t < cutoff).map(|t| (t, b)))
+        .collect();
+    stale.sort_by_key(|(touched, _)| *touched);
+    stale.into_iter().map(|(_, b)| b).collect()
+}
+
+fn stale_report(beads: &[Bead], workflow: &WorkflowConfig, threshold_days: u32, now: DateTime<Utc>) -> Vec<StaleBead> {
+    stale_beads(beads, workflow, threshold_days, now)
+        .into_iter()
+        .map(|bead| StaleBead {
+            days_stale: last_touched(bead).map(|t| (now - t).num_days()).unwrap_or_default(),
+            bead: bead.clone(),
+        })
+        .collect()
+}
+
+/// Penalty proportional to `count / total`, worth `weight` when all are affected
+fn ratio_penalty(count: usize, total: usize, weight: f64) -> u32 {
+    if total == 0 {
+        0
+    } else {
+        (weight * count as f64 / total as f64).round() as u32
+    }
+}
+
+fn ids(beads: &[&Bead]) -> Vec<String> {
+    beads.iter().map(|b| b.id.clone()).collect()
+}
+
+pub fn assess(
+    beads: &[Bead],
+    workflow: &WorkflowConfig,
+    stale_days: u32,
+    max_open_children: usize,
+    now: DateTime<Utc>,
+) -> ProjectHealth {
+    let beads: Vec<Bead> = beads.iter().filter(|b| b.status != "tombstone").cloned().collect();
+    let done: HashSet<&str> = beads
+        .iter()
+        .filter(|b| workflow.is_done(&b.status))
+        .map(|b| b.id.as_str())
+        .collect();
+    let open: Vec<&Bead> = beads
+        .iter()
+        .filter(|b| !is_container(b) && !done.contains(b.id.as_str()))
+        .collect();
+
+    let mut flags = Vec::new();
+
+    let blocked: Vec<&Bead> = open
+        .iter()
+        .copied()
+        .filter(|b| {
+            workflow.category_of(&b.status) == StatusCategory::Blocked
+                || b.dependencies.iter().any(|d| d.r#type == "blocks" && !done.contains(d.depends_on_id.as_str()))
+        })
+        .collect();
+    let blocked_ratio = if open.is_empty() { 0.0 } else { blocked.len() as f64 / open.len() as f64 };
+    if !blocked.is_empty() {
+        flags.push(HealthFlag {
+            kind: HealthFlagKind::BlockedWork,
+            severity: if blocked_ratio > 0.5 { Severity::Error } else { Severity::Warning },
+            message: format!("{} of {} open tasks are blocked", blocked.len(), open.len()),
+            bead_ids: ids(&blocked),
+            penalty: ratio_penalty(blocked.len(), open.len(), 30.0),
+        });
+    }
+
+    let stale = stale_beads(&beads, workflow, stale_days, now);
+    if !stale.is_empty() {
+        flags.push(HealthFlag {
+            kind: HealthFlagKind::StaleInProgress,
+            severity: Severity::Warning,
+            message: format!("{} in-progress beads not updated for {} days", stale.len(), stale_days),
+            bead_ids: ids(&stale),
+            penalty: (stale.len() as u32 * 5).min(20),
+        });
+    }
+
+    let no_estimate: Vec<&Bead> = open.iter().copied().filter(|b| b.estimate.unwrap_or(0) == 0).collect();
+    if !no_estimate.is_empty() {
+        flags.push(HealthFlag {
+            kind: HealthFlagKind::MissingEstimate,
+            severity: Severity::Warning,
+            message: format!("{} open tasks have no estimate", no_estimate.len()),
+            bead_ids: ids(&no_estimate),
+            penalty: ratio_penalty(no_estimate.len(), open.len(), 15.0),
+        });
+    }
+
+    let no_criteria: Vec<&Bead> = open
+        .iter()
+        .copied()
+        .filter(|b| b.acceptance_criteria.as_ref().is_none_or(|c| c.is_empty()))
+        .collect();
+    if !no_criteria.is_empty() {
+        flags.push(HealthFlag {
+            kind: HealthFlagKind::MissingAcceptanceCriteria,
+            severity: Severity::Warning,
+            message: format!("{} open tasks have no acceptance criteria", no_criteria.len()),
+            bead_ids: ids(&no_criteria),
+            penalty: ratio_penalty(no_criteria.len(), open.len(), 10.0),
+        });
+    }
+
+    let cycles: Vec<_> = crate::dependencies::validate(&beads, false)
+        .issues
+        .into_iter()
+        .filter(|i| i.kind == DependencyIssueKind::Cycle)
+        .collect();
+    for (i, cycle) in cycles.into_iter().enumerate() {
+        flags.push(HealthFlag {
+            kind: HealthFlagKind::DependencyCycle,
+            severity: Severity::Error,
+            message: cycle.message,
+            bead_ids: cycle.bead_ids,
+            // 10 points for each of the first two cycles
+            penalty: if i < 2 { 10 } else { 0 },
+        });
+    }
+
+    let mut open_children: HashMap<&str, Vec<&Bead>> = HashMap::new();
+    for bead in beads.iter().filter(|b| !done.contains(b.id.as_str())) {
+        if let Some(parent) = parent_of(bead) {
+            open_children.entry(parent).or_default().push(bead);
+        }
+    }
+    let mut oversized = 0;
+    for feature in beads.iter().filter(|b| b.issue_type == "feature" && !done.contains(b.id.as_str())) {
+        let Some(children) = open_children.get(feature.id.as_str()) else { continue };
+        if children.len() <= max_open_children {
+            continue;
+        }
+        flags.push(HealthFlag {
+            kind: HealthFlagKind::OversizedFeature,
+            severity: Severity::Warning,
+            message: format!("{} has {} open children; consider splitting it", feature.id, children.len()),
+            bead_ids: std::iter::once(feature.id.clone()).chain(children.iter().map(|b| b.id.clone())).collect(),
+            // 5 points for each of the first three features
+            penalty: if oversized < 3 { 5 } else { 0 },
+        });
+        oversized += 1;
+    }
+
+    // Stable, so flags of the same weight keep the order above
+    flags.sort_by_key(|f| (f.severity != Severity::Error, std::cmp::Reverse(f.penalty)));
+    let penalty: u32 = flags.iter().map(|f| f.penalty).sum();
+
+    ProjectHealth {
+        score: 100u32.saturating_sub(penalty),
+        open_count: open.len(),
+        blocked_count: blocked.len(),
+        blocked_ratio,
+        flags,
+    }
+}
+
+/// Health score and risk flags of the current project. `staleDays` and
+/// `maxOpenChildren` default to 14 and 15.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn get_project_health(
+    staleDays: Option<u32>,
+    maxOpenChildren: Option<usize>,
+    window: tauri::Window,
+    project_handle: Option<String>,
+) -> Result<ProjectHealth, BertError> {
+    let ctx = crate::project::context_for(&window, project_handle.as_deref())?;
+    let workflow = WorkflowConfig::load_for_beads_file(&ctx.beads_path()?)?;
+    let beads = crate::bd::load_beads(&ctx)?;
+
+    let health = assess(
+        &beads,
+        &workflow,
+        staleDays.unwrap_or(DEFAULT_STALE_DAYS),
+        maxOpenChildren.unwrap_or(DEFAULT_MAX_OPEN_CHILDREN),
+        Utc::now(),
+    );
+    eprintln!("🩺 Project health: {} ({} flags)", health.score, health.flags.len());
+    Ok(health)
+}
+
+/// In-progress beads of the current project not updated for more than
+/// `thresholdDays` (14 by default), least recently touched first
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn get_stale_beads(thresholdDays: Option<u32>, window: tauri::Window, project_handle: Option<String>) -> Result<Vec<StaleBead>, BertError> {
+    let ctx = crate::project::context_for(&window, project_handle.as_deref())?;
+    let workflow = WorkflowConfig::load_for_beads_file(&ctx.beads_path()?)?;
+    let beads = crate::bd::load_beads(&ctx)?;
+    Ok(stale_report(&beads, &workflow, thresholdDays.unwrap_or(DEFAULT_STALE_DAYS), Utc::now()))
+}
+
+/// Start a QA engineer session on the stale beads that drafts nudges to their
+/// owners and proposes re-triage `bd` commands, which show up as proposals
+/// awaiting approval. Returns the session ID, or None when nothing is stale.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn nudge_stale_beads(
+    thresholdDays: Option<u32>,
+    cliBackend: Option<String>,
+    app_handle: AppHandle,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    state: State<'_, AgentState>,
+    settings_state: State<'_, crate::SettingsState>,
+) -> Result<Option<String>, BertError> {
+    let ctx = crate::project::context_for(&window, project_handle.as_deref())?;
+    let workflow = WorkflowConfig::load_for_beads_file(&ctx.beads_path()?)?;
+    let beads = crate::bd::load_beads(&ctx)?;
+    let stale = stale_report(&beads, &workflow, thresholdDays.unwrap_or(DEFAULT_STALE_DAYS), Utc::now());
+    if stale.is_empty() {
+        return Ok(None);
+    }
+
+    let backend = crate::agent::session::resolve_backend(cliBackend.as_deref(), &settings_state)?;
+    let session_id = Uuid::new_v4().to_string();
+    crate::agent::session::start_session_internal(
+        &app_handle,
+        &state,
+        ctx,
+        session_id.clone(),
+        backend,
+        "qa-engineer".to_string(),
+        Some("triage_stale".to_string()),
+        None,
+        None,
+        Some(serde_json::to_string_pretty(&stale)?),
+        false,
+        false,
+    )?;
+    eprintln!("🔔 Nudging owners of {} stale beads in session {}", stale.len(), session_id);
+
+    *state.active_session_id.lock().unwrap() = Some(session_id.clone());
+    let _ = app_handle.emit("active-session-changed", session_id.clone());
+    Ok(Some(session_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bead(id: &str, issue_type: &str, status: &str, deps: &[(&str, &str)]) -> serde_json::Value {
+        let dependencies: Vec<_> = deps
+            .iter()
+            .map(|(on, ty)| json!({ "issue_id": id, "depends_on_id": on, "type": ty }))
+            .collect();
+        json!({
+            "id": id,
+            "status": status,
+            "issue_type": issue_type,
+            "estimate": 60,
+            "acceptance_criteria": ["works"],
+            "updated_at": "2026-03-01T10:00:00Z",
+            "dependencies": dependencies,
+        })
+    }
+
+    fn beads(values: Vec<serde_json::Value>) -> Vec<Bead> {
+        values.into_iter().map(crate::test_bead).collect()
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-03-10T10:00:00Z").unwrap().with_timezone(&Utc)
+    }
+
+    fn kinds(health: &ProjectHealth) -> Vec<HealthFlagKind> {
+        health.flags.iter().map(|f| f.kind).collect()
+    }
+
+    #[test]
+    fn test_healthy_project_scores_100() {
+        let project = beads(vec![
+            bead("f", "feature", "open", &[]),
+            bead("a", "task", "closed", &[("f", "parent-child")]),
+            bead("b", "task", "in_progress", &[("f", "parent-child"), ("a", "blocks")]),
+        ]);
+        let health = assess(&project, &WorkflowConfig::default(), 14, 15, now());
+        assert_eq!(health.score, 100);
+        assert!(health.flags.is_empty());
+        assert_eq!((health.open_count, health.blocked_count), (1, 0));
+    }
+
+    #[test]
+    fn test_flags_carry_drill_down_ids() {
+        let mut missing = bead("d", "task", "open", &[("f", "parent-child")]);
+        missing["estimate"] = json!(null);
+        missing["acceptance_criteria"] = json!(null);
+        let project = beads(vec![
+            bead("f", "feature", "open", &[]),
+            bead("a", "task", "in_progress", &[("f", "parent-child")]),
+            bead("b", "task", "open", &[("f", "parent-child"), ("c", "blocks")]),
+            bead("c", "task", "open", &[("f", "parent-child"), ("b", "blocks")]),
+            missing,
+        ]);
+        let health = assess(&project, &WorkflowConfig::default(), 7, 3, now());
+
+        assert_eq!(
+            kinds(&health),
+            vec![
+                HealthFlagKind::DependencyCycle,
+                HealthFlagKind::BlockedWork,
+                HealthFlagKind::StaleInProgress,
+                HealthFlagKind::OversizedFeature,
+                HealthFlagKind::MissingEstimate,
+                HealthFlagKind::MissingAcceptanceCriteria,
+            ]
+        );
+        let flag = |kind| health.flags.iter().find(|f| f.kind == kind).unwrap();
+        assert_eq!(flag(HealthFlagKind::BlockedWork).bead_ids, vec!["b", "c"]);
+        assert_eq!(flag(HealthFlagKind::StaleInProgress).bead_ids, vec!["a"]);
+        assert_eq!(flag(HealthFlagKind::MissingEstimate).bead_ids, vec!["d"]);
+        assert_eq!(flag(HealthFlagKind::OversizedFeature).bead_ids, vec!["f", "a", "b", "c", "d"]);
+        // 10 cycle + 15 blocked + 4 estimate + 3 criteria + 5 stale + 5 oversized
+        assert_eq!(health.score, 58);
+    }
+
+    #[test]
+    fn test_stale_beads_oldest_first() {
+        let mut older = bead("old", "task", "in_progress", &[]);
+        older["updated_at"] = json!("2026-01-01T00:00:00Z");
+        let project = beads(vec![bead("recent", "task", "in_progress", &[]), older, bead("open", "task", "open", &[])]);
+        let stale: Vec<&str> = stale_beads(&project, &WorkflowConfig::default(), 7, now()).iter().map(|b| b.id.as_str()).collect();
+        assert_eq!(stale, vec!["old", "recent"]);
+        assert!(stale_beads(&project, &WorkflowConfig::default(), 30, now()).iter().all(|b| b.id == "old"));
+
+        let report = stale_report(&project, &WorkflowConfig::default(), 7, now());
+        assert_eq!(report.iter().map(|s| s.days_stale).collect::<Vec<_>>(), vec![68, 9]);
+    }
+
+    #[test]
+    fn test_stale_bead_serializes_flat() {
+        let stale = StaleBead { bead: beads(vec![bead("a", "task", "in_progress", &[])]).remove(0), days_stale: 3 };
+        let value = serde_json::to_value(&stale).unwrap();
+        assert_eq!(value["id"], "a");
+        assert_eq!(value["days_stale"], 3);
+    }
+}
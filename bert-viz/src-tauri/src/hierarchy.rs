@@ -0,0 +1,256 @@
+//! Moving beads around the WBS tree
+//!
+//! `move_bead` re-parents a bead (and so its whole subtree) after checking
+//! that the move keeps the tree a tree and respects the type hierarchy:
+//! epics hold features and tasks, features hold tasks, and tasks, bugs and
+//! chores hold nothing. Given a position, it also stores the new order of the
+//! siblings as `sort_rank` metadata.
+//!
+//! `reorder_siblings` stores a manual order for the children of one parent,
+//! which `SortBy::Manual` honors when building the tree.
+
+use tauri::AppHandle;
+
+use crate::bd::{load_beads, sort_rank};
+use crate::error::BertError;
+use crate::project::{context_for, ProjectContext};
+use crate::{Bead, WBSNode};
+
+/// Level of an issue type in the WBS; parents must be above their children
+fn level(issue_type: &str) -> u8 {
+    match issue_type {
+        "epic" => 2,
+        "feature" => 1,
+        _ => 0,
+    }
+}
+
+fn find<'a>(beads: &'a [Bead], id: &str) -> Result<&'a Bead, BertError> {
+    beads
+        .iter()
+        .find(|b| b.id == id)
+        .ok_or_else(|| BertError::NotFound(format!("Bead with ID {} not found", id)))
+}
+
+fn parent_of(bead: &Bead) -> Option<&str> {
+    bead.dependencies
+        .iter()
+        .find(|d| d.r#type == "parent-child")
+        .map(|d| d.depends_on_id.as_str())
+}
+
+/// Check that `bead_id` may be moved under `new_parent_id` (or to the root)
+pub(crate) fn validate_move(beads: &[Bead], bead_id: &str, new_parent_id: Option<&str>) -> Result<(), BertError> {
+    let bead = find(beads, bead_id)?;
+    let Some(parent_id) = new_parent_id else { return Ok(()) };
+
+    // Existence and cycles
+    crate::bd::validate_new_dependency(beads, bead_id, parent_id, "parent-child")?;
+
+    let parent = find(beads, parent_id)?;
+    if level(&parent.issue_type) <= level(&bead.issue_type) {
+        return Err(BertError::InvalidInput(format!(
+            "A {} cannot be placed under a {} ({} under {})",
+            bead.issue_type, parent.issue_type, bead_id, parent_id
+        )));
+    }
+    Ok(())
+}
+
+/// Children of `parent_id` (roots for `None`) in their current order: by
+/// stored rank, then file order
+pub(crate) fn siblings<'a>(beads: &'a [Bead], parent_id: Option<&str>) -> Vec<&'a Bead> {
+    let mut siblings: Vec<&Bead> = beads
+        .iter()
+        .filter(|b| b.status != "tombstone" && parent_of(b) == parent_id)
+        .collect();
+    // Stable, so unranked beads keep their file order after the ranked ones
+    siblings.sort_by_key(|b| sort_rank(b).unwrap_or(i64::MAX));
+    siblings
+}
+
+/// Store `ordered` as the order of a group of siblings
+pub(crate) fn write_ranks(ctx: &ProjectContext, ordered: &[&Bead]) -> Result<(), BertError> {
+    for (rank, bead) in ordered.iter().enumerate() {
+        crate::bd::set_sort_rank_internal(ctx, bead, rank as i64)?;
+    }
+    Ok(())
+}
+
+/// New order of the siblings: `ordered_ids` first, then the rest in their
+/// current order
+fn reordered<'a>(siblings: Vec<&'a Bead>, ordered_ids: &[String]) -> Result<Vec<&'a Bead>, BertError> {
+    let mut ordered = Vec::with_capacity(siblings.len());
+    for id in ordered_ids {
+        let bead = siblings
+            .iter()
+            .find(|b| &b.id == id)
+            .ok_or_else(|| BertError::InvalidInput(format!("{} is not a child of this parent", id)))?;
+        if ordered.iter().any(|b: &&Bead| &b.id == id) {
+            return Err(BertError::InvalidInput(format!("{} is listed twice", id)));
+        }
+        ordered.push(*bead);
+    }
+    ordered.extend(siblings.into_iter().filter(|b| !ordered_ids.contains(&b.id)));
+    Ok(ordered)
+}
+
+/// The node of `id` in `tree`, with its subtree
+fn take_subtree(tree: Vec<WBSNode>, id: &str) -> Option<WBSNode> {
+    for node in tree {
+        if node.bead.id == id {
+            return Some(node);
+        }
+        if let Some(found) = take_subtree(node.children, id) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+/// Move a bead and its subtree under `newParentId` (to the root when absent).
+/// With `position`, the bead is placed at that index among its new siblings
+/// and the sibling order is stored. Returns the moved subtree.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn move_bead(
+    beadId: String,
+    newParentId: Option<String>,
+    position: Option<usize>,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    app_handle: AppHandle,
+) -> Result<WBSNode, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    let beads = load_beads(&ctx)?;
+    let new_parent_id = newParentId.as_deref();
+    validate_move(&beads, &beadId, new_parent_id)?;
+
+    let bead = find(&beads, &beadId)?;
+    crate::bd::set_parent_internal(&ctx, bead, new_parent_id)?;
+
+    if let Some(position) = position {
+        let mut ordered: Vec<&Bead> = siblings(&beads, new_parent_id).into_iter().filter(|b| b.id != beadId).collect();
+        ordered.insert(position.min(ordered.len()), bead);
+        write_ranks(&ctx, &ordered)?;
+    }
+    eprintln!("🌳 Moved {} under {}", beadId, new_parent_id.unwrap_or("the root"));
+    crate::project::emit_beads_updated(&app_handle, &ctx);
+
+    let beads = load_beads(&ctx)?;
+    take_subtree(crate::bert_core::build_wbs_tree(&beads), &beadId)
+        .ok_or_else(|| BertError::NotFound(format!("Bead with ID {} not found after the move", beadId)))
+}
+
+/// Store the order of the children of `parentId` (the root beads when
+/// absent). Children missing from `orderedIds` keep their relative order
+/// after the listed ones.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn reorder_siblings(
+    parentId: Option<String>,
+    orderedIds: Vec<String>,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    app_handle: AppHandle,
+) -> Result<(), BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    let beads = load_beads(&ctx)?;
+    if let Some(parent_id) = &parentId {
+        find(&beads, parent_id)?;
+    }
+
+    let ordered = reordered(siblings(&beads, parentId.as_deref()), &orderedIds)?;
+    write_ranks(&ctx, &ordered)?;
+    eprintln!("🌳 Reordered {} children of {}", ordered.len(), parentId.as_deref().unwrap_or("the root"));
+    crate::project::emit_beads_updated(&app_handle, &ctx);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bead(id: &str, issue_type: &str, parent: Option<&str>, rank: Option<i64>) -> Bead {
+        let deps: Vec<_> = parent
+            .iter()
+            .map(|p| json!({ "issue_id": id, "depends_on_id": p, "type": "parent-child" }))
+            .collect();
+        let mut value = json!({
+            "id": id,
+            "issue_type": issue_type,
+            "dependencies": deps,
+        });
+        if let Some(rank) = rank {
+            value["sort_rank"] = json!(rank);
+        }
+        crate::test_bead(value)
+    }
+
+    fn project() -> Vec<Bead> {
+        vec![
+            bead("e1", "epic", None, None),
+            bead("e2", "epic", None, None),
+            bead("f1", "feature", Some("e1"), None),
+            bead("t1", "task", Some("f1"), None),
+            bead("t2", "task", Some("f1"), Some(0)),
+        ]
+    }
+
+    #[test]
+    fn test_validate_move_enforces_type_hierarchy_and_cycles() {
+        let beads = project();
+        assert!(validate_move(&beads, "f1", Some("e2")).is_ok());
+        assert!(validate_move(&beads, "t1", Some("e2")).is_ok());
+        assert!(validate_move(&beads, "t1", None).is_ok());
+        // Features can't hold epics or features, tasks hold nothing
+        assert!(validate_move(&beads, "e2", Some("f1")).is_err());
+        assert!(validate_move(&beads, "t1", Some("t2")).is_err());
+        // An epic under its own descendant
+        assert!(validate_move(&beads, "e1", Some("f1")).is_err());
+        assert!(validate_move(&beads, "t1", Some("missing")).is_err());
+    }
+
+    #[test]
+    fn test_siblings_follow_ranks_then_file_order() {
+        let beads = project();
+        let ids = |parent| siblings(&beads, parent).iter().map(|b| b.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(Some("f1")), vec!["t2", "t1"]);
+        assert_eq!(ids(None), vec!["e1", "e2"]);
+    }
+
+    #[test]
+    fn test_reordered_puts_listed_first() {
+        let beads = vec![
+            bead("a", "task", None, None),
+            bead("b", "task", None, None),
+            bead("c", "task", None, None),
+        ];
+        let ids = |ordered: Vec<&Bead>| ordered.iter().map(|b| b.id.clone()).collect::<Vec<_>>();
+        let order = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(ids(reordered(siblings(&beads, None), &order(&["c", "a"])).unwrap()), vec!["c", "a", "b"]);
+        assert!(reordered(siblings(&beads, None), &order(&["x"])).is_err());
+        assert!(reordered(siblings(&beads, None), &order(&["a", "a"])).is_err());
+    }
+
+    #[test]
+    fn test_manual_sort_honors_ranks() {
+        let beads = vec![
+            bead("a", "task", None, None),
+            bead("b", "task", None, Some(1)),
+            bead("c", "task", None, Some(0)),
+        ];
+        let graph = crate::bert_core::build_dependency_graph(&beads);
+        let tree = crate::bert_core::sort_wbs_tree_siblings(crate::bert_core::build_wbs_tree(&beads), &graph, &crate::SortBy::Manual, &crate::SortOrder::None);
+        assert_eq!(tree.iter().map(|n| n.bead.id.as_str()).collect::<Vec<_>>(), vec!["c", "b", "a"]);
+    }
+
+    #[test]
+    fn test_take_subtree() {
+        let tree = crate::bert_core::build_wbs_tree(&project());
+        let feature = take_subtree(tree, "f1").unwrap();
+        assert_eq!(feature.children.len(), 2);
+    }
+}
@@ -0,0 +1,453 @@
+//! Bead change history
+//!
+//! Every time the watcher sees `.beads/issues.jsonl` change, the file is diffed
+//! against the last recorded snapshot and one entry per changed bead is
+//! appended to ~/.bp6/history/<project>/<bead-id>.jsonl. The snapshot itself
+//! lives next to the logs, so edits made while the app was closed are picked
+//! up the next time the project is opened.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use crate::baseline::{project_store_key, sanitize_file_name};
+use crate::error::BertError;
+
+const SNAPSHOT_FILE: &str = "_snapshot.json";
+
+/// Fields that change on every write and would drown out real edits
+const IGNORED_FIELDS: [&str; 1] = ["updated_at"];
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Created,
+    Updated,
+    Deleted,
+}
+
+/// One field's before/after values (null when absent)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Value,
+    pub new: Value,
+}
+
+/// A single entry in a bead's timeline
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry {
+    pub timestamp: String,
+    /// Who made the change, when known
+    pub actor: Option<String>,
+    pub kind: ChangeKind,
+    pub changes: Vec<FieldChange>,
+}
+
+type Snapshot = BTreeMap<String, Map<String, Value>>;
+
+/// Fields long enough to also be compared line by line
+const TEXT_FIELDS: [&str; 4] = ["description", "design", "notes", "acceptance_criteria"];
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LineOp {
+    Same,
+    Added,
+    Removed,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct LineChange {
+    pub op: LineOp,
+    pub text: String,
+}
+
+/// A field whose value differs between the two versions
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct FieldDiff {
+    pub field: String,
+    pub old: Value,
+    pub new: Value,
+    /// Line-by-line comparison of long text fields
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lines: Option<Vec<LineChange>>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BeadDiff {
+    pub bead_id: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub fields: Vec<FieldDiff>,
+}
+
+/// Get the history directory for a project (~/.bp6/history/<project key>)
+pub(crate) fn get_history_dir(project_path: &Path) -> Result<PathBuf, String> {
+    let home = dirs::home_dir().ok_or("Failed to get home directory")?;
+    let dir = home.join(".bp6").join("history").join(project_store_key(project_path));
+
+    if !dir.exists() {
+        fs::create_dir_all(&dir).map_err(|e| format!("Failed to create history directory: {}", e))?;
+    }
+    Ok(dir)
+}
+
+/// Repo root owning a `.beads/issues.jsonl` path
+pub(crate) fn project_path_for(beads_path: &Path) -> PathBuf {
+    beads_path
+        .parent()
+        .and_then(|beads_dir| beads_dir.parent())
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+pub(crate) fn field_changes(old: Option<&Map<String, Value>>, new: Option<&Map<String, Value>>) -> Vec<FieldChange> {
+    let empty = Map::new();
+    let old = old.unwrap_or(&empty);
+    let new = new.unwrap_or(&empty);
+
+    let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    keys.into_iter()
+        .filter(|k| !IGNORED_FIELDS.contains(&k.as_str()))
+        .filter_map(|k| {
+            let before = old.get(k).cloned().unwrap_or(Value::Null);
+            let after = new.get(k).cloned().unwrap_or(Value::Null);
+            (before != after).then(|| FieldChange { field: k.clone(), old: before, new: after })
+        })
+        .collect()
+}
+
+fn str_field(obj: Option<&Map<String, Value>>, key: &str) -> Option<String> {
+    obj.and_then(|o| o.get(key)).and_then(|v| v.as_str()).map(|s| s.to_string())
+}
+
+/// Diff two snapshots into per-bead history entries
+fn diff_snapshots(old: &Snapshot, new: &Snapshot, timestamp: &str, local_user: Option<&str>) -> Vec<(String, HistoryEntry)> {
+    let ids: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    let mut entries = Vec::new();
+
+    for id in ids {
+        let before = old.get(id);
+        let after = new.get(id);
+        let kind = match (before, after) {
+            (None, Some(_)) => ChangeKind::Created,
+            (Some(_), None) => ChangeKind::Deleted,
+            _ => ChangeKind::Updated,
+        };
+        let changes = field_changes(before, after);
+        if changes.is_empty() {
+            continue;
+        }
+
+        let actor = str_field(after, "updated_by")
+            .or_else(|| if kind == ChangeKind::Created { str_field(after, "created_by") } else { None })
+            .or_else(|| local_user.map(|u| u.to_string()));
+
+        entries.push((
+            id.clone(),
+            HistoryEntry {
+                timestamp: timestamp.to_string(),
+                actor,
+                kind,
+                changes,
+            },
+        ));
+    }
+    entries
+}
+
+fn read_snapshot(dir: &Path) -> Option<Snapshot> {
+    let contents = fs::read_to_string(dir.join(SNAPSHOT_FILE)).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+fn write_snapshot(dir: &Path, snapshot: &Snapshot) -> Result<(), String> {
+    let json = serde_json::to_string(snapshot).map_err(|e| format!("Failed to serialize history snapshot: {}", e))?;
+    let tmp = dir.join(format!("{}.tmp", SNAPSHOT_FILE));
+    fs::write(&tmp, json).map_err(|e| format!("Failed to write history snapshot: {}", e))?;
+    fs::rename(&tmp, dir.join(SNAPSHOT_FILE)).map_err(|e| format!("Failed to replace history snapshot: {}", e))
+}
+
+fn append_entry(dir: &Path, bead_id: &str, entry: &HistoryEntry) -> Result<(), String> {
+    let path = dir.join(format!("{}.jsonl", sanitize_file_name(bead_id)));
+    let line = serde_json::to_string(entry).map_err(|e| e.to_string())?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    writeln!(file, "{}", line).map_err(|e| format!("Failed to append to {}: {}", path.display(), e))
+}
+
+/// Diff `beads_path` against the stored snapshot in `dir` and append history entries.
+/// The first call for a project only seeds the snapshot.
+fn record_changes_in(dir: &Path, beads_path: &Path) -> Result<Vec<(String, HistoryEntry)>, String> {
+    let objects = crate::bd::read_jsonl_objects(beads_path)?;
+    let current: Snapshot = objects
+        .into_iter()
+        .filter_map(|obj| {
+            let id = obj.get("id").and_then(|v| v.as_str())?.to_string();
+            Some((id, obj))
+        })
+        .collect();
+
+    let Some(previous) = read_snapshot(dir) else {
+        write_snapshot(dir, &current)?;
+        return Ok(Vec::new());
+    };
+
+    let local_user = std::env::var("USER").or_else(|_| std::env::var("USERNAME")).ok();
+    let timestamp = chrono::Utc::now().to_rfc3339();
+    let entries = diff_snapshots(&previous, &current, &timestamp, local_user.as_deref());
+
+    for (bead_id, entry) in &entries {
+        append_entry(dir, bead_id, entry)?;
+    }
+    write_snapshot(dir, &current)?;
+    Ok(entries)
+}
+
+/// Record changes to a project's beads file. Called by the file watcher,
+/// which also gets the recorded entries by bead ID.
+pub fn record_changes(beads_path: &Path) -> Result<Vec<(String, HistoryEntry)>, String> {
+    let dir = get_history_dir(&project_path_for(beads_path))?;
+    let entries = record_changes_in(&dir, beads_path)?;
+    if !entries.is_empty() {
+        eprintln!("📝 Recorded history for {} changed beads", entries.len());
+    }
+    Ok(entries)
+}
+
+fn read_history(dir: &Path, bead_id: &str) -> Result<Vec<HistoryEntry>, String> {
+    let path = dir.join(format!("{}.jsonl", sanitize_file_name(bead_id)));
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    read_history_file(&path)
+}
+
+/// Entries of one bead's history log, oldest first
+pub(crate) fn read_history_file(path: &Path) -> Result<Vec<HistoryEntry>, String> {
+    let file = fs::File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut entries = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(|e| e.to_string())?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<HistoryEntry>(&line) {
+            Ok(entry) => entries.push(entry),
+            Err(e) => eprintln!("⚠️  Skipping malformed history line in {}: {}", path.display(), e),
+        }
+    }
+    Ok(entries)
+}
+
+fn parse_timestamp(ts: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(ts)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| format!("Invalid timestamp '{}': {}", ts, e))
+}
+
+/// Text of a field for the line diff (acceptance criteria may be a list)
+fn field_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items.iter().filter_map(Value::as_str).collect::<Vec<_>>().join("\n"),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Longest-common-subsequence diff of two texts' lines
+pub fn line_diff(old: &str, new: &str) -> Vec<LineChange> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    // common[i][j]: LCS length of old[i..] and new[j..]
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] { common[i + 1][j + 1] + 1 } else { common[i + 1][j].max(common[i][j + 1]) };
+        }
+    }
+
+    let line = |op, text: &str| LineChange { op, text: text.to_string() };
+    let (mut i, mut j) = (0, 0);
+    let mut changes = Vec::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            changes.push(line(LineOp::Same, old[i]));
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || common[i][j + 1] >= common[i + 1][j]) {
+            changes.push(line(LineOp::Added, new[j]));
+            j += 1;
+        } else {
+            changes.push(line(LineOp::Removed, old[i]));
+            i += 1;
+        }
+    }
+    changes
+}
+
+/// Fields that differ between a bead's version at `from` (its first version
+/// when None) and at `to` (the latest when None), from its history entries.
+/// A field's value at `from` is the old value of its first change after
+/// `from`; at `to`, the new value of its last change up to `to`.
+pub fn diff_entries(entries: &[HistoryEntry], from: Option<DateTime<Utc>>, to: Option<DateTime<Utc>>) -> Vec<FieldDiff> {
+    let mut values: BTreeMap<&str, (&Value, &Value)> = BTreeMap::new();
+    for entry in entries {
+        let Ok(at) = parse_timestamp(&entry.timestamp) else { continue };
+        if from.is_some_and(|from| at <= from) || to.is_some_and(|to| at > to) {
+            continue;
+        }
+        for change in &entry.changes {
+            values.entry(change.field.as_str()).and_modify(|(_, new)| *new = &change.new).or_insert((&change.old, &change.new));
+        }
+    }
+
+    values
+        .into_iter()
+        .filter(|(_, (old, new))| old != new)
+        .map(|(field, (old, new))| FieldDiff {
+            field: field.to_string(),
+            old: old.clone(),
+            new: new.clone(),
+            lines: TEXT_FIELDS.contains(&field).then(|| line_diff(&field_text(old), &field_text(new))),
+        })
+        .collect()
+}
+
+/// Compare a bead's versions at two times (RFC 3339); omit `fromTs` for its
+/// first recorded version and `toTs` for the current one
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn diff_bead(
+    beadId: String,
+    fromTs: Option<String>,
+    toTs: Option<String>,
+    window: tauri::Window,
+    project_handle: Option<String>,
+) -> Result<BeadDiff, BertError> {
+    let from = fromTs.as_deref().map(parse_timestamp).transpose().map_err(BertError::InvalidInput)?;
+    let to = toTs.as_deref().map(parse_timestamp).transpose().map_err(BertError::InvalidInput)?;
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            return Err(BertError::InvalidInput(format!(
+                "fromTs ({}) is after toTs ({})",
+                from.to_rfc3339(),
+                to.to_rfc3339()
+            )));
+        }
+    }
+
+    let beads_path = crate::project::context_for(&window, project_handle.as_deref())?.beads_path()?;
+    let dir = get_history_dir(&project_path_for(&beads_path)).map_err(BertError::Io)?;
+    let fields = diff_entries(&read_history(&dir, &beadId).map_err(BertError::Io)?, from, to);
+    Ok(BeadDiff { bead_id: beadId, from: fromTs, to: toTs, fields })
+}
+
+/// Get the timeline of field changes for a bead, newest first
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn get_bead_history(beadId: String, window: tauri::Window, project_handle: Option<String>) -> Result<Vec<HistoryEntry>, String> {
+    let beads_path = crate::project::context_for(&window, project_handle.as_deref())?.beads_path()?;
+    let dir = get_history_dir(&project_path_for(&beads_path))?;
+
+    let mut entries = read_history(&dir, &beadId)?;
+    entries.reverse();
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_beads(path: &Path, lines: &[&str]) {
+        fs::write(path, lines.join("\n") + "\n").unwrap();
+    }
+
+    fn entry(timestamp: &str, changes: &[(&str, Value, Value)]) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: timestamp.to_string(),
+            actor: None,
+            kind: ChangeKind::Updated,
+            changes: changes
+                .iter()
+                .map(|(field, old, new)| FieldChange { field: field.to_string(), old: old.clone(), new: new.clone() })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_diff_entries_between_versions() {
+        use serde_json::json;
+        let entries = vec![
+            entry("2026-03-01T10:00:00Z", &[("status", json!("open"), json!("in_progress"))]),
+            entry("2026-03-02T10:00:00Z", &[("notes", json!("a\nb\nc"), json!("a\nB\nc")), ("priority", json!(2), json!(1))]),
+            entry("2026-03-03T10:00:00Z", &[("status", json!("in_progress"), json!("closed")), ("priority", json!(1), json!(2))]),
+        ];
+        let at = |ts: &str| Some(parse_timestamp(ts).unwrap());
+
+        let all = diff_entries(&entries, None, None);
+        let fields: Vec<(&str, &Value, &Value)> = all.iter().map(|d| (d.field.as_str(), &d.old, &d.new)).collect();
+        // Priority went back to where it started
+        assert_eq!(fields, vec![("notes", &json!("a\nb\nc"), &json!("a\nB\nc")), ("status", &json!("open"), &json!("closed"))]);
+
+        let notes = all[0].lines.as_ref().unwrap();
+        let ops: Vec<(&LineOp, &str)> = notes.iter().map(|l| (&l.op, l.text.as_str())).collect();
+        assert_eq!(ops, vec![(&LineOp::Same, "a"), (&LineOp::Added, "B"), (&LineOp::Removed, "b"), (&LineOp::Same, "c")]);
+        assert!(all[1].lines.is_none());
+
+        let middle = diff_entries(&entries, at("2026-03-01T12:00:00Z"), at("2026-03-02T12:00:00Z"));
+        let fields: Vec<&str> = middle.iter().map(|d| d.field.as_str()).collect();
+        assert_eq!(fields, vec!["notes", "priority"]);
+    }
+
+    #[test]
+    fn test_records_created_updated_deleted() {
+        let dir = TempDir::new().unwrap();
+        let beads = dir.path().join("issues.jsonl");
+        let history = dir.path().join("history");
+        fs::create_dir_all(&history).unwrap();
+
+        write_beads(&beads, &[
+            r#"{"id":"a","title":"A","status":"open","updated_at":"1"}"#,
+            r#"{"id":"b","title":"B","status":"open"}"#,
+        ]);
+        assert!(record_changes_in(&history, &beads).unwrap().is_empty(), "first run only seeds");
+
+        write_beads(&beads, &[
+            r#"{"id":"a","title":"A","status":"closed","updated_at":"2","updated_by":"alice"}"#,
+            r#"{"id":"c","title":"C","status":"open"}"#,
+        ]);
+        assert_eq!(record_changes_in(&history, &beads).unwrap().len(), 3);
+
+        let a = read_history(&history, "a").unwrap();
+        assert_eq!(a.len(), 1);
+        assert_eq!(a[0].kind, ChangeKind::Updated);
+        assert_eq!(a[0].actor.as_deref(), Some("alice"));
+        let fields: Vec<&str> = a[0].changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, vec!["status", "updated_by"]);
+
+        assert_eq!(read_history(&history, "b").unwrap()[0].kind, ChangeKind::Deleted);
+        assert_eq!(read_history(&history, "c").unwrap()[0].kind, ChangeKind::Created);
+    }
+
+    #[test]
+    fn test_timestamp_only_change_is_ignored() {
+        let mut old = Snapshot::new();
+        let mut new = Snapshot::new();
+        old.insert("a".into(), serde_json::from_str(r#"{"id":"a","updated_at":"1"}"#).unwrap());
+        new.insert("a".into(), serde_json::from_str(r#"{"id":"a","updated_at":"2"}"#).unwrap());
+        assert!(diff_snapshots(&old, &new, "now", None).is_empty());
+    }
+}
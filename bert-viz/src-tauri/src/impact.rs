@@ -0,0 +1,238 @@
+//! Dependency impact of a single bead
+//!
+//! Answers "what happens around this bead" for the impact panel: every bead it
+//! transitively blocks (downstream) and waits on (upstream), the owners whose
+//! unfinished work it holds up, and how much shorter the critical path through
+//! the unfinished beads would be if it were closed today.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use crate::error::BertError;
+use crate::workflow::WorkflowConfig;
+use crate::Bead;
+
+/// A bead reached from the analysed one over blocking dependencies
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImpactedBead {
+    pub id: String,
+    pub title: String,
+    pub status: String,
+    pub owner: Option<String>,
+    /// Fewest blocking hops from the analysed bead
+    pub depth: usize,
+}
+
+/// Critical path through the unfinished beads, now and with the bead closed.
+/// Lengths count beads; minutes sum their estimates.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CriticalPathImpact {
+    pub on_critical_path: bool,
+    pub length: usize,
+    pub length_if_closed: usize,
+    pub estimate_minutes: u64,
+    pub estimate_minutes_if_closed: u64,
+}
+
+/// An owner with unfinished downstream beads
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AffectedOwner {
+    /// None for unassigned beads
+    pub owner: Option<String>,
+    pub bead_ids: Vec<String>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImpactAnalysis {
+    pub bead_id: String,
+    /// Beads transitively blocked by this one, nearest first
+    pub downstream: Vec<ImpactedBead>,
+    /// Beads this one transitively waits on, nearest first
+    pub upstream: Vec<ImpactedBead>,
+    pub critical_path: CriticalPathImpact,
+    /// Most affected first
+    pub affected_owners: Vec<AffectedOwner>,
+}
+
+/// Beads reachable from `start` over `edges` with their depth, breadth first.
+/// `start` itself is left out even when a cycle leads back to it.
+pub(crate) fn closure(start: &str, edges: &HashMap<String, Vec<String>>) -> Vec<(String, usize)> {
+    let mut seen: HashSet<&str> = HashSet::from([start]);
+    let mut queue: VecDeque<(&str, usize)> = VecDeque::from([(start, 0)]);
+    let mut reached = Vec::new();
+
+    while let Some((id, depth)) = queue.pop_front() {
+        for next in edges.get(id).into_iter().flatten() {
+            if seen.insert(next.as_str()) {
+                reached.push((next.clone(), depth + 1));
+                queue.push_back((next.as_str(), depth + 1));
+            }
+        }
+    }
+    reached
+}
+
+fn chain_minutes(chain: &[String], by_id: &HashMap<&str, &Bead>) -> u64 {
+    chain
+        .iter()
+        .filter_map(|id| by_id.get(id.as_str()))
+        .map(|b| u64::from(b.estimate.unwrap_or(0)))
+        .sum()
+}
+
+/// Impact of `bead_id` among `beads`
+pub fn analyze(beads: &[Bead], bead_id: &str, workflow: &WorkflowConfig) -> Result<ImpactAnalysis, BertError> {
+    let live: Vec<Bead> = beads.iter().filter(|b| b.status != "tombstone").cloned().collect();
+    let by_id: HashMap<&str, &Bead> = live.iter().map(|b| (b.id.as_str(), b)).collect();
+    let Some(bead) = by_id.get(bead_id) else {
+        return Err(BertError::NotFound(format!("Bead {} not found", bead_id)));
+    };
+
+    let graph = crate::bert_core::build_dependency_graph(&live);
+    let impacted = |edges: &HashMap<String, Vec<String>>| -> Vec<ImpactedBead> {
+        let mut reached: Vec<ImpactedBead> = closure(bead_id, edges)
+            .into_iter()
+            .filter_map(|(id, depth)| {
+                let b = by_id.get(id.as_str())?;
+                Some(ImpactedBead { id, title: b.title.clone(), status: b.status.clone(), owner: b.owner.clone(), depth })
+            })
+            .collect();
+        reached.sort_by(|a, b| a.depth.cmp(&b.depth).then_with(|| a.id.cmp(&b.id)));
+        reached
+    };
+    let downstream = impacted(&graph.blocks);
+    let upstream = impacted(&graph.blocked_by);
+
+    // Closing a bead takes it out of the unfinished beads the path runs through
+    let chain = crate::notifications::open_critical_chain(&live, workflow);
+    let without: Vec<Bead> = live.iter().filter(|b| b.id != bead_id).cloned().collect();
+    let chain_if_closed = if workflow.is_done(&bead.status) {
+        chain.clone()
+    } else {
+        crate::notifications::open_critical_chain(&without, workflow)
+    };
+    let critical_path = CriticalPathImpact {
+        on_critical_path: chain.iter().any(|id| id == bead_id),
+        length: chain.len(),
+        length_if_closed: chain_if_closed.len(),
+        estimate_minutes: chain_minutes(&chain, &by_id),
+        estimate_minutes_if_closed: chain_minutes(&chain_if_closed, &by_id),
+    };
+
+    let mut by_owner: BTreeMap<Option<String>, Vec<String>> = BTreeMap::new();
+    for b in downstream.iter().filter(|b| !workflow.is_done(&b.status)) {
+        let owner = b.owner.clone().filter(|o| !o.is_empty());
+        by_owner.entry(owner).or_default().push(b.id.clone());
+    }
+    let mut affected_owners: Vec<AffectedOwner> =
+        by_owner.into_iter().map(|(owner, bead_ids)| AffectedOwner { owner, bead_ids }).collect();
+    affected_owners.sort_by_key(|o| std::cmp::Reverse(o.bead_ids.len()));
+
+    Ok(ImpactAnalysis {
+        bead_id: bead_id.to_string(),
+        downstream,
+        upstream,
+        critical_path,
+        affected_owners,
+    })
+}
+
+/// Dependency impact of a bead in the current project
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn get_impact_analysis(beadId: String, window: tauri::Window, project_handle: Option<String>) -> Result<ImpactAnalysis, BertError> {
+    let ctx = crate::project::context_for(&window, project_handle.as_deref())?;
+    let workflow = WorkflowConfig::load_for_beads_file(&ctx.beads_path()?)?;
+    let beads = crate::bd::load_beads(&ctx)?;
+
+    let analysis = analyze(&beads, &beadId, &workflow)?;
+    eprintln!(
+        "💥 Impact of {}: {} downstream, {} upstream, {} owners",
+        beadId,
+        analysis.downstream.len(),
+        analysis.upstream.len(),
+        analysis.affected_owners.len()
+    );
+    Ok(analysis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bead(id: &str, status: &str, owner: Option<&str>, blockers: &[&str]) -> Bead {
+        let dependencies: Vec<_> = blockers
+            .iter()
+            .map(|on| json!({ "issue_id": id, "depends_on_id": on, "type": "blocks" }))
+            .collect();
+        crate::test_bead(json!({
+            "id": id,
+            "status": status,
+            "estimate": 60,
+            "owner": owner,
+            "dependencies": dependencies,
+        }))
+    }
+
+    fn ids(beads: &[ImpactedBead]) -> Vec<(&str, usize)> {
+        beads.iter().map(|b| (b.id.as_str(), b.depth)).collect()
+    }
+
+    #[test]
+    fn test_closures_follow_blocking_edges_both_ways() {
+        // a -> b -> c -> d, and a -> d directly
+        let beads = vec![
+            bead("a", "closed", None, &[]),
+            bead("b", "open", Some("ann"), &["a"]),
+            bead("c", "open", Some("bob"), &["b"]),
+            bead("d", "open", Some("ann"), &["c", "a"]),
+        ];
+        let impact = analyze(&beads, "b", &WorkflowConfig::default()).unwrap();
+
+        assert_eq!(ids(&impact.downstream), vec![("c", 1), ("d", 2)]);
+        assert_eq!(ids(&impact.upstream), vec![("a", 1)]);
+        assert_eq!(
+            impact.affected_owners,
+            vec![
+                AffectedOwner { owner: Some("ann".to_string()), bead_ids: vec!["d".to_string()] },
+                AffectedOwner { owner: Some("bob".to_string()), bead_ids: vec!["c".to_string()] },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_closing_a_critical_bead_shortens_the_path() {
+        let beads = vec![
+            bead("a", "open", None, &[]),
+            bead("b", "open", None, &["a"]),
+            bead("c", "open", None, &["b"]),
+            bead("x", "open", None, &[]),
+            bead("y", "open", None, &["x"]),
+        ];
+        let workflow = WorkflowConfig::default();
+
+        let critical = analyze(&beads, "a", &workflow).unwrap().critical_path;
+        assert!(critical.on_critical_path);
+        assert_eq!((critical.length, critical.length_if_closed), (3, 2));
+        assert_eq!((critical.estimate_minutes, critical.estimate_minutes_if_closed), (180, 120));
+
+        let off_path = analyze(&beads, "y", &workflow).unwrap().critical_path;
+        assert!(!off_path.on_critical_path);
+        assert_eq!(off_path.length, off_path.length_if_closed);
+    }
+
+    #[test]
+    fn test_cycles_and_unknown_beads() {
+        let beads = vec![bead("a", "open", None, &["b"]), bead("b", "open", None, &["a"])];
+        let impact = analyze(&beads, "a", &WorkflowConfig::default()).unwrap();
+        assert_eq!(ids(&impact.downstream), vec![("b", 1)]);
+        assert_eq!(ids(&impact.upstream), vec![("b", 1)]);
+
+        assert!(matches!(analyze(&beads, "zz", &WorkflowConfig::default()), Err(BertError::NotFound(_))));
+    }
+}
@@ -0,0 +1,492 @@
+//! Bulk import of beads from CSV
+//!
+//! Columns are mapped to Bead fields through a user-supplied `CsvImportMapping`.
+//! Parent and dependency cells may reference other rows (by temporary ID or
+//! title) or existing beads (by ID). Rows are created in topological order so
+//! parents and blockers always exist before the beads that point at them.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::io::Read;
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::Bead;
+
+/// Maps CSV column headers to Bead fields. Only `title` is required.
+/// Header matching is case-insensitive and ignores surrounding whitespace.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvImportMapping {
+    pub title: String,
+    /// Column holding a temporary ID other rows can reference
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub priority: Option<String>,
+    #[serde(default)]
+    pub issue_type: Option<String>,
+    #[serde(default)]
+    pub owner: Option<String>,
+    #[serde(default)]
+    pub estimate: Option<String>,
+    #[serde(default)]
+    pub labels: Option<String>,
+    #[serde(default)]
+    pub acceptance_criteria: Option<String>,
+    #[serde(default)]
+    pub external_reference: Option<String>,
+    /// Column referencing the parent row/bead
+    #[serde(default)]
+    pub parent: Option<String>,
+    /// Column listing blocking rows/beads
+    #[serde(default)]
+    pub dependencies: Option<String>,
+    /// Separator for multi-value cells (labels, dependencies). Defaults to ";".
+    #[serde(default)]
+    pub list_separator: Option<String>,
+    /// Issue type used when the row has none. Defaults to "task".
+    #[serde(default)]
+    pub default_issue_type: Option<String>,
+}
+
+/// A reference resolved to either another CSV row or an existing bead
+#[derive(Debug, Clone, PartialEq)]
+enum BeadRef {
+    Row(usize),
+    Existing(String),
+}
+
+#[derive(Debug, Clone)]
+struct ImportRow {
+    key: String,
+    bead: Bead,
+    parent: Option<BeadRef>,
+    blocked_by: Vec<BeadRef>,
+}
+
+/// Preview/outcome for one CSV row
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedBead {
+    /// 1-based data row number (header excluded)
+    pub row: usize,
+    /// Temporary ID from the mapping's `id` column, or `row-N`
+    pub key: String,
+    pub title: String,
+    pub issue_type: String,
+    /// Parent as a row key or existing bead ID
+    pub parent: Option<String>,
+    /// Blockers as row keys or existing bead IDs
+    pub blocked_by: Vec<String>,
+    /// Assigned bead ID (only set when not a dry run)
+    pub created_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvImportResult {
+    pub dry_run: bool,
+    /// Beads in creation order
+    pub planned: Vec<PlannedBead>,
+    /// Validation problems; nothing is created when this is non-empty
+    pub errors: Vec<String>,
+}
+
+fn column_index(headers: &csv::StringRecord, name: &str) -> Result<usize, String> {
+    let wanted = name.trim().to_lowercase();
+    headers
+        .iter()
+        .position(|h| h.trim().to_lowercase() == wanted)
+        .ok_or_else(|| format!("Column '{}' not found in CSV header", name))
+}
+
+fn optional_column(headers: &csv::StringRecord, name: &Option<String>) -> Result<Option<usize>, String> {
+    name.as_deref()
+        .filter(|n| !n.trim().is_empty())
+        .map(|n| column_index(headers, n))
+        .transpose()
+}
+
+fn parse_priority(raw: &str) -> Result<u32, String> {
+    let trimmed = raw.trim().trim_start_matches(['P', 'p']);
+    trimmed
+        .parse::<u32>()
+        .ok()
+        .filter(|p| *p <= 4)
+        .ok_or_else(|| format!("invalid priority '{}'", raw))
+}
+
+pub(crate) fn blank_bead(title: String) -> Bead {
+    Bead {
+        id: String::new(),
+        title,
+        description: None,
+        status: "open".to_string(),
+        priority: 2,
+        issue_type: "task".to_string(),
+        estimate: None,
+        dependencies: Vec::new(),
+        owner: None,
+        created_at: None,
+        created_by: None,
+        updated_at: None,
+        labels: None,
+        acceptance_criteria: None,
+        closed_at: None,
+        close_reason: None,
+        is_favorite: None,
+        parent: None,
+        external_reference: None,
+        due_date: None,
+        is_milestone: None,
+        design: None,
+        notes: None,
+        extra_metadata: serde_json::Map::new(),
+    }
+}
+
+/// Parse and validate CSV rows, resolving references against the other rows
+/// and `existing_ids`. Returns the rows plus any validation errors.
+fn parse_rows<R: Read>(
+    reader: R,
+    mapping: &CsvImportMapping,
+    existing_ids: &HashSet<String>,
+) -> Result<(Vec<ImportRow>, Vec<String>), String> {
+    let mut csv_reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(reader);
+    let headers = csv_reader
+        .headers()
+        .map_err(|e| format!("Failed to read CSV header: {}", e))?
+        .clone();
+
+    let title_col = column_index(&headers, &mapping.title)?;
+    let id_col = optional_column(&headers, &mapping.id)?;
+    let description_col = optional_column(&headers, &mapping.description)?;
+    let status_col = optional_column(&headers, &mapping.status)?;
+    let priority_col = optional_column(&headers, &mapping.priority)?;
+    let type_col = optional_column(&headers, &mapping.issue_type)?;
+    let owner_col = optional_column(&headers, &mapping.owner)?;
+    let estimate_col = optional_column(&headers, &mapping.estimate)?;
+    let labels_col = optional_column(&headers, &mapping.labels)?;
+    let ac_col = optional_column(&headers, &mapping.acceptance_criteria)?;
+    let ext_col = optional_column(&headers, &mapping.external_reference)?;
+    let parent_col = optional_column(&headers, &mapping.parent)?;
+    let deps_col = optional_column(&headers, &mapping.dependencies)?;
+
+    let separator = mapping.list_separator.clone().filter(|s| !s.is_empty()).unwrap_or_else(|| ";".to_string());
+    let default_type = mapping.default_issue_type.clone().unwrap_or_else(|| "task".to_string());
+
+    let mut rows = Vec::new();
+    let mut raw_refs: Vec<(Option<String>, Vec<String>)> = Vec::new();
+    let mut errors = Vec::new();
+
+    for (i, record) in csv_reader.records().enumerate() {
+        let row_num = i + 1;
+        let record = record.map_err(|e| format!("Failed to read CSV row {}: {}", row_num, e))?;
+        let cell = |col: Option<usize>| -> Option<String> {
+            col.and_then(|c| record.get(c)).map(|v| v.to_string()).filter(|v| !v.is_empty())
+        };
+        let list = |col: Option<usize>| -> Vec<String> {
+            cell(col)
+                .map(|v| v.split(separator.as_str()).map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect())
+                .unwrap_or_default()
+        };
+
+        let Some(title) = cell(Some(title_col)) else {
+            errors.push(format!("Row {}: missing title", row_num));
+            continue;
+        };
+
+        let mut bead = blank_bead(title);
+        bead.description = cell(description_col);
+        bead.owner = cell(owner_col);
+        bead.external_reference = cell(ext_col);
+        bead.issue_type = cell(type_col).unwrap_or_else(|| default_type.clone());
+        if let Some(status) = cell(status_col) {
+            bead.status = status;
+        }
+        if let Some(raw) = cell(priority_col) {
+            match parse_priority(&raw) {
+                Ok(p) => bead.priority = p,
+                Err(e) => errors.push(format!("Row {}: {}", row_num, e)),
+            }
+        }
+        if let Some(raw) = cell(estimate_col) {
+            match raw.parse::<u32>() {
+                Ok(e) => bead.estimate = Some(e),
+                Err(_) => errors.push(format!("Row {}: invalid estimate '{}'", row_num, raw)),
+            }
+        }
+        let labels = list(labels_col);
+        if !labels.is_empty() {
+            bead.labels = Some(labels);
+        }
+        bead.acceptance_criteria = cell(ac_col).map(|ac| ac.lines().map(|l| l.trim().to_string()).filter(|l| !l.is_empty()).collect());
+
+        let key = cell(id_col).unwrap_or_else(|| format!("row-{}", row_num));
+        rows.push(ImportRow { key, bead, parent: None, blocked_by: Vec::new() });
+        raw_refs.push((cell(parent_col), list(deps_col)));
+    }
+
+    // Resolve references: temporary ID, then exact title, then existing bead ID
+    let mut by_key: HashMap<&str, usize> = HashMap::new();
+    let mut by_title: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (idx, row) in rows.iter().enumerate() {
+        if by_key.insert(row.key.as_str(), idx).is_some() {
+            errors.push(format!("Duplicate temporary ID '{}'", row.key));
+        }
+        by_title.entry(row.bead.title.as_str()).or_default().push(idx);
+    }
+
+    let resolve = |reference: &str, errors: &mut Vec<String>, row_key: &str| -> Option<BeadRef> {
+        if let Some(idx) = by_key.get(reference) {
+            return Some(BeadRef::Row(*idx));
+        }
+        match by_title.get(reference).map(|v| v.as_slice()) {
+            Some([idx]) => return Some(BeadRef::Row(*idx)),
+            Some(_) => {
+                errors.push(format!("{}: reference '{}' matches several rows by title", row_key, reference));
+                return None;
+            }
+            None => {}
+        }
+        if existing_ids.contains(reference) {
+            return Some(BeadRef::Existing(reference.to_string()));
+        }
+        errors.push(format!("{}: unresolved reference '{}'", row_key, reference));
+        None
+    };
+
+    let mut resolved = Vec::with_capacity(rows.len());
+    for (row, (parent, deps)) in rows.iter().zip(raw_refs.iter()) {
+        let parent = parent.as_deref().and_then(|p| resolve(p, &mut errors, &row.key));
+        let blocked_by: Vec<BeadRef> = deps.iter().filter_map(|d| resolve(d, &mut errors, &row.key)).collect();
+        resolved.push((parent, blocked_by));
+    }
+    for (row, (parent, blocked_by)) in rows.iter_mut().zip(resolved) {
+        row.parent = parent;
+        row.blocked_by = blocked_by;
+    }
+
+    Ok((rows, errors))
+}
+
+/// Order rows so parents and blockers come first (Kahn's algorithm, ties by row order)
+fn topological_order(rows: &[ImportRow]) -> Result<Vec<usize>, String> {
+    let mut in_degree = vec![0usize; rows.len()];
+    let mut successors: Vec<Vec<usize>> = vec![Vec::new(); rows.len()];
+    for (idx, row) in rows.iter().enumerate() {
+        for r in row.parent.iter().chain(row.blocked_by.iter()) {
+            if let BeadRef::Row(pred) = r {
+                if *pred == idx {
+                    return Err(format!("{} references itself", row.key));
+                }
+                successors[*pred].push(idx);
+                in_degree[idx] += 1;
+            }
+        }
+    }
+
+    let mut ready: BTreeSet<usize> = (0..rows.len()).filter(|i| in_degree[*i] == 0).collect();
+    let mut order = Vec::with_capacity(rows.len());
+    while let Some(idx) = ready.pop_first() {
+        order.push(idx);
+        for succ in &successors[idx] {
+            in_degree[*succ] -= 1;
+            if in_degree[*succ] == 0 {
+                ready.insert(*succ);
+            }
+        }
+    }
+
+    if order.len() != rows.len() {
+        let stuck: Vec<&str> = (0..rows.len())
+            .filter(|i| in_degree[*i] > 0)
+            .map(|i| rows[i].key.as_str())
+            .collect();
+        return Err(format!("Dependency cycle between rows: {}", stuck.join(", ")));
+    }
+    Ok(order)
+}
+
+fn ref_label(r: &BeadRef, rows: &[ImportRow]) -> String {
+    match r {
+        BeadRef::Row(idx) => rows[*idx].key.clone(),
+        BeadRef::Existing(id) => id.clone(),
+    }
+}
+
+/// Build the preview in creation order
+fn plan(rows: &[ImportRow], order: &[usize]) -> Vec<PlannedBead> {
+    order
+        .iter()
+        .map(|idx| {
+            let row = &rows[*idx];
+            PlannedBead {
+                row: idx + 1,
+                key: row.key.clone(),
+                title: row.bead.title.clone(),
+                issue_type: row.bead.issue_type.clone(),
+                parent: row.parent.as_ref().map(|p| ref_label(p, rows)),
+                blocked_by: row.blocked_by.iter().map(|b| ref_label(b, rows)).collect(),
+                created_id: None,
+            }
+        })
+        .collect()
+}
+
+/// Import beads from a CSV file.
+///
+/// With `dryRun` set, nothing is written and the result lists what would be
+/// created (in creation order) along with any validation errors. Otherwise
+/// beads are created through `bd` and a single `beads-updated` event is emitted.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn import_beads_csv(
+    path: String,
+    mapping: CsvImportMapping,
+    dryRun: bool,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    app_handle: AppHandle,
+) -> Result<CsvImportResult, String> {
+    let ctx = crate::project::context_for(&window, project_handle.as_deref())?;
+    let file = std::fs::File::open(Path::new(&path)).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    let existing_ids: HashSet<String> = crate::bd::load_beads(&ctx)?.into_iter().map(|b| b.id).collect();
+
+    let (mut rows, mut errors) = parse_rows(file, &mapping, &existing_ids)?;
+    let order = match topological_order(&rows) {
+        Ok(order) => order,
+        Err(e) => {
+            errors.push(e);
+            (0..rows.len()).collect()
+        }
+    };
+    let mut planned = plan(&rows, &order);
+
+    if dryRun || !errors.is_empty() {
+        if !dryRun {
+            return Err(format!("CSV import aborted: {}", errors.join("; ")));
+        }
+        eprintln!("📥 import_beads_csv dry run: {} beads, {} errors", planned.len(), errors.len());
+        return Ok(CsvImportResult { dry_run: true, planned, errors });
+    }
+
+    let mut created: HashMap<usize, String> = HashMap::new();
+    let resolve_id = |r: &BeadRef, created: &HashMap<usize, String>| -> Option<String> {
+        match r {
+            BeadRef::Row(idx) => created.get(idx).cloned(),
+            BeadRef::Existing(id) => Some(id.clone()),
+        }
+    };
+
+    let mut result = Ok(());
+    for (pos, idx) in order.iter().enumerate() {
+        let row = &mut rows[*idx];
+        row.bead.parent = row.parent.as_ref().and_then(|p| resolve_id(p, &created));
+
+        let step = crate::bd::create_bead_internal(&ctx, &row.bead).and_then(|new_id| {
+            for blocker in &row.blocked_by {
+                if let Some(blocker_id) = resolve_id(blocker, &created) {
+                    crate::bd::add_dependency_internal(&ctx, &new_id, &blocker_id, "blocks")?;
+                }
+            }
+            Ok(new_id)
+        });
+
+        match step {
+            Ok(new_id) => {
+                planned[pos].created_id = Some(new_id.clone());
+                created.insert(*idx, new_id);
+            }
+            Err(e) => {
+                result = Err(format!(
+                    "Row {} ('{}') failed after creating {} beads: {}",
+                    idx + 1,
+                    row.bead.title,
+                    created.len(),
+                    e
+                ));
+                break;
+            }
+        }
+    }
+
+    if !created.is_empty() {
+        crate::project::emit_beads_updated(&app_handle, &ctx);
+    }
+    eprintln!("📥 import_beads_csv: created {} of {} beads from {}", created.len(), rows.len(), path);
+    result?;
+
+    Ok(CsvImportResult { dry_run: false, planned, errors })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping() -> CsvImportMapping {
+        CsvImportMapping {
+            title: "Summary".to_string(),
+            id: Some("Key".to_string()),
+            priority: Some("Priority".to_string()),
+            parent: Some("Parent".to_string()),
+            dependencies: Some("Blocked By".to_string()),
+            ..CsvImportMapping::default()
+        }
+    }
+
+    fn existing() -> HashSet<String> {
+        ["bp-9".to_string()].into_iter().collect()
+    }
+
+    #[test]
+    fn test_resolves_refs_and_orders_topologically() {
+        let csv = "Key,Summary,Priority,Parent,Blocked By\n\
+                   T2,Build it,P1,Epic,T3\n\
+                   T3,Design it,2,Epic,bp-9\n\
+                   T1,Epic,,,\n";
+        let (rows, errors) = parse_rows(csv.as_bytes(), &mapping(), &existing()).unwrap();
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(rows[0].bead.priority, 1);
+        assert_eq!(rows[0].parent, Some(BeadRef::Row(2)));
+        assert_eq!(rows[1].blocked_by, vec![BeadRef::Existing("bp-9".to_string())]);
+
+        let order = topological_order(&rows).unwrap();
+        let keys: Vec<&str> = order.iter().map(|i| rows[*i].key.as_str()).collect();
+        assert_eq!(keys, vec!["T1", "T3", "T2"]);
+
+        let planned = plan(&rows, &order);
+        assert_eq!(planned[2].parent.as_deref(), Some("T1"));
+        assert_eq!(planned[2].blocked_by, vec!["T3".to_string()]);
+    }
+
+    #[test]
+    fn test_reports_unresolved_and_invalid_values() {
+        let csv = "Key,Summary,Priority,Parent,Blocked By\n\
+                   T1,First,urgent,Nope,\n\
+                   T2,,,,\n";
+        let (_, errors) = parse_rows(csv.as_bytes(), &mapping(), &existing()).unwrap();
+        assert_eq!(errors.len(), 3, "{:?}", errors);
+    }
+
+    #[test]
+    fn test_detects_cycles() {
+        let csv = "Key,Summary,Priority,Parent,Blocked By\n\
+                   A,First,,,B\n\
+                   B,Second,,,A\n";
+        let (rows, errors) = parse_rows(csv.as_bytes(), &mapping(), &existing()).unwrap();
+        assert!(errors.is_empty());
+        assert!(topological_order(&rows).unwrap_err().contains("cycle"));
+    }
+
+    #[test]
+    fn test_missing_mapped_column_is_error() {
+        let csv = "Name\nfoo\n";
+        assert!(parse_rows(csv.as_bytes(), &mapping(), &existing()).is_err());
+    }
+}
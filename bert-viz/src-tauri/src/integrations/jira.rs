@@ -0,0 +1,477 @@
+//! Jira CSV export/import
+//!
+//! Export writes the WBS tree in the layout Jira's CSV importer expects:
+//! numeric `Issue Id`/`Parent Id` columns link epics, stories and sub-tasks.
+//! Import reads a Jira issue export and creates or updates beads, storing the
+//! Jira issue key in `external_reference` so a later import updates the same
+//! beads instead of duplicating them.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::{Bead, FilterParams, WBSNode};
+
+/// Map bead priority (0 = highest) to Jira's priority names
+fn jira_priority(priority: u32) -> &'static str {
+    match priority {
+        0 => "Highest",
+        1 => "High",
+        2 => "Medium",
+        3 => "Low",
+        _ => "Lowest",
+    }
+}
+
+fn bead_priority(jira: &str) -> u32 {
+    match jira.trim().to_lowercase().as_str() {
+        "highest" | "blocker" | "critical" => 0,
+        "high" | "major" => 1,
+        "low" | "minor" => 3,
+        "lowest" | "trivial" => 4,
+        _ => 2,
+    }
+}
+
+fn jira_status(status: &str) -> &'static str {
+    match status {
+        "closed" | "done" => "Done",
+        "in_progress" => "In Progress",
+        _ => "To Do",
+    }
+}
+
+fn bead_status(jira: &str) -> String {
+    match jira.trim().to_lowercase().as_str() {
+        "done" | "closed" | "resolved" | "won't do" | "cancelled" => "closed".to_string(),
+        "in progress" | "in review" | "in development" | "review" => "in_progress".to_string(),
+        _ => "open".to_string(),
+    }
+}
+
+/// Bead issue type for an imported Jira issue type
+fn bead_issue_type(jira: &str) -> String {
+    match jira.trim().to_lowercase().as_str() {
+        "epic" => "epic".to_string(),
+        "story" => "feature".to_string(),
+        "bug" => "bug".to_string(),
+        _ => "task".to_string(),
+    }
+}
+
+// ============================================================================
+// Export
+// ============================================================================
+
+struct JiraRow {
+    issue_id: usize,
+    parent_id: Option<usize>,
+    issue_type: &'static str,
+    bead: Bead,
+}
+
+/// Flatten the tree into Jira rows. Jira allows only three levels, so top-level
+/// nodes with children (or epics) become Epics, their children Stories (or Bugs),
+/// and anything deeper a Sub-task attached to its nearest story-level ancestor.
+fn tree_to_jira_rows(tree: &[WBSNode]) -> Vec<JiraRow> {
+    fn walk(
+        nodes: &[WBSNode],
+        level: usize,
+        parent_id: Option<usize>,
+        story_ancestor: Option<usize>,
+        rows: &mut Vec<JiraRow>,
+    ) {
+        for node in nodes {
+            let issue_id = rows.len() + 1;
+            let is_epic = node.bead.issue_type == "epic" || (level == 0 && !node.children.is_empty());
+            let (issue_type, parent, next_story) = if level == 0 {
+                let t = if is_epic {
+                    "Epic"
+                } else if node.bead.issue_type == "bug" {
+                    "Bug"
+                } else {
+                    "Task"
+                };
+                (t, None, if is_epic { None } else { Some(issue_id) })
+            } else if let Some(story) = story_ancestor {
+                ("Sub-task", Some(story), Some(story))
+            } else {
+                let t = if node.bead.issue_type == "bug" { "Bug" } else { "Story" };
+                (t, parent_id, Some(issue_id))
+            };
+
+            rows.push(JiraRow {
+                issue_id,
+                parent_id: parent,
+                issue_type,
+                bead: node.bead.clone(),
+            });
+            walk(&node.children, level + 1, Some(issue_id), next_story, rows);
+        }
+    }
+
+    let mut rows = Vec::new();
+    walk(tree, 0, None, None, &mut rows);
+    rows
+}
+
+/// Write the tree as a Jira-importable CSV
+pub fn write_jira_csv(tree: &[WBSNode], path: &Path) -> Result<usize, String> {
+    let rows = tree_to_jira_rows(tree);
+    // Jira expects one "Labels" column per label
+    let label_columns = rows
+        .iter()
+        .map(|r| r.bead.labels.as_ref().map(|l| l.len()).unwrap_or(0))
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    let mut writer = csv::Writer::from_path(path)
+        .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+
+    let mut header: Vec<String> = [
+        "Issue Id",
+        "Parent Id",
+        "Issue Type",
+        "Summary",
+        "Description",
+        "Priority",
+        "Status",
+        "Assignee",
+        "Original Estimate",
+        "Bead Id",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+    header.extend(std::iter::repeat_n("Labels".to_string(), label_columns));
+    writer
+        .write_record(&header)
+        .map_err(|e| format!("Failed to write Jira CSV header: {}", e))?;
+
+    for row in &rows {
+        let bead = &row.bead;
+        let mut record = vec![
+            row.issue_id.to_string(),
+            row.parent_id.map(|p| p.to_string()).unwrap_or_default(),
+            row.issue_type.to_string(),
+            bead.title.clone(),
+            bead.description.clone().unwrap_or_default(),
+            jira_priority(bead.priority).to_string(),
+            jira_status(&bead.status).to_string(),
+            bead.owner.clone().unwrap_or_default(),
+            // Bead estimates are minutes, Jira wants seconds
+            bead.estimate.map(|m| (m as u64 * 60).to_string()).unwrap_or_default(),
+            bead.id.clone(),
+        ];
+        let labels = bead.labels.clone().unwrap_or_default();
+        for i in 0..label_columns {
+            // Jira labels cannot contain spaces
+            record.push(labels.get(i).map(|l| l.replace(' ', "_")).unwrap_or_default());
+        }
+        writer
+            .write_record(&record)
+            .map_err(|e| format!("Failed to write Jira CSV row for {}: {}", bead.id, e))?;
+    }
+
+    writer.flush().map_err(|e| format!("Failed to flush {}: {}", path.display(), e))?;
+    Ok(rows.len())
+}
+
+// ============================================================================
+// Import
+// ============================================================================
+
+/// One issue parsed from a Jira CSV export
+#[derive(Debug, Clone, PartialEq)]
+struct JiraIssue {
+    key: String,
+    issue_id: Option<String>,
+    parent: Option<String>,
+    issue_type: String,
+    summary: String,
+    description: Option<String>,
+    status: String,
+    priority: String,
+    assignee: Option<String>,
+    labels: Vec<String>,
+    estimate_seconds: Option<u64>,
+}
+
+fn parse_jira_export<R: std::io::Read>(reader: R) -> Result<Vec<JiraIssue>, String> {
+    let mut csv_reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(reader);
+    let headers = csv_reader
+        .headers()
+        .map_err(|e| format!("Failed to read Jira CSV header: {}", e))?
+        .clone();
+
+    let find = |names: &[&str]| -> Option<usize> {
+        headers
+            .iter()
+            .position(|h| names.iter().any(|n| h.eq_ignore_ascii_case(n)))
+    };
+    let key_col = find(&["Issue key"]).ok_or_else(|| "Jira export has no 'Issue key' column".to_string())?;
+    let summary_col = find(&["Summary"]).ok_or_else(|| "Jira export has no 'Summary' column".to_string())?;
+    let id_col = find(&["Issue id"]);
+    let parent_col = find(&["Parent id", "Parent", "Parent key"]);
+    let type_col = find(&["Issue Type"]);
+    let description_col = find(&["Description"]);
+    let status_col = find(&["Status"]);
+    let priority_col = find(&["Priority"]);
+    let assignee_col = find(&["Assignee"]);
+    let estimate_col = find(&["Original Estimate", "Original estimate"]);
+    let label_cols: Vec<usize> = headers
+        .iter()
+        .enumerate()
+        .filter(|(_, h)| h.eq_ignore_ascii_case("Labels"))
+        .map(|(i, _)| i)
+        .collect();
+
+    let mut issues = Vec::new();
+    for (i, record) in csv_reader.records().enumerate() {
+        let record = record.map_err(|e| format!("Failed to read Jira CSV row {}: {}", i + 1, e))?;
+        let cell = |col: Option<usize>| -> Option<String> {
+            col.and_then(|c| record.get(c)).map(|v| v.to_string()).filter(|v| !v.is_empty())
+        };
+
+        let Some(key) = cell(Some(key_col)) else { continue };
+        issues.push(JiraIssue {
+            key,
+            issue_id: cell(id_col),
+            parent: cell(parent_col),
+            issue_type: cell(type_col).unwrap_or_else(|| "Task".to_string()),
+            summary: cell(Some(summary_col)).unwrap_or_default(),
+            description: cell(description_col),
+            status: cell(status_col).unwrap_or_default(),
+            priority: cell(priority_col).unwrap_or_default(),
+            assignee: cell(assignee_col),
+            labels: label_cols.iter().filter_map(|c| cell(Some(*c))).collect(),
+            estimate_seconds: cell(estimate_col).and_then(|s| s.parse().ok()),
+        });
+    }
+    Ok(issues)
+}
+
+/// Order issues so parents precede their children
+fn parent_first_order(issues: &[JiraIssue]) -> Vec<usize> {
+    let mut lookup: HashMap<&str, usize> = HashMap::new();
+    for (idx, issue) in issues.iter().enumerate() {
+        lookup.insert(issue.key.as_str(), idx);
+        if let Some(id) = &issue.issue_id {
+            lookup.insert(id.as_str(), idx);
+        }
+    }
+
+    let depth = |mut idx: usize| -> usize {
+        let mut d = 0;
+        while let Some(parent) = issues[idx].parent.as_deref().and_then(|p| lookup.get(p)) {
+            d += 1;
+            // Guard against malformed exports with parent cycles
+            if d > issues.len() {
+                break;
+            }
+            idx = *parent;
+        }
+        d
+    };
+
+    let mut order: Vec<usize> = (0..issues.len()).collect();
+    order.sort_by_key(|idx| depth(*idx));
+    order
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraImportEntry {
+    pub jira_key: String,
+    pub bead_id: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraImportResult {
+    pub created: Vec<JiraImportEntry>,
+    pub updated: Vec<JiraImportEntry>,
+    pub failed: Vec<JiraImportEntry>,
+}
+
+fn apply_issue(issue: &JiraIssue, bead: &mut Bead, parent_id: Option<String>) {
+    bead.title = issue.summary.clone();
+    bead.description = issue.description.clone();
+    bead.status = bead_status(&issue.status);
+    bead.priority = bead_priority(&issue.priority);
+    bead.issue_type = bead_issue_type(&issue.issue_type);
+    bead.owner = issue.assignee.clone();
+    if !issue.labels.is_empty() {
+        bead.labels = Some(issue.labels.clone());
+    }
+    if let Some(secs) = issue.estimate_seconds {
+        bead.estimate = Some((secs / 60) as u32);
+    }
+    bead.external_reference = Some(issue.key.clone());
+    if parent_id.is_some() {
+        bead.parent = parent_id;
+    }
+}
+
+/// Export the filtered WBS tree as a Jira-importable CSV.
+/// Returns the number of issues written.
+#[tauri::command]
+pub fn export_jira_csv(params: FilterParams, path: String, window: tauri::Window, project_handle: Option<String>) -> Result<usize, String> {
+    let ctx = crate::project::context_for(&window, project_handle.as_deref())?;
+    let workflow = crate::workflow::WorkflowConfig::load_for_beads_file(&ctx.beads_path()?)?;
+    let beads = crate::bd::load_beads(&ctx)?;
+    let tree = crate::export::filter_and_sort_tree(&beads, &params, &workflow);
+    let count = write_jira_csv(&tree, Path::new(&path))?;
+    eprintln!("📤 Exported {} issues to Jira CSV {}", count, path);
+    Ok(count)
+}
+
+/// Import a Jira CSV export. Issues whose key matches an existing bead's
+/// `external_reference` update that bead; the rest are created.
+#[tauri::command]
+pub fn import_jira_csv(path: String, window: tauri::Window, project_handle: Option<String>, app_handle: AppHandle) -> Result<JiraImportResult, String> {
+    let ctx = crate::project::context_for(&window, project_handle.as_deref())?;
+    let file = std::fs::File::open(&path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    let issues = parse_jira_export(file)?;
+    let beads = crate::bd::load_beads(&ctx)?;
+
+    // Jira key -> bead ID, seeded from previous imports
+    let mut key_to_bead: HashMap<String, String> = beads
+        .iter()
+        .filter_map(|b| b.external_reference.as_ref().map(|r| (r.clone(), b.id.clone())))
+        .collect();
+    let id_to_key: HashMap<&str, &str> = issues
+        .iter()
+        .filter_map(|i| i.issue_id.as_deref().map(|id| (id, i.key.as_str())))
+        .collect();
+
+    let mut result = JiraImportResult::default();
+    for idx in parent_first_order(&issues) {
+        let issue = &issues[idx];
+        let parent_id = issue
+            .parent
+            .as_deref()
+            .map(|p| id_to_key.get(p).copied().unwrap_or(p))
+            .and_then(|key| key_to_bead.get(key).cloned());
+
+        let existing = key_to_bead
+            .get(&issue.key)
+            .and_then(|id| beads.iter().find(|b| &b.id == id));
+
+        let outcome = match existing {
+            Some(bead) => {
+                let mut updated = bead.clone();
+                apply_issue(issue, &mut updated, parent_id);
+                crate::bd::update_bead_internal(&ctx, &updated).map(|_| (updated.id, false))
+            }
+            None => {
+                let mut bead: Bead = serde_json::from_value(serde_json::json!({
+                    "id": "",
+                    "title": "",
+                    "status": "open",
+                    "priority": 2,
+                    "issue_type": "task",
+                }))
+                .map_err(|e| e.to_string())?;
+                apply_issue(issue, &mut bead, parent_id);
+                crate::bd::create_bead_internal(&ctx, &bead).map(|id| (id, true))
+            }
+        };
+
+        match outcome {
+            Ok((bead_id, created)) => {
+                key_to_bead.insert(issue.key.clone(), bead_id.clone());
+                let entry = JiraImportEntry { jira_key: issue.key.clone(), bead_id: Some(bead_id), error: None };
+                if created {
+                    result.created.push(entry);
+                } else {
+                    result.updated.push(entry);
+                }
+            }
+            Err(e) => {
+                eprintln!("❌ Jira import of {} failed: {}", issue.key, e);
+                result.failed.push(JiraImportEntry { jira_key: issue.key.clone(), bead_id: None, error: Some(e.to_string()) });
+            }
+        }
+    }
+
+    if !result.created.is_empty() || !result.updated.is_empty() {
+        crate::project::emit_beads_updated(&app_handle, &ctx);
+    }
+    eprintln!(
+        "📥 Jira import: {} created, {} updated, {} failed",
+        result.created.len(),
+        result.updated.len(),
+        result.failed.len()
+    );
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn bead(id: &str, issue_type: &str, parent: Option<&str>) -> Bead {
+        let deps = parent
+            .map(|p| serde_json::json!([{"issue_id": id, "depends_on_id": p, "type": "parent-child"}]))
+            .unwrap_or_else(|| serde_json::json!([]));
+        crate::test_bead(serde_json::json!({
+            "id": id,
+            "title": format!("Title {}", id),
+            "priority": 1,
+            "issue_type": issue_type,
+            "estimate": 90,
+            "labels": ["needs review"],
+            "dependencies": deps,
+        }))
+    }
+
+    #[test]
+    fn test_export_maps_hierarchy_to_jira_levels() {
+        let beads = vec![
+            bead("bp-1", "epic", None),
+            bead("bp-1.1", "feature", Some("bp-1")),
+            bead("bp-1.1.1", "task", Some("bp-1.1")),
+            bead("bp-1.1.1.1", "task", Some("bp-1.1.1")),
+        ];
+        let tree = crate::bert_core::build_wbs_tree(&beads);
+        let rows = tree_to_jira_rows(&tree);
+        let summary: Vec<(&str, Option<usize>)> = rows.iter().map(|r| (r.issue_type, r.parent_id)).collect();
+        assert_eq!(
+            summary,
+            vec![("Epic", None), ("Story", Some(1)), ("Sub-task", Some(2)), ("Sub-task", Some(2))]
+        );
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("jira.csv");
+        assert_eq!(write_jira_csv(&tree, &path).unwrap(), 4);
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.starts_with("Issue Id,Parent Id,Issue Type,Summary"));
+        assert!(contents.contains(",High,To Do,,5400,bp-1,needs_review"));
+    }
+
+    #[test]
+    fn test_parse_export_and_order_parents_first() {
+        let csv = "Summary,Issue key,Issue id,Parent id,Issue Type,Status,Priority,Labels,Labels,Original Estimate\n\
+                   Child,PRJ-2,10002,10001,Story,In Progress,Low,a,b,7200\n\
+                   Epic,PRJ-1,10001,,Epic,Done,Highest,,,\n";
+        let issues = parse_jira_export(csv.as_bytes()).unwrap();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].labels, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(issues[0].estimate_seconds, Some(7200));
+        assert_eq!(parent_first_order(&issues), vec![1, 0]);
+
+        let mut b = bead("", "task", None);
+        apply_issue(&issues[0], &mut b, Some("bp-1".to_string()));
+        assert_eq!(b.status, "in_progress");
+        assert_eq!(b.priority, 3);
+        assert_eq!(b.issue_type, "feature");
+        assert_eq!(b.estimate, Some(120));
+        assert_eq!(b.external_reference.as_deref(), Some("PRJ-2"));
+        assert_eq!(b.parent.as_deref(), Some("bp-1"));
+    }
+}
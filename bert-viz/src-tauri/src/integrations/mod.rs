@@ -0,0 +1,5 @@
+//! Bridges to external project-management tools
+//!
+//! Each integration converts between beads and the tool's own import/export
+//! formats so projects can be round-tripped without a live API connection.
+pub mod jira;
@@ -0,0 +1,210 @@
+//! Project-wide label management
+//!
+//! Labels are plain strings on each bead. `list_labels` counts their use
+//! across the project, and `rename_label` / `merge_labels` rewrite every bead
+//! carrying them. Label colors live under the `labelColors` key of
+//! `.beads/bert-viz.json` so every view colors a label the same way; they
+//! follow a label when it is renamed or merged.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Map;
+use std::collections::BTreeMap;
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::bd::BeadOpResult;
+use crate::error::BertError;
+use crate::project::context_for;
+use crate::schedule::load_config_section;
+use crate::Bead;
+
+/// Key of the label colors in the project config file
+const CONFIG_KEY: &str = "labelColors";
+
+/// Label name -> CSS color
+pub type LabelColors = BTreeMap<String, String>;
+
+fn load_colors(beads_path: &Path) -> Result<LabelColors, BertError> {
+    Ok(load_config_section(beads_path, CONFIG_KEY)?.unwrap_or_default())
+}
+
+fn save_colors(beads_path: &Path, colors: &LabelColors) -> Result<(), BertError> {
+    let mut fields = Map::new();
+    fields.insert(CONFIG_KEY.to_string(), serde_json::to_value(colors)?);
+    crate::schedule::merge_config_fields(beads_path, fields)
+}
+
+/// A label in use, with the number of beads carrying it
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelUsage {
+    pub name: String,
+    pub count: usize,
+    pub color: Option<String>,
+}
+
+fn label_usage(beads: &[Bead], colors: &LabelColors) -> Vec<LabelUsage> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for label in beads.iter().filter(|b| b.status != "tombstone").flat_map(|b| b.labels.iter().flatten()) {
+        *counts.entry(label.as_str()).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .map(|(name, count)| LabelUsage {
+            name: name.to_string(),
+            count,
+            color: colors.get(name).cloned(),
+        })
+        .collect()
+}
+
+/// New labels of `bead` after merging `from` into `into`, or None when it
+/// carries none of them. `into` takes the place of the first merged label.
+fn merged_labels(bead: &Bead, from: &[String], into: &str) -> Option<Vec<String>> {
+    let labels = bead.labels.as_ref()?;
+    if !labels.iter().any(|l| from.contains(l)) {
+        return None;
+    }
+
+    let mut merged: Vec<String> = Vec::with_capacity(labels.len());
+    for label in labels {
+        let label = if from.contains(label) { into } else { label.as_str() };
+        if !merged.iter().any(|l| l == label) {
+            merged.push(label.to_string());
+        }
+    }
+    Some(merged)
+}
+
+/// Move the colors of `from` to `into`, unless `into` already has one
+fn merge_colors(colors: &mut LabelColors, from: &[String], into: &str) {
+    for label in from {
+        if let Some(color) = colors.remove(label) {
+            colors.entry(into.to_string()).or_insert(color);
+        }
+    }
+}
+
+/// Labels used in the project with their usage counts and colors
+#[tauri::command]
+pub fn list_labels(window: tauri::Window, project_handle: Option<String>) -> Result<Vec<LabelUsage>, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    let beads_path = ctx.beads_path()?;
+    let beads = crate::bd::load_beads(&ctx)?;
+    Ok(label_usage(&beads, &load_colors(&beads_path)?))
+}
+
+/// Replace the labels in `from` with `into` on every bead carrying them.
+/// Continues past beads that fail to update and reports each one.
+#[tauri::command]
+pub fn merge_labels(from: Vec<String>, into: String, window: tauri::Window, project_handle: Option<String>, app_handle: AppHandle) -> Result<Vec<BeadOpResult>, BertError> {
+    let into = into.trim().to_string();
+    if into.is_empty() || into.contains(',') {
+        return Err(BertError::InvalidInput("Label names cannot be empty or contain commas".to_string()));
+    }
+    let from: Vec<String> = from.into_iter().filter(|l| *l != into).collect();
+    if from.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    let beads_path = ctx.beads_path()?;
+    let beads = crate::bd::load_beads(&ctx)?;
+
+    let mut results = Vec::new();
+    for bead in &beads {
+        let Some(labels) = merged_labels(bead, &from, &into) else { continue };
+        let result = match crate::bd::set_labels_internal(&ctx, &bead.id, &labels) {
+            Ok(()) => BeadOpResult { index: results.len(), success: true, bead_id: Some(bead.id.clone()), error: None, queued: false, preview: None },
+            Err(e) => {
+                eprintln!("❌ Failed to relabel {}: {}", bead.id, e);
+                BeadOpResult { index: results.len(), success: false, bead_id: Some(bead.id.clone()), error: Some(e.to_string()), queued: false, preview: None }
+            }
+        };
+        results.push(result);
+    }
+
+    let mut colors = load_colors(&beads_path)?;
+    merge_colors(&mut colors, &from, &into);
+    save_colors(&beads_path, &colors)?;
+
+    eprintln!("🏷️  Merged {} into '{}' on {} bead(s)", from.join(", "), into, results.len());
+    if results.iter().any(|r| r.success) {
+        crate::project::emit_beads_updated(&app_handle, &ctx);
+    }
+    Ok(results)
+}
+
+/// Rename a label on every bead carrying it
+#[tauri::command]
+pub fn rename_label(old: String, new: String, window: tauri::Window, project_handle: Option<String>, app_handle: AppHandle) -> Result<Vec<BeadOpResult>, BertError> {
+    merge_labels(vec![old], new, window, project_handle, app_handle)
+}
+
+/// Set or clear (with `None`) the color of a label
+#[tauri::command]
+pub fn set_label_color(label: String, color: Option<String>, window: tauri::Window, project_handle: Option<String>) -> Result<LabelColors, BertError> {
+    let beads_path = context_for(&window, project_handle.as_deref())?.beads_path()?;
+    let mut colors = load_colors(&beads_path)?;
+    match color.filter(|c| !c.trim().is_empty()) {
+        Some(color) => colors.insert(label, color),
+        None => colors.remove(&label),
+    };
+    save_colors(&beads_path, &colors)?;
+    Ok(colors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn bead(id: &str, labels: &[&str]) -> Bead {
+        crate::test_bead(serde_json::json!({
+            "id": id,
+            "labels": labels,
+        }))
+    }
+
+    fn strings(labels: &[&str]) -> Vec<String> {
+        labels.iter().map(|l| l.to_string()).collect()
+    }
+
+    #[test]
+    fn test_usage_counts_labels() {
+        let beads = vec![bead("a", &["ui", "bug"]), bead("b", &["bug"]), bead("c", &[])];
+        let colors = LabelColors::from([("bug".to_string(), "#f00".to_string())]);
+        let usage = label_usage(&beads, &colors);
+        assert_eq!(
+            usage,
+            vec![
+                LabelUsage { name: "bug".to_string(), count: 2, color: Some("#f00".to_string()) },
+                LabelUsage { name: "ui".to_string(), count: 1, color: None },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_replaces_in_place_without_duplicates() {
+        let from = strings(&["defect", "bug"]);
+        assert_eq!(merged_labels(&bead("a", &["ui", "bug", "p1"]), &from, "issue"), Some(strings(&["ui", "issue", "p1"])));
+        assert_eq!(merged_labels(&bead("b", &["defect", "issue", "bug"]), &from, "issue"), Some(strings(&["issue"])));
+        assert_eq!(merged_labels(&bead("c", &["ui"]), &from, "issue"), None);
+    }
+
+    #[test]
+    fn test_colors_follow_merged_labels_and_persist() {
+        let dir = TempDir::new().unwrap();
+        let beads_path = dir.path().join("issues.jsonl");
+
+        let mut colors = LabelColors::from([
+            ("bug".to_string(), "#f00".to_string()),
+            ("defect".to_string(), "#0f0".to_string()),
+        ]);
+        merge_colors(&mut colors, &strings(&["bug", "defect"]), "issue");
+        assert_eq!(colors, LabelColors::from([("issue".to_string(), "#f00".to_string())]));
+
+        save_colors(&beads_path, &colors).unwrap();
+        assert_eq!(load_colors(&beads_path).unwrap(), colors);
+    }
+}
@@ -0,0 +1,312 @@
+//! Layout engines for `get_processed_data`
+//!
+//! Every engine turns the filtered beads and their WBS tree into a
+//! `GanttLayout` of positioned items and connectors, so the frontend draws
+//! them the same way whichever engine produced them:
+//!
+//! - `Gantt`: one row per visible tree node, positioned by earliest start
+//! - `Dag`: the blocking dependencies as a layered graph, one column per
+//!   dependency depth, ordered within columns to keep edges short
+//! - `OwnerTimeline`: one swimlane per owner with their leaf tasks on it
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::{Bead, GanttConnector, GanttItem, GanttLayout, NodeRange, Point, WBSNode};
+
+/// Vertical distance between rows, matching the Gantt rows
+const ROW_HEIGHT: usize = 48;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutKind {
+    #[default]
+    Gantt,
+    Dag,
+    OwnerTimeline,
+}
+
+/// Everything computed before layout that the engines may use
+pub(crate) struct LayoutInput<'a> {
+    pub beads: &'a [Bead],
+    pub tree: &'a [WBSNode],
+    pub x_map: &'a HashMap<String, usize>,
+    pub range_cache: &'a HashMap<String, NodeRange>,
+    pub critical_path: &'a HashSet<String>,
+    pub zoom: f64,
+    /// Fold leaf tasks into summary bars (Gantt only)
+    pub aggregate: bool,
+}
+
+pub(crate) trait LayoutEngine {
+    fn layout(&self, input: &LayoutInput) -> GanttLayout;
+}
+
+pub(crate) fn engine_for(kind: LayoutKind) -> Box<dyn LayoutEngine> {
+    match kind {
+        LayoutKind::Gantt => Box::new(GanttEngine),
+        LayoutKind::Dag => Box::new(DagEngine),
+        LayoutKind::OwnerTimeline => Box::new(OwnerTimelineEngine),
+    }
+}
+
+fn row_center(row: usize) -> f64 {
+    (row * ROW_HEIGHT + ROW_HEIGHT / 2) as f64
+}
+
+/// IDs of beads with a blocker that isn't closed
+fn blocked_ids(beads: &[Bead]) -> HashSet<&str> {
+    let status: HashMap<&str, &str> = beads.iter().map(|b| (b.id.as_str(), b.status.as_str())).collect();
+    beads
+        .iter()
+        .filter(|b| {
+            b.dependencies
+                .iter()
+                .filter(|d| d.r#type == "blocks")
+                .any(|d| status.get(d.depends_on_id.as_str()).is_some_and(|s| *s != "closed"))
+        })
+        .map(|b| b.id.as_str())
+        .collect()
+}
+
+/// Items placed by an engine, keyed by bead ID, plus connectors between them
+/// for every blocking dependency
+fn connect(items: &[GanttItem], critical_path: &HashSet<String>) -> Vec<GanttConnector> {
+    let by_id: HashMap<&str, &GanttItem> = items.iter().map(|i| (i.bead.id.as_str(), i)).collect();
+    let mut connectors = Vec::new();
+    for item in items {
+        for dep in item.bead.dependencies.iter().filter(|d| d.r#type == "blocks") {
+            let Some(pred) = by_id.get(dep.depends_on_id.as_str()) else { continue };
+            connectors.push(GanttConnector {
+                from: Point { x: pred.x + pred.width, y: row_center(pred.row) },
+                to: Point { x: item.x, y: row_center(item.row) },
+                is_critical: critical_path.contains(&item.bead.id) && critical_path.contains(&pred.bead.id),
+            });
+        }
+    }
+    connectors
+}
+
+/// The original Gantt chart
+struct GanttEngine;
+
+impl LayoutEngine for GanttEngine {
+    fn layout(&self, input: &LayoutInput) -> GanttLayout {
+        crate::bert_core::generate_gantt_layout(
+            input.beads,
+            input.tree,
+            input.x_map,
+            input.range_cache,
+            input.critical_path,
+            input.zoom,
+            input.aggregate,
+        )
+    }
+}
+
+/// Layered graph of blocking dependencies. Beads without any blocking
+/// dependency among the filtered beads are left out.
+struct DagEngine;
+
+impl LayoutEngine for DagEngine {
+    fn layout(&self, input: &LayoutInput) -> GanttLayout {
+        let ids: HashSet<&str> = input.beads.iter().map(|b| b.id.as_str()).collect();
+
+        // Blocking edges between filtered beads only
+        let mut blocks_map: HashMap<String, Vec<String>> = HashMap::new();
+        let mut linked: HashSet<&str> = HashSet::new();
+        for bead in input.beads {
+            for dep in bead.dependencies.iter().filter(|d| d.r#type == "blocks") {
+                if ids.contains(dep.depends_on_id.as_str()) {
+                    blocks_map.entry(bead.id.clone()).or_default().push(dep.depends_on_id.clone());
+                    linked.insert(bead.id.as_str());
+                    linked.insert(dep.depends_on_id.as_str());
+                }
+            }
+        }
+        let nodes: Vec<Bead> = input.beads.iter().filter(|b| linked.contains(b.id.as_str())).cloned().collect();
+        let layers = crate::bert_core::calculate_earliest_start_times(&nodes, &blocks_map, false);
+
+        let mut by_layer: BTreeMap<usize, Vec<&Bead>> = BTreeMap::new();
+        for bead in &nodes {
+            by_layer.entry(layers.get(&bead.id).copied().unwrap_or(0)).or_default().push(bead);
+        }
+
+        // Order each layer by the mean row of its blockers (barycenter
+        // heuristic) so edges cross less; ties go by priority then ID
+        let mut rows: HashMap<&str, usize> = HashMap::new();
+        for layer in by_layer.values_mut() {
+            let barycenter = |bead: &Bead| {
+                let pred_rows: Vec<usize> = blocks_map
+                    .get(&bead.id)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|p| rows.get(p.as_str()).copied())
+                    .collect();
+                if pred_rows.is_empty() {
+                    f64::MAX
+                } else {
+                    pred_rows.iter().sum::<usize>() as f64 / pred_rows.len() as f64
+                }
+            };
+            let mut keyed: Vec<(f64, &Bead)> = layer.iter().map(|b| (barycenter(b), *b)).collect();
+            keyed.sort_by(|(ka, a), (kb, b)| {
+                ka.total_cmp(kb).then(a.priority.cmp(&b.priority)).then(a.id.cmp(&b.id))
+            });
+            *layer = keyed.into_iter().map(|(_, b)| b).collect();
+            for (row, bead) in layer.iter().enumerate() {
+                rows.insert(bead.id.as_str(), row);
+            }
+        }
+
+        let blocked = blocked_ids(input.beads);
+        let items: Vec<GanttItem> = by_layer
+            .iter()
+            .flat_map(|(layer, beads)| beads.iter().map(move |b| (*layer, *b)))
+            .map(|(layer, bead)| GanttItem {
+                bead: bead.clone(),
+                x: layer as f64 * input.zoom,
+                width: 10.0 * input.zoom,
+                row: rows[bead.id.as_str()],
+                depth: 0,
+                is_critical: input.critical_path.contains(&bead.id),
+                is_blocked: blocked.contains(bead.id.as_str()),
+                is_milestone: false,
+                summary: None,
+            })
+            .collect();
+
+        let row_count = by_layer.values().map(Vec::len).max().unwrap_or(0);
+        GanttLayout {
+            connectors: connect(&items, input.critical_path),
+            items,
+            row_count,
+            row_depths: vec![0; row_count],
+            owner_lanes: Vec::new(),
+        }
+    }
+}
+
+/// One row per owner holding their leaf tasks at their scheduled position.
+/// Unowned beads and parents are left out.
+struct OwnerTimelineEngine;
+
+impl LayoutEngine for OwnerTimelineEngine {
+    fn layout(&self, input: &LayoutInput) -> GanttLayout {
+        let lanes = crate::bert_core::build_owner_lanes(input.beads, input.x_map);
+        let by_id: HashMap<&str, &Bead> = input.beads.iter().map(|b| (b.id.as_str(), b)).collect();
+        let blocked = blocked_ids(input.beads);
+
+        let mut items = Vec::new();
+        for (row, lane) in lanes.iter().enumerate() {
+            for id in &lane.bead_ids {
+                let Some(bead) = by_id.get(id.as_str()) else { continue };
+                let range = input.range_cache.get(id).cloned().unwrap_or(NodeRange {
+                    x: input.x_map.get(id).copied().unwrap_or(0) as f64,
+                    width: 10.0,
+                });
+                let is_milestone = bead.is_milestone == Some(true);
+                items.push(GanttItem {
+                    bead: (*bead).clone(),
+                    x: range.x * input.zoom,
+                    width: if is_milestone { 0.0 } else { range.width * input.zoom },
+                    row,
+                    depth: 0,
+                    is_critical: input.critical_path.contains(id),
+                    is_blocked: blocked.contains(id.as_str()),
+                    is_milestone,
+                    summary: None,
+                });
+            }
+        }
+
+        GanttLayout {
+            connectors: connect(&items, input.critical_path),
+            items,
+            row_count: lanes.len(),
+            row_depths: vec![0; lanes.len()],
+            owner_lanes: lanes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bead(id: &str, owner: &str, blockers: &[&str]) -> Bead {
+        let deps: Vec<_> = blockers
+            .iter()
+            .map(|b| json!({ "issue_id": id, "depends_on_id": b, "type": "blocks" }))
+            .collect();
+        crate::test_bead(json!({
+            "id": id,
+            "owner": owner,
+            "dependencies": deps,
+        }))
+    }
+
+    fn run(kind: LayoutKind, beads: &[Bead]) -> GanttLayout {
+        let tree = crate::bert_core::build_wbs_tree(beads);
+        let mut blocks_map: HashMap<String, Vec<String>> = HashMap::new();
+        for b in beads {
+            for d in b.dependencies.iter().filter(|d| d.r#type == "blocks") {
+                blocks_map.entry(b.id.clone()).or_default().push(d.depends_on_id.clone());
+            }
+        }
+        let x_map = crate::bert_core::calculate_earliest_start_times(beads, &blocks_map, false);
+        let mut range_cache = HashMap::new();
+        crate::bert_core::calculate_node_ranges(&tree, &x_map, &mut range_cache);
+        let critical_path = HashSet::new();
+        engine_for(kind).layout(&LayoutInput {
+            beads,
+            tree: &tree,
+            x_map: &x_map,
+            range_cache: &range_cache,
+            critical_path: &critical_path,
+            zoom: 1.0,
+            aggregate: false,
+        })
+    }
+
+    fn position(layout: &GanttLayout, id: &str) -> (f64, usize) {
+        let item = layout.items.iter().find(|i| i.bead.id == id).unwrap();
+        (item.x, item.row)
+    }
+
+    #[test]
+    fn test_dag_layers_follow_blocking_depth() {
+        // a -> b -> d, a -> c -> d; e is unrelated
+        let beads = vec![
+            bead("a", "ann", &[]),
+            bead("b", "bob", &["a"]),
+            bead("c", "ann", &["a"]),
+            bead("d", "bob", &["b", "c"]),
+            bead("e", "bob", &[]),
+        ];
+        let layout = run(LayoutKind::Dag, &beads);
+
+        assert_eq!(layout.items.len(), 4);
+        assert_eq!(position(&layout, "a").0, 0.0);
+        assert_eq!(position(&layout, "b").0, 1.0);
+        assert_eq!(position(&layout, "c").0, 1.0);
+        assert_eq!(position(&layout, "d").0, 2.0);
+        assert_ne!(position(&layout, "b").1, position(&layout, "c").1);
+        assert_eq!(layout.row_count, 2);
+        assert_eq!(layout.connectors.len(), 4);
+    }
+
+    #[test]
+    fn test_owner_timeline_has_a_row_per_owner() {
+        let beads = vec![bead("a", "ann", &[]), bead("b", "bob", &["a"]), bead("c", "ann", &["b"])];
+        let layout = run(LayoutKind::OwnerTimeline, &beads);
+
+        assert_eq!(layout.row_count, 2);
+        assert_eq!(layout.owner_lanes.iter().map(|l| l.owner.as_str()).collect::<Vec<_>>(), vec!["ann", "bob"]);
+        assert_eq!(position(&layout, "a").1, 0);
+        assert_eq!(position(&layout, "c").1, 0);
+        assert_eq!(position(&layout, "b").1, 1);
+        assert_eq!(layout.connectors.len(), 2);
+    }
+}
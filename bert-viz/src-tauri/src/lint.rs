@@ -0,0 +1,511 @@
+//! Beads file linting
+//!
+//! Loading skips lines that aren't valid beads and the rest of the app
+//! tolerates odd values (an unknown status counts as not started, a bad
+//! timestamp is ignored), so mistakes in a hand-edited or merged issues.jsonl
+//! go unnoticed. `lint_beads` checks every line against the shape bd writes:
+//! required fields, statuses of the project's workflow, priorities 0-4, known
+//! issue types, RFC 3339 timestamps, dependencies on beads that exist, and a
+//! `parent` field that agrees with the parent-child dependency. Each finding
+//! carries its line number and, where there is an obvious one, a fix as a
+//! `bd` command.
+
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+
+use crate::dependencies::Severity;
+use crate::error::BertError;
+use crate::workflow::WorkflowConfig;
+
+/// Fields every bead needs to load
+const REQUIRED_FIELDS: [&str; 5] = ["id", "title", "status", "priority", "issue_type"];
+/// Issue types bd knows
+pub(crate) const ISSUE_TYPES: [&str; 5] = ["bug", "feature", "task", "epic", "chore"];
+/// Timestamps bd writes as RFC 3339
+const TIMESTAMP_FIELDS: [&str; 3] = ["created_at", "updated_at", "closed_at"];
+const MAX_PRIORITY: u64 = 4;
+/// Priority suggested when there is nothing better to go on
+const DEFAULT_PRIORITY: u64 = 2;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum LintIssueKind {
+    /// Not a JSON object
+    InvalidJson,
+    MissingField,
+    /// Two lines with the same ID
+    DuplicateId,
+    /// Not a state of the project's workflow
+    InvalidStatus,
+    InvalidPriority,
+    UnknownIssueType,
+    MalformedTimestamp,
+    /// A dependency on a bead that doesn't exist
+    DanglingDependency,
+    /// The `parent` field and the parent-child dependencies disagree
+    ParentMismatch,
+}
+
+/// How to fix a finding
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LintFix {
+    pub description: String,
+    /// bd command applying the fix, when there is one
+    pub command: Option<String>,
+}
+
+impl LintFix {
+    fn manual(description: impl Into<String>) -> Option<Self> {
+        Some(LintFix { description: description.into(), command: None })
+    }
+
+    fn run(description: impl Into<String>, command: String) -> Option<Self> {
+        Some(LintFix { description: description.into(), command: Some(command) })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LintIssue {
+    /// 1-based line of issues.jsonl
+    pub line: usize,
+    pub bead_id: Option<String>,
+    pub kind: LintIssueKind,
+    pub severity: Severity,
+    pub field: Option<String>,
+    pub message: String,
+    pub fix: Option<LintFix>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LintReport {
+    /// No errors (warnings are allowed)
+    pub valid: bool,
+    /// Non-blank lines checked
+    pub line_count: usize,
+    pub issues: Vec<LintIssue>,
+}
+
+/// `value` lowercased with `-` and spaces as `_`, to match near misses like "In Progress"
+fn normalized(value: &str) -> String {
+    value.trim().to_lowercase().replace(['-', ' '], "_")
+}
+
+/// Closest priority bd accepts
+fn suggested_priority(value: &Value) -> u64 {
+    match value {
+        Value::Number(n) => n.as_f64().map_or(DEFAULT_PRIORITY, |p| p.round().clamp(0.0, MAX_PRIORITY as f64) as u64),
+        Value::String(s) => s
+            .trim()
+            .trim_start_matches(['p', 'P'])
+            .parse::<u64>()
+            .map_or(DEFAULT_PRIORITY, |p| p.min(MAX_PRIORITY)),
+        _ => DEFAULT_PRIORITY,
+    }
+}
+
+/// Checks of one bead line
+struct LineLinter<'a> {
+    line: usize,
+    id: Option<&'a str>,
+    issues: &'a mut Vec<LintIssue>,
+}
+
+impl LineLinter<'_> {
+    fn push(&mut self, kind: LintIssueKind, severity: Severity, field: Option<&str>, message: String, fix: Option<LintFix>) {
+        self.issues.push(LintIssue {
+            line: self.line,
+            bead_id: self.id.map(str::to_string),
+            kind,
+            severity,
+            field: field.map(str::to_string),
+            message,
+            fix,
+        });
+    }
+
+    /// `bd update` of this bead, or None when it has no ID to address it by
+    fn update(&self, flag: &str, value: &str) -> Option<String> {
+        self.id.map(|id| format!("bd update {} --{} {}", id, flag, value))
+    }
+
+    fn fix_with(&self, description: String, command: Option<String>) -> Option<LintFix> {
+        match command {
+            Some(command) => LintFix::run(description, command),
+            None => LintFix::manual(description),
+        }
+    }
+}
+
+/// Lint the contents of an issues.jsonl file against `workflow`
+pub fn lint(bytes: &[u8], workflow: &WorkflowConfig) -> LintReport {
+    let mut issues = Vec::new();
+    let mut objects: Vec<(usize, Map<String, Value>)> = Vec::new();
+
+    for (index, raw) in bytes.split(|&b| b == b'\n').enumerate() {
+        if raw.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+        let line = index + 1;
+        match serde_json::from_slice::<Value>(raw) {
+            Ok(Value::Object(obj)) => objects.push((line, obj)),
+            Ok(other) => issues.push(LintIssue {
+                line,
+                bead_id: None,
+                kind: LintIssueKind::InvalidJson,
+                severity: Severity::Error,
+                field: None,
+                message: format!("Expected a bead object, found {}", json_kind(&other)),
+                fix: LintFix::manual("Remove the line"),
+            }),
+            Err(e) => issues.push(LintIssue {
+                line,
+                bead_id: None,
+                kind: LintIssueKind::InvalidJson,
+                severity: Severity::Error,
+                field: None,
+                message: format!("Invalid JSON: {}", e),
+                fix: LintFix::manual("Repair or remove the line; a merge conflict or an interrupted write usually leaves one behind"),
+            }),
+        }
+    }
+
+    // Which IDs exist, and where each was first seen
+    let mut first_line: HashMap<&str, usize> = HashMap::new();
+    for (line, obj) in &objects {
+        if let Some(id) = obj.get("id").and_then(Value::as_str) {
+            first_line.entry(id).or_insert(*line);
+        }
+    }
+    let ids: HashSet<&str> = first_line.keys().copied().collect();
+
+    for (line, obj) in &objects {
+        let id = obj.get("id").and_then(Value::as_str).filter(|id| !id.trim().is_empty());
+        let mut linter = LineLinter { line: *line, id, issues: &mut issues };
+
+        for field in REQUIRED_FIELDS {
+            let missing = match obj.get(field) {
+                None | Some(Value::Null) => true,
+                Some(Value::String(s)) => s.trim().is_empty(),
+                Some(_) => false,
+            };
+            if !missing {
+                continue;
+            }
+            let (description, command) = match field {
+                "status" => ("Set it to open".to_string(), linter.update("status", "open")),
+                "priority" => (format!("Set it to {}", DEFAULT_PRIORITY), linter.update("priority", &DEFAULT_PRIORITY.to_string())),
+                "issue_type" => ("Set it to task".to_string(), linter.update("type", "task")),
+                "title" => ("Give the bead a title".to_string(), None),
+                _ => ("Give the bead a unique ID or remove the line".to_string(), None),
+            };
+            let fix = linter.fix_with(description, command);
+            linter.push(
+                LintIssueKind::MissingField,
+                Severity::Error,
+                Some(field),
+                format!("Missing required field {}; the bead is skipped when loading", field),
+                fix,
+            );
+        }
+
+        if let Some(id) = id {
+            if first_line[id] != *line {
+                linter.push(
+                    LintIssueKind::DuplicateId,
+                    Severity::Error,
+                    Some("id"),
+                    format!("{} is already defined on line {}", id, first_line[id]),
+                    LintFix::manual("Remove one of the copies, or give this one a new ID"),
+                );
+            }
+        }
+
+        match obj.get("status") {
+            Some(Value::String(status)) if !status.trim().is_empty() && !workflow.is_known_status(status) => {
+                let suggestion = normalized(status);
+                let suggestion = if workflow.is_known_status(&suggestion) { suggestion } else { "open".to_string() };
+                let fix = linter.fix_with(format!("Set it to {}", suggestion), linter.update("status", &suggestion));
+                linter.push(
+                    LintIssueKind::InvalidStatus,
+                    Severity::Warning,
+                    Some("status"),
+                    format!("Unknown status '{}'; it is treated as not started", status),
+                    fix,
+                );
+            }
+            Some(value @ (Value::Number(_) | Value::Bool(_) | Value::Array(_) | Value::Object(_))) => {
+                let fix = linter.fix_with("Set it to open".to_string(), linter.update("status", "open"));
+                linter.push(
+                    LintIssueKind::InvalidStatus,
+                    Severity::Error,
+                    Some("status"),
+                    format!("Status must be a string, found {}", json_kind(value)),
+                    fix,
+                );
+            }
+            _ => {}
+        }
+
+        if let Some(priority) = obj.get("priority").filter(|p| !p.is_null()) {
+            let in_range = priority.as_u64().filter(|p| *p <= MAX_PRIORITY);
+            if in_range.is_none() && !matches!(priority, Value::String(s) if s.trim().is_empty()) {
+                let suggestion = suggested_priority(priority).to_string();
+                let fix = linter.fix_with(format!("Set it to {}", suggestion), linter.update("priority", &suggestion));
+                // Anything but a non-negative integer fails to load
+                let (severity, message) = match priority.as_u64() {
+                    Some(p) => (Severity::Warning, format!("Priority {} is outside 0-{}", p, MAX_PRIORITY)),
+                    None => (Severity::Error, format!("Priority must be an integer from 0 to {}, found {}", MAX_PRIORITY, priority)),
+                };
+                linter.push(LintIssueKind::InvalidPriority, severity, Some("priority"), message, fix);
+            }
+        }
+
+        if let Some(Value::String(issue_type)) = obj.get("issue_type") {
+            if !issue_type.trim().is_empty() && !ISSUE_TYPES.contains(&issue_type.as_str()) {
+                let suggestion = normalized(issue_type);
+                let suggestion = if ISSUE_TYPES.contains(&suggestion.as_str()) { suggestion } else { "task".to_string() };
+                let fix = linter.fix_with(format!("Set it to {}", suggestion), linter.update("type", &suggestion));
+                linter.push(
+                    LintIssueKind::UnknownIssueType,
+                    Severity::Warning,
+                    Some("issue_type"),
+                    format!("Unknown issue type '{}' (expected one of {})", issue_type, ISSUE_TYPES.join(", ")),
+                    fix,
+                );
+            }
+        }
+
+        for field in TIMESTAMP_FIELDS {
+            let Some(value) = obj.get(field).filter(|v| !v.is_null()) else { continue };
+            if value.as_str().is_some_and(|ts| DateTime::parse_from_rfc3339(ts).is_ok()) {
+                continue;
+            }
+            linter.push(
+                LintIssueKind::MalformedTimestamp,
+                Severity::Warning,
+                Some(field),
+                format!("{} is not an RFC 3339 timestamp: {}; it is ignored", field, value),
+                LintFix::manual("Use the form 2026-01-31T12:00:00Z"),
+            );
+        }
+        if let Some(due) = obj.get("due_date").filter(|v| !v.is_null()) {
+            if !due.as_str().is_some_and(|d| crate::bert_core::parse_due_date(d).is_some()) {
+                linter.push(
+                    LintIssueKind::MalformedTimestamp,
+                    Severity::Warning,
+                    Some("due_date"),
+                    format!("due_date is neither YYYY-MM-DD nor RFC 3339: {}; it is ignored", due),
+                    LintFix::manual("Use the form 2026-01-31"),
+                );
+            }
+        }
+
+        let dependencies = obj.get("dependencies").and_then(Value::as_array).map(Vec::as_slice).unwrap_or_default();
+        let mut parents: Vec<&str> = Vec::new();
+        for dep in dependencies {
+            let target = dep.get("depends_on_id").and_then(Value::as_str);
+            let dep_type = dep.get("type").and_then(Value::as_str);
+            let (Some(target), Some(dep_type)) = (target, dep_type) else {
+                linter.push(
+                    LintIssueKind::MissingField,
+                    Severity::Error,
+                    Some("dependencies"),
+                    format!("Dependency without depends_on_id or type: {}; the bead is skipped when loading", dep),
+                    LintFix::manual("Remove the dependency"),
+                );
+                continue;
+            };
+            if dep_type == "parent-child" {
+                parents.push(target);
+            }
+            if !ids.contains(target) {
+                let fix = linter.fix_with(
+                    format!("Remove the dependency on {}", target),
+                    id.map(|id| format!("bd dep rm {} {}", id, target)),
+                );
+                linter.push(
+                    LintIssueKind::DanglingDependency,
+                    Severity::Error,
+                    Some("dependencies"),
+                    format!("{} dependency on missing bead {}", dep_type, target),
+                    fix,
+                );
+            }
+        }
+
+        let parent_field = obj.get("parent").and_then(Value::as_str).filter(|p| !p.trim().is_empty());
+        match (parent_field, parents.as_slice()) {
+            (Some(parent), []) => {
+                let fix = linter.fix_with(
+                    format!("Add the parent-child dependency on {}", parent),
+                    id.filter(|_| ids.contains(parent))
+                        .map(|id| format!("bd dep add {} {} --type parent-child", id, parent)),
+                );
+                linter.push(
+                    LintIssueKind::ParentMismatch,
+                    Severity::Warning,
+                    Some("parent"),
+                    format!("parent is {} but there is no parent-child dependency; the tree shows the bead at the top level", parent),
+                    fix,
+                );
+            }
+            (Some(parent), [first, ..]) if !parents.contains(&parent) => {
+                let fix = linter.fix_with(
+                    format!("Move the bead under {}, or drop the stale parent field if {} is right", parent, first),
+                    id.filter(|_| ids.contains(parent)).map(|id| format!("bd update {} --parent {}", id, parent)),
+                );
+                linter.push(
+                    LintIssueKind::ParentMismatch,
+                    Severity::Warning,
+                    Some("parent"),
+                    format!("parent is {} but the parent-child dependency points at {}; the tree follows the dependency", parent, first),
+                    fix,
+                );
+            }
+            _ => {}
+        }
+        if let [first, extra, ..] = parents.as_slice() {
+            let fix = linter.fix_with(
+                format!("Keep {} and remove the dependency on {}", first, extra),
+                id.map(|id| format!("bd dep rm {} {}", id, extra)),
+            );
+            linter.push(
+                LintIssueKind::ParentMismatch,
+                Severity::Warning,
+                Some("dependencies"),
+                format!("{} parent-child dependencies ({}); only {} is used", parents.len(), parents.join(", "), first),
+                fix,
+            );
+        }
+    }
+
+    issues.sort_by_key(|i| i.line);
+    LintReport {
+        valid: !issues.iter().any(|i| i.severity == Severity::Error),
+        line_count: bytes.split(|&b| b == b'\n').filter(|l| !l.iter().all(u8::is_ascii_whitespace)).count(),
+        issues,
+    }
+}
+
+fn json_kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+/// Lint the window's beads file, reporting line numbers and suggested fixes
+#[tauri::command]
+pub fn lint_beads(window: tauri::Window, project_handle: Option<String>) -> Result<LintReport, BertError> {
+    let beads_path = crate::project::context_for(&window, project_handle.as_deref())?.beads_path()?;
+    let bytes = std::fs::read(&beads_path)
+        .map_err(|e| BertError::Io(format!("Failed to read {}: {}", beads_path.display(), e)))?;
+    let workflow = WorkflowConfig::load_for_beads_file(&beads_path)?;
+    let report = lint(&bytes, &workflow);
+    eprintln!("🧹 lint_beads: {} issues in {} lines", report.issues.len(), report.line_count);
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(report: &LintReport) -> Vec<(usize, LintIssueKind)> {
+        report.issues.iter().map(|i| (i.line, i.kind)).collect()
+    }
+
+    fn commands(report: &LintReport) -> Vec<&str> {
+        report.issues.iter().filter_map(|i| i.fix.as_ref()?.command.as_deref()).collect()
+    }
+
+    #[test]
+    fn test_lint_reports_schema_problems_with_fixes() {
+        let content = concat!(
+            r#"{"id":"bp6-1","title":"Ok","status":"open","priority":1,"issue_type":"epic","created_at":"2026-01-01T00:00:00Z"}"#, "\n",
+            r#"{"id":"bp6-2","title":"Odd","status":"In Progress","priority":7,"issue_type":"Feature"}"#, "\n",
+            "\n",
+            r#"{"id":"bp6-3","status":"open","priority":"high","issue_type":"story","updated_at":"yesterday"}"#, "\n",
+            "{\"id\": \n",
+            r#"{"id":"bp6-1","title":"Copy","status":"open","priority":2,"issue_type":"task"}"#, "\n",
+        );
+        let report = lint(content.as_bytes(), &WorkflowConfig::default());
+        assert!(!report.valid);
+        assert_eq!(report.line_count, 5);
+        assert_eq!(
+            kinds(&report),
+            vec![
+                (2, LintIssueKind::InvalidStatus),
+                (2, LintIssueKind::InvalidPriority),
+                (2, LintIssueKind::UnknownIssueType),
+                (4, LintIssueKind::MissingField),
+                (4, LintIssueKind::InvalidPriority),
+                (4, LintIssueKind::UnknownIssueType),
+                (4, LintIssueKind::MalformedTimestamp),
+                (5, LintIssueKind::InvalidJson),
+                (6, LintIssueKind::DuplicateId),
+            ]
+        );
+        assert_eq!(
+            commands(&report),
+            vec![
+                "bd update bp6-2 --status in_progress",
+                "bd update bp6-2 --priority 4",
+                "bd update bp6-2 --type feature",
+                "bd update bp6-3 --priority 2",
+                "bd update bp6-3 --type task",
+            ]
+        );
+        // An out-of-range priority still loads; a string one doesn't
+        assert_eq!(report.issues[1].severity, Severity::Warning);
+        assert_eq!(report.issues[4].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_lint_checks_dependencies_and_parents() {
+        let content = concat!(
+            r#"{"id":"e","title":"E","status":"open","priority":1,"issue_type":"epic"}"#, "\n",
+            r#"{"id":"f","title":"F","status":"open","priority":1,"issue_type":"feature"}"#, "\n",
+            r#"{"id":"t1","title":"T1","status":"open","priority":2,"issue_type":"task","parent":"e"}"#, "\n",
+            r#"{"id":"t2","title":"T2","status":"open","priority":2,"issue_type":"task","parent":"e","dependencies":[{"issue_id":"t2","depends_on_id":"f","type":"parent-child"}]}"#, "\n",
+            r#"{"id":"t3","title":"T3","status":"open","priority":2,"issue_type":"task","dependencies":[{"issue_id":"t3","depends_on_id":"gone","type":"blocks"},{"issue_id":"t3","depends_on_id":"f","type":"parent-child"},{"issue_id":"t3","depends_on_id":"e","type":"parent-child"}]}"#, "\n",
+            r#"{"id":"t4","title":"T4","status":"open","priority":2,"issue_type":"task","parent":"f","dependencies":[{"issue_id":"t4","depends_on_id":"f","type":"parent-child"}]}"#, "\n",
+        );
+        let report = lint(content.as_bytes(), &WorkflowConfig::default());
+        assert_eq!(
+            kinds(&report),
+            vec![
+                (3, LintIssueKind::ParentMismatch),
+                (4, LintIssueKind::ParentMismatch),
+                (5, LintIssueKind::DanglingDependency),
+                (5, LintIssueKind::ParentMismatch),
+            ]
+        );
+        assert_eq!(
+            commands(&report),
+            vec!["bd dep add t1 e --type parent-child", "bd update t2 --parent e", "bd dep rm t3 gone", "bd dep rm t3 e"]
+        );
+    }
+
+    #[test]
+    fn test_custom_workflow_states_are_valid_statuses() {
+        let mut workflow = WorkflowConfig::default();
+        workflow.states.push(crate::workflow::WorkflowState {
+            name: "review".to_string(),
+            category: crate::workflow::StatusCategory::Doing,
+            color: None,
+        });
+        let content = r#"{"id":"a","title":"A","status":"review","priority":0,"issue_type":"bug"}"#;
+        let report = lint(content.as_bytes(), &workflow);
+        assert!(report.valid);
+        assert!(report.issues.is_empty());
+    }
+}
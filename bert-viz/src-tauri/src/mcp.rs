@@ -0,0 +1,401 @@
+//! Model Context Protocol server (`bert-viz-cli mcp`)
+//!
+//! Lets external agents (Claude Desktop, IDE assistants) work with a project's
+//! beads through typed tools instead of shelling out to `bd` and parsing its
+//! output. The server speaks JSON-RPC 2.0 over stdio, one message per line,
+//! and offers:
+//!
+//! - `list_ready`: open beads with no unfinished blockers, most urgent first
+//! - `get_bead`: one bead with all its fields
+//! - `create_bead`: a new bead, optionally under a parent
+//! - `add_dependency`: a blocking or parent-child edge, rejected if it would
+//!   create a cycle
+//!
+//! Mutations go through the same `bd` wrappers and validation as the app, so a
+//! running app picks them up through its beads watcher.
+
+use serde_json::{json, Map, Value};
+use std::collections::HashSet;
+use std::io::{BufRead, Write};
+
+use crate::error::BertError;
+use crate::project::ProjectContext;
+use crate::workflow::{StatusCategory, WorkflowConfig};
+use crate::Bead;
+
+/// MCP revision this server implements
+pub const PROTOCOL_VERSION: &str = "2024-11-05";
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Beads `list_ready` returns unless asked for more
+const DEFAULT_READY_LIMIT: usize = 20;
+
+fn tool_definitions() -> Value {
+    json!([
+        {
+            "name": "list_ready",
+            "description": "List open beads with no unfinished blockers, most urgent first",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "label": { "type": "string", "description": "Only beads with this label" },
+                    "limit": { "type": "integer", "minimum": 1, "description": "Most beads to return (default 20)" }
+                }
+            }
+        },
+        {
+            "name": "get_bead",
+            "description": "Get a bead with its description, dependencies and metadata",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "id": { "type": "string" } },
+                "required": ["id"]
+            }
+        },
+        {
+            "name": "create_bead",
+            "description": "Create a bead and return its id",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "title": { "type": "string" },
+                    "description": { "type": "string" },
+                    "issue_type": { "type": "string", "description": "task, bug, feature, epic or chore (default task)" },
+                    "priority": { "type": "integer", "minimum": 0, "maximum": 4, "description": "0 is most urgent (default 2)" },
+                    "owner": { "type": "string" },
+                    "labels": { "type": "array", "items": { "type": "string" } },
+                    "estimate": { "type": "integer", "minimum": 0, "description": "Minutes" },
+                    "parent": { "type": "string", "description": "Id of the parent bead" }
+                },
+                "required": ["title"]
+            }
+        },
+        {
+            "name": "add_dependency",
+            "description": "Make issue_id depend on depends_on_id",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "issue_id": { "type": "string", "description": "The bead that waits" },
+                    "depends_on_id": { "type": "string", "description": "The bead it waits for, or its parent" },
+                    "type": { "type": "string", "enum": ["blocks", "parent-child"], "description": "Default blocks" }
+                },
+                "required": ["issue_id", "depends_on_id"]
+            }
+        }
+    ])
+}
+
+fn rpc_result(id: &Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+fn rpc_error(id: &Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+fn text_content(text: String, is_error: bool) -> Value {
+    json!({ "content": [{ "type": "text", "text": text }], "isError": is_error })
+}
+
+fn string_arg<'a>(args: &'a Map<String, Value>, name: &str) -> Result<Option<&'a str>, BertError> {
+    match args.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s.as_str())),
+        Some(_) => Err(BertError::InvalidInput(format!("'{}' must be a string", name))),
+    }
+}
+
+fn required_arg<'a>(args: &'a Map<String, Value>, name: &str) -> Result<&'a str, BertError> {
+    string_arg(args, name)?
+        .filter(|s| !s.trim().is_empty())
+        .ok_or_else(|| BertError::InvalidInput(format!("'{}' is required", name)))
+}
+
+fn number_arg(args: &Map<String, Value>, name: &str) -> Result<Option<u64>, BertError> {
+    match args.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => value
+            .as_u64()
+            .map(Some)
+            .ok_or_else(|| BertError::InvalidInput(format!("'{}' must be a non-negative integer", name))),
+    }
+}
+
+/// Open beads whose blockers are all finished, most urgent (then oldest) first
+pub fn ready_beads(beads: &[Bead], workflow: &WorkflowConfig) -> Vec<Bead> {
+    let unfinished: HashSet<&str> = beads
+        .iter()
+        .filter(|b| b.status != "tombstone" && !workflow.is_done(&b.status))
+        .map(|b| b.id.as_str())
+        .collect();
+    let mut ready: Vec<Bead> = beads
+        .iter()
+        .filter(|b| unfinished.contains(b.id.as_str()))
+        .filter(|b| matches!(workflow.category_of(&b.status), StatusCategory::Todo | StatusCategory::Doing))
+        .filter(|b| {
+            !b.dependencies
+                .iter()
+                .any(|d| d.r#type == "blocks" && unfinished.contains(d.depends_on_id.as_str()))
+        })
+        .cloned()
+        .collect();
+    ready.sort_by(|a, b| a.priority.cmp(&b.priority).then_with(|| a.created_at.cmp(&b.created_at)));
+    ready
+}
+
+/// A stdio MCP server for one project
+pub struct McpServer {
+    ctx: ProjectContext,
+}
+
+impl McpServer {
+    pub fn new(ctx: ProjectContext) -> Self {
+        McpServer { ctx }
+    }
+
+    fn list_ready(&self, args: &Map<String, Value>) -> Result<Value, BertError> {
+        let beads = crate::bd::load_beads(&self.ctx)?;
+        let workflow = WorkflowConfig::load_for_beads_file(&self.ctx.beads_path()?)?;
+        let label = string_arg(args, "label")?;
+        let limit = number_arg(args, "limit")?.map_or(DEFAULT_READY_LIMIT, |n| n as usize);
+
+        let ready: Vec<Value> = ready_beads(&beads, &workflow)
+            .into_iter()
+            .filter(|b| label.is_none_or(|l| b.labels.iter().flatten().any(|bl| bl == l)))
+            .take(limit)
+            .map(|b| {
+                json!({
+                    "id": b.id,
+                    "title": b.title,
+                    "status": b.status,
+                    "priority": b.priority,
+                    "issue_type": b.issue_type,
+                    "owner": b.owner,
+                    "labels": b.labels.unwrap_or_default(),
+                })
+            })
+            .collect();
+        Ok(Value::Array(ready))
+    }
+
+    fn get_bead(&self, args: &Map<String, Value>) -> Result<Value, BertError> {
+        let id = required_arg(args, "id")?;
+        let bead = crate::bd::load_beads(&self.ctx)?
+            .into_iter()
+            .find(|b| b.id == id)
+            .ok_or_else(|| BertError::NotFound(format!("Bead with ID {} not found", id)))?;
+        Ok(serde_json::to_value(bead)?)
+    }
+
+    fn create_bead(&self, args: &Map<String, Value>) -> Result<Value, BertError> {
+        let title = required_arg(args, "title")?;
+        let priority = number_arg(args, "priority")?.unwrap_or(2);
+        if priority > 4 {
+            return Err(BertError::InvalidInput("'priority' must be between 0 and 4".to_string()));
+        }
+        let labels: Option<Vec<String>> = match args.get("labels") {
+            None | Some(Value::Null) => None,
+            Some(value) => Some(
+                serde_json::from_value(value.clone())
+                    .map_err(|_| BertError::InvalidInput("'labels' must be an array of strings".to_string()))?,
+            ),
+        };
+        let parent = string_arg(args, "parent")?;
+        if let Some(parent) = parent {
+            if !crate::bd::load_beads(&self.ctx)?.iter().any(|b| b.id == parent) {
+                return Err(BertError::NotFound(format!("Parent bead {} not found", parent)));
+            }
+        }
+
+        let bead: Bead = serde_json::from_value(json!({
+            "id": "",
+            "title": title.trim(),
+            "description": string_arg(args, "description")?,
+            "status": "open",
+            "priority": priority,
+            "issue_type": string_arg(args, "issue_type")?.unwrap_or("task"),
+            "estimate": number_arg(args, "estimate")?,
+            "owner": string_arg(args, "owner")?,
+            "labels": labels,
+            "parent": parent,
+        }))?;
+        let id = crate::bd::create_bead_internal(&self.ctx, &bead)?;
+        eprintln!("🔌 MCP created {}", id);
+        Ok(json!({ "id": id }))
+    }
+
+    fn add_dependency(&self, args: &Map<String, Value>) -> Result<Value, BertError> {
+        let issue_id = required_arg(args, "issue_id")?;
+        let depends_on_id = required_arg(args, "depends_on_id")?;
+        let dep_type = string_arg(args, "type")?.unwrap_or("blocks");
+
+        let beads = crate::bd::load_beads(&self.ctx)?;
+        crate::bd::validate_new_dependency(&beads, issue_id, depends_on_id, dep_type)?;
+        crate::bd::add_dependency_internal(&self.ctx, issue_id, depends_on_id, dep_type)?;
+        eprintln!("🔌 MCP added {} dependency {} -> {}", dep_type, issue_id, depends_on_id);
+        Ok(json!({ "issue_id": issue_id, "depends_on_id": depends_on_id, "type": dep_type }))
+    }
+
+    /// Run a tool; errors become an `isError` result the agent can read
+    fn call_tool(&self, name: &str, args: &Map<String, Value>) -> Option<Value> {
+        let result = match name {
+            "list_ready" => self.list_ready(args),
+            "get_bead" => self.get_bead(args),
+            "create_bead" => self.create_bead(args),
+            "add_dependency" => self.add_dependency(args),
+            _ => return None,
+        };
+        Some(match result {
+            Ok(value) => text_content(serde_json::to_string_pretty(&value).unwrap_or_default(), false),
+            Err(e) => text_content(e.to_string(), true),
+        })
+    }
+
+    /// Answer one JSON-RPC message; None for notifications
+    pub fn handle(&self, message: &Value) -> Option<Value> {
+        let id = message.get("id")?.clone();
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            return Some(rpc_error(&id, INVALID_REQUEST, "Missing method"));
+        };
+        let empty = Map::new();
+        let params = message.get("params").and_then(Value::as_object).unwrap_or(&empty);
+
+        Some(match method {
+            "initialize" => rpc_result(
+                &id,
+                json!({
+                    "protocolVersion": PROTOCOL_VERSION,
+                    "capabilities": { "tools": {} },
+                    "serverInfo": { "name": "bert-viz", "version": env!("CARGO_PKG_VERSION") },
+                }),
+            ),
+            "ping" => rpc_result(&id, json!({})),
+            "tools/list" => rpc_result(&id, json!({ "tools": tool_definitions() })),
+            "tools/call" => {
+                let Some(name) = params.get("name").and_then(Value::as_str) else {
+                    return Some(rpc_error(&id, INVALID_PARAMS, "Missing tool name"));
+                };
+                let args = params.get("arguments").and_then(Value::as_object).unwrap_or(&empty);
+                match self.call_tool(name, args) {
+                    Some(result) => rpc_result(&id, result),
+                    None => rpc_error(&id, INVALID_PARAMS, &format!("Unknown tool: {}", name)),
+                }
+            }
+            other => rpc_error(&id, METHOD_NOT_FOUND, &format!("Method not found: {}", other)),
+        })
+    }
+
+    /// Serve messages from `input` until it closes
+    pub fn run(&self, input: impl BufRead, mut output: impl Write) -> std::io::Result<()> {
+        for line in input.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response = match serde_json::from_str::<Value>(&line) {
+                Ok(message) => self.handle(&message),
+                Err(e) => Some(rpc_error(&Value::Null, PARSE_ERROR, &e.to_string())),
+            };
+            if let Some(response) = response {
+                writeln!(output, "{}", response)?;
+                output.flush()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn project() -> (TempDir, McpServer) {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join(".beads")).unwrap();
+        let lines = [
+            r#"{"id":"bp6-1","title":"Design","status":"closed","priority":1,"issue_type":"task"}"#,
+            r#"{"id":"bp6-2","title":"Build","status":"open","priority":2,"issue_type":"task","labels":["backend"],"dependencies":[{"issue_id":"bp6-2","depends_on_id":"bp6-1","type":"blocks"}]}"#,
+            r#"{"id":"bp6-3","title":"Ship","status":"open","priority":0,"issue_type":"task","dependencies":[{"issue_id":"bp6-3","depends_on_id":"bp6-2","type":"blocks"}]}"#,
+            r#"{"id":"bp6-4","title":"Docs","status":"in_progress","priority":1,"issue_type":"task"}"#,
+        ];
+        std::fs::write(dir.path().join(".beads/issues.jsonl"), lines.join("\n")).unwrap();
+        let server = McpServer::new(ProjectContext::new(dir.path()));
+        (dir, server)
+    }
+
+    fn call(server: &McpServer, name: &str, arguments: Value) -> (bool, String) {
+        let response = server
+            .handle(&json!({ "jsonrpc": "2.0", "id": 1, "method": "tools/call", "params": { "name": name, "arguments": arguments } }))
+            .unwrap();
+        let result = &response["result"];
+        (result["isError"].as_bool().unwrap(), result["content"][0]["text"].as_str().unwrap().to_string())
+    }
+
+    #[test]
+    fn test_initialize_and_list_tools() {
+        let (_dir, server) = project();
+        let init = server.handle(&json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} })).unwrap();
+        assert_eq!(init["result"]["protocolVersion"], PROTOCOL_VERSION);
+        assert!(server.handle(&json!({ "jsonrpc": "2.0", "method": "notifications/initialized" })).is_none());
+
+        let tools = server.handle(&json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" })).unwrap();
+        let names: Vec<&str> = tools["result"]["tools"].as_array().unwrap().iter().map(|t| t["name"].as_str().unwrap()).collect();
+        assert_eq!(names, vec!["list_ready", "get_bead", "create_bead", "add_dependency"]);
+
+        let unknown = server.handle(&json!({ "jsonrpc": "2.0", "id": 3, "method": "resources/list" })).unwrap();
+        assert_eq!(unknown["error"]["code"], METHOD_NOT_FOUND);
+    }
+
+    #[test]
+    fn test_read_tools() {
+        let (_dir, server) = project();
+        let (is_error, text) = call(&server, "list_ready", json!({}));
+        assert!(!is_error);
+        let ready: Vec<Value> = serde_json::from_str(&text).unwrap();
+        let ids: Vec<&str> = ready.iter().map(|b| b["id"].as_str().unwrap()).collect();
+        assert_eq!(ids, vec!["bp6-4", "bp6-2"]);
+
+        let (_, text) = call(&server, "list_ready", json!({ "label": "backend" }));
+        assert_eq!(serde_json::from_str::<Vec<Value>>(&text).unwrap().len(), 1);
+
+        let (is_error, text) = call(&server, "get_bead", json!({ "id": "bp6-3" }));
+        assert!(!is_error);
+        assert_eq!(serde_json::from_str::<Value>(&text).unwrap()["title"], "Ship");
+
+        let (is_error, text) = call(&server, "get_bead", json!({ "id": "bp6-9" }));
+        assert!(is_error && text.contains("bp6-9"));
+    }
+
+    #[test]
+    fn test_mutations_are_validated() {
+        let (_dir, server) = project();
+        let (is_error, text) = call(&server, "add_dependency", json!({ "issue_id": "bp6-1", "depends_on_id": "bp6-3" }));
+        assert!(is_error && text.contains("cycle"), "{}", text);
+
+        let (is_error, _) = call(&server, "create_bead", json!({ "title": " " }));
+        assert!(is_error);
+        let (is_error, _) = call(&server, "create_bead", json!({ "title": "Later", "priority": 9 }));
+        assert!(is_error);
+        let (is_error, _) = call(&server, "create_bead", json!({ "title": "Orphan", "parent": "bp6-9" }));
+        assert!(is_error);
+    }
+
+    #[test]
+    fn test_run_answers_each_line() {
+        let (_dir, server) = project();
+        let input = "{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"ping\"}\nnot json\n";
+        let mut output = Vec::new();
+        server.run(input.as_bytes(), &mut output).unwrap();
+
+        let responses: Vec<Value> = String::from_utf8(output).unwrap().lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0]["result"], json!({}));
+        assert_eq!(responses[1]["error"]["code"], PARSE_ERROR);
+    }
+}
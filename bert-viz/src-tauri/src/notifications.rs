@@ -0,0 +1,266 @@
+//! Desktop notifications for events the user may miss while away
+//!
+//! Notifications are raised from the backend: by the beads watcher (a bead
+//! assigned to the local user became unblocked, the critical path changed),
+//! by agent sessions (a run finished, the CLI crashed) and by `execute_bd` (a
+//! long-running command failed). Each kind can be turned off in the
+//! `notifications` section of the app settings. `init` stores the app handle
+//! at startup so call sites that have none, like `execute_bd`, can notify.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::error::BertError;
+use crate::settings::NotificationSettings;
+use crate::workflow::WorkflowConfig;
+use crate::Bead;
+
+/// `bd` commands failing after running this long are worth a notification
+pub const LONG_BD_COMMAND: Duration = Duration::from_secs(5);
+
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+/// Beads state last seen per beads file, to notify about changes only
+static SNAPSHOTS: Mutex<BTreeMap<PathBuf, BeadsSnapshot>> = Mutex::new(BTreeMap::new());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    BeadUnblocked,
+    SessionFinished,
+    SessionErrored,
+    CriticalPathChanged,
+    BdCommandFailed,
+}
+
+impl NotificationKind {
+    fn enabled(self, settings: &NotificationSettings) -> bool {
+        match self {
+            NotificationKind::BeadUnblocked => settings.bead_unblocked,
+            NotificationKind::SessionFinished => settings.session_finished,
+            NotificationKind::SessionErrored => settings.session_errored,
+            NotificationKind::CriticalPathChanged => settings.critical_path_changed,
+            NotificationKind::BdCommandFailed => settings.bd_command_failed,
+        }
+    }
+}
+
+/// Make notifications available; called once during setup
+pub fn init(app_handle: AppHandle) {
+    let _ = APP_HANDLE.set(app_handle);
+}
+
+fn show(app_handle: &AppHandle, title: &str, body: &str) -> Result<(), String> {
+    app_handle
+        .notification()
+        .builder()
+        .title(title)
+        .body(body)
+        .show()
+        .map_err(|e| e.to_string())
+}
+
+/// Show a notification unless its kind is turned off in the settings
+pub fn notify(kind: NotificationKind, title: &str, body: &str) {
+    let Some(app_handle) = APP_HANDLE.get() else { return };
+    if let Some(settings_state) = app_handle.try_state::<crate::SettingsState>() {
+        let enabled = settings_state.settings.lock().map(|s| kind.enabled(&s.notifications)).unwrap_or(true);
+        if !enabled {
+            return;
+        }
+    }
+
+    eprintln!("🔔 {}: {}", title, body);
+    if let Err(e) = show(app_handle, title, body) {
+        eprintln!("⚠️  Failed to show notification: {}", e);
+    }
+}
+
+// ============================================================================
+// Beads changes
+// ============================================================================
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct BeadsSnapshot {
+    /// Open beads of the local user whose blockers are all done
+    unblocked: HashSet<String>,
+    critical_path: HashSet<String>,
+}
+
+/// Who `bd` records as the actor, which is who beads get assigned to
+fn current_user() -> Option<String> {
+    ["BD_ACTOR", "USER", "USERNAME"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()))
+}
+
+fn assignee(bead: &Bead) -> Option<&str> {
+    bead.extra_metadata
+        .get("assignee")
+        .and_then(|v| v.as_str())
+        .or(bead.owner.as_deref())
+}
+
+/// Open beads assigned to `user` that had blockers, all of which are done
+fn unblocked_for(user: &str, beads: &[Bead], workflow: &WorkflowConfig) -> HashSet<String> {
+    let done: HashSet<&str> = beads
+        .iter()
+        .filter(|b| workflow.is_done(&b.status))
+        .map(|b| b.id.as_str())
+        .collect();
+
+    beads
+        .iter()
+        .filter(|b| !done.contains(b.id.as_str()))
+        .filter(|b| assignee(b).is_some_and(|a| a.eq_ignore_ascii_case(user)))
+        .filter(|b| {
+            let mut blockers = b.dependencies.iter().filter(|d| d.r#type == "blocks").peekable();
+            blockers.peek().is_some() && blockers.all(|d| done.contains(d.depends_on_id.as_str()))
+        })
+        .map(|b| b.id.clone())
+        .collect()
+}
+
+/// Critical path through the beads that aren't done
+fn open_critical_path(beads: &[Bead], workflow: &WorkflowConfig) -> HashSet<String> {
+    open_critical_chain(beads, workflow).into_iter().collect()
+}
+
+/// Critical path through the beads that aren't done, first blocker first
+pub(crate) fn open_critical_chain(beads: &[Bead], workflow: &WorkflowConfig) -> Vec<String> {
+    let open: Vec<Bead> = beads
+        .iter()
+        .filter(|b| b.status != "tombstone" && !workflow.is_done(&b.status))
+        .cloned()
+        .collect();
+    let open_ids: HashSet<&str> = open.iter().map(|b| b.id.as_str()).collect();
+
+    let mut successors_map: HashMap<String, Vec<String>> = HashMap::new();
+    for bead in &open {
+        for dep in bead.dependencies.iter().filter(|d| d.r#type == "blocks") {
+            if open_ids.contains(dep.depends_on_id.as_str()) {
+                successors_map.entry(dep.depends_on_id.clone()).or_default().push(bead.id.clone());
+            }
+        }
+    }
+    // Without any blockers there is no path; the search yields an empty ID
+    crate::bert_core::critical_path_chain(&open, &successors_map)
+        .into_iter()
+        .filter(|id| !id.is_empty())
+        .collect()
+}
+
+fn sorted(ids: &HashSet<String>) -> Vec<&str> {
+    let mut ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+    ids.sort();
+    ids
+}
+
+/// Notifications due when the beads go from `previous` to `current`
+fn changes(previous: &BeadsSnapshot, current: &BeadsSnapshot, beads: &[Bead]) -> Vec<(NotificationKind, String, String)> {
+    let mut notifications = Vec::new();
+
+    for id in sorted(&current.unblocked) {
+        if previous.unblocked.contains(id) {
+            continue;
+        }
+        let title = beads.iter().find(|b| b.id == id).map(|b| b.title.as_str()).unwrap_or_default();
+        notifications.push((NotificationKind::BeadUnblocked, format!("{} is unblocked", id), title.to_string()));
+    }
+
+    if previous.critical_path != current.critical_path {
+        let body = if current.critical_path.is_empty() {
+            "No open bead is blocked by another".to_string()
+        } else {
+            format!("{} open beads: {}", current.critical_path.len(), sorted(&current.critical_path).join(", "))
+        };
+        notifications.push((NotificationKind::CriticalPathChanged, "Critical path changed".to_string(), body));
+    }
+
+    notifications
+}
+
+/// Compare a changed beads file with its last snapshot. Called by the beads
+/// watcher; the first call for a file only takes the snapshot.
+pub fn beads_changed(beads_path: &Path) {
+    let beads = match crate::snapshot::read_beads(beads_path) {
+        Ok(beads) => beads,
+        Err(e) => {
+            eprintln!("⚠️  Notifications: failed to read {}: {}", beads_path.display(), e);
+            return;
+        }
+    };
+    let workflow = WorkflowConfig::load_for_beads_file(beads_path).unwrap_or_default();
+
+    let current = BeadsSnapshot {
+        unblocked: current_user().map(|user| unblocked_for(&user, &beads, &workflow)).unwrap_or_default(),
+        critical_path: open_critical_path(&beads, &workflow),
+    };
+    let previous = SNAPSHOTS.lock().unwrap().insert(beads_path.to_path_buf(), current.clone());
+
+    if let Some(previous) = previous {
+        for (kind, title, body) in changes(&previous, &current, &beads) {
+            notify(kind, &title, &body);
+        }
+    }
+}
+
+/// Show a notification to check that notifications are allowed by the OS
+#[tauri::command]
+pub fn notify_test(app_handle: AppHandle) -> Result<(), BertError> {
+    show(&app_handle, "Notifications are working", "You'll be notified about beads and agent sessions here")
+        .map_err(BertError::Internal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bead(id: &str, status: &str, assignee: Option<&str>, blockers: &[&str]) -> Bead {
+        let deps: Vec<_> = blockers
+            .iter()
+            .map(|b| json!({ "issue_id": id, "depends_on_id": b, "type": "blocks" }))
+            .collect();
+        crate::test_bead(json!({
+            "id": id,
+            "title": format!("Bead {}", id),
+            "status": status,
+            "assignee": assignee,
+            "dependencies": deps,
+        }))
+    }
+
+    #[test]
+    fn test_notifies_when_my_bead_is_unblocked() {
+        let workflow = WorkflowConfig::default();
+        let before = vec![
+            bead("a", "in_progress", Some("bob"), &[]),
+            bead("b", "open", Some("alice"), &["a"]),
+            bead("c", "open", Some("bob"), &["a"]),
+            bead("d", "open", Some("alice"), &[]),
+        ];
+        let mut after = before.clone();
+        after[0].status = "closed".to_string();
+
+        let snapshot = |beads: &[Bead]| BeadsSnapshot {
+            unblocked: unblocked_for("Alice", beads, &workflow),
+            critical_path: open_critical_path(beads, &workflow),
+        };
+        let previous = snapshot(&before);
+        let current = snapshot(&after);
+        assert!(previous.unblocked.is_empty());
+        assert_eq!(sorted(&current.unblocked), vec!["b"]);
+
+        let notifications = changes(&previous, &current, &after);
+        let kinds: Vec<_> = notifications.iter().map(|(kind, _, _)| *kind).collect();
+        assert_eq!(kinds, vec![NotificationKind::BeadUnblocked, NotificationKind::CriticalPathChanged]);
+        assert_eq!(notifications[0].1, "b is unblocked");
+        assert_eq!(notifications[0].2, "Bead b");
+
+        assert!(changes(&current, &current, &after).is_empty());
+    }
+}
@@ -0,0 +1,219 @@
+//! Setting up bead tracking in a new repo
+//!
+//! Opening a folder without `.beads` only gets the frontend an error.
+//! `init_project` is the one-click alternative: it runs `bd init` (or, without
+//! bd, creates `.beads/issues.jsonl` itself), optionally seeds a starter epic,
+//! then registers and opens the project like `open_project`, which starts the
+//! beads watcher.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tauri::AppHandle;
+
+use crate::error::BertError;
+use crate::project::ProjectContext;
+
+/// Starter epics that can be seeded into a new project
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum StarterTemplate {
+    /// A checklist for getting the plan into beads
+    GettingStarted,
+    /// The steps to a first release, each blocking the next
+    Release,
+}
+
+impl StarterTemplate {
+    /// Epic title and description, the titles of its tasks, and whether each
+    /// task blocks the next
+    fn outline(self) -> (&'static str, &'static str, &'static [&'static str], bool) {
+        match self {
+            StarterTemplate::GettingStarted => (
+                "Get started",
+                "Capture the plan for this project as beads.",
+                &[
+                    "Describe the project goals",
+                    "Break the first milestone into tasks",
+                    "Estimate and prioritize the tasks",
+                ],
+                false,
+            ),
+            StarterTemplate::Release => (
+                "First release",
+                "Everything needed to ship the first version.",
+                &["Define the release scope", "Implement the scope", "Test the release", "Ship the release"],
+                true,
+            ),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct InitProjectOptions {
+    /// Name in the project list. Defaults to the folder name.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Bead ID prefix. bd defaults to the folder name.
+    #[serde(default)]
+    pub prefix: Option<String>,
+    #[serde(default)]
+    pub starter_template: Option<StarterTemplate>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InitProjectResult {
+    pub path: String,
+    /// Whether `bd init` ran; false when the structure was created directly
+    pub used_bd: bool,
+    pub starter_epic_id: Option<String>,
+}
+
+fn check_prefix(prefix: &str) -> Result<(), BertError> {
+    let valid = prefix.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        && prefix.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && !prefix.ends_with('-');
+    if !valid {
+        return Err(BertError::InvalidInput(format!(
+            "Invalid prefix '{}': use letters, digits and inner hyphens",
+            prefix
+        )));
+    }
+    Ok(())
+}
+
+/// Create `.beads` with an empty issues.jsonl, recording the prefix where bd
+/// (and the native fallback) will find it
+fn init_beads_dir(project_path: &Path, prefix: Option<&str>) -> Result<PathBuf, BertError> {
+    let beads_dir = project_path.join(".beads");
+    fs::create_dir_all(&beads_dir)
+        .map_err(|e| BertError::Io(format!("Failed to create {}: {}", beads_dir.display(), e)))?;
+    if let Some(prefix) = prefix {
+        fs::write(beads_dir.join("config.yaml"), format!("issue-prefix: \"{}\"\n", prefix))
+            .map_err(|e| BertError::Io(format!("Failed to write the beads config: {}", e)))?;
+    }
+    let beads_path = beads_dir.join("issues.jsonl");
+    if !beads_path.exists() {
+        fs::write(&beads_path, "").map_err(|e| BertError::Io(format!("Failed to create {}: {}", beads_path.display(), e)))?;
+    }
+    Ok(beads_path)
+}
+
+fn run_bd_init(project_path: &Path, prefix: Option<&str>) -> Result<(), BertError> {
+    let mut cmd = Command::new("bd");
+    cmd.arg("init").arg("--quiet").current_dir(project_path);
+    if let Some(prefix) = prefix {
+        cmd.arg("--prefix").arg(prefix);
+    }
+    let output = cmd.output()?;
+    if !output.status.success() {
+        return Err(BertError::BdCommandFailed(String::from_utf8_lossy(&output.stderr).to_string()));
+    }
+    // bd only exports issues.jsonl on its first write; the watcher needs it now
+    init_beads_dir(project_path, None)?;
+    Ok(())
+}
+
+/// Create the epic of `template` and its tasks, returning the epic's ID
+fn seed_starter_epic(ctx: &ProjectContext, template: StarterTemplate) -> Result<String, BertError> {
+    let (title, description, tasks, chained) = template.outline();
+    let mut epic = crate::import::blank_bead(title.to_string());
+    epic.issue_type = "epic".to_string();
+    epic.description = Some(description.to_string());
+    let epic_id = crate::bd::create_bead_internal(ctx, &epic)?;
+
+    let mut previous: Option<String> = None;
+    for task in tasks {
+        let mut bead = crate::import::blank_bead(task.to_string());
+        bead.parent = Some(epic_id.clone());
+        let id = crate::bd::create_bead_internal(ctx, &bead)?;
+        if let Some(blocker) = previous.as_deref().filter(|_| chained) {
+            crate::bd::add_dependency_internal(ctx, &id, blocker, "blocks")?;
+        }
+        previous = Some(id);
+    }
+    Ok(epic_id)
+}
+
+/// Set up bead tracking in `path` and open it in this window
+#[tauri::command]
+pub fn init_project(
+    path: String,
+    options: Option<InitProjectOptions>,
+    window: tauri::Window,
+    app_handle: AppHandle,
+) -> Result<InitProjectResult, BertError> {
+    let options = options.unwrap_or_default();
+    let project_path = Path::new(&path);
+    if !project_path.is_dir() {
+        return Err(BertError::ProjectNotFound(format!("Project directory does not exist: {}", path)));
+    }
+    if project_path.join(".beads").exists() {
+        return Err(BertError::InvalidInput(format!("{} already has a .beads directory; open it instead", path)));
+    }
+    let prefix = options.prefix.as_deref().map(str::trim).filter(|p| !p.is_empty());
+    if let Some(prefix) = prefix {
+        check_prefix(prefix)?;
+    }
+
+    let used_bd = crate::bd::is_bd_available();
+    if used_bd {
+        run_bd_init(project_path, prefix)?;
+    } else {
+        init_beads_dir(project_path, prefix)?;
+    }
+    eprintln!("🌱 init_project: tracking beads in {}{}", path, if used_bd { "" } else { " (without bd)" });
+
+    let starter_epic_id = match options.starter_template {
+        Some(template) => Some(seed_starter_epic(&ProjectContext::new(project_path), template)?),
+        None => None,
+    };
+
+    if let Some(name) = options.name.filter(|n| !n.trim().is_empty()) {
+        crate::add_project(
+            crate::Project { name: name.trim().to_string(), path: path.clone(), is_favorite: false, last_opened: None },
+            app_handle.clone(),
+        )?;
+    }
+    crate::open_project(path.clone(), window, app_handle)?;
+
+    Ok(InitProjectResult { path, used_bd, starter_epic_id })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_init_without_bd_uses_configured_prefix() {
+        let dir = TempDir::new().unwrap();
+        let beads_path = init_beads_dir(dir.path(), Some("acme")).unwrap();
+        assert_eq!(beads_path, dir.path().join(".beads/issues.jsonl"));
+        assert_eq!(fs::read_to_string(&beads_path).unwrap(), "");
+        assert_eq!(crate::bd::find_repo_root_from(dir.path()).as_deref(), Some(dir.path()));
+
+        let id = crate::bd::native_create_bead(&beads_path, &crate::import::blank_bead("First".to_string())).unwrap();
+        assert!(id.starts_with("acme-"), "{}", id);
+    }
+
+    #[test]
+    fn test_prefix_validation() {
+        assert!(check_prefix("bp6").is_ok());
+        assert!(check_prefix("my-proj").is_ok());
+        for bad in ["-x", "x-", "a b", "ü"] {
+            assert!(check_prefix(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_release_template_chains_its_tasks() {
+        let (_, _, tasks, chained) = StarterTemplate::Release.outline();
+        assert_eq!(tasks.len(), 4);
+        assert!(chained);
+        assert!(!StarterTemplate::GettingStarted.outline().3);
+    }
+}
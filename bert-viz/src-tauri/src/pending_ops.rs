@@ -0,0 +1,448 @@
+//! Queue of bead mutations waiting for `bd` or the repo to come back
+//!
+//! An edit that fails because the environment is unavailable (the beads file
+//! is locked, `bd` cannot reach its database, the sync worktree is missing)
+//! used to be lost. Such failures are appended to
+//! ~/.bp6/pending-ops/<project>.jsonl instead, and the retrier replays the
+//! queue in order every `RETRY_INTERVAL`. While a project has queued edits,
+//! new ones queue behind them so they never apply out of order.
+//!
+//! Operations that fail for other reasons (a deleted bead, invalid input, a
+//! file that can't be written) are returned as errors; if that happens during
+//! a replay the operation stays in the queue marked failed until it is
+//! discarded.
+//!
+//! Each queue file has its own lock, held only while the file is read or
+//! written, never while `bd` runs.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::baseline::project_store_key;
+use crate::bd::BeadOp;
+use crate::error::BertError;
+use crate::events::{emit_scoped, EventScope};
+use crate::project::{ProjectContext, ProjectContexts};
+
+/// How often queued operations are retried
+const RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// `bd` errors that mean "try again later" rather than "this edit is wrong"
+const TRANSIENT_MARKERS: [&str; 5] = ["locked", "busy", "worktree", "timed out", "connection refused"];
+
+/// Prefix of the ID a queued create returns in place of the bead's
+pub const PENDING_ID_PREFIX: &str = "pending-";
+
+/// One lock per queue file, serializing its read-modify-writes
+static QUEUE_LOCKS: Mutex<BTreeMap<PathBuf, Arc<Mutex<()>>>> = Mutex::new(BTreeMap::new());
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingOp {
+    pub id: String,
+    pub op: BeadOp,
+    /// Project the operation was made in; it is only ever replayed there
+    #[serde(default)]
+    pub project_path: PathBuf,
+    pub queued_at: DateTime<Utc>,
+    /// Replays attempted so far
+    #[serde(default)]
+    pub attempts: u32,
+    pub last_error: String,
+    /// A replay failed for a reason retrying won't fix; kept until discarded
+    #[serde(default)]
+    pub failed: bool,
+}
+
+impl PendingOp {
+    fn new(op: BeadOp, error: String, project_path: &Path) -> Self {
+        PendingOp {
+            id: uuid::Uuid::new_v4().to_string(),
+            op,
+            project_path: canonical(project_path),
+            queued_at: Utc::now(),
+            attempts: 0,
+            last_error: error,
+            failed: false,
+        }
+    }
+
+    /// Whether the operation was made in the project at `project_path`
+    fn is_for(&self, project_path: &Path) -> bool {
+        self.project_path == canonical(project_path)
+    }
+
+    /// The bead the operation changes, or a `pending-` handle for a create
+    pub fn affected_id(&self) -> String {
+        match &self.op {
+            BeadOp::Create { .. } => format!("{}{}", PENDING_ID_PREFIX, self.id),
+            BeadOp::Update { bead } => bead.id.clone(),
+            BeadOp::Close { bead_id, .. } | BeadOp::Reopen { bead_id } | BeadOp::Claim { bead_id } => bead_id.clone(),
+            BeadOp::AddDependency { issue_id, .. } | BeadOp::RemoveDependency { issue_id, .. } => issue_id.clone(),
+        }
+    }
+}
+
+/// Outcome of `submit`
+pub enum Submitted {
+    /// Applied now; the affected bead ID
+    Applied(String),
+    Queued(Box<PendingOp>),
+}
+
+/// Payload of `pending-ops-changed`
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingOpsChanged {
+    pub project_path: PathBuf,
+    pub count: usize,
+}
+
+/// Whether an error may go away without changing the operation. I/O errors
+/// that may (a blocked, timed out or interrupted operation) arrive as
+/// `FileLocked`; other `Io` errors, such as a denied permission or a full
+/// disk, won't fix themselves.
+pub fn is_transient(error: &BertError) -> bool {
+    match error {
+        BertError::BdNotInstalled | BertError::FileLocked(_) | BertError::BeadsFileNotFound(_) => true,
+        BertError::BdCommandFailed(stderr) => {
+            let stderr = stderr.to_lowercase();
+            TRANSIENT_MARKERS.iter().any(|marker| stderr.contains(marker))
+        }
+        _ => false,
+    }
+}
+
+/// Lock of the queue file at `path`
+fn queue_lock(path: &Path) -> Arc<Mutex<()>> {
+    QUEUE_LOCKS.lock().unwrap_or_else(|e| e.into_inner()).entry(path.to_path_buf()).or_default().clone()
+}
+
+/// Run `f` while holding the lock of the queue file at `path`
+fn with_queue<T>(path: &Path, f: impl FnOnce() -> Result<T, BertError>) -> Result<T, BertError> {
+    let lock = queue_lock(path);
+    let _guard = lock.lock().unwrap_or_else(|e| e.into_inner());
+    f()
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Queue file of a project (~/.bp6/pending-ops/<project key>.jsonl)
+fn queue_path(project_path: &Path) -> Result<PathBuf, BertError> {
+    let home = dirs::home_dir().ok_or_else(|| BertError::Io("Failed to get home directory".to_string()))?;
+    let dir = home.join(".bp6").join("pending-ops");
+    fs::create_dir_all(&dir).map_err(|e| BertError::Io(format!("Failed to create {}: {}", dir.display(), e)))?;
+    Ok(dir.join(format!("{}.jsonl", project_store_key(project_path))))
+}
+
+fn read_queue(path: &Path) -> Result<Vec<PendingOp>, BertError> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let file = File::open(path).map_err(|e| BertError::Io(format!("Failed to open {}: {}", path.display(), e)))?;
+    let mut queue = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(&line) {
+            Ok(pending) => queue.push(pending),
+            Err(e) => eprintln!("⚠️  Skipping unreadable pending op in {}: {}", path.display(), e),
+        }
+    }
+    Ok(queue)
+}
+
+/// Replace the queue file, removing it once the queue is empty
+fn write_queue(path: &Path, queue: &[PendingOp]) -> Result<(), BertError> {
+    if queue.is_empty() {
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        return Ok(());
+    }
+    let tmp_path = path.with_extension("jsonl.tmp");
+    {
+        let mut file = File::create(&tmp_path)?;
+        for pending in queue {
+            writeln!(file, "{}", serde_json::to_string(pending)?)?;
+        }
+        file.sync_all()?;
+    }
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/// Replay `queue` in order with `apply`. Stops at the first transient failure
+/// (the environment is still down); other failures mark the operation failed
+/// and move on. Returns what is left and how many operations applied.
+fn drain(queue: Vec<PendingOp>, mut apply: impl FnMut(&BeadOp) -> Result<String, BertError>) -> (Vec<PendingOp>, usize) {
+    let mut remaining = Vec::with_capacity(queue.len());
+    let mut applied = 0;
+    let mut blocked = false;
+    for mut pending in queue {
+        if blocked || pending.failed {
+            remaining.push(pending);
+            continue;
+        }
+        pending.attempts += 1;
+        match apply(&pending.op) {
+            Ok(_) => applied += 1,
+            Err(e) => {
+                blocked = is_transient(&e);
+                pending.failed = !blocked;
+                pending.last_error = e.to_string();
+                remaining.push(pending);
+            }
+        }
+    }
+    (remaining, applied)
+}
+
+/// Apply `op` to the project, or queue it when the environment is unavailable
+/// or earlier edits are still queued
+pub fn submit(ctx: &ProjectContext, op: BeadOp) -> Result<Submitted, BertError> {
+    // Not queued for retry: it would never apply
+    ctx.ensure_writable()?;
+    let path = queue_path(ctx.project_path())?;
+    submit_with(&path, ctx.project_path(), op, |op| {
+        crate::bd::apply_bead_op(ctx, op)
+            .inspect(|bead_id| crate::autocommit::note(ctx, crate::autocommit::op_message(op, bead_id)))
+    })
+}
+
+/// `submit` against the queue file at `path`, applying with `apply`
+fn submit_with(
+    path: &Path,
+    project_path: &Path,
+    op: BeadOp,
+    apply: impl FnOnce(&BeadOp) -> Result<String, BertError>,
+) -> Result<Submitted, BertError> {
+    let push = |pending: PendingOp| -> Result<Submitted, BertError> {
+        eprintln!("📥 Queued {} for {}: {}", pending.id, pending.affected_id(), pending.last_error);
+        let mut queue = read_queue(path)?;
+        queue.push(pending.clone());
+        write_queue(path, &queue)?;
+        Ok(Submitted::Queued(Box::new(pending)))
+    };
+
+    let lock = queue_lock(path);
+    let guard = lock.lock().unwrap_or_else(|e| e.into_inner());
+    if read_queue(path)?.iter().any(|p| !p.failed && p.is_for(project_path)) {
+        return push(PendingOp::new(op, "Waiting for earlier queued edits".to_string(), project_path));
+    }
+    drop(guard);
+
+    match apply(&op) {
+        Ok(bead_id) => Ok(Submitted::Applied(bead_id)),
+        Err(e) if is_transient(&e) => {
+            let _guard = lock.lock().unwrap_or_else(|e| e.into_inner());
+            push(PendingOp::new(op, e.to_string(), project_path))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Operations queued for the project
+pub fn pending(ctx: &ProjectContext) -> Result<Vec<PendingOp>, BertError> {
+    let path = queue_path(ctx.project_path())?;
+    let queue = with_queue(&path, || read_queue(&path))?;
+    Ok(queue.into_iter().filter(|p| p.is_for(ctx.project_path())).collect())
+}
+
+/// Replay the project's queue; returns how many operations applied
+pub fn retry(ctx: &ProjectContext) -> Result<usize, BertError> {
+    let path = queue_path(ctx.project_path())?;
+    // Entries of another project are never replayed here, whatever file they ended up in
+    let queue: Vec<PendingOp> =
+        with_queue(&path, || read_queue(&path))?.into_iter().filter(|p| p.is_for(ctx.project_path())).collect();
+    if queue.iter().all(|p| p.failed) {
+        return Ok(0);
+    }
+
+    let attempted: HashSet<String> = queue.iter().map(|p| p.id.clone()).collect();
+    let (remaining, applied) = drain(queue, |op| {
+        crate::bd::apply_bead_op(ctx, op)
+            .inspect(|bead_id| crate::autocommit::note(ctx, crate::autocommit::op_message(op, bead_id)))
+    });
+
+    // The file may have changed while bd ran: keep ops queued or discarded meanwhile
+    let left = with_queue(&path, || {
+        let mut updated: HashMap<String, PendingOp> = remaining.into_iter().map(|p| (p.id.clone(), p)).collect();
+        let queue: Vec<PendingOp> = read_queue(&path)?
+            .into_iter()
+            .filter_map(|p| if attempted.contains(&p.id) { updated.remove(&p.id) } else { Some(p) })
+            .collect();
+        write_queue(&path, &queue)?;
+        Ok(queue.len())
+    })?;
+    if applied > 0 {
+        eprintln!("📤 Replayed {} queued ops for {} ({} left)", applied, ctx.project_path().display(), left);
+    }
+    Ok(applied)
+}
+
+pub fn emit_changed(app_handle: &AppHandle, ctx: &ProjectContext) {
+    let count = pending(ctx).map(|q| q.len()).unwrap_or(0);
+    let payload = PendingOpsChanged { project_path: ctx.project_path().to_path_buf(), count };
+    emit_scoped(app_handle, EventScope::Project(ctx), "pending-ops-changed", payload);
+}
+
+/// Replay the queues of the open projects every `RETRY_INTERVAL`
+pub fn spawn_retrier(app_handle: AppHandle) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(RETRY_INTERVAL);
+        let Some(contexts) = app_handle.try_state::<ProjectContexts>() else { continue };
+        for ctx in contexts.open_projects() {
+            match retry(&ctx) {
+                Ok(0) => {}
+                Ok(_) => {
+                    crate::project::emit_beads_updated(&app_handle, &ctx);
+                    emit_changed(&app_handle, &ctx);
+                }
+                Err(e) => eprintln!("⚠️  Failed to replay queued ops for {}: {}", ctx.project_path().display(), e),
+            }
+        }
+    });
+}
+
+/// Mutations waiting for the environment to recover, oldest first
+#[tauri::command]
+pub fn get_pending_ops(window: tauri::Window, project_handle: Option<String>) -> Result<Vec<PendingOp>, BertError> {
+    pending(&crate::project::context_for(&window, project_handle.as_deref())?)
+}
+
+/// Drop queued operations by ID, or all of them when `opIds` is None.
+/// Returns the operations still queued.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn discard_pending_ops(
+    opIds: Option<Vec<String>>,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    app_handle: AppHandle,
+) -> Result<Vec<PendingOp>, BertError> {
+    let ctx = crate::project::context_for(&window, project_handle.as_deref())?;
+    let path = queue_path(ctx.project_path())?;
+    let remaining = with_queue(&path, || {
+        let queue = read_queue(&path)?;
+        let before = queue.len();
+        let remaining: Vec<PendingOp> = queue
+            .into_iter()
+            .filter(|p| !p.is_for(ctx.project_path()) || opIds.as_ref().is_some_and(|ids| !ids.contains(&p.id)))
+            .collect();
+        write_queue(&path, &remaining)?;
+        eprintln!("🗑️  Discarded {} queued ops", before - remaining.len());
+        Ok(remaining.into_iter().filter(|p| p.is_for(ctx.project_path())).collect::<Vec<_>>())
+    })?;
+    emit_changed(&app_handle, &ctx);
+    Ok(remaining)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn close(id: &str) -> BeadOp {
+        BeadOp::Close { bead_id: id.to_string(), reason: None }
+    }
+
+    fn bead_id(op: &BeadOp) -> &str {
+        match op {
+            BeadOp::Close { bead_id, .. } => bead_id,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_transient_errors() {
+        assert!(is_transient(&BertError::FileLocked("issues.jsonl".to_string())));
+        assert!(is_transient(&BertError::BdCommandFailed("Error: database is locked".to_string())));
+        assert!(!is_transient(&BertError::BdCommandFailed("Error: issue bp6-9 not found".to_string())));
+        assert!(!is_transient(&BertError::NotFound("bp6-9".to_string())));
+        assert!(!is_transient(&BertError::BdCommandFailed("open .beads/beads.db: no such file or directory".to_string())));
+
+        let io = |kind: std::io::ErrorKind| BertError::from(std::io::Error::from(kind));
+        assert!(is_transient(&io(std::io::ErrorKind::WouldBlock)));
+        assert!(is_transient(&io(std::io::ErrorKind::TimedOut)));
+        assert!(!is_transient(&io(std::io::ErrorKind::PermissionDenied)));
+        assert!(!is_transient(&BertError::ProjectNotFound("/gone".to_string())));
+    }
+
+    #[test]
+    fn test_submit_returns_permanent_io_errors_instead_of_queueing() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("queue.jsonl");
+        let denied = |_: &BeadOp| Err(BertError::from(std::io::Error::from(std::io::ErrorKind::PermissionDenied)));
+        let result = submit_with(&path, dir.path(), close("a"), denied);
+        assert!(matches!(result, Err(BertError::Io(_))));
+        assert!(read_queue(&path).unwrap().is_empty());
+
+        // A blocked write is queued, and later edits wait behind it
+        let blocked = |_: &BeadOp| Err(BertError::from(std::io::Error::from(std::io::ErrorKind::WouldBlock)));
+        assert!(matches!(submit_with(&path, dir.path(), close("b"), blocked), Ok(Submitted::Queued(_))));
+        let applied = |op: &BeadOp| Ok(bead_id(op).to_string());
+        assert!(matches!(submit_with(&path, dir.path(), close("c"), applied), Ok(Submitted::Queued(_))));
+        assert_eq!(read_queue(&path).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_drain_stops_while_the_environment_is_down() {
+        let queue = vec![
+            PendingOp::new(close("a"), String::new(), Path::new("/tmp")),
+            PendingOp::new(close("gone"), String::new(), Path::new("/tmp")),
+            PendingOp::new(close("b"), String::new(), Path::new("/tmp")),
+            PendingOp::new(close("c"), String::new(), Path::new("/tmp")),
+        ];
+        let mut calls = Vec::new();
+        let (remaining, applied) = drain(queue, |op| {
+            calls.push(bead_id(op).to_string());
+            match bead_id(op) {
+                "gone" => Err(BertError::NotFound("gone".to_string())),
+                "b" => Err(BertError::FileLocked("issues.jsonl".to_string())),
+                id => Ok(id.to_string()),
+            }
+        });
+
+        assert_eq!(applied, 1);
+        assert_eq!(calls, vec!["a", "gone", "b"]);
+        let left: Vec<(&str, bool, u32)> = remaining.iter().map(|p| (bead_id(&p.op), p.failed, p.attempts)).collect();
+        assert_eq!(left, vec![("gone", true, 1), ("b", false, 1), ("c", false, 0)]);
+
+        // Once the file is unlocked the rest applies; the failed op is not retried
+        let (remaining, applied) = drain(remaining, |op| Ok(bead_id(op).to_string()));
+        assert_eq!(applied, 2);
+        assert_eq!(remaining.len(), 1);
+        assert!(remaining[0].failed);
+    }
+
+    #[test]
+    fn test_queue_file_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("project.jsonl");
+        assert!(read_queue(&path).unwrap().is_empty());
+
+        let queue = vec![PendingOp::new(close("a"), "locked".to_string(), dir.path())];
+        write_queue(&path, &queue).unwrap();
+        let read = read_queue(&path).unwrap();
+        assert_eq!(read.len(), 1);
+        assert_eq!(read[0].id, queue[0].id);
+        assert_eq!(read[0].affected_id(), "a");
+        assert!(read[0].is_for(dir.path()));
+        assert!(!read[0].is_for(&dir.path().join("other")));
+
+        write_queue(&path, &[]).unwrap();
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,198 @@
+//! Performance telemetry for the view pipeline
+//!
+//! `get_processed_data` and `get_project_view_model` time each stage (file
+//! load, filtering, tree building, layout, ...) and return the durations with
+//! the bead counts in a `timings` field, so the UI can show why a large project
+//! feels slow. The last `SAMPLE_LIMIT` timings are kept in memory and
+//! summarized per command by `get_performance_stats`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+/// Samples kept for `get_performance_stats`
+const SAMPLE_LIMIT: usize = 100;
+
+static SAMPLES: OnceLock<Mutex<VecDeque<PerfSample>>> = OnceLock::new();
+
+/// Duration of one pipeline stage
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StageTiming {
+    pub stage: String,
+    pub ms: f64,
+}
+
+/// Where the time of one view computation went
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Timings {
+    /// In pipeline order
+    pub stages: Vec<StageTiming>,
+    /// Beads read from the file
+    pub bead_count: usize,
+    /// Beads left after filtering
+    pub visible_count: usize,
+    /// Whole command, including what isn't broken down into stages
+    pub total_ms: f64,
+    /// Served from the view model cache; `stages` are those of the cached computation
+    pub cached: bool,
+}
+
+impl Timings {
+    /// Record a stage that started at `start` and ends now
+    pub fn record(&mut self, stage: &str, start: Instant) {
+        let ms = start.elapsed().as_secs_f64() * 1000.0;
+        eprintln!("⏱️  {}: {:.2}ms", stage, ms);
+        self.stages.push(StageTiming { stage: stage.to_string(), ms });
+    }
+}
+
+/// Timings of one command call
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PerfSample {
+    pub command: String,
+    /// RFC 3339
+    pub at: String,
+    pub timings: Timings,
+}
+
+/// Recent timings of one command
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandStats {
+    pub command: String,
+    pub count: usize,
+    pub cache_hits: usize,
+    pub avg_total_ms: f64,
+    pub max_total_ms: f64,
+    /// Average per stage over the computed (uncached) calls
+    pub avg_stages: Vec<StageTiming>,
+    pub max_bead_count: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PerformanceStats {
+    pub commands: Vec<CommandStats>,
+    /// Newest first
+    pub samples: Vec<PerfSample>,
+}
+
+fn samples() -> &'static Mutex<VecDeque<PerfSample>> {
+    SAMPLES.get_or_init(Default::default)
+}
+
+/// Keep `timings` of a `command` call for `get_performance_stats`
+pub fn record_sample(command: &str, timings: &Timings) {
+    eprintln!("⏱️  {} total: {:.2}ms ({} of {} beads)", command, timings.total_ms, timings.visible_count, timings.bead_count);
+    let mut samples = samples().lock().unwrap();
+    samples.push_front(PerfSample {
+        command: command.to_string(),
+        at: chrono::Utc::now().to_rfc3339(),
+        timings: timings.clone(),
+    });
+    samples.truncate(SAMPLE_LIMIT);
+}
+
+/// Summarize `samples` per command, in order of first appearance
+fn summarize<'a>(samples: impl IntoIterator<Item = &'a PerfSample>) -> Vec<CommandStats> {
+    let mut order: Vec<&str> = Vec::new();
+    let mut by_command: BTreeMap<&str, Vec<&Timings>> = BTreeMap::new();
+    for sample in samples {
+        if !by_command.contains_key(sample.command.as_str()) {
+            order.push(&sample.command);
+        }
+        by_command.entry(&sample.command).or_default().push(&sample.timings);
+    }
+
+    order
+        .into_iter()
+        .map(|command| {
+            let timings = &by_command[command];
+            let count = timings.len();
+
+            // Stages in pipeline order, averaged over the calls that ran them
+            let mut stage_order: Vec<&str> = Vec::new();
+            let mut stage_totals: BTreeMap<&str, (f64, usize)> = BTreeMap::new();
+            for t in timings.iter().filter(|t| !t.cached) {
+                for stage in &t.stages {
+                    let total = stage_totals.entry(&stage.stage).or_insert_with(|| {
+                        stage_order.push(&stage.stage);
+                        (0.0, 0)
+                    });
+                    total.0 += stage.ms;
+                    total.1 += 1;
+                }
+            }
+            let avg_stages = stage_order
+                .into_iter()
+                .map(|stage| {
+                    let (sum, n) = stage_totals[stage];
+                    StageTiming { stage: stage.to_string(), ms: sum / n as f64 }
+                })
+                .collect();
+
+            CommandStats {
+                command: command.to_string(),
+                count,
+                cache_hits: timings.iter().filter(|t| t.cached).count(),
+                avg_total_ms: timings.iter().map(|t| t.total_ms).sum::<f64>() / count as f64,
+                max_total_ms: timings.iter().map(|t| t.total_ms).fold(0.0, f64::max),
+                avg_stages,
+                max_bead_count: timings.iter().map(|t| t.bead_count).max().unwrap_or(0),
+            }
+        })
+        .collect()
+}
+
+/// Per-command summary and the raw timings of the last view computations
+#[tauri::command]
+pub fn get_performance_stats() -> PerformanceStats {
+    let samples = samples().lock().unwrap();
+    PerformanceStats { commands: summarize(samples.iter()), samples: samples.iter().cloned().collect() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(command: &str, total_ms: f64, cached: bool, stages: &[(&str, f64)]) -> PerfSample {
+        PerfSample {
+            command: command.to_string(),
+            at: String::new(),
+            timings: Timings {
+                stages: stages.iter().map(|(stage, ms)| StageTiming { stage: stage.to_string(), ms: *ms }).collect(),
+                bead_count: 10,
+                visible_count: 5,
+                total_ms,
+                cached,
+            },
+        }
+    }
+
+    #[test]
+    fn test_summarize_per_command() {
+        let samples = vec![
+            sample("view", 30.0, false, &[("load", 10.0), ("tree", 20.0)]),
+            sample("view", 2.0, true, &[("load", 10.0), ("tree", 20.0)]),
+            sample("processed", 8.0, false, &[("layout", 8.0)]),
+            sample("view", 10.0, false, &[("load", 2.0), ("tree", 6.0)]),
+        ];
+        let stats = summarize(&samples);
+        assert_eq!(stats.len(), 2);
+
+        let view = &stats[0];
+        assert_eq!((view.command.as_str(), view.count, view.cache_hits), ("view", 3, 1));
+        assert_eq!(view.avg_total_ms, 14.0);
+        assert_eq!(view.max_total_ms, 30.0);
+        // Cache hits don't count towards stage averages
+        assert_eq!(
+            view.avg_stages,
+            vec![StageTiming { stage: "load".into(), ms: 6.0 }, StageTiming { stage: "tree".into(), ms: 13.0 }]
+        );
+        assert_eq!(stats[1].command, "processed");
+    }
+}
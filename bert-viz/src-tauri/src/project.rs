@@ -0,0 +1,631 @@
+//! Per-window project state
+//!
+//! Each window can have its own project open, so nothing may depend on the
+//! process-wide current directory. `ProjectContexts` maps a window label to the
+//! `ProjectContext` for the project it has open (root path, cached beads file
+//! location). Windows with the same project open share one watcher of its
+//! beads file, and `beads-updated` events only go to those windows. Commands
+//! look up their context from the calling window with `context_for`.
+//!
+//! A window can also keep more projects open in tabs (see `tabs`). Each tab is
+//! registered here under its handle, `<window label>#<n>`, so it gets its own
+//! watcher registration and view model cache; events for it go to the owning
+//! window with the handle in the payload.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use notify::{Config, RecursiveMode, Watcher};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::error::BertError;
+use crate::events::{emit_scoped, EventScope};
+
+/// The project a window is working on
+#[derive(Clone, Debug)]
+pub struct ProjectContext {
+    project_path: PathBuf,
+    /// Located lazily; finding the sync worktree spawns `bd`, so the result is cached
+    beads_path: Arc<Mutex<Option<PathBuf>>>,
+}
+
+impl ProjectContext {
+    pub fn new(project_path: impl Into<PathBuf>) -> Self {
+        ProjectContext {
+            project_path: project_path.into(),
+            beads_path: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Directory the project was opened from
+    pub fn project_path(&self) -> &Path {
+        &self.project_path
+    }
+
+    /// Location of the project's `.beads/issues.jsonl` (sync worktree or working tree)
+    pub fn beads_path(&self) -> Result<PathBuf, BertError> {
+        let mut cache = self
+            .beads_path
+            .lock()
+            .map_err(|e| BertError::Internal(format!("Failed to lock beads path cache: {}", e)))?;
+        if cache.is_none() {
+            *cache = crate::bd::find_beads_file_from(&self.project_path);
+        }
+        cache.clone().ok_or_else(|| {
+            BertError::BeadsFileNotFound(format!(
+                "Could not locate .beads/issues.jsonl in {} or any parent directory",
+                self.project_path.display()
+            ))
+        })
+    }
+
+    /// Directory containing `.beads`, where `bd` and agent CLIs are run
+    pub fn repo_root(&self) -> Result<PathBuf, BertError> {
+        crate::bd::find_repo_root_from(&self.project_path).ok_or_else(|| {
+            BertError::ProjectNotFound(format!(
+                "Could not locate .beads directory in {} or any parent",
+                self.project_path.display()
+            ))
+        })
+    }
+
+    /// Fail with a `read_only_snapshot` error for a project opened from a
+    /// shared snapshot (see `viewer`)
+    pub fn ensure_writable(&self) -> Result<(), BertError> {
+        crate::viewer::ensure_writable(&self.project_path)
+    }
+
+    /// Forget the cached beads path (the file was removed or the sync mode changed)
+    pub fn clear_beads_path_cache(&self) {
+        if let Ok(mut cache) = self.beads_path.lock() {
+            *cache = None;
+        }
+    }
+}
+
+struct WindowProject {
+    context: ProjectContext,
+    /// Beads file whose watcher the window is registered with
+    watched: Option<PathBuf>,
+}
+
+/// Windows registered with a beads watcher and their contexts, by window label
+type WatcherWindows = Arc<Mutex<BTreeMap<String, ProjectContext>>>;
+
+/// A beads watcher shared by every window that has its project open
+struct SharedWatcher {
+    _watcher: BeadsWatcher,
+    windows: WatcherWindows,
+}
+
+/// Managed Tauri state: the open project of every window
+pub struct ProjectContexts {
+    windows: Mutex<HashMap<String, WindowProject>>,
+    /// Beads watchers by beads file, dropped when the last window using one
+    /// closes its project
+    watchers: Mutex<HashMap<PathBuf, SharedWatcher>>,
+    /// Most recently opened project, used by windows that never opened one
+    /// (e.g. agent session windows)
+    last_opened: Mutex<Option<ProjectContext>>,
+    /// Directory the app was launched from
+    launch_context: ProjectContext,
+}
+
+impl ProjectContexts {
+    pub fn new() -> Self {
+        let launch_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        ProjectContexts {
+            windows: Mutex::new(HashMap::new()),
+            watchers: Mutex::new(HashMap::new()),
+            last_opened: Mutex::new(None),
+            launch_context: ProjectContext::new(launch_dir),
+        }
+    }
+
+    /// Context for a window, falling back to the last opened project and then
+    /// the launch directory
+    pub fn get(&self, label: &str) -> ProjectContext {
+        if let Some(project) = self.windows.lock().ok().and_then(|w| w.get(label).map(|p| p.context.clone())) {
+            return project;
+        }
+        self.last_opened
+            .lock()
+            .ok()
+            .and_then(|last| last.clone())
+            .unwrap_or_else(|| self.launch_context.clone())
+    }
+
+    /// Context registered under `key`, without falling back to another project
+    pub(crate) fn get_open(&self, key: &str) -> Option<ProjectContext> {
+        self.windows.lock().ok().and_then(|w| w.get(key).map(|p| p.context.clone()))
+    }
+
+    /// Open `path` in the window `label`, replacing whatever it had open
+    ///
+    /// The window is registered with the watcher of the project's beads file,
+    /// which is started if no other window has the project open. A snapshot
+    /// bundle is unpacked and opened read-only.
+    pub fn open(&self, label: &str, path: &Path, app_handle: &AppHandle) -> Result<ProjectContext, BertError> {
+        let unpacked = if path.is_file() { Some(crate::viewer::unpack(path)?) } else { None };
+        let path = unpacked.as_deref().unwrap_or(path);
+        if !path.is_dir() {
+            return Err(BertError::ProjectNotFound(format!("Project directory does not exist: {}", path.display())));
+        }
+        let context = ProjectContext::new(path);
+        self.close(label);
+
+        let watched = match context.beads_path() {
+            Ok(beads_path) => {
+                // Catch up on edits made while the project was closed
+                if let Err(e) = crate::history::record_changes(&beads_path) {
+                    eprintln!("⚠️  Failed to record bead history: {}", e);
+                }
+                self.register(label, &context, &beads_path, app_handle)?;
+                Some(beads_path)
+            }
+            Err(e) => {
+                eprintln!("⚠️  open_project: {}; not watching", e);
+                None
+            }
+        };
+
+        let mut windows = self
+            .windows
+            .lock()
+            .map_err(|e| BertError::Internal(format!("Failed to lock project contexts: {}", e)))?;
+        windows.insert(label.to_string(), WindowProject { context: context.clone(), watched });
+        if let Ok(mut last) = self.last_opened.lock() {
+            *last = Some(context.clone());
+        }
+        Ok(context)
+    }
+
+    /// Add a window to the watcher of `beads_path`, starting one if needed
+    fn register(&self, label: &str, context: &ProjectContext, beads_path: &Path, app_handle: &AppHandle) -> Result<(), BertError> {
+        let mut watchers = self
+            .watchers
+            .lock()
+            .map_err(|e| BertError::Internal(format!("Failed to lock beads watchers: {}", e)))?;
+
+        if let Some(shared) = watchers.get(beads_path) {
+            shared.windows.lock().unwrap().insert(label.to_string(), context.clone());
+            eprintln!("🔍 {} shares the watcher of {}", label, beads_path.display());
+            return Ok(());
+        }
+
+        let windows: WatcherWindows = Arc::new(Mutex::new(BTreeMap::from([(label.to_string(), context.clone())])));
+        let watcher = BeadsWatcher::new(app_handle.clone(), windows.clone(), beads_path)?;
+        watchers.insert(beads_path.to_path_buf(), SharedWatcher { _watcher: watcher, windows });
+        Ok(())
+    }
+
+    /// Drop a window's project when the window is destroyed or opens another
+    /// one, stopping the project's watcher if no other window uses it
+    pub fn close(&self, label: &str) -> bool {
+        let Some(project) = self.windows.lock().ok().and_then(|mut w| w.remove(label)) else {
+            return false;
+        };
+
+        if let (Some(beads_path), Ok(mut watchers)) = (project.watched, self.watchers.lock()) {
+            let unused = watchers.get(&beads_path).is_some_and(|shared| {
+                let mut windows = shared.windows.lock().unwrap();
+                windows.remove(label);
+                windows.is_empty()
+            });
+            if unused {
+                watchers.remove(&beads_path);
+                eprintln!("🔓 Unwatched: {}", beads_path.display());
+            }
+        }
+        true
+    }
+
+    /// Windows that have `ctx`'s project open, with their project paths
+    pub(crate) fn windows_with(&self, ctx: &ProjectContext) -> Vec<(String, PathBuf)> {
+        let repo_root = ctx.repo_root().ok();
+        let Ok(windows) = self.windows.lock() else { return Vec::new() };
+        windows
+            .iter()
+            .filter(|(_, project)| {
+                project.context.project_path() == ctx.project_path()
+                    || (repo_root.is_some() && project.context.repo_root().ok() == repo_root)
+            })
+            .map(|(label, project)| (label.clone(), project.context.project_path().to_path_buf()))
+            .collect()
+    }
+
+    /// Most recently opened project, if any window opened one
+    #[cfg(feature = "rest-api")]
+    pub(crate) fn last_opened(&self) -> Option<ProjectContext> {
+        self.last_opened.lock().ok().and_then(|last| last.clone())
+    }
+
+    /// Every project open in a window or tab, once each
+    pub(crate) fn open_projects(&self) -> Vec<ProjectContext> {
+        let Ok(windows) = self.windows.lock() else { return Vec::new() };
+        let mut projects: Vec<ProjectContext> = Vec::new();
+        for project in windows.values() {
+            if !projects.iter().any(|p| p.project_path() == project.context.project_path()) {
+                projects.push(project.context.clone());
+            }
+        }
+        projects
+    }
+}
+
+impl Default for ProjectContexts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Separates the owning window's label from the tab number in a tab handle.
+/// Tauri window labels cannot contain it.
+const TAB_SEPARATOR: char = '#';
+
+/// Handle of the `n`th tab opened in window `label`
+pub(crate) fn tab_key(label: &str, n: u64) -> String {
+    format!("{}{}{}", label, TAB_SEPARATOR, n)
+}
+
+/// Window that shows the project registered under `key` (a window label or a
+/// tab handle)
+pub(crate) fn owner_window(key: &str) -> &str {
+    key.split(TAB_SEPARATOR).next().unwrap_or(key)
+}
+
+/// The tab handle in `key`, if it names a tab rather than a window
+pub(crate) fn tab_handle(key: &str) -> Option<&str> {
+    key.contains(TAB_SEPARATOR).then_some(key)
+}
+
+/// Look up the project context a command works on: the tab `project_handle`
+/// of the calling window, or the window's own project
+pub fn context_for(window: &tauri::Window, project_handle: Option<&str>) -> Result<ProjectContext, BertError> {
+    let contexts = window
+        .try_state::<ProjectContexts>()
+        .ok_or_else(|| BertError::Internal("Project contexts are not initialized".to_string()))?;
+    let Some(handle) = project_handle else {
+        return Ok(contexts.get(window.label()));
+    };
+    if tab_handle(handle).is_none() || owner_window(handle) != window.label() {
+        return Err(BertError::InvalidInput(format!("{} is not a project tab of this window", handle)));
+    }
+    contexts
+        .get_open(handle)
+        .ok_or_else(|| BertError::NotFound(format!("Project tab {} is not open", handle)))
+}
+
+/// Key of the project a command works on in per-project caches (see
+/// `context_for`)
+pub fn project_key(window: &tauri::Window, project_handle: Option<&str>) -> String {
+    project_handle.unwrap_or(window.label()).to_string()
+}
+
+/// Tell the windows that have `ctx`'s project open that its beads changed
+///
+/// Falls back to every window if none has opened the project explicitly.
+pub fn emit_beads_updated(app_handle: &AppHandle, ctx: &ProjectContext) {
+    let payload = BeadsUpdated { project_path: ctx.project_path().to_path_buf(), project_handle: None, checksum: None };
+    emit_scoped(app_handle, EventScope::Project(ctx), "beads-updated", payload);
+    crate::autocommit::schedule(app_handle, ctx);
+}
+
+/// Quiet period after the last file event before changed files are read
+const DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// Waits between checks of a file that is still being written
+const SETTLE_BACKOFF: [Duration; 6] = [
+    Duration::from_millis(25),
+    Duration::from_millis(50),
+    Duration::from_millis(100),
+    Duration::from_millis(200),
+    Duration::from_millis(400),
+    Duration::from_millis(800),
+];
+
+/// Payload of `beads-updated`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BeadsUpdated {
+    /// Project of the window receiving the event
+    project_path: PathBuf,
+    /// Tab of the window that has the project open, if it isn't the window's own
+    project_handle: Option<String>,
+    /// Hash of the new file content, as hex; set by the file watcher
+    checksum: Option<String>,
+}
+
+/// Watches one beads file and notifies the windows that have its project open
+///
+/// The notify callback only forwards events to a worker thread. The worker
+/// waits for a burst of events to end, reads each changed file once it is
+/// completely written and emits one `beads-updated` to every registered window
+/// if any content changed. It stops when the watcher is dropped.
+struct BeadsWatcher {
+    _watcher: notify::RecommendedWatcher,
+}
+
+impl BeadsWatcher {
+    fn new(handle: AppHandle, windows: WatcherWindows, beads_path: &Path) -> Result<Self, String> {
+        let (tx, rx) = mpsc::channel::<notify::Event>();
+
+        let mut watcher = notify::RecommendedWatcher::new(
+            move |res: std::result::Result<notify::Event, notify::Error>| match res {
+                Ok(event) => {
+                    let _ = tx.send(event);
+                }
+                Err(e) => eprintln!("Watch error: {:?}", e),
+            },
+            Config::default(),
+        ).map_err(|e| e.to_string())?;
+
+        // Watch the directory: the beads daemon replaces the file
+        let parent = beads_path
+            .parent()
+            .ok_or_else(|| format!("Invalid beads file path {}", beads_path.display()))?;
+        watcher.watch(parent, RecursiveMode::Recursive)
+            .map_err(|e| format!("Failed to watch {}: {}", parent.display(), e))?;
+        eprintln!("🔍 Now watching: {}", parent.display());
+
+        let name = parent.display().to_string();
+        std::thread::spawn(move || watch_beads(rx, handle, windows, name));
+
+        Ok(BeadsWatcher { _watcher: watcher })
+    }
+}
+
+/// `.jsonl` files touched by one burst of file events
+#[derive(Debug, Default)]
+struct EventBatch {
+    changed: BTreeSet<PathBuf>,
+    removed: BTreeSet<PathBuf>,
+}
+
+/// Wait for the next burst of events and collect it, returning once no event
+/// arrived for `DEBOUNCE`. Returns `None` when the watcher is gone.
+fn next_batch(rx: &Receiver<notify::Event>) -> Option<EventBatch> {
+    let mut batch = EventBatch::default();
+    let mut event = rx.recv().ok()?;
+    loop {
+        eprintln!("📁 Event: {:?}", event.kind);
+        for path in event.paths.iter().filter(|p| p.extension().and_then(|s| s.to_str()) == Some("jsonl")) {
+            match event.kind {
+                // The beads daemon deletes and recreates the file
+                notify::EventKind::Remove(_) => {
+                    batch.removed.insert(path.clone());
+                    batch.changed.insert(path.clone());
+                }
+                notify::EventKind::Create(_) | notify::EventKind::Modify(_) => {
+                    batch.changed.insert(path.clone());
+                }
+                _ => {}
+            }
+        }
+
+        match rx.recv_timeout(DEBOUNCE) {
+            Ok(next) => event = next,
+            Err(RecvTimeoutError::Timeout) if batch.changed.is_empty() => event = rx.recv().ok()?,
+            Err(_) => return Some(batch),
+        }
+    }
+}
+
+/// Read `path` once it is completely written: its size and modification
+/// time held still between two checks and it is empty or ends with a newline.
+/// Checks are spaced by `backoff`; returns `None` if the file never settled
+/// (or stayed deleted).
+fn read_when_stable(path: &Path, backoff: &[Duration]) -> Option<Vec<u8>> {
+    let mut last_stat = None;
+    for wait in backoff {
+        std::thread::sleep(*wait);
+        let Ok(metadata) = std::fs::metadata(path) else {
+            last_stat = None;
+            continue;
+        };
+        let stat = (metadata.len(), metadata.modified().ok());
+        if last_stat != Some(stat) {
+            last_stat = Some(stat);
+            continue;
+        }
+
+        match std::fs::read(path) {
+            Ok(bytes) if bytes.len() as u64 == stat.0 && (bytes.is_empty() || bytes.ends_with(b"\n")) => return Some(bytes),
+            _ => last_stat = None,
+        }
+    }
+    None
+}
+
+fn checksum(bytes: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Worker thread of a `BeadsWatcher`
+fn watch_beads(rx: Receiver<notify::Event>, handle: AppHandle, windows: WatcherWindows, name: String) {
+    let mut checksums: HashMap<PathBuf, u64> = HashMap::new();
+
+    while let Some(batch) = next_batch(&rx) {
+        if !batch.removed.is_empty() {
+            // Re-locate the beads file on the next read, and emit once it is back
+            for context in windows.lock().unwrap().values() {
+                context.clear_beads_path_cache();
+            }
+            for path in &batch.removed {
+                checksums.remove(path);
+                crate::bead_index::forget(path);
+            }
+        }
+
+        let mut updated = None;
+        let mut commented = BTreeSet::new();
+        let mut favorite_changes = Vec::new();
+        for path in &batch.changed {
+            let Some(bytes) = read_when_stable(path, &SETTLE_BACKOFF) else {
+                eprintln!("  ⚠️  {} did not settle; waiting for the next change", path.display());
+                checksums.remove(path);
+                continue;
+            };
+            let new_checksum = checksum(&bytes);
+            if checksums.insert(path.clone(), new_checksum) == Some(new_checksum) {
+                continue;
+            }
+
+            if let Some(bead_id) = crate::comments::bead_id_of(path) {
+                commented.insert(bead_id);
+            } else if path.file_name().and_then(|n| n.to_str()) == Some("issues.jsonl") {
+                crate::bead_index::refresh(path, &bytes);
+                match crate::history::record_changes(path) {
+                    Ok(entries) => favorite_changes.extend(crate::favorites::changes(path, &entries)),
+                    Err(e) => eprintln!("  ⚠️  Failed to record bead history: {}", e),
+                }
+                crate::notifications::beads_changed(path);
+                updated = Some(new_checksum);
+            } else {
+                updated = updated.or(Some(new_checksum));
+            }
+        }
+
+        for bead_id in commented {
+            let _ = handle.emit("comments-updated", crate::comments::CommentsUpdated { bead_id });
+        }
+        for change in favorite_changes {
+            eprintln!("  ⭐ Favorite {} changed", change.bead_id);
+            let owners: BTreeSet<String> = windows.lock().unwrap().keys().map(|k| owner_window(k).to_string()).collect();
+            for label in owners {
+                let _ = handle.emit_to(label.as_str(), "favorite-bead-changed", change.clone());
+            }
+        }
+
+        let Some(new_checksum) = updated else { continue };
+        let checksum = format!("{:016x}", new_checksum);
+        let windows: Vec<(String, ProjectContext)> = windows.lock().unwrap().iter().map(|(l, c)| (l.clone(), c.clone())).collect();
+        for (label, context) in &windows {
+            if let Ok(beads_path) = context.beads_path() {
+                crate::view_cache::push_view_model(&handle, label, &beads_path);
+            }
+            let payload = BeadsUpdated {
+                project_path: context.project_path().to_path_buf(),
+                project_handle: tab_handle(label).map(str::to_string),
+                checksum: Some(checksum.clone()),
+            };
+            match handle.emit_to(owner_window(label), "beads-updated", payload) {
+                Ok(_) => eprintln!("  ✅ Emitted beads-updated to {}", label),
+                Err(e) => eprintln!("  ❌ Failed to emit beads-updated: {:?}", e),
+            }
+        }
+    }
+    eprintln!("🔓 Stopped watching {}", name);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn project_with_beads() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join(".beads")).unwrap();
+        std::fs::write(dir.path().join(".beads/issues.jsonl"), "").unwrap();
+        std::fs::create_dir_all(dir.path().join("src/nested")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_tab_keys_name_their_window() {
+        let tab = tab_key("main", 3);
+        assert_eq!(tab, "main#3");
+        assert_eq!(owner_window(&tab), "main");
+        assert_eq!(tab_handle(&tab), Some("main#3"));
+
+        assert_eq!(owner_window("agent-session-1"), "agent-session-1");
+        assert_eq!(tab_handle("agent-session-1"), None);
+    }
+
+    #[test]
+    fn test_context_resolves_from_its_own_path() {
+        let project = project_with_beads();
+        let ctx = ProjectContext::new(project.path().join("src/nested"));
+
+        assert_eq!(ctx.repo_root().unwrap(), project.path());
+        assert_eq!(ctx.beads_path().unwrap(), project.path().join(".beads/issues.jsonl"));
+    }
+
+    #[test]
+    fn test_beads_path_is_cached_until_cleared() {
+        let project = project_with_beads();
+        let ctx = ProjectContext::new(project.path());
+        let first = ctx.beads_path().unwrap();
+
+        std::fs::remove_dir_all(project.path().join(".beads")).unwrap();
+        assert_eq!(ctx.beads_path().unwrap(), first);
+
+        ctx.clear_beads_path_cache();
+        assert!(ctx.beads_path().is_err());
+    }
+
+    #[test]
+    fn test_burst_of_events_is_coalesced() {
+        let (tx, rx) = mpsc::channel();
+        let event = |kind, path: &str| notify::Event::new(kind).add_path(PathBuf::from(path));
+        tx.send(event(notify::EventKind::Remove(notify::event::RemoveKind::File), "/p/.beads/issues.jsonl")).unwrap();
+        tx.send(event(notify::EventKind::Create(notify::event::CreateKind::File), "/p/.beads/issues.jsonl")).unwrap();
+        tx.send(event(notify::EventKind::Modify(notify::event::ModifyKind::Any), "/p/.beads/issues.jsonl")).unwrap();
+        tx.send(event(notify::EventKind::Modify(notify::event::ModifyKind::Any), "/p/.beads/config.yaml")).unwrap();
+
+        let batch = next_batch(&rx).unwrap();
+        assert_eq!(batch.changed.into_iter().collect::<Vec<_>>(), vec![PathBuf::from("/p/.beads/issues.jsonl")]);
+        assert_eq!(batch.removed.len(), 1);
+
+        drop(tx);
+        assert!(next_batch(&rx).is_none());
+    }
+
+    #[test]
+    fn test_read_when_stable_requires_complete_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("issues.jsonl");
+        let backoff = [Duration::from_millis(5); 4];
+
+        std::fs::write(&path, "{\"id\": \"a\"}\n{\"id\": ").unwrap();
+        assert!(read_when_stable(&path, &backoff).is_none());
+
+        std::fs::write(&path, "{\"id\": \"a\"}\n").unwrap();
+        assert_eq!(read_when_stable(&path, &backoff).unwrap(), b"{\"id\": \"a\"}\n");
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(read_when_stable(&path, &backoff).is_none());
+    }
+
+    #[test]
+    fn test_updates_are_routed_to_windows_of_the_project() {
+        let first = project_with_beads();
+        let second = project_with_beads();
+        let contexts = ProjectContexts::new();
+        for (label, path) in [("main", first.path().to_path_buf()), ("w2", first.path().join("src")), ("w3", second.path().to_path_buf())] {
+            let project = WindowProject { context: ProjectContext::new(path), watched: None };
+            contexts.windows.lock().unwrap().insert(label.to_string(), project);
+        }
+
+        let mut labels: Vec<_> = contexts.windows_with(&ProjectContext::new(first.path())).into_iter().map(|(l, _)| l).collect();
+        labels.sort();
+        assert_eq!(labels, vec!["main", "w2"]);
+
+        assert!(contexts.close("w3"));
+        assert!(contexts.windows_with(&ProjectContext::new(second.path())).is_empty());
+    }
+
+    #[test]
+    fn test_unopened_window_falls_back_to_launch_dir() {
+        let contexts = ProjectContexts::new();
+        assert_eq!(contexts.get("main").project_path(), std::env::current_dir().unwrap());
+        assert!(!contexts.close("main"));
+    }
+}
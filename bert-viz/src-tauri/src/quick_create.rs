@@ -0,0 +1,208 @@
+//! Quick create: a bead from one line of text
+//!
+//! `quick_create` reads GitHub-style shorthand out of the line and leaves the
+//! rest as the title:
+//!
+//! - `#bug`: an issue type when it names one (bug, feature, task, epic,
+//!   chore), a label otherwise
+//! - `p0`..`p4`: priority
+//! - `@alice`: owner
+//! - `est:120`: estimate in minutes; `90m`, `2h` and `1d` (a working day) work too
+//! - `parent:bp6-07y`, `label:auth,api`, `type:feature`, `priority:1`
+//!
+//! Quoted text is title text as is, and a leading `\` keeps a word out of the
+//! shorthand (`\#1` stays `#1`). With `preview`, nothing is created: the
+//! command returns the parsed bead and the `bd` commands that would run, so
+//! the UI can show what will be created first.
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::bd::{BeadOp, OpPreview};
+use crate::error::BertError;
+use crate::project::context_for;
+use crate::schedule::SchedulingConfig;
+use crate::search::{tokenize, unquote};
+use crate::Bead;
+
+/// How one shorthand token was read
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickField {
+    pub token: String,
+    /// Bead field it set: issueType, priority, owner, estimate, parent or labels
+    pub field: String,
+    pub value: String,
+}
+
+/// Result of `quick_create`
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickCreate {
+    /// The bead the text describes
+    pub bead: Bead,
+    /// The shorthand tokens, in order
+    pub fields: Vec<QuickField>,
+    /// Created bead ID (the pending handle when queued); None for a preview
+    pub bead_id: Option<String>,
+    /// What creating it would run; only for a preview
+    pub preview: Option<OpPreview>,
+}
+
+/// Minutes in `raw`: plain minutes, or a number with an m, h or d suffix
+fn parse_estimate(raw: &str, day_minutes: f64) -> Option<u32> {
+    let raw = raw.trim().to_lowercase();
+    let (number, unit) = match raw.char_indices().find(|(_, c)| c.is_alphabetic()) {
+        Some((i, _)) => raw.split_at(i),
+        None => (raw.as_str(), "m"),
+    };
+    let number: f64 = number.parse().ok().filter(|n: &f64| n.is_finite() && *n > 0.0)?;
+    let minutes = match unit {
+        "m" | "min" => number,
+        "h" => number * 60.0,
+        "d" => number * day_minutes,
+        _ => return None,
+    };
+    Some(minutes.round() as u32)
+}
+
+fn parse_priority(raw: &str) -> Option<u32> {
+    raw.trim_start_matches(['p', 'P']).parse().ok().filter(|p| *p <= 4)
+}
+
+/// Parse `text` into a new bead. `day_minutes` is what `1d` means.
+pub fn parse(text: &str, day_minutes: f64) -> Result<(Bead, Vec<QuickField>), BertError> {
+    let mut bead = crate::import::blank_bead(String::new());
+    let mut fields = Vec::new();
+    let mut title = Vec::new();
+
+    for token in tokenize(text) {
+        let invalid = |what: &str| BertError::InvalidInput(format!("Invalid {} in '{}'", what, token));
+        let mut set = |field: &str, value: String| fields.push(QuickField { token: token.clone(), field: field.to_string(), value });
+
+        if let Some(literal) = token.strip_prefix('\\') {
+            title.push(unquote(literal));
+        } else if token.starts_with('"') {
+            title.push(unquote(&token));
+        } else if let Some(tag) = token.strip_prefix('#').filter(|t| !t.is_empty()) {
+            let tag = tag.to_lowercase();
+            if crate::lint::ISSUE_TYPES.contains(&tag.as_str()) {
+                bead.issue_type = tag.clone();
+                set("issueType", tag);
+            } else {
+                bead.labels.get_or_insert_with(Vec::new).push(tag.clone());
+                set("labels", tag);
+            }
+        } else if let Some(owner) = token.strip_prefix('@').filter(|o| !o.is_empty()) {
+            bead.owner = Some(owner.to_string());
+            set("owner", owner.to_string());
+        } else if token.len() == 2 && token.starts_with(['p', 'P']) && token[1..].chars().all(|c| c.is_ascii_digit()) {
+            bead.priority = parse_priority(&token).ok_or_else(|| invalid("priority"))?;
+            set("priority", bead.priority.to_string());
+        } else if let Some((key, value)) = token.split_once(':').filter(|(_, v)| !v.is_empty()) {
+            let value = unquote(value);
+            match key.to_lowercase().as_str() {
+                "est" | "estimate" => {
+                    let minutes = parse_estimate(&value, day_minutes).ok_or_else(|| invalid("estimate"))?;
+                    bead.estimate = Some(minutes);
+                    set("estimate", minutes.to_string());
+                }
+                "priority" => {
+                    bead.priority = parse_priority(&value).ok_or_else(|| invalid("priority"))?;
+                    set("priority", bead.priority.to_string());
+                }
+                "type" => {
+                    let issue_type = value.to_lowercase();
+                    if !crate::lint::ISSUE_TYPES.contains(&issue_type.as_str()) {
+                        return Err(invalid("type"));
+                    }
+                    bead.issue_type = issue_type;
+                    set("issueType", bead.issue_type.clone());
+                }
+                "owner" | "assignee" => {
+                    bead.owner = Some(value.clone());
+                    set("owner", value);
+                }
+                "parent" => {
+                    bead.parent = Some(value.clone());
+                    set("parent", value);
+                }
+                "label" | "labels" => {
+                    for label in value.split(',').map(str::trim).filter(|l| !l.is_empty()) {
+                        bead.labels.get_or_insert_with(Vec::new).push(label.to_string());
+                        set("labels", label.to_string());
+                    }
+                }
+                // Not shorthand, e.g. a time of day
+                _ => title.push(token.clone()),
+            }
+        } else {
+            title.push(token.clone());
+        }
+    }
+
+    bead.title = title.join(" ").trim().to_string();
+    if bead.title.is_empty() {
+        return Err(BertError::InvalidInput("Nothing is left for the title".to_string()));
+    }
+    Ok((bead, fields))
+}
+
+/// Create a bead from one line of shorthand (see the module docs), or with
+/// `preview` only return how it was read
+#[tauri::command]
+pub fn quick_create(
+    text: String,
+    preview: Option<bool>,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    app_handle: AppHandle,
+) -> Result<QuickCreate, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    let scheduling = SchedulingConfig::load_for_beads_file(&ctx.beads_path()?)?;
+    let (bead, fields) = parse(&text, scheduling.hours_per_day * 60.0)?;
+    if let Some(parent) = &bead.parent {
+        crate::bd::get_bead_by_id(&ctx, parent)?;
+    }
+
+    let op = BeadOp::Create { bead: bead.clone() };
+    if preview.unwrap_or(false) {
+        let preview = crate::bd::preview_bead_op(&ctx, &op)?;
+        return Ok(QuickCreate { bead, fields, bead_id: None, preview: Some(preview) });
+    }
+    let bead_id = crate::bd::submit_op(&ctx, &app_handle, op)?;
+    eprintln!("⚡ quick_create: {} '{}' with {} shorthand field(s)", bead_id, bead.title, fields.len());
+    Ok(QuickCreate { bead, fields, bead_id: Some(bead_id), preview: None })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_shorthand_around_the_title() {
+        let (bead, fields) = parse("Fix login redirect #bug p1 @alice est:120 parent:bp6-07y label:auth", 480.0).unwrap();
+        assert_eq!(bead.title, "Fix login redirect");
+        assert_eq!((bead.issue_type.as_str(), bead.priority, bead.estimate), ("bug", 1, Some(120)));
+        assert_eq!((bead.owner.as_deref(), bead.parent.as_deref()), (Some("alice"), Some("bp6-07y")));
+        assert_eq!(bead.labels, Some(vec!["auth".to_string()]));
+        let read: Vec<&str> = fields.iter().map(|f| f.field.as_str()).collect();
+        assert_eq!(read, ["issueType", "priority", "owner", "estimate", "parent", "labels"]);
+
+        let (bead, _) = parse(r#"Ship "v2: #1 @home" \#3 #ux est:1.5h at 10:30 est:1d"#, 480.0).unwrap();
+        assert_eq!(bead.title, "Ship v2: #1 @home #3 at 10:30");
+        assert_eq!((bead.issue_type.as_str(), bead.priority), ("task", 2));
+        assert_eq!(bead.labels, Some(vec!["ux".to_string()]));
+        assert_eq!(bead.estimate, Some(480));
+    }
+
+    #[test]
+    fn test_rejects_bad_values_and_empty_titles() {
+        assert_eq!(parse("Task est:soon", 480.0).unwrap_err().code(), "invalid_input");
+        assert_eq!(parse("Task p9", 480.0).unwrap_err().code(), "invalid_input");
+        assert_eq!(parse("Task type:story", 480.0).unwrap_err().code(), "invalid_input");
+        assert_eq!(parse("#bug @alice p0", 480.0).unwrap_err().code(), "invalid_input");
+        assert_eq!(parse_estimate("90", 480.0), Some(90));
+        assert_eq!(parse_estimate("2x", 480.0), None);
+    }
+}
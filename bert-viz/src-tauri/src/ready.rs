@@ -0,0 +1,218 @@
+//! "What should I do next": the ready queue
+//!
+//! Computes the open beads whose blockers are all finished from the beads
+//! file, the same way `bd ready` does but without spawning it, and orders them
+//! for picking up: beads on the critical path through the unfinished work
+//! first, then by priority, then oldest first. Each entry says why it is ready
+//! and which beads closing it would unblock.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::error::BertError;
+use crate::workflow::{StatusCategory, WorkflowConfig};
+use crate::Bead;
+
+/// Which ready beads to return
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ReadyQueueParams {
+    /// At most this many entries
+    pub limit: Option<usize>,
+    /// Only beads owned by this user or unassigned
+    pub owner: Option<String>,
+    /// Only beads carrying all of these labels
+    pub labels: Vec<String>,
+    /// Include epics, which are usually finished through their children
+    pub include_epics: bool,
+    /// Include beads already in progress
+    pub include_in_progress: bool,
+}
+
+/// One bead in the ready queue
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadyBead {
+    pub id: String,
+    pub title: String,
+    pub status: String,
+    pub priority: u32,
+    pub issue_type: String,
+    pub owner: Option<String>,
+    pub created_at: Option<String>,
+    pub on_critical_path: bool,
+    /// Why the bead is ready, e.g. "All 2 blockers done (a, b)"
+    pub reason: String,
+    /// Beads waiting on nothing else, which closing this one makes ready
+    pub unblocks: Vec<String>,
+    /// Unfinished beads transitively blocked by this one
+    pub downstream_count: usize,
+}
+
+/// Ready beads among `beads`, in the order to pick them up
+pub fn ready_queue(beads: &[Bead], params: &ReadyQueueParams, workflow: &WorkflowConfig) -> Vec<ReadyBead> {
+    let unfinished: HashSet<&str> = beads
+        .iter()
+        .filter(|b| b.status != "tombstone" && !workflow.is_done(&b.status))
+        .map(|b| b.id.as_str())
+        .collect();
+    let blockers_of = |bead: &Bead| -> Vec<String> {
+        bead.dependencies.iter().filter(|d| d.r#type == "blocks").map(|d| d.depends_on_id.clone()).collect()
+    };
+    let live: Vec<Bead> = beads.iter().filter(|b| b.status != "tombstone").cloned().collect();
+    let graph = crate::bert_core::build_dependency_graph(&live);
+    let critical: HashSet<String> = crate::notifications::open_critical_chain(beads, workflow).into_iter().collect();
+
+    let mut queue: Vec<ReadyBead> = crate::mcp::ready_beads(beads, workflow)
+        .into_iter()
+        .filter(|b| params.include_epics || b.issue_type != "epic")
+        .filter(|b| params.include_in_progress || workflow.category_of(&b.status) == StatusCategory::Todo)
+        .filter(|b| match (&params.owner, b.owner.as_deref()) {
+            (Some(owner), Some(bead_owner)) if !bead_owner.is_empty() => bead_owner == owner,
+            _ => true,
+        })
+        .filter(|b| {
+            let bead_labels = b.labels.as_deref().unwrap_or_default();
+            params.labels.iter().all(|l| bead_labels.contains(l))
+        })
+        .map(|b| {
+            let blockers = blockers_of(&b);
+            let reason = match blockers.len() {
+                0 => "No blocking dependencies".to_string(),
+                1 => format!("Blocker {} is done", blockers[0]),
+                n => format!("All {} blockers done ({})", n, blockers.join(", ")),
+            };
+
+            // Dependents whose only unfinished blocker is this bead
+            let mut unblocks: Vec<String> = graph
+                .blocks
+                .get(&b.id)
+                .into_iter()
+                .flatten()
+                .filter(|id| unfinished.contains(id.as_str()))
+                .filter(|id| {
+                    live.iter()
+                        .find(|d| &d.id == *id)
+                        .is_some_and(|d| blockers_of(d).iter().all(|on| on == &b.id || !unfinished.contains(on.as_str())))
+                })
+                .cloned()
+                .collect();
+            unblocks.sort();
+            unblocks.dedup();
+            let downstream_count = crate::impact::closure(&b.id, &graph.blocks)
+                .iter()
+                .filter(|(id, _)| unfinished.contains(id.as_str()))
+                .count();
+
+            ReadyBead {
+                on_critical_path: critical.contains(&b.id),
+                id: b.id,
+                title: b.title,
+                status: b.status,
+                priority: b.priority,
+                issue_type: b.issue_type,
+                owner: b.owner,
+                created_at: b.created_at,
+                reason,
+                unblocks,
+                downstream_count,
+            }
+        })
+        .collect();
+
+    queue.sort_by(|a, b| {
+        b.on_critical_path
+            .cmp(&a.on_critical_path)
+            .then_with(|| a.priority.cmp(&b.priority))
+            .then_with(|| a.created_at.cmp(&b.created_at))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    if let Some(limit) = params.limit {
+        queue.truncate(limit);
+    }
+    queue
+}
+
+/// Ready beads of the current project, for the "what should I do next" panel
+#[tauri::command]
+pub fn get_ready_queue(
+    params: Option<ReadyQueueParams>,
+    window: tauri::Window,
+    project_handle: Option<String>,
+) -> Result<Vec<ReadyBead>, BertError> {
+    let ctx = crate::project::context_for(&window, project_handle.as_deref())?;
+    let workflow = WorkflowConfig::load_for_beads_file(&ctx.beads_path()?)?;
+    let beads = crate::bd::load_beads(&ctx)?;
+
+    let queue = ready_queue(&beads, &params.unwrap_or_default(), &workflow);
+    eprintln!("📋 Ready queue: {} beads ({} on the critical path)", queue.len(), queue.iter().filter(|b| b.on_critical_path).count());
+    Ok(queue)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bead(id: &str, status: &str, priority: u32, created_at: &str, blockers: &[&str]) -> Bead {
+        let dependencies: Vec<_> = blockers
+            .iter()
+            .map(|on| json!({ "issue_id": id, "depends_on_id": on, "type": "blocks" }))
+            .collect();
+        crate::test_bead(json!({
+            "id": id,
+            "status": status,
+            "priority": priority,
+            "created_at": created_at,
+            "dependencies": dependencies,
+        }))
+    }
+
+    fn ids(queue: &[ReadyBead]) -> Vec<&str> {
+        queue.iter().map(|b| b.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_critical_path_then_priority_then_age() {
+        // a -> b -> c is the critical path; x and y are independent
+        let beads = vec![
+            bead("a", "open", 3, "2024-01-03", &[]),
+            bead("b", "open", 0, "2024-01-01", &["a"]),
+            bead("c", "open", 0, "2024-01-01", &["b"]),
+            bead("x", "open", 1, "2024-01-02", &[]),
+            bead("y", "open", 1, "2024-01-01", &[]),
+            bead("z", "in_progress", 0, "2024-01-01", &[]),
+        ];
+        let workflow = WorkflowConfig::default();
+
+        let queue = ready_queue(&beads, &ReadyQueueParams::default(), &workflow);
+        assert_eq!(ids(&queue), vec!["a", "y", "x"]);
+        assert!(queue[0].on_critical_path);
+        assert_eq!(queue[0].unblocks, vec!["b".to_string()]);
+        assert_eq!(queue[0].downstream_count, 2);
+        assert_eq!(queue[0].reason, "No blocking dependencies");
+
+        let params = ReadyQueueParams { include_in_progress: true, limit: Some(2), ..Default::default() };
+        assert_eq!(ids(&ready_queue(&beads, &params, &workflow)), vec!["a", "z"]);
+    }
+
+    #[test]
+    fn test_reasons_and_partial_unblocks() {
+        // d waits on a and b; closing a alone doesn't make it ready
+        let beads = vec![
+            bead("a", "open", 2, "2024-01-01", &[]),
+            bead("b", "open", 2, "2024-01-02", &["old"]),
+            bead("old", "closed", 2, "2023-12-01", &[]),
+            bead("d", "open", 2, "2024-01-03", &["a", "b"]),
+        ];
+        let queue = ready_queue(&beads, &ReadyQueueParams::default(), &WorkflowConfig::default());
+        let mut ready = ids(&queue);
+        ready.sort();
+        assert_eq!(ready, vec!["a", "b"]);
+
+        let entry = |id: &str| queue.iter().find(|b| b.id == id).unwrap();
+        assert!(entry("a").unblocks.is_empty());
+        assert_eq!(entry("a").downstream_count, 1);
+        assert_eq!(entry("b").reason, "Blocker old is done");
+    }
+}
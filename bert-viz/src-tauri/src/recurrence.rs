@@ -0,0 +1,478 @@
+//! Recurring chores
+//!
+//! A recurrence attaches a schedule to a template bead: every N days, or a
+//! five-field cron expression (`minute hour day-of-month month day-of-week`,
+//! in local time). Recurrences live under the `recurrences` key of
+//! `.beads/bert-viz.json` together with when they last fired.
+//!
+//! The scheduler checks the open projects when the app starts, whenever a
+//! project is opened, and every `CHECK_INTERVAL`. A due recurrence creates one
+//! copy of its template with `bd`, linked back to the template by a `related`
+//! dependency. Periods missed while the app was closed produce a single
+//! instance, not one per period.
+
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Map;
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::error::BertError;
+use crate::project::{ProjectContext, ProjectContexts};
+use crate::schedule::load_config_section;
+use crate::Bead;
+
+/// Key of the recurrences in the project config file
+const CONFIG_KEY: &str = "recurrences";
+
+/// How often the scheduler looks for due recurrences
+const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Give the windows time to open their projects before the startup check
+const STARTUP_DELAY: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Serializes checks so the timer and a project opening never both create an
+/// instance for the same period
+static CHECK_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum RecurrenceSchedule {
+    Interval { days: u32 },
+    Cron { expression: String },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Recurrence {
+    pub id: String,
+    pub template_id: String,
+    pub schedule: RecurrenceSchedule,
+    #[serde(default)]
+    pub paused: bool,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub last_created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub last_instance_id: Option<String>,
+}
+
+/// A recurrence with when it next fires (None while paused)
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurrenceInfo {
+    #[serde(flatten)]
+    pub recurrence: Recurrence,
+    pub next_due: Option<DateTime<Utc>>,
+}
+
+/// A parsed cron expression; each field is a bitmask of the allowed values
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// Day of month and day of week were both restricted, so either may match
+    /// (as in standard cron)
+    either_day: bool,
+}
+
+fn parse_cron_field(field: &str, min: u32, max: u32) -> Result<u64, String> {
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().map_err(|_| format!("invalid step in '{}'", part))?),
+            None => (part, 1),
+        };
+        if step == 0 {
+            return Err(format!("step cannot be zero in '{}'", part));
+        }
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((a, b)) = range.split_once('-') {
+            let a = a.parse::<u32>().map_err(|_| format!("invalid value in '{}'", part))?;
+            let b = b.parse::<u32>().map_err(|_| format!("invalid value in '{}'", part))?;
+            (a, b)
+        } else {
+            let value = range.parse::<u32>().map_err(|_| format!("invalid value in '{}'", part))?;
+            // `5/15` means from 5 to the end in steps of 15
+            (value, if part.contains('/') { max } else { value })
+        };
+        if start < min || end > max || start > end {
+            return Err(format!("'{}' is outside {}-{}", part, min, max));
+        }
+        for value in (start..=end).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+impl CronSchedule {
+    pub fn parse(expression: &str) -> Result<Self, BertError> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let invalid = |message: String| BertError::InvalidInput(format!("Invalid cron expression '{}': {}", expression, message));
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(invalid("expected 5 fields".to_string()));
+        };
+        let mut weekdays = parse_cron_field(weekday, 0, 7).map_err(invalid)?;
+        // Both 0 and 7 are Sunday
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays | 1) & !(1 << 7);
+        }
+        Ok(CronSchedule {
+            minutes: parse_cron_field(minute, 0, 59).map_err(invalid)?,
+            hours: parse_cron_field(hour, 0, 23).map_err(invalid)?,
+            days: parse_cron_field(day, 1, 31).map_err(invalid)?,
+            months: parse_cron_field(month, 1, 12).map_err(invalid)?,
+            weekdays,
+            either_day: day != "*" && weekday != "*",
+        })
+    }
+
+    fn day_matches(&self, date: NaiveDate) -> bool {
+        let day = self.days & (1 << date.day()) != 0;
+        let weekday = self.weekdays & (1 << date.weekday().num_days_from_sunday()) != 0;
+        if self.either_day {
+            day || weekday
+        } else {
+            day && weekday
+        }
+    }
+
+    /// First matching minute strictly after `after`, within four years
+    pub fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let limit = after + Duration::days(4 * 366);
+        let mut t = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        while t <= limit {
+            let midnight = t.date().and_hms_opt(0, 0, 0)?;
+            if self.months & (1 << t.month()) == 0 || !self.day_matches(t.date()) {
+                t = midnight + Duration::days(1);
+            } else if self.hours & (1 << t.hour()) == 0 {
+                t = t.with_minute(0)? + Duration::hours(1);
+            } else if self.minutes & (1 << t.minute()) == 0 {
+                t += Duration::minutes(1);
+            } else {
+                return Some(t);
+            }
+        }
+        None
+    }
+}
+
+impl RecurrenceSchedule {
+    fn validate(&self) -> Result<(), BertError> {
+        match self {
+            RecurrenceSchedule::Interval { days: 0 } => {
+                Err(BertError::InvalidInput("A recurrence interval must be at least one day".to_string()))
+            }
+            RecurrenceSchedule::Interval { .. } => Ok(()),
+            RecurrenceSchedule::Cron { expression } => CronSchedule::parse(expression).map(|_| ()),
+        }
+    }
+}
+
+impl Recurrence {
+    /// When the next instance is due; None while paused
+    pub fn next_due(&self) -> Option<DateTime<Utc>> {
+        if self.paused {
+            return None;
+        }
+        let anchor = self.last_created_at.unwrap_or(self.created_at);
+        match &self.schedule {
+            RecurrenceSchedule::Interval { days } => Some(anchor + Duration::days(i64::from(*days))),
+            RecurrenceSchedule::Cron { expression } => {
+                let next = CronSchedule::parse(expression).ok()?.next_after(anchor.with_timezone(&Local).naive_local())?;
+                Local.from_local_datetime(&next).earliest().map(|t| t.with_timezone(&Utc))
+            }
+        }
+    }
+}
+
+/// Load the recurrences stored alongside `beads_path`
+pub fn load_for_beads_file(beads_path: &Path) -> Result<Vec<Recurrence>, BertError> {
+    Ok(load_config_section(beads_path, CONFIG_KEY)?.unwrap_or_default())
+}
+
+/// Save the recurrences alongside `beads_path`, keeping the other settings in the file
+pub fn save_for_beads_file(recurrences: &[Recurrence], beads_path: &Path) -> Result<(), BertError> {
+    let mut fields = Map::new();
+    fields.insert(CONFIG_KEY.to_string(), serde_json::to_value(recurrences)?);
+    crate::schedule::merge_config_fields(beads_path, fields)
+}
+
+/// The bead a recurrence creates from its template
+pub fn instance_of(template: &Bead, date: NaiveDate) -> Bead {
+    let mut bead = template.clone();
+    bead.id = String::new();
+    bead.title = format!("{} ({})", template.title, date.format("%Y-%m-%d"));
+    bead.status = "open".to_string();
+    bead.parent = template.parent.clone().or_else(|| {
+        template
+            .dependencies
+            .iter()
+            .find(|d| d.r#type == "parent-child")
+            .map(|d| d.depends_on_id.clone())
+    });
+    bead.dependencies = Vec::new();
+    bead.created_at = None;
+    bead.created_by = None;
+    bead.updated_at = None;
+    bead.closed_at = None;
+    bead.close_reason = None;
+    bead.is_favorite = None;
+    bead.external_reference = None;
+    bead.due_date = None;
+    bead.extra_metadata = Map::new();
+    bead
+}
+
+/// Create the instances of every recurrence due at `now` in `ctx`'s project.
+/// Returns the ids of the new beads.
+pub fn create_due(ctx: &ProjectContext, now: DateTime<Utc>) -> Result<Vec<String>, BertError> {
+    let _guard = CHECK_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let beads_path = ctx.beads_path()?;
+    let mut recurrences = load_for_beads_file(&beads_path)?;
+    if !recurrences.iter().any(|r| r.next_due().is_some_and(|due| due <= now)) {
+        return Ok(Vec::new());
+    }
+
+    let beads = crate::bd::load_beads(ctx)?;
+    let mut created = Vec::new();
+    for index in 0..recurrences.len() {
+        let recurrence = &recurrences[index];
+        if recurrence.next_due().is_none_or(|due| due > now) {
+            continue;
+        }
+        let Some(template) = beads.iter().find(|b| b.id == recurrence.template_id) else {
+            eprintln!("⚠️  Recurrence {}: template {} no longer exists", recurrence.id, recurrence.template_id);
+            continue;
+        };
+
+        let instance = instance_of(template, now.with_timezone(&Local).date_naive());
+        let new_id = crate::bd::create_bead_internal(ctx, &instance)?;
+        if let Err(e) = crate::bd::add_dependency_internal(ctx, &new_id, &template.id, "related") {
+            eprintln!("⚠️  Created {} but could not link it to {}: {}", new_id, template.id, e);
+        }
+        eprintln!("🔁 Recurrence {}: created {} from {}", recurrence.id, new_id, template.id);
+
+        recurrences[index].last_created_at = Some(now);
+        recurrences[index].last_instance_id = Some(new_id.clone());
+        created.push(new_id);
+        // Record each instance at once so a later failure can't duplicate it
+        save_for_beads_file(&recurrences, &beads_path)?;
+    }
+    Ok(created)
+}
+
+/// Check one project for due recurrences, refreshing its windows if any fired
+fn check_project(app_handle: &AppHandle, ctx: &ProjectContext) {
+    match create_due(ctx, Utc::now()) {
+        Ok(created) if !created.is_empty() => crate::project::emit_beads_updated(app_handle, ctx),
+        Ok(_) => {}
+        Err(e) => eprintln!("⚠️  Recurrences of {}: {}", ctx.project_path().display(), e),
+    }
+}
+
+/// Check a project that was just opened, off the command thread
+pub fn check_in_background(app_handle: &AppHandle, ctx: ProjectContext) {
+    let app_handle = app_handle.clone();
+    std::thread::spawn(move || check_project(&app_handle, &ctx));
+}
+
+/// Check the open projects shortly after startup and then every `CHECK_INTERVAL`
+pub fn spawn_scheduler(app_handle: AppHandle) {
+    std::thread::spawn(move || {
+        std::thread::sleep(STARTUP_DELAY);
+        loop {
+            if let Some(contexts) = app_handle.try_state::<ProjectContexts>() {
+                for ctx in contexts.open_projects() {
+                    check_project(&app_handle, &ctx);
+                }
+            }
+            std::thread::sleep(CHECK_INTERVAL);
+        }
+    });
+}
+
+fn find_mut<'a>(recurrences: &'a mut [Recurrence], id: &str) -> Result<&'a mut Recurrence, BertError> {
+    recurrences
+        .iter_mut()
+        .find(|r| r.id == id)
+        .ok_or_else(|| BertError::NotFound(format!("Recurrence {} not found", id)))
+}
+
+#[tauri::command]
+pub fn list_recurrences(window: tauri::Window, project_handle: Option<String>) -> Result<Vec<RecurrenceInfo>, BertError> {
+    let beads_path = crate::project::context_for(&window, project_handle.as_deref())?.beads_path()?;
+    Ok(load_for_beads_file(&beads_path)?
+        .into_iter()
+        .map(|recurrence| RecurrenceInfo { next_due: recurrence.next_due(), recurrence })
+        .collect())
+}
+
+/// Create `templateId` again on `schedule`, starting from now
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn add_recurrence(
+    templateId: String,
+    schedule: RecurrenceSchedule,
+    window: tauri::Window,
+    project_handle: Option<String>,
+) -> Result<RecurrenceInfo, BertError> {
+    schedule.validate()?;
+    let ctx = crate::project::context_for(&window, project_handle.as_deref())?;
+    let beads_path = ctx.beads_path()?;
+    if !crate::bd::load_beads(&ctx)?.iter().any(|b| b.id == templateId) {
+        return Err(BertError::NotFound(format!("Bead with ID {} not found", templateId)));
+    }
+
+    let _guard = CHECK_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut recurrences = load_for_beads_file(&beads_path)?;
+    let recurrence = Recurrence {
+        id: uuid::Uuid::new_v4().to_string(),
+        template_id: templateId,
+        schedule,
+        paused: false,
+        created_at: Utc::now(),
+        last_created_at: None,
+        last_instance_id: None,
+    };
+    recurrences.push(recurrence.clone());
+    save_for_beads_file(&recurrences, &beads_path)?;
+    eprintln!("🔁 Added recurrence {} of {}", recurrence.id, recurrence.template_id);
+    Ok(RecurrenceInfo { next_due: recurrence.next_due(), recurrence })
+}
+
+/// Pause a recurrence, or resume it with `paused: false`. Resuming does not
+/// catch up on the periods skipped while paused.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn pause_recurrence(
+    recurrenceId: String,
+    paused: bool,
+    window: tauri::Window,
+    project_handle: Option<String>,
+) -> Result<RecurrenceInfo, BertError> {
+    let beads_path = crate::project::context_for(&window, project_handle.as_deref())?.beads_path()?;
+    let _guard = CHECK_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut recurrences = load_for_beads_file(&beads_path)?;
+    let recurrence = find_mut(&mut recurrences, &recurrenceId)?;
+    if recurrence.paused && !paused {
+        recurrence.last_created_at = Some(Utc::now());
+    }
+    recurrence.paused = paused;
+    let recurrence = recurrence.clone();
+    save_for_beads_file(&recurrences, &beads_path)?;
+    eprintln!("🔁 Recurrence {} {}", recurrenceId, if paused { "paused" } else { "resumed" });
+    Ok(RecurrenceInfo { next_due: recurrence.next_due(), recurrence })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_cron_next_after() {
+        // Weekdays at 09:30
+        let cron = CronSchedule::parse("30 9 * * 1-5").unwrap();
+        assert_eq!(cron.next_after(at("2026-10-16 09:00")), Some(at("2026-10-16 09:30")));
+        // Friday after the run: next is Monday
+        assert_eq!(cron.next_after(at("2026-10-16 09:30")), Some(at("2026-10-19 09:30")));
+
+        // Every 15 minutes past the hour
+        let cron = CronSchedule::parse("*/15 * * * *").unwrap();
+        assert_eq!(cron.next_after(at("2026-10-16 23:50")), Some(at("2026-10-17 00:00")));
+
+        // The 1st of the month or any Sunday (7 is Sunday too)
+        let cron = CronSchedule::parse("0 0 1 * 7").unwrap();
+        assert_eq!(cron.next_after(at("2026-10-16 12:00")), Some(at("2026-10-18 00:00")));
+        assert_eq!(cron.next_after(at("2026-10-31 12:00")), Some(at("2026-11-01 00:00")));
+
+        // Leap day only
+        let cron = CronSchedule::parse("0 0 29 2 *").unwrap();
+        assert_eq!(cron.next_after(at("2026-10-16 12:00")), Some(at("2028-02-29 00:00")));
+    }
+
+    #[test]
+    fn test_invalid_schedules_are_rejected() {
+        for expression in ["* * * *", "60 * * * *", "* * 0 * *", "*/0 * * * *", "a * * * *", "5-1 * * * *"] {
+            assert!(CronSchedule::parse(expression).is_err(), "{} should be rejected", expression);
+        }
+        assert!(RecurrenceSchedule::Interval { days: 0 }.validate().is_err());
+    }
+
+    #[test]
+    fn test_interval_due_and_paused() {
+        let created = DateTime::parse_from_rfc3339("2026-10-01T08:00:00Z").unwrap().with_timezone(&Utc);
+        let mut recurrence = Recurrence {
+            id: "r1".to_string(),
+            template_id: "bp6-1".to_string(),
+            schedule: RecurrenceSchedule::Interval { days: 7 },
+            paused: false,
+            created_at: created,
+            last_created_at: None,
+            last_instance_id: None,
+        };
+        assert_eq!(recurrence.next_due(), Some(created + Duration::days(7)));
+
+        // Missed periods fire once, then count from the instance
+        let late = created + Duration::days(30);
+        recurrence.last_created_at = Some(late);
+        assert_eq!(recurrence.next_due(), Some(late + Duration::days(7)));
+
+        recurrence.paused = true;
+        assert_eq!(recurrence.next_due(), None);
+    }
+
+    #[test]
+    fn test_instance_copies_the_template() {
+        let template: Bead = serde_json::from_value(json!({
+            "id": "bp6-7",
+            "title": "Rotate keys",
+            "status": "deferred",
+            "priority": 2,
+            "issue_type": "chore",
+            "labels": ["ops"],
+            "created_at": "2026-01-01T00:00:00Z",
+            "closed_at": "2026-01-02T00:00:00Z",
+            "dependencies": [{ "issue_id": "bp6-7", "depends_on_id": "bp6-2", "type": "parent-child" }],
+        }))
+        .unwrap();
+        let instance = instance_of(&template, NaiveDate::from_ymd_opt(2026, 10, 16).unwrap());
+
+        assert_eq!(instance.title, "Rotate keys (2026-10-16)");
+        assert_eq!(instance.status, "open");
+        assert_eq!(instance.parent.as_deref(), Some("bp6-2"));
+        assert_eq!(instance.labels, Some(vec!["ops".to_string()]));
+        assert!(instance.dependencies.is_empty() && instance.created_at.is_none() && instance.closed_at.is_none());
+    }
+
+    #[test]
+    fn test_recurrences_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let beads_path = dir.path().join("issues.jsonl");
+        assert!(load_for_beads_file(&beads_path).unwrap().is_empty());
+
+        let recurrence = Recurrence {
+            id: "r1".to_string(),
+            template_id: "bp6-1".to_string(),
+            schedule: RecurrenceSchedule::Cron { expression: "0 9 * * 1".to_string() },
+            paused: true,
+            created_at: Utc::now(),
+            last_created_at: None,
+            last_instance_id: None,
+        };
+        save_for_beads_file(std::slice::from_ref(&recurrence), &beads_path).unwrap();
+        assert_eq!(load_for_beads_file(&beads_path).unwrap(), vec![recurrence]);
+    }
+}
@@ -0,0 +1,120 @@
+//! Cross-references between beads
+//!
+//! Descriptions, notes and acceptance criteria often mention other beads
+//! ("depends on bp6-123"). `ReferenceScanner` finds those mentions with a
+//! regex built from the ID prefixes used in the project, keeping only IDs
+//! that exist. The view model lists them as `references` on each node, and
+//! `get_backlinks` answers the reverse question for the detail panel: which
+//! beads mention this one.
+
+use regex::Regex;
+use std::collections::{BTreeSet, HashSet};
+
+use crate::error::BertError;
+use crate::Bead;
+
+/// Prefix of a bead ID: everything before the last '-' of its root ID
+/// (`bp6` for `bp6-07y.3.4`)
+fn id_prefix(id: &str) -> Option<&str> {
+    let root = id.split('.').next()?;
+    root.rsplit_once('-').map(|(prefix, _)| prefix).filter(|p| !p.is_empty())
+}
+
+pub struct ReferenceScanner {
+    /// None when the project has no prefixed IDs to look for
+    pattern: Option<Regex>,
+    ids: HashSet<String>,
+}
+
+impl ReferenceScanner {
+    pub fn new(beads: &[Bead]) -> Self {
+        let prefixes: BTreeSet<&str> = beads.iter().filter_map(|b| id_prefix(&b.id)).collect();
+        let pattern = (!prefixes.is_empty()).then(|| {
+            let alternatives: Vec<String> = prefixes.iter().map(|p| regex::escape(p)).collect();
+            // Child IDs add .N segments; a trailing period ends the sentence.
+            // ASCII word boundaries keep the regex on its fast path.
+            Regex::new(&format!(r"(?-u:\b)(?:{})-[0-9A-Za-z]+(?:\.[0-9]+)*(?-u:\b)", alternatives.join("|")))
+                .expect("escaped prefixes form a valid pattern")
+        });
+        ReferenceScanner { pattern, ids: beads.iter().map(|b| b.id.clone()).collect() }
+    }
+
+    /// IDs of the existing beads mentioned in `bead`'s text, in order of
+    /// appearance, without `bead` itself
+    pub fn references(&self, bead: &Bead) -> Vec<String> {
+        let Some(pattern) = &self.pattern else { return Vec::new() };
+        let texts = [bead.description.as_deref(), bead.design.as_deref(), bead.notes.as_deref()]
+            .into_iter()
+            .flatten()
+            .chain(bead.acceptance_criteria.iter().flatten().map(String::as_str));
+
+        let mut references: Vec<String> = Vec::new();
+        for text in texts {
+            for m in pattern.find_iter(text) {
+                let id = m.as_str();
+                if id != bead.id && self.ids.contains(id) && !references.iter().any(|r| r == id) {
+                    references.push(id.to_string());
+                }
+            }
+        }
+        references
+    }
+}
+
+/// Beads mentioning `bead_id` in their text
+fn backlinks<'a>(beads: &'a [Bead], bead_id: &str) -> Vec<&'a Bead> {
+    let scanner = ReferenceScanner::new(beads);
+    beads
+        .iter()
+        .filter(|b| b.status != "tombstone" && scanner.references(b).iter().any(|r| r == bead_id))
+        .collect()
+}
+
+/// Beads whose text mentions `beadId` ("mentioned by")
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn get_backlinks(beadId: String, window: tauri::Window, project_handle: Option<String>) -> Result<Vec<Bead>, BertError> {
+    let beads = crate::bd::load_beads(&crate::project::context_for(&window, project_handle.as_deref())?)?;
+    if !beads.iter().any(|b| b.id == beadId) {
+        return Err(BertError::NotFound(format!("Bead with ID {} not found", beadId)));
+    }
+    Ok(backlinks(&beads, &beadId).into_iter().cloned().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bead(id: &str, description: &str) -> Bead {
+        crate::test_bead(json!({
+            "id": id,
+            "description": description,
+            "acceptance_criteria": "Closes ext-9.1",
+        }))
+    }
+
+    #[test]
+    fn test_id_prefix() {
+        assert_eq!(id_prefix("bp6-07y.3.4"), Some("bp6"));
+        assert_eq!(id_prefix("my-proj-abc"), Some("my-proj"));
+        assert_eq!(id_prefix("plain"), None);
+    }
+
+    #[test]
+    fn test_references_only_existing_beads() {
+        let beads = vec![
+            bead("bp6-1", "Depends on bp6-2 and bp6-07y.3. See bp6-2 again, not bp6-999 or xbp6-2."),
+            bead("bp6-2", "Mentions itself: bp6-2"),
+            bead("bp6-07y.3", ""),
+            bead("ext-9.1", ""),
+        ];
+        let scanner = ReferenceScanner::new(&beads);
+        assert_eq!(scanner.references(&beads[0]), vec!["bp6-2", "bp6-07y.3", "ext-9.1"]);
+        assert_eq!(scanner.references(&beads[1]), vec!["ext-9.1"]);
+        assert!(scanner.references(&beads[3]).is_empty());
+
+        let mentioned_by: Vec<&str> = backlinks(&beads, "bp6-2").iter().map(|b| b.id.as_str()).collect();
+        assert_eq!(mentioned_by, vec!["bp6-1"]);
+    }
+}
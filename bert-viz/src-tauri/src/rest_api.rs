@@ -0,0 +1,405 @@
+//! Local REST API (`rest-api` feature)
+//!
+//! Serves project data to dashboards, scripts and web clients while the app
+//! runs. The server listens on 127.0.0.1 at the port from the `restApi`
+//! settings and answers read-only `GET` requests:
+//!
+//! - `/beads`: the beads, filtered like the bead list
+//! - `/view-model`: the `ProjectViewModel` the UI renders
+//! - `/metadata`: the view model's project metadata
+//! - `/critical-path`: the open beads on the critical path, in blocking order
+//!
+//! Every request needs `Authorization: Bearer <token>`. `?project=<path>`
+//! picks one of the known projects (the most recently opened one otherwise);
+//! `filter=<query>` and `hideClosed=true` filter `/beads` and `/view-model`;
+//! `scope=<epic id>` narrows them to one epic (see `FilterParams::scope_root_id`).
+//! The data comes from the same snapshot reads and view model cache as the
+//! windows use.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::error::BertError;
+use crate::project::{ProjectContext, ProjectContexts};
+use crate::view_cache::ProjectState;
+use crate::workflow::WorkflowConfig;
+use crate::{FilterParams, SettingsState};
+
+/// Longest request (line and headers) read before giving up
+const MAX_REQUEST_BYTES: u64 = 16 * 1024;
+
+/// How long a client may take to send its request
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Key of the view model cache entry of a project served over the API
+const CACHE_KEY_PREFIX: &str = "rest-api:";
+
+#[derive(Debug, Clone, PartialEq)]
+struct Request {
+    method: String,
+    path: String,
+    query: HashMap<String, String>,
+    authorization: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn json<T: Serialize>(status: u16, value: &T) -> Self {
+        let body = serde_json::to_string(value).unwrap_or_else(|e| format!("{{\"message\":\"{}\"}}", e));
+        Response { status, body }
+    }
+
+    fn error(error: &BertError) -> Self {
+        let status = match error {
+            BertError::NotFound(_) | BertError::ProjectNotFound(_) | BertError::BeadsFileNotFound(_) => 404,
+            BertError::InvalidInput(_) => 400,
+            BertError::FileLocked(_) => 503,
+            _ => 500,
+        };
+        Response::json(status, error)
+    }
+
+    fn message(status: u16, message: &str) -> Self {
+        Response::json(status, &serde_json::json!({ "message": message }))
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        204 => "No Content",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+/// Decode `%XX` escapes and `+` in a query component
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    Some(byte) => {
+                        out.push(byte);
+                        i += 2;
+                    }
+                    None => out.push(b'%'),
+                }
+            }
+            byte => out.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn read_request(reader: impl Read) -> Result<Request, String> {
+    let mut reader = BufReader::new(reader.take(MAX_REQUEST_BYTES));
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(|e| e.to_string())?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(format!("Malformed request line: {:?}", line.trim()));
+    };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key), percent_decode(value))
+        })
+        .collect();
+
+    let mut authorization = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).map_err(|e| e.to_string())? == 0 {
+            break;
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("authorization") {
+                authorization = Some(value.trim().to_string());
+            }
+        }
+    }
+
+    Ok(Request { method: method.to_string(), path: path.to_string(), query, authorization })
+}
+
+/// Compare in time independent of where the strings differ
+fn tokens_match(given: &str, expected: &str) -> bool {
+    given.len() == expected.len() && given.bytes().zip(expected.bytes()).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+fn authorized(request: &Request, token: &str) -> bool {
+    let given = request
+        .authorization
+        .as_deref()
+        .and_then(|value| value.strip_prefix("Bearer ").or_else(|| value.strip_prefix("bearer ")));
+    !token.is_empty() && given.is_some_and(|given| tokens_match(given.trim(), token))
+}
+
+fn filter_params(request: &Request) -> FilterParams {
+    FilterParams {
+        filter_text: request.query.get("filter").cloned().unwrap_or_default(),
+        hide_closed: request.query.get("hideClosed").is_some_and(|v| v == "true" || v == "1"),
+        scope_root_id: request.query.get("scope").filter(|id| !id.is_empty()).cloned(),
+        ..FilterParams::default()
+    }
+}
+
+/// Data for an authorized GET of `path` from the project at `beads_path`
+fn route(request: &Request, beads_path: &Path, project_state: &ProjectState) -> Result<Response, BertError> {
+    let params = filter_params(request);
+    let view_model = || {
+        let key = format!("{}{}", CACHE_KEY_PREFIX, beads_path.display());
+        project_state.load(&key, beads_path, &params).map(|(_, view_model)| view_model).map_err(BertError::from)
+    };
+    let response = match request.path.trim_end_matches('/') {
+        "/beads" => {
+            let beads = crate::snapshot::read_beads(beads_path)?;
+            let workflow = WorkflowConfig::load_for_beads_file(beads_path)?;
+            Response::json(200, &crate::bert_core::select_beads(beads, &params, &workflow))
+        }
+        "/view-model" => Response::json(200, &view_model()?),
+        "/metadata" => Response::json(200, &view_model()?.metadata),
+        "/critical-path" => {
+            let beads = crate::snapshot::read_beads(beads_path)?;
+            let workflow = WorkflowConfig::load_for_beads_file(beads_path)?;
+            Response::json(200, &crate::cli::critical_path(&beads, &workflow))
+        }
+        other => return Err(BertError::NotFound(format!("No endpoint {}", other))),
+    };
+    Ok(response)
+}
+
+/// Answer `request`, resolving its `project` parameter with `beads_path_for`
+fn handle(
+    request: &Request,
+    token: &str,
+    beads_path_for: impl FnOnce(Option<&str>) -> Result<PathBuf, BertError>,
+    project_state: &ProjectState,
+) -> Response {
+    if request.method == "OPTIONS" {
+        return Response { status: 204, body: String::new() };
+    }
+    if !authorized(request, token) {
+        return Response::message(401, "Missing or invalid bearer token");
+    }
+    if request.method != "GET" {
+        return Response::message(405, "The REST API is read-only; use GET");
+    }
+    beads_path_for(request.query.get("project").map(String::as_str))
+        .and_then(|beads_path| route(request, &beads_path, project_state))
+        .unwrap_or_else(|e| Response::error(&e))
+}
+
+/// Beads file of a known project, or of the most recently opened one
+fn beads_path_for(app_handle: &AppHandle, project: Option<&str>) -> Result<PathBuf, BertError> {
+    let ctx = match project {
+        Some(path) => {
+            if !crate::get_projects()?.iter().any(|p| Path::new(&p.path) == Path::new(path)) {
+                return Err(BertError::ProjectNotFound(format!("{} is not a known project", path)));
+            }
+            ProjectContext::new(path)
+        }
+        None => app_handle
+            .try_state::<ProjectContexts>()
+            .and_then(|contexts| contexts.last_opened())
+            .ok_or_else(|| BertError::ProjectNotFound("No project is open; pass ?project=<path>".to_string()))?,
+    };
+    ctx.beads_path()
+}
+
+fn write_response(stream: &mut TcpStream, response: &Response) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\
+         Access-Control-Allow-Origin: *\r\nAccess-Control-Allow-Headers: Authorization\r\n\
+         Access-Control-Allow-Methods: GET, OPTIONS\r\n",
+        response.status,
+        reason(response.status),
+        response.body.len()
+    )?;
+    if response.status == 401 {
+        write!(stream, "WWW-Authenticate: Bearer\r\n")?;
+    }
+    write!(stream, "\r\n{}", response.body)?;
+    stream.flush()
+}
+
+fn serve(app_handle: &AppHandle, mut stream: TcpStream) {
+    let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+    let response = match read_request(&stream) {
+        Ok(request) => {
+            let settings = app_handle
+                .try_state::<SettingsState>()
+                .and_then(|state| state.settings.lock().ok().map(|s| s.rest_api.clone()))
+                .unwrap_or_default();
+            match app_handle.try_state::<ProjectState>() {
+                _ if !settings.enabled => Response::message(503, "The REST API is disabled"),
+                Some(project_state) => {
+                    let response = handle(&request, &settings.token, |project| beads_path_for(app_handle, project), &project_state);
+                    eprintln!("🌐 {} {} -> {}", request.method, request.path, response.status);
+                    response
+                }
+                None => Response::message(503, "The app is still starting"),
+            }
+        }
+        Err(e) => Response::message(400, &e),
+    };
+    if let Err(e) = write_response(&mut stream, &response) {
+        eprintln!("⚠️  REST API: failed to write response: {}", e);
+    }
+}
+
+/// Start the server if the settings enable it
+pub fn start(app_handle: AppHandle) {
+    let Some(settings) = app_handle
+        .try_state::<SettingsState>()
+        .and_then(|state| state.settings.lock().ok().map(|s| s.rest_api.clone()))
+    else {
+        return;
+    };
+    if !settings.enabled {
+        return;
+    }
+    if settings.token.is_empty() {
+        eprintln!("⚠️  REST API enabled without a token; not starting it");
+        return;
+    }
+
+    let listener = match TcpListener::bind((Ipv4Addr::LOCALHOST, settings.port)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("⚠️  REST API could not listen on port {}: {}", settings.port, e);
+            return;
+        }
+    };
+    eprintln!("🌐 REST API listening on http://127.0.0.1:{}", settings.port);
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    let app_handle = app_handle.clone();
+                    std::thread::spawn(move || serve(&app_handle, stream));
+                }
+                Err(e) => eprintln!("⚠️  REST API: failed to accept a connection: {}", e),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const TOKEN: &str = "s3cret";
+
+    fn request(raw: &str) -> Request {
+        read_request(raw.as_bytes()).unwrap()
+    }
+
+    fn get(target: &str) -> Request {
+        request(&format!("GET {} HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer {}\r\n\r\n", target, TOKEN))
+    }
+
+    fn project() -> (TempDir, PathBuf) {
+        let dir = TempDir::new().unwrap();
+        let beads_path = dir.path().join("issues.jsonl");
+        let lines = [
+            r#"{"id":"bp6-1","title":"Design","status":"closed","priority":1,"issue_type":"task","estimate":60}"#,
+            r#"{"id":"bp6-2","title":"Build","status":"open","priority":1,"issue_type":"task","estimate":120,"dependencies":[{"issue_id":"bp6-2","depends_on_id":"bp6-1","type":"blocks"}]}"#,
+            r#"{"id":"bp6-3","title":"Ship it","status":"open","priority":2,"issue_type":"task","estimate":30,"dependencies":[{"issue_id":"bp6-3","depends_on_id":"bp6-2","type":"blocks"}]}"#,
+        ];
+        std::fs::write(&beads_path, lines.join("\n")).unwrap();
+        (dir, beads_path)
+    }
+
+    fn respond(request: &Request, beads_path: &Path) -> (u16, serde_json::Value) {
+        let response = handle(request, TOKEN, |_| Ok(beads_path.to_path_buf()), &ProjectState::new());
+        let body = if response.body.is_empty() { serde_json::Value::Null } else { serde_json::from_str(&response.body).unwrap() };
+        (response.status, body)
+    }
+
+    #[test]
+    fn test_read_request_decodes_query_and_authorization() {
+        let request = get("/beads?filter=status%3Aopen+owner%3Aalice&hideClosed=true");
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/beads");
+        assert_eq!(request.query["filter"], "status:open owner:alice");
+        assert_eq!(request.query["hideClosed"], "true");
+        assert_eq!(request.authorization.as_deref(), Some("Bearer s3cret"));
+
+        assert!(read_request("\r\n".as_bytes()).is_err());
+        assert_eq!(percent_decode("100%"), "100%");
+    }
+
+    #[test]
+    fn test_requests_need_the_token() {
+        let (_dir, beads_path) = project();
+        let anonymous = request("GET /beads HTTP/1.1\r\n\r\n");
+        let wrong = request("GET /beads HTTP/1.1\r\nauthorization: Bearer nope\r\n\r\n");
+        assert_eq!(respond(&anonymous, &beads_path).0, 401);
+        assert_eq!(respond(&wrong, &beads_path).0, 401);
+
+        // No token configured: nothing is authorized
+        let response = handle(&get("/beads"), "", |_| Ok(beads_path.clone()), &ProjectState::new());
+        assert_eq!(response.status, 401);
+
+        let post = request(&format!("POST /beads HTTP/1.1\r\nAuthorization: Bearer {}\r\n\r\n", TOKEN));
+        assert_eq!(respond(&post, &beads_path).0, 405);
+    }
+
+    #[test]
+    fn test_endpoints_serve_project_data() {
+        let (_dir, beads_path) = project();
+
+        let (status, beads) = respond(&get("/beads?hideClosed=true"), &beads_path);
+        assert_eq!(status, 200);
+        let ids: Vec<&str> = beads.as_array().unwrap().iter().map(|b| b["id"].as_str().unwrap()).collect();
+        assert_eq!(ids, vec!["bp6-2", "bp6-3"]);
+
+        let (status, metadata) = respond(&get("/metadata"), &beads_path);
+        assert_eq!(status, 200);
+        assert_eq!(metadata["totalBeads"], 3);
+
+        let (status, view_model) = respond(&get("/view-model/"), &beads_path);
+        assert_eq!(status, 200);
+        assert!(view_model["tree"].is_array());
+
+        let (status, path) = respond(&get("/critical-path"), &beads_path);
+        assert_eq!(status, 200);
+        let ids: Vec<&str> = path.as_array().unwrap().iter().map(|s| s["id"].as_str().unwrap()).collect();
+        assert_eq!(ids, vec!["bp6-2", "bp6-3"]);
+
+        let (status, error) = respond(&get("/nope"), &beads_path);
+        assert_eq!(status, 404);
+        assert_eq!(error["code"], "not_found");
+    }
+}
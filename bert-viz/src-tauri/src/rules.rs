@@ -0,0 +1,348 @@
+//! Priority aging and escalation rules
+//!
+//! Teams agree on policies like "open P2 bugs older than 14 days become P1" or
+//! "beads blocked for a week get the `stalled` label", then forget to apply
+//! them. Rules stored under the `rules` key of `.beads/bert-viz.json` describe
+//! such policies. `run_rules` evaluates them against the current beads and
+//! returns the changes they call for; unless it is a dry run, it applies them
+//! as one batch update and records the run in the audit log.
+//!
+//! Rules run in order, each seeing the changes of the ones before, and never
+//! touch finished beads.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Map;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::bd::{BeadOp, BeadOpResult};
+use crate::error::BertError;
+use crate::schedule::{config_path_for, load_config_section};
+use crate::workflow::{StatusCategory, WorkflowConfig};
+use crate::Bead;
+
+/// Key of the rules in the project config file
+const CONFIG_KEY: &str = "rules";
+
+/// Session the rule runs are audited under
+const AUDIT_SOURCE: &str = "rules";
+
+/// What a bead must match for a rule to apply; unset fields match anything
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RuleCondition {
+    pub issue_type: Option<String>,
+    pub priority: Option<u32>,
+    pub status: Option<String>,
+    pub label: Option<String>,
+    /// Created at least this many days ago
+    pub older_than_days: Option<u32>,
+    /// Not updated for at least this many days
+    pub idle_days: Option<u32>,
+    /// Blocked (by status or an unfinished blocker) and not updated for at
+    /// least this many days
+    pub blocked_days: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum RuleAction {
+    /// Raise the priority to `priority` (0 is most urgent); never lowers it
+    Escalate { priority: u32 },
+    AddLabel { label: String },
+    SetStatus { status: String },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Rule {
+    pub name: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub when: RuleCondition,
+    pub then: RuleAction,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// One field a rule changes on one bead
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleChange {
+    pub rule: String,
+    pub bead_id: String,
+    pub field: String,
+    pub old: String,
+    pub new: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RulesRun {
+    pub dry_run: bool,
+    pub changes: Vec<RuleChange>,
+    /// Outcome of each bead update; empty for dry runs
+    pub results: Vec<BeadOpResult>,
+}
+
+fn validate(rules: &[Rule]) -> Result<(), BertError> {
+    for rule in rules {
+        if rule.name.trim().is_empty() {
+            return Err(BertError::InvalidInput("Rule names cannot be empty".to_string()));
+        }
+        let invalid = |message: &str| Err(BertError::InvalidInput(format!("Rule '{}': {}", rule.name, message)));
+        match &rule.then {
+            RuleAction::Escalate { priority } if *priority > 4 => return invalid("priority must be between 0 and 4"),
+            RuleAction::AddLabel { label } if label.trim().is_empty() || label.contains(',') => {
+                return invalid("labels cannot be empty or contain commas")
+            }
+            RuleAction::SetStatus { status } if status.trim().is_empty() => return invalid("status cannot be empty"),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Load the rules stored alongside `beads_path` (none if there are none yet)
+pub fn load_for_beads_file(beads_path: &Path) -> Result<Vec<Rule>, BertError> {
+    let rules: Vec<Rule> = load_config_section(beads_path, CONFIG_KEY)?.unwrap_or_default();
+    validate(&rules)?;
+    Ok(rules)
+}
+
+/// Save the rules alongside `beads_path`, keeping the other settings in the file
+pub fn save_for_beads_file(rules: &[Rule], beads_path: &Path) -> Result<(), BertError> {
+    validate(rules)?;
+    let mut fields = Map::new();
+    fields.insert(CONFIG_KEY.to_string(), serde_json::to_value(rules)?);
+    crate::schedule::merge_config_fields(beads_path, fields)
+}
+
+fn days_since(timestamp: Option<DateTime<Utc>>, now: DateTime<Utc>, days: u32) -> bool {
+    timestamp.is_some_and(|t| now - t >= Duration::days(i64::from(days)))
+}
+
+fn matches(condition: &RuleCondition, bead: &Bead, blocked: bool, workflow: &WorkflowConfig, now: DateTime<Utc>) -> bool {
+    let created = bead
+        .created_at
+        .as_deref()
+        .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+    let touched = crate::health::last_touched(bead);
+
+    condition.issue_type.as_ref().is_none_or(|t| *t == bead.issue_type)
+        && condition.priority.is_none_or(|p| p == bead.priority)
+        && condition.status.as_ref().is_none_or(|s| *s == bead.status)
+        && condition.label.as_ref().is_none_or(|l| bead.labels.iter().flatten().any(|bl| bl == l))
+        && condition.older_than_days.is_none_or(|d| days_since(created, now, d))
+        && condition.idle_days.is_none_or(|d| days_since(touched, now, d))
+        && condition.blocked_days.is_none_or(|d| {
+            (blocked || workflow.category_of(&bead.status) == StatusCategory::Blocked) && days_since(touched, now, d)
+        })
+}
+
+/// Apply `action` to `bead`, returning the field changed with its old and new
+/// value, or None when the bead already satisfies it
+fn apply(action: &RuleAction, bead: &mut Bead) -> Option<(&'static str, String, String)> {
+    match action {
+        RuleAction::Escalate { priority } if bead.priority > *priority => {
+            let old = std::mem::replace(&mut bead.priority, *priority);
+            Some(("priority", old.to_string(), priority.to_string()))
+        }
+        RuleAction::AddLabel { label } if !bead.labels.iter().flatten().any(|l| l == label) => {
+            let labels = bead.labels.get_or_insert_with(Vec::new);
+            let old = labels.join(",");
+            labels.push(label.clone());
+            Some(("labels", old, labels.join(",")))
+        }
+        RuleAction::SetStatus { status } if bead.status != *status => {
+            let old = std::mem::replace(&mut bead.status, status.clone());
+            Some(("status", old, status.clone()))
+        }
+        _ => None,
+    }
+}
+
+/// Changes the enabled `rules` call for as of `now`, and the updated beads in
+/// file order
+pub fn evaluate(beads: &[Bead], rules: &[Rule], workflow: &WorkflowConfig, now: DateTime<Utc>) -> (Vec<RuleChange>, Vec<Bead>) {
+    let unfinished: HashSet<&str> = beads
+        .iter()
+        .filter(|b| b.status != "tombstone" && !workflow.is_done(&b.status))
+        .map(|b| b.id.as_str())
+        .collect();
+    let blocked: HashMap<&str, bool> = beads
+        .iter()
+        .map(|b| {
+            let waiting = b.dependencies.iter().any(|d| d.r#type == "blocks" && unfinished.contains(d.depends_on_id.as_str()));
+            (b.id.as_str(), waiting)
+        })
+        .collect();
+
+    let mut changes = Vec::new();
+    let mut updated = Vec::new();
+    for bead in beads.iter().filter(|b| unfinished.contains(b.id.as_str())) {
+        let mut current = bead.clone();
+        for rule in rules.iter().filter(|r| r.enabled) {
+            if !matches(&rule.when, &current, blocked[bead.id.as_str()], workflow, now) {
+                continue;
+            }
+            if let Some((field, old, new)) = apply(&rule.then, &mut current) {
+                changes.push(RuleChange { rule: rule.name.clone(), bead_id: bead.id.clone(), field: field.to_string(), old, new });
+            }
+        }
+        if changes.last().is_some_and(|c| c.bead_id == bead.id) {
+            updated.push(current);
+        }
+    }
+    (changes, updated)
+}
+
+/// Escalation rules of the current project
+#[tauri::command]
+pub fn get_rules(window: tauri::Window, project_handle: Option<String>) -> Result<Vec<Rule>, BertError> {
+    let beads_path = crate::project::context_for(&window, project_handle.as_deref())?.beads_path()?;
+    load_for_beads_file(&beads_path)
+}
+
+/// Replace the escalation rules of the current project
+#[tauri::command]
+pub fn save_rules(rules: Vec<Rule>, window: tauri::Window, project_handle: Option<String>) -> Result<(), BertError> {
+    let beads_path = crate::project::context_for(&window, project_handle.as_deref())?.beads_path()?;
+    save_for_beads_file(&rules, &beads_path)?;
+    eprintln!("📏 Saved {} rules to {}", rules.len(), config_path_for(&beads_path).display());
+    Ok(())
+}
+
+/// Evaluate the project's rules. A dry run only reports the changes; otherwise
+/// they are applied as one batch update and the run is audited.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn run_rules(dryRun: bool, window: tauri::Window, project_handle: Option<String>, app_handle: AppHandle) -> Result<RulesRun, BertError> {
+    let ctx = crate::project::context_for(&window, project_handle.as_deref())?;
+    let beads_path = ctx.beads_path()?;
+    let rules = load_for_beads_file(&beads_path)?;
+    let workflow = WorkflowConfig::load_for_beads_file(&beads_path)?;
+    let beads = crate::bd::load_beads(&ctx)?;
+
+    let (changes, updated) = evaluate(&beads, &rules, &workflow, Utc::now());
+    eprintln!("📏 run_rules: {} changes on {} beads{}", changes.len(), updated.len(), if dryRun { " (dry run)" } else { "" });
+    if dryRun || updated.is_empty() {
+        return Ok(RulesRun { dry_run: dryRun, changes, results: Vec::new() });
+    }
+
+    let ops: Vec<BeadOp> = updated.into_iter().map(|bead| BeadOp::Update { bead }).collect();
+    let results = crate::bd::apply_batch(&ctx, &app_handle, &ops);
+    crate::agent::safety::audit_app_action(
+        AUDIT_SOURCE,
+        "run_rules",
+        None,
+        serde_json::json!({ "project": ctx.project_path(), "changes": changes, "results": results }),
+    );
+    Ok(RulesRun { dry_run: false, changes, results })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn bead(id: &str, issue_type: &str, status: &str, priority: u32, created: &str, blockers: &[&str]) -> Bead {
+        let dependencies: Vec<_> = blockers
+            .iter()
+            .map(|on| json!({ "issue_id": id, "depends_on_id": on, "type": "blocks" }))
+            .collect();
+        crate::test_bead(json!({
+            "id": id,
+            "status": status,
+            "priority": priority,
+            "issue_type": issue_type,
+            "created_at": created,
+            "updated_at": created,
+            "dependencies": dependencies,
+        }))
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-03-30T10:00:00Z").unwrap().with_timezone(&Utc)
+    }
+
+    fn rules() -> Vec<Rule> {
+        serde_json::from_value(json!([
+            {
+                "name": "Age P2 bugs",
+                "when": { "issueType": "bug", "priority": 2, "olderThanDays": 14 },
+                "then": { "type": "escalate", "priority": 1 }
+            },
+            {
+                "name": "Stalled",
+                "when": { "blockedDays": 7 },
+                "then": { "type": "add-label", "label": "stalled" }
+            },
+            {
+                "name": "Disabled",
+                "enabled": false,
+                "then": { "type": "set-status", "status": "deferred" }
+            }
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_rules_escalate_and_label() {
+        let beads = vec![
+            bead("old-bug", "bug", "open", 2, "2026-03-01T10:00:00Z", &[]),
+            bead("new-bug", "bug", "open", 2, "2026-03-25T10:00:00Z", &[]),
+            bead("closed-bug", "bug", "closed", 2, "2026-03-01T10:00:00Z", &[]),
+            bead("waiting", "task", "open", 2, "2026-03-10T10:00:00Z", &["old-bug"]),
+            bead("blocked", "task", "blocked", 3, "2026-03-10T10:00:00Z", &[]),
+            bead("recent", "task", "open", 2, "2026-03-28T10:00:00Z", &["old-bug"]),
+        ];
+        let (changes, updated) = evaluate(&beads, &rules(), &WorkflowConfig::default(), now());
+
+        let summary: Vec<(&str, &str, &str, &str)> =
+            changes.iter().map(|c| (c.bead_id.as_str(), c.field.as_str(), c.old.as_str(), c.new.as_str())).collect();
+        assert_eq!(
+            summary,
+            vec![("old-bug", "priority", "2", "1"), ("waiting", "labels", "", "stalled"), ("blocked", "labels", "", "stalled")]
+        );
+        assert_eq!(updated.iter().map(|b| b.id.as_str()).collect::<Vec<_>>(), vec!["old-bug", "waiting", "blocked"]);
+        assert_eq!(updated[0].priority, 1);
+    }
+
+    #[test]
+    fn test_satisfied_rules_change_nothing() {
+        let mut labelled = bead("waiting", "task", "blocked", 2, "2026-03-10T10:00:00Z", &[]);
+        labelled.labels = Some(vec!["stalled".to_string()]);
+        let urgent = bead("urgent", "bug", "open", 0, "2026-03-01T10:00:00Z", &[]);
+        let escalate: Vec<Rule> = serde_json::from_value(json!([
+            { "name": "Age bugs", "when": { "issueType": "bug" }, "then": { "type": "escalate", "priority": 1 } }
+        ]))
+        .unwrap();
+
+        assert!(evaluate(&[labelled], &rules(), &WorkflowConfig::default(), now()).0.is_empty());
+        assert!(evaluate(&[urgent], &escalate, &WorkflowConfig::default(), now()).0.is_empty());
+    }
+
+    #[test]
+    fn test_rules_round_trip_and_validate() {
+        let dir = TempDir::new().unwrap();
+        let beads_path = dir.path().join("issues.jsonl");
+        assert!(load_for_beads_file(&beads_path).unwrap().is_empty());
+
+        save_for_beads_file(&rules(), &beads_path).unwrap();
+        assert_eq!(load_for_beads_file(&beads_path).unwrap(), rules());
+
+        let bad: Vec<Rule> = serde_json::from_value(json!([
+            { "name": "Too urgent", "then": { "type": "escalate", "priority": 7 } }
+        ]))
+        .unwrap();
+        assert!(matches!(save_for_beads_file(&bad, &beads_path), Err(BertError::InvalidInput(_))));
+    }
+}
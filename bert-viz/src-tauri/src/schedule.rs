@@ -0,0 +1,482 @@
+//! Calendar mapping for the Gantt axis
+//!
+//! The scheduler works in abstract cells. A per-project scheduling config in
+//! `.beads/bert-viz.json` (project start date, working days, hours per cell)
+//! turns those cells into real calendar dates so the UI can draw a timeline.
+//! The same file also holds the workflow config (see `workflow`).
+
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::BertError;
+use crate::{Bead, BeadNode};
+
+/// File name of the project-level config, stored next to issues.jsonl
+pub const CONFIG_FILE_NAME: &str = "bert-viz.json";
+
+/// Scheduling settings used to map Gantt cells to calendar dates
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulingConfig {
+    /// Project start date (YYYY-MM-DD). Defaults to the earliest bead creation date.
+    #[serde(default)]
+    pub start_date: Option<String>,
+    /// Working weekdays ("mon", "tue", ...)
+    #[serde(default = "default_working_days")]
+    pub working_days: Vec<String>,
+    /// Working hours represented by one Gantt cell
+    #[serde(default = "default_hours_per_cell")]
+    pub hours_per_cell: f64,
+    /// Working hours in one day
+    #[serde(default = "default_hours_per_day")]
+    pub hours_per_day: f64,
+    /// Tasks one owner can work on at the same time before the workload
+    /// report flags them as overallocated
+    #[serde(default = "default_owner_capacity")]
+    pub owner_capacity: usize,
+    /// Calendar days in one sprint; sprints run back to back from the start date
+    #[serde(default = "default_sprint_length_days")]
+    pub sprint_length_days: u32,
+}
+
+fn default_working_days() -> Vec<String> {
+    ["mon", "tue", "wed", "thu", "fri"].iter().map(|d| d.to_string()).collect()
+}
+
+fn default_hours_per_cell() -> f64 {
+    8.0
+}
+
+fn default_hours_per_day() -> f64 {
+    8.0
+}
+
+fn default_owner_capacity() -> usize {
+    1
+}
+
+fn default_sprint_length_days() -> u32 {
+    14
+}
+
+impl Default for SchedulingConfig {
+    fn default() -> Self {
+        SchedulingConfig {
+            start_date: None,
+            working_days: default_working_days(),
+            hours_per_cell: default_hours_per_cell(),
+            hours_per_day: default_hours_per_day(),
+            owner_capacity: default_owner_capacity(),
+            sprint_length_days: default_sprint_length_days(),
+        }
+    }
+}
+
+impl SchedulingConfig {
+    /// Load the config stored alongside `beads_path`.
+    /// Returns defaults if the file doesn't exist.
+    pub fn load_for_beads_file(beads_path: &Path) -> Result<Self, BertError> {
+        let path = config_path_for(beads_path);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(&path)
+            .map_err(|e| BertError::Io(format!("Failed to read {}: {}", path.display(), e)))?;
+        let config: SchedulingConfig = serde_json::from_str(&contents)
+            .map_err(|e| BertError::Parse(format!("Failed to parse {}: {}", path.display(), e)))?;
+        config.validate().map_err(BertError::InvalidInput)?;
+        Ok(config)
+    }
+
+    /// Save the config alongside `beads_path`
+    pub fn save_for_beads_file(&self, beads_path: &Path) -> Result<(), BertError> {
+        self.validate().map_err(BertError::InvalidInput)?;
+        let fields = match serde_json::to_value(self)? {
+            Value::Object(fields) => fields,
+            _ => return Err(BertError::Internal("Scheduling config did not serialize to an object".to_string())),
+        };
+        merge_config_fields(beads_path, fields)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if let Some(date) = &self.start_date {
+            NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|e| format!("Invalid startDate '{}': {}", date, e))?;
+        }
+        if self.hours_per_cell <= 0.0 || self.hours_per_day <= 0.0 {
+            return Err("hoursPerCell and hoursPerDay must be positive".to_string());
+        }
+        if self.owner_capacity == 0 {
+            return Err("ownerCapacity must be at least 1".to_string());
+        }
+        if self.sprint_length_days == 0 {
+            return Err("sprintLengthDays must be at least 1".to_string());
+        }
+        if self.working_days()?.is_empty() {
+            return Err("At least one working day is required".to_string());
+        }
+        Ok(())
+    }
+
+    fn working_days(&self) -> Result<Vec<Weekday>, String> {
+        self.working_days
+            .iter()
+            .map(|d| d.parse::<Weekday>().map_err(|_| format!("Invalid working day '{}'", d)))
+            .collect()
+    }
+}
+
+/// Path of the scheduling config for the project owning `beads_path`
+pub fn config_path_for(beads_path: &Path) -> PathBuf {
+    beads_path
+        .parent()
+        .map(|dir| dir.join(CONFIG_FILE_NAME))
+        .unwrap_or_else(|| PathBuf::from(CONFIG_FILE_NAME))
+}
+
+/// Read the `key` section of the project config file next to `beads_path`.
+/// `None` if the file or the section doesn't exist.
+pub(crate) fn load_config_section<T: DeserializeOwned>(beads_path: &Path, key: &str) -> Result<Option<T>, BertError> {
+    let path = config_path_for(beads_path);
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| BertError::Io(format!("Failed to read {}: {}", path.display(), e)))?;
+    let mut root: Map<String, Value> = serde_json::from_str(&contents)
+        .map_err(|e| BertError::Parse(format!("Failed to parse {}: {}", path.display(), e)))?;
+    match root.remove(key) {
+        Some(section) => serde_json::from_value(section)
+            .map(Some)
+            .map_err(|e| BertError::Parse(format!("Invalid {} in {}: {}", key, path.display(), e))),
+        None => Ok(None),
+    }
+}
+
+/// Write `fields` into the project config file, keeping keys owned by other
+/// settings (the file holds both scheduling and workflow config). Writers
+/// include background threads like the recurrence scheduler, so the merge runs
+/// under a lock on `<bert-viz.json>.lock` and replaces the file via rename.
+pub(crate) fn merge_config_fields(beads_path: &Path, fields: Map<String, Value>) -> Result<(), BertError> {
+    crate::viewer::ensure_writable(beads_path)?;
+    let path = config_path_for(beads_path);
+    let _lock = crate::bd::lock_file(&path.with_extension("json.lock"))?;
+    let mut root: Map<String, Value> = if path.exists() {
+        let contents = fs::read_to_string(&path)
+            .map_err(|e| BertError::Io(format!("Failed to read {}: {}", path.display(), e)))?;
+        serde_json::from_str(&contents)
+            .map_err(|e| BertError::Parse(format!("Failed to parse {}: {}", path.display(), e)))?
+    } else {
+        Map::new()
+    };
+    root.extend(fields);
+
+    let json = serde_json::to_string_pretty(&root)?;
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, json).map_err(|e| BertError::Io(format!("Failed to write {}: {}", tmp_path.display(), e)))?;
+    fs::rename(&tmp_path, &path).map_err(|e| BertError::Io(format!("Failed to replace {}: {}", path.display(), e)))
+}
+
+/// One labeled tick on the timeline axis
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AxisTick {
+    /// Cell index the tick sits at
+    pub cell: usize,
+    /// ISO date (YYYY-MM-DD) of the cell start
+    pub date: String,
+    /// Short display label, e.g. "Mon 12 Oct"
+    pub label: String,
+    /// True for the first tick of a calendar month
+    pub is_month_start: bool,
+}
+
+/// Date-labeled axis model for the Gantt chart
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineAxis {
+    pub start_date: String,
+    pub end_date: String,
+    pub hours_per_cell: f64,
+    pub total_cells: usize,
+    pub ticks: Vec<AxisTick>,
+}
+
+/// Maps cell offsets to working-calendar dates
+pub struct CalendarMapper {
+    start: NaiveDate,
+    working_days: Vec<Weekday>,
+    hours_per_cell: f64,
+    hours_per_day: f64,
+    sprint_length_days: i64,
+}
+
+impl CalendarMapper {
+    /// Build a mapper from config. When no start date is configured, the
+    /// earliest `created_at` among `beads` is used (today if none parse).
+    pub fn new(config: &SchedulingConfig, beads: &[Bead]) -> Self {
+        let configured = config
+            .start_date
+            .as_deref()
+            .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok());
+        let start = configured
+            .or_else(|| earliest_created_date(beads))
+            .unwrap_or_else(|| chrono::Utc::now().date_naive());
+
+        let mut working_days = config.working_days().unwrap_or_default();
+        if working_days.is_empty() {
+            working_days = SchedulingConfig::default().working_days().unwrap_or_default();
+        }
+        working_days.sort_by_key(|d| d.num_days_from_monday());
+        working_days.dedup();
+
+        CalendarMapper {
+            start,
+            working_days,
+            hours_per_cell: config.hours_per_cell.max(f64::EPSILON),
+            hours_per_day: config.hours_per_day.max(f64::EPSILON),
+            sprint_length_days: config.sprint_length_days.max(1) as i64,
+        }
+    }
+
+    pub fn sprint_length_days(&self) -> i64 {
+        self.sprint_length_days
+    }
+
+    /// 1-based number of the sprint containing `date` (0 or less before the start date)
+    pub fn sprint_number(&self, date: NaiveDate) -> i64 {
+        (date - self.start).num_days().div_euclid(self.sprint_length_days) + 1
+    }
+
+    /// First date of the sprint containing `date`
+    pub fn sprint_start(&self, date: NaiveDate) -> NaiveDate {
+        let offset = (self.sprint_number(date) - 1) * self.sprint_length_days;
+        self.start + chrono::Duration::days(offset)
+    }
+
+    /// Date on which `cell` starts
+    pub fn cell_start(&self, cell: usize) -> NaiveDate {
+        let hours = cell as f64 * self.hours_per_cell;
+        self.nth_working_day((hours / self.hours_per_day).floor() as usize)
+    }
+
+    /// Last working date covered by a task spanning `count` cells from `offset`
+    pub fn cell_end(&self, offset: usize, count: usize) -> NaiveDate {
+        let end_hours = (offset + count.max(1)) as f64 * self.hours_per_cell;
+        // Subtract a sliver so a task ending exactly at a day boundary stays on that day
+        let day = ((end_hours - 1e-9) / self.hours_per_day).floor().max(0.0) as usize;
+        self.nth_working_day(day)
+    }
+
+    /// The `n`th working day on or after the start date (0-based)
+    fn nth_working_day(&self, n: usize) -> NaiveDate {
+        // Every week after a working day holds each working day once
+        let per_week = self.working_days.len();
+        let mut date = self.first_working_day() + chrono::Duration::weeks((n / per_week) as i64);
+        let mut remaining = n % per_week;
+        while remaining > 0 {
+            date = date.succ_opt().unwrap_or(date);
+            if self.working_days.contains(&date.weekday()) {
+                remaining -= 1;
+            }
+        }
+        date
+    }
+
+    fn first_working_day(&self) -> NaiveDate {
+        let mut date = self.start;
+        for _ in 0..7 {
+            if self.working_days.contains(&date.weekday()) {
+                return date;
+            }
+            date = date.succ_opt().unwrap_or(date);
+        }
+        self.start
+    }
+
+    /// Build the axis model covering `total_cells` cells
+    pub fn axis(&self, total_cells: usize) -> TimelineAxis {
+        let mut ticks = Vec::with_capacity(total_cells + 1);
+        let mut last_month: Option<(i32, u32)> = None;
+        for cell in 0..=total_cells {
+            let date = self.cell_start(cell);
+            let month = (date.year(), date.month());
+            ticks.push(AxisTick {
+                cell,
+                date: date.format("%Y-%m-%d").to_string(),
+                label: date.format("%a %-d %b").to_string(),
+                is_month_start: last_month != Some(month),
+            });
+            last_month = Some(month);
+        }
+
+        TimelineAxis {
+            start_date: self.cell_start(0).format("%Y-%m-%d").to_string(),
+            end_date: self.cell_end(0, total_cells).format("%Y-%m-%d").to_string(),
+            hours_per_cell: self.hours_per_cell,
+            total_cells,
+            ticks,
+        }
+    }
+}
+
+fn earliest_created_date(beads: &[Bead]) -> Option<NaiveDate> {
+    beads
+        .iter()
+        .filter_map(|b| b.created_at.as_deref())
+        .filter_map(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.date_naive())
+        .min()
+}
+
+/// Fill `start_date`/`end_date` on every node and return the total cell span
+pub fn annotate_dates(nodes: &mut [BeadNode], mapper: &CalendarMapper) -> usize {
+    let mut total_cells = 0;
+    for node in nodes {
+        node.start_date = Some(mapper.cell_start(node.cell_offset).format("%Y-%m-%d").to_string());
+        node.end_date = Some(
+            mapper
+                .cell_end(node.cell_offset, node.cell_count)
+                .format("%Y-%m-%d")
+                .to_string(),
+        );
+        total_cells = total_cells.max(node.cell_offset + node.cell_count);
+        total_cells = total_cells.max(annotate_dates(&mut node.children, mapper));
+    }
+    total_cells
+}
+
+/// Get the scheduling config for the current project
+#[tauri::command]
+pub fn get_scheduling_config(window: tauri::Window, project_handle: Option<String>) -> Result<SchedulingConfig, BertError> {
+    let beads_path = crate::project::context_for(&window, project_handle.as_deref())?.beads_path()?;
+    SchedulingConfig::load_for_beads_file(&beads_path)
+}
+
+/// Save the scheduling config for the current project
+#[tauri::command]
+pub fn save_scheduling_config(
+    config: SchedulingConfig,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    project_state: tauri::State<'_, crate::view_cache::ProjectState>,
+) -> Result<(), BertError> {
+    let beads_path = crate::project::context_for(&window, project_handle.as_deref())?.beads_path()?;
+    config.save_for_beads_file(&beads_path)?;
+    project_state.invalidate(&crate::project::project_key(&window, project_handle.as_deref()));
+    eprintln!("📅 Saved scheduling config to {}", config_path_for(&beads_path).display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn mapper(start: &str, hours_per_cell: f64) -> CalendarMapper {
+        let config = SchedulingConfig {
+            start_date: Some(start.to_string()),
+            hours_per_cell,
+            ..SchedulingConfig::default()
+        };
+        CalendarMapper::new(&config, &[])
+    }
+
+    #[test]
+    fn test_cells_skip_weekends() {
+        // 2026-10-16 is a Friday
+        let m = mapper("2026-10-16", 8.0);
+        assert_eq!(m.cell_start(0).to_string(), "2026-10-16");
+        assert_eq!(m.cell_start(1).to_string(), "2026-10-19");
+        assert_eq!(m.cell_end(0, 2).to_string(), "2026-10-19");
+    }
+
+    #[test]
+    fn test_weekend_start_rolls_forward() {
+        let m = mapper("2026-10-17", 8.0);
+        assert_eq!(m.cell_start(0).to_string(), "2026-10-19");
+    }
+
+    #[test]
+    fn test_half_day_cells() {
+        let m = mapper("2026-10-19", 4.0);
+        assert_eq!(m.cell_start(1).to_string(), "2026-10-19");
+        assert_eq!(m.cell_start(2).to_string(), "2026-10-20");
+        assert_eq!(m.cell_end(0, 2).to_string(), "2026-10-19");
+    }
+
+    #[test]
+    fn test_sprints_count_from_start_date() {
+        let m = mapper("2026-10-05", 8.0);
+        let date = |d: &str| NaiveDate::parse_from_str(d, "%Y-%m-%d").unwrap();
+        assert_eq!(m.sprint_number(date("2026-10-05")), 1);
+        assert_eq!(m.sprint_number(date("2026-10-18")), 1);
+        assert_eq!(m.sprint_number(date("2026-10-19")), 2);
+        assert_eq!(m.sprint_start(date("2026-10-25")).to_string(), "2026-10-19");
+        assert_eq!(m.sprint_number(date("2026-10-04")), 0);
+        assert_eq!(m.sprint_start(date("2026-10-04")).to_string(), "2026-09-21");
+    }
+
+    #[test]
+    fn test_axis_marks_month_start() {
+        let m = mapper("2026-10-30", 8.0);
+        let axis = m.axis(2);
+        assert_eq!(axis.ticks.len(), 3);
+        assert!(axis.ticks[0].is_month_start);
+        assert!(axis.ticks[1].is_month_start); // Mon 2 Nov
+        assert!(!axis.ticks[2].is_month_start);
+    }
+
+    #[test]
+    fn test_config_roundtrip_and_validation() {
+        let dir = TempDir::new().unwrap();
+        let beads_path = dir.path().join("issues.jsonl");
+
+        assert_eq!(SchedulingConfig::load_for_beads_file(&beads_path).unwrap(), SchedulingConfig::default());
+
+        let config = SchedulingConfig {
+            start_date: Some("2026-01-05".to_string()),
+            working_days: vec!["mon".into(), "wed".into()],
+            ..SchedulingConfig::default()
+        };
+        config.save_for_beads_file(&beads_path).unwrap();
+        assert_eq!(SchedulingConfig::load_for_beads_file(&beads_path).unwrap(), config);
+
+        let bad = SchedulingConfig {
+            working_days: vec!["funday".into()],
+            ..SchedulingConfig::default()
+        };
+        assert!(bad.save_for_beads_file(&beads_path).is_err());
+    }
+
+    #[test]
+    fn test_concurrent_merges_keep_every_key() {
+        let dir = TempDir::new().unwrap();
+        let beads_path = dir.path().join("issues.jsonl");
+
+        let writers: Vec<_> = (0..8)
+            .map(|i| {
+                let beads_path = beads_path.clone();
+                std::thread::spawn(move || {
+                    let mut fields = Map::new();
+                    fields.insert(format!("key{}", i), Value::from(i));
+                    merge_config_fields(&beads_path, fields).unwrap();
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let contents = fs::read_to_string(config_path_for(&beads_path)).unwrap();
+        let root: Map<String, Value> = serde_json::from_str(&contents).unwrap();
+        assert_eq!(root.len(), 8);
+        assert!(!config_path_for(&beads_path).with_extension("json.tmp").exists());
+    }
+}
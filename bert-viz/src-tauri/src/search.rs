@@ -0,0 +1,289 @@
+//! Search query parsing and matching
+//!
+//! `FilterParams::filter_text` is parsed into a `SearchQuery` supporting:
+//!
+//! - free text: `login bug` (every word must appear somewhere)
+//! - exact phrases: `"session timeout"`
+//! - field qualifiers: `status:open`, `owner:alice`, `label:backend`,
+//!   `type:bug`, `id:bp6-12`, `priority:<=1` (also `<`, `>`, `>=`, `=`)
+//! - comma lists within a qualifier are ORed: `status:open,in_progress`
+//! - a leading `-` negates a term: `-label:wontfix`
+//!
+//! Terms are ANDed. Free text matches id, title, owner, labels, description,
+//! design, notes and acceptance criteria, case-insensitively.
+
+use crate::Bead;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparison {
+    Lt,
+    Le,
+    Eq,
+    Ge,
+    Gt,
+}
+
+impl Comparison {
+    fn test(self, actual: u32, expected: u32) -> bool {
+        match self {
+            Comparison::Lt => actual < expected,
+            Comparison::Le => actual <= expected,
+            Comparison::Eq => actual == expected,
+            Comparison::Ge => actual >= expected,
+            Comparison::Gt => actual > expected,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TermKind {
+    /// Word matched as a substring of any text field
+    Text(String),
+    /// Quoted phrase matched as a substring of any text field
+    Phrase(String),
+    Status(Vec<String>),
+    Owner(Vec<String>),
+    Label(Vec<String>),
+    Type(Vec<String>),
+    Id(Vec<String>),
+    Priority(Comparison, u32),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Term {
+    pub negated: bool,
+    pub kind: TermKind,
+}
+
+/// A parsed search query. An empty query matches everything.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SearchQuery {
+    pub terms: Vec<Term>,
+}
+
+/// Split on whitespace, keeping double-quoted sections (including any
+/// `field:"quoted value"`) together. Quotes are kept so the parser can tell
+/// phrases from words.
+pub(crate) fn tokenize(input: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in input.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                current.push(c);
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+pub(crate) fn unquote(s: &str) -> String {
+    s.trim_matches('"').to_string()
+}
+
+fn value_list(raw: &str) -> Vec<String> {
+    unquote(raw)
+        .split(',')
+        .map(|v| v.trim().to_lowercase())
+        .filter(|v| !v.is_empty())
+        .collect()
+}
+
+fn parse_priority(raw: &str) -> Option<(Comparison, u32)> {
+    let (cmp, rest) = if let Some(r) = raw.strip_prefix("<=") {
+        (Comparison::Le, r)
+    } else if let Some(r) = raw.strip_prefix(">=") {
+        (Comparison::Ge, r)
+    } else if let Some(r) = raw.strip_prefix('<') {
+        (Comparison::Lt, r)
+    } else if let Some(r) = raw.strip_prefix('>') {
+        (Comparison::Gt, r)
+    } else if let Some(r) = raw.strip_prefix('=') {
+        (Comparison::Eq, r)
+    } else {
+        (Comparison::Eq, raw)
+    };
+    let value = rest.trim().trim_start_matches(['p', 'P']).parse().ok()?;
+    Some((cmp, value))
+}
+
+fn parse_qualified(field: &str, value: &str) -> Option<TermKind> {
+    if value.is_empty() {
+        return None;
+    }
+    let kind = match field.to_lowercase().as_str() {
+        "status" | "is" => TermKind::Status(value_list(value)),
+        "owner" | "assignee" => TermKind::Owner(value_list(value)),
+        "label" | "labels" => TermKind::Label(value_list(value)),
+        "type" => TermKind::Type(value_list(value)),
+        "id" => TermKind::Id(value_list(value)),
+        "priority" | "p" => {
+            let (cmp, p) = parse_priority(value)?;
+            TermKind::Priority(cmp, p)
+        }
+        _ => return None,
+    };
+    Some(kind)
+}
+
+impl SearchQuery {
+    /// Parse a query string. Unknown qualifiers and malformed values fall back
+    /// to free-text matching so a query never fails to parse.
+    pub fn parse(input: &str) -> Self {
+        let mut terms = Vec::new();
+
+        for token in tokenize(input) {
+            let (negated, body) = match token.strip_prefix('-') {
+                Some(rest) if !rest.is_empty() => (true, rest),
+                _ => (false, token.as_str()),
+            };
+
+            if body.starts_with('"') {
+                let phrase = unquote(body).to_lowercase();
+                if !phrase.is_empty() {
+                    terms.push(Term { negated, kind: TermKind::Phrase(phrase) });
+                }
+                continue;
+            }
+
+            let qualified = body
+                .split_once(':')
+                .and_then(|(field, value)| parse_qualified(field, value));
+            let kind = qualified.unwrap_or_else(|| TermKind::Text(body.to_lowercase()));
+            terms.push(Term { negated, kind });
+        }
+
+        SearchQuery { terms }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// True if `bead` satisfies every term
+    pub fn matches(&self, bead: &Bead) -> bool {
+        self.terms.iter().all(|term| term_matches(&term.kind, bead) != term.negated)
+    }
+}
+
+fn text_fields(bead: &Bead) -> impl Iterator<Item = &str> {
+    let singles = [
+        Some(bead.id.as_str()),
+        Some(bead.title.as_str()),
+        bead.owner.as_deref(),
+        bead.description.as_deref(),
+        bead.design.as_deref(),
+        bead.notes.as_deref(),
+    ];
+    singles
+        .into_iter()
+        .flatten()
+        .chain(bead.labels.iter().flatten().map(|s| s.as_str()))
+        .chain(bead.acceptance_criteria.iter().flatten().map(|s| s.as_str()))
+}
+
+fn any_text_contains(bead: &Bead, needle: &str) -> bool {
+    text_fields(bead).any(|field| field.to_lowercase().contains(needle))
+}
+
+fn term_matches(kind: &TermKind, bead: &Bead) -> bool {
+    match kind {
+        TermKind::Text(word) | TermKind::Phrase(word) => any_text_contains(bead, word),
+        TermKind::Status(values) => values.iter().any(|v| bead.status.eq_ignore_ascii_case(v)),
+        TermKind::Type(values) => values.iter().any(|v| bead.issue_type.eq_ignore_ascii_case(v)),
+        TermKind::Id(values) => values.iter().any(|v| bead.id.to_lowercase().starts_with(v)),
+        TermKind::Owner(values) => bead
+            .owner
+            .as_deref()
+            .map(|o| values.iter().any(|v| o.to_lowercase().contains(v)))
+            .unwrap_or(false),
+        TermKind::Label(values) => bead
+            .labels
+            .as_ref()
+            .map(|labels| values.iter().any(|v| labels.iter().any(|l| l.eq_ignore_ascii_case(v))))
+            .unwrap_or(false),
+        TermKind::Priority(cmp, expected) => cmp.test(bead.priority, *expected),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bead() -> Bead {
+        crate::test_bead(serde_json::json!({
+            "id": "bp6-42",
+            "title": "Fix login redirect",
+            "priority": 1,
+            "issue_type": "bug",
+            "owner": "alice@example.com",
+            "labels": ["backend", "auth"],
+            "description": "Users hit a session timeout after SSO",
+            "notes": "Repro on staging",
+        }))
+    }
+
+    fn matches(q: &str) -> bool {
+        SearchQuery::parse(q).matches(&bead())
+    }
+
+    #[test]
+    fn test_tokenize_keeps_quoted_phrases() {
+        assert_eq!(tokenize(r#"a "b c" owner:"d e""#), vec!["a", "\"b c\"", "owner:\"d e\""]);
+    }
+
+    #[test]
+    fn test_parse_qualifiers() {
+        let q = SearchQuery::parse("status:open,in_progress priority:<=1 -label:wontfix login");
+        assert_eq!(q.terms.len(), 4);
+        assert_eq!(q.terms[0].kind, TermKind::Status(vec!["open".into(), "in_progress".into()]));
+        assert_eq!(q.terms[1].kind, TermKind::Priority(Comparison::Le, 1));
+        assert!(q.terms[2].negated);
+        assert_eq!(q.terms[3].kind, TermKind::Text("login".into()));
+    }
+
+    #[test]
+    fn test_unknown_qualifier_is_free_text() {
+        let q = SearchQuery::parse("foo:bar");
+        assert_eq!(q.terms[0].kind, TermKind::Text("foo:bar".into()));
+    }
+
+    #[test]
+    fn test_empty_query_matches_everything() {
+        assert!(SearchQuery::parse("   ").is_empty());
+        assert!(matches(""));
+    }
+
+    #[test]
+    fn test_field_matching() {
+        assert!(matches("status:open owner:alice label:backend"));
+        assert!(matches("type:bug id:bp6-4"));
+        assert!(matches("priority:<=1"));
+        assert!(matches("priority:p1"));
+        assert!(!matches("priority:>1"));
+        assert!(!matches("status:closed"));
+        assert!(matches("status:closed,open"));
+        assert!(!matches("label:back")); // labels match exactly
+    }
+
+    #[test]
+    fn test_free_text_searches_long_fields() {
+        assert!(matches("\"session timeout\""));
+        assert!(matches("staging"));
+        assert!(matches("LOGIN redirect"));
+        assert!(!matches("\"timeout session\""));
+        assert!(!matches("login -staging"));
+    }
+}
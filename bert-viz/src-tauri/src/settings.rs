@@ -1,8 +1,10 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
-use tauri::State;
+use tauri::{AppHandle, Emitter, State};
+use crate::agent::backends::ollama;
 use crate::agent::plugin::BackendId;
+use crate::error::BertError;
 use crate::SettingsState;
 
 /// Application settings structure
@@ -11,12 +13,225 @@ use crate::SettingsState;
 pub struct AppSettings {
     #[serde(rename = "cliBackend")]
     pub cli_backend: BackendId,
+    /// Base URL of the Ollama server used by the Ollama backend
+    #[serde(rename = "ollamaHost", default = "default_ollama_host")]
+    pub ollama_host: String,
+    /// Model the Ollama backend chats with
+    #[serde(rename = "ollamaModel", default = "default_ollama_model")]
+    pub ollama_model: String,
+    /// Append agent summaries to the notes of the bead a session works on
+    #[serde(rename = "autoCaptureSummary", default = "default_auto_capture_summary")]
+    pub auto_capture_summary: bool,
+    /// Which events raise desktop notifications
+    #[serde(default)]
+    pub notifications: NotificationSettings,
+    /// What agent CLIs may do without asking
+    #[serde(rename = "agentSafetyPolicy", default)]
+    pub agent_safety_policy: AgentSafetyPolicy,
+    /// Theme, density and formats, shared by all windows
+    #[serde(default)]
+    pub display: DisplayPrefs,
+    /// Local HTTP API for dashboards and scripts (`rest-api` builds only)
+    #[serde(rename = "restApi", default)]
+    pub rest_api: RestApiSettings,
+    /// Who beads are assigned to when the user starts work on them; the git
+    /// user of the project when unset
+    #[serde(rename = "ownerName", default)]
+    pub owner_name: Option<String>,
+    /// Agent CLI runs at a time; new sessions beyond it wait in the session
+    /// queue. Unlimited when unset.
+    #[serde(rename = "maxConcurrentSessions", default)]
+    pub max_concurrent_sessions: Option<usize>,
+    /// Retries and fallback backend for agent CLIs that fail to start
+    #[serde(rename = "agentFailover", default)]
+    pub agent_failover: FailoverPolicy,
+}
+
+/// Permissions agent CLIs run with
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum AgentSafetyPolicy {
+    /// Every tool runs without asking (`--yolo` / `--dangerously-skip-permissions`)
+    #[default]
+    FullAuto,
+    /// Tools that change files or run commands are held for the user's approval
+    ApproveWrites,
+    /// Agents can only read the repo
+    ReadOnly,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum Theme {
+    /// Follow the OS setting
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum Density {
+    #[default]
+    Comfortable,
+    Compact,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum DateFormat {
+    /// 2026-03-31
+    #[default]
+    Iso,
+    /// 03/31/2026
+    Us,
+    /// 31/03/2026
+    Eu,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum FirstDayOfWeek {
+    Saturday,
+    Sunday,
+    #[default]
+    Monday,
+}
+
+/// How the UI looks, independent of the open project
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DisplayPrefs {
+    pub theme: Theme,
+    pub density: Density,
+    /// Zoom of a window without saved startup state
+    pub default_zoom: f64,
+    pub date_format: DateFormat,
+    /// First column of calendar pickers and week headers
+    pub first_day_of_week: FirstDayOfWeek,
+}
+
+impl Default for DisplayPrefs {
+    fn default() -> Self {
+        DisplayPrefs {
+            theme: Theme::default(),
+            density: Density::default(),
+            default_zoom: 1.0,
+            date_format: DateFormat::default(),
+            first_day_of_week: FirstDayOfWeek::default(),
+        }
+    }
+}
+
+/// Zoom levels a default zoom may take
+const DEFAULT_ZOOM_RANGE: std::ops::RangeInclusive<f64> = 0.1..=10.0;
+
+/// Payload of `settings-updated`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsUpdated {
+    pub display: DisplayPrefs,
+}
+
+/// Per-event toggles for desktop notifications
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NotificationSettings {
+    /// A bead assigned to the local user had its last blocker closed
+    pub bead_unblocked: bool,
+    /// An agent session finished a run
+    pub session_finished: bool,
+    /// An agent CLI exited unexpectedly
+    pub session_errored: bool,
+    /// The critical path through the open beads changed
+    pub critical_path_changed: bool,
+    /// A long-running `bd` command failed
+    pub bd_command_failed: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        NotificationSettings {
+            bead_unblocked: true,
+            session_finished: true,
+            session_errored: true,
+            critical_path_changed: true,
+            bd_command_failed: true,
+        }
+    }
+}
+
+/// What happens when an agent CLI fails to start or exits right away
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FailoverPolicy {
+    /// Retries of the same backend after a rate limit or network error
+    pub retries: u32,
+    /// Wait before the first retry; it doubles with every further retry
+    pub backoff_ms: u64,
+    /// How long a new CLI is watched for an early exit; 0 turns the check off
+    pub startup_window_ms: u64,
+    /// Backend tried once the chosen one can't start, e.g. after an auth error
+    pub fallback_backend: Option<BackendId>,
+}
+
+impl Default for FailoverPolicy {
+    fn default() -> Self {
+        FailoverPolicy { retries: 2, backoff_ms: 1000, startup_window_ms: 1000, fallback_backend: None }
+    }
+}
+
+/// Most retries and longest waits a failover policy may ask for
+const MAX_FAILOVER_RETRIES: u32 = 10;
+const MAX_FAILOVER_WAIT_MS: u64 = 60_000;
+
+/// Port the local REST API listens on unless configured otherwise
+pub const DEFAULT_REST_API_PORT: u16 = 7878;
+
+/// Local REST API server. It only listens on 127.0.0.1 and every request must
+/// carry `Authorization: Bearer <token>`. Enabling it or changing the port
+/// takes effect on the next start; a new token applies at once.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RestApiSettings {
+    pub enabled: bool,
+    pub port: u16,
+    pub token: String,
+}
+
+impl Default for RestApiSettings {
+    fn default() -> Self {
+        RestApiSettings { enabled: false, port: DEFAULT_REST_API_PORT, token: String::new() }
+    }
+}
+
+fn default_ollama_host() -> String {
+    ollama::DEFAULT_HOST.to_string()
+}
+
+fn default_ollama_model() -> String {
+    ollama::DEFAULT_MODEL.to_string()
+}
+
+fn default_auto_capture_summary() -> bool {
+    true
 }
 
 impl Default for AppSettings {
     fn default() -> Self {
         AppSettings {
             cli_backend: BackendId::Gemini,
+            ollama_host: default_ollama_host(),
+            ollama_model: default_ollama_model(),
+            auto_capture_summary: default_auto_capture_summary(),
+            notifications: NotificationSettings::default(),
+            agent_safety_policy: AgentSafetyPolicy::default(),
+            display: DisplayPrefs::default(),
+            rest_api: RestApiSettings::default(),
+            owner_name: None,
+            max_concurrent_sessions: None,
+            agent_failover: FailoverPolicy::default(),
         }
     }
 }
@@ -101,6 +316,7 @@ pub fn get_cli_preference(settings_state: State<'_, SettingsState>) -> Result<St
     let cli_str = match settings.cli_backend {
         BackendId::Gemini => "gemini",
         BackendId::ClaudeCode => "claude",
+        BackendId::Ollama => "ollama",
     };
 
     Ok(cli_str.to_string())
@@ -117,7 +333,8 @@ pub fn set_cli_preference(
     let backend = match cliBackend.to_lowercase().as_str() {
         "gemini" => BackendId::Gemini,
         "claude" | "claude-code" => BackendId::ClaudeCode,
-        _ => return Err(format!("Invalid CLI backend: '{}'. Valid options are: 'gemini', 'claude', 'claude-code'", cliBackend)),
+        "ollama" => BackendId::Ollama,
+        _ => return Err(format!("Invalid CLI backend: '{}'. Valid options are: 'gemini', 'claude', 'claude-code', 'ollama'", cliBackend)),
     };
 
     // Update settings in state
@@ -134,6 +351,292 @@ pub fn set_cli_preference(
     Ok(())
 }
 
+/// Ollama server and model used by the Ollama backend
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OllamaConfig {
+    pub host: String,
+    pub model: String,
+}
+
+/// Tauri command to get the Ollama server and model
+#[tauri::command]
+pub fn get_ollama_config(settings_state: State<'_, SettingsState>) -> Result<OllamaConfig, String> {
+    let settings = settings_state.settings.lock()
+        .map_err(|e| format!("Failed to acquire settings lock: {}", e))?;
+
+    Ok(OllamaConfig {
+        host: settings.ollama_host.clone(),
+        model: settings.ollama_model.clone(),
+    })
+}
+
+/// Tauri command to set the Ollama server and model and persist to disk
+#[tauri::command]
+pub fn set_ollama_config(
+    config: OllamaConfig,
+    settings_state: State<'_, SettingsState>
+) -> Result<(), String> {
+    let host = config.host.trim();
+    if !host.starts_with("http://") && !host.starts_with("https://") {
+        return Err(format!("Invalid Ollama host: '{}'. Expected a URL like {}", host, ollama::DEFAULT_HOST));
+    }
+    if config.model.trim().is_empty() {
+        return Err("Ollama model cannot be empty".to_string());
+    }
+
+    let mut settings = settings_state.settings.lock()
+        .map_err(|e| format!("Failed to acquire settings lock: {}", e))?;
+
+    settings.ollama_host = host.to_string();
+    settings.ollama_model = config.model.trim().to_string();
+
+    let config_path = get_config_path()?;
+    settings.save_to_file(&config_path)?;
+
+    eprintln!("✅ Updated Ollama config to: {} ({})", settings.ollama_model, settings.ollama_host);
+    Ok(())
+}
+
+/// Tauri command to get whether agent summaries are captured into bead notes
+#[tauri::command]
+pub fn get_auto_capture_summary(settings_state: State<'_, SettingsState>) -> Result<bool, String> {
+    let settings = settings_state.settings.lock()
+        .map_err(|e| format!("Failed to acquire settings lock: {}", e))?;
+    Ok(settings.auto_capture_summary)
+}
+
+/// Tauri command to toggle capturing agent summaries into bead notes and persist to disk
+#[tauri::command]
+pub fn set_auto_capture_summary(
+    enabled: bool,
+    settings_state: State<'_, SettingsState>
+) -> Result<(), String> {
+    let mut settings = settings_state.settings.lock()
+        .map_err(|e| format!("Failed to acquire settings lock: {}", e))?;
+
+    settings.auto_capture_summary = enabled;
+
+    let config_path = get_config_path()?;
+    settings.save_to_file(&config_path)?;
+
+    eprintln!("✅ Updated summary auto-capture to: {}", enabled);
+    Ok(())
+}
+
+/// Tauri command to get the name beads are assigned to when work starts on them
+#[tauri::command]
+pub fn get_owner_name(settings_state: State<'_, SettingsState>) -> Result<Option<String>, String> {
+    let settings = settings_state.settings.lock()
+        .map_err(|e| format!("Failed to acquire settings lock: {}", e))?;
+    Ok(settings.owner_name.clone())
+}
+
+/// Tauri command to set the name beads are assigned to (None for the git user) and persist to disk
+#[tauri::command]
+pub fn set_owner_name(
+    owner_name: Option<String>,
+    settings_state: State<'_, SettingsState>
+) -> Result<(), String> {
+    let mut settings = settings_state.settings.lock()
+        .map_err(|e| format!("Failed to acquire settings lock: {}", e))?;
+
+    settings.owner_name = owner_name.map(|name| name.trim().to_string()).filter(|name| !name.is_empty());
+
+    let config_path = get_config_path()?;
+    settings.save_to_file(&config_path)?;
+
+    eprintln!("✅ Updated owner name to: {:?}", settings.owner_name);
+    Ok(())
+}
+
+/// Tauri command to get how many agent sessions may run at once (None for no limit)
+#[tauri::command]
+pub fn get_max_concurrent_sessions(settings_state: State<'_, SettingsState>) -> Result<Option<usize>, String> {
+    let settings = settings_state.settings.lock()
+        .map_err(|e| format!("Failed to acquire settings lock: {}", e))?;
+    Ok(settings.max_concurrent_sessions)
+}
+
+/// Tauri command to set how many agent sessions may run at once and persist to disk.
+/// Queued sessions start on their own once the limit allows.
+#[tauri::command]
+pub fn set_max_concurrent_sessions(
+    limit: Option<usize>,
+    settings_state: State<'_, SettingsState>
+) -> Result<(), String> {
+    if limit == Some(0) {
+        return Err("The session limit must be at least 1".to_string());
+    }
+    let mut settings = settings_state.settings.lock()
+        .map_err(|e| format!("Failed to acquire settings lock: {}", e))?;
+
+    settings.max_concurrent_sessions = limit;
+
+    let config_path = get_config_path()?;
+    settings.save_to_file(&config_path)?;
+
+    eprintln!("✅ Updated max concurrent sessions to: {:?}", limit);
+    Ok(())
+}
+
+/// Tauri command to get the retry and failover policy for agent CLIs
+#[tauri::command]
+pub fn get_agent_failover(settings_state: State<'_, SettingsState>) -> Result<FailoverPolicy, BertError> {
+    let settings = settings_state.settings.lock()
+        .map_err(|e| BertError::Internal(format!("Failed to acquire settings lock: {}", e)))?;
+    Ok(settings.agent_failover.clone())
+}
+
+/// Tauri command to set the retry and failover policy for agent CLIs and persist to disk
+#[tauri::command]
+pub fn set_agent_failover(
+    policy: FailoverPolicy,
+    settings_state: State<'_, SettingsState>
+) -> Result<(), BertError> {
+    if policy.retries > MAX_FAILOVER_RETRIES {
+        return Err(BertError::InvalidInput(format!("At most {} retries are allowed", MAX_FAILOVER_RETRIES)));
+    }
+    if policy.backoff_ms > MAX_FAILOVER_WAIT_MS || policy.startup_window_ms > MAX_FAILOVER_WAIT_MS {
+        return Err(BertError::InvalidInput(format!("Backoff and startup window must be at most {} ms", MAX_FAILOVER_WAIT_MS)));
+    }
+    let mut settings = settings_state.settings.lock()
+        .map_err(|e| BertError::Internal(format!("Failed to acquire settings lock: {}", e)))?;
+
+    settings.agent_failover = policy;
+
+    let config_path = get_config_path().map_err(BertError::Io)?;
+    settings.save_to_file(&config_path).map_err(BertError::Io)?;
+
+    eprintln!("✅ Updated agent failover policy: {:?}", settings.agent_failover);
+    Ok(())
+}
+
+/// Tauri command to get which events raise desktop notifications
+#[tauri::command]
+pub fn get_notification_settings(settings_state: State<'_, SettingsState>) -> Result<NotificationSettings, String> {
+    let settings = settings_state.settings.lock()
+        .map_err(|e| format!("Failed to acquire settings lock: {}", e))?;
+    Ok(settings.notifications.clone())
+}
+
+/// Tauri command to set which events raise desktop notifications and persist to disk
+#[tauri::command]
+pub fn set_notification_settings(
+    notifications: NotificationSettings,
+    settings_state: State<'_, SettingsState>
+) -> Result<(), String> {
+    let mut settings = settings_state.settings.lock()
+        .map_err(|e| format!("Failed to acquire settings lock: {}", e))?;
+
+    settings.notifications = notifications;
+
+    let config_path = get_config_path()?;
+    settings.save_to_file(&config_path)?;
+
+    eprintln!("✅ Updated notification settings: {:?}", settings.notifications);
+    Ok(())
+}
+
+/// Tauri command to get the permissions agent CLIs run with
+#[tauri::command]
+pub fn get_agent_safety_policy(settings_state: State<'_, SettingsState>) -> Result<AgentSafetyPolicy, BertError> {
+    let settings = settings_state.settings.lock()
+        .map_err(|e| BertError::Internal(format!("Failed to acquire settings lock: {}", e)))?;
+    Ok(settings.agent_safety_policy)
+}
+
+/// Tauri command to set the permissions agent CLIs run with and persist to disk.
+/// Runs already in progress keep the policy they started with.
+#[tauri::command]
+pub fn set_agent_safety_policy(
+    policy: AgentSafetyPolicy,
+    settings_state: State<'_, SettingsState>
+) -> Result<(), BertError> {
+    let mut settings = settings_state.settings.lock()
+        .map_err(|e| BertError::Internal(format!("Failed to acquire settings lock: {}", e)))?;
+
+    settings.agent_safety_policy = policy;
+
+    let config_path = get_config_path().map_err(BertError::Io)?;
+    settings.save_to_file(&config_path).map_err(BertError::Io)?;
+
+    eprintln!("✅ Updated agent safety policy to: {:?}", policy);
+    Ok(())
+}
+
+/// Tauri command to get the local REST API settings
+#[tauri::command]
+pub fn get_rest_api_settings(settings_state: State<'_, SettingsState>) -> Result<RestApiSettings, String> {
+    let settings = settings_state.settings.lock()
+        .map_err(|e| format!("Failed to acquire settings lock: {}", e))?;
+    Ok(settings.rest_api.clone())
+}
+
+/// Tauri command to set the local REST API settings and persist to disk.
+/// Enabling it without a token generates one; returns the saved settings.
+#[tauri::command]
+pub fn set_rest_api_settings(
+    mut rest_api: RestApiSettings,
+    settings_state: State<'_, SettingsState>
+) -> Result<RestApiSettings, String> {
+    if rest_api.port == 0 {
+        return Err("REST API port cannot be 0".to_string());
+    }
+    rest_api.token = rest_api.token.trim().to_string();
+    if rest_api.enabled && rest_api.token.is_empty() {
+        rest_api.token = uuid::Uuid::new_v4().simple().to_string();
+    }
+
+    let mut settings = settings_state.settings.lock()
+        .map_err(|e| format!("Failed to acquire settings lock: {}", e))?;
+
+    settings.rest_api = rest_api;
+
+    let config_path = get_config_path()?;
+    settings.save_to_file(&config_path)?;
+
+    eprintln!("✅ Updated REST API settings: enabled={} port={}", settings.rest_api.enabled, settings.rest_api.port);
+    Ok(settings.rest_api.clone())
+}
+
+/// Tauri command to get the display preferences
+#[tauri::command]
+pub fn get_display_prefs(settings_state: State<'_, SettingsState>) -> Result<DisplayPrefs, String> {
+    let settings = settings_state.settings.lock()
+        .map_err(|e| format!("Failed to acquire settings lock: {}", e))?;
+    Ok(settings.display.clone())
+}
+
+/// Tauri command to set the display preferences and persist to disk.
+/// Emits `settings-updated` so every window applies them.
+#[tauri::command]
+pub fn set_display_prefs(
+    prefs: DisplayPrefs,
+    app_handle: AppHandle,
+    settings_state: State<'_, SettingsState>
+) -> Result<(), String> {
+    if !DEFAULT_ZOOM_RANGE.contains(&prefs.default_zoom) {
+        return Err(format!(
+            "Invalid default zoom: {}. Expected {} to {}",
+            prefs.default_zoom,
+            DEFAULT_ZOOM_RANGE.start(),
+            DEFAULT_ZOOM_RANGE.end()
+        ));
+    }
+
+    let mut settings = settings_state.settings.lock()
+        .map_err(|e| format!("Failed to acquire settings lock: {}", e))?;
+
+    settings.display = prefs;
+
+    let config_path = get_config_path()?;
+    settings.save_to_file(&config_path)?;
+
+    eprintln!("✅ Updated display preferences: {:?}", settings.display);
+    let _ = app_handle.emit("settings-updated", SettingsUpdated { display: settings.display.clone() });
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -149,6 +652,7 @@ mod tests {
     fn test_settings_serialization() {
         let settings = AppSettings {
             cli_backend: BackendId::ClaudeCode,
+            ..AppSettings::default()
         };
 
         let json = serde_json::to_string(&settings).unwrap();
@@ -158,6 +662,51 @@ mod tests {
         assert_eq!(deserialized.cli_backend, BackendId::ClaudeCode);
     }
 
+    #[test]
+    fn test_settings_without_ollama_fields_use_defaults() {
+        let settings: AppSettings = serde_json::from_str(r#"{"cliBackend":"ollama"}"#).unwrap();
+        assert_eq!(settings.cli_backend, BackendId::Ollama);
+        assert_eq!(settings.ollama_host, ollama::DEFAULT_HOST);
+        assert_eq!(settings.ollama_model, ollama::DEFAULT_MODEL);
+        assert!(settings.auto_capture_summary);
+        assert_eq!(settings.notifications, NotificationSettings::default());
+        assert_eq!(settings.agent_safety_policy, AgentSafetyPolicy::FullAuto);
+        assert_eq!(settings.display, DisplayPrefs::default());
+    }
+
+    #[test]
+    fn test_display_prefs_serialization() {
+        let settings: AppSettings = serde_json::from_str(
+            r#"{"cliBackend":"claude","display":{"theme":"dark","dateFormat":"eu","firstDayOfWeek":"sunday"}}"#,
+        )
+        .unwrap();
+        assert_eq!(settings.display.theme, Theme::Dark);
+        assert_eq!(settings.display.date_format, DateFormat::Eu);
+        assert_eq!(settings.display.first_day_of_week, FirstDayOfWeek::Sunday);
+        assert_eq!(settings.display.density, Density::Comfortable);
+        assert_eq!(settings.display.default_zoom, 1.0);
+
+        let json = serde_json::to_string(&settings.display).unwrap();
+        assert!(json.contains(r#""defaultZoom":1.0"#));
+    }
+
+    #[test]
+    fn test_agent_safety_policy_serialization() {
+        let settings: AppSettings =
+            serde_json::from_str(r#"{"cliBackend":"claude","agentSafetyPolicy":"approve-writes"}"#).unwrap();
+        assert_eq!(settings.agent_safety_policy, AgentSafetyPolicy::ApproveWrites);
+        assert!(serde_json::to_string(&AgentSafetyPolicy::ReadOnly).unwrap().contains("read-only"));
+    }
+
+    #[test]
+    fn test_partial_notification_settings_use_defaults() {
+        let settings: AppSettings =
+            serde_json::from_str(r#"{"cliBackend":"claude","notifications":{"sessionFinished":false}}"#).unwrap();
+        assert!(!settings.notifications.session_finished);
+        assert!(settings.notifications.session_errored);
+        assert!(settings.notifications.bead_unblocked);
+    }
+
     #[test]
     fn test_load_missing_file() {
         let temp_path = env::temp_dir().join("nonexistent_settings.json");
@@ -175,6 +724,7 @@ mod tests {
         // Save settings
         let settings = AppSettings {
             cli_backend: BackendId::ClaudeCode,
+            ..AppSettings::default()
         };
         settings.save_to_file(&temp_path).unwrap();
 
@@ -239,6 +789,7 @@ mod tests {
         // Create settings with Gemini
         let settings1 = AppSettings {
             cli_backend: BackendId::Gemini,
+            ..AppSettings::default()
         };
         settings1.save_to_file(&temp_path).unwrap();
 
@@ -249,6 +800,7 @@ mod tests {
         // Update to Claude
         let settings2 = AppSettings {
             cli_backend: BackendId::ClaudeCode,
+            ..AppSettings::default()
         };
         settings2.save_to_file(&temp_path).unwrap();
 
@@ -259,6 +811,7 @@ mod tests {
         // Update back to Gemini
         let settings3 = AppSettings {
             cli_backend: BackendId::Gemini,
+            ..AppSettings::default()
         };
         settings3.save_to_file(&temp_path).unwrap();
 
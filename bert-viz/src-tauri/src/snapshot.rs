@@ -0,0 +1,240 @@
+//! Last good bead set of each beads file
+//!
+//! The bd daemon rewrites issues.jsonl by deleting and recreating it, and a
+//! read racing the rewrite can see an empty or truncated file that parses
+//! fine. Handing that to the UI clears the whole view for a moment.
+//!
+//! `read_beads` keeps the last accepted bead set of each file, stamped with a
+//! generation that increases whenever the file's contents are accepted. A
+//! candidate read that loses more than `MAX_DROP_PERCENT` of the beads is
+//! only accepted when removals were expected (`expect_removals`, set when the
+//! app runs `bd delete`) or when the file stays unchanged for `SETTLE`, which
+//! a rewrite in progress never does. Otherwise the last good set is returned.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::bead_index::{stamp, FileStamp};
+use crate::error::BertError;
+use crate::Bead;
+
+/// Largest drop in bead count accepted without confirmation
+pub const MAX_DROP_PERCENT: usize = 50;
+/// Below this many beads any drop is accepted; small projects shrink fast
+const MIN_CHECKED_BEADS: usize = 5;
+/// How long a shrunken file must stay unchanged before it is believed
+const SETTLE: Duration = Duration::from_millis(750);
+/// Give up and serve the last good set after this long
+const MAX_WAIT: Duration = Duration::from_secs(3);
+const POLL: Duration = Duration::from_millis(50);
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Snapshot {
+    pub generation: u64,
+    pub taken_at: DateTime<Utc>,
+    pub beads: Vec<Bead>,
+    #[serde(skip)]
+    stamp: Option<FileStamp>,
+}
+
+impl Snapshot {
+    /// Size and modification time of the file this was read from
+    pub(crate) fn stamp(&self) -> Option<FileStamp> {
+        self.stamp
+    }
+}
+
+#[derive(Default)]
+struct Entry {
+    snapshot: Option<Arc<Snapshot>>,
+    expect_removals: bool,
+}
+
+static SNAPSHOTS: Mutex<BTreeMap<PathBuf, Entry>> = Mutex::new(BTreeMap::new());
+
+/// Whether going from `previous` to `candidate` beads looks like a torn read
+fn is_suspicious(previous: usize, candidate: usize) -> bool {
+    previous >= MIN_CHECKED_BEADS && candidate * 100 < previous * (100 - MAX_DROP_PERCENT)
+}
+
+/// Make the next shrinking read of `beads_path` acceptable, e.g. before
+/// deleting beads
+pub fn expect_removals(beads_path: &Path) {
+    SNAPSHOTS.lock().unwrap().entry(beads_path.to_path_buf()).or_default().expect_removals = true;
+}
+
+/// Offer a freshly read bead set. Returns the snapshot to use and whether the
+/// candidate was accepted; `settled` accepts it regardless of its size.
+fn offer(beads_path: &Path, beads: Vec<Bead>, stamp: Option<FileStamp>, settled: bool) -> (Arc<Snapshot>, bool) {
+    let mut snapshots = SNAPSHOTS.lock().unwrap();
+    let entry = snapshots.entry(beads_path.to_path_buf()).or_default();
+
+    if let Some(previous) = &entry.snapshot {
+        if !settled && !entry.expect_removals && is_suspicious(previous.beads.len(), beads.len()) {
+            return (previous.clone(), false);
+        }
+        if stamp.is_some() && previous.stamp == stamp {
+            return (previous.clone(), true);
+        }
+    }
+
+    let generation = entry.snapshot.as_ref().map_or(1, |s| s.generation + 1);
+    let snapshot = Arc::new(Snapshot { generation, taken_at: Utc::now(), beads, stamp });
+    entry.snapshot = Some(snapshot.clone());
+    entry.expect_removals = false;
+    (snapshot, true)
+}
+
+/// Record beads the caller read from a file it knows to be stable (the beads
+/// watcher waits for writes to settle)
+pub fn record(beads_path: &Path, beads: Vec<Bead>, stamp: Option<FileStamp>) {
+    offer(beads_path, beads, stamp, true);
+}
+
+/// The last accepted snapshot of `beads_path`, if it was read before
+pub fn last_good(beads_path: &Path) -> Option<Arc<Snapshot>> {
+    SNAPSHOTS.lock().unwrap().get(beads_path).and_then(|e| e.snapshot.clone())
+}
+
+fn read_stamped(beads_path: &Path) -> Result<(Option<FileStamp>, Vec<Bead>), BertError> {
+    let before = stamp(beads_path);
+    let beads = crate::bd::read_beads(beads_path)?;
+    // A stamp that moved during the read doesn't describe what was read
+    let after = stamp(beads_path);
+    Ok((before.filter(|_| before == after), beads))
+}
+
+/// Read a beads file, guarding against torn reads during a rewrite
+pub fn read_snapshot(beads_path: &Path) -> Result<Arc<Snapshot>, BertError> {
+    let started = Instant::now();
+    let (mut candidate_stamp, mut candidate) = read_stamped(beads_path)?;
+    let (mut snapshot, accepted) = offer(beads_path, candidate.clone(), candidate_stamp, false);
+    if accepted {
+        return Ok(snapshot);
+    }
+
+    eprintln!(
+        "⏸️  {} shrank from {} to {} beads; waiting for it to settle",
+        beads_path.display(),
+        snapshot.beads.len(),
+        candidate.len()
+    );
+    let mut stable_since = Instant::now();
+    while started.elapsed() < MAX_WAIT {
+        std::thread::sleep(POLL);
+        let current = stamp(beads_path);
+        if current.is_some() && current == candidate_stamp {
+            if stable_since.elapsed() < SETTLE {
+                continue;
+            }
+            // Unchanged for SETTLE: the beads really were removed
+            return Ok(offer(beads_path, candidate, candidate_stamp, true).0);
+        }
+
+        // The file changed or vanished; a rewrite may have finished
+        stable_since = Instant::now();
+        match read_stamped(beads_path) {
+            Ok((read_stamp, beads)) => {
+                (candidate_stamp, candidate) = (read_stamp, beads);
+                let accepted;
+                (snapshot, accepted) = offer(beads_path, candidate.clone(), candidate_stamp, false);
+                if accepted {
+                    return Ok(snapshot);
+                }
+            }
+            Err(e) => {
+                eprintln!("  ⚠️  Re-read of {} failed: {}", beads_path.display(), e);
+                candidate_stamp = None;
+            }
+        }
+    }
+
+    eprintln!("  ⚠️  {} kept changing; serving generation {}", beads_path.display(), snapshot.generation);
+    Ok(snapshot)
+}
+
+/// Beads of `beads_path`, never a torn read (see `read_snapshot`)
+pub fn read_beads(beads_path: &Path) -> Result<Vec<Bead>, BertError> {
+    Ok(read_snapshot(beads_path)?.beads.clone())
+}
+
+/// The last good bead set of the window's project, with its generation, so
+/// the UI can keep showing it instead of an empty list
+#[tauri::command]
+pub fn get_last_good_snapshot(window: tauri::Window, project_handle: Option<String>) -> Result<Snapshot, BertError> {
+    let beads_path = crate::project::context_for(&window, project_handle.as_deref())?.beads_path()?;
+    match last_good(&beads_path) {
+        Some(snapshot) => Ok((*snapshot).clone()),
+        None => Ok((*read_snapshot(&beads_path)?).clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn write_beads(path: &Path, count: usize) {
+        let lines: Vec<String> = (0..count)
+            .map(|i| json!({"id": format!("s-{}", i), "title": "t", "status": "open", "priority": 2, "issue_type": "task"}).to_string())
+            .collect();
+        std::fs::write(path, lines.join("\n") + "\n").unwrap();
+    }
+
+    #[test]
+    fn test_is_suspicious() {
+        assert!(is_suspicious(10, 0));
+        assert!(is_suspicious(10, 4));
+        assert!(!is_suspicious(10, 5));
+        assert!(!is_suspicious(4, 0));
+        assert!(!is_suspicious(10, 30));
+    }
+
+    #[test]
+    fn test_offer_keeps_last_good_until_removals_expected() {
+        let path = Path::new("/nonexistent/offer/issues.jsonl");
+        let beads = |n: usize| -> Vec<Bead> {
+            (0..n)
+                .map(|i| serde_json::from_value(json!({"id": format!("o-{}", i), "title": "t", "status": "open", "priority": 2, "issue_type": "task"})).unwrap())
+                .collect()
+        };
+
+        let (first, accepted) = offer(path, beads(10), None, false);
+        assert!(accepted);
+        let (served, accepted) = offer(path, Vec::new(), None, false);
+        assert!(!accepted);
+        assert_eq!((served.generation, served.beads.len()), (first.generation, 10));
+
+        expect_removals(path);
+        let (second, accepted) = offer(path, beads(2), None, false);
+        assert!(accepted);
+        assert_eq!((second.generation, second.beads.len()), (first.generation + 1, 2));
+        // The flag is used up by the accepted read
+        offer(path, beads(10), None, false);
+        assert!(!offer(path, beads(1), None, false).1);
+    }
+
+    #[test]
+    fn test_read_accepts_a_shrink_once_the_file_settles() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("issues.jsonl");
+        write_beads(&path, 10);
+        let first = read_snapshot(&path).unwrap();
+        assert_eq!(first.beads.len(), 10);
+        // Unchanged file: same generation
+        assert_eq!(read_snapshot(&path).unwrap().generation, first.generation);
+
+        write_beads(&path, 3);
+        let started = Instant::now();
+        let shrunk = read_snapshot(&path).unwrap();
+        assert_eq!(shrunk.beads.len(), 3);
+        assert_eq!(shrunk.generation, first.generation + 1);
+        assert!(started.elapsed() >= SETTLE);
+    }
+}
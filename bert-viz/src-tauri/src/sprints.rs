@@ -0,0 +1,359 @@
+//! Sprints (iterations) with capacity planning
+//!
+//! Sprints are defined under the `sprints` key of `.beads/bert-viz.json`: a
+//! name, start and end dates, and the estimate minutes each owner can take on.
+//! A bead belongs to a sprint through a `sprint:<name>` label, so the
+//! assignment round-trips through bd like any other label.
+//!
+//! `get_sprint_report` compares the committed estimate with what was finished
+//! by the end date, lists the spillover once the sprint is over, and sums the
+//! load of each owner against their capacity.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::Map;
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::error::BertError;
+use crate::project::context_for;
+use crate::schedule::{config_path_for, load_config_section};
+use crate::workflow::WorkflowConfig;
+use crate::Bead;
+
+/// Key of the sprint definitions in the project config file
+const CONFIG_KEY: &str = "sprints";
+
+/// Prefix of the label assigning a bead to a sprint
+pub const SPRINT_LABEL_PREFIX: &str = "sprint:";
+
+/// One iteration of the project
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Sprint {
+    pub name: String,
+    /// First day (YYYY-MM-DD)
+    pub start: String,
+    /// Last day (YYYY-MM-DD), inclusive
+    pub end: String,
+    /// Owner -> estimate minutes they can take on in this sprint
+    #[serde(default)]
+    pub capacity: BTreeMap<String, u64>,
+}
+
+impl Sprint {
+    fn dates(&self) -> Result<(NaiveDate, NaiveDate), BertError> {
+        let parse = |d: &str| {
+            NaiveDate::parse_from_str(d, "%Y-%m-%d")
+                .map_err(|e| BertError::InvalidInput(format!("Invalid date '{}' in sprint '{}': {}", d, self.name, e)))
+        };
+        Ok((parse(&self.start)?, parse(&self.end)?))
+    }
+}
+
+fn validate(sprints: &[Sprint]) -> Result<(), BertError> {
+    let mut seen = HashSet::new();
+    for sprint in sprints {
+        let name = sprint.name.trim();
+        if name.is_empty() || name.contains(',') {
+            return Err(BertError::InvalidInput("Sprint names cannot be empty or contain commas".to_string()));
+        }
+        if !seen.insert(name) {
+            return Err(BertError::InvalidInput(format!("Duplicate sprint '{}'", name)));
+        }
+        let (start, end) = sprint.dates()?;
+        if end < start {
+            return Err(BertError::InvalidInput(format!("Sprint '{}' ends before it starts", name)));
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn load_sprints(beads_path: &Path) -> Result<Vec<Sprint>, BertError> {
+    Ok(load_config_section(beads_path, CONFIG_KEY)?.unwrap_or_default())
+}
+
+fn save_sprints_file(beads_path: &Path, sprints: &[Sprint]) -> Result<(), BertError> {
+    validate(sprints)?;
+    let mut fields = Map::new();
+    fields.insert(CONFIG_KEY.to_string(), serde_json::to_value(sprints)?);
+    crate::schedule::merge_config_fields(beads_path, fields)
+}
+
+/// Name of the sprint a bead is assigned to, if any
+pub fn sprint_of(bead: &Bead) -> Option<&str> {
+    bead.labels
+        .iter()
+        .flatten()
+        .find_map(|l| l.strip_prefix(SPRINT_LABEL_PREFIX))
+}
+
+/// Labels of `bead` with its sprint label replaced by one for `sprint`
+/// (removed with `None`)
+fn labels_with_sprint(bead: &Bead, sprint: Option<&str>) -> Vec<String> {
+    let mut labels: Vec<String> = bead
+        .labels
+        .iter()
+        .flatten()
+        .filter(|l| !l.starts_with(SPRINT_LABEL_PREFIX))
+        .cloned()
+        .collect();
+    if let Some(sprint) = sprint {
+        labels.push(format!("{}{}", SPRINT_LABEL_PREFIX, sprint));
+    }
+    labels
+}
+
+/// Committed and remaining work of one owner in a sprint
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnerSprintLoad {
+    /// None for unassigned beads
+    pub owner: Option<String>,
+    /// Estimate minutes of every bead of the owner in the sprint
+    pub committed_estimate: u64,
+    pub completed_estimate: u64,
+    /// Minutes the owner can take on, None if the sprint sets no capacity for them
+    pub capacity: Option<u64>,
+    pub over_capacity: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SprintReport {
+    pub sprint: Sprint,
+    /// IDs of the beads in the sprint, in file order
+    pub bead_ids: Vec<String>,
+    /// Estimate minutes of every bead in the sprint
+    pub committed_estimate: u64,
+    /// Estimate minutes of the beads finished by the end date
+    pub completed_estimate: u64,
+    /// The end date has passed
+    pub ended: bool,
+    /// Beads not finished by the end date, once the sprint has ended
+    pub spillover: Vec<String>,
+    /// Owners by name, unassigned beads last
+    pub owners: Vec<OwnerSprintLoad>,
+    /// Beads in the sprint without an estimate
+    pub unestimated_count: usize,
+}
+
+/// Whether `bead` was finished on or before `end`. Done beads without a
+/// parseable `closed_at` count as finished in time.
+fn finished_by(bead: &Bead, workflow: &WorkflowConfig, end: NaiveDate) -> bool {
+    if !workflow.is_done(&bead.status) {
+        return false;
+    }
+    bead.closed_at
+        .as_deref()
+        .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+        .map_or(true, |closed| closed.date_naive() <= end)
+}
+
+fn build_report(sprint: &Sprint, beads: &[Bead], workflow: &WorkflowConfig, today: NaiveDate) -> Result<SprintReport, BertError> {
+    let (_, end) = sprint.dates()?;
+    let ended = today > end;
+
+    let mut report = SprintReport {
+        sprint: sprint.clone(),
+        bead_ids: Vec::new(),
+        committed_estimate: 0,
+        completed_estimate: 0,
+        ended,
+        spillover: Vec::new(),
+        owners: Vec::new(),
+        unestimated_count: 0,
+    };
+
+    // BTreeMap orders owners by name with None (unassigned) first
+    let mut by_owner: BTreeMap<Option<String>, OwnerSprintLoad> = BTreeMap::new();
+    for owner in sprint.capacity.keys() {
+        by_owner.insert(Some(owner.clone()), OwnerSprintLoad { owner: Some(owner.clone()), ..OwnerSprintLoad::default() });
+    }
+
+    let in_sprint = beads
+        .iter()
+        .filter(|b| b.status != "tombstone" && sprint_of(b) == Some(sprint.name.as_str()));
+    for bead in in_sprint {
+        let estimate = bead.estimate.unwrap_or(0) as u64;
+        let finished = finished_by(bead, workflow, end);
+
+        report.bead_ids.push(bead.id.clone());
+        report.committed_estimate += estimate;
+        if bead.estimate.is_none() {
+            report.unestimated_count += 1;
+        }
+        if finished {
+            report.completed_estimate += estimate;
+        } else if ended {
+            report.spillover.push(bead.id.clone());
+        }
+
+        let owner = bead.owner.clone().filter(|o| !o.is_empty());
+        let load = by_owner
+            .entry(owner.clone())
+            .or_insert_with(|| OwnerSprintLoad { owner, ..OwnerSprintLoad::default() });
+        load.committed_estimate += estimate;
+        if finished {
+            load.completed_estimate += estimate;
+        }
+    }
+
+    report.owners = by_owner
+        .into_values()
+        .map(|mut load| {
+            load.capacity = load.owner.as_ref().and_then(|o| sprint.capacity.get(o).copied());
+            load.over_capacity = load.capacity.is_some_and(|c| load.committed_estimate > c);
+            load
+        })
+        .collect();
+    if report.owners.first().is_some_and(|l| l.owner.is_none()) {
+        report.owners.rotate_left(1);
+    }
+
+    Ok(report)
+}
+
+/// Sprints defined for the project
+#[tauri::command]
+pub fn list_sprints(window: tauri::Window, project_handle: Option<String>) -> Result<Vec<Sprint>, BertError> {
+    let beads_path = context_for(&window, project_handle.as_deref())?.beads_path()?;
+    load_sprints(&beads_path)
+}
+
+/// Replace the sprint definitions of the project
+#[tauri::command]
+pub fn save_sprints(sprints: Vec<Sprint>, window: tauri::Window, project_handle: Option<String>) -> Result<(), BertError> {
+    let beads_path = context_for(&window, project_handle.as_deref())?.beads_path()?;
+    save_sprints_file(&beads_path, &sprints)?;
+    eprintln!("🏃 Saved {} sprint(s) to {}", sprints.len(), config_path_for(&beads_path).display());
+    Ok(())
+}
+
+/// Move a bead into `sprint`, or out of any sprint with `None`
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn assign_to_sprint(beadId: String, sprint: Option<String>, window: tauri::Window, project_handle: Option<String>, app_handle: AppHandle) -> Result<Bead, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    if let Some(name) = &sprint {
+        if !load_sprints(&ctx.beads_path()?)?.iter().any(|s| &s.name == name) {
+            return Err(BertError::NotFound(format!("Sprint '{}' not found", name)));
+        }
+    }
+
+    let mut bead = crate::bead_index::get_bead_internal(&ctx, &beadId)?;
+    let labels = labels_with_sprint(&bead, sprint.as_deref());
+    crate::bd::set_labels_internal(&ctx, &bead.id, &labels)?;
+    bead.labels = Some(labels);
+
+    eprintln!("🏃 {} -> sprint {}", beadId, sprint.as_deref().unwrap_or("(none)"));
+    crate::project::emit_beads_updated(&app_handle, &ctx);
+    Ok(bead)
+}
+
+/// Committed vs completed work, spillover and per-owner load of a sprint
+#[tauri::command]
+pub fn get_sprint_report(sprint: String, window: tauri::Window, project_handle: Option<String>) -> Result<SprintReport, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    let beads_path = ctx.beads_path()?;
+    let definition = load_sprints(&beads_path)?
+        .into_iter()
+        .find(|s| s.name == sprint)
+        .ok_or_else(|| BertError::NotFound(format!("Sprint '{}' not found", sprint)))?;
+    let workflow = WorkflowConfig::load_for_beads_file(&beads_path)?;
+    let beads = crate::bd::load_beads(&ctx)?;
+    build_report(&definition, &beads, &workflow, chrono::Utc::now().date_naive())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn bead(id: &str, status: &str, owner: Option<&str>, estimate: Option<u32>, labels: &[&str]) -> Bead {
+        crate::test_bead(json!({
+            "id": id,
+            "status": status,
+            "owner": owner,
+            "estimate": estimate,
+            "labels": labels,
+            "closed_at": if status == "closed" { Some("2026-10-09T12:00:00Z") } else { None },
+        }))
+    }
+
+    fn sprint() -> Sprint {
+        Sprint {
+            name: "s1".to_string(),
+            start: "2026-10-05".to_string(),
+            end: "2026-10-16".to_string(),
+            capacity: BTreeMap::from([("ann".to_string(), 120), ("bob".to_string(), 240)]),
+        }
+    }
+
+    fn date(d: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(d, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_sprint_label_is_replaced() {
+        let b = bead("a", "open", None, None, &["ui", "sprint:old"]);
+        assert_eq!(sprint_of(&b), Some("old"));
+        assert_eq!(labels_with_sprint(&b, Some("new")), vec!["ui".to_string(), "sprint:new".to_string()]);
+        assert_eq!(labels_with_sprint(&b, None), vec!["ui".to_string()]);
+    }
+
+    #[test]
+    fn test_report_sums_work_and_spillover() {
+        let beads = vec![
+            bead("a", "closed", Some("ann"), Some(60), &["sprint:s1"]),
+            bead("b", "open", Some("ann"), Some(90), &["sprint:s1"]),
+            bead("c", "in_progress", Some("bob"), Some(30), &["sprint:s1"]),
+            bead("d", "open", None, None, &["sprint:s1"]),
+            bead("e", "open", Some("bob"), Some(500), &["sprint:s2"]),
+        ];
+        let workflow = WorkflowConfig::default();
+
+        let running = build_report(&sprint(), &beads, &workflow, date("2026-10-12")).unwrap();
+        assert_eq!(running.bead_ids, vec!["a", "b", "c", "d"]);
+        assert_eq!((running.committed_estimate, running.completed_estimate), (180, 60));
+        assert_eq!(running.unestimated_count, 1);
+        assert!(!running.ended);
+        assert!(running.spillover.is_empty());
+
+        let owners: Vec<_> = running.owners.iter().map(|l| l.owner.as_deref()).collect();
+        assert_eq!(owners, vec![Some("ann"), Some("bob"), None]);
+        let ann = &running.owners[0];
+        assert_eq!((ann.committed_estimate, ann.completed_estimate, ann.capacity), (150, 60, Some(120)));
+        assert!(ann.over_capacity);
+        assert!(!running.owners[1].over_capacity);
+
+        let over = build_report(&sprint(), &beads, &workflow, date("2026-10-17")).unwrap();
+        assert!(over.ended);
+        assert_eq!(over.spillover, vec!["b", "c", "d"]);
+    }
+
+    #[test]
+    fn test_closed_after_end_is_spillover() {
+        let mut late = bead("a", "closed", None, Some(30), &["sprint:s1"]);
+        late.closed_at = Some("2026-10-20T09:00:00Z".to_string());
+        let report = build_report(&sprint(), &[late], &WorkflowConfig::default(), date("2026-10-21")).unwrap();
+        assert_eq!(report.completed_estimate, 0);
+        assert_eq!(report.spillover, vec!["a"]);
+    }
+
+    #[test]
+    fn test_sprints_roundtrip_and_validation() {
+        let dir = TempDir::new().unwrap();
+        let beads_path = dir.path().join("issues.jsonl");
+        assert!(load_sprints(&beads_path).unwrap().is_empty());
+
+        save_sprints_file(&beads_path, &[sprint()]).unwrap();
+        assert_eq!(load_sprints(&beads_path).unwrap(), vec![sprint()]);
+
+        let backwards = Sprint { end: "2026-10-01".to_string(), ..sprint() };
+        assert!(save_sprints_file(&beads_path, &[backwards]).is_err());
+        assert!(save_sprints_file(&beads_path, &[sprint(), sprint()]).is_err());
+    }
+}
@@ -3,11 +3,22 @@
 /// This module provides Tauri commands to save and load application startup state
 /// including window size, filters, sort options, and UI preferences.
 ///
-/// State is persisted to ~/.bp6/startup.json
+/// State is persisted to ~/.bp6/startup.json. What belongs to one project
+/// (filters, collapsed nodes, selection, scroll position) is kept per project
+/// path in `projects`, so switching projects restores each one's context.
+/// Files written before that (version 1) are migrated on load: their flat
+/// state becomes the state of the most recently opened project.
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::State;
+
+use crate::error::BertError;
+use crate::settings::DisplayPrefs;
+use crate::SettingsState;
 
 // ============================================================================
 // Data Structures
@@ -58,6 +69,31 @@ fn default_view() -> String {
     "gantt".to_string()
 }
 
+/// Scroll offsets of the main view, in pixels
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScrollPosition {
+    pub top: f64,
+    pub left: f64,
+}
+
+/// UI context of one project, restored when it is opened again
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProjectUiState {
+    pub filters: FilterState,
+    pub sort: SortState,
+    pub collapsed_ids: Vec<String>,
+    pub selected_bead_id: Option<String>,
+    pub scroll: ScrollPosition,
+}
+
+/// Version of the startup.json schema written by this build
+const STARTUP_STATE_VERSION: u32 = 2;
+
+/// Serializes read-modify-write cycles of startup.json between windows
+static STARTUP_FILE_LOCK: Mutex<()> = Mutex::new(());
+
 /// Complete startup state containing all restorable application state
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -66,6 +102,21 @@ pub struct StartupState {
     pub filters: FilterState,
     pub sort: SortState,
     pub ui: UiState,
+    /// Copy of the display preferences in settings.json, which win on load,
+    /// so the first render already uses the right theme
+    #[serde(default)]
+    pub display: DisplayPrefs,
+    /// Missing (1) in files written before per-project state
+    #[serde(default = "legacy_version")]
+    pub version: u32,
+    /// Per-project UI state keyed by project path. Only changed through
+    /// `save_project_ui_state`; `save_startup_state` keeps what is on disk.
+    #[serde(default)]
+    pub projects: BTreeMap<String, ProjectUiState>,
+}
+
+fn legacy_version() -> u32 {
+    1
 }
 
 // ============================================================================
@@ -122,6 +173,9 @@ impl Default for StartupState {
             filters: FilterState::default(),
             sort: SortState::default(),
             ui: UiState::default(),
+            display: DisplayPrefs::default(),
+            version: STARTUP_STATE_VERSION,
+            projects: BTreeMap::new(),
         }
     }
 }
@@ -144,6 +198,63 @@ fn get_startup_state_path() -> Result<PathBuf, String> {
     Ok(bp6_dir.join("startup.json"))
 }
 
+/// Key of a project in `StartupState::projects`
+fn project_key(project_path: &str) -> String {
+    let trimmed = project_path.trim_end_matches(['/', '\\']);
+    if trimmed.is_empty() { project_path.to_string() } else { trimmed.to_string() }
+}
+
+/// Parse startup.json, migrating a version 1 file by filing its flat
+/// filters, sort and collapsed nodes under `last_project`. Returns whether
+/// the state was migrated.
+fn parse_startup_state(contents: &str, last_project: Option<&str>) -> Result<(StartupState, bool), String> {
+    let mut state: StartupState = serde_json::from_str(contents)
+        .map_err(|e| format!("Failed to parse startup state file: {}", e))?;
+    if state.version >= STARTUP_STATE_VERSION {
+        return Ok((state, false));
+    }
+
+    if let Some(project) = last_project {
+        state.projects.entry(project_key(project)).or_insert_with(|| ProjectUiState {
+            filters: state.filters.clone(),
+            sort: state.sort.clone(),
+            collapsed_ids: state.ui.collapsed_ids.clone(),
+            ..ProjectUiState::default()
+        });
+    }
+    state.version = STARTUP_STATE_VERSION;
+    Ok((state, true))
+}
+
+/// Path of the most recently opened project in projects.json
+fn last_opened_project() -> Option<String> {
+    let projects = crate::get_projects().ok()?;
+    projects.into_iter().filter(|p| p.last_opened.is_some()).max_by(|a, b| a.last_opened.cmp(&b.last_opened)).map(|p| p.path)
+}
+
+/// The startup state on disk, migrated to the current schema (and written
+/// back if it was), or None if there is none yet
+fn read_startup_state(path: &PathBuf) -> Result<Option<StartupState>, String> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read startup state file: {}", e))?;
+    let (state, migrated) = parse_startup_state(&contents, last_opened_project().as_deref())?;
+    if migrated {
+        write_startup_state(path, &state)?;
+        eprintln!("✅ Migrated startup state to per-project UI state ({} projects)", state.projects.len());
+    }
+    Ok(Some(state))
+}
+
+fn write_startup_state(path: &PathBuf, state: &StartupState) -> Result<(), String> {
+    let contents = serde_json::to_string_pretty(state)
+        .map_err(|e| format!("Failed to serialize startup state: {}", e))?;
+    fs::write(path, contents)
+        .map_err(|e| format!("Failed to write startup state file: {}", e))
+}
+
 // ============================================================================
 // Tauri Commands
 // ============================================================================
@@ -156,15 +267,14 @@ fn get_startup_state_path() -> Result<PathBuf, String> {
 /// # Returns
 /// Unit result or error message
 #[tauri::command]
-pub async fn save_startup_state(state: StartupState) -> Result<(), String> {
+pub async fn save_startup_state(mut state: StartupState) -> Result<(), String> {
+    let _guard = STARTUP_FILE_LOCK.lock().map_err(|e| format!("Failed to lock startup state: {}", e))?;
     let path = get_startup_state_path()?;
 
-    let contents = serde_json::to_string_pretty(&state)
-        .map_err(|e| format!("Failed to serialize startup state: {}", e))?;
-
-    fs::write(&path, contents)
-        .map_err(|e| format!("Failed to write startup state file: {}", e))?;
-    Ok(())
+    // Per-project state is saved on its own; keep what is on disk
+    state.projects = read_startup_state(&path)?.map(|existing| existing.projects).unwrap_or_default();
+    state.version = STARTUP_STATE_VERSION;
+    write_startup_state(&path, &state)
 }
 
 /// Load startup state from ~/.bp6/startup.json
@@ -172,24 +282,55 @@ pub async fn save_startup_state(state: StartupState) -> Result<(), String> {
 /// # Returns
 /// Optional StartupState if file exists and is valid, None otherwise
 #[tauri::command]
-pub async fn load_startup_state() -> Result<Option<StartupState>, String> {
+pub async fn load_startup_state(settings_state: State<'_, SettingsState>) -> Result<Option<StartupState>, String> {
     let path = get_startup_state_path()?;
 
-    if !path.exists() {
+    let state = {
+        let _guard = STARTUP_FILE_LOCK.lock().map_err(|e| format!("Failed to lock startup state: {}", e))?;
+        read_startup_state(&path)?
+    };
+    let Some(mut state) = state else {
         eprintln!("📂 No startup state file found at {}", path.display());
         return Ok(None);
-    }
-
-    let contents = fs::read_to_string(&path)
-        .map_err(|e| format!("Failed to read startup state file: {}", e))?;
-
-    let state: StartupState = serde_json::from_str(&contents)
-        .map_err(|e| format!("Failed to parse startup state file: {}", e))?;
+    };
+    state.display = settings_state.settings.lock()
+        .map_err(|e| format!("Failed to acquire settings lock: {}", e))?
+        .display
+        .clone();
 
     eprintln!("✅ Loaded startup state from {}", path.display());
     Ok(Some(state))
 }
 
+/// Save the UI state of the project at `projectPath`
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn save_project_ui_state(projectPath: String, state: ProjectUiState) -> Result<(), BertError> {
+    let _guard =
+        STARTUP_FILE_LOCK.lock().map_err(|e| BertError::Internal(format!("Failed to lock startup state: {}", e)))?;
+    let path = get_startup_state_path().map_err(BertError::Io)?;
+
+    let mut startup = read_startup_state(&path).map_err(BertError::Io)?.unwrap_or_default();
+    startup.projects.insert(project_key(&projectPath), state);
+    write_startup_state(&path, &startup).map_err(BertError::Io)
+}
+
+/// Load the UI state saved for the project at `projectPath`
+///
+/// # Returns
+/// None if nothing was saved for the project yet
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn load_project_ui_state(projectPath: String) -> Result<Option<ProjectUiState>, BertError> {
+    let _guard =
+        STARTUP_FILE_LOCK.lock().map_err(|e| BertError::Internal(format!("Failed to lock startup state: {}", e)))?;
+    let path = get_startup_state_path().map_err(BertError::Io)?;
+
+    let state = read_startup_state(&path).map_err(BertError::Io)?.and_then(|mut startup| startup.projects.remove(&project_key(&projectPath)));
+    eprintln!("📂 UI state for {}: {}", projectPath, if state.is_some() { "restored" } else { "none saved" });
+    Ok(state)
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -245,6 +386,8 @@ mod tests {
                 wbs_panel_width: Some(300.0),
                 current_view: "gantt".to_string(),
             },
+            display: DisplayPrefs::default(),
+            ..StartupState::default()
         };
 
         let json = serde_json::to_string(&state).unwrap();
@@ -288,6 +431,8 @@ mod tests {
             filters: FilterState::default(),
             sort: SortState::default(),
             ui: UiState::default(),
+            display: DisplayPrefs::default(),
+            ..StartupState::default()
         };
 
         // Serialize and deserialize
@@ -348,4 +493,42 @@ mod tests {
         assert!(json.contains("\"closedTimeFilter\":\"7d\""));
         assert!(json.contains("\"includeHierarchy\":false"));
     }
+
+    #[test]
+    fn test_flat_state_migrates_to_last_project() {
+        let mut flat = serde_json::to_value(StartupState::default()).unwrap();
+        let root = flat.as_object_mut().unwrap();
+        root.remove("version");
+        root.remove("projects");
+        root["filters"]["filterText"] = "owner:ann".into();
+        root["ui"]["collapsedIds"] = serde_json::json!(["bp6-1"]);
+        let contents = flat.to_string();
+
+        let (state, migrated) = parse_startup_state(&contents, Some("/work/bp6/")).unwrap();
+        assert!(migrated);
+        assert_eq!(state.version, STARTUP_STATE_VERSION);
+        let project = &state.projects["/work/bp6"];
+        assert_eq!(project.filters.filter_text, "owner:ann");
+        assert_eq!(project.collapsed_ids, vec!["bp6-1"]);
+        assert_eq!(project.selected_bead_id, None);
+
+        // Without a known project there is nothing to file it under
+        let (state, migrated) = parse_startup_state(&contents, None).unwrap();
+        assert!(migrated && state.projects.is_empty());
+
+        // Current files are read as they are
+        let current = serde_json::to_string(&state).unwrap();
+        let (_, migrated) = parse_startup_state(&current, Some("/work/bp6")).unwrap();
+        assert!(!migrated);
+    }
+
+    #[test]
+    fn test_project_ui_state_defaults_missing_fields() {
+        let state: ProjectUiState =
+            serde_json::from_str(r#"{"selectedBeadId":"bp6-2","scroll":{"top":120.0,"left":0.0}}"#).unwrap();
+        assert_eq!(state.selected_bead_id.as_deref(), Some("bp6-2"));
+        assert_eq!(state.scroll, ScrollPosition { top: 120.0, left: 0.0 });
+        assert!(state.filters.include_hierarchy);
+        assert!(state.collapsed_ids.is_empty());
+    }
 }
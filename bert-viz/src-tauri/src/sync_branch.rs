@@ -0,0 +1,296 @@
+//! Sync-branch management
+//!
+//! In sync-branch mode bd keeps `.beads/issues.jsonl` on a dedicated branch,
+//! checked out in a worktree at `.git/beads-worktrees/<branch>`, so bead
+//! edits don't pollute code branches. `find_beads_file_from` already reads
+//! that worktree; these commands let remote-mode users keep it in step with
+//! the remote without leaving the app:
+//!
+//! - `get_sync_status`: ahead/behind the remote sync branch, uncommitted bead
+//!   changes, and conflicts left by a pull
+//! - `pull_sync_branch` / `push_sync_branch`: merge the remote branch in, and
+//!   commit and publish local edits
+//! - `set_sync_branch`: switch the project to sync-branch mode
+//! - `resolve_sync_conflict`: finish or abort a conflicted pull. `newest`
+//!   merges the beads file line by line, keeping each bead's most recently
+//!   updated version.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+use crate::error::BertError;
+use crate::git::git;
+use crate::project::{context_for, ProjectContext};
+
+/// Remote used when the sync branch has no upstream yet
+const DEFAULT_REMOTE: &str = "origin";
+
+/// Where the project's beads sync
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncStatus {
+    pub branch: String,
+    /// Worktree the sync branch is checked out in
+    pub worktree: PathBuf,
+    /// Remote tracking branch, e.g. `origin/beads-sync`
+    pub upstream: Option<String>,
+    /// Local commits not pushed yet
+    pub ahead: u32,
+    /// Remote commits not pulled yet (as of the last fetch)
+    pub behind: u32,
+    /// The beads file has uncommitted changes
+    pub dirty: bool,
+    /// Files left unmerged by a pull; resolve before pushing
+    pub conflicts: Vec<String>,
+}
+
+/// How to finish a pull that left conflicts
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictStrategy {
+    /// Merge bead by bead, keeping the version with the latest `updated_at`
+    Newest,
+    /// Keep the local side
+    Ours,
+    /// Take the remote side
+    Theirs,
+    /// Abort the merge and return to the state before the pull
+    Abort,
+}
+
+/// Sync branch and worktree of the project, when it is in sync-branch mode
+fn sync_checkout(ctx: &ProjectContext) -> Result<(String, PathBuf), BertError> {
+    let repo_root = ctx.repo_root()?;
+    let branch = crate::bd::get_sync_branch_name(&repo_root).ok_or_else(|| {
+        BertError::InvalidInput("No sync branch configured; set one with set_sync_branch".to_string())
+    })?;
+    let worktree = repo_root.join(".git").join("beads-worktrees").join(&branch);
+    if !worktree.exists() {
+        return Err(BertError::NotFound(format!(
+            "Sync worktree {} does not exist; run `bd sync` once to create it",
+            worktree.display()
+        )));
+    }
+    Ok((branch, worktree))
+}
+
+/// Files with unresolved conflicts in `worktree`
+fn unmerged_files(worktree: &Path) -> Result<Vec<String>, BertError> {
+    let output = git(worktree, &["diff", "--name-only", "--diff-filter=U"])?;
+    Ok(output.lines().filter(|l| !l.is_empty()).map(str::to_string).collect())
+}
+
+/// Parse `git rev-list --left-right --count` output into (ahead, behind)
+fn parse_counts(output: &str) -> (u32, u32) {
+    let mut counts = output.split_whitespace().map(|n| n.parse().unwrap_or(0));
+    (counts.next().unwrap_or(0), counts.next().unwrap_or(0))
+}
+
+fn read_status(branch: String, worktree: PathBuf) -> Result<SyncStatus, BertError> {
+    let upstream = git(&worktree, &["rev-parse", "--abbrev-ref", "--symbolic-full-name", "@{upstream}"]).ok();
+    let (ahead, behind) = match upstream {
+        Some(_) => parse_counts(&git(&worktree, &["rev-list", "--left-right", "--count", "HEAD...@{upstream}"])?),
+        None => (0, 0),
+    };
+    let dirty = !git(&worktree, &["status", "--porcelain", "--", ".beads"])?.is_empty();
+    let conflicts = unmerged_files(&worktree)?;
+    Ok(SyncStatus { branch, worktree, upstream, ahead, behind, dirty, conflicts })
+}
+
+/// Merge two versions of the beads file: every bead of either side, the one
+/// with the later `updated_at` when both have it, in `ours` order with new
+/// beads from `theirs` appended
+fn merge_newest(ours: &str, theirs: &str) -> Result<Vec<Map<String, Value>>, BertError> {
+    let parse = |text: &str| -> Result<Vec<Map<String, Value>>, BertError> {
+        text.lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| serde_json::from_str(l).map_err(|e| BertError::Parse(format!("Invalid bead line in conflict: {}", e))))
+            .collect()
+    };
+    let id_of = |obj: &Map<String, Value>| obj.get("id").and_then(Value::as_str).map(str::to_string);
+    // Compared as instants: bd writes local offsets, which don't order as strings.
+    // A missing or invalid timestamp is older than any valid one.
+    let updated = |obj: &Map<String, Value>| {
+        obj.get("updated_at").and_then(Value::as_str).and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+    };
+
+    let mut merged = parse(ours)?;
+    let index: HashMap<String, usize> =
+        merged.iter().enumerate().filter_map(|(i, obj)| Some((id_of(obj)?, i))).collect();
+    for theirs in parse(theirs)? {
+        match id_of(&theirs).and_then(|id| index.get(&id)) {
+            Some(&i) if updated(&theirs) > updated(&merged[i]) => merged[i] = theirs,
+            Some(_) => {}
+            None => merged.push(theirs),
+        }
+    }
+    Ok(merged)
+}
+
+/// Ahead/behind of the sync branch versus its remote, uncommitted bead
+/// changes and pull conflicts. With `fetch`, the remote is fetched first.
+#[tauri::command]
+pub fn get_sync_status(fetch: Option<bool>, window: tauri::Window, project_handle: Option<String>) -> Result<SyncStatus, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    let (branch, worktree) = sync_checkout(&ctx)?;
+    if fetch.unwrap_or(false) {
+        git(&worktree, &["fetch", "--quiet"])?;
+    }
+    read_status(branch, worktree)
+}
+
+/// Use `branch` as the project's sync branch (`bd config set sync.branch`)
+#[tauri::command]
+pub fn set_sync_branch(branch: String, window: tauri::Window, project_handle: Option<String>) -> Result<(), BertError> {
+    let branch = branch.trim();
+    if branch.is_empty() || branch.contains(char::is_whitespace) {
+        return Err(BertError::InvalidInput(format!("Invalid sync branch name '{}'", branch)));
+    }
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    crate::bd::execute_bd(&ctx, vec!["config".into(), "set".into(), "sync.branch".into(), branch.to_string()])?;
+    // The beads file moves to the sync worktree once bd creates it
+    ctx.clear_beads_path_cache();
+    eprintln!("🔀 Sync branch of {} set to {}", ctx.project_path().display(), branch);
+    Ok(())
+}
+
+/// Fetch the remote sync branch and merge it in. A conflicted merge is left in
+/// place and reported in `conflicts` for `resolve_sync_conflict`.
+#[tauri::command]
+pub fn pull_sync_branch(window: tauri::Window, project_handle: Option<String>, app_handle: AppHandle) -> Result<SyncStatus, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    let (branch, worktree) = sync_checkout(&ctx)?;
+    if !unmerged_files(&worktree)?.is_empty() {
+        return Err(BertError::InvalidInput("Resolve the conflicts of the last pull first".to_string()));
+    }
+
+    // Local edits would block the merge
+    let beads_path = ctx.beads_path()?;
+    let entry = crate::autocommit::commit_beads_file(&beads_path, "bd: sync local changes");
+    if let Some(error) = entry.error {
+        return Err(BertError::Git(error));
+    }
+
+    if let Err(e) = git(&worktree, &["pull", "--no-rebase", "--no-edit"]) {
+        let conflicts = unmerged_files(&worktree)?;
+        if conflicts.is_empty() {
+            return Err(e);
+        }
+        eprintln!("⚠️  Pull of {} left conflicts in {}", branch, conflicts.join(", "));
+    }
+
+    ctx.clear_beads_path_cache();
+    crate::project::emit_beads_updated(&app_handle, &ctx);
+    read_status(branch, worktree)
+}
+
+/// Commit pending bead changes and push the sync branch, setting its
+/// upstream on the first push
+#[tauri::command]
+pub fn push_sync_branch(window: tauri::Window, project_handle: Option<String>) -> Result<SyncStatus, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    let (branch, worktree) = sync_checkout(&ctx)?;
+    let conflicts = unmerged_files(&worktree)?;
+    if !conflicts.is_empty() {
+        return Err(BertError::InvalidInput(format!("Resolve the conflicts in {} before pushing", conflicts.join(", "))));
+    }
+
+    let entry = crate::autocommit::commit_beads_file(&ctx.beads_path()?, "bd: sync local changes");
+    if let Some(error) = entry.error {
+        return Err(BertError::Git(error));
+    }
+
+    let has_upstream = git(&worktree, &["rev-parse", "--abbrev-ref", "@{upstream}"]).is_ok();
+    if has_upstream {
+        git(&worktree, &["push", "--quiet"])?;
+    } else {
+        git(&worktree, &["push", "--quiet", "--set-upstream", DEFAULT_REMOTE, &branch])?;
+    }
+    eprintln!("🔀 Pushed sync branch {}", branch);
+    read_status(branch, worktree)
+}
+
+/// Finish a conflicted pull with `strategy`, or abort it
+#[tauri::command]
+pub fn resolve_sync_conflict(
+    strategy: ConflictStrategy,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    app_handle: AppHandle,
+) -> Result<SyncStatus, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    let (branch, worktree) = sync_checkout(&ctx)?;
+    let conflicts = unmerged_files(&worktree)?;
+    if conflicts.is_empty() {
+        return Err(BertError::InvalidInput("The sync branch has no conflicts".to_string()));
+    }
+
+    if strategy == ConflictStrategy::Abort {
+        git(&worktree, &["merge", "--abort"])?;
+    } else {
+        for file in &conflicts {
+            match strategy {
+                ConflictStrategy::Newest if file.ends_with(".jsonl") => {
+                    let ours = git(&worktree, &["show", &format!(":2:{}", file)])?;
+                    let theirs = git(&worktree, &["show", &format!(":3:{}", file)])?;
+                    crate::bd::write_jsonl_atomic(&worktree.join(file), &merge_newest(&ours, &theirs)?)?;
+                }
+                // Other files have no bead structure to merge by
+                ConflictStrategy::Newest | ConflictStrategy::Theirs => {
+                    git(&worktree, &["checkout", "--theirs", "--", file])?;
+                }
+                _ => {
+                    git(&worktree, &["checkout", "--ours", "--", file])?;
+                }
+            }
+            git(&worktree, &["add", "--", file])?;
+        }
+        git(&worktree, &["commit", "--no-edit"])?;
+    }
+    eprintln!("🔀 Resolved sync conflicts of {} with {:?}", branch, strategy);
+
+    ctx.clear_beads_path_cache();
+    crate::project::emit_beads_updated(&app_handle, &ctx);
+    read_status(branch, worktree)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_counts() {
+        assert_eq!(parse_counts("3\t1"), (3, 1));
+        assert_eq!(parse_counts(""), (0, 0));
+    }
+
+    #[test]
+    fn test_merge_newest_keeps_latest_version_of_each_bead() {
+        let ours = concat!(
+            r#"{"id":"bp6-1","title":"ours","updated_at":"2026-10-02T10:00:00Z"}"#, "\n",
+            r#"{"id":"bp6-2","title":"ours","updated_at":"2026-10-05T10:00:00Z"}"#, "\n",
+            r#"{"id":"bp6-3","title":"only ours"}"#, "\n",
+        );
+        let theirs = concat!(
+            r#"{"id":"bp6-1","title":"theirs","updated_at":"2026-10-03T10:00:00Z"}"#, "\n",
+            r#"{"id":"bp6-2","title":"theirs","updated_at":"2026-10-04T10:00:00Z"}"#, "\n",
+            r#"{"id":"bp6-4","title":"only theirs"}"#, "\n",
+        );
+        let merged = merge_newest(ours, theirs).unwrap();
+        let summary: Vec<(&str, &str)> =
+            merged.iter().map(|o| (o["id"].as_str().unwrap(), o["title"].as_str().unwrap())).collect();
+        assert_eq!(
+            summary,
+            vec![("bp6-1", "theirs"), ("bp6-2", "ours"), ("bp6-3", "only ours"), ("bp6-4", "only theirs")]
+        );
+        assert!(matches!(merge_newest("<<<<<<< HEAD", ""), Err(BertError::Parse(_))));
+
+        // 10:00+02:00 is before 09:00Z, though it sorts after it as a string
+        let ours = r#"{"id":"bp6-1","title":"ours","updated_at":"2026-10-05T10:00:00+02:00"}"#;
+        let theirs = r#"{"id":"bp6-1","title":"theirs","updated_at":"2026-10-05T09:00:00Z"}"#;
+        assert_eq!(merge_newest(ours, theirs).unwrap()[0]["title"], "theirs");
+    }
+}
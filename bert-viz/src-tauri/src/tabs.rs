@@ -0,0 +1,152 @@
+//! Project tabs: more than one project in a window
+//!
+//! Besides its own project, a window can open others in tabs instead of new
+//! OS windows. `open_project_tab` registers the project with `ProjectContexts`
+//! under a new handle, which gives the tab a (shared) beads watcher and its own
+//! view model cache. Data commands take the handle as `projectHandle` and then
+//! work on the tab's project; without it they use the window's own project.
+//! Tabs close with `close_project_tab` or when their window is destroyed.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::error::BertError;
+use crate::project::{owner_window, tab_key, ProjectContexts};
+
+/// A project open in a tab
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectTab {
+    /// Passed as `projectHandle` to data commands
+    pub handle: String,
+    pub name: String,
+    pub project_path: PathBuf,
+}
+
+/// Managed Tauri state: the project tabs of every window, in the order they
+/// were opened
+pub struct ProjectManager {
+    tabs: Mutex<Vec<ProjectTab>>,
+    next_id: AtomicU64,
+}
+
+impl ProjectManager {
+    pub fn new() -> Self {
+        ProjectManager {
+            tabs: Mutex::new(Vec::new()),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, Vec<ProjectTab>>, BertError> {
+        self.tabs
+            .lock()
+            .map_err(|e| BertError::Internal(format!("Failed to lock project tabs: {}", e)))
+    }
+
+    /// Open `path` in a new tab of window `label`, or return the tab that
+    /// already has it open
+    pub fn open(&self, label: &str, path: &Path, app_handle: &AppHandle) -> Result<ProjectTab, BertError> {
+        if let Some(tab) = self.lock()?.iter().find(|t| owner_window(&t.handle) == label && t.project_path == path) {
+            return Ok(tab.clone());
+        }
+
+        let handle = tab_key(label, self.next_id.fetch_add(1, Ordering::Relaxed));
+        let contexts = app_handle
+            .try_state::<ProjectContexts>()
+            .ok_or_else(|| BertError::Internal("Project contexts are not initialized".to_string()))?;
+        let context = contexts.open(&handle, path, app_handle)?;
+
+        let tab = ProjectTab {
+            handle,
+            name: crate::project_name(&path.to_string_lossy()),
+            project_path: context.project_path().to_path_buf(),
+        };
+        self.lock()?.push(tab.clone());
+        Ok(tab)
+    }
+
+    /// Tabs of window `label`
+    pub fn list(&self, label: &str) -> Result<Vec<ProjectTab>, BertError> {
+        Ok(self.lock()?.iter().filter(|t| owner_window(&t.handle) == label).cloned().collect())
+    }
+
+    /// Close a tab, dropping its watcher registration and view model cache.
+    /// Returns false if no such tab was open.
+    pub fn close(&self, app_handle: &AppHandle, handle: &str) -> Result<bool, BertError> {
+        let mut tabs = self.lock()?;
+        let Some(index) = tabs.iter().position(|t| t.handle == handle) else {
+            return Ok(false);
+        };
+        tabs.remove(index);
+        drop(tabs);
+
+        if let Some(contexts) = app_handle.try_state::<ProjectContexts>() {
+            contexts.close(handle);
+        }
+        if let Some(project_state) = app_handle.try_state::<crate::view_cache::ProjectState>() {
+            project_state.remove(handle);
+        }
+        Ok(true)
+    }
+
+    /// Close every tab of a window that is being destroyed
+    pub fn close_window(&self, app_handle: &AppHandle, label: &str) {
+        let Ok(tabs) = self.list(label) else { return };
+        for tab in tabs {
+            let _ = self.close(app_handle, &tab.handle);
+        }
+    }
+}
+
+impl Default for ProjectManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Open a project in a new tab of this window and return its handle
+#[tauri::command]
+pub fn open_project_tab(
+    path: String,
+    window: tauri::Window,
+    app_handle: AppHandle,
+    manager: State<'_, ProjectManager>,
+) -> Result<ProjectTab, BertError> {
+    eprintln!("📂 open_project_tab: Opening {} in a tab of {}", path, window.label());
+    let tab = manager.open(window.label(), Path::new(&path), &app_handle)?;
+    crate::record_project_opened(&path)?;
+    if let Some(ctx) = app_handle.state::<ProjectContexts>().get_open(&tab.handle) {
+        crate::recurrence::check_in_background(&app_handle, ctx);
+    }
+    eprintln!("✅ open_project_tab: {} has {}", tab.handle, tab.project_path.display());
+
+    let _ = app_handle.emit("projects-updated", ());
+    Ok(tab)
+}
+
+/// Close one of this window's project tabs
+#[tauri::command]
+pub fn close_project_tab(
+    project_handle: String,
+    window: tauri::Window,
+    app_handle: AppHandle,
+    manager: State<'_, ProjectManager>,
+) -> Result<(), BertError> {
+    if owner_window(&project_handle) != window.label() {
+        return Err(BertError::InvalidInput(format!("{} is not a project tab of this window", project_handle)));
+    }
+    if !manager.close(&app_handle, &project_handle)? {
+        return Err(BertError::NotFound(format!("Project tab {} is not open", project_handle)));
+    }
+    Ok(())
+}
+
+/// Project tabs of this window, in the order they were opened
+#[tauri::command]
+pub fn list_project_tabs(window: tauri::Window, manager: State<'_, ProjectManager>) -> Result<Vec<ProjectTab>, BertError> {
+    manager.list(window.label())
+}
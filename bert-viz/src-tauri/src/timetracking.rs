@@ -0,0 +1,404 @@
+//! Time tracking per bead
+//!
+//! `start_timer` starts the clock on a bead and `stop_timer` turns it into a
+//! time entry; starting a timer on another bead stops the running one first.
+//! Entries are kept per project in ~/.bp6/time/<project>.jsonl, the running
+//! one without an end, so a timer survives restarts. The tracked minutes show
+//! up as `actualMinutes` on the view model's nodes, and `get_time_entries`
+//! compares them with the estimates.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use uuid::Uuid;
+
+use crate::baseline::project_store_key;
+use crate::error::BertError;
+use crate::project::context_for;
+use crate::{Bead, BeadNode};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeEntry {
+    pub id: String,
+    pub bead_id: String,
+    pub owner: Option<String>,
+    pub started_at: String,
+    /// None while the timer runs
+    pub ended_at: Option<String>,
+    pub minutes: u64,
+}
+
+/// Which entries `get_time_entries` returns; every field narrows it down
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeEntryFilter {
+    #[serde(default)]
+    pub bead_id: Option<String>,
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// Inclusive range of start dates (YYYY-MM-DD)
+    #[serde(default)]
+    pub start: Option<String>,
+    #[serde(default)]
+    pub end: Option<String>,
+}
+
+/// Tracked time of one bead against its estimate
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EstimateComparison {
+    pub bead_id: String,
+    pub title: Option<String>,
+    /// Minutes
+    pub estimate: Option<u32>,
+    pub actual_minutes: u64,
+    /// Positive when over the estimate; None without an estimate
+    pub variance_percent: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeReport {
+    /// Finished entries matching the filter, oldest first
+    pub entries: Vec<TimeEntry>,
+    pub total_minutes: u64,
+    /// One per bead of `entries`, by bead ID
+    pub comparisons: Vec<EstimateComparison>,
+    pub running: Option<TimeEntry>,
+}
+
+/// Time log of the project owning `beads_path` (~/.bp6/time/<project key>.jsonl)
+fn time_file(beads_path: &Path) -> Result<PathBuf, BertError> {
+    let home = dirs::home_dir().ok_or_else(|| BertError::Internal("Failed to get home directory".to_string()))?;
+    let project_path = crate::history::project_path_for(beads_path);
+    Ok(home.join(".bp6").join("time").join(format!("{}.jsonl", project_store_key(&project_path))))
+}
+
+fn read_entries(path: &Path) -> Result<Vec<TimeEntry>, BertError> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents = fs::read_to_string(path)
+        .map_err(|e| BertError::Io(format!("Failed to read {}: {}", path.display(), e)))?;
+
+    let mut entries = Vec::new();
+    for line in contents.lines().filter(|l| !l.trim().is_empty()) {
+        match serde_json::from_str::<TimeEntry>(line) {
+            Ok(entry) => entries.push(entry),
+            Err(e) => eprintln!("⚠️  Skipping malformed time entry in {}: {}", path.display(), e),
+        }
+    }
+    Ok(entries)
+}
+
+/// Time entries of the project of `beads_path`
+pub(crate) fn entries_for(beads_path: &Path) -> Result<Vec<TimeEntry>, BertError> {
+    read_entries(&time_file(beads_path)?)
+}
+
+/// Add `restored` entries to the project of `beads_path`, skipping the ones
+/// it already has
+pub(crate) fn restore_entries(beads_path: &Path, restored: &[TimeEntry]) -> Result<(), BertError> {
+    if restored.is_empty() {
+        return Ok(());
+    }
+    let path = time_file(beads_path)?;
+    let mut entries = read_entries(&path)?;
+    let known: HashSet<String> = entries.iter().map(|e| e.id.clone()).collect();
+    entries.extend(restored.iter().filter(|e| !known.contains(&e.id)).cloned());
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| BertError::Io(format!("Failed to create {}: {}", dir.display(), e)))?;
+    }
+    write_entries(&path, &entries)
+}
+
+fn append_entry(path: &Path, entry: &TimeEntry) -> Result<(), BertError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| BertError::Io(format!("Failed to create {}: {}", dir.display(), e)))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| BertError::Io(format!("Failed to open {}: {}", path.display(), e)))?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)
+        .map_err(|e| BertError::Io(format!("Failed to append to {}: {}", path.display(), e)))
+}
+
+fn write_entries(path: &Path, entries: &[TimeEntry]) -> Result<(), BertError> {
+    let mut contents = String::new();
+    for entry in entries {
+        contents.push_str(&serde_json::to_string(entry)?);
+        contents.push('\n');
+    }
+    let tmp = path.with_extension("jsonl.tmp");
+    fs::write(&tmp, contents).map_err(|e| BertError::Io(format!("Failed to write {}: {}", tmp.display(), e)))?;
+    fs::rename(&tmp, path).map_err(|e| BertError::Io(format!("Failed to replace {}: {}", path.display(), e)))
+}
+
+fn parse_time(ts: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(ts).ok().map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Minutes between two timestamps, rounded to the nearest minute
+fn minutes_between(start: &str, end: DateTime<Utc>) -> u64 {
+    parse_time(start)
+        .map(|start| ((end - start).num_seconds().max(0) as u64 + 30) / 60)
+        .unwrap_or(0)
+}
+
+/// Stop the running timer in `entries`, if any, returning the finished entry
+fn stop_running(entries: &mut [TimeEntry], now: DateTime<Utc>) -> Option<TimeEntry> {
+    let running = entries.iter_mut().find(|e| e.ended_at.is_none())?;
+    running.minutes = minutes_between(&running.started_at, now);
+    running.ended_at = Some(now.to_rfc3339());
+    Some(running.clone())
+}
+
+fn parse_date(raw: &Option<String>, label: &str) -> Result<Option<NaiveDate>, BertError> {
+    raw.as_deref()
+        .filter(|s| !s.trim().is_empty())
+        .map(|s| {
+            NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d")
+                .map_err(|_| BertError::InvalidInput(format!("Invalid {} date '{}'", label, s)))
+        })
+        .transpose()
+}
+
+fn build_report(entries: Vec<TimeEntry>, beads: &[Bead], filter: &TimeEntryFilter) -> Result<TimeReport, BertError> {
+    let start = parse_date(&filter.start, "start")?;
+    let end = parse_date(&filter.end, "end")?;
+
+    let running = entries.iter().find(|e| e.ended_at.is_none()).cloned();
+    let entries: Vec<TimeEntry> = entries
+        .into_iter()
+        .filter(|e| e.ended_at.is_some())
+        .filter(|e| filter.bead_id.as_ref().is_none_or(|id| *id == e.bead_id))
+        .filter(|e| filter.owner.as_ref().is_none_or(|owner| e.owner.as_ref() == Some(owner)))
+        .filter(|e| {
+            let day = parse_time(&e.started_at).map(|t| t.date_naive());
+            start.is_none_or(|s| day.is_some_and(|d| d >= s)) && end.is_none_or(|end| day.is_some_and(|d| d <= end))
+        })
+        .collect();
+
+    let mut actuals: BTreeMap<&str, u64> = BTreeMap::new();
+    for entry in &entries {
+        *actuals.entry(entry.bead_id.as_str()).or_default() += entry.minutes;
+    }
+    let by_id: HashMap<&str, &Bead> = beads.iter().map(|b| (b.id.as_str(), b)).collect();
+    let comparisons = actuals
+        .iter()
+        .map(|(&bead_id, &actual_minutes)| {
+            let bead = by_id.get(bead_id);
+            let estimate = bead.and_then(|b| b.estimate).filter(|e| *e > 0);
+            EstimateComparison {
+                bead_id: bead_id.to_string(),
+                title: bead.map(|b| b.title.clone()),
+                estimate,
+                actual_minutes,
+                variance_percent: estimate.map(|e| (actual_minutes as f64 - e as f64) * 100.0 / e as f64),
+            }
+        })
+        .collect();
+
+    Ok(TimeReport {
+        total_minutes: entries.iter().map(|e| e.minutes).sum(),
+        entries,
+        comparisons,
+        running,
+    })
+}
+
+/// Tracked minutes per bead of the project owning `beads_path`, finished
+/// entries only. Empty when the log can't be read.
+pub fn actual_minutes(beads_path: &Path) -> BTreeMap<String, u64> {
+    let entries = match time_file(beads_path).and_then(|path| read_entries(&path)) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("⚠️  Failed to read tracked time: {}", e);
+            return BTreeMap::new();
+        }
+    };
+    let mut actuals = BTreeMap::new();
+    for entry in entries.iter().filter(|e| e.ended_at.is_some()) {
+        *actuals.entry(entry.bead_id.clone()).or_default() += entry.minutes;
+    }
+    actuals
+}
+
+/// Set `actual_minutes` on every node: its own tracked time plus that of
+/// the nodes below it
+pub fn apply_actuals(nodes: &mut [BeadNode], actuals: &BTreeMap<String, u64>) -> u64 {
+    let mut sum = 0;
+    for node in nodes {
+        let own = actuals.get(&node.id).copied().unwrap_or(0);
+        node.actual_minutes = own + apply_actuals(&mut node.children, actuals);
+        sum += node.actual_minutes;
+    }
+    sum
+}
+
+/// Start tracking time on a bead, stopping the running timer first. Returns
+/// the running entry (the existing one if this bead's timer already runs).
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn start_timer(beadId: String, window: tauri::Window, project_handle: Option<String>, app_handle: AppHandle) -> Result<TimeEntry, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    // The time file is outside the project, so it is checked here
+    ctx.ensure_writable()?;
+    crate::bead_index::get_bead_internal(&ctx, &beadId)?;
+    let path = time_file(&ctx.beads_path()?)?;
+    let mut entries = read_entries(&path)?;
+
+    if let Some(running) = entries.iter().find(|e| e.ended_at.is_none()) {
+        if running.bead_id == beadId {
+            return Ok(running.clone());
+        }
+    }
+    let now = Utc::now();
+    if let Some(stopped) = stop_running(&mut entries, now) {
+        write_entries(&path, &entries)?;
+        eprintln!("⏱️  Stopped timer on {} ({} min)", stopped.bead_id, stopped.minutes);
+        crate::project::emit_beads_updated(&app_handle, &ctx);
+    }
+
+    let entry = TimeEntry {
+        id: Uuid::new_v4().to_string(),
+        bead_id: beadId,
+        owner: crate::comments::author(&ctx).0,
+        started_at: now.to_rfc3339(),
+        ended_at: None,
+        minutes: 0,
+    };
+    append_entry(&path, &entry)?;
+    eprintln!("⏱️  Started timer on {}", entry.bead_id);
+    Ok(entry)
+}
+
+/// Stop the running timer. Returns the finished entry, or None when no
+/// timer was running.
+#[tauri::command]
+pub fn stop_timer(window: tauri::Window, project_handle: Option<String>, app_handle: AppHandle) -> Result<Option<TimeEntry>, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    ctx.ensure_writable()?;
+    let path = time_file(&ctx.beads_path()?)?;
+    let mut entries = read_entries(&path)?;
+
+    let Some(stopped) = stop_running(&mut entries, Utc::now()) else { return Ok(None) };
+    write_entries(&path, &entries)?;
+    eprintln!("⏱️  Stopped timer on {} ({} min)", stopped.bead_id, stopped.minutes);
+    // actualMinutes changed
+    crate::project::emit_beads_updated(&app_handle, &ctx);
+    Ok(Some(stopped))
+}
+
+/// Finished time entries matching `filter`, with tracked time against the
+/// estimate for each bead and the running timer
+#[tauri::command]
+pub fn get_time_entries(filter: Option<TimeEntryFilter>, window: tauri::Window, project_handle: Option<String>) -> Result<TimeReport, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    let entries = read_entries(&time_file(&ctx.beads_path()?)?)?;
+    let beads = crate::bd::load_beads(&ctx)?;
+    build_report(entries, &beads, &filter.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn at(ts: &str) -> DateTime<Utc> {
+        parse_time(ts).unwrap()
+    }
+
+    fn entry(bead_id: &str, owner: &str, started_at: &str, minutes: u64) -> TimeEntry {
+        TimeEntry {
+            id: Uuid::new_v4().to_string(),
+            bead_id: bead_id.to_string(),
+            owner: Some(owner.to_string()),
+            started_at: started_at.to_string(),
+            ended_at: Some(started_at.to_string()),
+            minutes,
+        }
+    }
+
+    fn bead(id: &str, estimate: Option<u32>) -> Bead {
+        crate::test_bead(serde_json::json!({
+            "id": id,
+            "estimate": estimate,
+        }))
+    }
+
+    #[test]
+    fn test_stop_running_records_minutes() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("project.jsonl");
+        let mut running = entry("a", "ann", "2026-03-01T10:00:00Z", 0);
+        running.ended_at = None;
+        append_entry(&path, &entry("b", "ann", "2026-02-28T10:00:00Z", 30)).unwrap();
+        append_entry(&path, &running).unwrap();
+
+        let mut entries = read_entries(&path).unwrap();
+        let stopped = stop_running(&mut entries, at("2026-03-01T11:29:40Z")).unwrap();
+        assert_eq!((stopped.bead_id.as_str(), stopped.minutes), ("a", 90));
+        write_entries(&path, &entries).unwrap();
+
+        let mut entries = read_entries(&path).unwrap();
+        assert!(entries.iter().all(|e| e.ended_at.is_some()));
+        assert!(stop_running(&mut entries, Utc::now()).is_none());
+    }
+
+    #[test]
+    fn test_report_filters_and_compares_with_estimates() {
+        let mut running = entry("b", "bob", "2026-03-03T09:00:00Z", 0);
+        running.ended_at = None;
+        let entries = vec![
+            entry("a", "ann", "2026-03-01T10:00:00Z", 90),
+            entry("a", "bob", "2026-03-02T10:00:00Z", 30),
+            entry("b", "ann", "2026-03-02T12:00:00Z", 45),
+            running,
+        ];
+        let beads = vec![bead("a", Some(60)), bead("b", None)];
+
+        let report = build_report(entries.clone(), &beads, &TimeEntryFilter::default()).unwrap();
+        assert_eq!(report.entries.len(), 3);
+        assert_eq!(report.total_minutes, 165);
+        assert_eq!(report.running.as_ref().map(|e| e.bead_id.as_str()), Some("b"));
+        assert_eq!(report.comparisons[0].actual_minutes, 120);
+        assert_eq!(report.comparisons[0].variance_percent, Some(100.0));
+        assert_eq!(report.comparisons[1].variance_percent, None);
+
+        let filter = TimeEntryFilter { owner: Some("ann".to_string()), start: Some("2026-03-02".to_string()), ..Default::default() };
+        let report = build_report(entries.clone(), &beads, &filter).unwrap();
+        assert_eq!(report.entries.iter().map(|e| e.bead_id.as_str()).collect::<Vec<_>>(), vec!["b"]);
+
+        let filter = TimeEntryFilter { start: Some("March".to_string()), ..Default::default() };
+        assert!(build_report(entries, &beads, &filter).is_err());
+    }
+
+    #[test]
+    fn test_actuals_roll_up_to_parents() {
+        let beads: Vec<serde_json::Value> = vec![
+            serde_json::json!({"id": "f", "title": "f", "status": "open", "priority": 2, "issue_type": "feature"}),
+            serde_json::json!({"id": "a", "title": "a", "status": "open", "priority": 2, "issue_type": "task",
+                "dependencies": [{"issue_id": "a", "depends_on_id": "f", "type": "parent-child"}]}),
+        ];
+        let beads: Vec<Bead> = beads.into_iter().map(|v| serde_json::from_value(v).unwrap()).collect();
+        let mut view_model = crate::bert_core::build_project_view_model(
+            beads,
+            &crate::FilterParams::default(),
+            &crate::schedule::SchedulingConfig::default(),
+            &crate::workflow::WorkflowConfig::default(),
+        );
+
+        let actuals = BTreeMap::from([("f".to_string(), 10), ("a".to_string(), 50)]);
+        assert_eq!(apply_actuals(&mut view_model.tree, &actuals), 60);
+        assert_eq!(view_model.tree[0].actual_minutes, 60);
+        assert_eq!(view_model.tree[0].children[0].actual_minutes, 50);
+    }
+}
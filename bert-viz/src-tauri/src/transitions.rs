@@ -0,0 +1,326 @@
+//! Status transition policy
+//!
+//! Statuses are free-form, so nothing stops a bead from being closed while its
+//! children are still open or its acceptance criteria unmet, started while
+//! its blockers are unfinished, or an epic from being reopened after it
+//! shipped. The policy stored under the `transitions` key of
+//! `.beads/bert-viz.json` sets, per kind of transition, whether it is allowed
+//! silently (`off`), allowed with a warning (`warn`) or rejected (`error`).
+//!
+//! `update_bead`, `close_bead`, `reopen_bead` and `claim_bead` check their
+//! operation first: rejected transitions fail with a `BertError` whose code
+//! names the transition, warnings are returned with the result. `force`
+//! applies a rejected transition anyway, reporting it as a warning.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Map;
+use std::path::Path;
+
+use crate::bd::BeadOp;
+use crate::error::BertError;
+use crate::project::{context_for, ProjectContext};
+use crate::schedule::load_config_section;
+use crate::workflow::{StatusCategory, WorkflowConfig};
+use crate::Bead;
+
+/// Key of the transition policy in the project config file
+const CONFIG_KEY: &str = "transitions";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TransitionKind {
+    /// Finishing a bead whose children aren't finished
+    CloseWithOpenChildren,
+    /// Finishing a bead with acceptance criteria not checked off
+    CloseWithUnmetAcceptance,
+    /// Starting a bead whose blockers aren't finished
+    StartWhileBlocked,
+    /// Reopening a finished epic
+    ReopenClosedEpic,
+}
+
+impl TransitionKind {
+    /// `BertError` code of a rejected transition
+    pub fn code(self) -> &'static str {
+        match self {
+            TransitionKind::CloseWithOpenChildren => "close_with_open_children",
+            TransitionKind::CloseWithUnmetAcceptance => "close_with_unmet_acceptance",
+            TransitionKind::StartWhileBlocked => "start_while_blocked",
+            TransitionKind::ReopenClosedEpic => "reopen_closed_epic",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Enforcement {
+    Off,
+    Warn,
+    Error,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TransitionPolicy {
+    pub close_with_open_children: Enforcement,
+    pub close_with_unmet_acceptance: Enforcement,
+    pub start_while_blocked: Enforcement,
+    pub reopen_closed_epic: Enforcement,
+}
+
+impl Default for TransitionPolicy {
+    fn default() -> Self {
+        TransitionPolicy {
+            close_with_open_children: Enforcement::Warn,
+            close_with_unmet_acceptance: Enforcement::Warn,
+            start_while_blocked: Enforcement::Warn,
+            reopen_closed_epic: Enforcement::Warn,
+        }
+    }
+}
+
+impl TransitionPolicy {
+    fn enforcement(&self, kind: TransitionKind) -> Enforcement {
+        match kind {
+            TransitionKind::CloseWithOpenChildren => self.close_with_open_children,
+            TransitionKind::CloseWithUnmetAcceptance => self.close_with_unmet_acceptance,
+            TransitionKind::StartWhileBlocked => self.start_while_blocked,
+            TransitionKind::ReopenClosedEpic => self.reopen_closed_epic,
+        }
+    }
+
+    /// Load the policy stored alongside `beads_path`, or the defaults
+    pub fn load_for_beads_file(beads_path: &Path) -> Result<Self, BertError> {
+        Ok(load_config_section(beads_path, CONFIG_KEY)?.unwrap_or_default())
+    }
+}
+
+/// A transition the policy doesn't allow silently
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TransitionViolation {
+    pub kind: TransitionKind,
+    pub enforcement: Enforcement,
+    pub bead_id: String,
+    pub from_status: String,
+    pub to_status: String,
+    /// The open children or unfinished blockers behind it
+    pub related_ids: Vec<String>,
+    pub message: String,
+}
+
+/// Bead and target status of a status-changing operation
+fn transition_of<'a>(op: &'a BeadOp, beads: &'a [Bead]) -> Option<(&'a Bead, &'a str)> {
+    let find = |id: &str| beads.iter().find(|b| b.id == id);
+    match op {
+        BeadOp::Update { bead } => Some((bead, bead.status.as_str())),
+        BeadOp::Close { bead_id, .. } => Some((find(bead_id)?, "closed")),
+        BeadOp::Reopen { bead_id } => Some((find(bead_id)?, "open")),
+        BeadOp::Claim { bead_id } => Some((find(bead_id)?, "in_progress")),
+        _ => None,
+    }
+}
+
+/// Transitions `op` makes that the policy doesn't allow silently, given the
+/// project's current `beads`
+pub fn evaluate(op: &BeadOp, beads: &[Bead], policy: &TransitionPolicy, workflow: &WorkflowConfig) -> Vec<TransitionViolation> {
+    let Some((bead, to)) = transition_of(op, beads) else { return Vec::new() };
+    // The stored status; an update carries the new one
+    let Some(from) = beads.iter().find(|b| b.id == bead.id).map(|b| b.status.as_str()) else {
+        return Vec::new();
+    };
+    let (from_category, to_category) = (workflow.category_of(from), workflow.category_of(to));
+    if from_category == to_category {
+        return Vec::new();
+    }
+    let unfinished = |id: &str| beads.iter().find(|b| b.id == id).is_some_and(|b| !workflow.is_done(&b.status));
+
+    let mut found: Vec<(TransitionKind, Vec<String>, String)> = Vec::new();
+    if to_category == StatusCategory::Done {
+        let open_children: Vec<String> = beads
+            .iter()
+            .filter(|b| b.dependencies.iter().any(|d| d.r#type == "parent-child" && d.depends_on_id == bead.id))
+            .filter(|b| !workflow.is_done(&b.status))
+            .map(|b| b.id.clone())
+            .collect();
+        if !open_children.is_empty() {
+            let message = format!("{} has {} open children: {}", bead.id, open_children.len(), open_children.join(", "));
+            found.push((TransitionKind::CloseWithOpenChildren, open_children, message));
+        }
+        let unmet = crate::acceptance::unmet(bead);
+        if !unmet.is_empty() {
+            let message = format!("{} has {} unmet acceptance criteria: {}", bead.id, unmet.len(), unmet.join("; "));
+            found.push((TransitionKind::CloseWithUnmetAcceptance, Vec::new(), message));
+        }
+    }
+    if to_category == StatusCategory::Doing {
+        let blockers: Vec<String> = bead
+            .dependencies
+            .iter()
+            .filter(|d| d.r#type == "blocks" && unfinished(&d.depends_on_id))
+            .map(|d| d.depends_on_id.clone())
+            .collect();
+        if !blockers.is_empty() {
+            let message = format!("{} is blocked by unfinished {}", bead.id, blockers.join(", "));
+            found.push((TransitionKind::StartWhileBlocked, blockers, message));
+        }
+    }
+    if bead.issue_type == "epic" && from_category == StatusCategory::Done {
+        found.push((TransitionKind::ReopenClosedEpic, Vec::new(), format!("Epic {} is already {}", bead.id, from)));
+    }
+
+    found
+        .into_iter()
+        .filter(|(kind, ..)| policy.enforcement(*kind) != Enforcement::Off)
+        .map(|(kind, related_ids, message)| TransitionViolation {
+            kind,
+            enforcement: policy.enforcement(kind),
+            bead_id: bead.id.clone(),
+            from_status: from.to_string(),
+            to_status: to.to_string(),
+            related_ids,
+            message,
+        })
+        .collect()
+}
+
+/// Check `op` against the project's policy. Fails on the first rejected
+/// transition unless `force` is set; otherwise returns the warnings.
+pub(crate) fn check(ctx: &ProjectContext, op: &BeadOp, force: bool) -> Result<Vec<TransitionViolation>, BertError> {
+    if !matches!(op, BeadOp::Update { .. } | BeadOp::Close { .. } | BeadOp::Reopen { .. } | BeadOp::Claim { .. }) {
+        return Ok(Vec::new());
+    }
+    let beads_path = ctx.beads_path()?;
+    let policy = TransitionPolicy::load_for_beads_file(&beads_path)?;
+    let workflow = WorkflowConfig::load_for_beads_file(&beads_path)?;
+    let violations = evaluate(op, &crate::bd::load_beads(ctx)?, &policy, &workflow);
+
+    match violations.iter().find(|v| v.enforcement == Enforcement::Error) {
+        Some(rejected) if !force => Err(BertError::Transition(rejected.kind, format!(
+            "{} (pass force to do it anyway)",
+            rejected.message
+        ))),
+        _ => {
+            for violation in &violations {
+                eprintln!("⚠️  {}: {}", violation.kind.code(), violation.message);
+            }
+            Ok(violations)
+        }
+    }
+}
+
+/// Get the transition policy for the current project
+#[tauri::command]
+pub fn get_transition_policy(window: tauri::Window, project_handle: Option<String>) -> Result<TransitionPolicy, BertError> {
+    TransitionPolicy::load_for_beads_file(&context_for(&window, project_handle.as_deref())?.beads_path()?)
+}
+
+/// Save the transition policy for the current project
+#[tauri::command]
+pub fn save_transition_policy(policy: TransitionPolicy, window: tauri::Window, project_handle: Option<String>) -> Result<(), BertError> {
+    let beads_path = context_for(&window, project_handle.as_deref())?.beads_path()?;
+    let mut fields = Map::new();
+    fields.insert(CONFIG_KEY.to_string(), serde_json::to_value(&policy)?);
+    crate::schedule::merge_config_fields(&beads_path, fields)?;
+    eprintln!("🚦 Transition policy for {}: {:?}", beads_path.display(), policy);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn bead(id: &str, status: &str, issue_type: &str, deps: &[(&str, &str)]) -> Bead {
+        let dependencies: Vec<Value> =
+            deps.iter().map(|(on, ty)| json!({ "issue_id": id, "depends_on_id": on, "type": ty })).collect();
+        crate::test_bead(json!({
+            "id": id,
+            "status": status,
+            "issue_type": issue_type,
+            "dependencies": dependencies,
+        }))
+    }
+
+    fn project() -> Vec<Bead> {
+        vec![
+            bead("e", "closed", "epic", &[]),
+            bead("f", "open", "feature", &[("e", "parent-child")]),
+            bead("t1", "closed", "task", &[("f", "parent-child")]),
+            bead("t2", "open", "task", &[("f", "parent-child"), ("t1", "blocks")]),
+            bead("t3", "open", "task", &[("f", "parent-child"), ("t2", "blocks")]),
+        ]
+    }
+
+    fn kinds(violations: &[TransitionViolation]) -> Vec<(TransitionKind, Enforcement, Vec<&str>)> {
+        violations.iter().map(|v| (v.kind, v.enforcement, v.related_ids.iter().map(String::as_str).collect())).collect()
+    }
+
+    #[test]
+    fn test_evaluate_flags_each_transition() {
+        let beads = project();
+        let policy = TransitionPolicy { start_while_blocked: Enforcement::Error, ..Default::default() };
+        let workflow = WorkflowConfig::default();
+        let check = |op: BeadOp| evaluate(&op, &beads, &policy, &workflow);
+
+        assert_eq!(
+            kinds(&check(BeadOp::Close { bead_id: "f".into(), reason: None })),
+            vec![(TransitionKind::CloseWithOpenChildren, Enforcement::Warn, vec!["t2", "t3"])]
+        );
+        assert_eq!(
+            kinds(&check(BeadOp::Claim { bead_id: "t3".into() })),
+            vec![(TransitionKind::StartWhileBlocked, Enforcement::Error, vec!["t2"])]
+        );
+        // Its only blocker is closed
+        assert!(check(BeadOp::Claim { bead_id: "t2".into() }).is_empty());
+        assert_eq!(
+            kinds(&check(BeadOp::Reopen { bead_id: "e".into() })),
+            vec![(TransitionKind::ReopenClosedEpic, Enforcement::Warn, vec![])]
+        );
+        // Reopening a task is fine
+        assert!(check(BeadOp::Reopen { bead_id: "t1".into() }).is_empty());
+    }
+
+    #[test]
+    fn test_closing_with_unmet_acceptance_criteria() {
+        let mut beads = project();
+        let mut task = beads[3].clone();
+        task.acceptance_criteria = Some(vec!["Parses".into(), "Has tests".into()]);
+        task.extra_metadata.insert("acceptance_checklist".into(), json!([{ "text": "Parses", "done": true }]));
+        beads[3] = task.clone();
+        let policy = TransitionPolicy { close_with_unmet_acceptance: Enforcement::Error, ..Default::default() };
+        let workflow = WorkflowConfig::default();
+
+        let violations = evaluate(&BeadOp::Close { bead_id: "t2".into(), reason: None }, &beads, &policy, &workflow);
+        assert_eq!(kinds(&violations), vec![(TransitionKind::CloseWithUnmetAcceptance, Enforcement::Error, vec![])]);
+        assert_eq!(violations[0].message, "t2 has 1 unmet acceptance criteria: Has tests");
+
+        // Checking off the last item in the same update that closes it is fine
+        task.status = "closed".into();
+        task.extra_metadata.insert(
+            "acceptance_checklist".into(),
+            json!([{ "text": "Parses", "done": true }, { "text": "Has tests", "done": true }]),
+        );
+        assert!(evaluate(&BeadOp::Update { bead: task }, &beads, &policy, &workflow).is_empty());
+    }
+
+    #[test]
+    fn test_evaluate_uses_status_categories_and_skips_unchanged_or_off() {
+        let beads = project();
+        let workflow = WorkflowConfig::default();
+
+        // An update that leaves the category alone is no transition
+        let mut edited = beads[1].clone();
+        edited.title = "Renamed".into();
+        assert!(evaluate(&BeadOp::Update { bead: edited.clone() }, &beads, &TransitionPolicy::default(), &workflow).is_empty());
+
+        // "done" finishes work like "closed" does
+        edited.status = "done".into();
+        let violations = evaluate(&BeadOp::Update { bead: edited }, &beads, &TransitionPolicy::default(), &workflow);
+        assert_eq!(violations[0].kind, TransitionKind::CloseWithOpenChildren);
+        assert_eq!((violations[0].from_status.as_str(), violations[0].to_status.as_str()), ("open", "done"));
+
+        let off = TransitionPolicy { close_with_open_children: Enforcement::Off, ..Default::default() };
+        assert!(evaluate(&BeadOp::Close { bead_id: "f".into(), reason: None }, &beads, &off, &workflow).is_empty());
+    }
+}
@@ -0,0 +1,282 @@
+//! Estimate vs actual variance report
+//!
+//! Compares the estimate of every finished bead with what it really took:
+//! the tracked time when it has time entries, otherwise its cycle time
+//! (created to closed, in working minutes, as estimate suggestions count it).
+//! `get_variance_report` returns the ratio per bead and totals per owner,
+//! issue type and label, so a team can see who or what tends to run over and
+//! calibrate the next estimates. Beads that took several times their estimate
+//! are flagged as outliers.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::burndown::BurndownRange;
+use crate::error::BertError;
+use crate::project::context_for;
+use crate::schedule::SchedulingConfig;
+use crate::workflow::WorkflowConfig;
+use crate::Bead;
+
+/// Actual/estimate ratio from which a bead counts as wildly underestimated
+pub const OUTLIER_RATIO: f64 = 3.0;
+
+/// Group of beads without an owner
+const UNASSIGNED: &str = "unassigned";
+
+/// Where a bead's actual time comes from
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ActualSource {
+    Tracked,
+    CycleTime,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BeadVariance {
+    pub bead_id: String,
+    pub title: String,
+    pub owner: Option<String>,
+    pub issue_type: String,
+    pub labels: Vec<String>,
+    pub closed_at: Option<String>,
+    /// Minutes
+    pub estimate: u32,
+    pub actual_minutes: u64,
+    pub source: ActualSource,
+    /// Actual over estimate; above 1 when it ran over
+    pub ratio: f64,
+    pub is_outlier: bool,
+}
+
+/// Totals of the beads sharing an owner, issue type or label
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VarianceGroup {
+    pub key: String,
+    pub count: usize,
+    pub estimate_minutes: u64,
+    pub actual_minutes: u64,
+    /// Total actual over total estimate
+    pub ratio: f64,
+    /// Median of the bead ratios, less skewed by a single outlier
+    pub median_ratio: f64,
+    pub outliers: usize,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VarianceReport {
+    /// Worst ratio first
+    pub beads: Vec<BeadVariance>,
+    /// None when no bead qualified
+    pub overall: Option<VarianceGroup>,
+    pub by_owner: Vec<VarianceGroup>,
+    pub by_issue_type: Vec<VarianceGroup>,
+    pub by_label: Vec<VarianceGroup>,
+    pub outlier_ratio: f64,
+    /// Finished beads in range left out for lack of an estimate
+    pub unestimated: usize,
+}
+
+fn parse_day(ts: &str) -> Option<NaiveDate> {
+    DateTime::parse_from_rfc3339(ts)
+        .map(|dt| dt.with_timezone(&Utc).date_naive())
+        .ok()
+        .or_else(|| NaiveDate::parse_from_str(ts, "%Y-%m-%d").ok())
+}
+
+fn parse_bound(raw: &Option<String>, label: &str) -> Result<Option<NaiveDate>, BertError> {
+    raw.as_deref()
+        .filter(|s| !s.trim().is_empty())
+        .map(|s| parse_day(s.trim()).ok_or_else(|| BertError::InvalidInput(format!("Invalid {} date '{}'", label, s))))
+        .transpose()
+}
+
+fn median(mut values: Vec<f64>) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 { (values[mid - 1] + values[mid]) / 2.0 } else { values[mid] }
+}
+
+fn group(key: &str, beads: &[&BeadVariance]) -> VarianceGroup {
+    let estimate_minutes: u64 = beads.iter().map(|b| b.estimate as u64).sum();
+    let actual_minutes: u64 = beads.iter().map(|b| b.actual_minutes).sum();
+    VarianceGroup {
+        key: key.to_string(),
+        count: beads.len(),
+        estimate_minutes,
+        actual_minutes,
+        ratio: if estimate_minutes == 0 { 0.0 } else { actual_minutes as f64 / estimate_minutes as f64 },
+        median_ratio: median(beads.iter().map(|b| b.ratio).collect()),
+        outliers: beads.iter().filter(|b| b.is_outlier).count(),
+    }
+}
+
+fn groups<'a>(beads: &'a [BeadVariance], keys: impl Fn(&'a BeadVariance) -> Vec<&'a str>) -> Vec<VarianceGroup> {
+    let mut by_key: BTreeMap<&str, Vec<&BeadVariance>> = BTreeMap::new();
+    for bead in beads {
+        for key in keys(bead) {
+            by_key.entry(key).or_default().push(bead);
+        }
+    }
+    by_key.iter().map(|(key, beads)| group(key, beads)).collect()
+}
+
+/// Variance of the finished, estimated beads closed within `start..=end`.
+/// `tracked` holds the tracked minutes per bead.
+pub fn build_report(
+    beads: &[Bead],
+    tracked: &BTreeMap<String, u64>,
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+    workflow: &WorkflowConfig,
+    scheduling: &SchedulingConfig,
+) -> VarianceReport {
+    let in_range: Vec<&Bead> = beads
+        .iter()
+        .filter(|b| workflow.is_done(&b.status))
+        .filter(|b| {
+            let closed = b.closed_at.as_deref().and_then(parse_day);
+            start.is_none_or(|s| closed.is_some_and(|d| d >= s)) && end.is_none_or(|e| closed.is_some_and(|d| d <= e))
+        })
+        .collect();
+
+    let mut unestimated = 0;
+    let mut variances = Vec::new();
+    for bead in in_range {
+        let Some(estimate) = bead.estimate.filter(|e| *e > 0) else {
+            unestimated += 1;
+            continue;
+        };
+        let (actual_minutes, source) = match tracked.get(&bead.id).filter(|m| **m > 0) {
+            Some(&minutes) => (minutes, ActualSource::Tracked),
+            None => match crate::estimates::cycle_minutes(bead, scheduling.hours_per_day) {
+                Some(minutes) => (minutes as u64, ActualSource::CycleTime),
+                None => continue,
+            },
+        };
+        let ratio = actual_minutes as f64 / estimate as f64;
+        variances.push(BeadVariance {
+            bead_id: bead.id.clone(),
+            title: bead.title.clone(),
+            owner: bead.owner.clone(),
+            issue_type: bead.issue_type.clone(),
+            labels: bead.labels.clone().unwrap_or_default(),
+            closed_at: bead.closed_at.clone(),
+            estimate,
+            actual_minutes,
+            source,
+            ratio,
+            is_outlier: ratio >= OUTLIER_RATIO,
+        });
+    }
+    variances.sort_by(|a, b| b.ratio.total_cmp(&a.ratio).then_with(|| a.bead_id.cmp(&b.bead_id)));
+
+    VarianceReport {
+        overall: (!variances.is_empty()).then(|| group("all", &variances.iter().collect::<Vec<_>>())),
+        by_owner: groups(&variances, |b| vec![b.owner.as_deref().unwrap_or(UNASSIGNED)]),
+        by_issue_type: groups(&variances, |b| vec![b.issue_type.as_str()]),
+        by_label: groups(&variances, |b| b.labels.iter().map(String::as_str).collect()),
+        beads: variances,
+        outlier_ratio: OUTLIER_RATIO,
+        unestimated,
+    }
+}
+
+/// Estimate accuracy of the beads closed within `range` (all finished beads
+/// without one), per bead and per owner, issue type and label
+#[tauri::command]
+pub fn get_variance_report(
+    range: Option<BurndownRange>,
+    window: tauri::Window,
+    project_handle: Option<String>,
+) -> Result<VarianceReport, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    let beads_path = ctx.beads_path()?;
+    let workflow = WorkflowConfig::load_for_beads_file(&beads_path)?;
+    let scheduling = SchedulingConfig::load_for_beads_file(&beads_path)?;
+    let beads = crate::bd::load_beads(&ctx)?;
+
+    let range = range.unwrap_or_default();
+    let start = parse_bound(&range.start, "start")?;
+    let end = parse_bound(&range.end, "end")?;
+    if let (Some(start), Some(end)) = (start, end) {
+        if end < start {
+            return Err(BertError::InvalidInput(format!("Variance range end {} is before start {}", end, start)));
+        }
+    }
+
+    let tracked: BTreeMap<String, u64> = crate::timetracking::actual_minutes(&beads_path);
+    let report = build_report(&beads, &tracked, start, end, &workflow, &scheduling);
+    eprintln!("📏 get_variance_report: {} beads, {} outliers", report.beads.len(), report.beads.iter().filter(|b| b.is_outlier).count());
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bead(id: &str, owner: Option<&str>, estimate: Option<u32>, labels: &[&str], closed_at: &str) -> Bead {
+        crate::test_bead(json!({
+            "id": id,
+            "status": "closed",
+            "estimate": estimate,
+            "owner": owner,
+            "labels": labels,
+            "created_at": "2026-03-02T09:00:00Z",
+            "closed_at": closed_at,
+        }))
+    }
+
+    fn report(beads: &[Bead], tracked: &[(&str, u64)], start: Option<&str>) -> VarianceReport {
+        let tracked: BTreeMap<String, u64> = tracked.iter().map(|(id, m)| (id.to_string(), *m)).collect();
+        let start = start.and_then(parse_day);
+        build_report(beads, &tracked, start, None, &WorkflowConfig::default(), &SchedulingConfig::default())
+    }
+
+    #[test]
+    fn test_prefers_tracked_time_and_flags_outliers() {
+        let beads = vec![
+            bead("a", Some("ann"), Some(60), &[], "2026-03-02T12:00:00Z"),
+            bead("b", Some("ann"), Some(60), &[], "2026-03-02T10:00:00Z"),
+            bead("c", None, None, &[], "2026-03-02T10:00:00Z"),
+        ];
+        let report = report(&beads, &[("a", 30)], None);
+
+        assert_eq!(report.unestimated, 1);
+        let ids: Vec<&str> = report.beads.iter().map(|b| b.bead_id.as_str()).collect();
+        assert_eq!(ids, ["b", "a"]);
+        assert_eq!((report.beads[0].source, report.beads[0].actual_minutes), (ActualSource::CycleTime, 60));
+        assert_eq!((report.beads[1].source, report.beads[1].ratio), (ActualSource::Tracked, 0.5));
+        assert!(report.beads.iter().all(|b| !b.is_outlier));
+
+        let report = super::report(&beads, &[("a", 240)], None);
+        assert!(report.beads[0].is_outlier && report.beads[0].bead_id == "a");
+        assert_eq!(report.overall.unwrap().outliers, 1);
+    }
+
+    #[test]
+    fn test_groups_by_owner_type_and_label_within_range() {
+        let beads = vec![
+            bead("a", Some("ann"), Some(100), &["api", "ui"], "2026-03-05T12:00:00Z"),
+            bead("b", None, Some(100), &["api"], "2026-03-06T12:00:00Z"),
+            bead("old", Some("ann"), Some(100), &["api"], "2026-02-01T12:00:00Z"),
+        ];
+        let report = report(&beads, &[("a", 200), ("b", 50), ("old", 500)], Some("2026-03-01"));
+
+        assert_eq!(report.beads.len(), 2);
+        let owners: Vec<(&str, usize)> = report.by_owner.iter().map(|g| (g.key.as_str(), g.count)).collect();
+        assert_eq!(owners, [("ann", 1), ("unassigned", 1)]);
+        let api = report.by_label.iter().find(|g| g.key == "api").unwrap();
+        assert_eq!((api.count, api.estimate_minutes, api.actual_minutes, api.ratio), (2, 200, 250, 1.25));
+        assert_eq!(api.median_ratio, 1.25);
+        assert_eq!(report.by_issue_type.len(), 1);
+    }
+}
@@ -0,0 +1,532 @@
+//! Cached view model state for incremental updates
+//!
+//! Recomputing the full ProjectViewModel and shipping it over IPC is expensive
+//! for large projects. ProjectState keeps the last computed view model keyed by
+//! the beads file checksum and the filter params, and remembers a short history
+//! of flattened node snapshots so the frontend can request only what changed
+//! since the version it last rendered. Each window has its own cache, since
+//! windows can have different projects open.
+//!
+//! A window can also ask for pushed updates (`set_view_model_push`): the beads
+//! watcher then recomputes its view model with the params it last requested
+//! and sends it inline in a `view-model-updated` event, saving the round trip
+//! through `get_project_view_model` after every `beads-updated`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::error::BertError;
+use crate::schedule::SchedulingConfig;
+use crate::workflow::WorkflowConfig;
+use crate::{BeadNode, FilterParams, ProjectMetadata, ProjectViewModel, ViewIndexes};
+
+/// How many past versions are kept for diffing
+const MAX_SNAPSHOTS: usize = 16;
+
+/// Flattened view of a tree: bead ID -> node with `children` emptied.
+/// Structure is carried separately by `ViewIndexes` (order and parent links).
+type NodeSnapshot = HashMap<String, BeadNode>;
+
+/// Difference between a previously rendered version and the current view model
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ViewModelDiff {
+    /// Version of the current view model
+    pub version: u64,
+    /// Version the diff was computed against (None when a full model is returned)
+    pub base_version: Option<u64>,
+    /// Full tree, only set when `prev_version` is unknown or too old
+    pub full: Option<Vec<BeadNode>>,
+    /// Nodes present now but not in the base version (children emptied)
+    pub added: Vec<BeadNode>,
+    /// Nodes whose data changed since the base version (children emptied)
+    pub changed: Vec<BeadNode>,
+    /// IDs of nodes no longer present
+    pub removed: Vec<String>,
+    /// Current metadata (always sent; small)
+    pub metadata: ProjectMetadata,
+    /// Current indexes, used by the frontend to rebuild tree order
+    pub indexes: ViewIndexes,
+}
+
+/// A slice of the rows the tree shows, for virtualized scrolling
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ViewModelWindow {
+    /// Version of the view model the rows were taken from
+    pub version: u64,
+    pub start_row: usize,
+    /// Rows shown with the current expansion state, in and out of the window
+    pub total_rows: usize,
+    pub rows: Vec<ViewModelRow>,
+    pub metadata: ProjectMetadata,
+}
+
+/// A visible node with its children emptied
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ViewModelRow {
+    #[serde(flatten)]
+    pub node: BeadNode,
+    /// Children the node has, shown or not, so the row can offer to expand
+    pub child_count: usize,
+}
+
+#[derive(Default)]
+struct ViewModelCache {
+    version: u64,
+    /// (beads file path, file checksum, serialized params + scheduling and workflow config) of the cached model
+    key: Option<(PathBuf, u64, String)>,
+    current: Option<ProjectViewModel>,
+    snapshots: VecDeque<(u64, NodeSnapshot)>,
+    /// Params of the last request, reused for pushed updates
+    params: Option<FilterParams>,
+    /// Whether the watcher pushes recomputed view models to the window
+    push: bool,
+}
+
+/// Payload of `view-model-updated`
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ViewModelUpdated {
+    pub version: u64,
+    /// Tab the view model belongs to, if it isn't the window's own project
+    pub project_handle: Option<String>,
+    pub view_model: ProjectViewModel,
+}
+
+/// Managed Tauri state holding the cached view model of each window
+pub struct ProjectState {
+    caches: Mutex<HashMap<String, ViewModelCache>>,
+}
+
+impl ProjectState {
+    pub fn new() -> Self {
+        ProjectState {
+            caches: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Return the view model for `beads_path` + `params` in window `label`,
+    /// recomputing only when the file contents or params changed since the last call.
+    pub fn load(&self, label: &str, beads_path: &Path, params: &FilterParams) -> Result<(u64, ProjectViewModel), String> {
+        let bytes = std::fs::read(beads_path)
+            .map_err(|e| format!("Failed to read {}: {}", beads_path.display(), e))?;
+        let checksum = checksum_bytes(&bytes);
+        let scheduling = SchedulingConfig::load_for_beads_file(beads_path)?;
+        let workflow = WorkflowConfig::load_for_beads_file(beads_path)?;
+        let actuals = crate::timetracking::actual_minutes(beads_path);
+        let pinned = crate::watchlist::pinned_ids(beads_path);
+        // Scheduling and workflow config change the dates and counts in the model,
+        // tracked time its actualMinutes and the watchlist its isPinned, so they
+        // are part of the key
+        let params_key = format!(
+            "{}|{}|{}|{}|{}",
+            serde_json::to_string(params).map_err(|e| e.to_string())?,
+            serde_json::to_string(&scheduling).map_err(|e| e.to_string())?,
+            serde_json::to_string(&workflow).map_err(|e| e.to_string())?,
+            serde_json::to_string(&actuals).map_err(|e| e.to_string())?,
+            serde_json::to_string(&pinned).map_err(|e| e.to_string())?
+        );
+        let key = (beads_path.to_path_buf(), checksum, params_key);
+
+        let mut caches = self.caches.lock().map_err(|e| format!("Failed to acquire view cache lock: {}", e))?;
+        let cache = caches.entry(label.to_string()).or_default();
+        cache.params = Some(params.clone());
+        if cache.key.as_ref() == Some(&key) {
+            if let Some(current) = &cache.current {
+                eprintln!("⚡ View model cache hit (version {})", cache.version);
+                let mut view_model = current.clone();
+                view_model.timings.cached = true;
+                crate::external::resolve_tree(&mut view_model.tree, beads_path);
+                return Ok((cache.version, view_model));
+            }
+        }
+
+        let load_start = std::time::Instant::now();
+        // Parse the bytes the checksum was taken of, so the model matches its key
+        let (beads, warnings) = crate::bd::parse_beads_lenient(&bytes);
+        crate::bd::record_parse_warnings(beads_path, warnings.clone());
+        let mut load = crate::perf::Timings::default();
+        load.record("load", load_start);
+
+        let mut view_model = crate::bert_core::build_project_view_model(beads, params, &scheduling, &workflow);
+        view_model.timings.stages.splice(0..0, load.stages);
+        view_model.parse_warnings = warnings;
+        crate::timetracking::apply_actuals(&mut view_model.tree, &actuals);
+        crate::watchlist::apply_pins(&mut view_model.tree, &pinned);
+        let version = cache.store(key, view_model.clone());
+        // Not cached: the other projects' files aren't part of the key
+        crate::external::resolve_tree(&mut view_model.tree, beads_path);
+        Ok((version, view_model))
+    }
+
+    /// Drop a window's cached model (e.g. when it switches projects or a
+    /// config changes), keeping its push setting
+    pub fn invalidate(&self, label: &str) {
+        if let Ok(mut caches) = self.caches.lock() {
+            if let Some(cache) = caches.get_mut(label) {
+                cache.key = None;
+                cache.current = None;
+                cache.snapshots.clear();
+            }
+        }
+    }
+
+    /// Forget a closed window
+    pub fn remove(&self, label: &str) {
+        if let Ok(mut caches) = self.caches.lock() {
+            caches.remove(label);
+        }
+    }
+
+    fn set_push(&self, label: &str, enabled: bool) -> Result<(), BertError> {
+        let mut caches =
+            self.caches.lock().map_err(|e| BertError::Internal(format!("Failed to acquire view cache lock: {}", e)))?;
+        caches.entry(label.to_string()).or_default().push = enabled;
+        Ok(())
+    }
+
+    /// Params to recompute the view model of `label` with, if it wants pushed
+    /// updates and has requested a view model before
+    fn push_params(&self, label: &str) -> Option<FilterParams> {
+        let caches = self.caches.lock().ok()?;
+        caches.get(label).filter(|c| c.push).and_then(|c| c.params.clone())
+    }
+
+    /// Diff a window's current view model against `prev_version`
+    fn diff_since(&self, label: &str, prev_version: Option<u64>) -> Result<ViewModelDiff, String> {
+        let caches = self.caches.lock().map_err(|e| format!("Failed to acquire view cache lock: {}", e))?;
+        caches
+            .get(label)
+            .ok_or_else(|| "No view model has been computed for this window".to_string())?
+            .diff_since(prev_version)
+    }
+}
+
+impl Default for ProjectState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ViewModelCache {
+    /// Store a freshly computed model. The version only advances when the
+    /// flattened nodes actually differ from the latest snapshot.
+    fn store(&mut self, key: (PathBuf, u64, String), view_model: ProjectViewModel) -> u64 {
+        let snapshot = flatten_tree(&view_model.tree);
+        let unchanged = self
+            .snapshots
+            .back()
+            .map(|(_, latest)| *latest == snapshot)
+            .unwrap_or(false);
+
+        if !unchanged || self.snapshots.is_empty() {
+            self.version += 1;
+            self.snapshots.push_back((self.version, snapshot));
+            while self.snapshots.len() > MAX_SNAPSHOTS {
+                self.snapshots.pop_front();
+            }
+        }
+
+        self.key = Some(key);
+        self.current = Some(view_model);
+        self.version
+    }
+
+    fn diff_since(&self, prev_version: Option<u64>) -> Result<ViewModelDiff, String> {
+        let current = self
+            .current
+            .as_ref()
+            .ok_or_else(|| "No view model has been computed yet".to_string())?;
+        let (_, latest) = self
+            .snapshots
+            .back()
+            .ok_or_else(|| "No view model has been computed yet".to_string())?;
+
+        let base = prev_version.and_then(|v| self.snapshots.iter().find(|(sv, _)| *sv == v));
+
+        let Some((base_version, base_snapshot)) = base else {
+            return Ok(ViewModelDiff {
+                version: self.version,
+                base_version: None,
+                full: Some(current.tree.clone()),
+                added: Vec::new(),
+                changed: Vec::new(),
+                removed: Vec::new(),
+                metadata: current.metadata.clone(),
+                indexes: current.indexes.clone(),
+            });
+        };
+
+        let mut added = Vec::new();
+        let mut changed = Vec::new();
+        for (id, node) in latest {
+            match base_snapshot.get(id) {
+                None => added.push(node.clone()),
+                Some(old) if old != node => changed.push(node.clone()),
+                Some(_) => {}
+            }
+        }
+        let mut removed: Vec<String> = base_snapshot
+            .keys()
+            .filter(|id| !latest.contains_key(*id))
+            .cloned()
+            .collect();
+
+        // Deterministic output order
+        added.sort_by(|a, b| a.id.cmp(&b.id));
+        changed.sort_by(|a, b| a.id.cmp(&b.id));
+        removed.sort();
+
+        Ok(ViewModelDiff {
+            version: self.version,
+            base_version: Some(*base_version),
+            full: None,
+            added,
+            changed,
+            removed,
+            metadata: current.metadata.clone(),
+            indexes: current.indexes.clone(),
+        })
+    }
+}
+
+fn checksum_bytes(bytes: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn flatten_tree(tree: &[BeadNode]) -> NodeSnapshot {
+    fn walk(nodes: &[BeadNode], acc: &mut NodeSnapshot) {
+        for node in nodes {
+            let mut flat = node.clone();
+            flat.children = Vec::new();
+            acc.insert(node.id.clone(), flat);
+            walk(&node.children, acc);
+        }
+    }
+
+    let mut acc = HashMap::new();
+    walk(tree, &mut acc);
+    acc
+}
+
+/// Rows `start_row..start_row + row_count` of the tree flattened in display
+/// order, skipping the descendants of collapsed nodes. Returns them with the
+/// total number of rows.
+fn visible_window(tree: &[BeadNode], start_row: usize, row_count: usize) -> (usize, Vec<ViewModelRow>) {
+    fn walk(nodes: &[BeadNode], range: &std::ops::Range<usize>, row: &mut usize, rows: &mut Vec<ViewModelRow>) {
+        for node in nodes {
+            if range.contains(row) {
+                let mut flat = node.clone();
+                flat.children = Vec::new();
+                rows.push(ViewModelRow { node: flat, child_count: node.children.len() });
+            }
+            *row += 1;
+            if node.is_expanded {
+                walk(&node.children, range, row, rows);
+            }
+        }
+    }
+
+    let mut total = 0;
+    let mut rows = Vec::new();
+    walk(tree, &(start_row..start_row.saturating_add(row_count)), &mut total, &mut rows);
+    (total, rows)
+}
+
+/// Get the visible rows `startRow..startRow + rowCount` of the view model,
+/// honoring expansion state, so the frontend only receives what it scrolls to.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn get_view_model_window(
+    params: FilterParams,
+    startRow: usize,
+    rowCount: usize,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    project_state: State<'_, ProjectState>,
+) -> Result<ViewModelWindow, BertError> {
+    let beads_path = crate::project::context_for(&window, project_handle.as_deref())?.beads_path()?;
+
+    let (version, view_model) = project_state.load(&crate::project::project_key(&window, project_handle.as_deref()), &beads_path, &params)?;
+    let (total_rows, rows) = visible_window(&view_model.tree, startRow, rowCount);
+
+    Ok(ViewModelWindow {
+        version,
+        start_row: startRow,
+        total_rows,
+        rows,
+        metadata: view_model.metadata,
+    })
+}
+
+/// Recompute and send the view model of window or tab `label` after its beads
+/// file changed, if it asked for pushed updates. Called by the beads watcher.
+pub fn push_view_model(app_handle: &AppHandle, label: &str, beads_path: &Path) {
+    let Some(project_state) = app_handle.try_state::<ProjectState>() else { return };
+    let Some(params) = project_state.push_params(label) else { return };
+
+    match project_state.load(label, beads_path, &params) {
+        Ok((version, view_model)) => {
+            let project_handle = crate::project::tab_handle(label).map(str::to_string);
+            let update = ViewModelUpdated { version, project_handle, view_model };
+            if let Err(e) = app_handle.emit_to(crate::project::owner_window(label), "view-model-updated", update) {
+                eprintln!("  ❌ Failed to emit view-model-updated: {:?}", e);
+            }
+        }
+        Err(e) => eprintln!("  ⚠️  Failed to recompute the view model of {}: {}", label, e),
+    }
+}
+
+/// Have the beads watcher send this window its recomputed view model in a
+/// `view-model-updated` event whenever the beads change, using the params of
+/// its last view model request.
+#[tauri::command]
+pub fn set_view_model_push(enabled: bool, window: tauri::Window, project_handle: Option<String>, project_state: State<'_, ProjectState>) -> Result<(), BertError> {
+    crate::project::context_for(&window, project_handle.as_deref())?;
+    project_state.set_push(&crate::project::project_key(&window, project_handle.as_deref()), enabled)
+}
+
+/// Get only the BeadNodes that changed since `prevVersion`.
+///
+/// Recomputes the view model if the file or params changed, then diffs it
+/// against the requested version. Falls back to a full tree when
+/// `prevVersion` is missing or has aged out of the snapshot history.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn get_view_model_diff(
+    params: FilterParams,
+    prevVersion: Option<u64>,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    project_state: State<'_, ProjectState>,
+) -> Result<ViewModelDiff, BertError> {
+    let beads_path = crate::project::context_for(&window, project_handle.as_deref())?.beads_path()?;
+
+    project_state.load(&crate::project::project_key(&window, project_handle.as_deref()), &beads_path, &params)?;
+    let diff = project_state.diff_since(&crate::project::project_key(&window, project_handle.as_deref()), prevVersion)?;
+
+    eprintln!(
+        "🧮 get_view_model_diff: v{:?} -> v{} (+{} ~{} -{})",
+        prevVersion,
+        diff.version,
+        diff.added.len(),
+        diff.changed.len(),
+        diff.removed.len()
+    );
+    Ok(diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Bead;
+
+    fn bead(id: &str, title: &str) -> Bead {
+        crate::test_bead(serde_json::json!({
+            "id": id,
+            "title": title,
+        }))
+    }
+
+    fn view_model(beads: &[Bead]) -> ProjectViewModel {
+        crate::bert_core::build_project_view_model(beads.to_vec(), &FilterParams::default(), &SchedulingConfig::default(), &WorkflowConfig::default())
+    }
+
+    fn key(checksum: u64) -> (PathBuf, u64, String) {
+        (PathBuf::from("/tmp/issues.jsonl"), checksum, String::new())
+    }
+
+    #[test]
+    fn test_unknown_version_returns_full_tree() {
+        let mut cache = ViewModelCache::default();
+        cache.store(key(1), view_model(&[bead("a", "A")]));
+
+        let diff = cache.diff_since(Some(999)).unwrap();
+        assert!(diff.base_version.is_none());
+        assert_eq!(diff.full.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_diff_reports_added_changed_removed() {
+        let mut cache = ViewModelCache::default();
+        let v1 = cache.store(key(1), view_model(&[bead("a", "A"), bead("b", "B")]));
+        let v2 = cache.store(key(2), view_model(&[bead("a", "A2"), bead("c", "C")]));
+        assert_eq!(v2, v1 + 1);
+
+        let diff = cache.diff_since(Some(v1)).unwrap();
+        assert_eq!(diff.base_version, Some(v1));
+        assert!(diff.full.is_none());
+        assert_eq!(diff.added.iter().map(|n| n.id.as_str()).collect::<Vec<_>>(), vec!["c"]);
+        assert_eq!(diff.changed.iter().map(|n| n.id.as_str()).collect::<Vec<_>>(), vec!["a"]);
+        assert_eq!(diff.removed, vec!["b".to_string()]);
+    }
+
+    #[test]
+    fn test_window_skips_collapsed_children() {
+        let mut child: Bead = bead("b", "B");
+        child.dependencies = serde_json::from_value(serde_json::json!([
+            { "issue_id": "b", "depends_on_id": "a", "type": "parent-child" }
+        ]))
+        .unwrap();
+        let beads = [bead("a", "A"), child, bead("c", "C")];
+
+        let ids = |rows: &[ViewModelRow]| rows.iter().map(|r| r.node.id.clone()).collect::<Vec<_>>();
+        let tree = view_model(&beads).tree;
+        let (total, all) = visible_window(&tree, 0, usize::MAX);
+        assert_eq!(total, 3);
+        let a = ids(&all).iter().position(|id| id == "a").unwrap();
+        assert_eq!(ids(&all)[a + 1], "b");
+        assert!(all.iter().all(|r| r.node.children.is_empty()));
+
+        let (total, rows) = visible_window(&tree, 1, 5);
+        assert_eq!(total, 3);
+        assert_eq!(ids(&rows), ids(&all[1..]));
+
+        let params = FilterParams { collapsed_ids: vec!["a".to_string()], ..FilterParams::default() };
+        let collapsed = crate::bert_core::build_project_view_model(beads.to_vec(), &params, &SchedulingConfig::default(), &WorkflowConfig::default());
+        let (total, rows) = visible_window(&collapsed.tree, 0, usize::MAX);
+        assert_eq!(total, 2);
+        let parent = rows.iter().find(|r| r.node.id == "a").unwrap();
+        assert_eq!(parent.child_count, 1);
+        assert!(!parent.node.is_expanded);
+    }
+
+    #[test]
+    fn test_push_uses_last_params_and_survives_invalidate() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let beads_path = dir.path().join("issues.jsonl");
+        std::fs::write(&beads_path, serde_json::to_string(&bead("a", "A")).unwrap() + "\n").unwrap();
+
+        let state = ProjectState::new();
+        let params = FilterParams { filter_text: "A".to_string(), ..FilterParams::default() };
+        state.load("main", &beads_path, &params).unwrap();
+        assert!(state.push_params("main").is_none());
+
+        state.set_push("main", true).unwrap();
+        state.invalidate("main");
+        assert_eq!(state.push_params("main").unwrap().filter_text, "A");
+
+        state.remove("main");
+        assert!(state.push_params("main").is_none());
+    }
+
+    #[test]
+    fn test_identical_recompute_keeps_version() {
+        let mut cache = ViewModelCache::default();
+        let v1 = cache.store(key(1), view_model(&[bead("a", "A")]));
+        let v2 = cache.store(key(2), view_model(&[bead("a", "A")]));
+        assert_eq!(v1, v2);
+
+        let diff = cache.diff_since(Some(v1)).unwrap();
+        assert!(diff.added.is_empty() && diff.changed.is_empty() && diff.removed.is_empty());
+    }
+}
@@ -0,0 +1,116 @@
+//! Read-only viewer for shared snapshot exports
+//!
+//! A bundle written by `export_snapshot` (see `bundle`) can travel as a file
+//! attachment. Opening one (as a project or in a tab) unpacks it into a
+//! directory under `~/.bp6/snapshots` laid out like a project, so the tree,
+//! Gantt and reports render from it the usual way, without bd or the original
+//! repo. Automation config (auto-commit, recurrences, rules) is left out.
+//!
+//! The unpacked directory is marked with `.beads/bp6-snapshot.json`. Bead
+//! edits, comments, time tracking, baselines and project config writes all
+//! check `ensure_writable` first and fail with a `read_only_snapshot` error
+//! there.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::bundle::{SnapshotBundle, SnapshotInfo};
+use crate::error::BertError;
+use crate::project::context_for;
+
+/// Marker of an unpacked snapshot, inside its `.beads` directory
+const MARKER_FILE: &str = "bp6-snapshot.json";
+
+/// Config sections that drive automation rather than the view; a viewer
+/// shouldn't commit, create recurring beads or run rules
+const EXCLUDED_CONFIG: [&str; 3] = ["autoCommit", "recurrences", "rules"];
+
+/// Lay `bundle` out as a read-only project in `dir`, replacing what was there
+fn unpack_into(bundle: &SnapshotBundle, source: &Path, dir: &Path) -> Result<(), BertError> {
+    if dir.exists() {
+        fs::remove_dir_all(dir).map_err(|e| BertError::Io(format!("Failed to clear {}: {}", dir.display(), e)))?;
+    }
+    crate::bundle::write_project(bundle, dir, &EXCLUDED_CONFIG)?;
+    let info = SnapshotInfo { source: source.display().to_string(), ..bundle.info.clone() };
+    fs::write(dir.join(".beads").join(MARKER_FILE), serde_json::to_string_pretty(&info)?)
+        .map_err(|e| BertError::Io(format!("Failed to write the snapshot marker: {}", e)))
+}
+
+fn snapshots_dir() -> Result<PathBuf, BertError> {
+    let home = dirs::home_dir().ok_or_else(|| BertError::Io("Failed to get home directory".to_string()))?;
+    Ok(home.join(".bp6").join("snapshots"))
+}
+
+/// Unpack the bundle at `path` for viewing; returns the project directory to open
+pub(crate) fn unpack(path: &Path) -> Result<PathBuf, BertError> {
+    let bundle = crate::bundle::read_bundle(path)?;
+    let canonical = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let dir = snapshots_dir()?.join(format!("{:016x}", crate::baseline::stable_hash(canonical.as_os_str().as_encoded_bytes())));
+
+    unpack_into(&bundle, path, &dir)?;
+    eprintln!("📦 Unpacked snapshot of {} ({} beads) to {}", bundle.info.project_name, bundle.info.bead_count, dir.display());
+    Ok(dir)
+}
+
+/// The snapshot `path` (a project directory or a file in it) belongs to, if any
+pub(crate) fn snapshot_of(path: &Path) -> Option<SnapshotInfo> {
+    path.ancestors()
+        .map(|dir| dir.join(".beads").join(MARKER_FILE))
+        .find(|marker| marker.is_file())
+        .and_then(|marker| serde_json::from_str(&fs::read_to_string(marker).ok()?).ok())
+}
+
+/// Fail with a `read_only_snapshot` error when `path` is inside an unpacked
+/// snapshot
+pub(crate) fn ensure_writable(path: &Path) -> Result<(), BertError> {
+    match snapshot_of(path) {
+        Some(info) => Err(BertError::ReadOnlySnapshot(format!(
+            "This is a read-only snapshot of {} exported {}; open the project itself to make changes",
+            info.project_name, info.exported_at
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// The snapshot the current project was opened from, or None for a regular project
+#[tauri::command]
+pub fn get_snapshot_info(window: tauri::Window, project_handle: Option<String>) -> Result<Option<SnapshotInfo>, BertError> {
+    Ok(snapshot_of(context_for(&window, project_handle.as_deref())?.project_path()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Map};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_unpacks_to_a_read_only_project_without_automation() {
+        let dir = TempDir::new().unwrap();
+        let bead = serde_json::from_value(json!({ "id": "bp6-1", "title": "Plan", "status": "open", "priority": 2, "issue_type": "task" })).unwrap();
+        let config = json!({ "workflow": { "statuses": [] }, "autoCommit": { "enabled": true } });
+        let bundle = SnapshotBundle::new("bp6", vec![bead], config.as_object().unwrap().clone());
+
+        let project = dir.path().join("unpacked");
+        unpack_into(&bundle, &dir.path().join("plan.bp6"), &project).unwrap();
+        let config: serde_json::Value = serde_json::from_str(&fs::read_to_string(project.join(".beads/bert-viz.json")).unwrap()).unwrap();
+        assert!(config.get("workflow").is_some() && config.get("autoCommit").is_none());
+
+        let info = snapshot_of(&project).unwrap();
+        assert_eq!((info.project_name.as_str(), info.bead_count), ("bp6", 1));
+        assert!(info.source.ends_with("plan.bp6"));
+        let err = ensure_writable(&project.join(".beads/comments/bp6-1.jsonl")).unwrap_err();
+        assert_eq!(err.code(), "read_only_snapshot");
+        assert!(ensure_writable(dir.path()).is_ok());
+        // Config savers and agent-suggested bd commands keep the error code
+        let err = crate::schedule::merge_config_fields(&project.join(".beads/issues.jsonl"), Map::new()).unwrap_err();
+        assert_eq!(err.code(), "read_only_snapshot");
+        let ctx = crate::project::ProjectContext::new(project.clone());
+        let err = crate::bd::execute_bd(&ctx, vec!["close".to_string(), "bp6-1".to_string()]).unwrap_err();
+        assert_eq!(err.code(), "read_only_snapshot");
+
+        // Unpacking again replaces the old copy
+        unpack_into(&SnapshotBundle::new("bp6", Vec::new(), Map::new()), &dir.path().join("plan.bp6"), &project).unwrap();
+        assert!(!project.join(".beads/bert-viz.json").exists());
+    }
+}
@@ -0,0 +1,215 @@
+//! Personal watchlist of pinned beads
+//!
+//! Unlike `is_favorite`, which lives in the shared bead data, pins belong to
+//! the user: they are kept per project in ~/.bp6/watchlists/<key>.json, the
+//! key being the folder name and a stable hash of the project path, so they
+//! never show up in the repo and survive reloads of the beads file and
+//! toolchain upgrades. The view model marks pinned nodes with `isPinned`;
+//! pins on beads that no longer exist are kept and reported as missing by
+//! `get_watchlist`.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+use crate::error::BertError;
+use crate::project::context_for;
+use crate::BeadNode;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Pin {
+    pub bead_id: String,
+    pub pinned_at: String,
+}
+
+/// Contents of a watchlist file
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct Watchlist {
+    /// Project the pins belong to, for whoever browses ~/.bp6
+    #[serde(default)]
+    project_path: String,
+    /// Oldest first
+    #[serde(default)]
+    pins: Vec<Pin>,
+}
+
+/// A pin with the bead it points at
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchedBead {
+    pub bead_id: String,
+    pub pinned_at: String,
+    /// None when the bead no longer exists
+    pub title: Option<String>,
+    pub status: Option<String>,
+    pub owner: Option<String>,
+}
+
+/// Watchlist of the project owning `beads_path` (~/.bp6/watchlists/<project key>.json)
+fn watchlist_file(beads_path: &Path) -> Result<(PathBuf, PathBuf), BertError> {
+    let home = dirs::home_dir().ok_or_else(|| BertError::Internal("Failed to get home directory".to_string()))?;
+    let project_path = crate::history::project_path_for(beads_path);
+    let key = crate::baseline::project_store_key(&project_path);
+    let file = home.join(".bp6").join("watchlists").join(format!("{}.json", key));
+    Ok((file, project_path))
+}
+
+fn read_watchlist(path: &Path) -> Result<Watchlist, BertError> {
+    if !path.exists() {
+        return Ok(Watchlist::default());
+    }
+    let contents = fs::read_to_string(path).map_err(|e| BertError::Io(format!("Failed to read {}: {}", path.display(), e)))?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+fn write_watchlist(path: &Path, watchlist: &Watchlist) -> Result<(), BertError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| BertError::Io(format!("Failed to create {}: {}", dir.display(), e)))?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_string_pretty(watchlist)?)
+        .map_err(|e| BertError::Io(format!("Failed to write {}: {}", tmp.display(), e)))?;
+    fs::rename(&tmp, path).map_err(|e| BertError::Io(format!("Failed to replace {}: {}", path.display(), e)))
+}
+
+/// Pin or unpin `bead_id` in the watchlist at `path`; returns whether it changed
+fn set_pinned(path: &Path, project_path: &Path, bead_id: &str, pinned: bool) -> Result<bool, BertError> {
+    let mut watchlist = read_watchlist(path)?;
+    let present = watchlist.pins.iter().any(|p| p.bead_id == bead_id);
+    if present == pinned {
+        return Ok(false);
+    }
+    if pinned {
+        watchlist.pins.push(Pin { bead_id: bead_id.to_string(), pinned_at: Utc::now().to_rfc3339() });
+    } else {
+        watchlist.pins.retain(|p| p.bead_id != bead_id);
+    }
+    watchlist.project_path = project_path.display().to_string();
+    write_watchlist(path, &watchlist)?;
+    Ok(true)
+}
+
+/// IDs of the pinned beads of the project owning `beads_path`. Empty when the
+/// watchlist can't be read.
+pub fn pinned_ids(beads_path: &Path) -> BTreeSet<String> {
+    match watchlist_file(beads_path).and_then(|(path, _)| read_watchlist(&path)) {
+        Ok(watchlist) => watchlist.pins.into_iter().map(|p| p.bead_id).collect(),
+        Err(e) => {
+            eprintln!("⚠️  Failed to read the watchlist: {}", e);
+            BTreeSet::new()
+        }
+    }
+}
+
+/// Set `is_pinned` on every node of the tree
+pub fn apply_pins(nodes: &mut [BeadNode], pinned: &BTreeSet<String>) {
+    for node in nodes {
+        node.is_pinned = pinned.contains(&node.id);
+        apply_pins(&mut node.children, pinned);
+    }
+}
+
+fn update(bead_id: String, pinned: bool, window: tauri::Window, project_handle: Option<String>, app_handle: AppHandle) -> Result<Vec<WatchedBead>, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    let beads_path = ctx.beads_path()?;
+    if pinned {
+        crate::bead_index::get_bead_internal(&ctx, &bead_id)?;
+    }
+    let (path, project_path) = watchlist_file(&beads_path)?;
+    if set_pinned(&path, &project_path, &bead_id, pinned)? {
+        eprintln!("📌 {} {}", if pinned { "Pinned" } else { "Unpinned" }, bead_id);
+        // isPinned changed
+        crate::project::emit_beads_updated(&app_handle, &ctx);
+    }
+    get_watchlist(window, project_handle)
+}
+
+/// Add a bead to the user's watchlist of the current project
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn pin_bead(beadId: String, window: tauri::Window, project_handle: Option<String>, app_handle: AppHandle) -> Result<Vec<WatchedBead>, BertError> {
+    update(beadId, true, window, project_handle, app_handle)
+}
+
+/// Remove a bead from the user's watchlist of the current project
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn unpin_bead(beadId: String, window: tauri::Window, project_handle: Option<String>, app_handle: AppHandle) -> Result<Vec<WatchedBead>, BertError> {
+    update(beadId, false, window, project_handle, app_handle)
+}
+
+/// The user's pinned beads in the current project, oldest pin first
+#[tauri::command]
+pub fn get_watchlist(window: tauri::Window, project_handle: Option<String>) -> Result<Vec<WatchedBead>, BertError> {
+    let ctx = context_for(&window, project_handle.as_deref())?;
+    let beads_path = ctx.beads_path()?;
+    let watchlist = read_watchlist(&watchlist_file(&beads_path)?.0)?;
+    let beads = crate::bd::load_beads(&ctx)?;
+    let by_id: HashMap<&str, &crate::Bead> = beads.iter().filter(|b| b.status != "tombstone").map(|b| (b.id.as_str(), b)).collect();
+
+    Ok(watchlist
+        .pins
+        .into_iter()
+        .map(|pin| {
+            let bead = by_id.get(pin.bead_id.as_str());
+            WatchedBead {
+                title: bead.map(|b| b.title.clone()),
+                status: bead.map(|b| b.status.clone()),
+                owner: bead.and_then(|b| b.owner.clone()),
+                bead_id: pin.bead_id,
+                pinned_at: pin.pinned_at,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_pins_are_kept_once_and_removed() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("watchlists").join("project.json");
+
+        assert!(set_pinned(&path, dir.path(), "bp6-1", true).unwrap());
+        assert!(set_pinned(&path, dir.path(), "bp6-2", true).unwrap());
+        assert!(!set_pinned(&path, dir.path(), "bp6-1", true).unwrap());
+        let ids: Vec<String> = read_watchlist(&path).unwrap().pins.into_iter().map(|p| p.bead_id).collect();
+        assert_eq!(ids, ["bp6-1", "bp6-2"]);
+
+        assert!(set_pinned(&path, dir.path(), "bp6-1", false).unwrap());
+        assert!(!set_pinned(&path, dir.path(), "bp6-9", false).unwrap());
+        let watchlist = read_watchlist(&path).unwrap();
+        assert_eq!(watchlist.pins.len(), 1);
+        assert_eq!(watchlist.project_path, dir.path().display().to_string());
+    }
+
+    #[test]
+    fn test_apply_pins_marks_nested_nodes() {
+        let beads: Vec<crate::Bead> = vec![
+            serde_json::json!({"id": "f", "title": "f", "status": "open", "priority": 2, "issue_type": "feature"}),
+            serde_json::json!({"id": "a", "title": "a", "status": "open", "priority": 2, "issue_type": "task",
+                "dependencies": [{"issue_id": "a", "depends_on_id": "f", "type": "parent-child"}]}),
+        ]
+        .into_iter()
+        .map(|v| serde_json::from_value(v).unwrap())
+        .collect();
+        let mut view_model = crate::bert_core::build_project_view_model(
+            beads,
+            &crate::FilterParams::default(),
+            &crate::schedule::SchedulingConfig::default(),
+            &crate::workflow::WorkflowConfig::default(),
+        );
+
+        apply_pins(&mut view_model.tree, &BTreeSet::from(["a".to_string()]));
+        assert!(!view_model.tree[0].is_pinned);
+        assert!(view_model.tree[0].children[0].is_pinned);
+    }
+}
@@ -10,6 +10,8 @@ use std::path::PathBuf;
 use std::fs;
 use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
 
+use crate::error::BertError;
+
 /// WindowInfo contains metadata about a session window
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -117,7 +119,7 @@ impl WindowRegistry {
 pub async fn create_session_window(
     app: AppHandle,
     sessionId: String,
-) -> Result<String, String> {
+) -> Result<String, BertError> {
     eprintln!("🪟 create_session_window: session_id={}", sessionId);
 
     // Generate window label from session ID
@@ -169,7 +171,7 @@ pub async fn create_session_window(
 
     let _window = builder
         .build()
-        .map_err(|e| format!("Failed to create window: {}", e))?;
+        .map_err(|e| BertError::Window(format!("Failed to create window: {}", e)))?;
 
     eprintln!("✅ Created window: {}", window_label);
 
@@ -199,7 +201,7 @@ pub async fn create_session_window(
 pub async fn get_window_session_id(
     app: AppHandle,
     windowLabel: String,
-) -> Result<Option<String>, String> {
+) -> Result<Option<String>, BertError> {
     let registry = app.state::<WindowRegistry>();
     Ok(registry.get_session_id(&windowLabel))
 }
@@ -217,18 +219,18 @@ pub async fn get_window_session_id(
 pub async fn close_session_window(
     app: AppHandle,
     sessionId: String,
-) -> Result<(), String> {
+) -> Result<(), BertError> {
     eprintln!("🗑️  close_session_window: session_id={}", sessionId);
 
     let registry = app.state::<WindowRegistry>();
 
     // Get window label for session
     let window_label = registry.get_window_label(&sessionId)
-        .ok_or_else(|| format!("No window found for session {}", sessionId))?;
+        .ok_or_else(|| BertError::NotFound(format!("No window found for session {}", sessionId)))?;
 
     // Close the window
     if let Some(window) = app.get_webview_window(&window_label) {
-        window.close().map_err(|e| format!("Failed to close window: {}", e))?;
+        window.close().map_err(|e| BertError::Window(format!("Failed to close window: {}", e)))?;
         eprintln!("✅ Closed window: {}", window_label);
     } else {
         eprintln!("⚠️  Window {} not found (may already be closed)", window_label);
@@ -250,7 +252,7 @@ pub async fn close_session_window(
 #[tauri::command]
 pub async fn list_session_windows(
     app: AppHandle,
-) -> Result<Vec<WindowInfo>, String> {
+) -> Result<Vec<WindowInfo>, BertError> {
     let registry = app.state::<WindowRegistry>();
     Ok(registry.get_all_windows())
 }
@@ -273,21 +275,21 @@ pub struct WindowState {
 }
 
 /// Get the path to the window state file (~/.bp6/window-state.json)
-fn get_window_state_file_path() -> Result<PathBuf, String> {
+fn get_window_state_file_path() -> Result<PathBuf, BertError> {
     let home = dirs::home_dir().ok_or("Failed to get home directory")?;
     let bp6_dir = home.join(".bp6");
 
     // Ensure directory exists
     if !bp6_dir.exists() {
         fs::create_dir_all(&bp6_dir)
-            .map_err(|e| format!("Failed to create .bp6 directory: {}", e))?;
+            .map_err(|e| BertError::Io(format!("Failed to create .bp6 directory: {}", e)))?;
     }
 
     Ok(bp6_dir.join("window-state.json"))
 }
 
 /// Load all window states from disk
-fn load_window_states() -> Result<HashMap<String, WindowState>, String> {
+fn load_window_states() -> Result<HashMap<String, WindowState>, BertError> {
     let path = get_window_state_file_path()?;
 
     if !path.exists() {
@@ -295,21 +297,21 @@ fn load_window_states() -> Result<HashMap<String, WindowState>, String> {
     }
 
     let contents = fs::read_to_string(&path)
-        .map_err(|e| format!("Failed to read window state file: {}", e))?;
+        .map_err(|e| BertError::Io(format!("Failed to read window state file: {}", e)))?;
 
     serde_json::from_str(&contents)
-        .map_err(|e| format!("Failed to parse window state file: {}", e))
+        .map_err(|e| BertError::Parse(format!("Failed to parse window state file: {}", e)))
 }
 
 /// Save all window states to disk
-fn save_window_states(states: &HashMap<String, WindowState>) -> Result<(), String> {
+fn save_window_states(states: &HashMap<String, WindowState>) -> Result<(), BertError> {
     let path = get_window_state_file_path()?;
 
     let contents = serde_json::to_string_pretty(states)
-        .map_err(|e| format!("Failed to serialize window states: {}", e))?;
+        .map_err(|e| BertError::Internal(format!("Failed to serialize window states: {}", e)))?;
 
     fs::write(&path, contents)
-        .map_err(|e| format!("Failed to write window state file: {}", e))
+        .map_err(|e| BertError::Io(format!("Failed to write window state file: {}", e)))
 }
 
 /// Clean up stale window states (sessions closed > 30 days ago)
@@ -344,7 +346,7 @@ pub async fn save_window_state(
     width: u32,
     height: u32,
     isMaximized: bool,
-) -> Result<(), String> {
+) -> Result<(), BertError> {
     let mut states = load_window_states()?;
 
     // Cleanup stale entries before saving
@@ -381,7 +383,7 @@ pub async fn save_window_state(
 #[allow(non_snake_case)]
 pub async fn load_window_state(
     sessionId: String,
-) -> Result<Option<WindowState>, String> {
+) -> Result<Option<WindowState>, BertError> {
     let states = load_window_states()?;
     Ok(states.get(&sessionId).cloned())
 }
@@ -398,16 +400,16 @@ pub async fn toggle_window_always_on_top(
     app: AppHandle,
     windowLabel: String,
     alwaysOnTop: bool,
-) -> Result<(), String> {
+) -> Result<(), BertError> {
     eprintln!("🔄 toggle_window_always_on_top: window={}, state={}", windowLabel, alwaysOnTop);
 
     if let Some(window) = app.get_webview_window(&windowLabel) {
         window.set_always_on_top(alwaysOnTop)
-            .map_err(|e| format!("Failed to set always-on-top: {}", e))?;
+            .map_err(|e| BertError::Window(format!("Failed to set always-on-top: {}", e)))?;
         eprintln!("✅ Set always-on-top={} for window: {}", alwaysOnTop, windowLabel);
         Ok(())
     } else {
-        Err(format!("Window not found: {}", windowLabel))
+        Err(BertError::NotFound(format!("Window not found: {}", windowLabel)))
     }
 }
 
@@ -0,0 +1,201 @@
+//! Configurable workflow states
+//!
+//! Bead statuses are free-form strings, and teams use more than
+//! open/in_progress/closed (e.g. "review", "qa", "deferred"). A per-project
+//! workflow stored under the `workflow` key of `.beads/bert-viz.json` lists the
+//! custom states, the category each one belongs to and its display color.
+//! Filtering, distributions and metadata work on categories, so a custom
+//! "review" state counts as in progress and "shipped" counts as closed.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Map;
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::error::BertError;
+use crate::schedule::{config_path_for, load_config_section};
+
+/// Key of the workflow section in the project config file
+const CONFIG_KEY: &str = "workflow";
+
+/// What a status means for scheduling and progress reporting
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusCategory {
+    Todo,
+    Doing,
+    Done,
+    Blocked,
+}
+
+/// One workflow state
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowState {
+    /// Status string as stored in issues.jsonl
+    pub name: String,
+    pub category: StatusCategory,
+    /// CSS color used for the state in the UI
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+/// Workflow settings for a project
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowConfig {
+    pub states: Vec<WorkflowState>,
+}
+
+fn state(name: &str, category: StatusCategory, color: &str) -> WorkflowState {
+    WorkflowState {
+        name: name.to_string(),
+        category,
+        color: Some(color.to_string()),
+    }
+}
+
+/// The statuses `bd` itself uses
+fn builtin_states() -> Vec<WorkflowState> {
+    vec![
+        state("open", StatusCategory::Todo, "#94a3b8"),
+        state("pending", StatusCategory::Todo, "#94a3b8"),
+        state("in_progress", StatusCategory::Doing, "#f59e0b"),
+        state("blocked", StatusCategory::Blocked, "#ef4444"),
+        state("closed", StatusCategory::Done, "#10b981"),
+        state("done", StatusCategory::Done, "#10b981"),
+    ]
+}
+
+impl Default for WorkflowConfig {
+    fn default() -> Self {
+        WorkflowConfig { states: builtin_states() }
+    }
+}
+
+impl WorkflowConfig {
+    /// Category of a status. Configured states win, then the built-in `bd`
+    /// statuses; anything unknown is treated as not started.
+    pub fn category_of(&self, status: &str) -> StatusCategory {
+        self.states
+            .iter()
+            .find(|s| s.name == status)
+            .map(|s| s.category)
+            .or_else(|| builtin_states().into_iter().find(|s| s.name == status).map(|s| s.category))
+            .unwrap_or(StatusCategory::Todo)
+    }
+
+    /// Whether a status counts as finished work
+    pub fn is_done(&self, status: &str) -> bool {
+        self.category_of(status) == StatusCategory::Done
+    }
+
+    /// Whether a status is a configured state or one of bd's own
+    pub fn is_known_status(&self, status: &str) -> bool {
+        self.states.iter().chain(builtin_states().iter()).any(|s| s.name == status)
+    }
+
+    /// Load the workflow stored alongside `beads_path`.
+    /// Returns defaults if the file or the `workflow` section doesn't exist.
+    pub fn load_for_beads_file(beads_path: &Path) -> Result<Self, BertError> {
+        let Some(config) = load_config_section::<WorkflowConfig>(beads_path, CONFIG_KEY)? else {
+            return Ok(Self::default());
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Save the workflow alongside `beads_path`, keeping the other settings in the file
+    pub fn save_for_beads_file(&self, beads_path: &Path) -> Result<(), BertError> {
+        self.validate()?;
+        let mut fields = Map::new();
+        fields.insert(CONFIG_KEY.to_string(), serde_json::to_value(self)?);
+        crate::schedule::merge_config_fields(beads_path, fields)
+    }
+
+    fn validate(&self) -> Result<(), BertError> {
+        let mut seen = HashSet::new();
+        for state in &self.states {
+            if state.name.trim().is_empty() {
+                return Err(BertError::InvalidInput("Workflow state names cannot be empty".to_string()));
+            }
+            if !seen.insert(state.name.as_str()) {
+                return Err(BertError::InvalidInput(format!("Duplicate workflow state '{}'", state.name)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Get the workflow config for the current project
+#[tauri::command]
+pub fn get_workflow_config(window: tauri::Window, project_handle: Option<String>) -> Result<WorkflowConfig, BertError> {
+    let beads_path = crate::project::context_for(&window, project_handle.as_deref())?.beads_path()?;
+    WorkflowConfig::load_for_beads_file(&beads_path)
+}
+
+/// Save the workflow config for the current project
+#[tauri::command]
+pub fn save_workflow_config(
+    config: WorkflowConfig,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    project_state: tauri::State<'_, crate::view_cache::ProjectState>,
+) -> Result<(), BertError> {
+    let beads_path = crate::project::context_for(&window, project_handle.as_deref())?.beads_path()?;
+    config.save_for_beads_file(&beads_path)?;
+    project_state.invalidate(&crate::project::project_key(&window, project_handle.as_deref()));
+    eprintln!("🔀 Saved workflow config ({} states) to {}", config.states.len(), config_path_for(&beads_path).display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedule::SchedulingConfig;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_custom_states_and_builtin_fallback() {
+        let config = WorkflowConfig {
+            states: vec![
+                state("review", StatusCategory::Doing, "#6366f1"),
+                state("shipped", StatusCategory::Done, "#10b981"),
+            ],
+        };
+        assert_eq!(config.category_of("review"), StatusCategory::Doing);
+        assert!(config.is_done("shipped"));
+        assert!(config.is_done("closed"), "built-in statuses still apply");
+        assert_eq!(config.category_of("blocked"), StatusCategory::Blocked);
+        assert_eq!(config.category_of("whatever"), StatusCategory::Todo);
+    }
+
+    #[test]
+    fn test_workflow_and_scheduling_share_the_config_file() {
+        let dir = TempDir::new().unwrap();
+        let beads_path = dir.path().join("issues.jsonl");
+
+        let scheduling = SchedulingConfig {
+            start_date: Some("2026-10-19".to_string()),
+            ..SchedulingConfig::default()
+        };
+        scheduling.save_for_beads_file(&beads_path).unwrap();
+
+        let workflow = WorkflowConfig {
+            states: vec![state("qa", StatusCategory::Doing, "#a855f7")],
+        };
+        workflow.save_for_beads_file(&beads_path).unwrap();
+        scheduling.save_for_beads_file(&beads_path).unwrap();
+
+        assert_eq!(WorkflowConfig::load_for_beads_file(&beads_path).unwrap(), workflow);
+        assert_eq!(SchedulingConfig::load_for_beads_file(&beads_path).unwrap(), scheduling);
+    }
+
+    #[test]
+    fn test_duplicate_state_is_rejected() {
+        let config = WorkflowConfig {
+            states: vec![state("qa", StatusCategory::Doing, "#fff"), state("qa", StatusCategory::Done, "#000")],
+        };
+        assert!(matches!(config.validate(), Err(BertError::InvalidInput(_))));
+    }
+}
@@ -0,0 +1,192 @@
+//! Per-owner workload report
+//!
+//! Sums up the unfinished leaf tasks of each owner in the current view model:
+//! how many are open or in progress, their total estimate, how many are
+//! blocked or on the critical path, and how many of them are scheduled in the
+//! same cell at most. Owners whose peak exceeds the `ownerCapacity` of the
+//! scheduling config are flagged as overallocated.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::State;
+
+use crate::error::BertError;
+use crate::schedule::SchedulingConfig;
+use crate::view_cache::ProjectState;
+use crate::workflow::{StatusCategory, WorkflowConfig};
+use crate::{BeadNode, FilterParams};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnerWorkload {
+    /// None for unassigned tasks
+    pub owner: Option<String>,
+    pub open_count: usize,
+    pub in_progress_count: usize,
+    /// Estimate of the open and in-progress tasks, in minutes
+    pub total_estimate: u64,
+    pub blocked_count: usize,
+    pub critical_count: usize,
+    /// Most tasks scheduled in the same cell
+    pub peak_concurrency: usize,
+    pub overallocated: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkloadReport {
+    /// Owners by name, unassigned tasks last
+    pub owners: Vec<OwnerWorkload>,
+    /// Tasks an owner can work on at once
+    pub capacity: usize,
+}
+
+/// Cells (offset, count) an owner's tasks are scheduled in
+type Spans = Vec<(usize, usize)>;
+
+/// Most of `spans` (cell offset, cell count) that overlap in one cell
+fn peak_concurrency(spans: &[(usize, usize)]) -> usize {
+    let mut events: Vec<(usize, i64)> = spans
+        .iter()
+        .filter(|(_, count)| *count > 0)
+        .flat_map(|&(offset, count)| [(offset, 1), (offset + count, -1)])
+        .collect();
+    // Ends sort before starts in the same cell, so back-to-back tasks don't overlap
+    events.sort();
+
+    let mut current = 0i64;
+    let mut peak = 0i64;
+    for (_, delta) in events {
+        current += delta;
+        peak = peak.max(current);
+    }
+    peak as usize
+}
+
+fn build_report(tree: &[BeadNode], workflow: &WorkflowConfig, capacity: usize) -> WorkloadReport {
+    fn collect<'a>(nodes: &'a [BeadNode], leaves: &mut Vec<&'a BeadNode>) {
+        for node in nodes {
+            if node.children.is_empty() {
+                leaves.push(node);
+            } else {
+                collect(&node.children, leaves);
+            }
+        }
+    }
+    let mut leaves = Vec::new();
+    collect(tree, &mut leaves);
+
+    // BTreeMap orders owners by name with None (unassigned) first
+    let mut by_owner: BTreeMap<Option<String>, (OwnerWorkload, Spans)> = BTreeMap::new();
+    for node in leaves {
+        let category = workflow.category_of(&node.status);
+        if category == StatusCategory::Done {
+            continue;
+        }
+        let owner = node.owner.clone().filter(|o| !o.is_empty());
+        let (workload, spans) = by_owner.entry(owner.clone()).or_insert_with(|| {
+            (OwnerWorkload { owner, ..OwnerWorkload::default() }, Vec::new())
+        });
+
+        if category == StatusCategory::Doing {
+            workload.in_progress_count += 1;
+        } else {
+            workload.open_count += 1;
+        }
+        workload.total_estimate += node.estimate.unwrap_or(0) as u64;
+        if category == StatusCategory::Blocked || node.is_blocked {
+            workload.blocked_count += 1;
+        }
+        if node.is_critical {
+            workload.critical_count += 1;
+        }
+        spans.push((node.cell_offset, node.cell_count));
+    }
+
+    let mut owners: Vec<OwnerWorkload> = by_owner
+        .into_values()
+        .map(|(mut workload, spans)| {
+            workload.peak_concurrency = peak_concurrency(&spans);
+            workload.overallocated = workload.owner.is_some() && workload.peak_concurrency > capacity;
+            workload
+        })
+        .collect();
+    if owners.first().is_some_and(|w| w.owner.is_none()) {
+        owners.rotate_left(1);
+    }
+
+    WorkloadReport { owners, capacity }
+}
+
+/// Workload of each owner across the beads matching `params`
+#[tauri::command]
+pub fn get_workload_report(
+    params: FilterParams,
+    window: tauri::Window,
+    project_handle: Option<String>,
+    project_state: State<'_, ProjectState>,
+) -> Result<WorkloadReport, BertError> {
+    let beads_path = crate::project::context_for(&window, project_handle.as_deref())?.beads_path()?;
+    let workflow = WorkflowConfig::load_for_beads_file(&beads_path)?;
+    let scheduling = SchedulingConfig::load_for_beads_file(&beads_path)?;
+
+    let (_version, view_model) = project_state.load(&crate::project::project_key(&window, project_handle.as_deref()), &beads_path, &params)?;
+    Ok(build_report(&view_model.tree, &workflow, scheduling.owner_capacity))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Bead;
+    use serde_json::json;
+
+    fn bead(id: &str, status: &str, owner: Option<&str>, estimate: u32, blockers: &[&str]) -> Bead {
+        let deps: Vec<_> = blockers
+            .iter()
+            .map(|b| json!({ "issue_id": id, "depends_on_id": b, "type": "blocks" }))
+            .collect();
+        crate::test_bead(json!({
+            "id": id,
+            "status": status,
+            "owner": owner,
+            "estimate": estimate,
+            "dependencies": deps,
+        }))
+    }
+
+    #[test]
+    fn test_report_counts_work_and_flags_overallocation() {
+        let beads = vec![
+            bead("a", "in_progress", Some("ann"), 60, &[]),
+            bead("b", "open", Some("ann"), 120, &[]),
+            bead("c", "open", Some("bob"), 30, &["a"]),
+            bead("d", "open", Some("bob"), 30, &["c"]),
+            bead("e", "closed", Some("bob"), 30, &[]),
+            bead("f", "open", None, 0, &[]),
+        ];
+        let workflow = WorkflowConfig::default();
+        let view_model = crate::bert_core::build_project_view_model(beads, &FilterParams::default(), &SchedulingConfig::default(), &workflow);
+        let report = build_report(&view_model.tree, &workflow, 1);
+
+        let owners: Vec<_> = report.owners.iter().map(|w| w.owner.as_deref()).collect();
+        assert_eq!(owners, vec![Some("ann"), Some("bob"), None]);
+
+        let ann = &report.owners[0];
+        assert_eq!((ann.open_count, ann.in_progress_count, ann.total_estimate), (1, 1, 180));
+        // a and b both start in the first cell
+        assert_eq!(ann.peak_concurrency, 2);
+        assert!(ann.overallocated);
+
+        let bob = &report.owners[1];
+        assert_eq!((bob.open_count, bob.total_estimate, bob.blocked_count), (2, 60, 2));
+        // c waits for a and d for c, so they never overlap
+        assert_eq!(bob.peak_concurrency, 1);
+        assert!(!bob.overallocated);
+    }
+
+    #[test]
+    fn test_back_to_back_tasks_do_not_overlap() {
+        assert_eq!(peak_concurrency(&[(0, 2), (2, 1), (1, 3)]), 2);
+        assert_eq!(peak_concurrency(&[(0, 0), (0, 0)]), 0);
+    }
+}
@@ -24,6 +24,7 @@ import {
   onProjectsUpdated,
   saveWindowState,
   loadStartupState,
+  restoreSessions,
   saveStartupState,
   fetchBeads,
 } from "./api";
@@ -426,6 +427,9 @@ function App({ isSessionWindow = false, sessionId = null, windowLabel = "main" }
         const projs = await fetchProjects();
         setProjects(projs);
 
+        // Re-register sessions left open when the app last closed
+        await restoreSessions();
+
         // Initialize session store (loads sessions and sets up event listener)
         const sessionUnlisten = await useSessionStore.getState().initializeStore();
 
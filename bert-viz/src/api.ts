@@ -1,5 +1,6 @@
 import { invoke } from "@tauri-apps/api/core";
 import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
 
 export type { UnlistenFn };
 
@@ -11,6 +12,47 @@ export interface Dependency {
   [key: string]: any;
 }
 
+/** Stable error codes returned by backend commands (see src-tauri/src/error.rs) */
+export type BertErrorCode =
+  | "bd_not_installed"
+  | "bd_command_failed"
+  | "cli_not_installed"
+  | "beads_file_not_found"
+  | "project_not_found"
+  | "file_locked"
+  | "parse_error"
+  | "not_found"
+  | "invalid_input"
+  | "io_error"
+  | "agent_error"
+  | "window_error"
+  | "git_error"
+  | "close_with_open_children"
+  | "close_with_unmet_acceptance"
+  | "read_only_snapshot"
+  | "start_while_blocked"
+  | "reopen_closed_epic"
+  | "internal_error";
+
+/** Structured error rejected by backend commands */
+export interface BertError {
+  code: BertErrorCode;
+  message: string;
+  /** Whether the user can resolve it (install a tool, retry, fix input) */
+  recoverable: boolean;
+}
+
+export function isBertError(error: unknown): error is BertError {
+  return typeof error === "object" && error !== null && "code" in error && "message" in error;
+}
+
+/** Human-readable message for an error thrown by `invoke` */
+export function errorMessage(error: unknown): string {
+  if (isBertError(error)) return error.message;
+  if (error instanceof Error) return error.message;
+  return String(error);
+}
+
 export interface Bead {
   id: string;
   title: string;
@@ -80,6 +122,47 @@ export async function openProject(path: string): Promise<void> {
   await invoke("open_project", { path });
 }
 
+/** A project open in a tab of this window */
+export interface ProjectTab {
+  /** Pass as `projectHandle` to data commands to work on this tab's project */
+  handle: string;
+  name: string;
+  projectPath: string;
+}
+
+/** Open a project in a new tab of this window (or return the tab that has it open) */
+export async function openProjectTab(path: string): Promise<ProjectTab> {
+  return await invoke<ProjectTab>("open_project_tab", { path });
+}
+
+export async function closeProjectTab(projectHandle: string): Promise<void> {
+  await invoke("close_project_tab", { projectHandle });
+}
+
+/** Project tabs of this window, in the order they were opened */
+export async function listProjectTabs(): Promise<ProjectTab[]> {
+  return await invoke<ProjectTab[]>("list_project_tabs");
+}
+
+export type StarterTemplate = "getting-started" | "release";
+
+export interface InitProjectOptions {
+  name?: string;            // Defaults to the folder name
+  prefix?: string;          // Bead ID prefix
+  starterTemplate?: StarterTemplate;
+}
+
+export interface InitProjectResult {
+  path: string;
+  usedBd: boolean;          // False when .beads was created without the bd CLI
+  starterEpicId: string | null;
+}
+
+/** Set up bead tracking in a folder without .beads and open it in this window */
+export async function initProject(path: string, options?: InitProjectOptions): Promise<InitProjectResult> {
+  return await invoke<InitProjectResult>("init_project", { path, options: options ?? null });
+}
+
 export async function fetchBeads(): Promise<Bead[]> {
   try {
     return await invoke<Bead[]>("get_beads");
@@ -89,15 +172,410 @@ export async function fetchBeads(): Promise<Bead[]> {
   }
 }
 
-export async function updateBead(bead: Bead): Promise<void> {
+/** Last bead set read intact; `generation` increases each time the file's contents are accepted */
+export interface BeadSnapshot {
+  generation: number;
+  takenAt: string;
+  beads: Bead[];
+}
+
+/** The last good bead set, to keep showing while the bd daemon rewrites the file */
+export async function fetchLastGoodSnapshot(): Promise<BeadSnapshot> {
+  return await invoke<BeadSnapshot>("get_last_good_snapshot");
+}
+
+/** A line of issues.jsonl that isn't a valid bead and was skipped */
+export interface ParseWarning {
+  line: number;  // 1-based
+  message: string;
+  excerpt: string;  // Start of the offending line
+}
+
+/** Lines of the beads file skipped by the last read */
+export async function fetchParseWarnings(): Promise<ParseWarning[]> {
+  return await invoke<ParseWarning[]>("get_parse_warnings");
+}
+
+export type LintIssueKind =
+  | 'invalid-json'
+  | 'missing-field'
+  | 'duplicate-id'
+  | 'invalid-status'
+  | 'invalid-priority'
+  | 'unknown-issue-type'
+  | 'malformed-timestamp'
+  | 'dangling-dependency'
+  | 'parent-mismatch';
+
+export interface LintFix {
+  description: string;
+  command: string | null;  // bd command applying the fix, when there is one
+}
+
+export interface LintIssue {
+  line: number;  // 1-based
+  beadId: string | null;
+  kind: LintIssueKind;
+  severity: 'error' | 'warning';
+  field: string | null;
+  message: string;
+  fix: LintFix | null;
+}
+
+export interface LintReport {
+  valid: boolean;  // No errors (warnings are allowed)
+  lineCount: number;
+  issues: LintIssue[];
+}
+
+/** Check the beads file against the shape bd writes, with line numbers and suggested fixes */
+export async function lintBeads(): Promise<LintReport> {
+  return await invoke<LintReport>("lint_beads");
+}
+
+/** Look up a single bead without reloading the whole project */
+export async function fetchBead(id: string): Promise<Bead> {
+  return await invoke<Bead>("get_bead", { id });
+}
+
+/** Direct children of a bead, in file order */
+export async function fetchChildren(id: string): Promise<Bead[]> {
+  return await invoke<Bead[]>("get_children", { id });
+}
+
+/** Ancestors of a bead, parent first and root last */
+export async function fetchAncestors(id: string): Promise<Bead[]> {
+  return await invoke<Bead[]>("get_ancestors", { id });
+}
+
+/** Beads whose text mentions beadId ("mentioned by") */
+export async function fetchBacklinks(beadId: string): Promise<Bead[]> {
+  return await invoke<Bead[]>("get_backlinks", { beadId });
+}
+
+/** A bead reached from the analysed one over blocking dependencies */
+export interface ImpactedBead {
+  id: string;
+  title: string;
+  status: string;
+  owner: string | null;
+  /** Fewest blocking hops from the analysed bead */
+  depth: number;
+}
+
+/** Critical path through unfinished beads, now and with the bead closed (lengths in beads) */
+export interface CriticalPathImpact {
+  onCriticalPath: boolean;
+  length: number;
+  lengthIfClosed: number;
+  estimateMinutes: number;
+  estimateMinutesIfClosed: number;
+}
+
+export interface ImpactAnalysis {
+  beadId: string;
+  /** Beads transitively blocked by this one, nearest first */
+  downstream: ImpactedBead[];
+  /** Beads this one transitively waits on, nearest first */
+  upstream: ImpactedBead[];
+  criticalPath: CriticalPathImpact;
+  /** Owners (null = unassigned) with unfinished downstream beads, most affected first */
+  affectedOwners: { owner: string | null; beadIds: string[] }[];
+}
+
+/** What a bead blocks and waits on, and what closing it would do to the critical path */
+export async function fetchImpactAnalysis(beadId: string): Promise<ImpactAnalysis> {
+  return await invoke<ImpactAnalysis>("get_impact_analysis", { beadId });
+}
+
+export interface ReadyQueueParams {
+  limit?: number;
+  /** Only beads owned by this user or unassigned */
+  owner?: string;
+  /** Only beads carrying all of these labels */
+  labels?: string[];
+  includeEpics?: boolean;
+  includeInProgress?: boolean;
+}
+
+/** A bead whose blockers are all finished */
+export interface ReadyBead {
+  id: string;
+  title: string;
+  status: string;
+  priority: number;
+  issueType: string;
+  owner: string | null;
+  createdAt: string | null;
+  onCriticalPath: boolean;
+  /** Why it's ready, e.g. "All 2 blockers done (a, b)" */
+  reason: string;
+  /** Beads that become ready when this one closes */
+  unblocks: string[];
+  /** Unfinished beads transitively blocked by this one */
+  downstreamCount: number;
+}
+
+/** "What should I do next": ready beads, critical path first, then by priority and age */
+export async function fetchReadyQueue(params?: ReadyQueueParams): Promise<ReadyBead[]> {
+  return await invoke<ReadyBead[]>("get_ready_queue", { params });
+}
+
+/** A blocking dependency that looks missing, queued as a proposal */
+export interface DependencySuggestion {
+  blockerId: string;
+  blockedId: string;
+  /** 0..1 */
+  confidence: number;
+  reasons: string[];
+  command: string;
+  /** Approve with approveProposal, or reject with rejectProposal */
+  proposalId: string | null;
+}
+
+/**
+ * Suggest missing "blocks" dependencies from text references, shared artifacts
+ * and sibling numbering, in the project or the subtree of scope. Strongest first.
+ */
+export async function suggestDependencies(scope?: string): Promise<DependencySuggestion[]> {
+  return await invoke<DependencySuggestion[]>("suggest_dependencies", { scope: scope ?? null });
+}
+
+/** What a `project#bead-id` reference to another project resolved to */
+export type ExternalState = 'unresolved' | 'done' | 'open' | 'missing-project' | 'missing-bead' | 'invalid';
+
+export interface ExternalDependency {
+  reference: string;
+  projectPath: string | null;  // The project directory the reference resolved to
+  beadId: string;
+  state: ExternalState;
+  title: string | null;
+  status: string | null;
+}
+
+export interface ExternalCheck extends ExternalDependency {
+  fromBeadId: string;  // The bead in this project that waits on it
+}
+
+/** Look up every cross-project dependency of the project, including broken ones */
+export async function checkExternalDependencies(): Promise<ExternalCheck[]> {
+  return await invoke<ExternalCheck[]>("check_external_dependencies");
+}
+
+/**
+ * Make a bead wait on a bead in another project. reference is
+ * `project#bead-id`, with the project's path (absolute or relative to this
+ * project) or registered name; it has to resolve.
+ */
+export async function addExternalDependency(beadId: string, reference: string): Promise<ExternalDependency> {
+  return await invoke<ExternalDependency>("add_external_dependency", { beadId, reference });
+}
+
+export async function removeExternalDependency(beadId: string, reference: string): Promise<void> {
+  await invoke("remove_external_dependency", { beadId, reference });
+}
+
+/** An estimate for an unestimated bead, queued as a proposal */
+export interface EstimateSuggestion {
+  beadId: string;
+  title: string;
+  /** Minutes */
+  estimate: number;
+  /** 0..1 */
+  confidence: number;
+  reasons: string[];
+  command: string;
+  proposalId: string | null;
+}
+
+export interface EstimateSuggestions {
+  suggestions: EstimateSuggestion[];
+  /** Product manager session proposing further estimates, when useAgent was set */
+  sessionId: string | null;
+}
+
+/**
+ * Suggest estimates for unestimated beads from similar finished beads and
+ * estimated siblings, in the project or the subtree of scope. With useAgent,
+ * also asks an agent session, whose replies arrive as more proposals.
+ */
+export async function suggestEstimates(scope?: string, useAgent = false, cliBackend?: string): Promise<EstimateSuggestions> {
+  return await invoke<EstimateSuggestions>("suggest_estimates", { scope: scope ?? null, useAgent, cliBackend: cliBackend ?? null });
+}
+
+/** What a bead must match for a rule to apply; omitted fields match anything */
+export interface RuleCondition {
+  issueType?: string | null;
+  priority?: number | null;
+  status?: string | null;
+  label?: string | null;
+  /** Created at least this many days ago */
+  olderThanDays?: number | null;
+  /** Not updated for at least this many days */
+  idleDays?: number | null;
+  /** Blocked (by status or an unfinished blocker) and not updated for this many days */
+  blockedDays?: number | null;
+}
+
+export type RuleAction =
+  | { type: "escalate"; priority: number }
+  | { type: "add-label"; label: string }
+  | { type: "set-status"; status: string };
+
+/** A priority aging or escalation rule, e.g. "open P2 bugs older than 14 days become P1" */
+export interface Rule {
+  name: string;
+  enabled?: boolean;
+  when?: RuleCondition;
+  then: RuleAction;
+}
+
+export interface RuleChange {
+  rule: string;
+  beadId: string;
+  field: "priority" | "labels" | "status";
+  old: string;
+  new: string;
+}
+
+export interface RulesRun {
+  dryRun: boolean;
+  changes: RuleChange[];
+  /** Outcome of each bead update; empty for dry runs */
+  results: BeadOpResult[];
+}
+
+export async function fetchRules(): Promise<Rule[]> {
+  return await invoke<Rule[]>("get_rules");
+}
+
+export async function saveRules(rules: Rule[]): Promise<void> {
+  await invoke("save_rules", { rules });
+}
+
+/** Evaluate the project's rules; unless dryRun, apply the changes as one audited batch */
+export async function runRules(dryRun: boolean): Promise<RulesRun> {
+  return await invoke<RulesRun>("run_rules", { dryRun });
+}
+
+/** Every N days, or a five-field cron expression in local time */
+export type RecurrenceSchedule =
+  | { type: "interval"; days: number }
+  | { type: "cron"; expression: string };
+
+/** A template bead created again on a schedule */
+export interface Recurrence {
+  id: string;
+  templateId: string;
+  schedule: RecurrenceSchedule;
+  paused: boolean;
+  createdAt: string;
+  lastCreatedAt: string | null;
+  lastInstanceId: string | null;
+  /** null while paused */
+  nextDue: string | null;
+}
+
+export async function listRecurrences(): Promise<Recurrence[]> {
+  return await invoke<Recurrence[]>("list_recurrences");
+}
+
+/** Create copies of templateId on schedule, linked back to it; the first one after one period */
+export async function addRecurrence(templateId: string, schedule: RecurrenceSchedule): Promise<Recurrence> {
+  return await invoke<Recurrence>("add_recurrence", { templateId, schedule });
+}
+
+/** Pause a recurrence, or resume it without catching up on the skipped periods */
+export async function pauseRecurrence(recurrenceId: string, paused: boolean): Promise<Recurrence> {
+  return await invoke<Recurrence>("pause_recurrence", { recurrenceId, paused });
+}
+
+/**
+ * Move a bead and its subtree under newParentId (to the root when null),
+ * optionally at a position among its new siblings. Resolves to the moved subtree.
+ */
+export async function moveBead(beadId: string, newParentId: string | null, position?: number): Promise<WBSNode> {
+  return await invoke<WBSNode>("move_bead", { beadId, newParentId, position });
+}
+
+/**
+ * Store a manual order for the children of parentId (root beads when null),
+ * used by the 'manual' sort. Unlisted children follow the listed ones.
+ */
+export async function reorderSiblings(parentId: string | null, orderedIds: string[]): Promise<void> {
+  await invoke("reorder_siblings", { parentId, orderedIds });
+}
+
+export type TransitionKind = 'close-with-open-children' | 'close-with-unmet-acceptance' | 'start-while-blocked' | 'reopen-closed-epic';
+
+/** off: allowed silently, warn: allowed and reported, error: rejected unless forced */
+export type Enforcement = 'off' | 'warn' | 'error';
+
+export interface TransitionPolicy {
+  closeWithOpenChildren: Enforcement;
+  closeWithUnmetAcceptance: Enforcement;
+  startWhileBlocked: Enforcement;
+  reopenClosedEpic: Enforcement;
+}
+
+/** A status change the transition policy doesn't allow silently */
+export interface TransitionViolation {
+  kind: TransitionKind;
+  enforcement: Enforcement;
+  beadId: string;
+  fromStatus: string;
+  toStatus: string;
+  relatedIds: string[];  // The open children or unfinished blockers behind it
+  message: string;
+}
+
+export async function fetchTransitionPolicy(): Promise<TransitionPolicy> {
+  return await invoke<TransitionPolicy>("get_transition_policy");
+}
+
+export async function saveTransitionPolicy(policy: TransitionPolicy): Promise<void> {
+  await invoke("save_transition_policy", { policy });
+}
+
+/**
+ * Status-changing mutations resolve to the policy warnings they triggered.
+ * A rejected transition throws a BertError coded after it (e.g.
+ * "start_while_blocked"); pass force to apply it anyway.
+ */
+export async function updateBead(bead: Bead, force = false): Promise<TransitionViolation[]> {
   try {
-    await invoke("update_bead", { updatedBead: bead });
+    return await invoke<TransitionViolation[]>("update_bead", { updatedBead: bead, force });
   } catch (error) {
     console.error("Failed to update bead:", error);
     throw error;
   }
 }
 
+/** An acceptance criterion and whether it is met */
+export interface AcceptanceItem {
+  text: string;
+  done: boolean;
+}
+
+export interface AcceptanceProgress {
+  done: number;
+  total: number;
+  ratio: number;  // done / total, 0-1
+}
+
+/** Check off the acceptance criterion at index, or uncheck it; resolves to the checklist */
+export async function toggleAcceptanceItem(beadId: string, index: number): Promise<AcceptanceItem[]> {
+  return await invoke<AcceptanceItem[]>("toggle_acceptance_item", { beadId, index });
+}
+
+export async function addAcceptanceItem(beadId: string, text: string): Promise<AcceptanceItem[]> {
+  return await invoke<AcceptanceItem[]>("add_acceptance_item", { beadId, text });
+}
+
+/**
+ * Create a bead; resolves to its ID, or to `pending-<opId>` when the create
+ * was queued because bd or the repo is unavailable (see getPendingOps)
+ */
 export async function createBead(bead: Bead): Promise<string> {
   try {
     console.log('🔧 api.createBead: Invoking Tauri command with bead:', bead);
@@ -110,36 +588,112 @@ export async function createBead(bead: Bead): Promise<string> {
   }
 }
 
-export async function closeBead(beadId: string, reason?: string): Promise<void> {
+/** How one shorthand token of a quick-create line was read */
+export interface QuickField {
+  token: string;
+  field: 'issueType' | 'priority' | 'owner' | 'estimate' | 'parent' | 'labels';
+  value: string;
+}
+
+export interface QuickCreate {
+  bead: Bead;
+  fields: QuickField[];
+  beadId: string | null;  // null for a preview
+  preview: OpPreview | null;
+}
+
+/**
+ * Create a bead from one line like `Fix login redirect #bug p1 @alice est:2h
+ * parent:bp6-07y label:auth`. With preview, nothing is created and the result
+ * shows how the line was read.
+ */
+export async function quickCreate(text: string, preview = false): Promise<QuickCreate> {
+  return await invoke<QuickCreate>("quick_create", { text, preview });
+}
+
+export async function closeBead(beadId: string, reason?: string, force = false): Promise<TransitionViolation[]> {
   try {
-    await invoke("close_bead", { beadId, reason });
+    return await invoke<TransitionViolation[]>("close_bead", { beadId, reason, force });
   } catch (error) {
     console.error("Failed to close bead:", error);
     throw error;
   }
 }
 
-export async function reopenBead(beadId: string): Promise<void> {
+/** What to do with the open work under a bead being closed */
+export type CascadeStrategy =
+  | { strategy: 'close-all-descendants'; reasons?: Record<string, string> }  // Bead ID -> close reason
+  | { strategy: 'reparent-open-children'; newParentId: string }
+  | { strategy: 'abort-if-open-children' };
+
+export type CascadeStep =
+  | { action: 'close'; beadId: string; reason: string | null }
+  | { action: 'reparent'; beadId: string; fromParentId: string; toParentId: string };
+
+export interface CascadeOutcome {
+  steps: CascadeStep[];  // In execution order, ending with the bead itself
+  applied: boolean;      // False for previews
+}
+
+/**
+ * Close a bead and handle its open descendants with strategy. A failed step
+ * rolls back the ones before it; with preview, only the plan is returned.
+ */
+export async function closeBeadCascade(beadId: string, strategy: CascadeStrategy, reason?: string, preview = false): Promise<CascadeOutcome> {
+  return await invoke<CascadeOutcome>("close_bead_cascade", { beadId, strategy, reason: reason ?? null, preview });
+}
+
+/** What a mutation would do; returned instead of running it when previewing */
+export interface OpPreview {
+  /** bd command lines that would run, in order ('<new-id>' stands for the created ID) */
+  commands: string[];
+  /** bd is missing, so issues.jsonl would be patched directly (commands is empty) */
+  native: boolean;
+  /** The affected bead as it would look afterwards */
+  bead: Bead | null;
+}
+
+export async function previewUpdateBead(bead: Bead): Promise<OpPreview> {
+  return await invoke<OpPreview>("update_bead", { updatedBead: bead, preview: true });
+}
+
+export async function previewCreateBead(bead: Bead): Promise<OpPreview> {
+  return await invoke<OpPreview>("create_bead", { newBead: bead, preview: true });
+}
+
+export async function previewCloseBead(beadId: string, reason?: string): Promise<OpPreview> {
+  return await invoke<OpPreview>("close_bead", { beadId, reason, preview: true });
+}
+
+/**
+ * Preview a batch without running it; each result carries its preview, built
+ * on top of the predicted result of the operations before it
+ */
+export async function previewBatch(ops: PendingOp["op"][]): Promise<BeadOpResult[]> {
+  return await invoke<BeadOpResult[]>("batch_update_beads", { ops, preview: true });
+}
+
+export async function reopenBead(beadId: string, force = false): Promise<TransitionViolation[]> {
   try {
-    await invoke("reopen_bead", { beadId });
+    return await invoke<TransitionViolation[]>("reopen_bead", { beadId, force });
   } catch (error) {
     console.error("Failed to reopen bead:", error);
     throw error;
   }
 }
 
-export async function claimBead(beadId: string): Promise<void> {
+export async function claimBead(beadId: string, force = false): Promise<TransitionViolation[]> {
   try {
-    await invoke("claim_bead", { beadId });
+    return await invoke<TransitionViolation[]>("claim_bead", { beadId, force });
   } catch (error) {
     console.error("Failed to claim bead:", error);
     throw error;
   }
 }
 
-export async function fetchProcessedData(params: FilterParams): Promise<ProcessedData> {
+export async function fetchProcessedData(params: FilterParams, layoutKind: LayoutKind = 'gantt'): Promise<ProcessedData> {
   try {
-    return await invoke<ProcessedData>("get_processed_data", { params });
+    return await invoke<ProcessedData>("get_processed_data", { params, layoutKind });
   } catch (error) {
     console.error("Failed to fetch processed data:", error);
     throw error;
@@ -159,94 +713,736 @@ export async function fetchProjectViewModel(params: FilterParams): Promise<Proje
   }
 }
 
-// ============================================================================
-// Agent API (bp6-5s4.2.5)
-// ============================================================================
+/**
+ * Fetch only the visible rows startRow..startRow + rowCount of the view model,
+ * for virtualized scrolling. Rows have their children emptied.
+ */
+export async function fetchViewModelWindow(params: FilterParams, startRow: number, rowCount: number): Promise<ViewModelWindow> {
+  try {
+    return await invoke<ViewModelWindow>("get_view_model_window", { params, startRow, rowCount });
+  } catch (error) {
+    console.error("Failed to fetch view model window:", error);
+    throw error;
+  }
+}
 
-// NOTE: Rust uses #[serde(rename_all = "camelCase")], so fields are camelCase in JSON
-export interface SessionInfo {
-  sessionId: string;
-  beadId: string | null;  // Optional bead ID
-  persona: string;        // PersonaType as string
-  backendId: string;      // BackendId as string
-  status: 'running' | 'paused';
-  createdAt: number;      // Unix timestamp in seconds (Rust u64)
-  cliSessionId?: string | null;  // CLI session ID for resume capability
-  executionMode: 'headless' | 'interactive';  // Execution mode
-  commandsRemaining?: number | null;  // Number of commands remaining in queue (headless only)
-  totalCommands?: number | null;      // Total commands in original queue (headless only)
-  lastActivity: number;   // Unix timestamp of last activity (Rust u64)
-  hasUnread: boolean;     // Whether session has unread messages
-  messageCount: number;   // Number of messages in session
+/**
+ * Have the file watcher push this window's recomputed view model in a
+ * `view-model-updated` event whenever the beads change, using the params of
+ * the last view model request.
+ */
+export async function setViewModelPush(enabled: boolean): Promise<void> {
+  return await invoke("set_view_model_push", { enabled });
 }
 
-// Persona icon mapping (aligned with PersonaType enum - uses hyphens as per Rust backend)
-export const PERSONA_ICONS: Record<string, string> = {
-  'product-manager': '📋',
-  'qa-engineer': '🧪',
-  'specialist': '⚡',
-  // Future: architect, security, etc.
-};
+/** Payload of view-model-updated */
+export interface ViewModelUpdated {
+  version: number;
+  /** Tab the view model belongs to; null for the window's own project */
+  projectHandle: string | null;
+  viewModel: ProjectViewModel;
+}
 
 /**
- * Format session runtime from created_at timestamp to "Xm Ys" format.
- * @param createdAt Unix timestamp in seconds (Rust u64)
- * @returns Formatted runtime string (e.g., "5m 32s")
+ * Listen for view models pushed by the file watcher (see setViewModelPush).
+ * `beads-updated` is still emitted after each push.
  */
-export function formatSessionRuntime(createdAt: number): string {
-  const nowSeconds = Math.floor(Date.now() / 1000);
-  const elapsed = nowSeconds - createdAt;
-  const minutes = Math.floor(elapsed / 60);
-  const remainingSeconds = elapsed % 60;
-  return `${minutes}m ${remainingSeconds}s`;
+export async function onViewModelUpdated(callback: (update: ViewModelUpdated) => void): Promise<UnlistenFn> {
+  return getCurrentWebviewWindow().listen<ViewModelUpdated>("view-model-updated", (event) => {
+    callback(event.payload);
+  });
+}
+
+export interface OwnerWorkload {
+  owner: string | null;  // null for unassigned tasks
+  openCount: number;
+  inProgressCount: number;
+  totalEstimate: number;  // Minutes of open and in-progress work
+  blockedCount: number;
+  criticalCount: number;
+  peakConcurrency: number;  // Most tasks scheduled in the same cell
+  overallocated: boolean;
+}
+
+export interface WorkloadReport {
+  owners: OwnerWorkload[];
+  capacity: number;
 }
 
 /**
- * Get persona icon for a given persona type.
- * @param persona PersonaType as string (with hyphens, e.g., "product-manager")
- * @returns Emoji icon for the persona, or ❓ if unknown
+ * Per-owner load across the beads matching params. Owners with more tasks
+ * scheduled at once than the project's ownerCapacity are overallocated.
  */
-export function getPersonaIcon(persona: string): string {
-  return PERSONA_ICONS[persona] || '❓';
+export async function fetchWorkloadReport(params: FilterParams): Promise<WorkloadReport> {
+  return await invoke<WorkloadReport>("get_workload_report", { params });
 }
 
-export interface AgentChunk {
-  content: string;
-  isDone: boolean;
-  sessionId?: string;  // Session ID for multi-window routing
+/** An iteration; beads join it through a `sprint:<name>` label */
+export interface Sprint {
+  name: string;
+  start: string;  // YYYY-MM-DD
+  end: string;    // YYYY-MM-DD, inclusive
+  capacity: Record<string, number>;  // Owner -> estimate minutes
+}
+
+export interface OwnerSprintLoad {
+  owner: string | null;  // null for unassigned beads
+  committedEstimate: number;
+  completedEstimate: number;
+  capacity: number | null;
+  overCapacity: boolean;
+}
+
+export interface SprintReport {
+  sprint: Sprint;
+  beadIds: string[];
+  committedEstimate: number;
+  completedEstimate: number;  // Minutes finished by the end date
+  ended: boolean;
+  spillover: string[];  // Unfinished beads, once the sprint has ended
+  owners: OwnerSprintLoad[];
+  unestimatedCount: number;
+}
+
+export async function fetchSprints(): Promise<Sprint[]> {
+  return await invoke<Sprint[]>("list_sprints");
+}
+
+export async function saveSprints(sprints: Sprint[]): Promise<void> {
+  return await invoke("save_sprints", { sprints });
+}
+
+/** Move a bead into a sprint, or out of any sprint with null */
+export async function assignToSprint(beadId: string, sprint: string | null): Promise<Bead> {
+  return await invoke<Bead>("assign_to_sprint", { beadId, sprint });
+}
+
+export async function fetchSprintReport(sprint: string): Promise<SprintReport> {
+  return await invoke<SprintReport>("get_sprint_report", { sprint });
 }
 
+export type GraphFormat = 'mermaid' | 'dot';
+
 /**
- * CLI backend type for agent sessions.
- * - 'gemini': Use Google Gemini CLI
- * - 'claude' | 'claude-code': Use Claude Code CLI
+ * Blocking dependencies as Mermaid or Graphviz DOT text, ready to paste into
+ * docs. With scope, only the subtree of that bead (typically an epic).
  */
-export type CliBackend = 'gemini' | 'claude' | 'claude-code';
+export async function exportDependencyGraph(format: GraphFormat, scope?: string): Promise<string> {
+  return await invoke<string>("export_dependency_graph", { format, scope: scope ?? null });
+}
+
+/** What a read-only snapshot was exported from, for the viewer banner */
+export interface SnapshotInfo {
+  projectName: string;
+  exportedAt: string;
+  beadCount: number;
+  source: string;  // The bundle file it was opened from
+}
 
 /**
- * Start a new agent session.
- * @param persona - The agent persona ('specialist', 'product-manager', 'qa-engineer')
- * @param task - Optional task type for the persona
- * @param beadId - Optional bead ID for context
+ * Write the project to one compressed bundle at path: beads, comments,
+ * config, baselines and tracked time. Opening the file with openProject shows
+ * it read-only (every edit rejects with a "read_only_snapshot" BertError);
+ * importSnapshot restores it. Resolves to the bead count.
+ */
+export async function exportSnapshot(path: string): Promise<number> {
+  return await invoke<number>("export_snapshot", { path });
+}
+
+/** What importSnapshot restored */
+export interface ImportSummary {
+  projectPath: string;
+  projectName: string;
+  beadCount: number;
+  commentThreads: number;
+  baselines: number;
+  timeEntries: number;
+}
+
+/** Restore a snapshot bundle as a regular project in targetDir, which must not have one yet */
+export async function importSnapshot(path: string, targetDir: string): Promise<ImportSummary> {
+  return await invoke<ImportSummary>("import_snapshot", { path, targetDir });
+}
+
+/** The snapshot the project was opened from, or null for a regular project */
+export async function getSnapshotInfo(): Promise<SnapshotInfo | null> {
+  return await invoke<SnapshotInfo | null>("get_snapshot_info");
+}
+
+export type HealthFlagKind =
+  | 'blocked-work'
+  | 'stale-in-progress'
+  | 'missing-estimate'
+  | 'missing-acceptance-criteria'
+  | 'dependency-cycle'
+  | 'oversized-feature';
+
+export interface HealthFlag {
+  kind: HealthFlagKind;
+  severity: 'error' | 'warning';
+  message: string;
+  beadIds: string[];  // Beads to drill down into
+  penalty: number;    // Points taken off the score
+}
+
+export interface ProjectHealth {
+  score: number;      // 0-100
+  openCount: number;
+  blockedCount: number;
+  blockedRatio: number;
+  flags: HealthFlag[];  // Worst first
+}
+
+/** Health score and risk flags; thresholds default to 14 days and 15 open children */
+export async function fetchProjectHealth(staleDays?: number, maxOpenChildren?: number): Promise<ProjectHealth> {
+  return await invoke<ProjectHealth>("get_project_health", { staleDays: staleDays ?? null, maxOpenChildren: maxOpenChildren ?? null });
+}
+
+/** An in-progress bead not updated for the threshold */
+export interface StaleBead extends Bead {
+  days_stale: number;
+}
+
+/** In-progress beads idle for more than thresholdDays (default 14), oldest first */
+export async function fetchStaleBeads(thresholdDays?: number): Promise<StaleBead[]> {
+  return await invoke<StaleBead[]>("get_stale_beads", { thresholdDays: thresholdDays ?? null });
+}
+
+/**
+ * Start a QA engineer session that drafts nudges to the owners of stale beads
+ * and proposes re-triage commands for approval.
+ * Returns the session ID, or null when nothing is stale.
+ */
+export async function nudgeStaleBeads(thresholdDays?: number, cliBackend?: string): Promise<string | null> {
+  return await invoke<string | null>("nudge_stale_beads", { thresholdDays: thresholdDays ?? null, cliBackend: cliBackend ?? null });
+}
+
+// ============================================================================
+// Activity Feed
+// ============================================================================
+
+export interface FieldChange {
+  field: string;
+  old: unknown;  // null when the field was absent
+  new: unknown;
+}
+
+export type ActivityDetail =
+  | { type: "bead-change"; kind: "created" | "updated" | "deleted"; changes: FieldChange[] }
+  | { type: "session-started" | "session-ended"; sessionId: string; persona: string; backend: string }
+  | { type: "comment"; commentId: string; parentId: string | null; excerpt: string };
+
+export type ActivityEntry = {
+  id: string;
+  timestamp: string;
+  beadId: string | null;
+  beadTitle: string | null;  // null for deleted beads
+  actor: string | null;
+} & ActivityDetail;
+
+export interface ActivityPage {
+  entries: ActivityEntry[];
+  nextCursor: string | null;  // Pass back as cursor for the next (older) page
+}
+
+/** Bead changes, agent sessions and comments, newest first */
+export async function fetchActivityFeed(limit?: number, since?: string, cursor?: string): Promise<ActivityPage> {
+  return await invoke<ActivityPage>("get_activity_feed", { limit: limit ?? null, since: since ?? null, cursor: cursor ?? null });
+}
+
+// ============================================================================
+// Favorites
+// ============================================================================
+
+/** Star or unstar a bead; resolves to the bead with its new is_favorite */
+export async function toggleBeadFavorite(beadId: string): Promise<Bead> {
+  return await invoke<Bead>("toggle_bead_favorite", { beadId });
+}
+
+export async function fetchFavorites(): Promise<Bead[]> {
+  return await invoke<Bead[]>("get_favorites");
+}
+
+export interface FavoriteBeadChanged {
+  beadId: string;
+  title: string;
+  changes: FieldChange[];  // status/owner changes only
+}
+
+/** Fired when the status or owner of a starred bead changes on disk */
+export async function onFavoriteBeadChanged(callback: (change: FavoriteBeadChanged) => void): Promise<UnlistenFn> {
+  return getCurrentWebviewWindow().listen<FavoriteBeadChanged>("favorite-bead-changed", (event) => {
+    callback(event.payload);
+  });
+}
+
+// ============================================================================
+// Watchlist
+// ============================================================================
+
+/** A bead on the user's own watchlist; title/status/owner are null once it no longer exists */
+export interface WatchedBead {
+  beadId: string;
+  pinnedAt: string;
+  title: string | null;
+  status: string | null;
+  owner: string | null;
+}
+
+/** Pin a bead to the user's watchlist (kept in ~/.bp6, not in the repo) */
+export async function pinBead(beadId: string): Promise<WatchedBead[]> {
+  return await invoke<WatchedBead[]>("pin_bead", { beadId });
+}
+
+export async function unpinBead(beadId: string): Promise<WatchedBead[]> {
+  return await invoke<WatchedBead[]>("unpin_bead", { beadId });
+}
+
+export async function fetchWatchlist(): Promise<WatchedBead[]> {
+  return await invoke<WatchedBead[]>("get_watchlist");
+}
+
+// ============================================================================
+// Comments
+// ============================================================================
+
+export interface Comment {
+  id: string;
+  beadId: string;
+  parentId: string | null;  // Comment this one replies to
+  author: string | null;
+  authorEmail: string | null;
+  body: string;
+  createdAt: string;
+  editedAt: string | null;
+  deleted: boolean;         // Kept without a body when it has replies
+}
+
+/** Comments of a bead, oldest first; build threads from parentId */
+export async function fetchComments(beadId: string): Promise<Comment[]> {
+  return await invoke<Comment[]>("list_comments", { beadId });
+}
+
+export async function addComment(beadId: string, body: string, parentId?: string): Promise<Comment> {
+  return await invoke<Comment>("add_comment", { beadId, body, parentId: parentId ?? null });
+}
+
+export async function editComment(beadId: string, commentId: string, body: string): Promise<Comment> {
+  return await invoke<Comment>("edit_comment", { beadId, commentId, body });
+}
+
+export async function deleteComment(beadId: string, commentId: string): Promise<void> {
+  return await invoke("delete_comment", { beadId, commentId });
+}
+
+export async function onCommentsUpdated(callback: (update: { beadId: string }) => void): Promise<UnlistenFn> {
+  return listen<{ beadId: string }>('comments-updated', (event) => callback(event.payload));
+}
+
+// ============================================================================
+// Time Tracking
+// ============================================================================
+
+export interface TimeEntry {
+  id: string;
+  beadId: string;
+  owner: string | null;
+  startedAt: string;
+  endedAt: string | null;  // null while the timer runs
+  minutes: number;
+}
+
+export interface TimeEntryFilter {
+  beadId?: string;
+  owner?: string;
+  start?: string;  // YYYY-MM-DD, inclusive
+  end?: string;
+}
+
+export interface EstimateComparison {
+  beadId: string;
+  title: string | null;
+  estimate: number | null;
+  actualMinutes: number;
+  variancePercent: number | null;  // Positive when over the estimate
+}
+
+export interface TimeReport {
+  entries: TimeEntry[];
+  totalMinutes: number;
+  comparisons: EstimateComparison[];
+  running: TimeEntry | null;
+}
+
+/** Start tracking time on a bead; a timer running on another bead is stopped */
+export async function startTimer(beadId: string): Promise<TimeEntry> {
+  return await invoke<TimeEntry>("start_timer", { beadId });
+}
+
+/** Stop the running timer; null when none was running */
+export async function stopTimer(): Promise<TimeEntry | null> {
+  return await invoke<TimeEntry | null>("stop_timer");
+}
+
+export async function fetchTimeEntries(filter?: TimeEntryFilter): Promise<TimeReport> {
+  return await invoke<TimeReport>("get_time_entries", { filter: filter ?? null });
+}
+
+export type ActualSource = 'tracked' | 'cycle-time';
+
+export interface BeadVariance {
+  beadId: string;
+  title: string;
+  owner: string | null;
+  issueType: string;
+  labels: string[];
+  closedAt: string | null;
+  estimate: number;  // Minutes
+  actualMinutes: number;
+  source: ActualSource;
+  ratio: number;  // Actual over estimate
+  isOutlier: boolean;
+}
+
+export interface VarianceGroup {
+  key: string;
+  count: number;
+  estimateMinutes: number;
+  actualMinutes: number;
+  ratio: number;  // Total actual over total estimate
+  medianRatio: number;
+  outliers: number;
+}
+
+export interface VarianceReport {
+  beads: BeadVariance[];  // Worst ratio first
+  overall: VarianceGroup | null;
+  byOwner: VarianceGroup[];
+  byIssueType: VarianceGroup[];
+  byLabel: VarianceGroup[];
+  outlierRatio: number;
+  unestimated: number;
+}
+
+/**
+ * Estimate accuracy of the finished beads closed within the range (YYYY-MM-DD,
+ * inclusive; unbounded when omitted), using tracked time or else cycle time
+ */
+export async function fetchVarianceReport(range?: { start?: string; end?: string }): Promise<VarianceReport> {
+  return await invoke<VarianceReport>("get_variance_report", { range: range ?? null });
+}
+
+// ============================================================================
+// Labels
+// ============================================================================
+
+export interface LabelUsage {
+  name: string;
+  count: number;      // Beads carrying the label
+  color: string | null;
+}
+
+/** Outcome of updating one bead in a bulk operation */
+export interface BeadOpResult {
+  index: number;
+  success: boolean;
+  beadId: string | null;
+  error: string | null;
+  /** Waiting in the pending queue because bd or the repo was unavailable (error says why) */
+  queued: boolean;
+  /** Present for previewed batches */
+  preview?: OpPreview;
+}
+
+export async function listLabels(): Promise<LabelUsage[]> {
+  return await invoke<LabelUsage[]>("list_labels");
+}
+
+/** Rename a label on every bead; its color moves along */
+export async function renameLabel(oldName: string, newName: string): Promise<BeadOpResult[]> {
+  return await invoke<BeadOpResult[]>("rename_label", { old: oldName, new: newName });
+}
+
+/** Replace the labels in `from` with `into` on every bead carrying them */
+export async function mergeLabels(from: string[], into: string): Promise<BeadOpResult[]> {
+  return await invoke<BeadOpResult[]>("merge_labels", { from, into });
+}
+
+/** Set a label's color, or clear it with null; returns every label color */
+export async function setLabelColor(label: string, color: string | null): Promise<Record<string, string>> {
+  return await invoke<Record<string, string>>("set_label_color", { label, color });
+}
+
+// ============================================================================
+// Agent API (bp6-5s4.2.5)
+// ============================================================================
+
+// NOTE: Rust uses #[serde(rename_all = "camelCase")], so fields are camelCase in JSON
+export interface SessionInfo {
+  sessionId: string;
+  beadId: string | null;  // Optional bead ID
+  persona: string;        // PersonaType as string
+  backendId: string;      // BackendId as string
+  status: 'running' | 'paused' | 'stopped' | 'error';  // 'stopped' once a reply's CLI process exits, or after a restart
+  createdAt: number;      // Unix timestamp in seconds (Rust u64)
+  cliSessionId?: string | null;  // CLI session ID for resume capability
+  executionMode: 'headless' | 'interactive';  // Execution mode
+  commandsRemaining?: number | null;  // Number of commands remaining in queue (headless only)
+  totalCommands?: number | null;      // Total commands in original queue (headless only)
+  lastActivity: number;   // Unix timestamp of last activity (Rust u64)
+  hasUnread: boolean;     // Whether session has unread messages
+  messageCount: number;   // Number of messages in session
+  usage?: TokenUsage;     // Token usage accumulated over the session's CLI runs
+  worktree?: SessionWorktree | null;  // Dedicated git worktree (isolated sessions only)
+  spawnAttempts?: SpawnAttempt[];     // How the latest run got started, retries and failovers included
+}
+
+/** Why an agent run didn't start */
+export type StartupFailure = 'not-installed' | 'auth' | 'rate-limited' | 'transient' | 'failed';
+
+/** One try at starting an agent run */
+export interface SpawnAttempt {
+  backendId: string;
+  /** 1 for the first try on this backend */
+  attempt: number;
+  startedAt: string;
+  /** null for the attempt that started */
+  failure: StartupFailure | null;
+  error: string | null;
+}
+
+export interface SessionWorktree {
+  path: string;
+  branch: string;
+  repoRoot: string;
+}
+
+export interface AutopilotConfig {
+  /** Only beads carrying all of these labels are picked */
+  labels: string[];
+  maxConcurrency: number;
+  /** Stop after starting this many tasks */
+  maxTasks?: number | null;
+  pollIntervalSecs: number;
+  /** Stop once nothing is ready and no session is running */
+  stopWhenIdle: boolean;
+  /** Stop after the first task that fails to start or isn't closed */
+  stopOnFailure: boolean;
+  cliBackend?: CliBackend | null;
+  /** Run each session in its own worktree, merged back once its bead closes */
+  worktree: boolean;
+}
+
+export type AutopilotPhase = 'idle' | 'running' | 'stopping';
+
+export interface AutopilotRun {
+  beadId: string;
+  title: string;
+  sessionId: string;
+  startedAt: number;
+  finishedAt: number | null;
+  outcome: 'running' | 'closed' | 'notClosed' | 'failed';
+  error: string | null;
+}
+
+export interface AutopilotStatus {
+  phase: AutopilotPhase;
+  config: AutopilotConfig | null;
+  runs: AutopilotRun[];
+  stopReason: string | null;
+  lastPoll: number | null;
+  lastError: string | null;
+}
+
+export type SwarmTaskStatus = 'blocked' | 'ready' | 'running' | 'done' | 'failed';
+
+export interface SwarmTask {
+  beadId: string;
+  title: string;
+  status: SwarmTaskStatus;
+  sessionId: string | null;
+  error: string | null;
+}
+
+/** Progress of a feature swarm; finished once nothing is running or startable */
+export interface SwarmProgress {
+  featureId: string;
+  maxParallel: number;
+  tasks: SwarmTask[];
+  total: number;
+  done: number;
+  running: number;
+  failed: number;
+  finished: boolean;
+  stopped: boolean;
+  error: string | null;
+}
+
+/** A persona sessions can be started with; user personas come from ~/.bp6/personas */
+export interface PersonaInfo {
+  name: string;
+  builtIn: boolean;
+  description: string | null;
+  /** Tasks (templates) the persona accepts */
+  tasks: string[];
+  /** Backend used when a session doesn't name one */
+  backend: 'gemini' | 'claude' | 'ollama' | null;
+}
+
+export async function listPersonas(): Promise<PersonaInfo[]> {
+  return await invoke<PersonaInfo[]>('list_personas');
+}
+
+/**
+ * Variables prompt templates can use as {{name}}. Project variables win over
+ * the built-in ones; templates in .beads/templates/<persona>/ replace the
+ * built-in templates of the same name.
+ */
+export interface TemplateVariables {
+  /** Current values of project_name, tech_stack, branch and today */
+  builtin: Record<string, string>;
+  /** The project's own variables from .beads/bert-viz.json */
+  project: Record<string, string>;
+}
+
+export async function getTemplateVariables(): Promise<TemplateVariables> {
+  return await invoke<TemplateVariables>('get_template_variables');
+}
+
+/** Replace the project's own template variables; names use letters, digits and underscores */
+export async function setTemplateVariables(variables: Record<string, string>): Promise<void> {
+  await invoke('set_template_variables', { variables });
+}
+
+/** Fired when the user personas in ~/.bp6/personas are reloaded */
+export async function onPersonasChanged(callback: () => void): Promise<UnlistenFn> {
+  return listen('personas-changed', () => callback());
+}
+
+// Persona icon mapping (aligned with PersonaType enum - uses hyphens as per Rust backend)
+export const PERSONA_ICONS: Record<string, string> = {
+  'product-manager': '📋',
+  'qa-engineer': '🧪',
+  'specialist': '⚡',
+  // Future: architect, security, etc.
+};
+
+/**
+ * Format session runtime from created_at timestamp to "Xm Ys" format.
+ * @param createdAt Unix timestamp in seconds (Rust u64)
+ * @returns Formatted runtime string (e.g., "5m 32s")
+ */
+export function formatSessionRuntime(createdAt: number): string {
+  const nowSeconds = Math.floor(Date.now() / 1000);
+  const elapsed = nowSeconds - createdAt;
+  const minutes = Math.floor(elapsed / 60);
+  const remainingSeconds = elapsed % 60;
+  return `${minutes}m ${remainingSeconds}s`;
+}
+
+/**
+ * Get persona icon for a given persona type.
+ * @param persona PersonaType as string (with hyphens, e.g., "product-manager")
+ * @returns Emoji icon for the persona, or ❓ if unknown
+ */
+export function getPersonaIcon(persona: string): string {
+  return PERSONA_ICONS[persona] || '❓';
+}
+
+export interface AgentChunk {
+  content: string;
+  isDone: boolean;
+  sessionId?: string;  // Session ID for multi-window routing
+  usage?: TokenUsage;  // Set on the final chunk of a CLI run
+}
+
+/** Token counts and cost reported by a CLI backend (Gemini reports no cost) */
+export interface TokenUsage {
+  inputTokens: number;
+  outputTokens: number;
+  cacheReadTokens: number;
+  cacheCreationTokens: number;
+  costUsd: number | null;
+}
+
+/**
+ * CLI backend type for agent sessions.
+ * - 'gemini': Use Google Gemini CLI
+ * - 'claude' | 'claude-code': Use Claude Code CLI
+ * - 'ollama': Use a local Ollama server (see getOllamaConfig)
+ */
+export type CliBackend = 'gemini' | 'claude' | 'claude-code' | 'ollama';
+
+/**
+ * Start a new agent session.
+ * @param persona - The agent persona ('specialist', 'product-manager', 'qa-engineer')
+ * @param task - Optional task type for the persona
+ * @param beadId - Optional bead ID for context
  * @param cliBackend - Optional CLI backend to use (defaults to 'gemini' if not provided)
  * @param role - Optional specialist role ('web', 'rust', 'flutter', etc.) - only used with 'specialist' persona
- * @returns The session ID (UUID) of the newly created session
+ * @param worktree - Run the session in its own git worktree so parallel sessions don't share files
+ * @returns The session ID (UUID) of the newly created session. At the maxConcurrentSessions
+ * limit the session is queued under that ID instead (see getSessionQueue / onSessionQueueChanged)
  */
 export async function startAgentSession(
   persona: string,
   task?: string,
   beadId?: string,
   cliBackend?: CliBackend,
-  role?: string
+  role?: string,
+  worktree?: boolean
 ): Promise<string> {
   try {
-    return await invoke<string>("start_agent_session", { persona, task, beadId, cliBackend, role });
+    return await invoke<string>("start_agent_session", { persona, task, beadId, cliBackend, role, worktree });
   } catch (error) {
     console.error("Failed to start agent session:", error);
     throw error;
   }
 }
 
+/** A new session waiting for the concurrency limit */
+export interface QueuedSession {
+  sessionId: string;  // The ID it will run under
+  position: number;   // 1 starts next
+  persona: string;
+  task: string | null;
+  beadId: string | null;
+  backendId: CliBackend;
+  projectPath: string;
+  queuedAt: string;
+}
+
+export async function getSessionQueue(): Promise<QueuedSession[]> {
+  return await invoke<QueuedSession[]>("get_session_queue");
+}
+
+export async function cancelQueuedSession(id: string): Promise<void> {
+  await invoke("cancel_queued_session", { id });
+}
+
+/** Fired whenever sessions are queued, started from the queue or cancelled */
+export async function onSessionQueueChanged(callback: (queue: QueuedSession[]) => void): Promise<UnlistenFn> {
+  return listen<QueuedSession[]>("session-queue-changed", (event) => callback(event.payload));
+}
+
+/**
+ * "Work on this": claim the bead for the local user (in_progress, owner from
+ * settings or git) and start an implementation session on it. The claim is
+ * undone if the session fails to start.
+ * @param beadId - The bead to work on
+ * @param persona - Agent persona (defaults to 'product-manager')
+ * @param cliBackend - Optional CLI backend (defaults to the configured one)
+ * @returns The session ID (UUID) of the new session
+ */
+export async function workOnBead(beadId: string, persona?: string, cliBackend?: CliBackend): Promise<string> {
+  try {
+    return await invoke<string>("work_on_bead", { beadId, persona, cliBackend });
+  } catch (error) {
+    console.error("Failed to start work on bead:", error);
+    throw error;
+  }
+}
+
 /**
  * Send a message to a specific agent session.
  * @param sessionId - The session ID to send the message to
@@ -274,71 +1470,319 @@ export async function stopAgentSession(sessionId: string): Promise<void> {
   }
 }
 
-/**
- * Interrupt a specific agent session without terminating it.
- * Sends SIGINT to stop the current streaming response but keeps the session alive.
- * @param sessionId - The session ID to interrupt
- */
-export async function interruptAgentSession(sessionId: string): Promise<void> {
-  try {
-    await invoke("interrupt_agent_session", { sessionId });
-  } catch (error) {
-    console.error("Failed to interrupt agent session:", error);
-    throw error;
-  }
+/**
+ * Interrupt a specific agent session without terminating it.
+ * Sends SIGINT to stop the current streaming response but keeps the session alive.
+ * @param sessionId - The session ID to interrupt
+ */
+export async function interruptAgentSession(sessionId: string): Promise<void> {
+  try {
+    await invoke("interrupt_agent_session", { sessionId });
+  } catch (error) {
+    console.error("Failed to interrupt agent session:", error);
+    throw error;
+  }
+}
+
+/**
+ * Handover a headless session to interactive mode.
+ * Clears the command queue and allows user to take control.
+ * @param sessionId - The session ID to handover
+ */
+export async function handoverToInteractive(sessionId: string): Promise<void> {
+  try {
+    await invoke("handover_to_interactive", { sessionId });
+  } catch (error) {
+    console.error("Failed to handover session to interactive:", error);
+    throw error;
+  }
+}
+
+/**
+ * Get the current CLI backend preference from persistent storage.
+ * @returns The current CLI backend ('gemini', 'claude', 'claude-code', or 'ollama')
+ * @throws Error if unable to read CLI preference from storage
+ */
+export async function getCliPreference(): Promise<string> {
+  try {
+    return await invoke<string>("get_cli_preference");
+  } catch (error) {
+    console.error("Failed to get CLI preference:", error);
+    throw new Error(`Unable to retrieve CLI preference: ${error}`);
+  }
+}
+
+/**
+ * Set the CLI backend preference in persistent storage.
+ * @param cliBackend - The CLI backend to use ('gemini', 'claude', 'claude-code', or 'ollama')
+ * @throws Error if unable to save CLI preference to storage
+ */
+export async function setCliPreference(cliBackend: string): Promise<void> {
+  try {
+    await invoke("set_cli_preference", { cliBackend });
+  } catch (error) {
+    console.error("Failed to set CLI preference:", error);
+    throw new Error(`Unable to save CLI preference: ${error}`);
+  }
+}
+
+/** Ollama server and model used by the 'ollama' backend */
+export interface OllamaConfig {
+  host: string;   // e.g. http://localhost:11434
+  model: string;  // e.g. llama3.1
+}
+
+/** Whether agent summaries (<bead-summary> blocks) are appended to bead notes */
+export async function getAutoCaptureSummary(): Promise<boolean> {
+  return await invoke<boolean>('get_auto_capture_summary');
+}
+
+export async function setAutoCaptureSummary(enabled: boolean): Promise<void> {
+  await invoke('set_auto_capture_summary', { enabled });
+}
+
+/** Owner assigned to beads claimed from the app; null falls back to git user.name */
+export async function getOwnerName(): Promise<string | null> {
+  return await invoke<string | null>('get_owner_name');
+}
+
+export async function setOwnerName(ownerName: string | null): Promise<void> {
+  await invoke('set_owner_name', { ownerName });
+}
+
+/** How many agent sessions may run at once; null means no limit */
+export async function getMaxConcurrentSessions(): Promise<number | null> {
+  return await invoke<number | null>('get_max_concurrent_sessions');
+}
+
+export async function setMaxConcurrentSessions(limit: number | null): Promise<void> {
+  await invoke('set_max_concurrent_sessions', { limit });
+}
+
+/** What happens when an agent CLI fails to start or exits right away */
+export interface FailoverPolicy {
+  /** Retries of the same backend after a rate limit or network error */
+  retries: number;
+  /** Wait before the first retry; doubles with every further retry */
+  backoffMs: number;
+  /** How long a new CLI is watched for an early exit; 0 turns the check off */
+  startupWindowMs: number;
+  /** Backend tried once the chosen one can't start, e.g. after an auth error */
+  fallbackBackend: string | null;
+}
+
+export async function getAgentFailover(): Promise<FailoverPolicy> {
+  return await invoke<FailoverPolicy>('get_agent_failover');
+}
+
+export async function setAgentFailover(policy: FailoverPolicy): Promise<void> {
+  await invoke('set_agent_failover', { policy });
+}
+
+/** Which events raise desktop notifications */
+export interface NotificationSettings {
+  beadUnblocked: boolean;       // a bead assigned to me had its last blocker closed
+  sessionFinished: boolean;
+  sessionErrored: boolean;
+  criticalPathChanged: boolean;
+  bdCommandFailed: boolean;     // only commands that ran for a while
+}
+
+export async function getNotificationSettings(): Promise<NotificationSettings> {
+  return await invoke<NotificationSettings>('get_notification_settings');
+}
+
+export async function setNotificationSettings(notifications: NotificationSettings): Promise<void> {
+  await invoke('set_notification_settings', { notifications });
+}
+
+/** What agent CLIs may do without asking */
+export type AgentSafetyPolicy = 'full-auto' | 'approve-writes' | 'read-only';
+
+export async function getAgentSafetyPolicy(): Promise<AgentSafetyPolicy> {
+  return await invoke<AgentSafetyPolicy>('get_agent_safety_policy');
+}
+
+export async function setAgentSafetyPolicy(policy: AgentSafetyPolicy): Promise<void> {
+  await invoke('set_agent_safety_policy', { policy });
+}
+
+/**
+ * Local REST API (GET /beads, /view-model, /metadata, /critical-path on
+ * 127.0.0.1, with `Authorization: Bearer <token>`). Only builds with the
+ * `rest-api` feature serve it; enabling it or changing the port applies on restart.
+ */
+export interface RestApiSettings {
+  enabled: boolean;
+  port: number;
+  token: string;
+}
+
+export async function getRestApiSettings(): Promise<RestApiSettings> {
+  return await invoke<RestApiSettings>('get_rest_api_settings');
+}
+
+/** Save the REST API settings; enabling it with an empty token generates one */
+export async function setRestApiSettings(restApi: RestApiSettings): Promise<RestApiSettings> {
+  return await invoke<RestApiSettings>('set_rest_api_settings', { restApi });
+}
+
+/** How the UI looks; shared by every window */
+export interface DisplayPrefs {
+  theme: 'system' | 'light' | 'dark';
+  density: 'comfortable' | 'compact';
+  /** Zoom of a window without saved startup state (0.1 to 10) */
+  defaultZoom: number;
+  dateFormat: 'iso' | 'us' | 'eu';
+  firstDayOfWeek: 'saturday' | 'sunday' | 'monday';
+}
+
+export async function getDisplayPrefs(): Promise<DisplayPrefs> {
+  return await invoke<DisplayPrefs>('get_display_prefs');
+}
+
+/** Save display preferences; every window receives `settings-updated` */
+export async function setDisplayPrefs(prefs: DisplayPrefs): Promise<void> {
+  await invoke('set_display_prefs', { prefs });
+}
+
+/** Fired in every window when display preferences change */
+export async function onSettingsUpdated(
+  callback: (payload: { display: DisplayPrefs }) => void
+): Promise<UnlistenFn> {
+  return listen<{ display: DisplayPrefs }>('settings-updated', (event) => callback(event.payload));
+}
+
+/** Show a notification to check that the OS allows them */
+export async function notifyTest(): Promise<void> {
+  await invoke('notify_test');
+}
+
+/** Fired when a session's summary was appended to its bead's notes */
+export async function onSummaryCaptured(
+  callback: (payload: { sessionId: string; beadId: string; summary: string }) => void
+): Promise<UnlistenFn> {
+  return getCurrentWebviewWindow().listen<{ sessionId: string; beadId: string; summary: string }>('summary-captured', (event) => callback(event.payload));
+}
+
+/** Fired for every failed try at starting a session's run, before it is retried or failed over */
+export async function onSessionSpawnFailed(
+  callback: (payload: { sessionId: string; attempt: SpawnAttempt }) => void
+): Promise<UnlistenFn> {
+  return getCurrentWebviewWindow().listen<{ sessionId: string; attempt: SpawnAttempt }>('session-spawn-failed', (event) => callback(event.payload));
+}
+
+/** Payload of `session-status-changed`, fired when a session's CLI process exits */
+export interface SessionStatusChange {
+  sessionId: string;
+  /** 'error' sessions (the CLI crashed) are removed from the session list */
+  status: 'stopped' | 'error';
+  /** null if the process was ended by a signal */
+  exitCode: number | null;
+}
+
+export async function onSessionStatusChanged(callback: (change: SessionStatusChange) => void): Promise<UnlistenFn> {
+  return listen<SessionStatusChange>('session-status-changed', (event) => callback(event.payload));
+}
+
+/**
+ * Open a session's CLI interactively in a terminal, resuming its conversation.
+ * Output arrives through onPtyData (raw, ANSI escapes included) until onPtyExit.
+ */
+export async function ptySpawnForSession(sessionId: string, cols?: number, rows?: number): Promise<void> {
+  await invoke('pty_spawn_for_session', { sessionId, cols, rows });
+}
+
+export async function ptyWrite(sessionId: string, data: string): Promise<void> {
+  await invoke('pty_write', { sessionId, data });
+}
+
+export async function ptyResize(sessionId: string, cols: number, rows: number): Promise<void> {
+  await invoke('pty_resize', { sessionId, cols, rows });
+}
+
+/** Close a session's terminal; the agent session itself stays */
+export async function ptyKill(sessionId: string): Promise<void> {
+  await invoke('pty_kill', { sessionId });
+}
+
+export async function onPtyData(callback: (payload: { sessionId: string; data: string }) => void): Promise<UnlistenFn> {
+  return listen<{ sessionId: string; data: string }>('pty-data', (event) => callback(event.payload));
+}
+
+/** exitCode is null if the terminal was killed */
+export async function onPtyExit(callback: (payload: { sessionId: string; exitCode: number | null }) => void): Promise<UnlistenFn> {
+  return listen<{ sessionId: string; exitCode: number | null }>('pty-exit', (event) => callback(event.payload));
+}
+
+export async function getOllamaConfig(): Promise<OllamaConfig> {
+  return await invoke<OllamaConfig>('get_ollama_config');
+}
+
+export async function setOllamaConfig(config: OllamaConfig): Promise<void> {
+  await invoke('set_ollama_config', { config });
+}
+
+/** Start working the ready queue of the current project */
+export async function startAutopilot(config: AutopilotConfig): Promise<AutopilotStatus> {
+  return await invoke<AutopilotStatus>('start_autopilot', { config });
+}
+
+/** Stop starting tasks; sessions already running finish first */
+export async function stopAutopilot(): Promise<AutopilotStatus> {
+  return await invoke<AutopilotStatus>('stop_autopilot');
+}
+
+export async function getAutopilotStatus(): Promise<AutopilotStatus> {
+  return await invoke<AutopilotStatus>('get_autopilot_status');
+}
+
+/** Implement a feature's tasks with up to maxParallel agent sessions, respecting blockers */
+export async function startFeatureSwarm(featureId: string, maxParallel: number): Promise<SwarmProgress> {
+  return await invoke<SwarmProgress>('start_feature_swarm', { featureId, maxParallel });
+}
+
+/** Stop starting tasks; sessions already running finish first */
+export async function stopFeatureSwarm(featureId: string): Promise<SwarmProgress> {
+  return await invoke<SwarmProgress>('stop_feature_swarm', { featureId });
+}
+
+export async function getFeatureSwarm(featureId: string): Promise<SwarmProgress | null> {
+  return await invoke<SwarmProgress | null>('get_feature_swarm', { featureId });
 }
 
-/**
- * Handover a headless session to interactive mode.
- * Clears the command queue and allows user to take control.
- * @param sessionId - The session ID to handover
- */
-export async function handoverToInteractive(sessionId: string): Promise<void> {
-  try {
-    await invoke("handover_to_interactive", { sessionId });
-  } catch (error) {
-    console.error("Failed to handover session to interactive:", error);
-    throw error;
-  }
+export async function onSwarmProgress(callback: (progress: SwarmProgress) => void): Promise<UnlistenFn> {
+  return listen<SwarmProgress>('swarm-progress', (event) => callback(event.payload));
 }
 
-/**
- * Get the current CLI backend preference from persistent storage.
- * @returns The current CLI backend ('gemini', 'claude', or 'claude-code')
- * @throws Error if unable to read CLI preference from storage
- */
-export async function getCliPreference(): Promise<string> {
-  try {
-    return await invoke<string>("get_cli_preference");
-  } catch (error) {
-    console.error("Failed to get CLI preference:", error);
-    throw new Error(`Unable to retrieve CLI preference: ${error}`);
-  }
+export async function onAutopilotStatus(callback: (status: AutopilotStatus) => void): Promise<UnlistenFn> {
+  return listen<AutopilotStatus>('autopilot-status', (event) => callback(event.payload));
 }
 
 /**
- * Set the CLI backend preference in persistent storage.
- * @param cliBackend - The CLI backend to use ('gemini', 'claude', or 'claude-code')
- * @throws Error if unable to save CLI preference to storage
+ * List all active agent sessions.
+ * @returns Array of SessionInfo for all currently active sessions
  */
-export async function setCliPreference(cliBackend: string): Promise<void> {
+export async function listActiveSessions(): Promise<SessionInfo[]> {
   try {
-    await invoke("set_cli_preference", { cliBackend });
+    return await invoke<SessionInfo[]>('list_active_sessions');
   } catch (error) {
-    console.error("Failed to set CLI preference:", error);
-    throw new Error(`Unable to save CLI preference: ${error}`);
+    console.error('Failed to list active sessions:', error);
+    return [];
   }
 }
 
 /**
- * List all active agent sessions.
- * @returns Array of SessionInfo for all currently active sessions
+ * Re-register sessions that were open when the app last closed
+ * (from ~/.bp6/sessions/active.json). Restored sessions are stopped until
+ * the next message resumes them via the CLI's --resume support.
+ * @returns The restored sessions
  */
-export async function listActiveSessions(): Promise<SessionInfo[]> {
+export async function restoreSessions(): Promise<SessionInfo[]> {
   try {
-    return await invoke<SessionInfo[]>('list_active_sessions');
+    return await invoke<SessionInfo[]>('restore_sessions');
   } catch (error) {
-    console.error('Failed to list active sessions:', error);
+    console.error('Failed to restore sessions:', error);
     return [];
   }
 }
@@ -359,12 +1803,24 @@ export async function getActiveSessionId(): Promise<string | null> {
   return await invoke<string | null>('get_active_session_id');
 }
 
+/** What to do with an isolated session's git worktree when it ends */
+export type WorktreeAction = 'merge' | 'discard' | 'keep';
+
 /**
  * Terminate a specific agent session.
  * @param sessionId - The session ID to terminate
+ * @param worktreeAction - Required if the session's worktree has changes
+ */
+export async function terminateSession(sessionId: string, worktreeAction?: WorktreeAction): Promise<void> {
+  await invoke('terminate_session', { sessionId, worktreeAction });
+}
+
+/**
+ * Merge or discard the git worktree of an isolated session (live or terminated).
+ * Without an action, only an unchanged worktree is removed.
  */
-export async function terminateSession(sessionId: string): Promise<void> {
-  await invoke('terminate_session', { sessionId });
+export async function cleanupSessionWorktree(sessionId: string, action?: WorktreeAction): Promise<void> {
+  await invoke('cleanup_session_worktree', { sessionId, action });
 }
 
 /**
@@ -450,11 +1906,24 @@ export interface UiStateData {
   wbsPanelWidth?: number;
 }
 
+/** UI context of one project, restored when it is opened again */
+export interface ProjectUiState {
+  filters: FilterStateData;
+  sort: SortStateData;
+  collapsedIds: string[];
+  selectedBeadId: string | null;
+  scroll: { top: number; left: number };
+}
+
 export interface StartupState {
   window: MainWindowState;
   filters: FilterStateData;
   sort: SortStateData;
   ui: UiStateData;
+  /** Filled from settings on load; ignored on save */
+  display?: DisplayPrefs;
+  /** Per-project UI state keyed by project path; ignored on save (use saveProjectUiState) */
+  projects?: Record<string, ProjectUiState>;
 }
 
 /**
@@ -481,6 +1950,30 @@ export async function saveStartupState(state: StartupState): Promise<void> {
   }
 }
 
+/**
+ * Save the UI state (filters, collapsed nodes, selection, scroll) of one project
+ */
+export async function saveProjectUiState(projectPath: string, state: ProjectUiState): Promise<void> {
+  try {
+    await invoke('save_project_ui_state', { projectPath, state });
+  } catch (error) {
+    console.error('Failed to save project UI state:', error);
+    throw error;
+  }
+}
+
+/**
+ * Load the UI state saved for a project; null if none was saved yet
+ */
+export async function loadProjectUiState(projectPath: string): Promise<ProjectUiState | null> {
+  try {
+    return await invoke<ProjectUiState | null>('load_project_ui_state', { projectPath });
+  } catch (error) {
+    console.error('Failed to load project UI state:', error);
+    return null;
+  }
+}
+
 /**
  * Save window state for a session
  */
@@ -564,6 +2057,227 @@ export async function loadSessionHistory(
   }
 }
 
+/** Summary of a persisted session log (~/.bp6/sessions/<bead>/<session>.jsonl) */
+export interface SessionLogSummary {
+  logPath: string;
+  sessionId: string;
+  beadId: string | null;
+  persona: string;
+  backend: string;
+  startedAt: string | null;
+  lastEventAt: string | null;
+  messageCount: number;
+}
+
+export interface TranscriptEntry {
+  role: 'user' | 'assistant' | 'system';
+  /** Raw markdown */
+  content: string;
+  timestamp: string;
+  /** False for an assistant reply cut off before it finished */
+  complete: boolean;
+}
+
+export interface SessionTranscript {
+  summary: SessionLogSummary;
+  entries: TranscriptEntry[];
+}
+
+export interface SessionLogMatch {
+  summary: SessionLogSummary;
+  snippets: { role: string; timestamp: string; snippet: string }[];
+}
+
+/** List past agent sessions of a bead (null for untracked sessions), newest first */
+export async function listSessionLogs(beadId: string | null): Promise<SessionLogSummary[]> {
+  return await invoke<SessionLogSummary[]>('list_session_logs', { beadId });
+}
+
+/** Load a past session as a transcript for replay */
+export async function getSessionTranscript(logPath: string): Promise<SessionTranscript> {
+  return await invoke<SessionTranscript>('get_session_transcript', { logPath });
+}
+
+/** Full-text search across all past session logs */
+export async function searchSessionLogs(query: string): Promise<SessionLogMatch[]> {
+  return await invoke<SessionLogMatch[]>('search_session_logs', { query });
+}
+
+/** A diff, test report or command output found in a completed agent reply */
+export interface SessionArtifact {
+  kind: 'diff' | 'test-report' | 'command-output';
+  /** Changed files of a diff, the summary line of a test run, or the command of an output block */
+  title: string;
+  content: string;
+  /** Test reports only */
+  tests?: { passed: number; failed: number; skipped: number };
+  timestamp: string;
+}
+
+/** Diffs, test reports and command output of a session, oldest first (also pushed as `session-artifacts-added`) */
+export async function getSessionArtifacts(sessionId: string): Promise<SessionArtifact[]> {
+  return await invoke<SessionArtifact[]>('get_session_artifacts', { sessionId });
+}
+
+/** Agent usage of one bead (beadId null for untracked sessions) */
+export interface BeadCost {
+  beadId: string | null;
+  usage: TokenUsage;
+  sessionCount: number;
+  runCount: number;
+}
+
+export interface AgentCostReport {
+  beads: BeadCost[];  // Most expensive first
+  total: TokenUsage;
+}
+
+/** Token usage accumulated by an open session */
+export async function getSessionUsage(sessionId: string): Promise<TokenUsage> {
+  return await invoke<TokenUsage>('get_session_usage', { sessionId });
+}
+
+export interface ChangedFile {
+  path: string;  // Relative to the session's worktree or repo root
+  kind: 'created' | 'modified' | 'deleted';
+  changedAt: number;
+}
+
+/** Files an open session created, modified or deleted */
+export async function getSessionChangedFiles(sessionId: string): Promise<ChangedFile[]> {
+  return await invoke<ChangedFile[]>('get_session_changed_files', { sessionId });
+}
+
+/** Fired with a session's full changed-file list after each burst of edits */
+export async function onSessionFilesChanged(
+  callback: (payload: { sessionId: string; files: ChangedFile[] }) => void
+): Promise<UnlistenFn> {
+  return getCurrentWebviewWindow().listen<{ sessionId: string; files: ChangedFile[] }>('session-files-changed', (event) => callback(event.payload));
+}
+
+/** Usage and cost of every logged agent run, per bead */
+export async function getAgentCostReport(): Promise<AgentCostReport> {
+  return await invoke<AgentCostReport>('get_agent_cost_report');
+}
+
+/** A `bd` command proposed by an agent, queued for approval */
+export interface Proposal {
+  id: string;
+  sessionId: string;
+  beadId: string | null;
+  command: string;
+  createdAt: number;
+  status: 'pending' | 'approved' | 'rejected' | 'failed';
+  /** Command output (approved) or error message (failed) */
+  result: string | null;
+}
+
+/** Pending proposals, optionally for one session */
+export async function listPendingProposals(sessionId?: string): Promise<Proposal[]> {
+  return await invoke<Proposal[]>('list_pending_proposals', { sessionId });
+}
+
+export async function approveProposal(proposalId: string): Promise<Proposal> {
+  return await invoke<Proposal>('approve_proposal', { proposalId });
+}
+
+export async function rejectProposal(proposalId: string): Promise<Proposal> {
+  return await invoke<Proposal>('reject_proposal', { proposalId });
+}
+
+/** Approve every pending proposal of a session, in order */
+export async function approveAllProposals(sessionId: string): Promise<Proposal[]> {
+  return await invoke<Proposal[]>('approve_all', { sessionId });
+}
+
+export async function onProposalAdded(callback: (proposal: Proposal) => void): Promise<UnlistenFn> {
+  return getCurrentWebviewWindow().listen<Proposal>('proposal-added', (event) => callback(event.payload));
+}
+
+export async function onProposalResolved(callback: (proposal: Proposal) => void): Promise<UnlistenFn> {
+  return getCurrentWebviewWindow().listen<Proposal>('proposal-resolved', (event) => callback(event.payload));
+}
+
+export interface ToolUse {
+  name: string;
+  input: unknown;
+}
+
+/** A write the agent CLI refused under the approve-writes policy */
+export interface ApprovalRequest {
+  id: string;
+  sessionId: string;
+  beadId: string | null;
+  persona: string;
+  backend: string;
+  tool: ToolUse;
+  createdAt: string;
+}
+
+export type AuditDecision = 'allowed' | 'awaiting-approval' | 'blocked' | 'approved' | 'rejected';
+
+export interface AuditEntry {
+  timestamp: string;
+  sessionId: string;
+  beadId: string | null;
+  persona: string;
+  backend: string;
+  policy: AgentSafetyPolicy;
+  tool: string;
+  input: unknown;
+  decision: AuditDecision;
+  approvalId: string | null;
+}
+
+export async function listAgentApprovals(sessionId?: string): Promise<ApprovalRequest[]> {
+  return await invoke<ApprovalRequest[]>('list_agent_approvals', { sessionId: sessionId ?? null });
+}
+
+/** Approve a refused write; the session retries it with that call (a shell tool only with its command) allowed for one run */
+export async function approveAgentAction(approvalId: string): Promise<void> {
+  await invoke('approve_agent_action', { approvalId });
+}
+
+export async function rejectAgentAction(approvalId: string): Promise<void> {
+  await invoke('reject_agent_action', { approvalId });
+}
+
+/** Audited agent actions of a day (YYYY-MM-DD, UTC; today by default) */
+export async function fetchAuditLog(date?: string, sessionId?: string): Promise<AuditEntry[]> {
+  return await invoke<AuditEntry[]>('get_audit_log', { date: date ?? null, sessionId: sessionId ?? null });
+}
+
+export async function onAgentApprovalRequested(callback: (request: ApprovalRequest) => void): Promise<UnlistenFn> {
+  return listen<ApprovalRequest>('agent-approval-requested', (event) => callback(event.payload));
+}
+
+export async function onAgentApprovalResolved(
+  callback: (resolution: { id: string; decision: AuditDecision }) => void
+): Promise<UnlistenFn> {
+  return listen<{ id: string; decision: AuditDecision }>('agent-approval-resolved', (event) => callback(event.payload));
+}
+
+export type BdSubcommand = 'create' | 'update' | 'close' | 'dep-add' | 'dep-remove';
+
+export interface PreviewChange {
+  beadId: string | null;
+  field: string;
+  old: string | null;
+  new: string;
+}
+
+export interface CommandPreview {
+  subcommand: BdSubcommand;
+  args: string[];
+  summary: string;
+  changes: PreviewChange[];
+}
+
+/** Describe what approving a suggested bd command would change; rejects commands outside the allowed subcommands/flags or naming unknown beads */
+export async function previewSuggestion(command: string): Promise<CommandPreview> {
+  return await invoke<CommandPreview>("preview_suggestion", { command });
+}
+
 export async function approveSuggestion(command: string): Promise<string> {
   try {
     return await invoke<string>("approve_suggestion", { command });
@@ -592,14 +2306,54 @@ export async function onSessionListChanged(
   });
 }
 
+/** A mutation queued while bd or the repo was unavailable, retried automatically */
+export interface PendingOp {
+  id: string;
+  /** The operation as batch_update_beads takes it, e.g. { op: "close", beadId, reason } */
+  op: { op: string; [field: string]: unknown };
+  /** Project the operation was made in; it is only replayed there */
+  projectPath: string;
+  queuedAt: string;
+  attempts: number;
+  lastError: string;
+  /** Retrying won't fix it; discard it */
+  failed: boolean;
+}
+
+export async function getPendingOps(): Promise<PendingOp[]> {
+  return await invoke<PendingOp[]>("get_pending_ops");
+}
+
+/** Discard queued operations by ID, or all of them; resolves to those left */
+export async function discardPendingOps(opIds?: string[]): Promise<PendingOp[]> {
+  return await invoke<PendingOp[]>("discard_pending_ops", { opIds: opIds ?? null });
+}
+
+export async function onPendingOpsChanged(
+  callback: (change: { projectPath: string; count: number }) => void
+): Promise<UnlistenFn> {
+  return getCurrentWebviewWindow().listen<{ projectPath: string; count: number }>("pending-ops-changed", (event) =>
+    callback(event.payload)
+  );
+}
+
+/** Payload of beads-updated; checksum is set when the file watcher saw the change */
+export interface BeadsUpdated {
+  projectPath: string;
+  /** Tab whose project changed; null for the window's own project */
+  projectHandle: string | null;
+  checksum: string | null;
+}
+
 /**
  * Listen for bead update events from the backend.
+ * Only events for this window's project (or broadcast to all windows) are received.
  * @param callback - Function to call when beads are updated
  * @returns A promise that resolves to an unlisten function for cleanup
  */
-export async function onBeadsUpdated(callback: () => void): Promise<UnlistenFn> {
-  return listen("beads-updated", () => {
-    callback();
+export async function onBeadsUpdated(callback: (update: BeadsUpdated | null) => void): Promise<UnlistenFn> {
+  return getCurrentWebviewWindow().listen<BeadsUpdated | null>("beads-updated", (event) => {
+    callback(event.payload);
   });
 }
 
@@ -624,6 +2378,16 @@ export interface GanttItem {
   depth: number;
   isCritical: boolean;
   isBlocked: boolean;
+  /** Set on a summary bar standing in for leaf tasks folded into it at low zoom */
+  summary?: GanttSummary;
+}
+
+/** The leaf tasks an aggregated Gantt bar stands for */
+export interface GanttSummary {
+  count: number;
+  statusCounts: Record<string, number>;
+  criticalCount: number;
+  blockedCount: number;
 }
 
 export interface GanttConnector {
@@ -632,6 +2396,9 @@ export interface GanttConnector {
   isCritical: boolean;
 }
 
+/** Engine laying out get_processed_data: Gantt rows, dependency graph or owner swimlanes */
+export type LayoutKind = 'gantt' | 'dag' | 'owner_timeline';
+
 export interface GanttLayout {
   items: GanttItem[];
   connectors: GanttConnector[];
@@ -646,10 +2413,35 @@ export interface BucketDistribution {
   closed: number;
 }
 
+/** Status counts for one calendar week or sprint */
+export interface DatedBucket extends BucketDistribution {
+  /** e.g. "Week of 12 Oct" or "Sprint 3" */
+  label: string;
+  startDate: string;
+  endDate: string;
+}
+
+/** Duration of one pipeline stage (load, filter, tree, layout, distributions, compute) */
+export interface StageTiming {
+  stage: string;
+  ms: number;
+}
+
+/** Where the time of one view computation went */
+export interface Timings {
+  stages: StageTiming[];
+  beadCount: number;
+  visibleCount: number;  // After filtering
+  totalMs: number;
+  cached: boolean;  // Served from the view model cache; stages are those of the cached computation
+}
+
 export interface ProcessedData {
   tree: WBSNode[];
   layout: GanttLayout;
   distributions: BucketDistribution[];
+  timings: Timings;
+  parseWarnings: ParseWarning[];  // Lines of the beads file that were skipped
 }
 
 // ============================================================================
@@ -696,6 +2488,15 @@ export interface BeadNode {
   isBlocked: boolean;
   isCritical: boolean;
   blockingIds: string[];
+  references: string[];      // Beads mentioned in the text fields
+  totalEstimate: number;      // Sum of leaf task estimates below (own for leaves)
+  completedEstimate: number;  // Part of totalEstimate that is done
+  progressPercent: number;    // 0-100, by estimate or by done leaf tasks
+  actualMinutes: number;      // Tracked time here and below
+  acceptanceProgress?: AcceptanceProgress;  // Only beads with acceptance criteria
+  externalDependencies: ExternalDependency[];  // Beads in other projects it waits on
+  isExternallyBlocked: boolean;  // One of them isn't done or can't be found
+  isPinned: boolean;  // On the user's own watchlist, unlike the shared is_favorite
 
   // Logical Positioning (NOT pixels - frontend converts)
   depth: number;
@@ -730,6 +2531,8 @@ export interface ProjectMetadata {
   closedCount: number;
   totalDuration: number;
   distributions: BucketDistribution[];
+  /** Filled when FilterParams.distribution_mode is 'week' or 'sprint' */
+  datedDistributions: DatedBucket[];
 }
 
 /**
@@ -740,6 +2543,26 @@ export interface ProjectViewModel {
   tree: BeadNode[];
   metadata: ProjectMetadata;
   indexes: ViewIndexes;
+  timings: Timings;
+  parseWarnings: ParseWarning[];  // Lines of the beads file that were skipped
+}
+
+/**
+ * A visible row of the tree; childCount tells whether it can be expanded.
+ */
+export interface ViewModelRow extends BeadNode {
+  childCount: number;
+}
+
+/**
+ * A slice of the visible rows, with the total row count for the scrollbar.
+ */
+export interface ViewModelWindow {
+  version: number;
+  startRow: number;
+  totalRows: number;
+  rows: ViewModelRow[];
+  metadata: ProjectMetadata;
 }
 
 export interface FilterParams {
@@ -749,8 +2572,14 @@ export interface FilterParams {
   include_hierarchy?: boolean;
   zoom?: number;
   collapsed_ids?: string[];
-  sort_by?: 'priority' | 'title' | 'type' | 'id' | 'none';
+  sort_by?: 'priority' | 'title' | 'type' | 'id' | 'manual' | 'none';
   sort_order?: 'asc' | 'desc' | 'none';
+  /** Also bucket the header histogram by calendar week or sprint */
+  distribution_mode?: 'cell' | 'week' | 'sprint';
+  /** Below this zoom, fold leaf tasks into summary bars on their feature/epic */
+  aggregate_below_zoom?: number;
+  /** Focus mode: only this epic, its subtree and the beads outside it that block them */
+  scope_root_id?: string;
 }
 
 /**
@@ -873,3 +2702,158 @@ export async function touchSession(
     console.error('Failed to touch session:', error);
   }
 }
+
+/** One line of a line-by-line comparison of a long text field */
+export interface LineChange {
+  op: 'same' | 'added' | 'removed';
+  text: string;
+}
+
+/** A field that differs between two versions of a bead */
+export interface FieldDiff {
+  field: string;
+  old: unknown;
+  new: unknown;
+  /** Present for long text fields (description, design, notes, acceptance_criteria) */
+  lines?: LineChange[];
+}
+
+export interface BeadDiff {
+  beadId: string;
+  from: string | null;
+  to: string | null;
+  fields: FieldDiff[];
+}
+
+/**
+ * Compare a bead's versions at two RFC 3339 times, from its recorded history.
+ * Omit fromTs for its first recorded version and toTs for the current one.
+ */
+export async function diffBead(beadId: string, fromTs?: string, toTs?: string): Promise<BeadDiff> {
+  return await invoke<BeadDiff>("diff_bead", { beadId, fromTs, toTs });
+}
+
+export interface CommitSummary {
+  commit: string;
+  shortCommit: string;
+  author: string;
+  date: string;
+  subject: string;
+}
+
+/** State of the open project's checkout, for the header */
+export interface GitStatus {
+  branch: string | null;  // null on a detached HEAD
+  upstream: string | null;
+  ahead: number;
+  behind: number;
+  dirtyFiles: number;
+  beadsDirty: boolean;  // Uncommitted changes under .beads
+  lastCommit: CommitSummary | null;
+}
+
+/** Branch, upstream divergence, uncommitted changes and last commit of the project */
+export async function fetchGitStatus(): Promise<GitStatus> {
+  return await invoke<GitStatus>("get_git_status");
+}
+
+/** Fired after checkouts, commits, staging and fetches, once fetchGitStatus was called */
+export async function onGitStatusChanged(
+  callback: (payload: { repoRoot: string; status: GitStatus }) => void
+): Promise<UnlistenFn> {
+  return getCurrentWebviewWindow().listen<{ repoRoot: string; status: GitStatus }>('git-status-changed', (event) => callback(event.payload));
+}
+
+/** Auto-commit of the beads file after edits made in the app (stored in .beads/bert-viz.json) */
+export interface AutoCommitConfig {
+  enabled: boolean;
+  batchWindowSecs: number;  // Quiet period before pending edits are committed
+}
+
+export interface AutoCommitEntry {
+  commit: string | null;  // Short hash; null when the commit failed
+  message: string;
+  branch: string | null;  // The sync branch in sync-branch mode
+  at: string;
+  error: string | null;
+}
+
+export async function fetchAutoCommitConfig(): Promise<AutoCommitConfig> {
+  return await invoke<AutoCommitConfig>("get_autocommit_config");
+}
+
+export async function saveAutoCommitConfig(config: AutoCommitConfig): Promise<void> {
+  await invoke("save_autocommit_config", { config });
+}
+
+/** Auto-commits of the project's beads file since the app started, newest first */
+export async function fetchAutoCommitLog(): Promise<AutoCommitEntry[]> {
+  return await invoke<AutoCommitEntry[]>("get_autocommit_log");
+}
+
+/** Fired after each auto-commit attempt */
+export async function onAutoCommit(callback: (entry: AutoCommitEntry) => void): Promise<UnlistenFn> {
+  return getCurrentWebviewWindow().listen<AutoCommitEntry>('autocommit-created', (event) => callback(event.payload));
+}
+
+/** Sync-branch mode: beads live on a dedicated branch checked out in .git/beads-worktrees */
+export interface SyncStatus {
+  branch: string;
+  worktree: string;
+  upstream: string | null;
+  ahead: number;
+  behind: number;  // As of the last fetch
+  dirty: boolean;  // Uncommitted bead changes
+  conflicts: string[];  // Left by a pull; resolve before pushing
+}
+
+export type ConflictStrategy = 'newest' | 'ours' | 'theirs' | 'abort';
+
+export async function fetchSyncStatus(fetch = false): Promise<SyncStatus> {
+  return await invoke<SyncStatus>("get_sync_status", { fetch });
+}
+
+export async function setSyncBranch(branch: string): Promise<void> {
+  await invoke("set_sync_branch", { branch });
+}
+
+/** Merge the remote sync branch in; conflicts are reported, not thrown */
+export async function pullSyncBranch(): Promise<SyncStatus> {
+  return await invoke<SyncStatus>("pull_sync_branch");
+}
+
+/** Commit pending bead changes and push the sync branch */
+export async function pushSyncBranch(): Promise<SyncStatus> {
+  return await invoke<SyncStatus>("push_sync_branch");
+}
+
+/** Finish a conflicted pull; 'newest' keeps each bead's most recently updated version */
+export async function resolveSyncConflict(strategy: ConflictStrategy): Promise<SyncStatus> {
+  return await invoke<SyncStatus>("resolve_sync_conflict", { strategy });
+}
+
+export interface CommandStats {
+  command: string;
+  count: number;
+  cacheHits: number;
+  avgTotalMs: number;
+  maxTotalMs: number;
+  avgStages: StageTiming[];  // Over uncached calls
+  maxBeadCount: number;
+}
+
+export interface PerfSample {
+  command: string;
+  at: string;
+  timings: Timings;
+}
+
+export interface PerformanceStats {
+  commands: CommandStats[];
+  samples: PerfSample[];  // Newest first
+}
+
+/** Timings of the last view computations, summarized per command */
+export async function fetchPerformanceStats(): Promise<PerformanceStats> {
+  return await invoke<PerformanceStats>("get_performance_stats");
+}
@@ -3,7 +3,7 @@ import { Terminal, MessageSquare, Trash2, X, Square, Pin, PinOff, Hand } from 'l
 import { MessageBubble } from './MessageBubble';
 import { useAgentSession } from '../../hooks/useAgentSession';
 import { useDraggable } from '../../hooks/useDraggable';
-import { approveSuggestion, CliBackend, toggleWindowAlwaysOnTop, handoverToInteractive } from '../../api';
+import { approveSuggestion, CliBackend, toggleWindowAlwaysOnTop, handoverToInteractive, errorMessage } from '../../api';
 import { sanitizeAgentHtml } from '../../utils/sanitizeAgentHtml';
 import { getCurrentWindow } from '@tauri-apps/api/window';
 import { useSessionStore } from '../../stores/sessionStore';
@@ -137,7 +137,7 @@ const ChatDialog: React.FC<ChatDialogProps> = ({
       await sendMessage(`✅ Executed: ${command}\nResult: ${result}`);
     } catch (error) {
       console.error('Failed to approve suggestion:', error);
-      await sendMessage(`❌ Error executing command: ${error}`);
+      await sendMessage(`❌ Error executing command: ${errorMessage(error)}`);
     }
   }, [sendMessage]);
 
@@ -174,7 +174,7 @@ const ChatDialog: React.FC<ChatDialogProps> = ({
       console.log(`Handed over session ${sessionId} to interactive mode`);
     } catch (error) {
       console.error('Failed to handover session:', error);
-      alert(`Failed to take over session: ${error}`);
+      alert(`Failed to take over session: ${errorMessage(error)}`);
     } finally {
       setIsHandingOver(false);
     }
@@ -1,6 +1,6 @@
 import { useState, useEffect, memo, useCallback } from 'react';
 import { X, ExternalLink, Hand } from 'lucide-react';
-import { SessionInfo, getPersonaIcon, formatSessionRuntime, createSessionWindow, handoverToInteractive } from '../../api';
+import { SessionInfo, getPersonaIcon, formatSessionRuntime, createSessionWindow, handoverToInteractive, errorMessage } from '../../api';
 import { cn } from '../../utils';
 
 /**
@@ -79,7 +79,7 @@ export const SessionItem = memo<SessionItemProps>(({
       console.log(`Opened session ${session.sessionId} in new window: ${windowLabel}`);
     } catch (error) {
       console.error('Failed to open session in new window:', error);
-      alert(`Failed to open window: ${error}`);
+      alert(`Failed to open window: ${errorMessage(error)}`);
     }
   }, [session.sessionId]);
 
@@ -102,7 +102,7 @@ export const SessionItem = memo<SessionItemProps>(({
       console.log(`Handed over session ${session.sessionId} to interactive mode`);
     } catch (error) {
       console.error('Failed to handover session:', error);
-      alert(`Failed to take over session: ${error}`);
+      alert(`Failed to take over session: ${errorMessage(error)}`);
     } finally {
       setIsHandingOver(false);
     }
@@ -13,7 +13,7 @@ import {
   CliBackend,
   AgentChunk
 } from '../api';
-import { listen } from '@tauri-apps/api/event';
+import { getCurrentWebviewWindow } from '@tauri-apps/api/webviewWindow';
 
 interface Message {
   role: 'user' | 'assistant';
@@ -104,7 +104,7 @@ export function useAgentSession({
     const eventName = `agent-chunk-${sid}`;
     const listenerId = `L-${Math.random().toString(36).substr(2, 9)}`;
 
-    unlistenChunkRef.current = await listen<AgentChunk>(eventName, (event) => {
+    unlistenChunkRef.current = await getCurrentWebviewWindow().listen<AgentChunk>(eventName, (event) => {
       const { content, isDone } = event.payload;
 
       console.log(`📨 LISTENER ${listenerId}: Event received | content length:`, content?.length || 0, '| isDone:', isDone);
@@ -149,7 +149,7 @@ export function useAgentSession({
       }
     });
 
-    unlistenStderrRef.current = await listen<string>('agent-stderr', (event) => {
+    unlistenStderrRef.current = await getCurrentWebviewWindow().listen<string>('agent-stderr', (event) => {
       setDebugLogs(prev => [...prev, `[Stderr] ${event.payload}`]);
     });
 